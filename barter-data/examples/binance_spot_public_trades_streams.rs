@@ -0,0 +1,53 @@
+use barter_data::{
+    exchange::binance::spot::BinanceSpot,
+    streams::{reconnect::stream::ReconnectingStream, Streams},
+    subscription::trade::PublicTrades,
+};
+use barter_instrument::{exchange::ExchangeId, instrument::kind::InstrumentKind};
+use tokio_stream::StreamExt;
+use tracing::{info, warn};
+
+#[rustfmt::skip]
+#[tokio::main]
+async fn main() {
+    // Initialise INFO Tracing log subscriber
+    init_logging();
+
+    // Initialise PublicTrades Streams for BinanceSpot BTCUSDT only
+    // '--> spot markets are subscribed to via the BinanceSpot connector & InstrumentKind::Spot
+    let mut streams = Streams::<PublicTrades>::builder()
+        .subscribe([
+            (BinanceSpot::default(), "btc", "usdt", InstrumentKind::Spot, PublicTrades),
+        ])
+        .init()
+        .await
+        .unwrap();
+
+    // Select the ExchangeId::BinanceSpot stream, distinct from ExchangeId::BinanceFuturesUsd
+    // Note: use `Streams.select(ExchangeId)` to interact with individual exchange streams!
+    let mut binance_spot_stream = streams
+        .select(ExchangeId::BinanceSpot)
+        .unwrap()
+        .with_error_handler(|error| warn!(?error, "MarketStream generated error"));
+
+    while let Some(event) = binance_spot_stream.next().await {
+        info!("{event:?}");
+    }
+}
+
+// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        // Filter messages based on the INFO
+        .with_env_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        // Disable colours on release builds
+        .with_ansi(cfg!(debug_assertions))
+        // Enable Json formatting
+        .json()
+        // Install this Tracing subscriber as global default
+        .init()
+}
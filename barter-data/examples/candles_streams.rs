@@ -0,0 +1,54 @@
+use barter_data::{
+    exchange::binance::spot::BinanceSpot,
+    streams::{reconnect::stream::ReconnectingStream, Streams},
+    subscription::candle::{ClosedCandles, Interval},
+};
+use barter_instrument::{exchange::ExchangeId, instrument::kind::InstrumentKind};
+use tokio_stream::StreamExt;
+use tracing::{info, warn};
+
+#[rustfmt::skip]
+#[tokio::main]
+async fn main() {
+    // Initialise INFO Tracing log subscriber
+    init_logging();
+
+    // Initialise closed 1m Candles Streams for BinanceSpot only
+    // '--> use Candles to also receive every update of the in-progress Candle
+    let mut streams = Streams::<ClosedCandles>::builder()
+        .subscribe([
+            (BinanceSpot::default(), "btc", "usdt", InstrumentKind::Spot, ClosedCandles::new(Interval::M1)),
+            (BinanceSpot::default(), "eth", "usdt", InstrumentKind::Spot, ClosedCandles::new(Interval::M1)),
+        ])
+        .init()
+        .await
+        .unwrap();
+
+    // Select the ExchangeId::BinanceSpot stream
+    // Note: use `Streams.select(ExchangeId)` to interact with individual exchange streams!
+    let mut binance_stream = streams
+        .select(ExchangeId::BinanceSpot)
+        .unwrap()
+        .with_error_handler(|error| warn!(?error, "MarketStream generated error"));
+
+    while let Some(event) = binance_stream.next().await {
+        info!("{event:?}");
+    }
+}
+
+// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        // Filter messages based on the INFO
+        .with_env_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        // Disable colours on release builds
+        .with_ansi(cfg!(debug_assertions))
+        // Enable Json formatting
+        .json()
+        // Install this Tracing subscriber as global default
+        .init()
+}
@@ -0,0 +1,59 @@
+use barter_data::{
+    exchange::coinbase::Coinbase,
+    streams::{reconnect::stream::ReconnectingStream, Streams},
+    subscription::trade::PublicTrades,
+};
+use barter_instrument::instrument::kind::InstrumentKind;
+use futures_util::StreamExt;
+use tracing::{info, warn};
+
+#[rustfmt::skip]
+#[tokio::main]
+async fn main() {
+    // Initialise INFO Tracing log subscriber
+    init_logging();
+
+    // Initialise PublicTrades Streams for Coinbase only
+    // '--> each call to StreamBuilder::subscribe() creates a separate WebSocket connection
+    let streams = Streams::<PublicTrades>::builder()
+
+        // Separate WebSocket connection for BTC-USD stream
+        .subscribe([
+            (Coinbase, "btc", "usd", InstrumentKind::Spot, PublicTrades),
+        ])
+
+        // Separate WebSocket connection for ETH-USD stream
+        .subscribe([
+            (Coinbase, "eth", "usd", InstrumentKind::Spot, PublicTrades),
+        ])
+        .init()
+        .await
+        .unwrap();
+
+    // Select and merge every exchange Stream using futures_util::stream::select_all
+    // Note: use `Streams.select(ExchangeId)` to interact with individual exchange streams!
+    let mut joined_stream = streams
+        .select_all()
+        .with_error_handler(|error| warn!(?error, "MarketStream generated error"));
+
+    while let Some(event) = joined_stream.next().await {
+        info!("{event:?}");
+    }
+}
+
+// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        // Filter messages based on the INFO
+        .with_env_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        // Disable colours on release builds
+        .with_ansi(cfg!(debug_assertions))
+        // Enable Json formatting
+        .json()
+        // Install this Tracing subscriber as global default
+        .init()
+}
@@ -28,7 +28,7 @@ async fn main() {
             (BinanceFuturesUsd::default(), "eth", "usd", InstrumentKind::Perpetual, OrderBooksL1),
         ])
         .subscribe([
-            (Kraken, "xbt", "usd", InstrumentKind::Spot, OrderBooksL1),
+            (Kraken, "btc", "usd", InstrumentKind::Spot, OrderBooksL1),
             (Kraken, "ada", "usd", InstrumentKind::Spot, OrderBooksL1),
             (Kraken, "matic", "usd", InstrumentKind::Spot, OrderBooksL1),
             (Kraken, "dot", "usd", InstrumentKind::Spot, OrderBooksL1),
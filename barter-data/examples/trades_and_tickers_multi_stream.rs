@@ -0,0 +1,80 @@
+use barter_data::{
+    event::DataKind,
+    exchange::{bybit::futures::BybitPerpetualsUsd, okx::Okx},
+    streams::{
+        consumer::MarketStreamResult,
+        reconnect::{stream::ReconnectingStream, Event},
+        Streams,
+    },
+    subscription::{ticker::Tickers, trade::PublicTrades},
+};
+use barter_instrument::instrument::{kind::InstrumentKind, Instrument};
+use tokio_stream::StreamExt;
+use tracing::{info, warn};
+
+#[rustfmt::skip]
+#[tokio::main]
+async fn main() {
+    // Initialise INFO Tracing log subscriber
+    init_logging();
+
+    // Initialise MarketEvent<DataKind> Streams combining PublicTrades & Tickers
+    let streams: Streams<MarketStreamResult<Instrument, DataKind>> = Streams::builder_multi()
+
+        // Add PublicTrades Streams
+        .add(Streams::<PublicTrades>::builder()
+            .subscribe([
+                (BybitPerpetualsUsd::default(), "btc", "usdt", InstrumentKind::Perpetual, PublicTrades),
+            ])
+            .subscribe([
+                (Okx, "btc", "usdt", InstrumentKind::Perpetual, PublicTrades),
+            ])
+        )
+
+        // Add Tickers Streams
+        .add(Streams::<Tickers>::builder()
+            .subscribe([
+                (BybitPerpetualsUsd::default(), "btc", "usdt", InstrumentKind::Perpetual, Tickers),
+            ])
+            .subscribe([
+                (Okx, "btc", "usdt", InstrumentKind::Perpetual, Tickers),
+            ])
+        )
+        .init()
+        .await
+        .unwrap();
+
+    // Select and merge every exchange Stream using futures_util::stream::select_all
+    let mut joined_stream = streams
+        .select_all()
+        .with_error_handler(|error| warn!(?error, "MarketStream generated error"));
+
+    // Each MarketEvent<DataKind> can be matched on to handle the inner kind
+    while let Some(event) = joined_stream.next().await {
+        match event {
+            Event::Item(market) => match &market.kind {
+                DataKind::Trade(trade) => info!(exchange = %market.exchange, ?trade, "trade"),
+                DataKind::Ticker(ticker) => info!(exchange = %market.exchange, ?ticker, "ticker"),
+                _ => info!("{market:?}"),
+            },
+            reconnect => info!("{reconnect:?}"),
+        }
+    }
+}
+
+// Initialise an INFO `Subscriber` for `Tracing` Json logs and install it as the global default.
+fn init_logging() {
+    tracing_subscriber::fmt()
+        // Filter messages based on the INFO
+        .with_env_filter(
+            tracing_subscriber::filter::EnvFilter::builder()
+                .with_default_directive(tracing_subscriber::filter::LevelFilter::INFO.into())
+                .from_env_lossy(),
+        )
+        // Disable colours on release builds
+        .with_ansi(cfg!(debug_assertions))
+        // Enable Json formatting
+        .json()
+        // Install this Tracing subscriber as global default
+        .init()
+}
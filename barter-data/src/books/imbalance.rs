@@ -0,0 +1,114 @@
+use crate::{books::OrderBook, event::MarketEvent, subscription::book::OrderBookEvent};
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+/// Normalised `[-1, 1]` bid/ask volume imbalance of an L2 [`OrderBook`] at a point in time.
+///
+/// Positive values indicate more resting bid volume (ie/ buying pressure), and negative values
+/// more resting ask volume. `None` if both sides of the [`OrderBook`] are empty.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct Imbalance {
+    pub time_exchange: DateTime<Utc>,
+    /// Volume imbalance of the best bid & ask [`Level`](super::Level)s.
+    pub top_of_book: Option<Decimal>,
+    /// Volume imbalance of the best `depth` [`Level`](super::Level)s of each side.
+    pub depth: Option<Decimal>,
+}
+
+/// Strategy input adapter that maintains a local L2 [`OrderBook`] from the
+/// [`OrderBookEvent`] [`MarketEvent`]s of a single instrument, generating the [`Imbalance`] of
+/// the book after each update.
+///
+/// Useful for building microstructure strategies on top of an
+/// [`OrderBooksL2`](crate::subscription::book::OrderBooksL2) subscription.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct OrderBookImbalance {
+    depth: usize,
+    book: OrderBook,
+}
+
+impl OrderBookImbalance {
+    /// Construct a new [`OrderBookImbalance`] calculating the depth [`Imbalance`] over the best
+    /// `depth` [`Level`](super::Level)s of each side.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth,
+            book: OrderBook::default(),
+        }
+    }
+
+    /// Update the local [`OrderBook`] with the next [`OrderBookEvent`] [`MarketEvent`], returning
+    /// the resulting [`Imbalance`].
+    pub fn update<InstrumentKey>(
+        &mut self,
+        event: &MarketEvent<InstrumentKey, OrderBookEvent>,
+    ) -> Imbalance {
+        self.book.update(event.kind.clone());
+
+        Imbalance {
+            time_exchange: event.time_exchange,
+            top_of_book: self.book.top_of_book_imbalance(),
+            depth: self.book.imbalance(self.depth),
+        }
+    }
+
+    /// Return a reference to the local [`OrderBook`].
+    pub fn book(&self) -> &OrderBook {
+        &self.book
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::books::Level;
+    use barter_instrument::exchange::ExchangeId;
+    use rust_decimal_macros::dec;
+
+    fn book_event(kind: OrderBookEvent) -> MarketEvent<&'static str, OrderBookEvent> {
+        MarketEvent {
+            time_exchange: Utc::now(),
+            time_received: Utc::now(),
+            exchange: ExchangeId::BinanceSpot,
+            instrument: "btc_usdt",
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_order_book_imbalance_tracks_snapshot_and_updates() {
+        let mut imbalance = OrderBookImbalance::new(2);
+
+        // Bid volumes: 30, 10 & ask volumes: 10, 10
+        let snapshot = book_event(OrderBookEvent::Snapshot(OrderBook::new(
+            1,
+            None,
+            vec![
+                Level::new(dec!(99), dec!(30)),
+                Level::new(dec!(98), dec!(10)),
+            ],
+            vec![
+                Level::new(dec!(101), dec!(10)),
+                Level::new(dec!(102), dec!(10)),
+            ],
+        )));
+
+        let actual = imbalance.update(&snapshot);
+        assert_eq!(actual.time_exchange, snapshot.time_exchange);
+        assert_eq!(actual.top_of_book, Some(dec!(0.5)));
+        assert_eq!(actual.depth, Some(dec!(1) / dec!(3)));
+
+        // Best bid is removed, leaving bid volume 10 vs ask volume 20
+        let update = OrderBook::new(2, None, vec![Level::new(dec!(99), dec!(0))], vec![]);
+        let actual = imbalance.update(&book_event(OrderBookEvent::Update(update)));
+        assert_eq!(actual.top_of_book, Some(dec!(0)));
+        assert_eq!(actual.depth, Some(dec!(-1) / dec!(3)));
+
+        // Remaining bid is removed, leaving an empty side of the book
+        let update = OrderBook::new(2, None, vec![Level::new(dec!(98), dec!(0))], vec![]);
+        let actual = imbalance.update(&book_event(OrderBookEvent::Update(update)));
+        assert_eq!(actual.top_of_book, Some(dec!(-1)));
+        assert_eq!(actual.depth, Some(dec!(-1)));
+    }
+}
@@ -18,7 +18,7 @@ use futures::Stream;
 use futures_util::StreamExt;
 use parking_lot::RwLock;
 use std::{fmt::Debug, hash::Hash, sync::Arc};
-use tracing::warn;
+use tracing::{info, warn};
 
 /// Maintains a set of local L2 [`OrderBook`]s by applying streamed [`OrderBookEvent`]s to the
 /// associated [`OrderBook`] in the [`OrderBookMap`].
@@ -43,6 +43,14 @@ where
                     warn!(%exchange, "OrderBook manager input stream disconnected");
                     continue;
                 }
+                MarketStreamEvent::Reconnected { origin, downtime } => {
+                    info!(
+                        exchange = %origin,
+                        ?downtime,
+                        "OrderBook manager input stream reconnected"
+                    );
+                    continue;
+                }
                 MarketStreamEvent::Item(event) => event,
             };
 
@@ -6,6 +6,10 @@ use serde::{Deserialize, Serialize, Serializer};
 use std::cmp::Ordering;
 use tracing::debug;
 
+/// Provides an [`OrderBookImbalance`](imbalance::OrderBookImbalance) strategy input adapter
+/// generating the normalised bid/ask volume imbalance of an L2 [`OrderBook`].
+pub mod imbalance;
+
 /// Provides a [`OrderBookL2Manager`](manager::OrderBookL2Manager) for maintaining a set of local
 /// L2 [`OrderBook`]s.
 pub mod manager;
@@ -116,22 +120,41 @@ impl OrderBook {
             (None, None) => None,
         }
     }
+
+    /// Calculate the normalised `[-1, 1]` volume imbalance of the best bid and ask levels.
+    ///
+    /// See [`volume_imbalance`] for details.
+    pub fn top_of_book_imbalance(&self) -> Option<Decimal> {
+        self.imbalance(1)
+    }
+
+    /// Calculate the normalised `[-1, 1]` volume imbalance of the bid and ask volume summed over
+    /// the best `depth` levels of each side.
+    ///
+    /// See [`volume_imbalance`] for details.
+    pub fn imbalance(&self, depth: usize) -> Option<Decimal> {
+        volume_imbalance(self.bids.volume(depth), self.asks.volume(depth))
+    }
 }
 
 /// Normalised Barter [`Level`]s for one [`Side`] of the [`OrderBook`].
 #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
 pub struct OrderBookSide<Side> {
-    #[serde(skip_serializing)]
+    #[serde(skip_serializing, default)]
     pub side: Side,
     levels: Vec<Level>,
 }
 
 /// Unit type to tag an [`OrderBookSide`] as the bid Side (ie/ buyers) of an [`OrderBook`].
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Display)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Display,
+)]
 pub struct Bids;
 
 /// Unit type to tag an [`OrderBookSide`] as the ask Side (ie/ sellers) of an [`OrderBook`].
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Display)]
+#[derive(
+    Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Deserialize, Display,
+)]
 pub struct Asks;
 
 impl Serialize for Asks {
@@ -202,6 +225,15 @@ where
     Side: std::fmt::Display + std::fmt::Debug,
 {
     /// Return a reference to the [`OrderBookSide`] levels.
+    /// Sum the amount of the best `depth` [`Level`]s.
+    pub fn volume(&self, depth: usize) -> Decimal {
+        self.levels
+            .iter()
+            .take(depth)
+            .map(|level| level.amount)
+            .sum()
+    }
+
     pub fn levels(&self) -> &[Level] {
         &self.levels
     }
@@ -311,6 +343,16 @@ pub fn volume_weighted_mid_price(best_bid: Level, best_ask: Level) -> Decimal {
         / (best_bid.amount + best_ask.amount)
 }
 
+/// Calculate the normalised `[-1, 1]` volume imbalance `(bids - asks) / (bids + asks)`.
+///
+/// Positive values indicate more resting bid volume (ie/ buying pressure), and negative values
+/// more resting ask volume. An empty side of the book yields the extreme of the other side, and
+/// `None` is returned if both sides are empty.
+pub fn volume_imbalance(bid_volume: Decimal, ask_volume: Decimal) -> Option<Decimal> {
+    let total = bid_volume + ask_volume;
+    (!total.is_zero()).then(|| (bid_volume - ask_volume) / total)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -647,4 +689,94 @@ mod tests {
             }
         }
     }
+
+    mod imbalance {
+        use super::*;
+        use rust_decimal_macros::dec;
+
+        #[test]
+        fn test_imbalance_at_several_depth_levels() {
+            // Bid volumes: 10, 20, 30 & ask volumes: 30, 10, 60
+            let book = OrderBook::new(
+                0,
+                Default::default(),
+                vec![
+                    Level::new(dec!(99), dec!(10)),
+                    Level::new(dec!(98), dec!(20)),
+                    Level::new(dec!(97), dec!(30)),
+                ],
+                vec![
+                    Level::new(dec!(101), dec!(30)),
+                    Level::new(dec!(102), dec!(10)),
+                    Level::new(dec!(103), dec!(60)),
+                ],
+            );
+
+            struct TestCase {
+                depth: usize,
+                expected: Option<Decimal>,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: top of book (10 - 30) / 40
+                    depth: 1,
+                    expected: Some(dec!(-0.5)),
+                },
+                TestCase {
+                    // TC1: (30 - 40) / 70
+                    depth: 2,
+                    expected: Some(dec!(-10) / dec!(70)),
+                },
+                TestCase {
+                    // TC2: (60 - 100) / 160
+                    depth: 3,
+                    expected: Some(dec!(-0.25)),
+                },
+                TestCase {
+                    // TC3: depth beyond the book uses every level
+                    depth: 10,
+                    expected: Some(dec!(-0.25)),
+                },
+                TestCase {
+                    // TC4: zero depth has no volume
+                    depth: 0,
+                    expected: None,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                assert_eq!(
+                    book.imbalance(test.depth),
+                    test.expected,
+                    "TC{index} failed"
+                );
+            }
+
+            assert_eq!(book.top_of_book_imbalance(), book.imbalance(1));
+        }
+
+        #[test]
+        fn test_imbalance_with_empty_side_of_book() {
+            let bids_only = OrderBook::new(
+                0,
+                Default::default(),
+                vec![Level::new(dec!(99), dec!(10))],
+                vec![],
+            );
+            assert_eq!(bids_only.imbalance(5), Some(dec!(1)));
+
+            let asks_only = OrderBook::new(
+                0,
+                Default::default(),
+                vec![],
+                vec![Level::new(dec!(101), dec!(10))],
+            );
+            assert_eq!(asks_only.imbalance(5), Some(dec!(-1)));
+
+            let empty =
+                OrderBook::new::<Vec<_>, Vec<_>, Level>(0, Default::default(), vec![], vec![]);
+            assert_eq!(empty.top_of_book_imbalance(), None);
+        }
+    }
 }
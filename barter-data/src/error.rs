@@ -21,6 +21,24 @@ pub enum DataError {
     #[error("SocketError: {0}")]
     Socket(#[from] SocketError),
 
+    #[error("historical data cache I/O error: {0}")]
+    CacheIo(#[from] std::io::Error),
+
+    #[error("historical data archive not found (eg/ market not listed at the date): {url}")]
+    ArchiveMissing { url: String },
+
+    #[error(
+        "historical data archive {url} checksum mismatch: expected {expected}, actual {actual}"
+    )]
+    ArchiveChecksum {
+        url: String,
+        expected: String,
+        actual: String,
+    },
+
+    #[error("historical data archive {url} invalid: {reason}")]
+    ArchiveInvalid { url: String, reason: String },
+
     #[error("unsupported dynamic Subscription for exchange: {exchange}, kind: {sub_kind}")]
     Unsupported {
         exchange: ExchangeId,
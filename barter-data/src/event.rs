@@ -4,7 +4,9 @@ use crate::{
     subscription::{
         book::{OrderBookEvent, OrderBookL1},
         candle::Candle,
+        funding::FundingRate,
         liquidation::Liquidation,
+        ticker::Ticker,
         trade::PublicTrade,
     },
 };
@@ -80,6 +82,8 @@ pub enum DataKind {
     OrderBook(OrderBookEvent),
     Candle(Candle),
     Liquidation(Liquidation),
+    Ticker(Ticker),
+    FundingRate(FundingRate),
 }
 
 impl<InstrumentKey> From<MarketStreamResult<InstrumentKey, PublicTrade>>
@@ -161,3 +165,102 @@ impl<InstrumentKey> From<MarketEvent<InstrumentKey, Liquidation>>
         value.map_kind(Liquidation::into)
     }
 }
+
+impl<InstrumentKey> From<MarketStreamResult<InstrumentKey, Ticker>>
+    for MarketStreamResult<InstrumentKey, DataKind>
+{
+    fn from(value: MarketStreamResult<InstrumentKey, Ticker>) -> Self {
+        value.map_ok(MarketEvent::from)
+    }
+}
+
+impl<InstrumentKey> From<MarketEvent<InstrumentKey, Ticker>>
+    for MarketEvent<InstrumentKey, DataKind>
+{
+    fn from(value: MarketEvent<InstrumentKey, Ticker>) -> Self {
+        value.map_kind(Ticker::into)
+    }
+}
+
+impl<InstrumentKey> From<MarketStreamResult<InstrumentKey, FundingRate>>
+    for MarketStreamResult<InstrumentKey, DataKind>
+{
+    fn from(value: MarketStreamResult<InstrumentKey, FundingRate>) -> Self {
+        value.map_ok(MarketEvent::from)
+    }
+}
+
+impl<InstrumentKey> From<MarketEvent<InstrumentKey, FundingRate>>
+    for MarketEvent<InstrumentKey, DataKind>
+{
+    fn from(value: MarketEvent<InstrumentKey, FundingRate>) -> Self {
+        value.map_kind(FundingRate::into)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::books::OrderBook;
+    use barter_integration::Side;
+
+    fn market_event<T>(kind: T) -> MarketEvent<&'static str, T> {
+        MarketEvent {
+            time_exchange: DateTime::<Utc>::MIN_UTC,
+            time_received: DateTime::<Utc>::MIN_UTC,
+            exchange: ExchangeId::BinanceSpot,
+            instrument: "btc_usdt",
+            kind,
+        }
+    }
+
+    #[test]
+    fn test_market_event_kinds_round_trip_through_data_kind() {
+        let trade = PublicTrade {
+            id: "1".to_string(),
+            price: 100.0,
+            amount: 1.0,
+            side: Side::Buy,
+        };
+        let ticker = Ticker {
+            last_price: 100.0,
+            volume: 10.0,
+            count: Some(5),
+            first_id: Some(1),
+            last_id: Some(5),
+        };
+        let book = OrderBookEvent::Snapshot(OrderBook::default());
+        let candle = Candle {
+            close_time: DateTime::<Utc>::MIN_UTC,
+            open: 99.0,
+            high: 101.0,
+            low: 98.0,
+            close: 100.0,
+            volume: 10.0,
+            trade_count: 5,
+        };
+
+        let events = [
+            MarketEvent::from(market_event(trade.clone())),
+            MarketEvent::from(market_event(ticker)),
+            MarketEvent::from(market_event(book.clone())),
+            MarketEvent::from(market_event(candle)),
+        ];
+
+        // Inner kinds are matchable downstream, & survive serialisation of the DataKind
+        for event in events {
+            let serialised = serde_json::to_string(&event).unwrap();
+            let deserialised =
+                serde_json::from_str::<MarketEvent<&str, DataKind>>(&serialised).unwrap();
+            assert_eq!(deserialised.kind, event.kind);
+
+            match event.kind {
+                DataKind::Trade(actual) => assert_eq!(actual, trade),
+                DataKind::Ticker(actual) => assert_eq!(actual, ticker),
+                DataKind::OrderBook(actual) => assert_eq!(actual, book),
+                DataKind::Candle(actual) => assert_eq!(actual, candle),
+                kind => panic!("unexpected DataKind: {kind:?}"),
+            }
+        }
+    }
+}
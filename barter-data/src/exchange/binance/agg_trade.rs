@@ -0,0 +1,558 @@
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    streams::rate_limit::{RateLimiter, RequestKind},
+    subscription::trade::PublicTrade,
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::{de::de_str, error::SocketError, Side};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize, Serializer};
+use std::{
+    fs, io,
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::{Path, PathBuf},
+};
+use tokio::time::Instant;
+
+/// [`BinanceSpot`](super::spot::BinanceSpot) HTTP aggregate trades url.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#compressed-aggregate-trades-list>
+pub const HTTP_AGG_TRADES_URL_BINANCE_SPOT: &str = "https://api.binance.com/api/v3/aggTrades";
+
+/// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) HTTP aggregate trades url.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#compressed-aggregate-trades-list>
+pub const HTTP_AGG_TRADES_URL_BINANCE_FUTURES_USD: &str =
+    "https://fapi.binance.com/fapi/v1/aggTrades";
+
+/// Maximum number of aggregate trades Binance returns per request.
+pub const BINANCE_AGG_TRADES_LIMIT: u32 = 1000;
+
+/// Maximum time range Binance permits between the `startTime` & `endTime` of a request, in
+/// milliseconds.
+pub const BINANCE_AGG_TRADES_MAX_WINDOW_MS: i64 = 60 * 60 * 1000;
+
+/// Default minimum duration between consecutive aggregate trades requests.
+pub const DEFAULT_AGG_TRADES_REQUEST_INTERVAL: std::time::Duration =
+    std::time::Duration::from_millis(50);
+
+/// Configuration for constructing an [`AggTradeFetcher`] via the new() constructor method.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct AggTradeConfig {
+    /// [`ExchangeId`] of the generated [`MarketEvent`]s.
+    pub exchange: ExchangeId,
+    /// HTTP aggregate trades url (eg/ [`HTTP_AGG_TRADES_URL_BINANCE_SPOT`]).
+    pub url: String,
+    /// Maximum number of aggregate trades requested per page.
+    pub limit: u32,
+    /// Minimum duration between consecutive requests, used to respect the exchange rate limits.
+    pub request_interval: std::time::Duration,
+    /// Optional directory in which fetched ranges are cached as JSONL files, so repeated fetches
+    /// of the same range (eg/ re-running a backtest) are read from disk.
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl AggTradeConfig {
+    /// Default [`AggTradeConfig`] for fetching [`BinanceSpot`](super::spot::BinanceSpot)
+    /// aggregate trades, without a cache.
+    pub fn binance_spot() -> Self {
+        Self {
+            exchange: ExchangeId::BinanceSpot,
+            url: HTTP_AGG_TRADES_URL_BINANCE_SPOT.to_string(),
+            limit: BINANCE_AGG_TRADES_LIMIT,
+            request_interval: DEFAULT_AGG_TRADES_REQUEST_INTERVAL,
+            cache_dir: None,
+        }
+    }
+
+    /// Default [`AggTradeConfig`] for fetching
+    /// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) aggregate trades, without a
+    /// cache.
+    pub fn binance_futures_usd() -> Self {
+        Self {
+            exchange: ExchangeId::BinanceFuturesUsd,
+            url: HTTP_AGG_TRADES_URL_BINANCE_FUTURES_USD.to_string(),
+            ..Self::binance_spot()
+        }
+    }
+}
+
+/// Binance REST client that fetches the historical aggregate trades of a market for
+/// microstructure backtests, transparently paginating the exchange's per-request limit.
+///
+/// The first aggregate trade of a range is located by time (walking the range in windows of at
+/// most one hour, as required by Binance), after which the range is walked by the last-seen
+/// aggregate trade id.
+#[derive(Debug)]
+pub struct AggTradeFetcher {
+    http_client: reqwest::Client,
+    config: AggTradeConfig,
+    /// Time the next request is permitted to be sent.
+    next_request: Mutex<Option<Instant>>,
+    /// [`RateLimiter`] shared with other clients of the same exchange.
+    rate_limiter: RateLimiter,
+}
+
+/// Position of the next aggregate trades page to request.
+#[derive(Copy, Clone, Debug)]
+enum Cursor {
+    Time {
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    },
+    FromId(u64),
+}
+
+impl AggTradeFetcher {
+    /// Constructs a new [`AggTradeFetcher`] using the provided [`AggTradeConfig`].
+    pub fn new(config: AggTradeConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            config,
+            next_request: Mutex::new(None),
+            rate_limiter: RateLimiter::default(),
+        }
+    }
+
+    /// Set the [`RateLimiter`] that additionally paces requests, shared with other REST clients
+    /// of the same exchange.
+    pub fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Self {
+            rate_limiter,
+            ..self
+        }
+    }
+
+    /// Fetch every [`BinanceAggTrade`] of the provided market (eg/ "BTCUSDT") executed within
+    /// the inclusive `[start, end]` range, in ascending id order.
+    ///
+    /// If a cache directory is configured, a previously fetched range is read from it's cache
+    /// file, & a newly fetched range is written to it.
+    pub async fn fetch_agg_trades(
+        &self,
+        market: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<BinanceAggTrade>, DataError> {
+        let Some(cache_path) = self.cache_path(market, start, end) else {
+            return self.fetch_range(market, start, end).await;
+        };
+
+        if cache_path.exists() {
+            return Ok(read_cache(&cache_path)?);
+        }
+
+        let trades = self.fetch_range(market, start, end).await?;
+        write_cache(&cache_path, &trades)?;
+        Ok(trades)
+    }
+
+    /// Fetch every aggregate trade of the provided market within the inclusive `[start, end]`
+    /// range, as normalised [`PublicTrade`] [`MarketEvent`]s timestamped at each trade time.
+    pub async fn fetch_market_events<InstrumentKey>(
+        &self,
+        instrument: InstrumentKey,
+        market: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MarketEvent<InstrumentKey, PublicTrade>>, DataError>
+    where
+        InstrumentKey: Clone,
+    {
+        Ok(self
+            .fetch_agg_trades(market, start, end)
+            .await?
+            .into_iter()
+            .map(|trade| MarketEvent {
+                time_exchange: trade.time,
+                time_received: trade.time,
+                exchange: self.config.exchange,
+                instrument: instrument.clone(),
+                kind: PublicTrade::from(trade),
+            })
+            .collect())
+    }
+
+    /// Fetch every [`BinanceAggTrade`] within the inclusive `[start, end]` range from the
+    /// exchange.
+    async fn fetch_range(
+        &self,
+        market: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<BinanceAggTrade>, DataError> {
+        let mut trades = Vec::new();
+        let mut window_start = start;
+
+        // Locate the first aggregate trade of the range by time, skipping windows without trades
+        let mut page = loop {
+            if window_start > end {
+                return Ok(trades);
+            }
+
+            let window_end = (window_start
+                + Duration::milliseconds(BINANCE_AGG_TRADES_MAX_WINDOW_MS - 1))
+            .min(end);
+            let cursor = Cursor::Time {
+                start: window_start,
+                end: window_end,
+            };
+
+            match self.fetch_page(market, cursor).await? {
+                page if page.is_empty() => window_start = window_end + Duration::milliseconds(1),
+                page => break page,
+            }
+        };
+
+        // Walk the remainder of the range by the last-seen aggregate trade id, until a page
+        // passes the end of the range or is the final (partial) page of available trades
+        while let Some(last) = page.last() {
+            let next_id = last.id + 1;
+            let page_len = page.len();
+            let within_range = page.into_iter().take_while(|trade| trade.time <= end);
+            let prev_len = trades.len();
+            trades.extend(within_range);
+
+            if page_len < self.config.limit as usize || trades.len() - prev_len < page_len {
+                break;
+            }
+
+            page = self.fetch_page(market, Cursor::FromId(next_id)).await?;
+        }
+
+        Ok(trades)
+    }
+
+    /// Fetch a single page of up to `limit` [`BinanceAggTrade`]s at the provided [`Cursor`].
+    async fn fetch_page(
+        &self,
+        market: &str,
+        cursor: Cursor,
+    ) -> Result<Vec<BinanceAggTrade>, DataError> {
+        self.pace().await;
+        self.rate_limiter
+            .acquire(self.config.exchange, RequestKind::Rest)
+            .await;
+
+        let mut query = vec![
+            ("symbol", market.to_string()),
+            ("limit", self.config.limit.to_string()),
+        ];
+        match cursor {
+            Cursor::Time { start, end } => {
+                query.push(("startTime", start.timestamp_millis().to_string()));
+                query.push(("endTime", end.timestamp_millis().to_string()));
+            }
+            Cursor::FromId(id) => query.push(("fromId", id.to_string())),
+        }
+
+        Ok(self
+            .http_client
+            .get(&self.config.url)
+            .query(&query)
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(SocketError::Http)?
+            .json::<Vec<BinanceAggTrade>>()
+            .await
+            .map_err(SocketError::Http)?)
+    }
+
+    /// Waits until the configured request interval has elapsed since the previous request.
+    async fn pace(&self) {
+        let send_at = {
+            let mut next_request = self.next_request.lock();
+            let now = Instant::now();
+            let send_at = next_request.map_or(now, |next| next.max(now));
+            *next_request = Some(send_at + self.config.request_interval);
+            send_at
+        };
+
+        tokio::time::sleep_until(send_at).await;
+    }
+
+    /// Returns the cache file path of the provided market & range, if a cache is configured.
+    fn cache_path(
+        &self,
+        market: &str,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Option<PathBuf> {
+        self.config.cache_dir.as_ref().map(|dir| {
+            dir.join(format!(
+                "{}_{market}_{}_{}.jsonl",
+                self.config.exchange.as_str(),
+                start.timestamp_millis(),
+                end.timestamp_millis()
+            ))
+        })
+    }
+}
+
+/// Read the [`BinanceAggTrade`]s of a cached range, one JSON object per line.
+fn read_cache(path: &Path) -> io::Result<Vec<BinanceAggTrade>> {
+    BufReader::new(fs::File::open(path)?)
+        .lines()
+        .map(|line| Ok(serde_json::from_str(&line?)?))
+        .collect()
+}
+
+/// Write the [`BinanceAggTrade`]s of a fetched range to it's cache file, one JSON object per
+/// line. The file is written under a temporary name & renamed once complete, so an interrupted
+/// write is never mistaken for a cached range.
+fn write_cache(path: &Path, trades: &[BinanceAggTrade]) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let partial = path.with_extension("jsonl.partial");
+    let mut writer = BufWriter::new(fs::File::create(&partial)?);
+    for trade in trades {
+        serde_json::to_writer(&mut writer, trade)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+
+    fs::rename(partial, path)
+}
+
+/// Binance REST aggregate trade, (de)serialised in the exchange wire format so it can be cached.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#compressed-aggregate-trades-list>
+/// ```json
+/// {
+///     "a": 26129,
+///     "p": "0.01633102",
+///     "q": "4.70443515",
+///     "f": 27781,
+///     "l": 27781,
+///     "T": 1498793709153,
+///     "m": true,
+///     "M": true
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceAggTrade {
+    #[serde(rename = "a")]
+    pub id: u64,
+    #[serde(
+        rename = "p",
+        deserialize_with = "de_str",
+        serialize_with = "se_f64_as_str"
+    )]
+    pub price: f64,
+    #[serde(
+        rename = "q",
+        deserialize_with = "de_str",
+        serialize_with = "se_f64_as_str"
+    )]
+    pub amount: f64,
+    #[serde(rename = "f")]
+    pub first_trade_id: u64,
+    #[serde(rename = "l")]
+    pub last_trade_id: u64,
+    #[serde(rename = "T", with = "chrono::serde::ts_milliseconds")]
+    pub time: DateTime<Utc>,
+    #[serde(rename = "m")]
+    pub buyer_is_maker: bool,
+}
+
+impl From<BinanceAggTrade> for PublicTrade {
+    fn from(trade: BinanceAggTrade) -> Self {
+        Self {
+            id: trade.id.to_string(),
+            price: trade.price,
+            amount: trade.amount,
+            side: match trade.buyer_is_maker {
+                true => Side::Sell,
+                false => Side::Buy,
+            },
+        }
+    }
+}
+
+/// Serialize an `f64` as a string, matching the Binance wire format of prices & quantities.
+fn se_f64_as_str<S>(value: &f64, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.collect_str(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn agg_trade(id: u64, time_ms: i64) -> String {
+        format!(
+            r#"{{"a":{id},"p":"100.{id}","q":"0.5","f":{},"l":{},"T":{time_ms},"m":{},"M":true}}"#,
+            id * 10,
+            id * 10 + 1,
+            id & 1 == 0
+        )
+    }
+
+    /// Serves recorded Binance aggTrades responses, keyed by the request cursor (ie/
+    /// "startTime=<ms>" or "fromId=<id>"), recording the cursor of every request received.
+    async fn serve_recorded_agg_trades(
+        responses: Vec<(String, String)>,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/v3/aggTrades", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let requests_server = Arc::clone(&requests);
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap();
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+
+                let cursor = request
+                    .split(['?', '&', ' '])
+                    .find(|param| param.starts_with("startTime=") || param.starts_with("fromId="))
+                    .unwrap()
+                    .to_string();
+                requests_server.lock().push(cursor.clone());
+
+                let body = responses
+                    .iter()
+                    .find(|(key, _)| *key == cursor)
+                    .map(|(_, body)| body.clone())
+                    .unwrap_or_else(|| "[]".to_string());
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    #[test]
+    fn test_de_binance_agg_trade_round_trips_wire_format() {
+        let input = r#"{"a":26129,"p":"0.01633102","q":"4.70443515","f":27781,"l":27781,"T":1498793709153,"m":true,"M":true}"#;
+
+        let actual = serde_json::from_str::<BinanceAggTrade>(input).unwrap();
+        assert_eq!(
+            actual,
+            BinanceAggTrade {
+                id: 26129,
+                price: 0.01633102,
+                amount: 4.70443515,
+                first_trade_id: 27781,
+                last_trade_id: 27781,
+                time: DateTime::from_timestamp_millis(1498793709153).unwrap(),
+                buyer_is_maker: true,
+            }
+        );
+        assert_eq!(PublicTrade::from(actual.clone()).side, Side::Sell);
+
+        let cached = serde_json::to_string(&actual).unwrap();
+        assert_eq!(
+            serde_json::from_str::<BinanceAggTrade>(&cached).unwrap(),
+            actual
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_agg_trades_paginates_by_last_seen_id_and_caches_range() {
+        let start = DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+        let start_ms = start.timestamp_millis();
+        let hour_ms = BINANCE_AGG_TRADES_MAX_WINDOW_MS;
+        let end = start + Duration::milliseconds(2 * hour_ms + 10_000);
+
+        // First hour has no trades, then full pages (limit of 2) are walked by id until a page
+        // passes the end of the range
+        let (url, requests) = serve_recorded_agg_trades(vec![
+            (
+                format!("startTime={}", start_ms + hour_ms),
+                format!(
+                    "[{},{}]",
+                    agg_trade(1, start_ms + hour_ms + 1),
+                    agg_trade(2, start_ms + hour_ms + 2)
+                ),
+            ),
+            (
+                "fromId=3".to_string(),
+                format!(
+                    "[{},{}]",
+                    agg_trade(3, start_ms + hour_ms + 3),
+                    agg_trade(4, start_ms + 2 * hour_ms)
+                ),
+            ),
+            (
+                "fromId=5".to_string(),
+                format!(
+                    "[{},{}]",
+                    agg_trade(5, end.timestamp_millis()),
+                    agg_trade(6, end.timestamp_millis() + 1)
+                ),
+            ),
+        ])
+        .await;
+
+        let cache_dir = std::env::temp_dir().join(format!(
+            "barter_agg_trade_cache_{}_{}",
+            std::process::id(),
+            start_ms
+        ));
+        let _ = fs::remove_dir_all(&cache_dir);
+        let config = AggTradeConfig {
+            url,
+            limit: 2,
+            request_interval: std::time::Duration::from_millis(1),
+            cache_dir: Some(cache_dir.clone()),
+            ..AggTradeConfig::binance_spot()
+        };
+
+        let actual = AggTradeFetcher::new(config.clone())
+            .fetch_market_events("btc_usdt", "BTCUSDT", start, end)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual
+                .iter()
+                .map(|event| event.kind.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["1", "2", "3", "4", "5"]
+        );
+        assert_eq!(actual[4].time_exchange, end);
+        assert_eq!(actual[0].kind.side, Side::Buy);
+        assert_eq!(actual[1].kind.side, Side::Sell);
+        assert_eq!(
+            *requests.lock(),
+            vec![
+                format!("startTime={start_ms}"),
+                format!("startTime={}", start_ms + hour_ms),
+                "fromId=3".to_string(),
+                "fromId=5".to_string(),
+            ]
+        );
+
+        // Second run over the same range is read from the cache without any requests
+        let cached = AggTradeFetcher::new(config)
+            .fetch_market_events("btc_usdt", "BTCUSDT", start, end)
+            .await
+            .unwrap();
+
+        assert_eq!(cached, actual);
+        assert_eq!(requests.lock().len(), 4);
+
+        fs::remove_dir_all(cache_dir).unwrap();
+    }
+}
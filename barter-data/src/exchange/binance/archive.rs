@@ -0,0 +1,582 @@
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    subscription::{
+        candle::{Candle, Interval},
+        trade::PublicTrade,
+    },
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::{error::SocketError, Side};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{
+    io::{Cursor, Read},
+    str::FromStr,
+};
+
+/// [`BinanceSpot`](super::spot::BinanceSpot) historical data archive base url.
+///
+/// See docs: <https://github.com/binance/binance-public-data>
+pub const ARCHIVE_URL_BINANCE_SPOT: &str = "https://data.binance.vision/data/spot";
+
+/// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) historical data archive base url.
+///
+/// See docs: <https://github.com/binance/binance-public-data>
+pub const ARCHIVE_URL_BINANCE_FUTURES_USD: &str = "https://data.binance.vision/data/futures/um";
+
+/// Archive timestamps at or above this value are in microseconds rather than milliseconds.
+///
+/// [`BinanceSpot`](super::spot::BinanceSpot) archives switched to microseconds from 2025-01-01.
+const ARCHIVE_TIMESTAMP_MICROS_THRESHOLD: i64 = 100_000_000_000_000;
+
+/// Configuration for constructing an [`ArchiveLoader`] via the new() constructor method.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct ArchiveConfig {
+    /// [`ExchangeId`] of the generated [`MarketEvent`]s.
+    pub exchange: ExchangeId,
+    /// Archive base url (eg/ [`ARCHIVE_URL_BINANCE_SPOT`]).
+    pub url: String,
+}
+
+impl ArchiveConfig {
+    /// Default [`ArchiveConfig`] for loading [`BinanceSpot`](super::spot::BinanceSpot) archives.
+    pub fn binance_spot() -> Self {
+        Self {
+            exchange: ExchangeId::BinanceSpot,
+            url: ARCHIVE_URL_BINANCE_SPOT.to_string(),
+        }
+    }
+
+    /// Default [`ArchiveConfig`] for loading
+    /// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) archives.
+    pub fn binance_futures_usd() -> Self {
+        Self {
+            exchange: ExchangeId::BinanceFuturesUsd,
+            url: ARCHIVE_URL_BINANCE_FUTURES_USD.to_string(),
+        }
+    }
+}
+
+/// Period of market data contained in a single Binance archive.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum ArchivePeriod {
+    /// Archive of a single day (eg/ BTCUSDT-1m-2024-01-01.zip).
+    Daily(NaiveDate),
+    /// Archive of a full calendar month (eg/ BTCUSDT-1m-2024-01.zip).
+    Monthly { year: i32, month: u32 },
+}
+
+impl ArchivePeriod {
+    /// Archive url path segment of the period.
+    fn path(&self) -> &'static str {
+        match self {
+            Self::Daily(_) => "daily",
+            Self::Monthly { .. } => "monthly",
+        }
+    }
+
+    /// Archive file name date of the period.
+    fn date(&self) -> String {
+        match self {
+            Self::Daily(date) => date.format("%Y-%m-%d").to_string(),
+            Self::Monthly { year, month } => format!("{year:04}-{month:02}"),
+        }
+    }
+}
+
+/// Loader of Binance's compressed historical data archives (<https://data.binance.vision>),
+/// the cheapest way to get deep kline & trade history.
+///
+/// Each archive is downloaded alongside it's accompanying CHECKSUM file, verified against it's
+/// SHA-256 digest, & decompressed into the contained CSV before being normalised into
+/// [`MarketEvent`]s.
+#[derive(Debug)]
+pub struct ArchiveLoader {
+    http_client: reqwest::Client,
+    config: ArchiveConfig,
+}
+
+impl ArchiveLoader {
+    /// Constructs a new [`ArchiveLoader`] using the provided [`ArchiveConfig`].
+    pub fn new(config: ArchiveConfig) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            config,
+        }
+    }
+
+    /// Load every [`Candle`] of the provided market (eg/ "BTCUSDT") & [`Interval`] within the
+    /// [`ArchivePeriod`], as [`MarketEvent`]s timestamped at each [`Candle`] close time.
+    pub async fn fetch_candles<InstrumentKey>(
+        &self,
+        instrument: InstrumentKey,
+        market: &str,
+        interval: Interval,
+        period: ArchivePeriod,
+    ) -> Result<Vec<MarketEvent<InstrumentKey, Candle>>, DataError>
+    where
+        InstrumentKey: Clone,
+    {
+        let url = format!(
+            "{}/{}/klines/{market}/{interval}/{market}-{interval}-{}.zip",
+            self.config.url,
+            period.path(),
+            period.date(),
+            interval = interval.as_str(),
+        );
+
+        let csv = self.download(&url).await?;
+        Ok(parse_klines(&url, &csv)?
+            .into_iter()
+            .map(|candle| MarketEvent {
+                time_exchange: candle.close_time,
+                time_received: candle.close_time,
+                exchange: self.config.exchange,
+                instrument: instrument.clone(),
+                kind: candle,
+            })
+            .collect())
+    }
+
+    /// Load every [`PublicTrade`] of the provided market (eg/ "BTCUSDT") within the
+    /// [`ArchivePeriod`], as [`MarketEvent`]s timestamped at each trade time.
+    pub async fn fetch_trades<InstrumentKey>(
+        &self,
+        instrument: InstrumentKey,
+        market: &str,
+        period: ArchivePeriod,
+    ) -> Result<Vec<MarketEvent<InstrumentKey, PublicTrade>>, DataError>
+    where
+        InstrumentKey: Clone,
+    {
+        let url = format!(
+            "{}/{}/trades/{market}/{market}-trades-{}.zip",
+            self.config.url,
+            period.path(),
+            period.date(),
+        );
+
+        let csv = self.download(&url).await?;
+        Ok(parse_trades(&url, &csv)?
+            .into_iter()
+            .map(|(time, trade)| MarketEvent {
+                time_exchange: time,
+                time_received: time,
+                exchange: self.config.exchange,
+                instrument: instrument.clone(),
+                kind: trade,
+            })
+            .collect())
+    }
+
+    /// Download the archive at the provided url, verify it against it's CHECKSUM file & return
+    /// the decompressed CSV it contains.
+    async fn download(&self, url: &str) -> Result<String, DataError> {
+        let archive = self.get(url).await?;
+
+        let checksum_url = format!("{url}.CHECKSUM");
+        let checksum = String::from_utf8_lossy(&self.get(&checksum_url).await?).into_owned();
+        verify_checksum(url, &archive, &checksum)?;
+
+        decompress(url, &archive)
+    }
+
+    /// Send a GET request to the provided url, returning the response body.
+    async fn get(&self, url: &str) -> Result<Vec<u8>, DataError> {
+        let response = self
+            .http_client
+            .get(url)
+            .send()
+            .await
+            .map_err(SocketError::Http)?;
+
+        // Archives do not exist for dates before a market was listed
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Err(DataError::ArchiveMissing {
+                url: url.to_string(),
+            });
+        }
+
+        Ok(response
+            .error_for_status()
+            .map_err(SocketError::Http)?
+            .bytes()
+            .await
+            .map_err(SocketError::Http)?
+            .to_vec())
+    }
+}
+
+/// Verify the SHA-256 digest of the archive matches the provided CHECKSUM file contents
+/// (eg/ "<sha256>  BTCUSDT-1m-2024-01-01.zip").
+#[allow(clippy::result_large_err)]
+fn verify_checksum(url: &str, archive: &[u8], checksum: &str) -> Result<(), DataError> {
+    let expected = checksum.split_whitespace().next().unwrap_or_default();
+    let actual = hex::encode(Sha256::digest(archive));
+
+    if expected.eq_ignore_ascii_case(&actual) {
+        Ok(())
+    } else {
+        Err(DataError::ArchiveChecksum {
+            url: url.to_string(),
+            expected: expected.to_string(),
+            actual,
+        })
+    }
+}
+
+/// Decompress the single CSV file contained in the ZIP archive.
+#[allow(clippy::result_large_err)]
+fn decompress(url: &str, archive: &[u8]) -> Result<String, DataError> {
+    let invalid = |reason: String| DataError::ArchiveInvalid {
+        url: url.to_string(),
+        reason,
+    };
+
+    let mut zip = zip::ZipArchive::new(Cursor::new(archive))
+        .map_err(|error| invalid(format!("failed to open ZIP: {error}")))?;
+    let mut file = zip
+        .by_index(0)
+        .map_err(|error| invalid(format!("failed to open ZIP entry: {error}")))?;
+
+    let mut csv = String::new();
+    file.read_to_string(&mut csv)
+        .map_err(|error| invalid(format!("failed to decompress ZIP entry: {error}")))?;
+
+    Ok(csv)
+}
+
+/// Parse the rows of a klines archive CSV into [`Candle`]s.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://github.com/binance/binance-public-data#klines>
+/// Columns: open_time, open, high, low, close, volume, close_time, quote_volume, count,
+/// taker_buy_volume, taker_buy_quote_volume, ignore.
+/// ```csv
+/// 1704067200000,42283.58,42298.62,42261.02,42298.61,35.9,1704067259999,1519032,1327,17.6,744204,0
+/// ```
+#[allow(clippy::result_large_err)]
+fn parse_klines(url: &str, csv: &str) -> Result<Vec<Candle>, DataError> {
+    parse_rows(url, csv, |row| {
+        Ok(Candle {
+            close_time: row.timestamp(6)?,
+            open: row.field(1)?,
+            high: row.field(2)?,
+            low: row.field(3)?,
+            close: row.field(4)?,
+            volume: row.field(5)?,
+            trade_count: row.field(8)?,
+        })
+    })
+}
+
+/// Parse the rows of a trades archive CSV into timestamped [`PublicTrade`]s.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://github.com/binance/binance-public-data#trades>
+/// ```csv
+/// id,price,qty,quote_qty,time,is_buyer_maker,is_best_match
+/// 3360930838,42283.58,0.00332,140.38149,1704067200000,True,True
+/// ```
+///
+/// buyer_is_maker => Side::Sell
+/// !buyer_is_maker => Side::Buy
+#[allow(clippy::result_large_err)]
+fn parse_trades(url: &str, csv: &str) -> Result<Vec<(DateTime<Utc>, PublicTrade)>, DataError> {
+    parse_rows(url, csv, |row| {
+        let side = if row.field::<String>(5)?.eq_ignore_ascii_case("true") {
+            Side::Sell
+        } else {
+            Side::Buy
+        };
+
+        Ok((
+            row.timestamp(4)?,
+            PublicTrade {
+                id: row.field(0)?,
+                price: row.field(1)?,
+                amount: row.field(2)?,
+                side,
+            },
+        ))
+    })
+}
+
+/// Parse every row of an archive CSV using the provided parser, skipping the header row that
+/// only some archives (eg/ futures) include.
+#[allow(clippy::result_large_err)]
+fn parse_rows<T, F>(url: &str, csv: &str, parser: F) -> Result<Vec<T>, DataError>
+where
+    F: Fn(&ArchiveRow<'_>) -> Result<T, String>,
+{
+    let invalid = |line: usize, reason: String| DataError::ArchiveInvalid {
+        url: url.to_string(),
+        reason: format!("line {line}: {reason}"),
+    };
+
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader(csv.as_bytes())
+        .into_records()
+        .enumerate()
+        .filter_map(|(index, record)| {
+            let line = index + 1;
+            let record = match record {
+                Ok(record) => record,
+                Err(error) => return Some(Err(invalid(line, error.to_string()))),
+            };
+
+            let header = index == 0 && record.get(0).is_some_and(|id| id.parse::<u64>().is_err());
+            (!header).then(|| parser(&ArchiveRow(&record)).map_err(|reason| invalid(line, reason)))
+        })
+        .collect()
+}
+
+/// Row of an archive CSV.
+struct ArchiveRow<'a>(&'a csv::StringRecord);
+
+impl ArchiveRow<'_> {
+    /// Parse the field at the provided column index.
+    fn field<T>(&self, index: usize) -> Result<T, String>
+    where
+        T: FromStr,
+        T::Err: std::fmt::Display,
+    {
+        let field = self
+            .0
+            .get(index)
+            .ok_or_else(|| format!("missing column {index}"))?;
+
+        field
+            .parse()
+            .map_err(|error| format!("column {index} value {field} invalid: {error}"))
+    }
+
+    /// Parse the millisecond or microsecond epoch timestamp at the provided column index.
+    fn timestamp(&self, index: usize) -> Result<DateTime<Utc>, String> {
+        let timestamp = self.field::<i64>(index)?;
+
+        let datetime = if timestamp >= ARCHIVE_TIMESTAMP_MICROS_THRESHOLD {
+            DateTime::from_timestamp_micros(timestamp)
+        } else {
+            DateTime::from_timestamp_millis(timestamp)
+        };
+
+        datetime.ok_or_else(|| format!("column {index} timestamp {timestamp} out of range"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    const KLINES_ARCHIVE: &[u8] = include_bytes!("../../../tests/data/BTCUSDT-1m-2024-01-01.zip");
+    const KLINES_CHECKSUM: &[u8] =
+        include_bytes!("../../../tests/data/BTCUSDT-1m-2024-01-01.zip.CHECKSUM");
+
+    const KLINES_PATH: &str = "/data/spot/daily/klines/BTCUSDT/1m/BTCUSDT-1m-2024-01-01.zip";
+
+    /// Serves the recorded archive files keyed by url path, responding 404 to any other path.
+    async fn serve_recorded_archives(files: Vec<(String, Vec<u8>)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/data/spot", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap();
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+                let path = request.split(' ').nth(1).unwrap_or_default();
+
+                let (status, body) = files
+                    .iter()
+                    .find(|(file, _)| file == path)
+                    .map(|(_, body)| ("200 OK", body.clone()))
+                    .unwrap_or_else(|| ("404 Not Found", Vec::new()));
+
+                let header = format!(
+                    "HTTP/1.1 {status}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n",
+                    body.len()
+                );
+                socket.write_all(header.as_bytes()).await.unwrap();
+                socket.write_all(&body).await.unwrap();
+            }
+        });
+
+        url
+    }
+
+    fn recorded_klines_archive() -> Vec<(String, Vec<u8>)> {
+        vec![
+            (KLINES_PATH.to_string(), KLINES_ARCHIVE.to_vec()),
+            (format!("{KLINES_PATH}.CHECKSUM"), KLINES_CHECKSUM.to_vec()),
+        ]
+    }
+
+    fn loader(url: String) -> ArchiveLoader {
+        ArchiveLoader::new(ArchiveConfig {
+            url,
+            ..ArchiveConfig::binance_spot()
+        })
+    }
+
+    fn date(year: i32, month: u32, day: u32) -> ArchivePeriod {
+        ArchivePeriod::Daily(NaiveDate::from_ymd_opt(year, month, day).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_fetch_candles_decodes_recorded_archive() {
+        let url = serve_recorded_archives(recorded_klines_archive()).await;
+
+        let actual = loader(url)
+            .fetch_candles("btc_usdt", "BTCUSDT", Interval::M1, date(2024, 1, 1))
+            .await
+            .unwrap();
+
+        let close_time = |millis| DateTime::from_timestamp_millis(millis).unwrap();
+        let expected = vec![
+            Candle {
+                close_time: close_time(1704067259999),
+                open: 42283.58,
+                high: 42298.62,
+                low: 42261.02,
+                close: 42298.61,
+                volume: 35.92724,
+                trade_count: 1327,
+            },
+            Candle {
+                close_time: close_time(1704067319999),
+                open: 42298.62,
+                high: 42320.0,
+                low: 42298.61,
+                close: 42320.0,
+                volume: 21.37292,
+                trade_count: 944,
+            },
+            Candle {
+                close_time: close_time(1704067379999),
+                open: 42320.0,
+                high: 42331.54,
+                low: 42306.64,
+                close: 42308.46,
+                volume: 31.74938,
+                trade_count: 1105,
+            },
+        ];
+
+        assert_eq!(
+            actual.iter().map(|event| event.kind).collect::<Vec<_>>(),
+            expected
+        );
+        assert!(actual.iter().all(|event| {
+            event.exchange == ExchangeId::BinanceSpot
+                && event.instrument == "btc_usdt"
+                && event.time_exchange == event.kind.close_time
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_candles_with_missing_archive_date() {
+        let url = serve_recorded_archives(recorded_klines_archive()).await;
+
+        // BTCUSDT archive does not exist for a date before it was listed
+        let actual = loader(url)
+            .fetch_candles("btc_usdt", "BTCUSDT", Interval::M1, date(2017, 1, 1))
+            .await;
+
+        assert!(
+            matches!(
+                &actual,
+                Err(DataError::ArchiveMissing { url })
+                    if url.ends_with("/BTCUSDT/1m/BTCUSDT-1m-2017-01-01.zip")
+            ),
+            "{actual:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_candles_with_checksum_mismatch() {
+        let url = serve_recorded_archives(vec![
+            (KLINES_PATH.to_string(), KLINES_ARCHIVE.to_vec()),
+            (
+                format!("{KLINES_PATH}.CHECKSUM"),
+                format!("{}  BTCUSDT-1m-2024-01-01.zip", "0".repeat(64)).into_bytes(),
+            ),
+        ])
+        .await;
+
+        let actual = loader(url)
+            .fetch_candles("btc_usdt", "BTCUSDT", Interval::M1, date(2024, 1, 1))
+            .await;
+
+        assert!(
+            matches!(&actual, Err(DataError::ArchiveChecksum { .. })),
+            "{actual:?}"
+        );
+    }
+
+    #[test]
+    fn test_parse_trades_with_spot_and_futures_archive_formats() {
+        // Spot: no header, capitalised booleans, microsecond timestamps from 2025-01-01
+        let spot = "\
+            4404540271,93576.00,0.00012,11.22912,1735689600000123,True,True\n\
+            4404540272,93576.01,0.00500,467.88005,1735689600001456,False,True\n";
+
+        // Futures: header, lowercase booleans, millisecond timestamps
+        let futures = "\
+            id,price,qty,quote_qty,time,is_buyer_maker\n\
+            5608417035,93571.00,0.002,187.142,1735689600011,true\n";
+
+        let spot = parse_trades("spot", spot).unwrap();
+        assert_eq!(
+            spot,
+            vec![
+                (
+                    DateTime::from_timestamp_micros(1735689600000123).unwrap(),
+                    PublicTrade {
+                        id: "4404540271".to_string(),
+                        price: 93576.0,
+                        amount: 0.00012,
+                        side: Side::Sell,
+                    }
+                ),
+                (
+                    DateTime::from_timestamp_micros(1735689600001456).unwrap(),
+                    PublicTrade {
+                        id: "4404540272".to_string(),
+                        price: 93576.01,
+                        amount: 0.005,
+                        side: Side::Buy,
+                    }
+                ),
+            ]
+        );
+
+        let futures = parse_trades("futures", futures).unwrap();
+        assert_eq!(
+            futures,
+            vec![(
+                DateTime::from_timestamp_millis(1735689600011).unwrap(),
+                PublicTrade {
+                    id: "5608417035".to_string(),
+                    price: 93571.0,
+                    amount: 0.002,
+                    side: Side::Sell,
+                }
+            )]
+        );
+
+        // Malformed rows are rejected w/ the offending line
+        assert!(matches!(
+            parse_trades("spot", "4404540271,not_a_price,0.1,1.0,1735689600000,True,True"),
+            Err(DataError::ArchiveInvalid { reason, .. }) if reason.starts_with("line 1:")
+        ));
+    }
+}
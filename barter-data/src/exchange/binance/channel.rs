@@ -2,6 +2,8 @@ use super::{futures::BinanceFuturesUsd, Binance};
 use crate::{
     subscription::{
         book::{OrderBooksL1, OrderBooksL2},
+        candle::{Candles, Interval},
+        funding::FundingRates,
         liquidation::Liquidations,
         trade::PublicTrades,
         Subscription,
@@ -46,6 +48,32 @@ impl BinanceChannel {
     ///
     /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#liquidation-order-streams>
     pub const LIQUIDATIONS: Self = Self("@forceOrder");
+
+    /// [`BinanceFuturesUsd`] mark price & funding rate channel name.
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#mark-price-stream>
+    pub const FUNDING_RATES: Self = Self("@markPrice");
+
+    /// [`Binance`] kline/candlestick channel name for the provided [`Interval`].
+    ///
+    /// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-streams>
+    /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#kline-candlestick-streams>
+    pub fn klines(interval: Interval) -> Self {
+        Self(match interval {
+            Interval::M1 => "@kline_1m",
+            Interval::M3 => "@kline_3m",
+            Interval::M5 => "@kline_5m",
+            Interval::M15 => "@kline_15m",
+            Interval::M30 => "@kline_30m",
+            Interval::H1 => "@kline_1h",
+            Interval::H2 => "@kline_2h",
+            Interval::H4 => "@kline_4h",
+            Interval::H6 => "@kline_6h",
+            Interval::H12 => "@kline_12h",
+            Interval::D1 => "@kline_1d",
+            Interval::W1 => "@kline_1w",
+        })
+    }
 }
 
 impl<Server, Instrument> Identifier<BinanceChannel>
@@ -72,6 +100,14 @@ impl<Server, Instrument> Identifier<BinanceChannel>
     }
 }
 
+impl<Server, Instrument, const ONLY_CLOSED: bool> Identifier<BinanceChannel>
+    for Subscription<Binance<Server>, Instrument, Candles<ONLY_CLOSED>>
+{
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::klines(self.kind.0)
+    }
+}
+
 impl<Instrument> Identifier<BinanceChannel>
     for Subscription<BinanceFuturesUsd, Instrument, Liquidations>
 {
@@ -80,6 +116,14 @@ impl<Instrument> Identifier<BinanceChannel>
     }
 }
 
+impl<Instrument> Identifier<BinanceChannel>
+    for Subscription<BinanceFuturesUsd, Instrument, FundingRates>
+{
+    fn id(&self) -> BinanceChannel {
+        BinanceChannel::FUNDING_RATES
+    }
+}
+
 impl AsRef<str> for BinanceChannel {
     fn as_ref(&self) -> &str {
         self.0
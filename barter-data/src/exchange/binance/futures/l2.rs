@@ -177,7 +177,8 @@ where
 /// 4. -- *DIFFERENT FROM SPOT* --
 ///    Drop any event where u is < lastUpdateId in the snapshot.
 /// 5. -- *DIFFERENT FROM SPOT* --
-///    The first processed event should have U <= lastUpdateId AND u >= lastUpdateId
+///    The first processed event should have U <= lastUpdateId AND u >= lastUpdateId. An event
+///    that follows on directly from the snapshot (U = lastUpdateId + 1) is also accepted.
 /// 6. -- *DIFFERENT FROM SPOT* --
 ///    While listening to the stream, each new event's pu should be equal to the previous
 ///    event's u, otherwise initialize the process from step 3.
@@ -219,7 +220,7 @@ impl BinanceFuturesUsdOrderBookL2Sequencer {
         }
 
         if self.is_first_update() {
-            // 5. The first processed event should have U <= lastUpdateId AND u >= lastUpdateId:
+            // 5. The first processed event should have U <= lastUpdateId + 1 AND u >= lastUpdateId:
             self.validate_first_update(&update)?;
         } else {
             // 6. Each new event's pu should be equal to the previous event's u:
@@ -244,12 +245,15 @@ impl BinanceFuturesUsdOrderBookL2Sequencer {
     /// BinanceFuturesUsd: How To Manage A Local OrderBook Correctly: Step 5:
     /// "The first processed event should have U <= lastUpdateId AND u >= lastUpdateId"
     ///
+    /// An event that follows on directly from the snapshot (U = lastUpdateId + 1) leaves no gap,
+    /// so is also accepted.
+    ///
     /// See docs: <https://binance-docs.github.io/apidocs/futures/en/#how-to-manage-a-local-order-book-correctly>
     pub fn validate_first_update(
         &self,
         update: &BinanceFuturesOrderBookL2Update,
     ) -> Result<(), DataError> {
-        if update.first_update_id <= self.last_update_id
+        if update.first_update_id <= self.last_update_id + 1
             && update.last_update_id >= self.last_update_id
         {
             Ok(())
@@ -518,7 +522,25 @@ mod tests {
                 }),
             },
             TestCase {
-                // TC3: invalid first update w/  u < lastUpdateId & U > lastUpdateId
+                // TC3: valid first update w/ U = lastUpdateId + 1
+                updater: BinanceFuturesUsdOrderBookL2Sequencer {
+                    updates_processed: 0,
+                    last_update_id: 100,
+                },
+                input: BinanceFuturesOrderBookL2Update {
+                    subscription_id: SubscriptionId::from("subscription_id"),
+                    time_exchange: Default::default(),
+                    time_engine: Default::default(),
+                    first_update_id: 101,
+                    last_update_id: 110,
+                    prev_last_update_id: 100,
+                    bids: vec![],
+                    asks: vec![],
+                },
+                expected: Ok(()),
+            },
+            TestCase {
+                // TC4: invalid first update w/  u < lastUpdateId & U > lastUpdateId
                 updater: BinanceFuturesUsdOrderBookL2Sequencer {
                     updates_processed: 0,
                     last_update_id: 100,
@@ -732,4 +754,93 @@ mod tests {
             assert_eq!(test.book, test.expected, "TC{index} failed");
         }
     }
+
+    #[test]
+    fn test_replay_recorded_snapshot_and_deltas() {
+        let snapshot = serde_json::from_str::<BinanceOrderBookL2Snapshot>(
+            r#"{
+                "lastUpdateId": 100, "E": 1571889248000, "T": 1571889247999,
+                "bids": [["100.0", "1"], ["99.0", "2"]],
+                "asks": [["101.0", "1"], ["102.0", "3"]]
+            }"#,
+        )
+        .unwrap();
+
+        let deltas = [
+            // Outdated delta w/ u < lastUpdateId is dropped
+            r#"{"e": "depthUpdate", "E": 1571889248100, "T": 1571889248099, "s": "BTCUSDT",
+                "U": 90, "u": 95, "pu": 85, "b": [["100.0", "50"]], "a": []}"#,
+            // First processed delta w/ U <= lastUpdateId + 1 <= u
+            r#"{"e": "depthUpdate", "E": 1571889248200, "T": 1571889248199, "s": "BTCUSDT",
+                "U": 96, "u": 105, "pu": 95,
+                "b": [["100.0", "0"], ["99.5", "4"]], "a": [["101.0", "2"]]}"#,
+            r#"{"e": "depthUpdate", "E": 1571889248300, "T": 1571889248299, "s": "BTCUSDT",
+                "U": 106, "u": 110, "pu": 105, "b": [], "a": [["102.0", "0"], ["103.0", "1"]]}"#,
+            r#"{"e": "depthUpdate", "E": 1571889248400, "T": 1571889248399, "s": "BTCUSDT",
+                "U": 111, "u": 115, "pu": 110, "b": [["99.0", "5"]], "a": []}"#,
+        ];
+
+        let mut transformer = BinanceFuturesUsdOrderBooksL2Transformer {
+            instrument_map: Map::from_iter([(
+                SubscriptionId::from("@depth@100ms|BTCUSDT"),
+                BinanceOrderBookL2Meta::new(
+                    "btc_usdt_perpetual",
+                    BinanceFuturesUsdOrderBookL2Sequencer::new(snapshot.last_update_id),
+                ),
+            )]),
+        };
+
+        let mut book = match OrderBookEvent::from(snapshot) {
+            OrderBookEvent::Snapshot(book) => book,
+            OrderBookEvent::Update(_) => panic!("expected OrderBookEvent::Snapshot"),
+        };
+
+        let mut outputs = 0;
+        for delta in deltas {
+            let delta = serde_json::from_str::<BinanceFuturesOrderBookL2Update>(delta).unwrap();
+            for event in transformer.transform(delta) {
+                book.update(event.unwrap().kind);
+                outputs += 1;
+            }
+        }
+
+        assert_eq!(outputs, 3);
+        assert_eq!(
+            book,
+            OrderBook::new(
+                115,
+                DateTime::from_timestamp_millis(1571889248399),
+                vec![
+                    Level::new(dec!(99.5), dec!(4)),
+                    Level::new(dec!(99.0), dec!(5))
+                ],
+                vec![
+                    Level::new(dec!(101.0), dec!(2)),
+                    Level::new(dec!(103.0), dec!(1))
+                ],
+            )
+        );
+
+        // Gap in update ids surfaces a terminal resync error
+        let gap = serde_json::from_str::<BinanceFuturesOrderBookL2Update>(
+            r#"{"e": "depthUpdate", "E": 1571889248500, "T": 1571889248499, "s": "BTCUSDT",
+                "U": 120, "u": 125, "pu": 118, "b": [["98.0", "1"]], "a": []}"#,
+        )
+        .unwrap();
+
+        let error = transformer
+            .transform(gap)
+            .into_iter()
+            .next()
+            .unwrap()
+            .unwrap_err();
+        assert!(error.is_terminal());
+        assert!(matches!(
+            error,
+            DataError::InvalidSequence {
+                prev_last_update_id: 115,
+                first_update_id: 120,
+            }
+        ));
+    }
 }
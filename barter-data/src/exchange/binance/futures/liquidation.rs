@@ -32,12 +32,50 @@ use serde::{Deserialize, Serialize};
 ///     }
 /// }
 /// ```
+///
+/// The all-market `!forceOrder@arr` stream delivered via a combined stream connection wraps the
+/// same event in a `{"stream": "!forceOrder@arr", "data": {...}}` envelope, which is also
+/// accepted:
+/// ```json
+/// {
+///     "stream": "!forceOrder@arr",
+///     "data": {
+///         "e": "forceOrder",
+///         "E": 1665523974222,
+///         "o": { "s": "ETHUSDT", "S": "BUY", ... }
+///     }
+/// }
+/// ```
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(from = "BinanceLiquidationMessage")]
 pub struct BinanceLiquidation {
-    #[serde(alias = "o")]
     pub order: BinanceLiquidationOrder,
 }
 
+/// Raw [`BinanceLiquidation`] message shapes, with or without the combined stream envelope.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BinanceLiquidationMessage {
+    Combined { data: BinanceLiquidationEvent },
+    Event(BinanceLiquidationEvent),
+}
+
+/// Raw [`BinanceLiquidation`] event containing the nested liquidation order.
+#[derive(Deserialize)]
+struct BinanceLiquidationEvent {
+    #[serde(alias = "o")]
+    order: BinanceLiquidationOrder,
+}
+
+impl From<BinanceLiquidationMessage> for BinanceLiquidation {
+    fn from(message: BinanceLiquidationMessage) -> Self {
+        match message {
+            BinanceLiquidationMessage::Combined { data: event }
+            | BinanceLiquidationMessage::Event(event) => Self { order: event.order },
+        }
+    }
+}
+
 /// [`BinanceFuturesUsd`](super::BinanceFuturesUsd) Liquidation order.
 ///
 /// ### Raw Payload Examples
@@ -161,5 +199,52 @@ mod tests {
                 }
             );
         }
+
+        #[test]
+        fn test_binance_all_market_liquidation() {
+            let input = r#"
+            {
+                "stream": "!forceOrder@arr",
+                "data": {
+                    "e": "forceOrder",
+                    "E": 1665523974222,
+                    "o": {
+                        "s": "ETHUSDT",
+                        "S": "BUY",
+                        "o": "LIMIT",
+                        "f": "IOC",
+                        "q": "1.250",
+                        "p": "1290.52",
+                        "ap": "1288.10",
+                        "X": "FILLED",
+                        "l": "1.250",
+                        "z": "1.250",
+                        "T": 1665523974217
+                    }
+                }
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<BinanceLiquidation>(input).unwrap(),
+                BinanceLiquidation {
+                    order: BinanceLiquidationOrder {
+                        subscription_id: SubscriptionId::from("@forceOrder|ETHUSDT"),
+                        side: Side::Buy,
+                        price: 1290.52,
+                        quantity: 1.25,
+                        time: datetime_utc_from_epoch_duration(Duration::from_millis(
+                            1665523974217,
+                        )),
+                    },
+                }
+            );
+        }
+
+        #[test]
+        fn test_binance_liquidation_invalid() {
+            let input = r#"{"stream": "!forceOrder@arr", "data": {"e": "forceOrder"}}"#;
+            assert!(serde_json::from_str::<BinanceLiquidation>(input).is_err());
+        }
     }
 }
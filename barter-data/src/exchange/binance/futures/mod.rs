@@ -1,4 +1,4 @@
-use self::liquidation::BinanceLiquidation;
+use self::{funding::BinanceFundingRate, liquidation::BinanceLiquidation};
 use super::{Binance, ExchangeServer};
 use crate::{
     exchange::{
@@ -8,12 +8,15 @@ use crate::{
         StreamSelector,
     },
     instrument::InstrumentData,
-    subscription::{book::OrderBooksL2, liquidation::Liquidations},
+    subscription::{book::OrderBooksL2, funding::FundingRates, liquidation::Liquidations},
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream, NoInitialSnapshots,
 };
 use barter_instrument::exchange::ExchangeId;
 
+/// Mark price & funding rate types.
+pub mod funding;
+
 /// Level 2 OrderBook types.
 pub mod l2;
 
@@ -57,3 +60,13 @@ where
         StatelessTransformer<Self, Instrument::Key, Liquidations, BinanceLiquidation>,
     >;
 }
+
+impl<Instrument> StreamSelector<Instrument, FundingRates> for BinanceFuturesUsd
+where
+    Instrument: InstrumentData,
+{
+    type SnapFetcher = NoInitialSnapshots;
+    type Stream = ExchangeWsStream<
+        StatelessTransformer<Self, Instrument::Key, FundingRates, BinanceFundingRate>,
+    >;
+}
@@ -0,0 +1,428 @@
+use crate::{
+    error::DataError,
+    event::MarketEvent,
+    streams::rate_limit::{RateLimiter, RequestKind},
+    subscription::candle::{Candle, Interval},
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::{
+    de::{de_str, de_u64_epoch_ms_as_datetime_utc},
+    error::SocketError,
+};
+use chrono::{DateTime, Duration, Utc};
+use parking_lot::Mutex;
+use serde::{de::IgnoredAny, Deserialize, Serialize};
+use tokio::time::Instant;
+
+/// [`BinanceSpot`](super::spot::BinanceSpot) HTTP klines url.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-data>
+pub const HTTP_KLINES_URL_BINANCE_SPOT: &str = "https://api.binance.com/api/v3/klines";
+
+/// [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd) HTTP klines url.
+///
+/// See docs: <https://binance-docs.github.io/apidocs/futures/en/#kline-candlestick-data>
+pub const HTTP_KLINES_URL_BINANCE_FUTURES_USD: &str = "https://fapi.binance.com/fapi/v1/klines";
+
+/// Maximum number of klines Binance returns per request.
+pub const BINANCE_KLINES_LIMIT: u32 = 1000;
+
+/// Default minimum duration between consecutive kline requests.
+///
+/// A klines request costs 2 of the 6000 request weight Binance allows per minute, so pacing
+/// requests 50ms apart uses at most 40% of the weight limit.
+pub const DEFAULT_KLINES_REQUEST_INTERVAL: std::time::Duration =
+    std::time::Duration::from_millis(50);
+
+/// Configuration for constructing a [`HistoricalFetcher`] via the new() constructor method.
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// [`ExchangeId`] of the generated [`MarketEvent`]s.
+    pub exchange: ExchangeId,
+    /// HTTP klines url (eg/ [`HTTP_KLINES_URL_BINANCE_SPOT`]).
+    pub url: String,
+    /// Maximum number of klines requested per page.
+    pub limit: u32,
+    /// Minimum duration between consecutive requests, used to respect the exchange rate limits.
+    pub request_interval: std::time::Duration,
+}
+
+impl Config {
+    /// Default [`Config`] for fetching [`BinanceSpot`](super::spot::BinanceSpot) klines.
+    pub fn binance_spot() -> Self {
+        Self {
+            exchange: ExchangeId::BinanceSpot,
+            url: HTTP_KLINES_URL_BINANCE_SPOT.to_string(),
+            limit: BINANCE_KLINES_LIMIT,
+            request_interval: DEFAULT_KLINES_REQUEST_INTERVAL,
+        }
+    }
+
+    /// Default [`Config`] for fetching [`BinanceFuturesUsd`](super::futures::BinanceFuturesUsd)
+    /// klines.
+    pub fn binance_futures_usd() -> Self {
+        Self {
+            exchange: ExchangeId::BinanceFuturesUsd,
+            url: HTTP_KLINES_URL_BINANCE_FUTURES_USD.to_string(),
+            ..Self::binance_spot()
+        }
+    }
+}
+
+/// Binance REST client that fetches historical [`Candle`]s for a market, transparently
+/// paginating the exchange's per-request kline limit.
+///
+/// Requests are paced by the configured request interval, including across concurrent fetches
+/// using the same [`HistoricalFetcher`].
+#[derive(Debug)]
+pub struct HistoricalFetcher {
+    http_client: reqwest::Client,
+    config: Config,
+    /// Time the next request is permitted to be sent.
+    next_request: Mutex<Option<Instant>>,
+    /// [`RateLimiter`] shared with other clients of the same exchange.
+    rate_limiter: RateLimiter,
+}
+
+impl HistoricalFetcher {
+    /// Constructs a new [`HistoricalFetcher`] using the provided [`Config`].
+    pub fn new(config: Config) -> Self {
+        Self {
+            http_client: reqwest::Client::new(),
+            config,
+            next_request: Mutex::new(None),
+            rate_limiter: RateLimiter::default(),
+        }
+    }
+
+    /// Set the [`RateLimiter`] that additionally paces requests, shared with other REST clients
+    /// of the same exchange.
+    pub fn with_rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Self {
+            rate_limiter,
+            ..self
+        }
+    }
+
+    /// Fetch every [`Candle`] of the provided market (eg/ "BTCUSDT") & [`Interval`] that opened
+    /// within the inclusive `[start, end]` range, in ascending time order.
+    pub async fn fetch_candles(
+        &self,
+        market: &str,
+        interval: Interval,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Candle>, DataError> {
+        let mut candles = Vec::new();
+        let mut cursor = start;
+
+        while cursor <= end {
+            let page = self.fetch_page(market, interval, cursor, end).await?;
+            let Some(last) = page.last() else {
+                break;
+            };
+
+            // Next page starts after the close of the final kline in this page
+            cursor = last.close_time + Duration::milliseconds(1);
+            let final_page = page.len() < self.config.limit as usize;
+            candles.extend(page);
+
+            if final_page {
+                break;
+            }
+        }
+
+        Ok(candles)
+    }
+
+    /// Fetch every [`Candle`] of the provided market within the inclusive `[start, end]` range,
+    /// as [`MarketEvent`]s timestamped at each [`Candle`] close time.
+    pub async fn fetch_market_events<InstrumentKey>(
+        &self,
+        instrument: InstrumentKey,
+        market: &str,
+        interval: Interval,
+        start: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<MarketEvent<InstrumentKey, Candle>>, DataError>
+    where
+        InstrumentKey: Clone,
+    {
+        Ok(self
+            .fetch_candles(market, interval, start, end)
+            .await?
+            .into_iter()
+            .map(|candle| MarketEvent {
+                time_exchange: candle.close_time,
+                time_received: candle.close_time,
+                exchange: self.config.exchange,
+                instrument: instrument.clone(),
+                kind: candle,
+            })
+            .collect())
+    }
+
+    /// Fetch a single page of up to `limit` [`Candle`]s, starting at the provided cursor.
+    async fn fetch_page(
+        &self,
+        market: &str,
+        interval: Interval,
+        cursor: DateTime<Utc>,
+        end: DateTime<Utc>,
+    ) -> Result<Vec<Candle>, DataError> {
+        self.pace().await;
+        self.rate_limiter
+            .acquire(self.config.exchange, RequestKind::Rest)
+            .await;
+
+        let klines = self
+            .http_client
+            .get(&self.config.url)
+            .query(&[
+                ("symbol", market.to_string()),
+                ("interval", interval.as_str().to_string()),
+                ("startTime", cursor.timestamp_millis().to_string()),
+                ("endTime", end.timestamp_millis().to_string()),
+                ("limit", self.config.limit.to_string()),
+            ])
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(SocketError::Http)?
+            .json::<Vec<BinanceKlineRest>>()
+            .await
+            .map_err(SocketError::Http)?;
+
+        Ok(klines.into_iter().map(Candle::from).collect())
+    }
+
+    /// Waits until the configured request interval has elapsed since the previous request.
+    async fn pace(&self) {
+        let send_at = {
+            let mut next_request = self.next_request.lock();
+            let now = Instant::now();
+            let send_at = next_request.map_or(now, |next| next.max(now));
+            *next_request = Some(send_at + self.config.request_interval);
+            send_at
+        };
+
+        tokio::time::sleep_until(send_at).await;
+    }
+}
+
+/// Binance REST kline, returned as a JSON array.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-data>
+/// ```json
+/// [
+///     1499040000000,
+///     "0.01634790",
+///     "0.80000000",
+///     "0.01575800",
+///     "0.01577100",
+///     "148976.11427815",
+///     1499644799999,
+///     "2434.19055334",
+///     308,
+///     "1756.87402397",
+///     "28.46694368",
+///     "0"
+/// ]
+/// ```
+#[derive(Clone, PartialEq, Debug, Deserialize)]
+pub struct BinanceKlineRest(
+    #[serde(deserialize_with = "de_u64_epoch_ms_as_datetime_utc")] pub DateTime<Utc>,
+    #[serde(deserialize_with = "de_str")] pub f64,
+    #[serde(deserialize_with = "de_str")] pub f64,
+    #[serde(deserialize_with = "de_str")] pub f64,
+    #[serde(deserialize_with = "de_str")] pub f64,
+    #[serde(deserialize_with = "de_str")] pub f64,
+    #[serde(deserialize_with = "de_u64_epoch_ms_as_datetime_utc")] pub DateTime<Utc>,
+    IgnoredAny,
+    pub u64,
+    IgnoredAny,
+    IgnoredAny,
+    IgnoredAny,
+);
+
+impl From<BinanceKlineRest> for Candle {
+    fn from(kline: BinanceKlineRest) -> Self {
+        let BinanceKlineRest(_, open, high, low, close, volume, close_time, _, trade_count, ..) =
+            kline;
+
+        Self {
+            close_time,
+            open,
+            high,
+            low,
+            close,
+            volume,
+            trade_count,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    fn kline(open_time_ms: i64, close: &str) -> String {
+        format!(
+            r#"[{open_time_ms},"100.0","110.0","90.0","{close}","12.5",{},"1250.0",42,"6.0","600.0","0"]"#,
+            open_time_ms + 59_999
+        )
+    }
+
+    /// Serves recorded Binance klines responses, keyed by the request `startTime`, recording the
+    /// `startTime` of every request received.
+    async fn serve_recorded_klines(
+        responses: Vec<(i64, String)>,
+    ) -> (String, Arc<Mutex<Vec<i64>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/api/v3/klines", listener.local_addr().unwrap());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let requests_server = Arc::clone(&requests);
+
+        tokio::spawn(async move {
+            loop {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut buffer = vec![0; 4096];
+                let read = socket.read(&mut buffer).await.unwrap();
+                let request = String::from_utf8_lossy(&buffer[..read]).to_string();
+
+                let start_time = request
+                    .split(['?', '&', ' '])
+                    .find_map(|param| param.strip_prefix("startTime="))
+                    .and_then(|start_time| start_time.parse::<i64>().ok())
+                    .unwrap();
+                requests_server.lock().push(start_time);
+
+                let body = responses
+                    .iter()
+                    .find(|(start, _)| *start == start_time)
+                    .map(|(_, body)| body.clone())
+                    .unwrap_or_else(|| "[]".to_string());
+
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+            }
+        });
+
+        (url, requests)
+    }
+
+    #[test]
+    fn test_de_binance_kline_rest() {
+        let input = r#"[1499040000000,"0.01634790","0.80000000","0.01575800","0.01577100","148976.11427815",1499644799999,"2434.19055334",308,"1756.87402397","28.46694368","0"]"#;
+
+        let actual = Candle::from(serde_json::from_str::<BinanceKlineRest>(input).unwrap());
+
+        assert_eq!(
+            actual,
+            Candle {
+                close_time: DateTime::from_timestamp_millis(1499644799999).unwrap(),
+                open: 0.01634790,
+                high: 0.80000000,
+                low: 0.01575800,
+                close: 0.01577100,
+                volume: 148976.11427815,
+                trade_count: 308,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fetch_candles_paginates_until_final_partial_page() {
+        let start = DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+        let minute = 60_000;
+        let start_ms = start.timestamp_millis();
+
+        // First page is full (limit of 2), second page is the final partial page
+        let (url, requests) = serve_recorded_klines(vec![
+            (
+                start_ms,
+                format!(
+                    "[{},{}]",
+                    kline(start_ms, "101.0"),
+                    kline(start_ms + minute, "102.0")
+                ),
+            ),
+            (
+                start_ms + 2 * minute,
+                format!("[{}]", kline(start_ms + 2 * minute, "103.0")),
+            ),
+        ])
+        .await;
+
+        let fetcher = HistoricalFetcher::new(Config {
+            url,
+            limit: 2,
+            request_interval: std::time::Duration::from_millis(1),
+            ..Config::binance_spot()
+        });
+
+        let actual = fetcher
+            .fetch_market_events(
+                "btc_usdt",
+                "BTCUSDT",
+                Interval::M1,
+                start,
+                start + Duration::minutes(10),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            actual
+                .iter()
+                .map(|event| event.kind.close)
+                .collect::<Vec<_>>(),
+            vec![101.0, 102.0, 103.0]
+        );
+        assert_eq!(
+            actual[2].time_exchange,
+            start + Duration::milliseconds(3 * minute - 1)
+        );
+        assert!(actual
+            .iter()
+            .all(|event| event.exchange == ExchangeId::BinanceSpot));
+
+        // Second page starts 1ms after the close of the first page's final kline
+        assert_eq!(*requests.lock(), vec![start_ms, start_ms + 2 * minute]);
+    }
+
+    #[tokio::test]
+    async fn test_fetch_candles_with_empty_range() {
+        let (url, requests) = serve_recorded_klines(vec![]).await;
+        let fetcher = HistoricalFetcher::new(Config {
+            url,
+            ..Config::binance_spot()
+        });
+        let start = DateTime::from_timestamp_millis(1_700_000_000_000).unwrap();
+
+        // End before start requires no requests
+        let actual = fetcher
+            .fetch_candles("BTCUSDT", Interval::M1, start, start - Duration::minutes(1))
+            .await
+            .unwrap();
+        assert!(actual.is_empty());
+        assert!(requests.lock().is_empty());
+
+        // Range without any klines yields a single empty page
+        let actual = fetcher
+            .fetch_candles("BTCUSDT", Interval::M1, start, start + Duration::minutes(1))
+            .await
+            .unwrap();
+        assert!(actual.is_empty());
+        assert_eq!(requests.lock().len(), 1);
+    }
+}
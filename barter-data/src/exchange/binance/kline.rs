@@ -0,0 +1,196 @@
+use super::BinanceChannel;
+use crate::{
+    event::{MarketEvent, MarketIter},
+    exchange::ExchangeSub,
+    subscription::candle::{Candle, Candles, Interval},
+    Identifier,
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::{
+    de::{de_str, de_u64_epoch_ms_as_datetime_utc},
+    subscription::SubscriptionId,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+/// Binance real-time kline (candlestick) message.
+///
+/// If `ONLY_CLOSED` is true, updates of the in-progress kline are dropped when converting into
+/// normalised [`Candle`]s (see [`Candles`]).
+///
+/// ### Raw Payload Examples
+/// See docs: <https://binance-docs.github.io/apidocs/spot/en/#kline-candlestick-streams>
+/// ```json
+/// {
+///     "e": "kline",
+///     "E": 1672515782136,
+///     "s": "BNBBTC",
+///     "k": {
+///         "t": 1672515780000,
+///         "T": 1672515839999,
+///         "s": "BNBBTC",
+///         "i": "1m",
+///         "f": 100,
+///         "L": 200,
+///         "o": "0.0010",
+///         "c": "0.0020",
+///         "h": "0.0025",
+///         "l": "0.0015",
+///         "v": "1000",
+///         "n": 100,
+///         "x": false,
+///         "q": "1.0000",
+///         "V": "500",
+///         "Q": "0.500",
+///         "B": "123456"
+///     }
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceKline<const ONLY_CLOSED: bool = false> {
+    #[serde(alias = "E", deserialize_with = "de_u64_epoch_ms_as_datetime_utc")]
+    pub time: DateTime<Utc>,
+    #[serde(alias = "k")]
+    pub kline: BinanceKlineData,
+}
+
+/// Nested "k" object of a [`BinanceKline`].
+///
+/// See [`BinanceKline`] for full raw payload examples.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BinanceKlineData {
+    #[serde(alias = "s")]
+    pub market: SmolStr,
+    #[serde(alias = "i")]
+    pub interval: Interval,
+    #[serde(alias = "T", deserialize_with = "de_u64_epoch_ms_as_datetime_utc")]
+    pub close_time: DateTime<Utc>,
+    #[serde(alias = "o", deserialize_with = "de_str")]
+    pub open: f64,
+    #[serde(alias = "h", deserialize_with = "de_str")]
+    pub high: f64,
+    #[serde(alias = "l", deserialize_with = "de_str")]
+    pub low: f64,
+    #[serde(alias = "c", deserialize_with = "de_str")]
+    pub close: f64,
+    #[serde(alias = "v", deserialize_with = "de_str")]
+    pub volume: f64,
+    #[serde(alias = "n")]
+    pub trade_count: u64,
+    #[serde(alias = "x")]
+    pub closed: bool,
+}
+
+impl<const ONLY_CLOSED: bool> Identifier<Option<SubscriptionId>> for BinanceKline<ONLY_CLOSED> {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(
+            ExchangeSub::from((
+                BinanceChannel::klines(self.kline.interval),
+                self.kline.market.as_str(),
+            ))
+            .id(),
+        )
+    }
+}
+
+impl<InstrumentKey, const ONLY_CLOSED: bool>
+    From<(ExchangeId, InstrumentKey, BinanceKline<ONLY_CLOSED>)>
+    for MarketIter<InstrumentKey, Candle>
+{
+    fn from(
+        (exchange_id, instrument, BinanceKline { time, kline }): (
+            ExchangeId,
+            InstrumentKey,
+            BinanceKline<ONLY_CLOSED>,
+        ),
+    ) -> Self {
+        if !Candles::<ONLY_CLOSED>::yields(kline.closed) {
+            return Self(vec![]);
+        }
+
+        Self(vec![Ok(MarketEvent {
+            time_exchange: time,
+            time_received: Utc::now(),
+            exchange: exchange_id,
+            instrument,
+            kind: Candle {
+                close_time: kline.close_time,
+                open: kline.open,
+                high: kline.high,
+                low: kline.low,
+                close: kline.close,
+                volume: kline.volume,
+                trade_count: kline.trade_count,
+            },
+        })])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kline_message(closed: bool) -> String {
+        format!(
+            r#"
+            {{
+                "e":"kline","E":1672515782136,"s":"BNBBTC",
+                "k":{{
+                    "t":1672515780000,"T":1672515839999,"s":"BNBBTC","i":"1m","f":100,"L":200,
+                    "o":"0.0010","c":"0.0020","h":"0.0025","l":"0.0015","v":"1000","n":100,
+                    "x":{closed},"q":"1.0000","V":"500","Q":"0.500","B":"123456"
+                }}
+            }}
+            "#
+        )
+    }
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_binance_kline() {
+            let actual = serde_json::from_str::<BinanceKline>(&kline_message(false)).unwrap();
+
+            let expected = BinanceKline {
+                time: DateTime::from_timestamp_millis(1672515782136).unwrap(),
+                kline: BinanceKlineData {
+                    market: SmolStr::new("BNBBTC"),
+                    interval: Interval::M1,
+                    close_time: DateTime::from_timestamp_millis(1672515839999).unwrap(),
+                    open: 0.0010,
+                    high: 0.0025,
+                    low: 0.0015,
+                    close: 0.0020,
+                    volume: 1000.0,
+                    trade_count: 100,
+                    closed: false,
+                },
+            };
+
+            assert_eq!(actual, expected);
+            assert_eq!(actual.id(), Some(SubscriptionId::from("@kline_1m|BNBBTC")));
+        }
+    }
+
+    #[test]
+    fn test_binance_kline_only_closed_drops_in_progress_klines() {
+        fn candles<const ONLY_CLOSED: bool>(closed: bool) -> usize {
+            let kline =
+                serde_json::from_str::<BinanceKline<ONLY_CLOSED>>(&kline_message(closed)).unwrap();
+
+            MarketIter::<&str, Candle>::from((ExchangeId::BinanceSpot, "bnb_btc", kline))
+                .0
+                .len()
+        }
+
+        // Every kline update is yielded by default
+        assert_eq!(candles::<false>(false), 1);
+        assert_eq!(candles::<false>(true), 1);
+
+        // Only closed klines are yielded if ONLY_CLOSED
+        assert_eq!(candles::<true>(false), 0);
+        assert_eq!(candles::<true>(true), 1);
+    }
+}
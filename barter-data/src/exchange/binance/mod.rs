@@ -1,12 +1,12 @@
 use self::{
-    book::l1::BinanceOrderBookL1, channel::BinanceChannel, market::BinanceMarket,
-    subscription::BinanceSubResponse, trade::BinanceTrade,
+    book::l1::BinanceOrderBookL1, channel::BinanceChannel, kline::BinanceKline,
+    market::BinanceMarket, subscription::BinanceSubResponse, trade::BinanceTrade,
 };
 use crate::{
     exchange::{Connector, ExchangeServer, ExchangeSub, StreamSelector},
     instrument::InstrumentData,
     subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::{book::OrderBooksL1, trade::PublicTrades, Map},
+    subscription::{book::OrderBooksL1, candle::Candles, trade::PublicTrades, Map},
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream, NoInitialSnapshots,
 };
@@ -15,6 +15,14 @@ use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
 use std::{fmt::Debug, marker::PhantomData};
 use url::Url;
 
+/// REST [`AggTradeFetcher`](agg_trade::AggTradeFetcher) for historical Binance aggregate trades,
+/// with an optional local disk cache.
+pub mod agg_trade;
+
+/// [`ArchiveLoader`](archive::ArchiveLoader) for Binance's compressed historical kline & trade
+/// archives.
+pub mod archive;
+
 /// OrderBook types common to both [`BinanceSpot`](spot::BinanceSpot) and
 /// [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
 pub mod book;
@@ -27,6 +35,13 @@ pub mod channel;
 /// [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
 pub mod futures;
 
+/// REST [`HistoricalFetcher`](historical::HistoricalFetcher) for historical Binance klines.
+pub mod historical;
+
+/// Kline (candlestick) types common to both [`BinanceSpot`](spot::BinanceSpot) and
+/// [`BinanceFuturesUsd`](futures::BinanceFuturesUsd).
+pub mod kline;
+
 /// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
 /// into an exchange [`Connector`] specific market used for generating [`Connector::requests`].
 pub mod market;
@@ -120,6 +135,23 @@ where
     >;
 }
 
+impl<Instrument, Server, const ONLY_CLOSED: bool> StreamSelector<Instrument, Candles<ONLY_CLOSED>>
+    for Binance<Server>
+where
+    Instrument: InstrumentData,
+    Server: ExchangeServer + Debug + Send + Sync,
+{
+    type SnapFetcher = NoInitialSnapshots;
+    type Stream = ExchangeWsStream<
+        StatelessTransformer<
+            Self,
+            Instrument::Key,
+            Candles<ONLY_CLOSED>,
+            BinanceKline<ONLY_CLOSED>,
+        >,
+    >;
+}
+
 impl<'de, Server> serde::Deserialize<'de> for Binance<Server>
 where
     Server: ExchangeServer,
@@ -42,3 +42,59 @@ where
     type SnapFetcher = BinanceSpotOrderBooksL2SnapshotFetcher;
     type Stream = ExchangeWsStream<BinanceSpotOrderBooksL2Transformer<Instrument::Key>>;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        exchange::{
+            binance::{futures::BinanceFuturesUsd, market::BinanceMarket},
+            subscription::ExchangeSub,
+            Connector,
+        },
+        subscription::{trade::PublicTrades, Subscription},
+        Identifier,
+    };
+    use barter_instrument::instrument::{kind::InstrumentKind, Instrument};
+    use barter_integration::protocol::websocket::WsMessage;
+
+    #[test]
+    fn test_binance_spot_connector_is_distinct_from_futures() {
+        assert_eq!(BinanceSpot::ID, ExchangeId::BinanceSpot);
+        assert_ne!(BinanceSpot::ID, BinanceFuturesUsd::ID);
+
+        let spot_url = BinanceSpot::url().unwrap();
+        assert_eq!(spot_url.as_str(), WEBSOCKET_BASE_URL_BINANCE_SPOT);
+        assert_ne!(spot_url, BinanceFuturesUsd::url().unwrap());
+    }
+
+    #[test]
+    fn test_binance_spot_public_trades_subscription() {
+        let subscription: Subscription<BinanceSpot, Instrument, PublicTrades> = (
+            BinanceSpot::default(),
+            "btc",
+            "usdt",
+            InstrumentKind::Spot,
+            PublicTrades,
+        )
+            .into();
+
+        let market: BinanceMarket = subscription.id();
+        assert_eq!(market.as_ref(), "BTCUSDT");
+
+        let requests = BinanceSpot::requests(vec![ExchangeSub::new(&subscription)]);
+        let expected = serde_json::json!({
+            "method": "SUBSCRIBE",
+            "params": ["btcusdt@trade"],
+            "id": 1
+        });
+
+        match requests.as_slice() {
+            [WsMessage::Text(request)] => assert_eq!(
+                serde_json::from_str::<serde_json::Value>(request).unwrap(),
+                expected
+            ),
+            other => panic!("expected a single text subscription request, got: {other:?}"),
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use self::{subscription::BitstampSubResponse, trade::BitstampTrade};
+use crate::{
+    exchange::{
+        connector::{exchange_connector, ExchangeConnector},
+        Connector,
+    },
+    subscription::trade::PublicTrades,
+};
+use barter_instrument::{asset::symbol::Symbol, exchange::ExchangeId};
+use barter_macro::{DeExchange, SerExchange};
+use serde_json::json;
+use smol_str::{format_smolstr, SmolStr, StrExt};
+
+/// [`Subscription`](crate::subscription::Subscription) response type and response
+/// [`Validator`](barter_integration::Validator) for [`Bitstamp`].
+pub mod subscription;
+
+/// Public trade types for [`Bitstamp`].
+pub mod trade;
+
+/// [`Bitstamp`] server base url.
+///
+/// See docs: <https://www.bitstamp.net/websocket/v2/>
+pub const BASE_URL_BITSTAMP: &str = "wss://ws.bitstamp.net";
+
+/// [`Bitstamp`] exchange.
+///
+/// See docs: <https://www.bitstamp.net/websocket/v2/>
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, DeExchange, SerExchange,
+)]
+pub struct Bitstamp;
+
+impl ExchangeConnector for Bitstamp {
+    const BASE_URL: &'static str = BASE_URL_BITSTAMP;
+
+    fn market(base: &Symbol, quote: &Symbol) -> SmolStr {
+        format_smolstr!("{base}{quote}").to_lowercase_smolstr()
+    }
+
+    fn subscribe(channel: &str, market: &str) -> serde_json::Value {
+        json!({
+            "event": "bts:subscribe",
+            "data": {
+                "channel": format!("{channel}_{market}"),
+            },
+        })
+    }
+}
+
+exchange_connector! {
+    exchange: Bitstamp,
+    id: ExchangeId::Bitstamp,
+    channel: BitstampChannel,
+    market: BitstampMarket,
+    sub_response: BitstampSubResponse,
+    kinds: [
+        /// [`Bitstamp`] real-time trades channel.
+        ///
+        /// See docs: <https://www.bitstamp.net/websocket/v2/>
+        PublicTrades => TRADES("live_trades"): BitstampTrade,
+    ],
+}
@@ -0,0 +1,81 @@
+use barter_integration::{error::SocketError, Validator};
+use serde::{Deserialize, Serialize};
+
+/// [`Bitstamp`](super::Bitstamp) WebSocket subscription response.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.bitstamp.net/websocket/v2/>
+/// #### Subscription Success
+/// ```json
+/// {
+///     "event": "bts:subscription_succeeded",
+///     "channel": "live_trades_btcusd",
+///     "data": {}
+/// }
+/// ```
+///
+/// #### Subscription Failure
+/// ```json
+/// {
+///     "event": "bts:error",
+///     "channel": "",
+///     "data": {"code": null, "message": "Bad subscription string."}
+/// }
+/// ```
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+#[serde(tag = "event")]
+pub enum BitstampSubResponse {
+    #[serde(rename = "bts:subscription_succeeded")]
+    Subscribed { channel: String },
+    #[serde(rename = "bts:error")]
+    Error { data: BitstampSubError },
+}
+
+/// Communicates the reason a [`Bitstamp`](super::Bitstamp) subscription failed.
+///
+/// See [`BitstampSubResponse`] for full raw payload examples.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct BitstampSubError {
+    pub message: String,
+}
+
+impl Validator for BitstampSubResponse {
+    fn validate(self) -> Result<Self, SocketError>
+    where
+        Self: Sized,
+    {
+        match &self {
+            BitstampSubResponse::Subscribed { .. } => Ok(self),
+            BitstampSubResponse::Error { data } => Err(SocketError::Subscribe(format!(
+                "received failure subscription response: {}",
+                data.message
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitstamp_sub_response() {
+        let success = serde_json::from_str::<BitstampSubResponse>(
+            r#"{"event":"bts:subscription_succeeded","channel":"live_trades_btcusd","data":{}}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            success,
+            BitstampSubResponse::Subscribed {
+                channel: "live_trades_btcusd".to_owned()
+            }
+        );
+        assert!(success.validate().is_ok());
+
+        let failure = serde_json::from_str::<BitstampSubResponse>(
+            r#"{"event":"bts:error","channel":"","data":{"code":null,"message":"Bad subscription string."}}"#,
+        )
+        .unwrap();
+        assert!(failure.validate().is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use super::BitstampChannel;
+use crate::{
+    event::{MarketEvent, MarketIter},
+    exchange::ExchangeSub,
+    subscription::trade::PublicTrade,
+    Identifier,
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::{subscription::SubscriptionId, Side};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Bitstamp real-time trade WebSocket message.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.bitstamp.net/websocket/v2/>
+/// ```json
+/// {
+///     "data": {
+///         "id": 311436201,
+///         "timestamp": "1700000000",
+///         "amount": 0.0125,
+///         "amount_str": "0.01250000",
+///         "price": 37012,
+///         "price_str": "37012",
+///         "type": 1,
+///         "microtimestamp": "1700000000123456",
+///         "buy_order_id": 1683915245993984,
+///         "sell_order_id": 1683915246325760
+///     },
+///     "channel": "live_trades_btcusd",
+///     "event": "trade"
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BitstampTrade {
+    #[serde(alias = "channel", deserialize_with = "de_trade_subscription_id")]
+    pub subscription_id: SubscriptionId,
+    pub data: BitstampTradeData,
+}
+
+/// [`BitstampTrade`] data, see [`BitstampTrade`] for full raw payload examples.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BitstampTradeData {
+    pub id: u64,
+    #[serde(
+        alias = "microtimestamp",
+        deserialize_with = "de_str_epoch_us_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+    pub amount: f64,
+    pub price: f64,
+    #[serde(alias = "type", deserialize_with = "de_side_from_type")]
+    pub side: Side,
+}
+
+impl Identifier<Option<SubscriptionId>> for BitstampTrade {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl<InstrumentKey> From<(ExchangeId, InstrumentKey, BitstampTrade)>
+    for MarketIter<InstrumentKey, PublicTrade>
+{
+    fn from((exchange_id, instrument, trade): (ExchangeId, InstrumentKey, BitstampTrade)) -> Self {
+        Self(vec![Ok(MarketEvent {
+            time_exchange: trade.data.time,
+            time_received: Utc::now(),
+            exchange: exchange_id,
+            instrument,
+            kind: PublicTrade {
+                id: trade.data.id.to_string(),
+                price: trade.data.price,
+                amount: trade.data.amount,
+                side: trade.data.side,
+            },
+        })])
+    }
+}
+
+/// Deserialize a [`BitstampTrade`] "channel" (eg/ "live_trades_btcusd") as the associated
+/// [`SubscriptionId`] (eg/ SubscriptionId("live_trades|btcusd").
+pub fn de_trade_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    let channel = <&str as Deserialize>::deserialize(deserializer)?;
+    channel
+        .strip_prefix(BitstampChannel::TRADES.0)
+        .and_then(|market| market.strip_prefix('_'))
+        .map(|market| ExchangeSub::from((BitstampChannel::TRADES, market)).id())
+        .ok_or_else(|| {
+            serde::de::Error::custom(format!("unexpected Bitstamp trade channel: {channel}"))
+        })
+}
+
+/// Deserialize a `String` microseconds value (eg/ "1700000000123456") as `DateTime<Utc>`.
+pub fn de_str_epoch_us_as_datetime_utc<'de, D>(deserializer: D) -> Result<DateTime<Utc>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    barter_integration::de::de_str(deserializer).and_then(|epoch_us| {
+        DateTime::from_timestamp_micros(epoch_us)
+            .ok_or_else(|| serde::de::Error::custom("invalid Bitstamp microtimestamp"))
+    })
+}
+
+/// Deserialize a [`BitstampTradeData`] "type" integer field to a Barter [`Side`].
+///
+/// Variants:
+/// 0 => Side::Buy
+/// 1 => Side::Sell
+pub fn de_side_from_type<'de, D>(deserializer: D) -> Result<Side, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    match <u8 as Deserialize>::deserialize(deserializer)? {
+        0 => Ok(Side::Buy),
+        1 => Ok(Side::Sell),
+        other => Err(serde::de::Error::custom(format!(
+            "unexpected Bitstamp trade type: {other}"
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_de_bitstamp_trade() {
+        let input = r#"
+        {
+            "data": {
+                "id": 311436201, "timestamp": "1700000000", "amount": 0.0125,
+                "amount_str": "0.01250000", "price": 37012, "price_str": "37012", "type": 1,
+                "microtimestamp": "1700000000123456", "buy_order_id": 1683915245993984,
+                "sell_order_id": 1683915246325760
+            },
+            "channel": "live_trades_btcusd",
+            "event": "trade"
+        }"#;
+
+        assert_eq!(
+            serde_json::from_str::<BitstampTrade>(input).unwrap(),
+            BitstampTrade {
+                subscription_id: SubscriptionId::from("live_trades|btcusd"),
+                data: BitstampTradeData {
+                    id: 311436201,
+                    time: DateTime::from_timestamp_micros(1700000000123456).unwrap(),
+                    amount: 0.0125,
+                    price: 37012.0,
+                    side: Side::Sell,
+                },
+            }
+        );
+
+        // Subscription success responses are not trades
+        assert!(serde_json::from_str::<BitstampTrade>(
+            r#"{"event":"bts:subscription_succeeded","channel":"live_trades_btcusd","data":{}}"#
+        )
+        .is_err());
+    }
+}
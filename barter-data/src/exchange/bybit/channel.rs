@@ -1,6 +1,6 @@
 use crate::{
     exchange::bybit::Bybit,
-    subscription::{trade::PublicTrades, Subscription},
+    subscription::{ticker::Tickers, trade::PublicTrades, Subscription},
     Identifier,
 };
 use serde::Serialize;
@@ -17,6 +17,11 @@ impl BybitChannel {
     ///
     /// See docs: <https://bybit-exchange.github.io/docs/v5/websocket/public/trade>
     pub const TRADES: Self = Self("publicTrade");
+
+    /// [`Bybit`] real-time tickers channel name.
+    ///
+    /// See docs: <https://bybit-exchange.github.io/docs/v5/websocket/public/ticker>
+    pub const TICKERS: Self = Self("tickers");
 }
 
 impl<Server, Instrument> Identifier<BybitChannel>
@@ -27,6 +32,14 @@ impl<Server, Instrument> Identifier<BybitChannel>
     }
 }
 
+impl<Server, Instrument> Identifier<BybitChannel>
+    for Subscription<Bybit<Server>, Instrument, Tickers>
+{
+    fn id(&self) -> BybitChannel {
+        BybitChannel::TICKERS
+    }
+}
+
 impl AsRef<str> for BybitChannel {
     fn as_ref(&self) -> &str {
         self.0
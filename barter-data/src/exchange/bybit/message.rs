@@ -58,8 +58,8 @@ pub struct BybitPayload<T> {
     pub data: T,
 }
 
-/// Deserialize a [`BybitPayload`] "s" (eg/ "publicTrade.BTCUSDT") as the associated
-/// [`SubscriptionId`].
+/// Deserialize a [`BybitPayload`] "s" (eg/ "publicTrade.BTCUSDT", "tickers.BTCUSDT") as the
+/// associated [`SubscriptionId`].
 ///
 /// eg/ "publicTrade|BTCUSDT"
 pub fn de_message_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
@@ -70,10 +70,11 @@ where
     let mut tokens = input.split('.');
 
     match (tokens.next(), tokens.next(), tokens.next()) {
-        (Some("publicTrade"), Some(market), None) => Ok(SubscriptionId::from(format!(
-            "{}|{market}",
-            BybitChannel::TRADES.0
-        ))),
+        (Some(channel), Some(market), None)
+            if channel == BybitChannel::TRADES.0 || channel == BybitChannel::TICKERS.0 =>
+        {
+            Ok(SubscriptionId::from(format!("{channel}|{market}")))
+        }
         _ => Err(Error::invalid_value(
             Unexpected::Str(input),
             &"invalid message type expected pattern: <type>.<symbol>",
@@ -0,0 +1,238 @@
+use crate::{
+    event::{MarketEvent, MarketIter},
+    exchange::bybit::{message::BybitPayload, subscription::BybitResponse},
+    subscription::ticker::Ticker,
+    Identifier,
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::subscription::SubscriptionId;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+/// Terse type alias for an [`BybitTicker`](BybitTickerInner) real-time tickers WebSocket message.
+pub type BybitTicker = BybitPayload<BybitTickerInner>;
+
+/// [`Bybit`](super::Bybit) tickers websocket message supports both [`BybitTicker`] and
+/// [`BybitResponse`].
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum BybitTickerMessage {
+    Response(BybitResponse),
+    Ticker(BybitTicker),
+}
+
+/// ### Raw Payload Examples
+/// See docs: <https://bybit-exchange.github.io/docs/v5/websocket/public/ticker>
+///
+/// Spot tickers are always a "snapshot", whereas perpetual tickers are a "snapshot" followed by
+/// "delta" messages only containing the fields that changed.
+///
+/// #### Perpetual Snapshot
+///```json
+/// {
+///     "topic": "tickers.BTCUSDT",
+///     "type": "snapshot",
+///     "data": {
+///         "symbol": "BTCUSDT",
+///         "tickDirection": "PlusTick",
+///         "price24hPcnt": "0.017103",
+///         "lastPrice": "17216.00",
+///         "prevPrice24h": "16926.50",
+///         "highPrice24h": "17281.50",
+///         "lowPrice24h": "16915.00",
+///         "markPrice": "17217.33",
+///         "indexPrice": "17227.36",
+///         "openInterest": "68744.761",
+///         "turnover24h": "1570383121.943499",
+///         "volume24h": "91705.276",
+///         "fundingRate": "-0.000212",
+///         "bid1Price": "17215.50",
+///         "bid1Size": "84.489",
+///         "ask1Price": "17216.00",
+///         "ask1Size": "83.020"
+///     },
+///     "cs": 24987956059,
+///     "ts": 1673272861686
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BybitTickerInner {
+    pub symbol: String,
+
+    #[serde(rename = "lastPrice", default, deserialize_with = "de_str_opt")]
+    pub last_price: Option<f64>,
+
+    #[serde(rename = "volume24h", default, deserialize_with = "de_str_opt")]
+    pub volume_24h: Option<f64>,
+}
+
+impl Identifier<Option<SubscriptionId>> for BybitTickerMessage {
+    fn id(&self) -> Option<SubscriptionId> {
+        match self {
+            BybitTickerMessage::Ticker(ticker) => Some(ticker.subscription_id.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<InstrumentKey> From<(ExchangeId, InstrumentKey, BybitTickerMessage)>
+    for MarketIter<InstrumentKey, Ticker>
+{
+    fn from(
+        (exchange_id, instrument, message): (ExchangeId, InstrumentKey, BybitTickerMessage),
+    ) -> Self {
+        let BybitTickerMessage::Ticker(ticker) = message else {
+            return Self(vec![]);
+        };
+
+        // Perpetual "delta" tickers without a last price & volume cannot be normalised
+        let (Some(last_price), Some(volume)) = (ticker.data.last_price, ticker.data.volume_24h)
+        else {
+            return Self(vec![]);
+        };
+
+        Self(vec![Ok(MarketEvent {
+            time_exchange: ticker.time,
+            time_received: Utc::now(),
+            exchange: exchange_id,
+            instrument,
+            kind: Ticker {
+                last_price,
+                volume,
+                count: None,
+                first_id: None,
+                last_id: None,
+            },
+        })])
+    }
+}
+
+/// Deserialize an optional `String` field as an `Option<f64>`, since perpetual "delta" tickers
+/// omit fields that have not changed.
+fn de_str_opt<'de, D>(deserializer: D) -> Result<Option<f64>, D::Error>
+where
+    D: serde::de::Deserializer<'de>,
+{
+    Option::<&str>::deserialize(deserializer)?
+        .map(|value| value.parse::<f64>().map_err(serde::de::Error::custom))
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_integration::de::datetime_utc_from_epoch_duration;
+    use smol_str::ToSmolStr;
+    use std::time::Duration;
+
+    mod de {
+        use super::*;
+
+        #[test]
+        fn test_bybit_ticker_snapshot() {
+            let input = r#"
+            {
+                "topic": "tickers.BTCUSDT",
+                "type": "snapshot",
+                "data": {
+                    "symbol": "BTCUSDT", "tickDirection": "PlusTick", "price24hPcnt": "0.017103",
+                    "lastPrice": "17216.00", "prevPrice24h": "16926.50",
+                    "highPrice24h": "17281.50", "lowPrice24h": "16915.00",
+                    "prevPrice1h": "17238.00", "markPrice": "17217.33", "indexPrice": "17227.36",
+                    "openInterest": "68744.761", "openInterestValue": "1183601235.91",
+                    "turnover24h": "1570383121.943499", "volume24h": "91705.276",
+                    "nextFundingTime": "1673280000000", "fundingRate": "-0.000212",
+                    "bid1Price": "17215.50", "bid1Size": "84.489",
+                    "ask1Price": "17216.00", "ask1Size": "83.020"
+                },
+                "cs": 24987956059,
+                "ts": 1673272861686
+            }
+            "#;
+
+            let actual = serde_json::from_str::<BybitTicker>(input).unwrap();
+            let expected = BybitTicker {
+                subscription_id: SubscriptionId("tickers|BTCUSDT".to_smolstr()),
+                r#type: "snapshot".to_string(),
+                time: datetime_utc_from_epoch_duration(Duration::from_millis(1673272861686)),
+                data: BybitTickerInner {
+                    symbol: "BTCUSDT".to_string(),
+                    last_price: Some(17216.00),
+                    volume_24h: Some(91705.276),
+                },
+            };
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn test_bybit_ticker_message_pong_response() {
+            let input =
+                r#"{"success": true, "ret_msg": "pong", "conn_id": "0970e817", "op": "ping"}"#;
+
+            let actual = serde_json::from_str::<BybitTickerMessage>(input).unwrap();
+            assert!(matches!(actual, BybitTickerMessage::Response(_)));
+            assert_eq!(actual.id(), None);
+        }
+    }
+
+    #[test]
+    fn test_bybit_ticker_delta_without_last_price_is_skipped() {
+        let input = r#"
+        {
+            "topic": "tickers.BTCUSDT",
+            "type": "delta",
+            "data": {
+                "symbol": "BTCUSDT", "bid1Price": "17215.50", "bid1Size": "84.489",
+                "ask1Price": "17216.00", "ask1Size": "83.020"
+            },
+            "cs": 24987956060,
+            "ts": 1673272861786
+        }
+        "#;
+
+        let message = serde_json::from_str::<BybitTickerMessage>(input).unwrap();
+        assert_eq!(message.id(), Some(SubscriptionId::from("tickers|BTCUSDT")));
+
+        let tickers = MarketIter::<&str, Ticker>::from((
+            ExchangeId::BybitPerpetualsUsd,
+            "btc_usdt_perp",
+            message,
+        ));
+        assert!(tickers.0.is_empty());
+    }
+
+    #[test]
+    fn test_bybit_ticker_snapshot_has_no_trade_count_or_ids() {
+        let input = r#"
+        {
+            "topic": "tickers.BTCUSDT",
+            "type": "snapshot",
+            "data": {
+                "symbol": "BTCUSDT", "lastPrice": "17216.00", "volume24h": "91705.276"
+            },
+            "cs": 24987956059,
+            "ts": 1673272861686
+        }
+        "#;
+
+        let message = serde_json::from_str::<BybitTickerMessage>(input).unwrap();
+        let tickers = MarketIter::<&str, Ticker>::from((
+            ExchangeId::BybitPerpetualsUsd,
+            "btc_usdt_perp",
+            message,
+        ));
+
+        let ticker = tickers.0.into_iter().next().unwrap().unwrap().kind;
+        assert_eq!(
+            ticker,
+            Ticker {
+                last_price: 17216.00,
+                volume: 91705.276,
+                count: None,
+                first_id: None,
+                last_id: None,
+            }
+        );
+    }
+}
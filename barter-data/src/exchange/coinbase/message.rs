@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// [`Coinbase`](super::Coinbase) Advanced Trade WebSocket channel message, containing the
+/// channel name, the exchange timestamp & it's events (eg/
+/// [`CoinbaseTradeEvent`](super::trade::CoinbaseTradeEvent)s).
+///
+/// ### Raw Payload Examples
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels>
+///
+/// #### Ticker
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#ticker-channel>
+/// ```json
+/// {
+///     "channel": "ticker",
+///     "client_id": "",
+///     "timestamp": "2023-02-09T20:30:37.167359596Z",
+///     "sequence_num": 0,
+///     "events": [
+///         {
+///             "type": "snapshot",
+///             "tickers": [
+///                 {
+///                     "type": "ticker",
+///                     "product_id": "BTC-USD",
+///                     "price": "21932.98",
+///                     "volume_24_h": "16038.28770938",
+///                     "low_24_h": "21835.29",
+///                     "high_24_h": "23011.18",
+///                     "low_52_w": "15460",
+///                     "high_52_w": "48240",
+///                     "price_percent_chg_24_h": "-4.15775596190603",
+///                     "best_bid": "21931.98",
+///                     "best_bid_quantity": "8000.21",
+///                     "best_ask": "21933.98",
+///                     "best_ask_quantity": "8038.07770938"
+///                 }
+///             ]
+///         }
+///     ]
+/// }
+/// ```
+///
+/// #### Market Trades
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#market-trades-channel>
+/// ```json
+/// {
+///     "channel": "market_trades",
+///     "client_id": "",
+///     "timestamp": "2023-02-09T20:19:35.39625135Z",
+///     "sequence_num": 0,
+///     "events": [
+///         {
+///             "type": "update",
+///             "trades": [
+///                 {
+///                     "trade_id": "000000000",
+///                     "product_id": "ETH-USD",
+///                     "price": "1260.01",
+///                     "size": "0.3",
+///                     "side": "BUY",
+///                     "time": "2019-08-14T20:42:27.265Z"
+///                 }
+///             ]
+///         }
+///     ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Deserialize, Serialize)]
+pub struct CoinbaseMessage<T> {
+    pub channel: String,
+    pub timestamp: DateTime<Utc>,
+    pub events: Vec<T>,
+}
+
+/// [`Coinbase`](super::Coinbase) Advanced Trade event type, communicating if the event contains
+/// an initial snapshot, or an update since the previous event.
+///
+/// See [`CoinbaseMessage`] for full raw payload examples.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CoinbaseEventKind {
+    Snapshot,
+    Update,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+        use chrono::TimeZone;
+
+        #[test]
+        fn test_coinbase_message_timestamp_with_nanosecond_precision() {
+            let input = r#"
+            {
+                "channel": "ticker",
+                "client_id": "",
+                "timestamp": "2023-02-09T20:30:37.167359596Z",
+                "sequence_num": 0,
+                "events": []
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<CoinbaseMessage<serde_json::Value>>(input).unwrap(),
+                CoinbaseMessage {
+                    channel: "ticker".to_string(),
+                    timestamp: Utc.timestamp_nanos(1675974637167359596),
+                    events: vec![],
+                }
+            );
+        }
+    }
+}
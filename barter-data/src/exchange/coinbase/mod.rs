@@ -1,83 +1,73 @@
-use self::{
-    channel::CoinbaseChannel, market::CoinbaseMarket, subscription::CoinbaseSubResponse,
-    trade::CoinbaseTrade,
-};
+use self::{subscription::CoinbaseSubResponse, ticker::CoinbaseTicker, trade::CoinbaseTrades};
 use crate::{
-    exchange::{Connector, ExchangeSub, StreamSelector},
-    instrument::InstrumentData,
-    subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::trade::PublicTrades,
-    transformer::stateless::StatelessTransformer,
-    ExchangeWsStream, NoInitialSnapshots,
+    exchange::{
+        connector::{exchange_connector, ExchangeConnector},
+        Connector,
+    },
+    subscription::{ticker::Tickers, trade::PublicTrades},
 };
-use barter_instrument::exchange::ExchangeId;
-use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
+use barter_instrument::{asset::symbol::Symbol, exchange::ExchangeId};
 use barter_macro::{DeExchange, SerExchange};
 use serde_json::json;
-use url::Url;
-
-/// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
-/// into an exchange [`Connector`] specific channel used for generating [`Connector::requests`].
-pub mod channel;
+use smol_str::{format_smolstr, SmolStr, StrExt};
 
-/// Defines the type that translates a Barter [`Subscription`](crate::subscription::Subscription)
-/// into an exchange [`Connector`] specific market used for generating [`Connector::requests`].
-pub mod market;
+/// Generic [`CoinbaseMessage<T>`](message::CoinbaseMessage) type common to
+/// [`Coinbase`] channels.
+pub mod message;
 
 /// [`Subscription`](crate::subscription::Subscription) response type and response
 /// [`Validator`](barter_integration::Validator) for [`Coinbase`].
 pub mod subscription;
 
+/// Ticker types for [`Coinbase`].
+pub mod ticker;
+
 /// Public trade types for [`Coinbase`].
 pub mod trade;
 
-/// [`Coinbase`] server base url.
+/// [`Coinbase`] Advanced Trade server base url.
 ///
-/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-overview>
-pub const BASE_URL_COINBASE: &str = "wss://ws-feed.exchange.coinbase.com";
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview>
+pub const BASE_URL_COINBASE: &str = "wss://advanced-trade-ws.coinbase.com";
 
-/// [`Coinbase`] exchange.
+/// [`Coinbase`] Advanced Trade exchange.
 ///
-/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-overview>
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview>
 #[derive(
     Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, DeExchange, SerExchange,
 )]
 pub struct Coinbase;
 
-impl Connector for Coinbase {
-    const ID: ExchangeId = ExchangeId::Coinbase;
-    type Channel = CoinbaseChannel;
-    type Market = CoinbaseMarket;
-    type Subscriber = WebSocketSubscriber;
-    type SubValidator = WebSocketSubValidator;
-    type SubResponse = CoinbaseSubResponse;
+impl ExchangeConnector for Coinbase {
+    const BASE_URL: &'static str = BASE_URL_COINBASE;
 
-    fn url() -> Result<Url, SocketError> {
-        Url::parse(BASE_URL_COINBASE).map_err(SocketError::UrlParse)
+    fn market(base: &Symbol, quote: &Symbol) -> SmolStr {
+        format_smolstr!("{base}-{quote}").to_uppercase_smolstr()
     }
 
-    fn requests(exchange_subs: Vec<ExchangeSub<Self::Channel, Self::Market>>) -> Vec<WsMessage> {
-        exchange_subs
-            .into_iter()
-            .map(|ExchangeSub { channel, market }| {
-                WsMessage::Text(
-                    json!({
-                        "type": "subscribe",
-                        "product_ids": [market.as_ref()],
-                        "channels": [channel.as_ref()],
-                    })
-                    .to_string(),
-                )
-            })
-            .collect()
+    fn subscribe(channel: &str, market: &str) -> serde_json::Value {
+        json!({
+            "type": "subscribe",
+            "product_ids": [market],
+            "channel": channel,
+        })
     }
 }
 
-impl<Instrument> StreamSelector<Instrument, PublicTrades> for Coinbase
-where
-    Instrument: InstrumentData,
-{
-    type SnapFetcher = NoInitialSnapshots;
-    type Stream =
-        ExchangeWsStream<StatelessTransformer<Self, Instrument::Key, PublicTrades, CoinbaseTrade>>;
+exchange_connector! {
+    exchange: Coinbase,
+    id: ExchangeId::Coinbase,
+    channel: CoinbaseChannel,
+    market: CoinbaseMarket,
+    sub_response: CoinbaseSubResponse,
+    kinds: [
+        /// [`Coinbase`] real-time market trades channel.
+        ///
+        /// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#market-trades-channel>
+        PublicTrades => TRADES("market_trades"): CoinbaseTrades,
+        /// [`Coinbase`] real-time ticker channel.
+        ///
+        /// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#ticker-channel>
+        Tickers => TICKER("ticker"): CoinbaseTicker,
+    ],
 }
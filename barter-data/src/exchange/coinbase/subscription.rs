@@ -1,16 +1,20 @@
 use barter_integration::{error::SocketError, Validator};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-/// [`Coinbase`](super::Coinbase) WebSocket subscription response.
+/// [`Coinbase`](super::Coinbase) Advanced Trade WebSocket subscription response.
 ///
 /// ### Raw Payload Examples
-/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-overview#subscribe>
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview#subscribe>
 /// #### Subscripion Success
 /// ```json
 /// {
-///     "type":"subscriptions",
-///     "channels":[
-///         {"name":"matches","product_ids":["BTC-USD", "ETH-USD"]}
+///     "channel": "subscriptions",
+///     "client_id": "",
+///     "timestamp": "2023-02-09T20:32:49.523473839Z",
+///     "sequence_num": 1,
+///     "events": [
+///         {"subscriptions": {"market_trades": ["BTC-USD", "ETH-USD"]}}
 ///     ]
 /// }
 /// ```
@@ -18,34 +22,26 @@ use serde::{Deserialize, Serialize};
 /// #### Subscription Failure
 /// ```json
 /// {
-///     "type":"error",
-///     "message":"Failed to subscribe",
-///     "reason":"GIBBERISH-USD is not a valid product"
+///     "type": "error",
+///     "message": "Failed to subscribe"
 /// }
 /// ```
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
-#[serde(tag = "type", rename_all = "lowercase")]
+#[serde(untagged)]
 pub enum CoinbaseSubResponse {
-    #[serde(alias = "subscriptions")]
-    Subscribed {
-        channels: Vec<CoinbaseChannels>,
-    },
-    Error {
-        reason: String,
-    },
+    Subscribed { events: Vec<CoinbaseSubscriptions> },
+    Error { message: String },
 }
 
 /// Communicates the [`Coinbase`](super::Coinbase) product_ids (eg/ "ETH-USD") associated with
-/// a successful channel (eg/ "matches") subscription.
+/// each successful channel (eg/ "market_trades") subscription.
 ///
 /// See [`CoinbaseSubResponse`] for full raw paylaod examples.
 ///
-/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-overview#subscribe>
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-overview#subscribe>
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
-pub struct CoinbaseChannels {
-    #[serde(alias = "name")]
-    pub channel: String,
-    pub product_ids: Vec<String>,
+pub struct CoinbaseSubscriptions {
+    pub subscriptions: BTreeMap<String, Vec<String>>,
 }
 
 impl Validator for CoinbaseSubResponse {
@@ -55,9 +51,9 @@ impl Validator for CoinbaseSubResponse {
     {
         match &self {
             CoinbaseSubResponse::Subscribed { .. } => Ok(self),
-            CoinbaseSubResponse::Error { reason } => Err(SocketError::Subscribe(format!(
+            CoinbaseSubResponse::Error { message } => Err(SocketError::Subscribe(format!(
                 "received failure subscription response: {}",
-                reason
+                message
             ))),
         }
     }
@@ -82,16 +78,21 @@ mod tests {
                     // TC0: input response is Subscribed
                     input: r#"
                     {
-                        "type":"subscriptions",
-                        "channels":[
-                            {"name":"matches","product_ids":["BTC-USD", "ETH-USD"]}
+                        "channel": "subscriptions",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:32:49.523473839Z",
+                        "sequence_num": 1,
+                        "events": [
+                            {"subscriptions": {"market_trades": ["BTC-USD", "ETH-USD"]}}
                         ]
                     }
                     "#,
                     expected: Ok(CoinbaseSubResponse::Subscribed {
-                        channels: vec![CoinbaseChannels {
-                            channel: "matches".to_string(),
-                            product_ids: vec!["BTC-USD".to_string(), "ETH-USD".to_string()],
+                        events: vec![CoinbaseSubscriptions {
+                            subscriptions: BTreeMap::from([(
+                                "market_trades".to_string(),
+                                vec!["BTC-USD".to_string(), "ETH-USD".to_string()],
+                            )]),
                         }],
                     }),
                 },
@@ -99,13 +100,28 @@ mod tests {
                     // TC1: input response is failed subscription
                     input: r#"
                     {
-                        "type":"error",
-                        "message":"Failed to subscribe",
-                        "reason":"GIBBERISH-USD is not a valid product"
+                        "type": "error",
+                        "message": "Failed to subscribe"
                     }
                     "#,
                     expected: Ok(CoinbaseSubResponse::Error {
-                        reason: "GIBBERISH-USD is not a valid product".to_string(),
+                        message: "Failed to subscribe".to_string(),
+                    }),
+                },
+                TestCase {
+                    // TC2: input ticker data is not a subscription response
+                    input: r#"
+                    {
+                        "channel": "ticker",
+                        "client_id": "",
+                        "timestamp": "2023-02-09T20:30:37.167359596Z",
+                        "sequence_num": 0,
+                        "events": [{"type": "snapshot", "tickers": []}]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "".to_string(),
+                        item: "".to_string(),
                     }),
                 },
             ];
@@ -139,9 +155,11 @@ mod tests {
             TestCase {
                 // TC0: input response is successful subscription
                 input_response: CoinbaseSubResponse::Subscribed {
-                    channels: vec![CoinbaseChannels {
-                        channel: "matches".to_string(),
-                        product_ids: vec!["BTC-USD".to_string(), "ETH-USD".to_string()],
+                    events: vec![CoinbaseSubscriptions {
+                        subscriptions: BTreeMap::from([(
+                            "market_trades".to_string(),
+                            vec!["BTC-USD".to_string(), "ETH-USD".to_string()],
+                        )]),
                     }],
                 },
                 is_valid: true,
@@ -149,7 +167,7 @@ mod tests {
             TestCase {
                 // TC1: input response is failed subscription
                 input_response: CoinbaseSubResponse::Error {
-                    reason: "GIBBERISH-USD is not a valid product".to_string(),
+                    message: "Failed to subscribe".to_string(),
                 },
                 is_valid: false,
             },
@@ -0,0 +1,179 @@
+use super::{
+    message::{CoinbaseEventKind, CoinbaseMessage},
+    CoinbaseChannel,
+};
+use crate::{
+    event::{MarketEvent, MarketIter},
+    exchange::ExchangeSub,
+    subscription::ticker::Ticker,
+    Identifier,
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::subscription::SubscriptionId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+/// [`Coinbase`](super::Coinbase) real-time ticker data, timestamped by the exchange, and the
+/// associated [`SubscriptionId`] (eg/ "ticker|BTC-USD").
+///
+/// See [`CoinbaseMessage`] for full raw payload examples.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#ticker-channel>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(try_from = "CoinbaseMessage<CoinbaseTickerEvent>")]
+pub struct CoinbaseTicker {
+    pub subscription_id: SubscriptionId,
+    pub time: DateTime<Utc>,
+    pub ticker: CoinbaseTickerData,
+}
+
+/// [`Coinbase`](super::Coinbase) ticker event, containing either a snapshot or an update of
+/// the latest ticker data.
+///
+/// See [`CoinbaseMessage`] for full raw payload examples.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseTickerEvent {
+    #[serde(rename = "type")]
+    pub kind: CoinbaseEventKind,
+    pub tickers: Vec<CoinbaseTickerData>,
+}
+
+/// [`Coinbase`](super::Coinbase) latest trade price & rolling 24 hour volume.
+///
+/// See [`CoinbaseMessage`] for full raw payload examples.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#ticker-channel>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseTickerData {
+    pub product_id: SmolStr,
+    #[serde(deserialize_with = "barter_integration::de::de_str")]
+    pub price: f64,
+    #[serde(
+        rename = "volume_24_h",
+        deserialize_with = "barter_integration::de::de_str"
+    )]
+    pub volume_24h: f64,
+}
+
+impl TryFrom<CoinbaseMessage<CoinbaseTickerEvent>> for CoinbaseTicker {
+    type Error = String;
+
+    fn try_from(message: CoinbaseMessage<CoinbaseTickerEvent>) -> Result<Self, Self::Error> {
+        let CoinbaseMessage {
+            channel,
+            timestamp,
+            events,
+        } = message;
+
+        let ticker = events
+            .into_iter()
+            .flat_map(|event| event.tickers)
+            .next()
+            .ok_or_else(|| format!("empty Coinbase {channel} message"))?;
+
+        Ok(Self {
+            subscription_id: ExchangeSub::from((CoinbaseChannel::TICKER, &ticker.product_id)).id(),
+            time: timestamp,
+            ticker,
+        })
+    }
+}
+
+impl Identifier<Option<SubscriptionId>> for CoinbaseTicker {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl<InstrumentKey> From<(ExchangeId, InstrumentKey, CoinbaseTicker)>
+    for MarketIter<InstrumentKey, Ticker>
+{
+    fn from(
+        (exchange_id, instrument, ticker): (ExchangeId, InstrumentKey, CoinbaseTicker),
+    ) -> Self {
+        Self(vec![Ok(MarketEvent {
+            time_exchange: ticker.time,
+            time_received: Utc::now(),
+            exchange: exchange_id,
+            instrument,
+            kind: Ticker {
+                last_price: ticker.ticker.price,
+                volume: ticker.ticker.volume_24h,
+                count: None,
+                first_id: None,
+                last_id: None,
+            },
+        })])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_de_coinbase_ticker() {
+        let input = r#"
+        {
+            "channel": "ticker", "client_id": "",
+            "timestamp": "2023-02-09T20:30:37.167359596Z", "sequence_num": 0,
+            "events": [
+                {
+                    "type": "snapshot",
+                    "tickers": [
+                        {
+                            "type": "ticker", "product_id": "BTC-USD", "price": "21932.98",
+                            "volume_24_h": "16038.28770938", "low_24_h": "21835.29",
+                            "high_24_h": "23011.18", "low_52_w": "15460", "high_52_w": "48240",
+                            "price_percent_chg_24_h": "-4.15775596190603",
+                            "best_bid": "21931.98", "best_bid_quantity": "8000.21",
+                            "best_ask": "21933.98", "best_ask_quantity": "8038.07770938"
+                        }
+                    ]
+                }
+            ]
+        }"#;
+
+        let actual = serde_json::from_str::<CoinbaseTicker>(input).unwrap();
+        let expected = CoinbaseTicker {
+            subscription_id: SubscriptionId::from("ticker|BTC-USD"),
+            time: Utc.timestamp_nanos(1675974637167359596),
+            ticker: CoinbaseTickerData {
+                product_id: SmolStr::new("BTC-USD"),
+                price: 21932.98,
+                volume_24h: 16038.28770938,
+            },
+        };
+
+        assert_eq!(actual, expected);
+    }
+
+    #[test]
+    fn test_coinbase_ticker_is_timestamped_by_exchange() {
+        let ticker = CoinbaseTicker {
+            subscription_id: SubscriptionId::from("ticker|BTC-USD"),
+            time: Utc.timestamp_nanos(1675974637167359596),
+            ticker: CoinbaseTickerData {
+                product_id: SmolStr::new("BTC-USD"),
+                price: 21932.98,
+                volume_24h: 16038.28770938,
+            },
+        };
+
+        let MarketIter(events) =
+            MarketIter::<&str, Ticker>::from((ExchangeId::Coinbase, "instrument", ticker));
+
+        let event = events[0].as_ref().unwrap();
+        assert_eq!(
+            event.time_exchange,
+            Utc.timestamp_nanos(1675974637167359596)
+        );
+        assert_eq!(event.kind.last_price, 21932.98);
+        assert_eq!(event.kind.volume, 16038.28770938);
+        assert_eq!(event.kind.count, None);
+        assert_eq!(event.kind.first_id, None);
+        assert_eq!(event.kind.last_id, None);
+    }
+}
@@ -1,4 +1,7 @@
-use super::CoinbaseChannel;
+use super::{
+    message::{CoinbaseEventKind, CoinbaseMessage},
+    CoinbaseChannel,
+};
 use crate::{
     event::{MarketEvent, MarketIter},
     exchange::ExchangeSub,
@@ -9,124 +12,198 @@ use barter_instrument::exchange::ExchangeId;
 use barter_integration::{subscription::SubscriptionId, Side};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
 
-/// Coinbase real-time trade WebSocket message.
+/// Collection of [`CoinbaseTrade`] items with an associated [`SubscriptionId`]
+/// (eg/ "market_trades|BTC-USD").
+///
+/// See [`CoinbaseMessage`] for full raw payload examples.
 ///
-/// ### Raw Payload Examples
-/// See docs: <https://docs.cloud.coinbase.com/exchange/docs/websocket-channels#match>
-/// ```json
-/// {
-///     "type": "match",
-///     "trade_id": 10,
-///     "sequence": 50,
-///     "maker_order_id": "ac928c66-ca53-498f-9c13-a110027a60e8",
-///     "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
-///     "time": "2014-11-07T08:19:27.028459Z",
-///     "product_id": "BTC-USD",
-///     "size": "5.23512",
-///     "price":
-///     "400.23",
-///     "side": "sell"
-/// }
-/// ```
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#market-trades-channel>
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
-pub struct CoinbaseTrade {
-    #[serde(alias = "product_id", deserialize_with = "de_trade_subscription_id")]
+#[serde(try_from = "CoinbaseMessage<CoinbaseTradeEvent>")]
+pub struct CoinbaseTrades {
     pub subscription_id: SubscriptionId,
-    #[serde(alias = "trade_id")]
-    pub id: u64,
+    pub trades: Vec<CoinbaseTrade>,
+}
+
+/// [`Coinbase`](super::Coinbase) market trades event, containing either a snapshot of the most
+/// recent trades, or the trades executed since the previous event.
+///
+/// See [`CoinbaseMessage`] for full raw payload examples.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseTradeEvent {
+    #[serde(rename = "type")]
+    pub kind: CoinbaseEventKind,
+    pub trades: Vec<CoinbaseTrade>,
+}
+
+/// [`Coinbase`](super::Coinbase) trade.
+///
+/// See [`CoinbaseMessage`] for full raw payload examples.
+///
+/// See docs: <https://docs.cdp.coinbase.com/advanced-trade/docs/ws-channels#market-trades-channel>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CoinbaseTrade {
+    pub product_id: SmolStr,
+    #[serde(rename = "trade_id")]
+    pub id: String,
     pub time: DateTime<Utc>,
-    #[serde(alias = "size", deserialize_with = "barter_integration::de::de_str")]
+    #[serde(rename = "size", deserialize_with = "barter_integration::de::de_str")]
     pub amount: f64,
     #[serde(deserialize_with = "barter_integration::de::de_str")]
     pub price: f64,
     pub side: Side,
 }
 
-impl Identifier<Option<SubscriptionId>> for CoinbaseTrade {
+impl TryFrom<CoinbaseMessage<CoinbaseTradeEvent>> for CoinbaseTrades {
+    type Error = String;
+
+    fn try_from(message: CoinbaseMessage<CoinbaseTradeEvent>) -> Result<Self, Self::Error> {
+        let subscription_id = message
+            .events
+            .iter()
+            .flat_map(|event| event.trades.first())
+            .next()
+            .map(|trade| ExchangeSub::from((CoinbaseChannel::TRADES, &trade.product_id)).id())
+            .ok_or_else(|| format!("empty Coinbase {} message", message.channel))?;
+
+        // Snapshots contain historical trades executed before subscribing, so only updates
+        // are streamed
+        let trades = message
+            .events
+            .into_iter()
+            .filter(|event| event.kind == CoinbaseEventKind::Update)
+            .flat_map(|event| event.trades)
+            .collect();
+
+        Ok(Self {
+            subscription_id,
+            trades,
+        })
+    }
+}
+
+impl Identifier<Option<SubscriptionId>> for CoinbaseTrades {
     fn id(&self) -> Option<SubscriptionId> {
         Some(self.subscription_id.clone())
     }
 }
 
-impl<InstrumentKey> From<(ExchangeId, InstrumentKey, CoinbaseTrade)>
+impl<InstrumentKey: Clone> From<(ExchangeId, InstrumentKey, CoinbaseTrades)>
     for MarketIter<InstrumentKey, PublicTrade>
 {
-    fn from((exchange_id, instrument, trade): (ExchangeId, InstrumentKey, CoinbaseTrade)) -> Self {
-        Self(vec![Ok(MarketEvent {
-            time_exchange: trade.time,
-            time_received: Utc::now(),
-            exchange: exchange_id,
-            instrument,
-            kind: PublicTrade {
-                id: trade.id.to_string(),
-                price: trade.price,
-                amount: trade.amount,
-                side: trade.side,
-            },
-        })])
+    fn from(
+        (exchange_id, instrument, trades): (ExchangeId, InstrumentKey, CoinbaseTrades),
+    ) -> Self {
+        trades
+            .trades
+            .into_iter()
+            .map(|trade| {
+                Ok(MarketEvent {
+                    time_exchange: trade.time,
+                    time_received: Utc::now(),
+                    exchange: exchange_id,
+                    instrument: instrument.clone(),
+                    kind: PublicTrade {
+                        id: trade.id,
+                        price: trade.price,
+                        amount: trade.amount,
+                        side: trade.side,
+                    },
+                })
+            })
+            .collect()
     }
 }
 
-/// Deserialize a [`CoinbaseTrade`] "product_id" (eg/ "BTC-USD") as the associated [`SubscriptionId`]
-/// (eg/ SubscriptionId("matches|BTC-USD").
-pub fn de_trade_subscription_id<'de, D>(deserializer: D) -> Result<SubscriptionId, D::Error>
-where
-    D: serde::de::Deserializer<'de>,
-{
-    <&str as Deserialize>::deserialize(deserializer)
-        .map(|product_id| ExchangeSub::from((CoinbaseChannel::TRADES, product_id)).id())
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
     use barter_integration::error::SocketError;
-    use chrono::NaiveDateTime;
+    use chrono::TimeZone;
     use serde::de::Error;
-    use std::str::FromStr;
 
     #[test]
-    fn test_de_coinbase_trade() {
+    fn test_de_coinbase_trades() {
         struct TestCase {
             input: &'static str,
-            expected: Result<CoinbaseTrade, SocketError>,
+            expected: Result<CoinbaseTrades, SocketError>,
         }
 
         let cases = vec![
             TestCase {
-                // TC0: invalid Coinbase message w/ unknown tag
-                input: r#"{"type": "unknown", "sequence": 50,"product_id": "BTC-USD"}"#,
+                // TC0: invalid Coinbase message w/ unknown event type
+                input: r#"
+                {
+                    "channel": "market_trades", "client_id": "",
+                    "timestamp": "2023-02-09T20:19:35.39625135Z", "sequence_num": 0,
+                    "events": [{"type": "unknown", "trades": []}]
+                }"#,
                 expected: Err(SocketError::Deserialise {
                     error: serde_json::Error::custom(""),
                     payload: "".to_owned(),
                 }),
             },
             TestCase {
-                // TC1: valid Spot CoinbaseTrade
+                // TC1: valid Spot CoinbaseTrades update
+                input: r#"
+                {
+                    "channel": "market_trades", "client_id": "",
+                    "timestamp": "2023-02-09T20:19:35.39625135Z", "sequence_num": 1,
+                    "events": [
+                        {
+                            "type": "update",
+                            "trades": [
+                                {
+                                    "trade_id": "10", "product_id": "BTC-USD", "price": "400.23",
+                                    "size": "5.23512", "side": "SELL",
+                                    "time": "2014-11-07T08:19:27.028459Z"
+                                }
+                            ]
+                        }
+                    ]
+                }"#,
+                expected: Ok(CoinbaseTrades {
+                    subscription_id: SubscriptionId::from("market_trades|BTC-USD"),
+                    trades: vec![CoinbaseTrade {
+                        product_id: SmolStr::new("BTC-USD"),
+                        id: "10".to_string(),
+                        price: 400.23,
+                        amount: 5.23512,
+                        side: Side::Sell,
+                        time: Utc.timestamp_micros(1415348367028459).unwrap(),
+                    }],
+                }),
+            },
+            TestCase {
+                // TC2: valid Spot CoinbaseTrades snapshot of historical trades is skipped
                 input: r#"
                 {
-                    "type": "match","trade_id": 10,"sequence": 50,
-                    "maker_order_id": "ac928c66-ca53-498f-9c13-a110027a60e8",
-                    "taker_order_id": "132fb6ae-456b-4654-b4e0-d681ac05cea1",
-                    "time": "2014-11-07T08:19:27.028459Z",
-                    "product_id": "BTC-USD", "size": "5.23512", "price": "400.23", "side": "sell"
+                    "channel": "market_trades", "client_id": "",
+                    "timestamp": "2023-02-09T20:19:35.39625135Z", "sequence_num": 0,
+                    "events": [
+                        {
+                            "type": "snapshot",
+                            "trades": [
+                                {
+                                    "trade_id": "9", "product_id": "BTC-USD", "price": "400.01",
+                                    "size": "0.3", "side": "BUY",
+                                    "time": "2014-11-07T08:19:26.028459Z"
+                                }
+                            ]
+                        }
+                    ]
                 }"#,
-                expected: Ok(CoinbaseTrade {
-                    subscription_id: SubscriptionId::from("matches|BTC-USD"),
-                    id: 10,
-                    price: 400.23,
-                    amount: 5.23512,
-                    side: Side::Sell,
-                    time: NaiveDateTime::from_str("2014-11-07T08:19:27.028459")
-                        .unwrap()
-                        .and_utc(),
+                expected: Ok(CoinbaseTrades {
+                    subscription_id: SubscriptionId::from("market_trades|BTC-USD"),
+                    trades: vec![],
                 }),
             },
         ];
 
         for (index, test) in cases.into_iter().enumerate() {
-            let actual = serde_json::from_str::<CoinbaseTrade>(test.input);
+            let actual = serde_json::from_str::<CoinbaseTrades>(test.input);
             match (actual, test.expected) {
                 (Ok(actual), Ok(expected)) => {
                     assert_eq!(actual, expected, "TC{} failed", index)
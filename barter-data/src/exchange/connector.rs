@@ -0,0 +1,337 @@
+use super::PingInterval;
+use barter_instrument::asset::symbol::Symbol;
+use smol_str::SmolStr;
+
+/// Exchange specific formats a WebSocket [`Connector`](super::Connector) integration is
+/// generated from by the `exchange_connector!` macro.
+///
+/// Most exchanges only differ in their base url, market symbol mapping, subscribe message
+/// format & the raw type of each [`SubscriptionKind`](crate::subscription::SubscriptionKind)
+/// supported. Implementing this trait & invoking `exchange_connector!` generates the
+/// remaining glue:
+/// - Channel type w/ a constant per [`SubscriptionKind`](crate::subscription::SubscriptionKind),
+///   plus it's [`Subscription`](crate::subscription::Subscription)
+///   [`Identifier`](crate::Identifier) implementations.
+/// - Market type, plus it's [`Subscription`](crate::subscription::Subscription)
+///   [`Identifier`](crate::Identifier) implementations for every supported
+///   [`InstrumentData`](crate::instrument::InstrumentData).
+/// - [`Connector`](super::Connector) implementation sending one subscribe message per
+///   [`ExchangeSub`](super::ExchangeSub).
+/// - [`StreamSelector`](super::StreamSelector) implementation per
+///   [`SubscriptionKind`](crate::subscription::SubscriptionKind), transforming the raw type
+///   with a [`StatelessTransformer`](crate::transformer::stateless::StatelessTransformer).
+///
+/// Exchanges that batch subscriptions, require snapshots or maintain stateful transformers
+/// (eg/ OrderBook sequencing) should implement [`Connector`](super::Connector) manually.
+///
+/// ### Examples
+/// - [`Coinbase`](super::coinbase::Coinbase)
+/// - [`Bitstamp`](super::bitstamp::Bitstamp)
+pub trait ExchangeConnector {
+    /// Base url of the exchange WebSocket server.
+    const BASE_URL: &'static str;
+
+    /// Maps an instrument base & quote [`Symbol`] into the exchange market symbol
+    /// (eg/ "BTC-USD").
+    fn market(base: &Symbol, quote: &Symbol) -> SmolStr;
+
+    /// Constructs the subscribe message payload for a single channel & market combination.
+    fn subscribe(channel: &str, market: &str) -> serde_json::Value;
+
+    /// Defines [`PingInterval`] of custom application-level pings for the exchange server.
+    ///
+    /// Defaults to `None`, meaning that no custom pings are sent.
+    fn ping_interval() -> Option<PingInterval> {
+        None
+    }
+}
+
+/// Generates the [`Connector`](super::Connector) integration of an exchange implementing
+/// [`ExchangeConnector`], as described by it's documentation.
+///
+/// ### Examples
+/// ```ignore
+/// exchange_connector! {
+///     exchange: Coinbase,
+///     id: ExchangeId::Coinbase,
+///     channel: CoinbaseChannel,
+///     market: CoinbaseMarket,
+///     sub_response: CoinbaseSubResponse,
+///     kinds: [
+///         /// Coinbase real-time trades channel.
+///         PublicTrades => TRADES("market_trades"): CoinbaseTrades,
+///         /// Coinbase real-time ticker channel.
+///         Tickers => TICKER("ticker"): CoinbaseTicker,
+///     ],
+/// }
+/// ```
+macro_rules! exchange_connector {
+    (
+        exchange: $exchange:ident,
+        id: $id:path,
+        channel: $channel:ident,
+        market: $market:ident,
+        sub_response: $sub_response:ty,
+        kinds: [$(
+            $(#[$kind_meta:meta])*
+            $kind:ty => $constant:ident($channel_name:literal): $raw:ty
+        ),+ $(,)?] $(,)?
+    ) => {
+        #[doc = concat!(
+            "Type that defines how to translate a Barter ",
+            "[`Subscription`](crate::subscription::Subscription) into a [`",
+            stringify!($exchange),
+            "`] channel to be subscribed to."
+        )]
+        #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, serde::Serialize)]
+        pub struct $channel(pub &'static str);
+
+        impl $channel {
+            $(
+                $(#[$kind_meta])*
+                pub const $constant: Self = Self($channel_name);
+            )+
+        }
+
+        $(
+            impl<Instrument> $crate::Identifier<$channel>
+                for $crate::subscription::Subscription<$exchange, Instrument, $kind>
+            {
+                fn id(&self) -> $channel {
+                    $channel::$constant
+                }
+            }
+        )+
+
+        impl AsRef<str> for $channel {
+            fn as_ref(&self) -> &str {
+                self.0
+            }
+        }
+
+        #[doc = concat!(
+            "Type that defines how to translate a Barter ",
+            "[`Subscription`](crate::subscription::Subscription) into a [`",
+            stringify!($exchange),
+            "`] market that can be subscribed to."
+        )]
+        #[derive(
+            Clone,
+            Eq,
+            PartialEq,
+            Ord,
+            PartialOrd,
+            Hash,
+            Debug,
+            serde::Deserialize,
+            serde::Serialize,
+        )]
+        pub struct $market(pub smol_str::SmolStr);
+
+        impl<Kind> $crate::Identifier<$market>
+            for $crate::subscription::Subscription<
+                $exchange,
+                barter_instrument::instrument::Instrument,
+                Kind,
+            >
+        {
+            fn id(&self) -> $market {
+                $market(<$exchange as $crate::exchange::connector::ExchangeConnector>::market(
+                    &self.instrument.base,
+                    &self.instrument.quote,
+                ))
+            }
+        }
+
+        impl<InstrumentKey, Kind> $crate::Identifier<$market>
+            for $crate::subscription::Subscription<
+                $exchange,
+                barter_instrument::Keyed<InstrumentKey, barter_instrument::instrument::Instrument>,
+                Kind,
+            >
+        {
+            fn id(&self) -> $market {
+                $market(<$exchange as $crate::exchange::connector::ExchangeConnector>::market(
+                    &self.instrument.value.base,
+                    &self.instrument.value.quote,
+                ))
+            }
+        }
+
+        impl<Kind> $crate::Identifier<$market>
+            for $crate::subscription::Subscription<
+                $exchange,
+                $crate::instrument::MarketInstrumentData,
+                Kind,
+            >
+        {
+            fn id(&self) -> $market {
+                $market(self.instrument.name_exchange.clone())
+            }
+        }
+
+        impl AsRef<str> for $market {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl $crate::exchange::Connector for $exchange {
+            const ID: barter_instrument::exchange::ExchangeId = $id;
+            type Channel = $channel;
+            type Market = $market;
+            type Subscriber = $crate::subscriber::WebSocketSubscriber;
+            type SubValidator = $crate::subscriber::validator::WebSocketSubValidator;
+            type SubResponse = $sub_response;
+
+            fn url() -> Result<url::Url, barter_integration::error::SocketError> {
+                url::Url::parse(
+                    <$exchange as $crate::exchange::connector::ExchangeConnector>::BASE_URL,
+                )
+                .map_err(barter_integration::error::SocketError::UrlParse)
+            }
+
+            fn ping_interval() -> Option<$crate::exchange::PingInterval> {
+                <$exchange as $crate::exchange::connector::ExchangeConnector>::ping_interval()
+            }
+
+            fn requests(
+                exchange_subs: Vec<$crate::exchange::ExchangeSub<Self::Channel, Self::Market>>,
+            ) -> Vec<barter_integration::protocol::websocket::WsMessage> {
+                exchange_subs
+                    .into_iter()
+                    .map(|$crate::exchange::ExchangeSub { channel, market }| {
+                        let payload = <$exchange as $crate::exchange::connector::ExchangeConnector>
+                            ::subscribe(channel.as_ref(), market.as_ref());
+                        barter_integration::protocol::websocket::WsMessage::Text(
+                            payload.to_string(),
+                        )
+                    })
+                    .collect()
+            }
+        }
+
+        $(
+            impl<Instrument> $crate::exchange::StreamSelector<Instrument, $kind> for $exchange
+            where
+                Instrument: $crate::instrument::InstrumentData,
+            {
+                type SnapFetcher = $crate::NoInitialSnapshots;
+                type Stream = $crate::ExchangeWsStream<
+                    $crate::transformer::stateless::StatelessTransformer<
+                        Self,
+                        Instrument::Key,
+                        $kind,
+                        $raw,
+                    >,
+                >;
+            }
+        )+
+    };
+}
+
+pub(crate) use exchange_connector;
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        exchange::{
+            bitstamp::{trade::BitstampTrade, Bitstamp, BitstampChannel, BitstampMarket},
+            coinbase::{trade::CoinbaseTrades, Coinbase, CoinbaseChannel, CoinbaseMarket},
+            Connector, ExchangeSub,
+        },
+        subscription::{ticker::Tickers, trade::PublicTrades, Subscription},
+        Identifier,
+    };
+    use barter_instrument::instrument::{kind::InstrumentKind, Instrument};
+    use barter_integration::{protocol::websocket::WsMessage, subscription::SubscriptionId};
+    use serde_json::json;
+
+    /// Hand-written [`Coinbase`] subscribe messages, to compare with those generated by the macro.
+    fn coinbase_requests_hand_written(subs: &[(&str, &str)]) -> Vec<WsMessage> {
+        subs.iter()
+            .map(|(channel, market)| {
+                WsMessage::Text(
+                    json!({
+                        "type": "subscribe",
+                        "product_ids": [market],
+                        "channel": channel,
+                    })
+                    .to_string(),
+                )
+            })
+            .collect()
+    }
+
+    fn spot_subscription<Exchange, Kind>(
+        exchange: Exchange,
+        base: &str,
+        quote: &str,
+        kind: Kind,
+    ) -> Subscription<Exchange, Instrument, Kind> {
+        Subscription::new(exchange, (base, quote, InstrumentKind::Spot), kind)
+    }
+
+    #[test]
+    fn macro_generated_connector_subscribe_messages_match_hand_written() {
+        let trades = ExchangeSub::new(&spot_subscription(Coinbase, "btc", "usd", PublicTrades));
+        let tickers = ExchangeSub::new(&spot_subscription(Coinbase, "eth", "usd", Tickers));
+        assert_eq!(trades.id(), SubscriptionId::from("market_trades|BTC-USD"));
+        assert_eq!(tickers.id(), SubscriptionId::from("ticker|ETH-USD"));
+
+        assert_eq!(
+            Coinbase::requests(vec![trades, tickers]),
+            coinbase_requests_hand_written(&[("market_trades", "BTC-USD"), ("ticker", "ETH-USD")])
+        );
+        assert_eq!(
+            Coinbase::url().unwrap().as_str(),
+            "wss://advanced-trade-ws.coinbase.com/"
+        );
+
+        let trades = ExchangeSub::new(&spot_subscription(Bitstamp, "btc", "usd", PublicTrades));
+        assert_eq!(trades.id(), SubscriptionId::from("live_trades|btcusd"));
+        assert_eq!(
+            Bitstamp::requests(vec![trades]),
+            vec![WsMessage::Text(
+                r#"{"data":{"channel":"live_trades_btcusd"},"event":"bts:subscribe"}"#.to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn macro_generated_connector_routes_deserialised_trades_to_subscription() {
+        let coinbase = serde_json::from_str::<CoinbaseTrades>(
+            r#"{
+                "channel": "market_trades", "client_id": "",
+                "timestamp": "2023-02-09T20:19:35.39625135Z", "sequence_num": 1,
+                "events": [{"type": "update", "trades": [{
+                    "trade_id": "10", "product_id": "BTC-USD", "price": "400.23",
+                    "size": "5.23512", "side": "SELL", "time": "2014-11-07T08:19:27.028459Z"
+                }]}]
+            }"#,
+        )
+        .unwrap();
+        let subscription = spot_subscription(Coinbase, "btc", "usd", PublicTrades);
+        assert_eq!(
+            coinbase.id(),
+            Some(ExchangeSub::<CoinbaseChannel, CoinbaseMarket>::new(&subscription).id())
+        );
+
+        let bitstamp = serde_json::from_str::<BitstampTrade>(
+            r#"{
+                "data": {
+                    "id": 311436201, "amount": 0.0125, "price": 37012, "type": 0,
+                    "microtimestamp": "1700000000123456"
+                },
+                "channel": "live_trades_btcusd",
+                "event": "trade"
+            }"#,
+        )
+        .unwrap();
+        let subscription = spot_subscription(Bitstamp, "btc", "usd", PublicTrades);
+        assert_eq!(
+            bitstamp.id(),
+            Some(ExchangeSub::<BitstampChannel, BitstampMarket>::new(&subscription).id())
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use super::Kraken;
 use crate::{
-    subscription::{book::OrderBooksL1, trade::PublicTrades, Subscription},
+    subscription::{book::OrderBooksL1, ticker::Tickers, trade::PublicTrades, Subscription},
     Identifier,
 };
 use serde::Serialize;
@@ -8,20 +8,26 @@ use serde::Serialize;
 /// Type that defines how to translate a Barter [`Subscription`] into a
 /// [`Kraken`](super::Kraken) channel to be subscribed to.
 ///
-/// See docs: <https://docs.kraken.com/websockets/#message-subscribe>
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ticker>
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Serialize)]
 pub struct KrakenChannel(pub &'static str);
 
 impl KrakenChannel {
     /// [`Kraken`] real-time trades channel name.
     ///
-    /// See docs: <https://docs.kraken.com/websockets/#message-subscribe>
+    /// See docs: <https://docs.kraken.com/api/docs/websocket-v2/trade>
     pub const TRADES: Self = Self("trade");
 
-    /// [`Kraken`] real-time OrderBook Level1 (top of books) channel name.
+    /// [`Kraken`] real-time OrderBook Level1 (top of books) channel name, being the ticker
+    /// channel triggered by every best bid & offer change.
     ///
-    /// See docs: <https://docs.kraken.com/websockets/#message-subscribe>
-    pub const ORDER_BOOK_L1: Self = Self("spread");
+    /// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ticker>
+    pub const ORDER_BOOK_L1: Self = Self("ticker");
+
+    /// [`Kraken`] real-time ticker channel name.
+    ///
+    /// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ticker>
+    pub const TICKER: Self = Self("ticker");
 }
 
 impl<Instrument> Identifier<KrakenChannel> for Subscription<Kraken, Instrument, PublicTrades> {
@@ -36,6 +42,12 @@ impl<Instrument> Identifier<KrakenChannel> for Subscription<Kraken, Instrument,
     }
 }
 
+impl<Instrument> Identifier<KrakenChannel> for Subscription<Kraken, Instrument, Tickers> {
+    fn id(&self) -> KrakenChannel {
+        KrakenChannel::TICKER
+    }
+}
+
 impl AsRef<str> for KrakenChannel {
     fn as_ref(&self) -> &str {
         self.0
@@ -38,5 +38,5 @@ impl AsRef<str> for KrakenMarket {
 }
 
 fn kraken_market(base: &Symbol, quote: &Symbol) -> KrakenMarket {
-    KrakenMarket(format_smolstr!("{base}/{quote}").to_lowercase_smolstr())
+    KrakenMarket(format_smolstr!("{base}/{quote}").to_uppercase_smolstr())
 }
@@ -2,71 +2,80 @@ use crate::Identifier;
 use barter_integration::subscription::SubscriptionId;
 use serde::{Deserialize, Serialize};
 
-/// [`Kraken`](super::Kraken) message variants that can be received over
+/// [`Kraken`](super::Kraken) WebSocket v2 message variants that can be received over
 /// [`WebSocket`](barter_integration::protocol::websocket::WebSocket).
 ///
 /// ### Raw Payload Examples
-/// See docs: <https://docs.kraken.com/websockets/#overview>
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/add_order>
 ///
-/// #### OrderBookL1
-/// See docs: <https://docs.kraken.com/websockets/#message-spread>
+/// #### Ticker & OrderBookL1
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ticker>
 /// ```json
-/// [
-///     0,
-///     [
-///         "5698.40000",
-///         "5700.00000",
-///         "1542057299.545897",
-///         "1.01234567",
-///         "0.98765432"
-///     ],
-///     "spread",
-///     "XBT/USD"
-/// ]
+/// {
+///     "channel": "ticker",
+///     "type": "update",
+///     "data": [
+///         {
+///             "symbol": "BTC/USD",
+///             "bid": 63480.1,
+///             "bid_qty": 0.56138406,
+///             "ask": 63480.2,
+///             "ask_qty": 2.49730339,
+///             "last": 63480.2,
+///             "volume": 1795.15914335,
+///             "vwap": 63294.6,
+///             "low": 62400.0,
+///             "high": 64100.0,
+///             "change": 880.2,
+///             "change_pct": 1.41,
+///             "timestamp": "2024-05-13T15:01:20.157282Z"
+///         }
+///     ]
+/// }
 /// ```
 ///
 /// #### Trades
-/// See docs: <https://docs.kraken.com/websockets/#message-trade>
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/trade>
 /// ```json
-/// [
-///     0,
-///     [
-///         [
-///             "5541.20000",
-///             "0.15850568",
-///             "1534614057.321597",
-///             "s",
-///             "l",
-///             ""
-///         ],
-///         [
-///         "6060.00000",
-///         "0.02455000",
-///         "1534614057.324998",
-///         "b",
-///         "l",
-///         ""
-///         ]
-///     ],
-///     "trade",
-///     "XBT/USD"
-/// ]
+/// {
+///     "channel": "trade",
+///     "type": "update",
+///     "data": [
+///         {
+///             "symbol": "MATIC/USD",
+///             "side": "sell",
+///             "price": 0.5117,
+///             "qty": 40.0,
+///             "ord_type": "market",
+///             "trade_id": 4665906,
+///             "timestamp": "2023-09-25T07:49:37.708706Z"
+///         }
+///     ]
+/// }
 /// ```
 ///
 /// #### Heartbeat
-/// See docs: <https://docs.kraken.com/websockets/#message-heartbeat>
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/heartbeat>
 /// ```json
 /// {
-///   "event": "heartbeat"
+///   "channel": "heartbeat"
 /// }
 /// ```
 ///
-/// #### KrakenError Generic
-/// See docs: <https://docs.kraken.com/websockets/#errortypes>
+/// #### Status
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/status>
 /// ```json
 /// {
-///     "errorMessage": "Malformed request",
-///     "event": "error"
+///     "channel": "status",
+///     "type": "update",
+///     "data": [
+///         {
+///             "api_version": "v2",
+///             "connection_id": 12393906104898154000,
+///             "system": "online",
+///             "version": "2.0.0"
+///         }
+///     ]
 /// }
 /// ```
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Deserialize, Serialize)]
@@ -88,34 +97,55 @@ where
     }
 }
 
+/// [`Kraken`](super::Kraken) WebSocket v2 channel data message, containing the channel name &
+/// it's data items (eg/ [`KrakenTrade`](super::trade::KrakenTrade)s).
+///
+/// See [`KrakenMessage`] for full raw payload examples.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Deserialize, Serialize)]
+pub struct KrakenData<T> {
+    pub channel: String,
+    pub data: Vec<T>,
+}
+
+impl<T> KrakenData<T> {
+    /// Returns the [`SubscriptionId`] (eg/ "ticker|BTC/USD") of the data items, which always
+    /// belong to a single symbol, along with the data items.
+    pub fn into_subscription_data<F>(self, symbol: F) -> Result<(SubscriptionId, Vec<T>), String>
+    where
+        F: Fn(&T) -> &str,
+    {
+        let first = self
+            .data
+            .first()
+            .ok_or_else(|| format!("empty Kraken {} data", self.channel))?;
+        let subscription_id = SubscriptionId::from(format!("{}|{}", self.channel, symbol(first)));
+
+        Ok((subscription_id, self.data))
+    }
+}
+
 /// [`Kraken`](super::Kraken) messages received over the WebSocket which are not subscription data.
 ///
-/// eg/ [`Kraken`](super::Kraken) sends a [`KrakenEvent::Heartbeat`] if no subscription traffic
-/// has been sent within the last second.
+/// eg/ [`Kraken`](super::Kraken) sends a [`KrakenEvent::Heartbeat`] every second if no
+/// subscription traffic has been sent.
 ///
 /// See [`KrakenMessage`] for full raw payload examples.
 ///
-/// See docs: <https://docs.kraken.com/websockets/#message-heartbeat>
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/heartbeat>
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
-#[serde(tag = "event", rename_all = "camelCase")]
+#[serde(tag = "channel", rename_all = "snake_case")]
 pub enum KrakenEvent {
     Heartbeat,
-    Error(KrakenError),
+    Status,
 }
 
-/// [`Kraken`](super::Kraken) generic error message String received over the WebSocket.
+/// [`Kraken`](super::Kraken) error message String received over the WebSocket in response to a
+/// request (eg/ a [`KrakenSubResponse::Error`](super::subscription::KrakenSubResponse)).
 ///
-/// Note that since the [`KrakenError`] is only made up of a renamed message String field, it can
-/// be used flexible as a [`KrakenSubResponse::Error`](super::subscription::KrakenSubResponse)
-/// or as a generic error received over the WebSocket while subscriptions are active.
-///
-/// See [`KrakenMessage`] for full raw payload examples.
-///
-/// See docs: <https://docs.kraken.com/websockets/#errortypes> <br>
-/// See docs: <https://docs.kraken.com/websockets/#message-subscriptionStatus>
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/subscribe>
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub struct KrakenError {
-    #[serde(alias = "errorMessage")]
+    #[serde(rename = "error")]
     pub message: String,
 }
 
@@ -136,16 +166,35 @@ mod tests {
 
             let tests = vec![
                 TestCase {
-                    // TC0: valid KrakenTrades::Event(KrakenEvent::Heartbeat)
-                    input: r#"{"event": "heartbeat"}"#,
+                    // TC0: valid KrakenMessage::Event(KrakenEvent::Heartbeat)
+                    input: r#"{"channel": "heartbeat"}"#,
                     expected: Ok(KrakenMessage::Event(KrakenEvent::Heartbeat)),
                 },
                 TestCase {
-                    // TC1: valid KrakenTrades::Event(KrakenEvent::Error(KrakenError))
-                    input: r#"{"errorMessage": "Malformed request", "event": "error"}"#,
-                    expected: Ok(KrakenMessage::Event(KrakenEvent::Error(KrakenError {
-                        message: "Malformed request".to_string(),
-                    }))),
+                    // TC1: valid KrakenMessage::Event(KrakenEvent::Status)
+                    input: r#"
+                    {
+                        "channel": "status",
+                        "type": "update",
+                        "data": [
+                            {
+                                "api_version": "v2",
+                                "connection_id": 12393906104898154000,
+                                "system": "online",
+                                "version": "2.0.0"
+                            }
+                        ]
+                    }
+                    "#,
+                    expected: Ok(KrakenMessage::Event(KrakenEvent::Status)),
+                },
+                TestCase {
+                    // TC2: invalid KrakenMessage from unknown channel
+                    input: r#"{"channel": "unknown"}"#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "".to_string(),
+                        item: "".to_string(),
+                    }),
                 },
             ];
 
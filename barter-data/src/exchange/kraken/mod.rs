@@ -1,12 +1,13 @@
 use self::{
     book::l1::KrakenOrderBookL1, channel::KrakenChannel, market::KrakenMarket,
-    message::KrakenMessage, subscription::KrakenSubResponse, trade::KrakenTrades,
+    message::KrakenMessage, subscription::KrakenSubResponse, ticker::KrakenTicker,
+    trade::KrakenTrades,
 };
 use crate::{
     exchange::{Connector, ExchangeSub, StreamSelector},
     instrument::InstrumentData,
     subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::{book::OrderBooksL1, trade::PublicTrades},
+    subscription::{book::OrderBooksL1, ticker::Tickers, trade::PublicTrades},
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream, NoInitialSnapshots,
 };
@@ -34,17 +35,20 @@ pub mod message;
 /// [`Validator`](barter_integration) for [`Kraken`].
 pub mod subscription;
 
+/// Ticker types for [`Kraken`].
+pub mod ticker;
+
 /// Public trade types for [`Kraken`].
 pub mod trade;
 
-/// [`Kraken`] server base url.
+/// [`Kraken`] WebSocket v2 server base url.
 ///
-/// See docs: <https://docs.kraken.com/websockets/#overview>
-pub const BASE_URL_KRAKEN: &str = "wss://ws.kraken.com/";
+/// See docs: <https://docs.kraken.com/api/docs/guides/spot-ws-intro>
+pub const BASE_URL_KRAKEN: &str = "wss://ws.kraken.com/v2";
 
 /// [`Kraken`] exchange.
 ///
-/// See docs: <https://docs.kraken.com/websockets/#overview>
+/// See docs: <https://docs.kraken.com/api/docs/guides/spot-ws-intro>
 #[derive(
     Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, DeExchange, SerExchange,
 )]
@@ -66,13 +70,23 @@ impl Connector for Kraken {
         exchange_subs
             .into_iter()
             .map(|ExchangeSub { channel, market }| {
+                let mut params = json!({
+                    "channel": channel.as_ref(),
+                    "symbol": [market.as_ref()],
+                });
+
+                // Tickers update with every best bid & offer change, providing OrderBookL1s,
+                // and trades are streamed without a snapshot of the most recent trades
+                if channel == KrakenChannel::TICKER {
+                    params["event_trigger"] = json!("bbo");
+                } else if channel == KrakenChannel::TRADES {
+                    params["snapshot"] = json!(false);
+                }
+
                 WsMessage::Text(
                     json!({
-                        "event": "subscribe",
-                        "pair": [market.as_ref()],
-                        "subscription": {
-                            "name": channel.as_ref()
-                        }
+                        "method": "subscribe",
+                        "params": params,
                     })
                     .to_string(),
                 )
@@ -99,3 +113,57 @@ where
         StatelessTransformer<Self, Instrument::Key, OrderBooksL1, KrakenOrderBookL1>,
     >;
 }
+
+impl<Instrument> StreamSelector<Instrument, Tickers> for Kraken
+where
+    Instrument: InstrumentData,
+{
+    type SnapFetcher = NoInitialSnapshots;
+    type Stream =
+        ExchangeWsStream<StatelessTransformer<Self, Instrument::Key, Tickers, KrakenTicker>>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exchange::kraken::market::KrakenMarket;
+    use smol_str::SmolStr;
+
+    #[test]
+    fn test_kraken_requests() {
+        let requests = Kraken::requests(vec![
+            ExchangeSub::from((KrakenChannel::TICKER, KrakenMarket(SmolStr::new("BTC/USD")))),
+            ExchangeSub::from((KrakenChannel::TRADES, KrakenMarket(SmolStr::new("ETH/USD")))),
+        ]);
+
+        let requests = requests
+            .into_iter()
+            .map(|request| match request {
+                WsMessage::Text(text) => serde_json::from_str::<serde_json::Value>(&text).unwrap(),
+                other => panic!("unexpected request: {other:?}"),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            requests,
+            vec![
+                json!({
+                    "method": "subscribe",
+                    "params": {
+                        "channel": "ticker",
+                        "symbol": ["BTC/USD"],
+                        "event_trigger": "bbo"
+                    }
+                }),
+                json!({
+                    "method": "subscribe",
+                    "params": {
+                        "channel": "trade",
+                        "symbol": ["ETH/USD"],
+                        "snapshot": false
+                    }
+                }),
+            ]
+        );
+    }
+}
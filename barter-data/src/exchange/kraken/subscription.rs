@@ -5,46 +5,50 @@ use serde::{Deserialize, Serialize};
 /// [`Kraken`](super::Kraken) message received in response to WebSocket subscription requests.
 ///
 /// ### Raw Payload Examples
-/// See docs: <https://docs.kraken.com/websockets/#message-subscriptionStatus>
-/// #### Subscription Trade Success
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ticker>
+/// #### Subscription Ticker Success
 /// ```json
 /// {
-///   "channelID": 10001,
-///   "channelName": "ticker",
-///   "event": "subscriptionStatus",
-///   "pair": "XBT/EUR",
-///   "status": "subscribed",
-///   "subscription": {
-///     "name": "ticker"
-///   }
+///   "method": "subscribe",
+///   "result": {
+///     "channel": "ticker",
+///     "event_trigger": "bbo",
+///     "snapshot": true,
+///     "symbol": "BTC/USD"
+///   },
+///   "success": true,
+///   "time_in": "2023-09-25T09:04:31.742599Z",
+///   "time_out": "2023-09-25T09:04:31.742648Z"
 /// }
 /// ```
 ///
-/// #### Subscription Trade Failure
+/// #### Subscription Ticker Failure
 /// ```json
 /// {
-///   "errorMessage": "Subscription name invalid",
-///   "event": "subscriptionStatus",
-///   "pair": "XBT/USD",
-///   "status": "error",
-///   "subscription": {
-///     "name": "trades"
-///   }
+///   "error": "Currency pair not supported BTC/USDX",
+///   "method": "subscribe",
+///   "success": false,
+///   "symbol": "BTC/USDX",
+///   "time_in": "2023-09-25T09:04:31.742599Z",
+///   "time_out": "2023-09-25T09:04:31.742648Z"
 /// }
 /// ```
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
-#[serde(tag = "status", rename_all = "camelCase")]
+#[serde(untagged)]
 pub enum KrakenSubResponse {
-    Subscribed {
-        #[serde(alias = "channelID")]
-        channel_id: u64,
-        #[serde(alias = "channelName")]
-        channel_name: String,
-        pair: String,
-    },
+    Subscribed { result: KrakenSubResult },
     Error(KrakenError),
 }
 
+/// Channel & symbol of a successful [`Kraken`](super::Kraken) subscription.
+///
+/// See [`KrakenSubResponse`] for full raw payload examples.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct KrakenSubResult {
+    pub channel: String,
+    pub symbol: String,
+}
+
 impl Validator for KrakenSubResponse {
     fn validate(self) -> Result<Self, SocketError>
     where
@@ -79,39 +83,55 @@ mod tests {
                     // TC0: input response is Subscribed
                     input: r#"
                     {
-                        "channelID": 10001,
-                        "channelName": "ticker",
-                        "event": "subscriptionStatus",
-                        "pair": "XBT/EUR",
-                        "status": "subscribed",
-                        "subscription": {
-                            "name": "ticker"
-                        }
+                        "method": "subscribe",
+                        "result": {
+                            "channel": "ticker",
+                            "event_trigger": "bbo",
+                            "snapshot": true,
+                            "symbol": "BTC/USD"
+                        },
+                        "success": true,
+                        "time_in": "2023-09-25T09:04:31.742599Z",
+                        "time_out": "2023-09-25T09:04:31.742648Z"
                     }
                     "#,
                     expected: Ok(KrakenSubResponse::Subscribed {
-                        channel_id: 10001,
-                        channel_name: "ticker".to_string(),
-                        pair: "XBT/EUR".to_string(),
+                        result: KrakenSubResult {
+                            channel: "ticker".to_string(),
+                            symbol: "BTC/USD".to_string(),
+                        },
                     }),
                 },
                 TestCase {
                     // TC1: input response is failed subscription
                     input: r#"
                     {
-                        "errorMessage": "Subscription name invalid",
-                        "event": "subscriptionStatus",
-                        "pair": "XBT/USD",
-                        "status": "error",
-                        "subscription": {
-                            "name": "trades"
-                        }
+                        "error": "Currency pair not supported BTC/USDX",
+                        "method": "subscribe",
+                        "success": false,
+                        "symbol": "BTC/USDX",
+                        "time_in": "2023-09-25T09:04:31.742599Z",
+                        "time_out": "2023-09-25T09:04:31.742648Z"
                     }
                     "#,
                     expected: Ok(KrakenSubResponse::Error(KrakenError {
-                        message: "Subscription name invalid".to_string(),
+                        message: "Currency pair not supported BTC/USDX".to_string(),
                     })),
                 },
+                TestCase {
+                    // TC2: input status message is not a subscription response
+                    input: r#"
+                    {
+                        "channel": "status",
+                        "type": "update",
+                        "data": [{"api_version": "v2", "system": "online", "version": "2.0.0"}]
+                    }
+                    "#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "".to_string(),
+                        item: "".to_string(),
+                    }),
+                },
             ];
 
             for (index, test) in cases.into_iter().enumerate() {
@@ -143,16 +163,17 @@ mod tests {
             TestCase {
                 // TC0: input response is successful subscription
                 input_response: KrakenSubResponse::Subscribed {
-                    channel_id: 10001,
-                    channel_name: "ticker".to_string(),
-                    pair: "XBT/EUR".to_string(),
+                    result: KrakenSubResult {
+                        channel: "ticker".to_string(),
+                        symbol: "BTC/USD".to_string(),
+                    },
                 },
                 is_valid: true,
             },
             TestCase {
                 // TC1: input response is failed subscription
                 input_response: KrakenSubResponse::Error(KrakenError {
-                    message: "Subscription name invalid".to_string(),
+                    message: "Currency pair not supported BTC/USDX".to_string(),
                 }),
                 is_valid: false,
             },
@@ -0,0 +1,198 @@
+use super::{message::KrakenData, KrakenMessage};
+use crate::{
+    event::{MarketEvent, MarketIter},
+    subscription::ticker::Ticker,
+    Identifier,
+};
+use barter_instrument::exchange::ExchangeId;
+use barter_integration::subscription::SubscriptionId;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
+
+/// Terse type alias for an [`Kraken`](super::Kraken) real-time ticker WebSocket message.
+pub type KrakenTicker = KrakenMessage<KrakenTickerInner>;
+
+/// [`Kraken`](super::Kraken) real-time ticker data and the associated [`SubscriptionId`]
+/// (eg/ "ticker|BTC/USD").
+///
+/// See [`KrakenMessage`](super::message::KrakenMessage) for full raw payload examples.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ticker>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(try_from = "KrakenData<KrakenTickerData>")]
+pub struct KrakenTickerInner {
+    pub subscription_id: SubscriptionId,
+    pub ticker: KrakenTickerData,
+}
+
+/// [`Kraken`](super::Kraken) best bid & offer, and rolling 24 hour ticker statistics.
+///
+/// See [`KrakenMessage`](super::message::KrakenMessage) for full raw payload examples.
+///
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/ticker>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KrakenTickerData {
+    pub symbol: SmolStr,
+    #[serde(rename = "bid")]
+    pub best_bid_price: Decimal,
+    #[serde(rename = "bid_qty")]
+    pub best_bid_amount: Decimal,
+    #[serde(rename = "ask")]
+    pub best_ask_price: Decimal,
+    #[serde(rename = "ask_qty")]
+    pub best_ask_amount: Decimal,
+    #[serde(rename = "last")]
+    pub last_price: f64,
+    #[serde(rename = "volume")]
+    pub volume_24h: f64,
+    #[serde(rename = "timestamp")]
+    pub time: DateTime<Utc>,
+}
+
+impl TryFrom<KrakenData<KrakenTickerData>> for KrakenTickerInner {
+    type Error = String;
+
+    fn try_from(data: KrakenData<KrakenTickerData>) -> Result<Self, Self::Error> {
+        let (subscription_id, data) = data.into_subscription_data(|ticker| &ticker.symbol)?;
+        let ticker = data
+            .into_iter()
+            .next()
+            .ok_or_else(|| "empty Kraken ticker data".to_string())?;
+
+        Ok(Self {
+            subscription_id,
+            ticker,
+        })
+    }
+}
+
+impl Identifier<Option<SubscriptionId>> for KrakenTickerInner {
+    fn id(&self) -> Option<SubscriptionId> {
+        Some(self.subscription_id.clone())
+    }
+}
+
+impl<InstrumentKey> From<(ExchangeId, InstrumentKey, KrakenTicker)>
+    for MarketIter<InstrumentKey, Ticker>
+{
+    fn from((exchange_id, instrument, ticker): (ExchangeId, InstrumentKey, KrakenTicker)) -> Self {
+        match ticker {
+            KrakenTicker::Data(ticker) => Self(vec![Ok(MarketEvent {
+                time_exchange: ticker.ticker.time,
+                time_received: Utc::now(),
+                exchange: exchange_id,
+                instrument,
+                kind: Ticker {
+                    last_price: ticker.ticker.last_price,
+                    volume: ticker.ticker.volume_24h,
+                    count: None,
+                    first_id: None,
+                    last_id: None,
+                },
+            })]),
+            KrakenTicker::Event(_) => MarketIter(vec![]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+        use crate::exchange::kraken::message::KrakenEvent;
+        use chrono::TimeZone;
+        use rust_decimal_macros::dec;
+
+        #[test]
+        fn test_kraken_message_ticker() {
+            let input = r#"
+            {
+                "channel": "ticker",
+                "type": "update",
+                "data": [
+                    {
+                        "symbol": "BTC/USD",
+                        "bid": 63480.1,
+                        "bid_qty": 0.56138406,
+                        "ask": 63480.2,
+                        "ask_qty": 2.49730339,
+                        "last": 63480.2,
+                        "volume": 1795.15914335,
+                        "vwap": 63294.6,
+                        "low": 62400.0,
+                        "high": 64100.0,
+                        "change": 880.2,
+                        "change_pct": 1.41,
+                        "timestamp": "2024-05-13T15:01:20.157282Z"
+                    }
+                ]
+            }
+            "#;
+
+            assert_eq!(
+                serde_json::from_str::<KrakenTicker>(input).unwrap(),
+                KrakenTicker::Data(KrakenTickerInner {
+                    subscription_id: SubscriptionId::from("ticker|BTC/USD"),
+                    ticker: KrakenTickerData {
+                        symbol: SmolStr::new("BTC/USD"),
+                        best_bid_price: dec!(63480.1),
+                        best_bid_amount: dec!(0.56138406),
+                        best_ask_price: dec!(63480.2),
+                        best_ask_amount: dec!(2.49730339),
+                        last_price: 63480.2,
+                        volume_24h: 1795.15914335,
+                        time: Utc.timestamp_micros(1715612480157282).unwrap(),
+                    },
+                })
+            );
+
+            assert_eq!(
+                serde_json::from_str::<KrakenTicker>(r#"{"channel": "heartbeat"}"#).unwrap(),
+                KrakenTicker::Event(KrakenEvent::Heartbeat)
+            );
+        }
+
+        #[test]
+        fn test_kraken_ticker_is_timestamped_by_exchange() {
+            let ticker = serde_json::from_str::<KrakenTicker>(
+                r#"
+                {
+                    "channel": "ticker",
+                    "type": "snapshot",
+                    "data": [
+                        {
+                            "symbol": "BTC/USD",
+                            "bid": 63480.1,
+                            "bid_qty": 0.5,
+                            "ask": 63480.2,
+                            "ask_qty": 2.5,
+                            "last": 63480.2,
+                            "volume": 1795.1,
+                            "timestamp": "2024-05-13T15:01:20.157282Z"
+                        }
+                    ]
+                }
+                "#,
+            )
+            .unwrap();
+
+            let MarketIter(events) =
+                MarketIter::<&str, Ticker>::from((ExchangeId::Kraken, "instrument", ticker));
+
+            let event = events[0].as_ref().unwrap();
+            assert_eq!(
+                event.time_exchange,
+                Utc.timestamp_micros(1715612480157282).unwrap()
+            );
+            assert_eq!(event.kind.last_price, 63480.2);
+            assert_eq!(event.kind.volume, 1795.1);
+            assert_eq!(event.kind.count, None);
+            assert_eq!(event.kind.first_id, None);
+            assert_eq!(event.kind.last_id, None);
+        }
+    }
+}
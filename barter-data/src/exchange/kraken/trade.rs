@@ -1,27 +1,25 @@
-use super::KrakenMessage;
+use super::{message::KrakenData, KrakenMessage};
 use crate::{
     event::{MarketEvent, MarketIter},
     subscription::trade::PublicTrade,
     Identifier,
 };
 use barter_instrument::exchange::ExchangeId;
-use barter_integration::{
-    de::{datetime_utc_from_epoch_duration, extract_next},
-    subscription::SubscriptionId,
-    Side,
-};
+use barter_integration::{subscription::SubscriptionId, Side};
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use smol_str::SmolStr;
 
 /// Terse type alias for an [`Kraken`](super::Kraken) real-time trades WebSocket message.
 pub type KrakenTrades = KrakenMessage<KrakenTradesInner>;
 
-/// Collection of [`KrakenTrade`] items with an associated [`SubscriptionId`] (eg/ "trade|XBT/USD").
+/// Collection of [`KrakenTrade`] items with an associated [`SubscriptionId`] (eg/ "trade|BTC/USD").
 ///
 /// See [`KrakenMessage`](super::message::KrakenMessage) for full raw payload examples.
 ///
-/// See docs: <https://docs.kraken.com/websockets/#message-trade>
-#[derive(Clone, PartialEq, PartialOrd, Debug, Serialize)]
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/trade>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+#[serde(try_from = "KrakenData<KrakenTrade>")]
 pub struct KrakenTradesInner {
     pub subscription_id: SubscriptionId,
     pub trades: Vec<KrakenTrade>,
@@ -31,14 +29,29 @@ pub struct KrakenTradesInner {
 ///
 /// See [`KrakenMessage`](super::message::KrakenMessage) for full raw payload examples.
 ///
-/// See docs: <https://docs.kraken.com/websockets/#message-trade>
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Serialize)]
+/// See docs: <https://docs.kraken.com/api/docs/websocket-v2/trade>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct KrakenTrade {
+    pub symbol: SmolStr,
+    pub side: Side,
     pub price: f64,
-    #[serde(rename = "quantity")]
+    #[serde(rename = "qty")]
     pub amount: f64,
+    pub trade_id: u64,
+    #[serde(rename = "timestamp")]
     pub time: DateTime<Utc>,
-    pub side: Side,
+}
+
+impl TryFrom<KrakenData<KrakenTrade>> for KrakenTradesInner {
+    type Error = String;
+
+    fn try_from(data: KrakenData<KrakenTrade>) -> Result<Self, Self::Error> {
+        data.into_subscription_data(|trade| &trade.symbol)
+            .map(|(subscription_id, trades)| Self {
+                subscription_id,
+                trades,
+            })
+    }
 }
 
 impl Identifier<Option<SubscriptionId>> for KrakenTradesInner {
@@ -47,18 +60,6 @@ impl Identifier<Option<SubscriptionId>> for KrakenTradesInner {
     }
 }
 
-/// Generate a custom [`Kraken`](super::Kraken) trade identifier since it is not provided in the
-/// [`KrakenTrade`] model.
-fn custom_kraken_trade_id(trade: &KrakenTrade) -> String {
-    format!(
-        "{}_{}_{}_{}",
-        trade.time.timestamp_micros(),
-        trade.side,
-        trade.price,
-        trade.amount
-    )
-}
-
 impl<InstrumentKey: Clone> From<(ExchangeId, InstrumentKey, KrakenTrades)>
     for MarketIter<InstrumentKey, PublicTrade>
 {
@@ -74,7 +75,7 @@ impl<InstrumentKey: Clone> From<(ExchangeId, InstrumentKey, KrakenTrades)>
                         exchange,
                         instrument: instrument.clone(),
                         kind: PublicTrade {
-                            id: custom_kraken_trade_id(&trade),
+                            id: trade.trade_id.to_string(),
                             price: trade.price,
                             amount: trade.amount,
                             side: trade.side,
@@ -87,134 +88,15 @@ impl<InstrumentKey: Clone> From<(ExchangeId, InstrumentKey, KrakenTrades)>
     }
 }
 
-impl<'de> serde::de::Deserialize<'de> for KrakenTradesInner {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        struct SeqVisitor;
-
-        impl<'de> serde::de::Visitor<'de> for SeqVisitor {
-            type Value = KrakenTradesInner;
-
-            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("KrakenTradesInner struct from the Kraken WebSocket API")
-            }
-
-            fn visit_seq<SeqAccessor>(
-                self,
-                mut seq: SeqAccessor,
-            ) -> Result<Self::Value, SeqAccessor::Error>
-            where
-                SeqAccessor: serde::de::SeqAccess<'de>,
-            {
-                // KrakenTrades Sequence Format:
-                // [channelID, [[price, volume, time, side, orderType, misc]], channelName, pair]
-                // <https://docs.kraken.com/websockets/#message-trade>
-
-                // Extract deprecated channelID & ignore
-                let _: serde::de::IgnoredAny = extract_next(&mut seq, "channelID")?;
-
-                // Extract Vec<KrakenTrade>
-                let trades = extract_next(&mut seq, "Vec<KrakenTrade>")?;
-
-                // Extract channelName (eg/ "trade") & ignore
-                let _: serde::de::IgnoredAny = extract_next(&mut seq, "channelName")?;
-
-                // Extract pair (eg/ "XBT/USD") & map to SubscriptionId (ie/ "trade|{pair}")
-                let subscription_id = extract_next::<SeqAccessor, String>(&mut seq, "pair")
-                    .map(|pair| SubscriptionId::from(format!("trade|{pair}")))?;
-
-                // Ignore any additional elements or SerDe will fail
-                //  '--> Exchange may add fields without warning
-                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
-
-                Ok(KrakenTradesInner {
-                    subscription_id,
-                    trades,
-                })
-            }
-        }
-
-        // Use Visitor implementation to deserialise the KrakenTrades
-        deserializer.deserialize_seq(SeqVisitor)
-    }
-}
-
-impl<'de> serde::de::Deserialize<'de> for KrakenTrade {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::de::Deserializer<'de>,
-    {
-        struct SeqVisitor;
-
-        impl<'de> serde::de::Visitor<'de> for SeqVisitor {
-            type Value = KrakenTrade;
-
-            fn expecting(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-                formatter.write_str("KrakenTrade struct from the Kraken WebSocket API")
-            }
-
-            fn visit_seq<SeqAccessor>(
-                self,
-                mut seq: SeqAccessor,
-            ) -> Result<Self::Value, SeqAccessor::Error>
-            where
-                SeqAccessor: serde::de::SeqAccess<'de>,
-            {
-                // KrakenTrade Sequence Format:
-                // [price, volume, time, side, orderType, misc]
-                // <https://docs.kraken.com/websockets/#message-trade>
-
-                // Extract String price & parse to f64
-                let price = extract_next::<SeqAccessor, String>(&mut seq, "price")?
-                    .parse()
-                    .map_err(serde::de::Error::custom)?;
-
-                // Extract String amount & parse to f64
-                let amount = extract_next::<SeqAccessor, String>(&mut seq, "quantity")?
-                    .parse()
-                    .map_err(serde::de::Error::custom)?;
-
-                // Extract String price, parse to f64, map to DateTime<Utc>
-                let time = extract_next::<SeqAccessor, String>(&mut seq, "time")?
-                    .parse()
-                    .map(|time| {
-                        datetime_utc_from_epoch_duration(std::time::Duration::from_secs_f64(time))
-                    })
-                    .map_err(serde::de::Error::custom)?;
-
-                // Extract Side
-                let side: Side = extract_next(&mut seq, "side")?;
-
-                // Ignore any additional elements or SerDe will fail
-                //  '--> Exchange may add fields without warning
-                while seq.next_element::<serde::de::IgnoredAny>()?.is_some() {}
-
-                Ok(KrakenTrade {
-                    price,
-                    amount,
-                    time,
-                    side,
-                })
-            }
-        }
-
-        // Use Visitor implementation to deserialise the KrakenTrade
-        deserializer.deserialize_seq(SeqVisitor)
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
     mod de {
         use super::*;
-        use barter_integration::{
-            de::datetime_utc_from_epoch_duration, error::SocketError, subscription::SubscriptionId,
-            Side,
-        };
+        use crate::exchange::kraken::message::KrakenEvent;
+        use barter_integration::error::SocketError;
+        use chrono::TimeZone;
 
         #[test]
         fn test_kraken_message_trades() {
@@ -223,55 +105,71 @@ mod tests {
                 expected: Result<KrakenTrades, SocketError>,
             }
 
-            let tests = vec![TestCase {
-                // TC0: valid KrakenTrades::Data(KrakenTradesInner)
-                input: r#"
-                    [
-                        0,
-                        [
-                            [
-                                "5541.20000",
-                                "0.15850568",
-                                "1534614057.321597",
-                                "s",
-                                "l",
-                                ""
-                            ],
-                            [
-                                "6060.00000",
-                                "0.02455000",
-                                "1534614057.324998",
-                                "b",
-                                "l",
-                                ""
-                            ]
-                        ],
-                      "trade",
-                      "XBT/USD"
-                    ]
+            let tests = vec![
+                TestCase {
+                    // TC0: valid KrakenTrades::Data(KrakenTradesInner)
+                    input: r#"
+                    {
+                        "channel": "trade",
+                        "type": "update",
+                        "data": [
+                            {
+                                "symbol": "BTC/USD",
+                                "side": "sell",
+                                "price": 5541.2,
+                                "qty": 0.15850568,
+                                "ord_type": "limit",
+                                "trade_id": 4665906,
+                                "timestamp": "2018-08-18T17:40:57.321597Z"
+                            },
+                            {
+                                "symbol": "BTC/USD",
+                                "side": "buy",
+                                "price": 6060.0,
+                                "qty": 0.02455,
+                                "ord_type": "market",
+                                "trade_id": 4665907,
+                                "timestamp": "2018-08-18T17:40:57.324998Z"
+                            }
+                        ]
+                    }
                     "#,
-                expected: Ok(KrakenTrades::Data(KrakenTradesInner {
-                    subscription_id: SubscriptionId::from("trade|XBT/USD"),
-                    trades: vec![
-                        KrakenTrade {
-                            price: 5541.2,
-                            amount: 0.15850568,
-                            time: datetime_utc_from_epoch_duration(
-                                std::time::Duration::from_secs_f64(1534614057.321597),
-                            ),
-                            side: Side::Sell,
-                        },
-                        KrakenTrade {
-                            price: 6060.0,
-                            amount: 0.02455000,
-                            time: datetime_utc_from_epoch_duration(
-                                std::time::Duration::from_secs_f64(1534614057.324998),
-                            ),
-                            side: Side::Buy,
-                        },
-                    ],
-                })),
-            }];
+                    expected: Ok(KrakenTrades::Data(KrakenTradesInner {
+                        subscription_id: SubscriptionId::from("trade|BTC/USD"),
+                        trades: vec![
+                            KrakenTrade {
+                                symbol: SmolStr::new("BTC/USD"),
+                                side: Side::Sell,
+                                price: 5541.2,
+                                amount: 0.15850568,
+                                trade_id: 4665906,
+                                time: Utc.timestamp_micros(1534614057321597).unwrap(),
+                            },
+                            KrakenTrade {
+                                symbol: SmolStr::new("BTC/USD"),
+                                side: Side::Buy,
+                                price: 6060.0,
+                                amount: 0.02455,
+                                trade_id: 4665907,
+                                time: Utc.timestamp_micros(1534614057324998).unwrap(),
+                            },
+                        ],
+                    })),
+                },
+                TestCase {
+                    // TC1: valid KrakenTrades::Event(KrakenEvent::Heartbeat)
+                    input: r#"{"channel": "heartbeat"}"#,
+                    expected: Ok(KrakenTrades::Event(KrakenEvent::Heartbeat)),
+                },
+                TestCase {
+                    // TC2: invalid KrakenTrades with empty data
+                    input: r#"{"channel": "trade", "type": "update", "data": []}"#,
+                    expected: Err(SocketError::Unsupported {
+                        entity: "".to_string(),
+                        item: "".to_string(),
+                    }),
+                },
+            ];
 
             for (index, test) in tests.into_iter().enumerate() {
                 let actual = serde_json::from_str::<KrakenTrades>(test.input);
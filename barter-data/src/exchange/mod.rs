@@ -20,12 +20,21 @@ pub mod bitfinex;
 /// `Bitmex [`Connector`] and [`StreamSelector`] implementations.
 pub mod bitmex;
 
+/// `Bitstamp` [`Connector`] and [`StreamSelector`] implementations, generated via
+/// [`ExchangeConnector`](connector::ExchangeConnector).
+pub mod bitstamp;
+
 /// `Bybit` ['Connector'] and ['StreamSelector'] implementation
 pub mod bybit;
 
-/// `Coinbase` [`Connector`] and [`StreamSelector`] implementations.
+/// `Coinbase` [`Connector`] and [`StreamSelector`] implementations, generated via
+/// [`ExchangeConnector`](connector::ExchangeConnector).
 pub mod coinbase;
 
+/// [`ExchangeConnector`](connector::ExchangeConnector) trait & macro that generate the
+/// [`Connector`] and [`StreamSelector`] glue of simple WebSocket exchange integrations.
+pub mod connector;
+
 /// `GateioSpot`, `GateioFuturesUsd` & `GateioFuturesBtc` [`Connector`] and [`StreamSelector`]
 /// implementations.
 pub mod gateio;
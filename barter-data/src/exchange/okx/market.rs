@@ -74,3 +74,67 @@ fn okx_market(instrument: &Instrument) -> OkxMarket {
 fn format_expiry<'a>(expiry: DateTime<Utc>) -> DelayedFormat<StrftimeItems<'a>> {
     expiry.date_naive().format("%g%m%d")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_instrument::instrument::kind::{
+        future::FutureContract,
+        option::{OptionContract, OptionExercise},
+        InstrumentKind,
+    };
+    use chrono::TimeZone;
+    use rust_decimal_macros::dec;
+
+    #[test]
+    fn test_okx_market() {
+        // 26th of September 2025
+        let expiry = Utc.timestamp_millis_opt(1758844800000).unwrap();
+
+        struct TestCase {
+            input: Instrument,
+            expected: &'static str,
+        }
+
+        let cases = vec![
+            TestCase {
+                // TC0: Spot
+                input: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+                expected: "BTC-USDT",
+            },
+            TestCase {
+                // TC1: Perpetual appends "-SWAP"
+                input: Instrument::from(("btc", "usdt", InstrumentKind::Perpetual)),
+                expected: "BTC-USDT-SWAP",
+            },
+            TestCase {
+                // TC2: Future appends the expiry
+                input: Instrument::from((
+                    "btc",
+                    "usd",
+                    InstrumentKind::Future(FutureContract { expiry }),
+                )),
+                expected: "BTC-USD-250926",
+            },
+            TestCase {
+                // TC3: Option appends the expiry, strike & kind
+                input: Instrument::from((
+                    "btc",
+                    "usd",
+                    InstrumentKind::Option(OptionContract {
+                        kind: OptionKind::Call,
+                        exercise: OptionExercise::European,
+                        expiry,
+                        strike: dec!(70000),
+                    }),
+                )),
+                expected: "BTC-USD-250926-70000-C",
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = okx_market(&test.input);
+            assert_eq!(actual.as_ref(), test.expected, "TC{index} failed");
+        }
+    }
+}
@@ -1,16 +1,20 @@
 use self::{
-    channel::OkxChannel, market::OkxMarket, subscription::OkxSubResponse, trade::OkxTrades,
+    channel::OkxChannel, market::OkxMarket, subscription::OkxSubResponse, ticker::OkxTickers,
+    trade::OkxTrades,
 };
 use crate::{
     exchange::{Connector, ExchangeSub, PingInterval, StreamSelector},
     instrument::InstrumentData,
     subscriber::{validator::WebSocketSubValidator, WebSocketSubscriber},
-    subscription::trade::PublicTrades,
+    subscription::{ticker::Tickers, trade::PublicTrades},
     transformer::stateless::StatelessTransformer,
     ExchangeWsStream, NoInitialSnapshots,
 };
 use barter_instrument::exchange::ExchangeId;
-use barter_integration::{error::SocketError, protocol::websocket::WsMessage};
+use barter_integration::{
+    error::SocketError,
+    protocol::websocket::{CompressedWebSocketParser, WsMessage},
+};
 use barter_macro::{DeExchange, SerExchange};
 use serde_json::json;
 use std::time::Duration;
@@ -28,6 +32,9 @@ pub mod market;
 /// [`Validator`](barter_integration::Validator) for [`Okx`].
 pub mod subscription;
 
+/// Ticker types for [`Okx`].
+pub mod ticker;
+
 /// Public trade types for [`Okx`].
 pub mod trade;
 
@@ -43,6 +50,9 @@ pub const PING_INTERVAL_OKX: Duration = Duration::from_secs(29);
 
 /// [`Okx`] exchange.
 ///
+/// [`Okx`] may send raw deflate compressed binary frames, so it's market streams are parsed with
+/// the [`CompressedWebSocketParser`].
+///
 /// See docs: <https://www.okx.com/docs-v5/en/#websocket-api>
 #[derive(
     Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, DeExchange, SerExchange,
@@ -84,6 +94,19 @@ where
     Instrument: InstrumentData,
 {
     type SnapFetcher = NoInitialSnapshots;
-    type Stream =
-        ExchangeWsStream<StatelessTransformer<Self, Instrument::Key, PublicTrades, OkxTrades>>;
+    type Stream = ExchangeWsStream<
+        StatelessTransformer<Self, Instrument::Key, PublicTrades, OkxTrades>,
+        CompressedWebSocketParser,
+    >;
+}
+
+impl<Instrument> StreamSelector<Instrument, Tickers> for Okx
+where
+    Instrument: InstrumentData,
+{
+    type SnapFetcher = NoInitialSnapshots;
+    type Stream = ExchangeWsStream<
+        StatelessTransformer<Self, Instrument::Key, Tickers, OkxTickers>,
+        CompressedWebSocketParser,
+    >;
 }
@@ -0,0 +1,166 @@
+use super::trade::OkxMessage;
+use crate::{
+    event::{MarketEvent, MarketIter},
+    subscription::ticker::Ticker,
+};
+use barter_instrument::exchange::ExchangeId;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Terse type alias for an [`Okx`](super::Okx) real-time tickers WebSocket message.
+pub type OkxTickers = OkxMessage<OkxTicker>;
+
+/// [`Okx`](super::Okx) real-time ticker WebSocket message.
+///
+/// ### Raw Payload Examples
+/// See docs: <https://www.okx.com/docs-v5/en/#public-data-websocket-tickers-channel>
+/// #### Perpetual Ticker
+/// ```json
+/// {
+///   "arg": {
+///     "channel": "tickers",
+///     "instId": "BTC-USDT-SWAP"
+///   },
+///   "data": [
+///     {
+///       "instType": "SWAP",
+///       "instId": "BTC-USDT-SWAP",
+///       "last": "42219.9",
+///       "lastSz": "0.1",
+///       "askPx": "42220.0",
+///       "askSz": "11",
+///       "bidPx": "42219.9",
+///       "bidSz": "5",
+///       "open24h": "41000",
+///       "high24h": "42500",
+///       "low24h": "40888.8",
+///       "volCcy24h": "2222",
+///       "vol24h": "2222000",
+///       "sodUtc0": "41500",
+///       "sodUtc8": "41700",
+///       "ts": "1630048897897"
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct OkxTicker {
+    #[serde(rename = "last", deserialize_with = "barter_integration::de::de_str")]
+    pub last_price: f64,
+    #[serde(rename = "vol24h", deserialize_with = "barter_integration::de::de_str")]
+    pub volume: f64,
+    #[serde(
+        rename = "ts",
+        deserialize_with = "barter_integration::de::de_str_u64_epoch_ms_as_datetime_utc"
+    )]
+    pub time: DateTime<Utc>,
+}
+
+impl<InstrumentKey: Clone> From<(ExchangeId, InstrumentKey, OkxTickers)>
+    for MarketIter<InstrumentKey, Ticker>
+{
+    fn from((exchange, instrument, tickers): (ExchangeId, InstrumentKey, OkxTickers)) -> Self {
+        tickers
+            .data
+            .into_iter()
+            .map(|ticker| {
+                Ok(MarketEvent {
+                    time_exchange: ticker.time,
+                    time_received: Utc::now(),
+                    exchange,
+                    instrument: instrument.clone(),
+                    kind: Ticker {
+                        last_price: ticker.last_price,
+                        volume: ticker.volume,
+                        count: None,
+                        first_id: None,
+                        last_id: None,
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod de {
+        use super::*;
+        use barter_integration::{
+            de::datetime_utc_from_epoch_duration, subscription::SubscriptionId,
+        };
+        use std::time::Duration;
+
+        #[test]
+        fn test_okx_message_tickers() {
+            let input = r#"
+            {
+                "arg": {
+                    "channel": "tickers",
+                    "instId": "BTC-USDT-SWAP"
+                },
+                "data": [
+                    {
+                        "instType": "SWAP",
+                        "instId": "BTC-USDT-SWAP",
+                        "last": "42219.9",
+                        "lastSz": "0.1",
+                        "askPx": "42220.0",
+                        "askSz": "11",
+                        "bidPx": "42219.9",
+                        "bidSz": "5",
+                        "open24h": "41000",
+                        "high24h": "42500",
+                        "low24h": "40888.8",
+                        "volCcy24h": "2222",
+                        "vol24h": "2222000",
+                        "sodUtc0": "41500",
+                        "sodUtc8": "41700",
+                        "ts": "1630048897897"
+                    }
+                ]
+            }
+            "#;
+
+            let actual = serde_json::from_str::<OkxTickers>(input).unwrap();
+            let expected = OkxTickers {
+                subscription_id: SubscriptionId::from("tickers|BTC-USDT-SWAP"),
+                data: vec![OkxTicker {
+                    last_price: 42219.9,
+                    volume: 2222000.0,
+                    time: datetime_utc_from_epoch_duration(Duration::from_millis(1630048897897)),
+                }],
+            };
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_okx_tickers_have_no_trade_count_or_ids() {
+        let tickers = OkxTickers {
+            subscription_id: "tickers|BTC-USDT-SWAP".into(),
+            data: vec![OkxTicker {
+                last_price: 42219.9,
+                volume: 2222000.0,
+                time: Utc::now(),
+            }],
+        };
+
+        let MarketIter(events) =
+            MarketIter::<&str, Ticker>::from((ExchangeId::Okx, "btc_usdt_swap", tickers));
+
+        assert_eq!(
+            events[0].as_ref().unwrap().kind,
+            Ticker {
+                last_price: 42219.9,
+                volume: 2222000.0,
+                count: None,
+                first_id: None,
+                last_id: None,
+            }
+        );
+    }
+}
@@ -191,4 +191,59 @@ mod tests {
             }
         }
     }
+
+    mod compressed {
+        use super::*;
+        use barter_integration::{
+            error::SocketError,
+            protocol::{
+                websocket::{CompressedWebSocketParser, WsMessage},
+                StreamParser,
+            },
+        };
+        use flate2::{write::GzEncoder, Compression};
+        use std::io::Write;
+
+        #[test]
+        fn test_gzip_compressed_okx_trade_frame_decodes_to_market_event() {
+            let input = r#"{"arg":{"channel":"trades","instId":"BTC-USDT"},"data":[{"instId":"BTC-USDT","tradeId":"130639474","px":"42219.9","sz":"0.12060306","side":"buy","ts":"1630048897897"}]}"#;
+            let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+            encoder.write_all(input.as_bytes()).unwrap();
+            let frame = WsMessage::Binary(encoder.finish().unwrap());
+
+            let trades = CompressedWebSocketParser::parse::<OkxTrades>(Ok(frame))
+                .unwrap()
+                .unwrap();
+            let events =
+                MarketIter::<&str, PublicTrade>::from((ExchangeId::Okx, "btc_usdt", trades))
+                    .0
+                    .into_iter()
+                    .collect::<Result<Vec<_>, _>>()
+                    .unwrap();
+
+            assert_eq!(events.len(), 1);
+            let event = &events[0];
+            assert_eq!(event.exchange, ExchangeId::Okx);
+            assert_eq!(event.instrument, "btc_usdt");
+            assert_eq!(event.time_exchange.timestamp_millis(), 1630048897897);
+            assert_eq!(
+                event.kind,
+                PublicTrade {
+                    id: "130639474".to_string(),
+                    price: 42219.9,
+                    amount: 0.12060306,
+                    side: Side::Buy,
+                }
+            );
+
+            // Frame claiming gzip compression with a malformed body
+            let malformed = vec![0x1f, 0x8b, 0x08, 0x00, 0xde, 0xad, 0xbe, 0xef];
+            assert!(matches!(
+                CompressedWebSocketParser::parse::<OkxTrades>(Ok(WsMessage::Binary(
+                    malformed.clone()
+                ))),
+                Some(Err(SocketError::Decompress { payload, .. })) if payload == malformed
+            ));
+        }
+    }
 }
@@ -100,7 +100,7 @@ use barter_instrument::exchange::ExchangeId;
 use barter_integration::{
     error::SocketError,
     protocol::{
-        websocket::{WebSocketParser, WsMessage, WsSink, WsStream},
+        websocket::{WebSocket, WebSocketParser, WsError, WsMessage, WsSink, WsStream},
         StreamParser,
     },
     ExchangeStream, Transformer,
@@ -156,9 +156,46 @@ pub mod books;
 /// [`futures_usd`](exchange::binance::futures::l2::BinanceFuturesUsdOrderBooksL2Transformer).
 pub mod transformer;
 
-/// Convenient type alias for an [`ExchangeStream`] utilising a tungstenite
-/// [`WebSocket`](barter_integration::protocol::websocket::WebSocket).
-pub type ExchangeWsStream<Transformer> = ExchangeStream<WebSocketParser, WsStream, Transformer>;
+/// Convenient type alias for an [`ExchangeStream`] utilising a tungstenite [`WebSocket`].
+///
+/// Connectors for exchanges that send compressed binary frames (eg/
+/// [`Okx`](exchange::okx::Okx)) use the
+/// [`CompressedWebSocketParser`](barter_integration::protocol::websocket::CompressedWebSocketParser)
+/// in place of the default [`WebSocketParser`].
+pub type ExchangeWsStream<Transformer, Parser = WebSocketParser> =
+    ExchangeStream<Parser, ClosingWsStream, Transformer>;
+
+/// [`WsStream`] that keeps the [`distribute_messages_to_exchange`] task of it's WebSocket
+/// connection alive for as long as it exists.
+///
+/// Once dropped (eg/ on reconnect or shutdown), the task sends a close frame to the exchange via
+/// the [`WsSink`], rather than leaving the WebSocket connection half-closed.
+#[derive(Debug)]
+pub struct ClosingWsStream {
+    stream: WsStream,
+    _ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
+}
+
+impl ClosingWsStream {
+    /// Construct a new [`ClosingWsStream`] that keeps the provided [`WsSink`] transmitter alive.
+    pub fn new(stream: WsStream, ws_sink_tx: mpsc::UnboundedSender<WsMessage>) -> Self {
+        Self {
+            stream,
+            _ws_sink_tx: ws_sink_tx,
+        }
+    }
+}
+
+impl Stream for ClosingWsStream {
+    type Item = <WsStream as Stream>::Item;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Self::Item>> {
+        self.stream.poll_next_unpin(cx)
+    }
+}
 
 /// Defines a generic identification type for the implementor.
 pub trait Identifier<T> {
@@ -207,9 +244,10 @@ pub trait SnapshotFetcher<Exchange, Kind> {
 }
 
 #[async_trait]
-impl<Exchange, Instrument, Kind, Transformer> MarketStream<Exchange, Instrument, Kind>
-    for ExchangeWsStream<Transformer>
+impl<Exchange, Instrument, Kind, Transformer, Parser> MarketStream<Exchange, Instrument, Kind>
+    for ExchangeWsStream<Transformer, Parser>
 where
+    Parser: StreamParser<Stream = WebSocket, Message = WsMessage, Error = WsError> + Send,
     Exchange: Connector + Send + Sync,
     Instrument: InstrumentData,
     Kind: SubscriptionKind + Send + Sync,
@@ -254,15 +292,16 @@ where
             ));
         }
 
+        // Keep the WsSink task alive for as long as the WsStream, so it can close the connection
+        let ws_stream = ClosingWsStream::new(ws_stream, ws_sink_tx.clone());
+
         // Initialise Transformer associated with this Exchange and SubscriptionKind
         let mut transformer =
             Transformer::init(instrument_map, &initial_snapshots, ws_sink_tx).await?;
 
         // Process any buffered active subscription events received during Subscription validation
-        let mut processed = process_buffered_events::<WebSocketParser, _>(
-            &mut transformer,
-            buffered_websocket_events,
-        );
+        let mut processed =
+            process_buffered_events::<Parser, _>(&mut transformer, buffered_websocket_events);
 
         // Extend buffered events with any initial snapshot events
         processed.extend(initial_snapshots.into_iter().map(Ok));
@@ -318,6 +357,9 @@ where
 /// Transmit [`WsMessage`]s sent from the [`ExchangeTransformer`] to the exchange via
 /// the [`WsSink`].
 ///
+/// Once every transmitter is dropped (ie/ the [`ClosingWsStream`] & [`ExchangeTransformer`]), a
+/// close frame is sent to the exchange to gracefully close the WebSocket connection.
+///
 /// **Note:**
 /// ExchangeTransformer is operating in a synchronous trait context so we use this separate task
 /// to avoid adding `#[\async_trait\]` to the transformer - this avoids allocations.
@@ -340,6 +382,11 @@ pub async fn distribute_messages_to_exchange(
             );
         }
     }
+
+    // Send close frame to the exchange, which fails if the WebSocket is already disconnected
+    if let Err(error) = ws_sink.close().await {
+        debug!(%exchange, %error, "failed to send close frame to the exchange via WsSink");
+    }
 }
 
 /// Schedule the sending of custom application-level ping [`WsMessage`]s to the exchange using
@@ -348,15 +395,24 @@ pub async fn distribute_messages_to_exchange(
 /// **Notes:**
 ///  - This is only used for those exchanges that require custom application-level pings.
 ///  - This is additional to the protocol-level pings already handled by `tokio_tungstenite`.
+///  - Only a weak transmitter is held, so scheduled pings do not keep the WebSocket connection
+///    open once it's [`ClosingWsStream`] is dropped.
 pub async fn schedule_pings_to_exchange(
     exchange: ExchangeId,
     ws_sink_tx: mpsc::UnboundedSender<WsMessage>,
     PingInterval { mut interval, ping }: PingInterval,
 ) {
+    let ws_sink_tx = ws_sink_tx.downgrade();
+
     loop {
         // Wait for next scheduled ping
         interval.tick().await;
 
+        // Stop pinging once the WebSocket connection has been dropped
+        let Some(ws_sink_tx) = ws_sink_tx.upgrade() else {
+            break;
+        };
+
         // Construct exchange custom application-level ping payload
         let payload = ping();
         debug!(%exchange, %payload, "sending custom application-level ping to exchange");
@@ -366,3 +422,47 @@ pub async fn schedule_pings_to_exchange(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::net::TcpListener;
+
+    #[tokio::test]
+    async fn test_dropping_closing_ws_stream_sends_close_frame() {
+        // Mock exchange server that returns the first message received on it's WebSocket
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("ws://{}", listener.local_addr().unwrap());
+        let server = tokio::spawn(async move {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut websocket = tokio_tungstenite::accept_async(socket).await.unwrap();
+            websocket.next().await
+        });
+
+        let websocket = barter_integration::protocol::websocket::connect(url.as_str())
+            .await
+            .unwrap();
+        let (ws_sink, ws_stream) = websocket.split();
+        let (ws_sink_tx, ws_sink_rx) = mpsc::unbounded_channel();
+        let distribute = tokio::spawn(distribute_messages_to_exchange(
+            ExchangeId::BinanceSpot,
+            ws_sink,
+            ws_sink_rx,
+        ));
+
+        // Transformer drops it's transmitter, but the ClosingWsStream keeps the connection open
+        let ws_stream = ClosingWsStream::new(ws_stream, ws_sink_tx.clone());
+        drop(ws_sink_tx);
+        tokio::task::yield_now().await;
+        assert!(!distribute.is_finished());
+
+        drop(ws_stream);
+        distribute.await.unwrap();
+
+        let received = server.await.unwrap();
+        assert!(
+            matches!(received, Some(Ok(WsMessage::Close(_)))),
+            "expected close frame, received: {received:?}"
+        );
+    }
+}
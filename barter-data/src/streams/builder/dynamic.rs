@@ -5,7 +5,7 @@ use crate::{
         bitfinex::{market::BitfinexMarket, Bitfinex},
         bitmex::{market::BitmexMarket, Bitmex},
         bybit::{futures::BybitPerpetualsUsd, market::BybitMarket, spot::BybitSpot},
-        coinbase::{market::CoinbaseMarket, Coinbase},
+        coinbase::{Coinbase, CoinbaseMarket},
         gateio::{
             future::{GateioFuturesBtc, GateioFuturesUsd},
             market::GateioMarket,
@@ -23,7 +23,9 @@ use crate::{
     },
     subscription::{
         book::{OrderBookEvent, OrderBookL1, OrderBooksL1},
+        candle::{Candle, Candles},
         liquidation::{Liquidation, Liquidations},
+        ticker::{Ticker, Tickers},
         trade::{PublicTrade, PublicTrades},
         SubKind, Subscription,
     },
@@ -55,6 +57,10 @@ pub struct DynamicStreams<InstrumentKey> {
     >,
     pub liquidations:
         VecMap<ExchangeId, UnboundedReceiverStream<MarketStreamResult<InstrumentKey, Liquidation>>>,
+    pub candles:
+        VecMap<ExchangeId, UnboundedReceiverStream<MarketStreamResult<InstrumentKey, Candle>>>,
+    pub tickers:
+        VecMap<ExchangeId, UnboundedReceiverStream<MarketStreamResult<InstrumentKey, Ticker>>>,
 }
 
 impl<InstrumentKey> DynamicStreams<InstrumentKey> {
@@ -79,13 +85,17 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
         Subscription<BinanceSpot, Instrument, PublicTrades>: Identifier<BinanceMarket>,
         Subscription<BinanceSpot, Instrument, PublicTrades>: Identifier<BinanceMarket>,
         Subscription<BinanceSpot, Instrument, OrderBooksL1>: Identifier<BinanceMarket>,
+        Subscription<BinanceSpot, Instrument, Candles>: Identifier<BinanceMarket>,
         Subscription<BinanceFuturesUsd, Instrument, PublicTrades>: Identifier<BinanceMarket>,
         Subscription<BinanceFuturesUsd, Instrument, OrderBooksL1>: Identifier<BinanceMarket>,
         Subscription<BinanceFuturesUsd, Instrument, Liquidations>: Identifier<BinanceMarket>,
+        Subscription<BinanceFuturesUsd, Instrument, Candles>: Identifier<BinanceMarket>,
         Subscription<Bitfinex, Instrument, PublicTrades>: Identifier<BitfinexMarket>,
         Subscription<Bitmex, Instrument, PublicTrades>: Identifier<BitmexMarket>,
         Subscription<BybitSpot, Instrument, PublicTrades>: Identifier<BybitMarket>,
+        Subscription<BybitSpot, Instrument, Tickers>: Identifier<BybitMarket>,
         Subscription<BybitPerpetualsUsd, Instrument, PublicTrades>: Identifier<BybitMarket>,
+        Subscription<BybitPerpetualsUsd, Instrument, Tickers>: Identifier<BybitMarket>,
         Subscription<Coinbase, Instrument, PublicTrades>: Identifier<CoinbaseMarket>,
         Subscription<GateioSpot, Instrument, PublicTrades>: Identifier<GateioMarket>,
         Subscription<GateioFuturesUsd, Instrument, PublicTrades>: Identifier<GateioMarket>,
@@ -96,6 +106,7 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
         Subscription<Kraken, Instrument, PublicTrades>: Identifier<KrakenMarket>,
         Subscription<Kraken, Instrument, OrderBooksL1>: Identifier<KrakenMarket>,
         Subscription<Okx, Instrument, PublicTrades>: Identifier<OkxMarket>,
+        Subscription<Okx, Instrument, Tickers>: Identifier<OkxMarket>,
     {
         // Validate & dedup Subscription batches
         let batches = validate_batches(subscription_batches)?;
@@ -152,6 +163,24 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
                                     .forward_to(txs.l1s.get(&exchange).unwrap().clone());
                                     Ok(())
                                 }
+                                (ExchangeId::BinanceSpot, SubKind::Candles(interval)) => {
+                                    init_market_stream(
+                                        STREAM_RECONNECTION_POLICY,
+                                        subs.into_iter()
+                                            .map(|sub| {
+                                                Subscription::new(
+                                                    BinanceSpot::default(),
+                                                    sub.instrument,
+                                                    Candles::new(interval),
+                                                )
+                                            })
+                                            .collect(),
+                                    )
+                                    .await?
+                                    .boxed()
+                                    .forward_to(txs.candles.get(&exchange).unwrap().clone());
+                                    Ok(())
+                                }
                                 (ExchangeId::BinanceFuturesUsd, SubKind::PublicTrades) => {
                                     init_market_stream(
                                         STREAM_RECONNECTION_POLICY,
@@ -206,6 +235,24 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
                                     .forward_to(txs.liquidations.get(&exchange).unwrap().clone());
                                     Ok(())
                                 }
+                                (ExchangeId::BinanceFuturesUsd, SubKind::Candles(interval)) => {
+                                    init_market_stream(
+                                        STREAM_RECONNECTION_POLICY,
+                                        subs.into_iter()
+                                            .map(|sub| {
+                                                Subscription::new(
+                                                    BinanceFuturesUsd::default(),
+                                                    sub.instrument,
+                                                    Candles::new(interval),
+                                                )
+                                            })
+                                            .collect(),
+                                    )
+                                    .await?
+                                    .boxed()
+                                    .forward_to(txs.candles.get(&exchange).unwrap().clone());
+                                    Ok(())
+                                }
                                 (ExchangeId::Bitfinex, SubKind::PublicTrades) => {
                                     init_market_stream(
                                         STREAM_RECONNECTION_POLICY,
@@ -260,6 +307,24 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
                                     .forward_to(txs.trades.get(&exchange).unwrap().clone());
                                     Ok(())
                                 }
+                                (ExchangeId::BybitSpot, SubKind::Tickers) => {
+                                    init_market_stream(
+                                        STREAM_RECONNECTION_POLICY,
+                                        subs.into_iter()
+                                            .map(|sub| {
+                                                Subscription::new(
+                                                    BybitSpot::default(),
+                                                    sub.instrument,
+                                                    Tickers,
+                                                )
+                                            })
+                                            .collect(),
+                                    )
+                                    .await?
+                                    .boxed()
+                                    .forward_to(txs.tickers.get(&exchange).unwrap().clone());
+                                    Ok(())
+                                }
                                 (ExchangeId::BybitPerpetualsUsd, SubKind::PublicTrades) => {
                                     init_market_stream(
                                         STREAM_RECONNECTION_POLICY,
@@ -278,6 +343,24 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
                                     .forward_to(txs.trades.get(&exchange).unwrap().clone());
                                     Ok(())
                                 }
+                                (ExchangeId::BybitPerpetualsUsd, SubKind::Tickers) => {
+                                    init_market_stream(
+                                        STREAM_RECONNECTION_POLICY,
+                                        subs.into_iter()
+                                            .map(|sub| {
+                                                Subscription::new(
+                                                    BybitPerpetualsUsd::default(),
+                                                    sub.instrument,
+                                                    Tickers,
+                                                )
+                                            })
+                                            .collect(),
+                                    )
+                                    .await?
+                                    .boxed()
+                                    .forward_to(txs.tickers.get(&exchange).unwrap().clone());
+                                    Ok(())
+                                }
                                 (ExchangeId::Coinbase, SubKind::PublicTrades) => {
                                     init_market_stream(
                                         STREAM_RECONNECTION_POLICY,
@@ -454,6 +537,20 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
                                     .forward_to(txs.trades.get(&exchange).unwrap().clone());
                                     Ok(())
                                 }
+                                (ExchangeId::Okx, SubKind::Tickers) => {
+                                    init_market_stream(
+                                        STREAM_RECONNECTION_POLICY,
+                                        subs.into_iter()
+                                            .map(|sub| {
+                                                Subscription::new(Okx, sub.instrument, Tickers)
+                                            })
+                                            .collect(),
+                                    )
+                                    .await?
+                                    .boxed()
+                                    .forward_to(txs.tickers.get(&exchange).unwrap().clone());
+                                    Ok(())
+                                }
                                 (exchange, sub_kind) => {
                                     Err(DataError::Unsupported { exchange, sub_kind })
                                 }
@@ -491,6 +588,18 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
                 .into_iter()
                 .map(|(exchange, rx)| (exchange, UnboundedReceiverStream::new(rx)))
                 .collect(),
+            candles: channels
+                .rxs
+                .candles
+                .into_iter()
+                .map(|(exchange, rx)| (exchange, UnboundedReceiverStream::new(rx)))
+                .collect(),
+            tickers: channels
+                .rxs
+                .tickers
+                .into_iter()
+                .map(|(exchange, rx)| (exchange, UnboundedReceiverStream::new(rx)))
+                .collect(),
         })
     }
 
@@ -566,6 +675,42 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
         select_all(std::mem::take(&mut self.liquidations).into_values())
     }
 
+    /// Remove an exchange [`Candle`] `Stream` from the [`DynamicStreams`] collection.
+    ///
+    /// Note that calling this method will permanently remove this `Stream` from [`Self`].
+    pub fn select_candles(
+        &mut self,
+        exchange: ExchangeId,
+    ) -> Option<UnboundedReceiverStream<MarketStreamResult<InstrumentKey, Candle>>> {
+        self.candles.remove(&exchange)
+    }
+
+    /// Select and merge every exchange [`Candle`] `Stream` using
+    /// [`SelectAll`](futures_util::stream::select_all).
+    pub fn select_all_candles(
+        &mut self,
+    ) -> SelectAll<UnboundedReceiverStream<MarketStreamResult<InstrumentKey, Candle>>> {
+        select_all(std::mem::take(&mut self.candles).into_values())
+    }
+
+    /// Remove an exchange [`Ticker`] `Stream` from the [`DynamicStreams`] collection.
+    ///
+    /// Note that calling this method will permanently remove this `Stream` from [`Self`].
+    pub fn select_tickers(
+        &mut self,
+        exchange: ExchangeId,
+    ) -> Option<UnboundedReceiverStream<MarketStreamResult<InstrumentKey, Ticker>>> {
+        self.tickers.remove(&exchange)
+    }
+
+    /// Select and merge every exchange [`Ticker`] `Stream` using
+    /// [`SelectAll`](futures_util::stream::select_all).
+    pub fn select_all_tickers(
+        &mut self,
+    ) -> SelectAll<UnboundedReceiverStream<MarketStreamResult<InstrumentKey, Ticker>>> {
+        select_all(std::mem::take(&mut self.tickers).into_values())
+    }
+
     /// Select and merge every exchange `Stream` for every data type using [`select_all`]
     ///
     /// Note that using [`MarketEvent<Instrument, DataKind>`] as the `Output` is suitable for most
@@ -578,12 +723,16 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
         MarketStreamResult<InstrumentKey, OrderBookL1>: Into<Output>,
         MarketStreamResult<InstrumentKey, OrderBookEvent>: Into<Output>,
         MarketStreamResult<InstrumentKey, Liquidation>: Into<Output>,
+        MarketStreamResult<InstrumentKey, Candle>: Into<Output>,
+        MarketStreamResult<InstrumentKey, Ticker>: Into<Output>,
     {
         let Self {
             trades,
             l1s,
             l2s,
             liquidations,
+            candles,
+            tickers,
         } = self;
 
         let trades = trades
@@ -602,7 +751,20 @@ impl<InstrumentKey> DynamicStreams<InstrumentKey> {
             .into_values()
             .map(|stream| stream.map(MarketStreamResult::into).boxed());
 
-        let all = trades.chain(l1s).chain(l2s).chain(liquidations);
+        let candles = candles
+            .into_values()
+            .map(|stream| stream.map(MarketStreamResult::into).boxed());
+
+        let tickers = tickers
+            .into_values()
+            .map(|stream| stream.map(MarketStreamResult::into).boxed());
+
+        let all = trades
+            .chain(l1s)
+            .chain(l2s)
+            .chain(liquidations)
+            .chain(candles)
+            .chain(tickers);
 
         select_all(all)
     }
@@ -700,6 +862,26 @@ where
                         rxs.liquidations.insert(sub.exchange, rx);
                     }
                 }
+                SubKind::Candles(_) => {
+                    if let (None, None) = (
+                        txs.candles.get(&sub.exchange),
+                        rxs.candles.get(&sub.exchange),
+                    ) {
+                        let (tx, rx) = mpsc::unbounded_channel();
+                        txs.candles.insert(sub.exchange, tx);
+                        rxs.candles.insert(sub.exchange, rx);
+                    }
+                }
+                SubKind::Tickers => {
+                    if let (None, None) = (
+                        txs.tickers.get(&sub.exchange),
+                        rxs.tickers.get(&sub.exchange),
+                    ) {
+                        let (tx, rx) = mpsc::unbounded_channel();
+                        txs.tickers.insert(sub.exchange, tx);
+                        rxs.tickers.insert(sub.exchange, rx);
+                    }
+                }
                 unsupported => return Err(DataError::UnsupportedSubKind(unsupported)),
             }
         }
@@ -728,6 +910,10 @@ struct Txs<InstrumentKey> {
         ExchangeId,
         mpsc::UnboundedSender<MarketStreamResult<InstrumentKey, Liquidation>>,
     >,
+    candles:
+        FnvHashMap<ExchangeId, mpsc::UnboundedSender<MarketStreamResult<InstrumentKey, Candle>>>,
+    tickers:
+        FnvHashMap<ExchangeId, mpsc::UnboundedSender<MarketStreamResult<InstrumentKey, Ticker>>>,
 }
 
 impl<InstrumentKey> Default for Txs<InstrumentKey> {
@@ -737,6 +923,8 @@ impl<InstrumentKey> Default for Txs<InstrumentKey> {
             l1s: Default::default(),
             l2s: Default::default(),
             liquidations: Default::default(),
+            candles: Default::default(),
+            tickers: Default::default(),
         }
     }
 }
@@ -758,6 +946,10 @@ struct Rxs<InstrumentKey> {
         ExchangeId,
         mpsc::UnboundedReceiver<MarketStreamResult<InstrumentKey, Liquidation>>,
     >,
+    candles:
+        FnvHashMap<ExchangeId, mpsc::UnboundedReceiver<MarketStreamResult<InstrumentKey, Candle>>>,
+    tickers:
+        FnvHashMap<ExchangeId, mpsc::UnboundedReceiver<MarketStreamResult<InstrumentKey, Ticker>>>,
 }
 
 impl<InstrumentKey> Default for Rxs<InstrumentKey> {
@@ -767,6 +959,8 @@ impl<InstrumentKey> Default for Rxs<InstrumentKey> {
             l1s: Default::default(),
             l2s: Default::default(),
             liquidations: Default::default(),
+            candles: Default::default(),
+            tickers: Default::default(),
         }
     }
 }
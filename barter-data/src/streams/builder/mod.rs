@@ -4,8 +4,10 @@ use crate::{
     exchange::StreamSelector,
     instrument::InstrumentData,
     streams::{
-        consumer::{init_market_stream, MarketStreamResult, STREAM_RECONNECTION_POLICY},
-        reconnect::stream::ReconnectingStream,
+        consumer::{init_rate_limited_market_stream, MarketStreamResult},
+        rate_limit::RateLimiter,
+        reconnect::stream::{ReconnectingStream, ReconnectionBackoffPolicy},
+        shutdown::Shutdown,
     },
     subscription::{Subscription, SubscriptionKind},
     Identifier,
@@ -13,7 +15,7 @@ use crate::{
 use barter_instrument::exchange::ExchangeId;
 use barter_integration::Validator;
 use futures_util::StreamExt;
-use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin};
+use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin, time::Duration};
 use tokio::sync::mpsc;
 
 /// Defines the [`MultiStreamBuilder`](multi::MultiStreamBuilder) API for ergonomically
@@ -40,6 +42,21 @@ where
     pub channels:
         HashMap<ExchangeId, ExchangeChannel<MarketStreamResult<InstrumentKey, Kind::Event>>>,
     pub futures: Vec<SubscribeFuture>,
+    /// [`ReconnectionBackoffPolicy`] used by the [`MarketStream`]s of subsequently added
+    /// [`Subscription`]s.
+    pub policy: ReconnectionBackoffPolicy,
+    /// [`RateLimiter`] pacing the WebSocket (re)connections of subsequently added
+    /// [`Subscription`]s.
+    pub rate_limiter: RateLimiter,
+    /// Staleness timeout after which a connection of subsequently added [`Subscription`]s that
+    /// has not yielded any data is considered dead & reconnected.
+    pub staleness_timeout: Option<Duration>,
+    /// Interval at which the [`MarketEvent`](crate::event::MarketEvent)s of each instrument of
+    /// subsequently added [`Subscription`]s are throttled, if any.
+    pub throttle: Option<Duration>,
+    /// [`Shutdown`] signal used to gracefully stop the [`MarketStream`]s of subsequently added
+    /// [`Subscription`]s.
+    pub shutdown: Shutdown,
 }
 
 impl<InstrumentKey, Kind> Debug for StreamBuilder<InstrumentKey, Kind>
@@ -51,6 +68,11 @@ where
         f.debug_struct("StreamBuilder<InstrumentKey, SubscriptionKind>")
             .field("channels", &self.channels)
             .field("num_futures", &self.futures.len())
+            .field("policy", &self.policy)
+            .field("rate_limiter", &self.rate_limiter)
+            .field("staleness_timeout", &self.staleness_timeout)
+            .field("throttle", &self.throttle)
+            .field("shutdown", &self.shutdown)
             .finish()
     }
 }
@@ -64,9 +86,64 @@ where
         Self {
             channels: HashMap::new(),
             futures: Vec::new(),
+            policy: ReconnectionBackoffPolicy::default(),
+            rate_limiter: RateLimiter::default(),
+            staleness_timeout: None,
+            throttle: None,
+            shutdown: Shutdown::new(),
         }
     }
 
+    /// Set the [`ReconnectionBackoffPolicy`] used by the [`MarketStream`]s of
+    /// [`Subscription`]s subsequently added via [`subscribe()`](StreamBuilder::subscribe()).
+    pub fn reconnection_policy(self, policy: ReconnectionBackoffPolicy) -> Self {
+        Self { policy, ..self }
+    }
+
+    /// Set the [`RateLimiter`] used to pace the WebSocket (re)connections of [`Subscription`]s
+    /// subsequently added via [`subscribe()`](StreamBuilder::subscribe()).
+    ///
+    /// Cloned [`RateLimiter`]s share their limits, so the same [`RateLimiter`] can be provided
+    /// to several [`StreamBuilder`]s to pace every connection to an exchange together.
+    pub fn rate_limiter(self, rate_limiter: RateLimiter) -> Self {
+        Self {
+            rate_limiter,
+            ..self
+        }
+    }
+
+    /// Set the staleness timeout of the connections of [`Subscription`]s subsequently added via
+    /// [`subscribe()`](StreamBuilder::subscribe()).
+    ///
+    /// A connection that does not yield any data within the timeout is considered dead (eg/ the
+    /// exchange stopped sending data without closing the socket) & is reconnected.
+    pub fn staleness_timeout(self, timeout: Duration) -> Self {
+        Self {
+            staleness_timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Throttle the [`MarketEvent`](crate::event::MarketEvent)s of [`Subscription`]s
+    /// subsequently added via [`subscribe()`](StreamBuilder::subscribe()), so at most the latest
+    /// [`MarketEvent`](crate::event::MarketEvent) of each instrument is emitted once per
+    /// `interval` (eg/ to conflate high-frequency ticker updates).
+    pub fn throttle(self, interval: Duration) -> Self {
+        Self {
+            throttle: Some(interval),
+            ..self
+        }
+    }
+
+    /// Set the [`Shutdown`] signal used to gracefully stop the [`MarketStream`]s of
+    /// [`Subscription`]s subsequently added via [`subscribe()`](StreamBuilder::subscribe()).
+    ///
+    /// Cloned [`Shutdown`]s share the same signal, so an externally owned [`Shutdown`] can be
+    /// provided to stop the [`Streams`] alongside the rest of a service.
+    pub fn shutdown(self, shutdown: Shutdown) -> Self {
+        Self { shutdown, ..self }
+    }
+
     /// Add a collection of [`Subscription`]s to the [`StreamBuilder`] that will be actioned on
     /// a distinct [`WebSocket`](barter_integration::protocol::websocket::WebSocket) connection.
     ///
@@ -78,7 +155,7 @@ where
         Sub: Into<Subscription<Exchange, Instrument, Kind>>,
         Exchange: StreamSelector<Instrument, Kind> + Ord + Send + Sync + 'static,
         Instrument: InstrumentData<Key = InstrumentKey> + Ord + 'static,
-        Instrument::Key: Send + 'static,
+        Instrument::Key: Clone + PartialEq + Send + 'static,
         Kind: Ord + Send + Sync + 'static,
         Kind::Event: Send,
         Subscription<Exchange, Instrument, Kind>:
@@ -90,6 +167,11 @@ where
         // Acquire channel Sender to send Market<Kind::Event> from consumer loop to user
         // '--> Add ExchangeChannel Entry if this Exchange <--> SubscriptionKind combination is new
         let exchange_tx = self.channels.entry(Exchange::ID).or_default().tx.clone();
+        let policy = self.policy.clone();
+        let rate_limiter = self.rate_limiter.clone();
+        let staleness_timeout = self.staleness_timeout;
+        let throttle = self.throttle;
+        let shutdown = self.shutdown.clone();
 
         // Add Future that once awaited will yield the Result<(), SocketError> of subscribing
         self.futures.push(Box::pin(async move {
@@ -104,10 +186,23 @@ where
             subscriptions.dedup();
 
             // Initialise a MarketEvent `ReconnectingStream`
-            init_market_stream(STREAM_RECONNECTION_POLICY, subscriptions)
-                .await?
-                .boxed()
-                .forward_to(exchange_tx);
+            let stream = init_rate_limited_market_stream(
+                policy,
+                rate_limiter,
+                staleness_timeout,
+                subscriptions,
+            )
+            .await?
+            .with_shutdown(shutdown);
+
+            // Optionally conflate high-frequency MarketEvents of each instrument
+            match throttle {
+                Some(interval) => stream
+                    .with_throttle(interval)
+                    .boxed()
+                    .forward_to(exchange_tx),
+                None => stream.boxed().forward_to(exchange_tx),
+            };
 
             Ok(())
         }));
@@ -134,6 +229,7 @@ where
                 .into_iter()
                 .map(|(exchange, channel)| (exchange, channel.rx))
                 .collect(),
+            shutdown: self.shutdown,
         })
     }
 }
@@ -1,6 +1,8 @@
 use super::{ExchangeChannel, StreamBuilder, Streams};
 use crate::{
-    error::DataError, streams::consumer::MarketStreamResult, subscription::SubscriptionKind,
+    error::DataError,
+    streams::{consumer::MarketStreamResult, shutdown::Shutdown},
+    subscription::SubscriptionKind,
 };
 use barter_instrument::exchange::ExchangeId;
 use std::{collections::HashMap, fmt::Debug, future::Future, pin::Pin};
@@ -15,6 +17,9 @@ pub type BuilderInitFuture = Pin<Box<dyn Future<Output = Result<(), DataError>>>
 pub struct MultiStreamBuilder<Output> {
     pub channels: HashMap<ExchangeId, ExchangeChannel<Output>>,
     pub futures: Vec<BuilderInitFuture>,
+    /// [`Shutdown`] signal used to gracefully stop the [`Streams`] of every added
+    /// [`StreamBuilder`].
+    pub shutdown: Shutdown,
 }
 
 impl<Output> Debug for MultiStreamBuilder<Output>
@@ -25,6 +30,7 @@ where
         f.debug_struct("MultiStreamBuilder<Output>")
             .field("channels", &self.channels)
             .field("num_futures", &self.futures.len())
+            .field("shutdown", &self.shutdown)
             .finish()
     }
 }
@@ -35,9 +41,16 @@ impl<Output> MultiStreamBuilder<Output> {
         Self {
             channels: HashMap::new(),
             futures: Vec::new(),
+            shutdown: Shutdown::new(),
         }
     }
 
+    /// Set the [`Shutdown`] signal used to gracefully stop the [`Streams`] of every
+    /// [`StreamBuilder`] added to the [`MultiStreamBuilder`].
+    pub fn shutdown(self, shutdown: Shutdown) -> Self {
+        Self { shutdown, ..self }
+    }
+
     /// Add a [`StreamBuilder<SubscriptionKind>`](StreamBuilder) to the [`MultiStreamBuilder`]. Creates a
     /// [`Future`] that calls [`StreamBuilder::init`] and maps the [`SubscriptionKind::Event`](SubscriptionKind)
     /// into a common `Output`.
@@ -65,10 +78,18 @@ impl<Output> MultiStreamBuilder<Output> {
         }
 
         // Init Streams<Kind::Event> & send mapped Outputs to the associated exchange_tx
+        let shutdown = self.shutdown.clone();
         self.futures.push(Box::pin(async move {
-            builder
-                .init()
-                .await?
+            let streams = builder.init().await?;
+
+            // Task to propagate the MultiStreamBuilder Shutdown to the StreamBuilder Streams
+            let builder_shutdown = streams.shutdown.clone();
+            tokio::spawn(async move {
+                shutdown.wait().await;
+                builder_shutdown.shutdown();
+            });
+
+            streams
                 .streams
                 .into_iter()
                 .for_each(|(exchange, mut exchange_rx)| {
@@ -106,6 +127,7 @@ impl<Output> MultiStreamBuilder<Output> {
                 .into_iter()
                 .map(|(exchange, channel)| (exchange, channel.rx))
                 .collect(),
+            shutdown: self.shutdown,
         })
     }
 }
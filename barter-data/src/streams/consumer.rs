@@ -4,6 +4,7 @@ use crate::{
     exchange::StreamSelector,
     instrument::InstrumentData,
     streams::{
+        rate_limit::{RateLimiter, RequestKind},
         reconnect,
         reconnect::stream::{
             init_reconnecting_stream, ReconnectingStream, ReconnectionBackoffPolicy,
@@ -22,6 +23,9 @@ pub const STREAM_RECONNECTION_POLICY: ReconnectionBackoffPolicy = ReconnectionBa
     backoff_ms_initial: 125,
     backoff_multiplier: 2,
     backoff_ms_max: 60000,
+    backoff_jitter_pct: 0,
+    backoff_reset_ms: 0,
+    max_reconnection_attempts: None,
 };
 
 /// Convenient type alias for a [`MarketEvent`] [`Result`] consumed via a
@@ -37,12 +41,36 @@ pub type MarketStreamEvent<InstrumentKey, Kind> =
 /// Initialises a [`reconnecting`](`ReconnectingStream`) [`MarketStream`] using a collection of
 /// [`Subscription`]s.
 ///
-/// The provided [`ReconnectionBackoffPolicy`] dictates how the exponential backoff scales
-/// between reconnections.
+/// The provided [`ReconnectionBackoffPolicy`] dictates how the backoff scales between
+/// reconnections, and how many consecutive reconnection attempts may fail before the
+/// [`MarketStream`] is terminated.
 pub async fn init_market_stream<Exchange, Instrument, Kind>(
     policy: ReconnectionBackoffPolicy,
     subscriptions: Vec<Subscription<Exchange, Instrument, Kind>>,
 ) -> Result<impl Stream<Item = MarketStreamResult<Instrument::Key, Kind::Event>>, DataError>
+where
+    Exchange: StreamSelector<Instrument, Kind>,
+    Instrument: InstrumentData,
+    Kind: SubscriptionKind,
+    Subscription<Exchange, Instrument, Kind>:
+        Identifier<Exchange::Channel> + Identifier<Exchange::Market>,
+{
+    init_rate_limited_market_stream(policy, RateLimiter::default(), None, subscriptions).await
+}
+
+/// Initialises a [`reconnecting`](`ReconnectingStream`) [`MarketStream`] using a collection of
+/// [`Subscription`]s, waiting for the [`RateLimiter`] to permit each (re)connection.
+///
+/// If a staleness timeout is provided, a connection that does not yield any data within it is
+/// considered dead & reconnected.
+///
+/// See [`init_market_stream`] for details of the [`ReconnectionBackoffPolicy`].
+pub async fn init_rate_limited_market_stream<Exchange, Instrument, Kind>(
+    policy: ReconnectionBackoffPolicy,
+    rate_limiter: RateLimiter,
+    staleness_timeout: Option<std::time::Duration>,
+    subscriptions: Vec<Subscription<Exchange, Instrument, Kind>>,
+) -> Result<impl Stream<Item = MarketStreamResult<Instrument::Key, Kind::Event>>, DataError>
 where
     Exchange: StreamSelector<Instrument, Kind>,
     Instrument: InstrumentData,
@@ -72,11 +100,16 @@ where
 
     Ok(init_reconnecting_stream(move || {
         let subscriptions = subscriptions.clone();
-        async move { Exchange::Stream::init::<Exchange::SnapFetcher>(&subscriptions).await }
+        let rate_limiter = rate_limiter.clone();
+        async move {
+            rate_limiter.acquire(exchange, RequestKind::WebSocket).await;
+            Exchange::Stream::init::<Exchange::SnapFetcher>(&subscriptions).await
+        }
     })
     .await?
     .with_reconnect_backoff(policy, stream_key)
     .with_termination_on_error(|error| error.is_terminal(), stream_key)
+    .with_staleness_timeout(staleness_timeout, stream_key)
     .with_reconnection_events(exchange))
 }
 
@@ -0,0 +1,217 @@
+use crate::{
+    event::DataKind,
+    subscription::{candle::Candle, trade::PublicTrade},
+};
+use fnv::FnvHashMap;
+use std::{collections::VecDeque, hash::Hash};
+
+/// Market data that may be re-delivered by an exchange (eg/ the last message before a
+/// reconnect), and can be identified as a duplicate by a [`Deduplicator`].
+pub trait Deduplicate {
+    type Key: PartialEq;
+
+    /// Returns the key identifying duplicates of this data, or `None` if it should never be
+    /// considered a duplicate.
+    fn dedup_key(&self) -> Option<Self::Key>;
+}
+
+impl Deduplicate for PublicTrade {
+    type Key = String;
+
+    fn dedup_key(&self) -> Option<Self::Key> {
+        (!self.id.is_empty()).then(|| self.id.clone())
+    }
+}
+
+impl Deduplicate for Candle {
+    type Key = Candle;
+
+    /// An identical [`Candle`] (same close time & values) is a duplicate, so in-progress
+    /// [`Candle`] updates that change it's values are not suppressed.
+    fn dedup_key(&self) -> Option<Self::Key> {
+        Some(*self)
+    }
+}
+
+/// [`Deduplicate::Key`] of a [`DataKind`], only [`PublicTrade`]s & [`Candle`]s are
+/// de-duplicated.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DataKindKey {
+    Trade(String),
+    Candle(Candle),
+}
+
+impl Deduplicate for DataKind {
+    type Key = DataKindKey;
+
+    fn dedup_key(&self) -> Option<Self::Key> {
+        match self {
+            DataKind::Trade(trade) => trade.dedup_key().map(DataKindKey::Trade),
+            DataKind::Candle(candle) => candle.dedup_key().map(DataKindKey::Candle),
+            _ => None,
+        }
+    }
+}
+
+/// Drops duplicate market data by remembering the most recent [`Deduplicate::Key`]s seen for
+/// each instrument.
+///
+/// Memory is bounded by the `capacity` of the ring of keys kept per instrument, so a duplicate
+/// is only detected if it's original was one of the `capacity` most recent keys.
+#[derive(Clone, Debug)]
+pub struct Deduplicator<InstrumentKey, Key> {
+    capacity: usize,
+    seen: FnvHashMap<InstrumentKey, VecDeque<Key>>,
+}
+
+impl<InstrumentKey, Key> Deduplicator<InstrumentKey, Key>
+where
+    InstrumentKey: Eq + Hash + Clone,
+    Key: PartialEq,
+{
+    /// Construct a new [`Deduplicator`] remembering the provided number of keys per instrument.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            seen: FnvHashMap::default(),
+        }
+    }
+
+    /// Determines if the key has already been seen for the provided instrument, remembering it
+    /// if not.
+    pub fn is_duplicate(&mut self, instrument: &InstrumentKey, key: Key) -> bool {
+        let seen = match self.seen.get_mut(instrument) {
+            Some(seen) => seen,
+            None => self
+                .seen
+                .entry(instrument.clone())
+                .or_insert_with(|| VecDeque::with_capacity(self.capacity)),
+        };
+
+        if seen.contains(&key) {
+            return true;
+        }
+
+        if seen.len() == self.capacity {
+            seen.pop_front();
+        }
+        seen.push_back(key);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::MarketEvent,
+        streams::reconnect::{stream::ReconnectingStream, Event},
+    };
+    use barter_instrument::exchange::ExchangeId;
+    use barter_integration::Side;
+    use chrono::Utc;
+    use futures::StreamExt;
+
+    fn trade(
+        instrument: &'static str,
+        id: &str,
+    ) -> Event<ExchangeId, Result<MarketEvent<&'static str, PublicTrade>, ()>> {
+        Event::Item(Ok(MarketEvent {
+            time_exchange: Utc::now(),
+            time_received: Utc::now(),
+            exchange: ExchangeId::BinanceSpot,
+            instrument,
+            kind: PublicTrade {
+                id: id.to_string(),
+                price: 100.0,
+                amount: 1.0,
+                side: Side::Buy,
+            },
+        }))
+    }
+
+    fn trade_ids(
+        events: Vec<Event<ExchangeId, Result<MarketEvent<&'static str, PublicTrade>, ()>>>,
+    ) -> Vec<(&'static str, String)> {
+        events
+            .into_iter()
+            .filter_map(Event::into_item)
+            .map(|result| {
+                let market = result.unwrap();
+                (market.instrument, market.kind.id)
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_with_deduplication_drops_trades_redelivered_after_reconnect() {
+        let events = vec![
+            trade("btc_usdt", "1"),
+            trade("btc_usdt", "2"),
+            trade("eth_usdt", "2"),
+            Event::Reconnecting(ExchangeId::BinanceSpot),
+            Event::Reconnected {
+                origin: ExchangeId::BinanceSpot,
+                downtime: std::time::Duration::from_millis(125),
+            },
+            // Last message before the reconnect is re-delivered
+            trade("btc_usdt", "2"),
+            trade("btc_usdt", "3"),
+            trade("eth_usdt", "3"),
+        ];
+
+        let actual = futures::stream::iter(events)
+            .with_deduplication(2)
+            .collect::<Vec<_>>()
+            .await;
+
+        // Reconnection markers are passed through
+        assert_eq!(actual.len(), 7);
+        assert!(matches!(actual[3], Event::Reconnecting(_)));
+        assert!(matches!(actual[4], Event::Reconnected { .. }));
+
+        // Duplicates are tracked per instrument
+        assert_eq!(
+            trade_ids(actual),
+            vec![
+                ("btc_usdt", "1".to_string()),
+                ("btc_usdt", "2".to_string()),
+                ("eth_usdt", "2".to_string()),
+                ("btc_usdt", "3".to_string()),
+                ("eth_usdt", "3".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_deduplicator_is_bounded_by_capacity() {
+        let mut deduplicator = Deduplicator::new(2);
+
+        assert!(!deduplicator.is_duplicate(&"btc_usdt", 1));
+        assert!(!deduplicator.is_duplicate(&"btc_usdt", 2));
+        assert!(deduplicator.is_duplicate(&"btc_usdt", 1));
+        assert!(!deduplicator.is_duplicate(&"btc_usdt", 3));
+
+        // Key 1 has been evicted from the ring, so is no longer detected
+        assert!(!deduplicator.is_duplicate(&"btc_usdt", 1));
+
+        let candle = Candle {
+            close_time: Utc::now(),
+            open: 1.0,
+            high: 2.0,
+            low: 0.5,
+            close: 1.5,
+            volume: 10.0,
+            trade_count: 5,
+        };
+        let updated = Candle {
+            volume: 11.0,
+            ..candle
+        };
+        assert_eq!(
+            DataKind::Candle(candle).dedup_key(),
+            Some(DataKindKey::Candle(candle))
+        );
+        assert_ne!(candle.dedup_key(), updated.dedup_key());
+    }
+}
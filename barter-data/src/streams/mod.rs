@@ -1,4 +1,7 @@
-use self::builder::{multi::MultiStreamBuilder, StreamBuilder};
+use self::{
+    builder::{multi::MultiStreamBuilder, StreamBuilder},
+    shutdown::Shutdown,
+};
 use crate::subscription::SubscriptionKind;
 use barter_instrument::exchange::ExchangeId;
 use fnv::FnvHashMap;
@@ -15,14 +18,32 @@ pub mod builder;
 /// drive a re-connecting [`MarketStream`](super::MarketStream).
 pub mod consumer;
 
+/// Bounded [`Deduplicator`](dedup::Deduplicator) dropping market data re-delivered by an
+/// exchange, such as the last message before a reconnect.
+pub mod dedup;
+
+/// Token bucket [`RateLimiter`](rate_limit::RateLimiter) shared across every connection to an
+/// exchange, pacing subscription & REST requests.
+pub mod rate_limit;
+
 /// Defines a [`ReconnectingStream`] and associated logic for generating an auto reconnecting
 /// `Stream`.
 pub mod reconnect;
 
+/// Per-instrument [`Throttler`](throttle::Throttler) conflating high-frequency market data (eg/
+/// ticker updates) into at most one update per interval.
+pub mod throttle;
+
+/// Cloneable [`Shutdown`](shutdown::Shutdown) signal used to gracefully stop [`Streams`] and
+/// close their WebSocket connections.
+pub mod shutdown;
+
 /// Ergonomic collection of exchange [`MarketEvent<T>`](crate::event::MarketEvent) receivers.
 #[derive(Debug)]
 pub struct Streams<T> {
     pub streams: FnvHashMap<ExchangeId, mpsc::UnboundedReceiver<T>>,
+    /// [`Shutdown`] signal shared by every exchange `Stream` feeding these [`Streams`].
+    pub shutdown: Shutdown,
 }
 
 impl<T> Streams<T> {
@@ -48,6 +69,14 @@ impl<T> Streams<T> {
             .map(UnboundedReceiverStream::new)
     }
 
+    /// Gracefully shutdown every exchange `Stream`, closing their WebSocket connections &
+    /// aborting any pending reconnection attempts.
+    ///
+    /// Each exchange `Stream` yields any remaining buffered events before ending with `None`.
+    pub fn shutdown(&self) {
+        self.shutdown.shutdown()
+    }
+
     /// Select and merge every exchange `Stream` using [`select_all`].
     pub fn select_all(self) -> impl Stream<Item = T> {
         let all = self.streams.into_values().map(UnboundedReceiverStream::new);
@@ -0,0 +1,155 @@
+use barter_instrument::exchange::ExchangeId;
+use fnv::FnvHashMap;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{sync::Arc, time::Duration};
+use tokio::time::Instant;
+
+/// Kind of exchange request a [`RateLimit`] applies to, since exchanges often enforce different
+/// limits for REST requests & WebSocket subscriptions.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum RequestKind {
+    /// WebSocket connection & subscription requests.
+    WebSocket,
+    /// HTTP REST requests.
+    Rest,
+}
+
+/// Token bucket rate limit, permitting bursts of up to `burst` requests, with tokens refilled
+/// at a rate of `requests_per_second`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct RateLimit {
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+impl RateLimit {
+    /// Construct a new [`RateLimit`].
+    pub fn new(requests_per_second: f64, burst: u32) -> Self {
+        Self {
+            requests_per_second,
+            burst,
+        }
+    }
+}
+
+/// Token bucket rate limiter keyed by [`ExchangeId`] & [`RequestKind`].
+///
+/// Cloned [`RateLimiter`]s share the same buckets, so requests sent by every connection to the
+/// same exchange are paced together. Requests for an [`ExchangeId`] & [`RequestKind`] without a
+/// configured [`RateLimit`] are not limited.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimiter {
+    buckets: FnvHashMap<(ExchangeId, RequestKind), Arc<Mutex<TokenBucket>>>,
+}
+
+impl RateLimiter {
+    /// Construct a new [`RateLimiter`] without any configured [`RateLimit`]s.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Configure the [`RateLimit`] of [`RequestKind`] requests sent to the provided exchange.
+    pub fn with_limit(mut self, exchange: ExchangeId, kind: RequestKind, limit: RateLimit) -> Self {
+        self.buckets.insert(
+            (exchange, kind),
+            Arc::new(Mutex::new(TokenBucket::new(limit))),
+        );
+        self
+    }
+
+    /// Waits until a [`RequestKind`] request to the provided exchange is permitted by it's
+    /// [`RateLimit`].
+    pub async fn acquire(&self, exchange: ExchangeId, kind: RequestKind) {
+        let Some(bucket) = self.buckets.get(&(exchange, kind)) else {
+            return;
+        };
+
+        let send_at = bucket.lock().reserve(Instant::now());
+        tokio::time::sleep_until(send_at).await;
+    }
+}
+
+/// Token bucket state of a single [`RateLimit`].
+#[derive(Debug)]
+struct TokenBucket {
+    limit: RateLimit,
+    /// Available tokens, negative when future tokens have already been reserved.
+    tokens: f64,
+    refilled: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            refilled: Instant::now(),
+        }
+    }
+
+    /// Reserve a token, returning the time the request it permits may be sent.
+    fn reserve(&mut self, now: Instant) -> Instant {
+        let refill =
+            now.duration_since(self.refilled).as_secs_f64() * self.limit.requests_per_second;
+        self.tokens = (self.tokens + refill).min(f64::from(self.limit.burst));
+        self.refilled = now;
+
+        self.tokens -= 1.0;
+        if self.tokens >= 0.0 {
+            now
+        } else {
+            now + Duration::from_secs_f64(-self.tokens / self.limit.requests_per_second)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn test_rate_limiter_paces_requests_beyond_burst() {
+        let limiter = RateLimiter::new().with_limit(
+            ExchangeId::BinanceSpot,
+            RequestKind::WebSocket,
+            RateLimit::new(10.0, 2),
+        );
+        let start = Instant::now();
+
+        // Schedule 6 concurrent subscriptions on separate connections sharing the limiter
+        let handles = (0..6)
+            .map(|_| {
+                let limiter = limiter.clone();
+                tokio::spawn(async move {
+                    limiter
+                        .acquire(ExchangeId::BinanceSpot, RequestKind::WebSocket)
+                        .await;
+                    Instant::now().duration_since(start)
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut sent = Vec::new();
+        for handle in handles {
+            sent.push(handle.await.unwrap());
+        }
+        sent.sort();
+
+        // Burst of 2 sent immediately, remainder paced out every 100ms
+        let expected = [0, 0, 100, 200, 300, 400].map(Duration::from_millis);
+        for (actual, expected) in sent.into_iter().zip(expected) {
+            assert!(actual.abs_diff(expected) < Duration::from_millis(5));
+        }
+
+        // Other exchanges & request kinds are not limited
+        let start = Instant::now();
+        limiter
+            .acquire(ExchangeId::Okx, RequestKind::WebSocket)
+            .await;
+        limiter
+            .acquire(ExchangeId::BinanceSpot, RequestKind::Rest)
+            .await;
+        assert_eq!(Instant::now(), start);
+    }
+}
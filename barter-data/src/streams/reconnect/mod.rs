@@ -3,11 +3,20 @@ use serde::{Deserialize, Serialize};
 pub mod stream;
 
 /// [`ReconnectingStream`] `Event` that communicates either `Stream::Item`, or that the inner
-/// `Stream` is currently reconnecting.
+/// `Stream` has disconnected & reconnected.
+///
+/// The reconnection markers are interleaved in order with the `Stream::Item`s, so consumers can
+/// invalidate any state derived from the data preceding the gap (eg/ a local order book).
+/// Consumers that do not care can filter them out via [`Event::into_item`].
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub enum Event<Origin, T> {
     /// [`ReconnectingStream`] has disconnecting and is attempting to reconnect.
     Reconnecting(Origin),
+    /// [`ReconnectingStream`] has reconnected after being disconnected for the `downtime`.
+    Reconnected {
+        origin: Origin,
+        downtime: std::time::Duration,
+    },
     Item(T),
 }
 
@@ -18,9 +27,18 @@ impl<Origin, T> Event<Origin, T> {
     {
         match self {
             Event::Reconnecting(origin) => Event::Reconnecting(origin),
+            Event::Reconnected { origin, downtime } => Event::Reconnected { origin, downtime },
             Event::Item(item) => Event::Item(op(item)),
         }
     }
+
+    /// Returns the `Stream::Item`, or `None` if this [`Event`] is a reconnection marker.
+    pub fn into_item(self) -> Option<T> {
+        match self {
+            Event::Item(item) => Some(item),
+            Event::Reconnecting(_) | Event::Reconnected { .. } => None,
+        }
+    }
 }
 
 impl<Origin, T, E> Event<Origin, Result<T, E>> {
@@ -30,6 +48,7 @@ impl<Origin, T, E> Event<Origin, Result<T, E>> {
     {
         match self {
             Event::Reconnecting(origin) => Event::Reconnecting(origin),
+            Event::Reconnected { origin, downtime } => Event::Reconnected { origin, downtime },
             Event::Item(result) => Event::Item(result.map(op)),
         }
     }
@@ -40,6 +59,7 @@ impl<Origin, T, E> Event<Origin, Result<T, E>> {
     {
         match self {
             Event::Reconnecting(origin) => Event::Reconnecting(origin),
+            Event::Reconnected { origin, downtime } => Event::Reconnected { origin, downtime },
             Event::Item(result) => Event::Item(result.map_err(op)),
         }
     }
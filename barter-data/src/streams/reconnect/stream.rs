@@ -1,9 +1,27 @@
-use crate::streams::{consumer::StreamKey, reconnect::Event};
+use crate::{
+    event::MarketEvent,
+    streams::{
+        consumer::{StreamKey, STREAM_RECONNECTION_POLICY},
+        dedup::{Deduplicate, Deduplicator},
+        reconnect::Event,
+        shutdown::Shutdown,
+        throttle::Throttler,
+    },
+};
 use derive_more::{Constructor, From};
 use futures::Stream;
 use futures_util::StreamExt;
+use parking_lot::Mutex;
+use rand::Rng;
 use serde::{Deserialize, Serialize};
-use std::{convert, fmt::Debug, future, future::Future};
+use std::{
+    convert,
+    fmt::{Debug, Display},
+    future,
+    future::Future,
+    hash::Hash,
+    sync::Arc,
+};
 use tokio::{sync::mpsc, task::JoinHandle};
 use tracing::{error, info, warn};
 
@@ -13,8 +31,13 @@ pub trait ReconnectingStream
 where
     Self: Stream + Sized,
 {
-    /// Add an exponential backoff policy to an initialised [`ReconnectingStream`] using the
-    /// provided [`ReconnectionBackoffPolicy`].
+    /// Add a backoff policy to an initialised [`ReconnectingStream`] using the provided
+    /// [`ReconnectionBackoffPolicy`].
+    ///
+    /// The backoff duration scales between consecutive failed reconnection attempts, and only
+    /// resets once a successful connection stays up for the policy's reset threshold. If the
+    /// policy's maximum consecutive reconnection attempts fail, a terminal error is logged and
+    /// the [`ReconnectingStream`] ends.
     fn with_reconnect_backoff<St, InitError>(
         self,
         policy: ReconnectionBackoffPolicy,
@@ -28,25 +51,40 @@ where
         self.enumerate()
             .scan(
                 ReconnectionState::from(policy),
-                move |state, (attempt, result)| match result {
-                    Ok(stream) => {
-                        info!(attempt, ?stream_key, "successfully initialised Stream");
-                        state.reset_backoff();
-                        futures::future::Either::Left(future::ready(Some(Ok(stream))))
-                    }
-                    Err(error) => {
-                        warn!(
-                            attempt,
-                            ?stream_key,
-                            ?error,
-                            "failed to re-initialise Stream"
-                        );
-                        let sleep_fut = state.generate_sleep_future();
-                        state.multiply_backoff();
-                        futures::future::Either::Right(Box::pin(async move {
-                            sleep_fut.await;
-                            Some(Err(error))
-                        }))
+                move |state, (attempt, result)| {
+                    // Reset backoff if the previous connection stayed up past the threshold
+                    state.reset_backoff_if_stable(tokio::time::Instant::now());
+
+                    match result {
+                        Ok(stream) => {
+                            info!(attempt, ?stream_key, "successfully initialised Stream");
+                            state.connected_at = Some(tokio::time::Instant::now());
+                            futures::future::Either::Left(future::ready(Some(Ok(stream))))
+                        }
+                        Err(error) if state.record_failure() => {
+                            error!(
+                                attempt,
+                                ?stream_key,
+                                ?error,
+                                failures = state.failures,
+                                "failed to re-initialise Stream after maximum reconnection attempts, terminating"
+                            );
+                            futures::future::Either::Left(future::ready(None))
+                        }
+                        Err(error) => {
+                            warn!(
+                                attempt,
+                                ?stream_key,
+                                ?error,
+                                "failed to re-initialise Stream"
+                            );
+                            let sleep_fut = tokio::time::sleep(state.backoff_duration());
+                            state.multiply_backoff();
+                            futures::future::Either::Right(Box::pin(async move {
+                                sleep_fut.await;
+                                Some(Err(error))
+                            }))
+                        }
                     }
                 },
             )
@@ -83,8 +121,45 @@ where
         })
     }
 
+    /// Terminates the inner [`Stream`] if it does not yield an item within the provided staleness
+    /// timeout, detecting connections that have silently stopped sending data without closing.
+    /// This will cause the [`ReconnectingStream`] to re-initialise the inner [`Stream`].
+    ///
+    /// The timeout applies per connection & is reset by every item it yields, so a connection
+    /// multiplexing several [`Subscription`](crate::subscription::Subscription)s stays alive while
+    /// any of them are active. A `None` timeout disables the watchdog.
+    fn with_staleness_timeout<St>(
+        self,
+        timeout: Option<std::time::Duration>,
+        stream_key: StreamKey,
+    ) -> impl Stream<Item = impl Stream<Item = St::Item>>
+    where
+        Self: Stream<Item = St>,
+        St: Stream,
+    {
+        self.map(move |stream| match timeout {
+            Some(timeout) => futures::future::Either::Left(tokio_stream::StreamExt::map_while(
+                tokio_stream::StreamExt::timeout(stream, timeout),
+                move |result| match result {
+                    Ok(item) => Some(item),
+                    Err(_) => {
+                        warn!(
+                            ?stream_key,
+                            ?timeout,
+                            "MarketStream exceeded staleness timeout without data, reconnecting"
+                        );
+                        None
+                    }
+                },
+            )),
+            None => futures::future::Either::Right(stream),
+        })
+    }
+
     /// Maps every [`ReconnectingStream`] `Stream::Item` into an [`reconnect::Event::Item`](Event),
-    /// and chain a [`reconnect::Event::Reconnecting`](Event)
+    /// chain a [`reconnect::Event::Reconnecting`](Event) when each inner [`Stream`] ends, and
+    /// precede every reconnected inner [`Stream`] with a
+    /// [`reconnect::Event::Reconnected`](Event) communicating the downtime.
     fn with_reconnection_events<St, Origin>(
         self,
         origin: Origin,
@@ -94,12 +169,28 @@ where
         St: Stream,
         Origin: Clone + 'static,
     {
+        let disconnected_at = Arc::new(Mutex::new(None::<tokio::time::Instant>));
+
         self.map(move |stream| {
-            stream
-                .map(Event::Item)
-                .chain(futures::stream::once(future::ready(Event::Reconnecting(
-                    origin.clone(),
-                ))))
+            // Every inner Stream after the first is preceded by a Reconnected marker
+            let reconnected =
+                disconnected_at
+                    .lock()
+                    .take()
+                    .map(|disconnected_at| Event::Reconnected {
+                        origin: origin.clone(),
+                        downtime: disconnected_at.elapsed(),
+                    });
+
+            let disconnected_at = Arc::clone(&disconnected_at);
+            let origin = origin.clone();
+
+            futures::stream::iter(reconnected)
+                .chain(stream.map(Event::Item))
+                .chain(futures::stream::once(futures::future::lazy(move |_| {
+                    *disconnected_at.lock() = Some(tokio::time::Instant::now());
+                    Event::Reconnecting(origin)
+                })))
         })
         .flatten()
     }
@@ -118,6 +209,9 @@ where
         self.filter_map(move |event| {
             std::future::ready(match event {
                 Event::Reconnecting(origin) => Some(Event::Reconnecting(origin)),
+                Event::Reconnected { origin, downtime } => {
+                    Some(Event::Reconnected { origin, downtime })
+                }
                 Event::Item(Ok(item)) => Some(Event::Item(item)),
                 Event::Item(Err(error)) => {
                     op(error);
@@ -127,6 +221,155 @@ where
         })
     }
 
+    /// Handles encountered errors with the provided closure before filtering them out, similar to
+    /// [`Self::with_error_handler`], but aggregates identical errors (same `Display` output) that
+    /// occur within the provided `window`, even if interleaved with other errors.
+    ///
+    /// The closure is called once per aggregation window of each distinct error, with the first
+    /// error encountered and the number of identical errors that occurred. Useful for avoiding log
+    /// floods during a storm of errors on repeated reconnects.
+    ///
+    /// The aggregated error is handled once it's window closes, even if the upstream [`Stream`]
+    /// has since gone idle, & any remaining aggregated error is handled once it ends.
+    fn with_error_handler_aggregated<FnOnErr, Origin, T, E>(
+        self,
+        window: std::time::Duration,
+        op: FnOnErr,
+    ) -> impl Stream<Item = Event<Origin, T>>
+    where
+        Self: Stream<Item = Event<Origin, Result<T, E>>>,
+        E: Display,
+        FnOnErr: Fn(E, u64) + 'static,
+    {
+        let state = (Box::pin(self), ErrorAggregator::new(window), op);
+
+        futures::stream::unfold(state, |(mut stream, mut aggregator, op)| async move {
+            loop {
+                let deadline = aggregator.deadline();
+                let event = tokio::select! {
+                    event = stream.next() => event,
+                    _ = tokio::time::sleep_until(deadline.unwrap_or_else(tokio::time::Instant::now)),
+                        if deadline.is_some() => {
+                        // Emit the aggregated errors whose window has elapsed while idle
+                        for (error, count) in aggregator.expire(tokio::time::Instant::now()) {
+                            op(error, count);
+                        }
+                        continue;
+                    }
+                };
+
+                // Emit any aggregated errors whose window has elapsed
+                let now = tokio::time::Instant::now();
+                for (error, count) in aggregator.expire(now) {
+                    op(error, count);
+                }
+
+                let output = match event {
+                    Some(Event::Reconnecting(origin)) => Event::Reconnecting(origin),
+                    Some(Event::Reconnected { origin, downtime }) => {
+                        Event::Reconnected { origin, downtime }
+                    }
+                    Some(Event::Item(Ok(item))) => Event::Item(item),
+                    Some(Event::Item(Err(error))) => {
+                        if let Some((error, count)) = aggregator.record(error, now) {
+                            op(error, count);
+                        }
+                        continue;
+                    }
+                    // Stream has ended, so emit any remaining aggregated errors
+                    None => {
+                        for (error, count) in aggregator.flush() {
+                            op(error, count);
+                        }
+                        return None;
+                    }
+                };
+
+                return Some((output, (stream, aggregator, op)));
+            }
+        })
+    }
+
+    /// Drops duplicate [`MarketEvent`]s (eg/ trades re-delivered after a reconnect) using a
+    /// [`Deduplicator`] that remembers the `capacity` most recent [`Deduplicate::Key`]s of each
+    /// instrument. Reconnection markers & errors are passed through untouched.
+    fn with_deduplication<Origin, InstrumentKey, T, E>(
+        self,
+        capacity: usize,
+    ) -> impl Stream<Item = Event<Origin, Result<MarketEvent<InstrumentKey, T>, E>>>
+    where
+        Self: Stream<Item = Event<Origin, Result<MarketEvent<InstrumentKey, T>, E>>>,
+        InstrumentKey: Eq + Hash + Clone,
+        T: Deduplicate,
+    {
+        self.scan(Deduplicator::new(capacity), |deduplicator, event| {
+            let is_duplicate = match &event {
+                Event::Item(Ok(market)) => market
+                    .kind
+                    .dedup_key()
+                    .is_some_and(|key| deduplicator.is_duplicate(&market.instrument, key)),
+                _ => false,
+            };
+
+            future::ready(Some((!is_duplicate).then_some(event)))
+        })
+        .filter_map(future::ready)
+    }
+
+    /// Conflates high-frequency [`MarketEvent`]s (eg/ ticker updates) using a [`Throttler`], so
+    /// at most the latest [`MarketEvent`] of each instrument is emitted once per `interval`.
+    ///
+    /// Conflated [`MarketEvent`]s are replaced rather than buffered, & the latest is emitted once
+    /// it's window closes, even if the upstream [`Stream`] has since gone idle. Reconnection
+    /// markers & errors are passed through untouched, & pending [`MarketEvent`]s are flushed
+    /// once the upstream [`Stream`] ends.
+    fn with_throttle<Origin, InstrumentKey, T, E>(
+        self,
+        interval: std::time::Duration,
+    ) -> impl Stream<Item = Event<Origin, Result<MarketEvent<InstrumentKey, T>, E>>>
+    where
+        Self: Stream<Item = Event<Origin, Result<MarketEvent<InstrumentKey, T>, E>>>,
+        InstrumentKey: PartialEq + Clone,
+    {
+        let state = (Box::pin(self), Throttler::new(interval), false);
+
+        futures::stream::unfold(state, |(mut stream, mut throttler, mut ended)| async move {
+            loop {
+                if let Some(market) = throttler.pop_closed(tokio::time::Instant::now()) {
+                    return Some((Event::Item(Ok(market)), (stream, throttler, ended)));
+                }
+
+                if ended {
+                    return throttler
+                        .pop_any()
+                        .map(|market| (Event::Item(Ok(market)), (stream, throttler, ended)));
+                }
+
+                let deadline = throttler.next_deadline();
+                tokio::select! {
+                    event = stream.next() => match event {
+                        Some(Event::Item(Ok(market))) => {
+                            let instrument = market.instrument.clone();
+                            throttler.update(instrument, market, tokio::time::Instant::now());
+                        }
+                        Some(event) => return Some((event, (stream, throttler, ended))),
+                        None => ended = true,
+                    },
+                    _ = tokio::time::sleep_until(deadline.unwrap_or_else(tokio::time::Instant::now)),
+                        if deadline.is_some() => {}
+                }
+            }
+        })
+    }
+
+    /// Ends the [`ReconnectingStream`] once the provided [`Shutdown`] is signalled.
+    ///
+    /// Any active inner [`Stream`] & pending reconnection attempt (including it's backoff) are
+    /// dropped once the [`ReconnectingStream`] is, so no further reconnections are attempted.
+    fn with_shutdown(self, shutdown: Shutdown) -> impl Stream<Item = Self::Item> {
+        self.take_until(async move { shutdown.wait().await })
+    }
+
     /// Spawn a task to forward items in [`Self`] to the provided channel transmitter.
     fn forward_to<T>(mut self, tx: mpsc::UnboundedSender<T>) -> JoinHandle<()>
     where
@@ -160,6 +403,9 @@ where
 }
 
 /// Reconnection backoff policy for a [`ReconnectingStream::with_reconnect_backoff`].
+///
+/// Supports fixed (`backoff_multiplier` of 1) & exponential backoff, capped at `backoff_ms_max`,
+/// with optional random jitter.
 #[derive(
     Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize, Constructor,
 )]
@@ -176,12 +422,53 @@ pub struct ReconnectionBackoffPolicy {
 
     /// Maximum possible backoff duration between reconnection attempts.
     pub backoff_ms_max: u64,
+
+    /// Maximum random jitter applied to each backoff duration, as a percentage of it (eg/ 10 for
+    /// +/- 10%). Avoids many `Stream`s reconnecting in lockstep after an outage.
+    #[serde(default)]
+    pub backoff_jitter_pct: u8,
+
+    /// Minimum millisecond duration a successful connection must stay up before the backoff
+    /// duration & failed attempt count are reset. A value of 0 resets after every successful
+    /// connection.
+    #[serde(default)]
+    pub backoff_reset_ms: u64,
+
+    /// Maximum consecutive failed reconnection attempts before the `Stream` is terminated. `None`
+    /// retries forever.
+    #[serde(default)]
+    pub max_reconnection_attempts: Option<u32>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
+impl ReconnectionBackoffPolicy {
+    /// Constructs a fixed [`ReconnectionBackoffPolicy`] that waits the same millisecond duration
+    /// between every reconnection attempt, retrying forever.
+    pub fn fixed(backoff_ms: u64) -> Self {
+        Self {
+            backoff_ms_initial: backoff_ms,
+            backoff_multiplier: 1,
+            backoff_ms_max: backoff_ms,
+            backoff_jitter_pct: 0,
+            backoff_reset_ms: 0,
+            max_reconnection_attempts: None,
+        }
+    }
+}
+
+impl Default for ReconnectionBackoffPolicy {
+    fn default() -> Self {
+        STREAM_RECONNECTION_POLICY
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 struct ReconnectionState {
     policy: ReconnectionBackoffPolicy,
     backoff_ms_current: u64,
+    /// Consecutive failed reconnection attempts since the backoff was last reset.
+    failures: u32,
+    /// Time the most recent successful connection was established.
+    connected_at: Option<tokio::time::Instant>,
 }
 
 impl From<ReconnectionBackoffPolicy> for ReconnectionState {
@@ -189,6 +476,8 @@ impl From<ReconnectionBackoffPolicy> for ReconnectionState {
         Self {
             backoff_ms_current: policy.backoff_ms_initial,
             policy,
+            failures: 0,
+            connected_at: None,
         }
     }
 }
@@ -196,6 +485,29 @@ impl From<ReconnectionBackoffPolicy> for ReconnectionState {
 impl ReconnectionState {
     fn reset_backoff(&mut self) {
         self.backoff_ms_current = self.policy.backoff_ms_initial;
+        self.failures = 0;
+    }
+
+    /// Resets the backoff if the most recent successful connection stayed up for at least the
+    /// policy's reset threshold.
+    fn reset_backoff_if_stable(&mut self, now: tokio::time::Instant) {
+        let Some(connected_at) = self.connected_at.take() else {
+            return;
+        };
+
+        let reset_threshold = std::time::Duration::from_millis(self.policy.backoff_reset_ms);
+        if now.duration_since(connected_at) >= reset_threshold {
+            self.reset_backoff();
+        }
+    }
+
+    /// Records a failed reconnection attempt, returning true if the maximum consecutive
+    /// reconnection attempts have been exhausted.
+    fn record_failure(&mut self) -> bool {
+        self.failures += 1;
+        self.policy
+            .max_reconnection_attempts
+            .is_some_and(|max| self.failures >= max)
     }
 
     fn multiply_backoff(&mut self) {
@@ -204,8 +516,551 @@ impl ReconnectionState {
         self.backoff_ms_current = next_capped;
     }
 
-    fn generate_sleep_future(&self) -> tokio::time::Sleep {
-        let sleep_duration = std::time::Duration::from_millis(self.backoff_ms_current);
-        tokio::time::sleep(sleep_duration)
+    /// Current backoff duration, with random jitter applied.
+    fn backoff_duration(&self) -> std::time::Duration {
+        let jitter_ms = self.backoff_ms_current * self.policy.backoff_jitter_pct as u64 / 100;
+        let backoff_ms = match jitter_ms {
+            0 => self.backoff_ms_current,
+            jitter_ms => rand::thread_rng().gen_range(
+                self.backoff_ms_current.saturating_sub(jitter_ms)
+                    ..=self.backoff_ms_current + jitter_ms,
+            ),
+        };
+
+        std::time::Duration::from_millis(backoff_ms)
+    }
+}
+
+/// Aggregates identical errors (same `Display` output) encountered within a time window. Used by
+/// [`ReconnectingStream::with_error_handler_aggregated`].
+///
+/// Each distinct error has it's own window, so interleaved errors are aggregated separately. The
+/// number of distinct errors is expected to be small, so they are stored in insertion order &
+/// looked up linearly.
+#[derive(Debug)]
+struct ErrorAggregator<E> {
+    window: std::time::Duration,
+    aggregates: Vec<AggregatedError<E>>,
+}
+
+#[derive(Debug)]
+struct AggregatedError<E> {
+    error: E,
+    key: String,
+    count: u64,
+    window_start: tokio::time::Instant,
+}
+
+impl<E> ErrorAggregator<E>
+where
+    E: Display,
+{
+    fn new(window: std::time::Duration) -> Self {
+        Self {
+            window,
+            aggregates: Vec::new(),
+        }
+    }
+
+    /// Record the next error. Returns the previously aggregated identical error & it's count if
+    /// it's window has elapsed.
+    fn record(&mut self, error: E, now: tokio::time::Instant) -> Option<(E, u64)> {
+        let key = error.to_string();
+        let next = AggregatedError {
+            error,
+            key,
+            count: 1,
+            window_start: now,
+        };
+
+        let Some(index) = self
+            .aggregates
+            .iter()
+            .position(|aggregate| aggregate.key == next.key)
+        else {
+            self.aggregates.push(next);
+            return None;
+        };
+
+        let current = &mut self.aggregates[index];
+        if now.duration_since(current.window_start) < self.window {
+            current.count += 1;
+            return None;
+        }
+
+        // Window has elapsed, so start a new window (ordered after the other aggregates)
+        let previous = self.aggregates.remove(index);
+        self.aggregates.push(next);
+        Some((previous.error, previous.count))
+    }
+
+    /// Returns the instant the earliest aggregated error window closes, if any.
+    fn deadline(&self) -> Option<tokio::time::Instant> {
+        self.aggregates
+            .iter()
+            .map(|aggregate| aggregate.window_start + self.window)
+            .min()
+    }
+
+    /// Returns the aggregated errors & their counts whose window has elapsed.
+    fn expire(&mut self, now: tokio::time::Instant) -> Vec<(E, u64)> {
+        let (expired, active) = std::mem::take(&mut self.aggregates)
+            .into_iter()
+            .partition::<Vec<_>, _>(|aggregate| {
+                now.duration_since(aggregate.window_start) >= self.window
+            });
+
+        self.aggregates = active;
+        expired
+            .into_iter()
+            .map(|aggregate| (aggregate.error, aggregate.count))
+            .collect()
+    }
+
+    /// Returns every aggregated error & it's count.
+    fn flush(&mut self) -> Vec<(E, u64)> {
+        self.aggregates
+            .drain(..)
+            .map(|aggregate| (aggregate.error, aggregate.count))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    #[tokio::test]
+    async fn test_with_error_handler_aggregated() {
+        let mut events = (0..100)
+            .map(|_| Event::Item(Err::<u64, _>("connection refused")))
+            .collect::<Vec<Event<&str, _>>>();
+        events.push(Event::Reconnecting("origin"));
+        events.push(Event::Item(Ok(1)));
+
+        let aggregated = Arc::new(Mutex::new(Vec::new()));
+        let aggregated_handler = Arc::clone(&aggregated);
+
+        let actual = futures::stream::iter(events)
+            .with_error_handler_aggregated(Duration::from_secs(60), move |error, count| {
+                aggregated_handler.lock().unwrap().push((error, count))
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(actual, vec![Event::Reconnecting("origin"), Event::Item(1)]);
+        assert_eq!(
+            *aggregated.lock().unwrap(),
+            vec![("connection refused", 100)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_with_error_handler_aggregated_interleaved_errors() {
+        let events = (0..100)
+            .map(|index| match index % 2 {
+                0 => Event::Item(Err::<u64, _>("connection refused")),
+                _ => Event::Item(Err("connection reset")),
+            })
+            .collect::<Vec<Event<&str, _>>>();
+
+        let aggregated = Arc::new(Mutex::new(Vec::new()));
+        let aggregated_handler = Arc::clone(&aggregated);
+
+        let actual = futures::stream::iter(events)
+            .with_error_handler_aggregated(Duration::from_secs(60), move |error, count| {
+                aggregated_handler.lock().unwrap().push((error, count))
+            })
+            .collect::<Vec<_>>()
+            .await;
+
+        assert!(actual.is_empty());
+        assert_eq!(
+            *aggregated.lock().unwrap(),
+            vec![("connection refused", 50), ("connection reset", 50)]
+        );
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_error_handler_aggregated_flushes_window_while_idle() {
+        let aggregated = Arc::new(Mutex::new(Vec::new()));
+        let aggregated_handler = Arc::clone(&aggregated);
+
+        // Errors followed by a Stream that stays idle for longer than the window
+        let events =
+            futures::stream::iter((0..3).map(|_| Event::Item(Err::<u64, _>("connection refused"))))
+                .chain(futures::stream::pending::<Event<&str, _>>());
+
+        let mut stream = Box::pin(
+            events.with_error_handler_aggregated(Duration::from_secs(60), move |error, count| {
+                aggregated_handler.lock().unwrap().push((error, count))
+            }),
+        );
+
+        // Aggregated error is not emitted before it's window closes
+        let next = tokio::time::timeout(Duration::from_secs(59), stream.next()).await;
+        assert!(next.is_err());
+        assert!(aggregated.lock().unwrap().is_empty());
+
+        // Aggregated error is emitted once it's window closes, without awaiting another error
+        let next = tokio::time::timeout(Duration::from_secs(2), stream.next()).await;
+        assert!(next.is_err());
+        assert_eq!(*aggregated.lock().unwrap(), vec![("connection refused", 3)]);
+    }
+
+    #[test]
+    fn test_error_aggregator_record() {
+        let start = tokio::time::Instant::now();
+        let window = Duration::from_secs(2);
+        let half = window / 2;
+        let mut aggregator = ErrorAggregator::new(window);
+
+        // Identical errors within window are aggregated, even if interleaved
+        assert_eq!(aggregator.record("a", start), None);
+        assert_eq!(aggregator.record("b", start + half), None);
+        assert_eq!(aggregator.record("a", start + half), None);
+        assert_eq!(aggregator.record("b", start + half), None);
+        assert_eq!(aggregator.deadline(), Some(start + window));
+
+        // Identical error after it's window has elapsed emits the previous aggregate
+        assert_eq!(aggregator.record("a", start + window), Some(("a", 2)));
+        assert_eq!(aggregator.deadline(), Some(start + half + window));
+
+        // Each aggregate expires once it's own window has elapsed
+        assert_eq!(aggregator.expire(start + window), vec![]);
+        assert_eq!(aggregator.expire(start + half + window), vec![("b", 2)]);
+        assert_eq!(aggregator.flush(), vec![("a", 1)]);
+        assert_eq!(aggregator.deadline(), None);
+    }
+
+    /// Runs a [`ReconnectingStream`] over a mock transport until it terminates, where each
+    /// connection attempt either fails (`None`), or succeeds with a connection that stays up for
+    /// the provided milliseconds. Returns the milliseconds elapsed between each attempt.
+    async fn observed_reconnection_delays(
+        policy: ReconnectionBackoffPolicy,
+        outcomes: Vec<Option<u64>>,
+    ) -> Vec<u64> {
+        let attempts = Arc::new(Mutex::new(Vec::new()));
+        let attempts_init = Arc::clone(&attempts);
+
+        let init_stream = move || {
+            let mut attempts = attempts_init.lock().unwrap();
+            let outcome = outcomes[attempts.len()];
+            attempts.push(tokio::time::Instant::now());
+
+            async move {
+                match outcome {
+                    Some(uptime_ms) => Ok(futures::stream::once(tokio::time::sleep(
+                        Duration::from_millis(uptime_ms),
+                    ))
+                    .filter_map(|_| future::ready(None::<u64>))),
+                    None => Err("connection refused"),
+                }
+            }
+        };
+
+        let stream_key = StreamKey {
+            exchange: barter_instrument::exchange::ExchangeId::BinanceSpot,
+            kind: "mock",
+        };
+
+        init_reconnecting_stream(init_stream)
+            .await
+            .unwrap()
+            .with_reconnect_backoff(policy, stream_key)
+            .flatten()
+            .collect::<Vec<_>>()
+            .await;
+
+        let attempts = attempts.lock().unwrap();
+        attempts
+            .windows(2)
+            .map(|window| (window[1] - window[0]).as_millis() as u64)
+            .collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_reconnect_backoff_exponential_schedule_resets_after_success() {
+        let policy = ReconnectionBackoffPolicy {
+            backoff_ms_initial: 100,
+            backoff_multiplier: 2,
+            backoff_ms_max: 300,
+            backoff_jitter_pct: 0,
+            backoff_reset_ms: 0,
+            max_reconnection_attempts: Some(4),
+        };
+
+        // Initial connection, 3 failures, success, then 4 failures terminate the Stream
+        let outcomes = vec![Some(0), None, None, None, Some(0), None, None, None, None];
+
+        let actual = observed_reconnection_delays(policy, outcomes).await;
+
+        // Backoff doubles until capped at 300ms, and the success resets it
+        assert_eq!(actual, vec![0, 100, 200, 300, 0, 100, 200, 300]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_reconnect_backoff_only_resets_after_stable_connection() {
+        let policy = ReconnectionBackoffPolicy {
+            backoff_ms_initial: 100,
+            backoff_multiplier: 2,
+            backoff_ms_max: 60000,
+            backoff_jitter_pct: 0,
+            backoff_reset_ms: 1000,
+            max_reconnection_attempts: Some(3),
+        };
+
+        // Short-lived 500ms connection does not reset the backoff, but a 2000ms one does
+        let outcomes = vec![Some(0), None, Some(500), None, Some(2000), None, None, None];
+
+        let actual = observed_reconnection_delays(policy, outcomes).await;
+
+        assert_eq!(actual, vec![0, 100, 500, 200, 2000, 100, 200]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_reconnect_backoff_fixed_schedule() {
+        let policy = ReconnectionBackoffPolicy {
+            max_reconnection_attempts: Some(4),
+            ..ReconnectionBackoffPolicy::fixed(250)
+        };
+
+        let actual =
+            observed_reconnection_delays(policy, vec![Some(0), None, None, None, None]).await;
+
+        assert_eq!(actual, vec![0, 250, 250, 250]);
+    }
+
+    #[test]
+    fn test_reconnection_state_backoff_duration_with_jitter() {
+        let state = ReconnectionState::from(ReconnectionBackoffPolicy {
+            backoff_jitter_pct: 10,
+            ..ReconnectionBackoffPolicy::fixed(1000)
+        });
+
+        for _ in 0..100 {
+            let backoff_ms = state.backoff_duration().as_millis();
+            assert!(
+                (900..=1100).contains(&backoff_ms),
+                "backoff_ms: {backoff_ms}"
+            );
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_staleness_timeout_reconnects_silent_stream() {
+        let connections = Arc::new(Mutex::new(Vec::new()));
+        let connections_init = Arc::clone(&connections);
+
+        // First connection yields an item every 400ms, then goes silent without closing
+        let init_stream = move || {
+            let connections = Arc::clone(&connections_init);
+            async move {
+                let connection = {
+                    let mut connections = connections.lock().unwrap();
+                    connections.push(tokio::time::Instant::now());
+                    connections.len()
+                };
+
+                let items = match connection {
+                    1 => vec![1, 2, 3],
+                    _ => vec![4],
+                };
+
+                Ok::<_, &str>(
+                    futures::stream::iter(items)
+                        .then(|item| async move {
+                            tokio::time::sleep(Duration::from_millis(400)).await;
+                            item
+                        })
+                        .chain(futures::stream::pending())
+                        .boxed(),
+                )
+            }
+        };
+
+        let stream_key = StreamKey {
+            exchange: barter_instrument::exchange::ExchangeId::BinanceSpot,
+            kind: "mock",
+        };
+        let start = tokio::time::Instant::now();
+
+        let actual = init_reconnecting_stream(init_stream)
+            .await
+            .unwrap()
+            .filter_map(|result| future::ready(result.ok()))
+            .with_staleness_timeout(Some(Duration::from_millis(500)), stream_key)
+            .with_reconnection_events("origin")
+            .take(6)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                Event::Item(1),
+                Event::Item(2),
+                Event::Item(3),
+                Event::Reconnecting("origin"),
+                Event::Reconnected {
+                    origin: "origin",
+                    downtime: Duration::ZERO,
+                },
+                Event::Item(4),
+            ]
+        );
+
+        // Active data reset the watchdog, so the reconnect only occurred 500ms after the last item
+        let connections = connections.lock().unwrap();
+        assert_eq!(connections.len(), 2);
+        assert_eq!(connections[1] - start, Duration::from_millis(1700));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_reconnection_events_interleaves_markers_in_order() {
+        let connections = Arc::new(Mutex::new(0));
+
+        // Mock transport drops once after two items, and takes 300ms to reconnect
+        let init_stream = move || {
+            let connections = Arc::clone(&connections);
+            async move {
+                let connection = {
+                    let mut connections = connections.lock().unwrap();
+                    *connections += 1;
+                    *connections
+                };
+
+                match connection {
+                    1 => Ok::<_, &str>(futures::stream::iter(vec![1, 2]).boxed()),
+                    2 => {
+                        tokio::time::sleep(Duration::from_millis(300)).await;
+                        Ok(futures::stream::iter(vec![3]).boxed())
+                    }
+                    _ => Ok(futures::stream::pending().boxed()),
+                }
+            }
+        };
+
+        let actual = init_reconnecting_stream(init_stream)
+            .await
+            .unwrap()
+            .filter_map(|result| future::ready(result.ok()))
+            .with_reconnection_events("origin")
+            .take(7)
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(
+            actual,
+            vec![
+                Event::Item(1),
+                Event::Item(2),
+                Event::Reconnecting("origin"),
+                Event::Reconnected {
+                    origin: "origin",
+                    downtime: Duration::from_millis(300),
+                },
+                Event::Item(3),
+                Event::Reconnecting("origin"),
+                Event::Reconnected {
+                    origin: "origin",
+                    downtime: Duration::ZERO,
+                },
+            ]
+        );
+
+        // Consumers that do not care about reconnections can trivially filter them out
+        let items = actual
+            .into_iter()
+            .filter_map(Event::into_item)
+            .collect::<Vec<_>>();
+        assert_eq!(items, vec![1, 2, 3]);
+    }
+
+    fn mock_stream_key() -> StreamKey {
+        StreamKey {
+            exchange: barter_instrument::exchange::ExchangeId::BinanceSpot,
+            kind: "mock",
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_shutdown_ends_active_stream() {
+        let shutdown = Shutdown::new();
+
+        // Connection yields a single item & then stays open without sending any more data
+        let init_stream = || async {
+            Ok::<_, &str>(futures::stream::iter([1u64]).chain(futures::stream::pending()))
+        };
+
+        let mut stream = Box::pin(
+            init_reconnecting_stream(init_stream)
+                .await
+                .unwrap()
+                .with_reconnect_backoff(ReconnectionBackoffPolicy::fixed(100), mock_stream_key())
+                .with_reconnection_events("origin")
+                .with_shutdown(shutdown.clone()),
+        );
+
+        assert_eq!(stream.next().await, Some(Event::Item(1)));
+
+        shutdown.shutdown();
+        assert!(shutdown.is_shutdown());
+        assert_eq!(stream.next().await, None);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_shutdown_aborts_pending_reconnection() {
+        let shutdown = Shutdown::new();
+        let attempts = Arc::new(Mutex::new(0));
+
+        let init_stream = {
+            let attempts = Arc::clone(&attempts);
+            move || {
+                let attempt = {
+                    let mut attempts = attempts.lock().unwrap();
+                    *attempts += 1;
+                    *attempts
+                };
+
+                async move {
+                    match attempt {
+                        // Initial connection yields a single item before disconnecting
+                        1 => Ok::<_, &str>(futures::stream::iter([1u64])),
+                        // Reconnection attempts never complete (eg/ unresponsive exchange)
+                        _ => future::pending().await,
+                    }
+                }
+            }
+        };
+
+        let mut stream = Box::pin(
+            init_reconnecting_stream(init_stream)
+                .await
+                .unwrap()
+                .with_reconnect_backoff(ReconnectionBackoffPolicy::fixed(100), mock_stream_key())
+                .with_reconnection_events("origin")
+                .with_shutdown(shutdown.clone()),
+        );
+
+        assert_eq!(stream.next().await, Some(Event::Item(1)));
+        assert_eq!(stream.next().await, Some(Event::Reconnecting("origin")));
+
+        // Reconnection attempt is pending when shutdown is signalled
+        let pending = tokio::time::timeout(Duration::from_secs(1), stream.next()).await;
+        assert!(pending.is_err());
+        assert_eq!(*attempts.lock().unwrap(), 2);
+
+        shutdown.shutdown();
+        assert_eq!(stream.next().await, None);
+
+        // No further reconnection is attempted
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        assert_eq!(stream.next().await, None);
+        assert_eq!(*attempts.lock().unwrap(), 2);
     }
 }
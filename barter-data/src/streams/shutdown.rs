@@ -0,0 +1,46 @@
+use tokio::sync::watch;
+
+/// Cloneable signal used to gracefully shutdown every
+/// [`ReconnectingStream`](super::reconnect::stream::ReconnectingStream) it is provided to.
+///
+/// Once signalled, each stream stops yielding items & ends, aborting any pending reconnection
+/// attempt. Dropping the WebSocket connection of each stream sends a close frame to the
+/// exchange.
+///
+/// Cloned [`Shutdown`]s share the same signal, so the same [`Shutdown`] can be provided to
+/// several [`StreamBuilder`](super::builder::StreamBuilder)s to stop them together.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    tx: watch::Sender<bool>,
+}
+
+impl Shutdown {
+    /// Construct a new [`Shutdown`] that has not been signalled.
+    pub fn new() -> Self {
+        Self {
+            tx: watch::Sender::new(false),
+        }
+    }
+
+    /// Signal every stream sharing this [`Shutdown`] to gracefully shutdown.
+    pub fn shutdown(&self) {
+        self.tx.send_replace(true);
+    }
+
+    /// Returns true if this [`Shutdown`] has been signalled.
+    pub fn is_shutdown(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Completes once this [`Shutdown`] has been signalled.
+    pub async fn wait(&self) {
+        // Error is not possible since self holds the watch::Sender
+        let _ = self.tx.subscribe().wait_for(|shutdown| *shutdown).await;
+    }
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,186 @@
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Conflates high-frequency market data (eg/ ticker updates) of each instrument, so at most the
+/// latest item of each instrument is emitted once per throttle interval.
+///
+/// The first item of an instrument opens a window, & every subsequent item received within the
+/// window replaces the previous, rather than being buffered. The latest item is emitted once the
+/// window closes, so the final update before an idle period is never withheld.
+#[derive(Clone, Debug)]
+pub struct Throttler<InstrumentKey, Item> {
+    interval: Duration,
+    /// Open window of each instrument, with it's closing deadline & latest item.
+    ///
+    /// A connection multiplexes relatively few instruments, so windows are searched linearly
+    /// rather than requiring a hashable instrument key.
+    windows: Vec<(InstrumentKey, Instant, Item)>,
+}
+
+impl<InstrumentKey, Item> Throttler<InstrumentKey, Item>
+where
+    InstrumentKey: PartialEq,
+{
+    /// Construct a new [`Throttler`] emitting at most one item per instrument each `interval`.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            windows: Vec::new(),
+        }
+    }
+
+    /// Conflate the item into the open window of it's instrument, opening a new window closing
+    /// one interval from `now` if there is none.
+    pub fn update(&mut self, instrument: InstrumentKey, item: Item, now: Instant) {
+        match self
+            .windows
+            .iter_mut()
+            .find(|(key, _, _)| *key == instrument)
+        {
+            Some((_, _, latest)) => *latest = item,
+            None => self.windows.push((instrument, now + self.interval, item)),
+        }
+    }
+
+    /// Returns the closing deadline of the next window to close, if any are open.
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.windows.iter().map(|(_, deadline, _)| *deadline).min()
+    }
+
+    /// Close the earliest window that closed at or before `now`, returning it's latest item.
+    pub fn pop_closed(&mut self, now: Instant) -> Option<Item> {
+        let index = self
+            .windows
+            .iter()
+            .enumerate()
+            .filter(|(_, (_, deadline, _))| *deadline <= now)
+            .min_by_key(|(_, (_, deadline, _))| *deadline)
+            .map(|(index, _)| index)?;
+
+        Some(self.windows.remove(index).2)
+    }
+
+    /// Close the earliest open window regardless of it's deadline, returning it's latest item.
+    ///
+    /// Used to flush every pending item once the upstream `Stream` has ended.
+    pub fn pop_any(&mut self) -> Option<Item> {
+        self.next_deadline()
+            .and_then(|deadline| self.pop_closed(deadline))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        event::MarketEvent,
+        streams::reconnect::{stream::ReconnectingStream, Event},
+        subscription::ticker::Ticker,
+    };
+    use barter_instrument::exchange::ExchangeId;
+    use chrono::Utc;
+    use futures::StreamExt;
+
+    type TickerEvent = Event<ExchangeId, Result<MarketEvent<&'static str, Ticker>, ()>>;
+
+    fn ticker(instrument: &'static str, count: u64) -> TickerEvent {
+        Event::Item(Ok(MarketEvent {
+            time_exchange: Utc::now(),
+            time_received: Utc::now(),
+            exchange: ExchangeId::BinanceSpot,
+            instrument,
+            kind: Ticker {
+                last_price: 100.0 + count as f64,
+                volume: 10.0,
+                count: Some(count),
+                first_id: Some(0),
+                last_id: Some(count),
+            },
+        }))
+    }
+
+    fn sequence(event: &TickerEvent) -> (&'static str, u64) {
+        match event {
+            Event::Item(Ok(market)) => (market.instrument, market.kind.count.unwrap()),
+            _ => panic!("expected MarketEvent"),
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_with_throttle_emits_latest_update_once_per_window() {
+        let interval = Duration::from_secs(1);
+        let start = Instant::now();
+
+        // 100 btc_usdt updates within the first window, & a final eth_usdt update before idling
+        let updates = futures::stream::iter(0..100)
+            .then(|sequence| async move {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+                ticker("btc_usdt", sequence)
+            })
+            .chain(futures::stream::once(async {
+                tokio::time::sleep(Duration::from_secs(2)).await;
+                ticker("eth_usdt", 1)
+            }))
+            .chain(futures::stream::pending());
+
+        let mut throttled = Box::pin(updates.with_throttle(interval));
+
+        // Only the latest update of the window is emitted, once the window closes
+        let first = throttled.next().await.unwrap();
+        assert_eq!(sequence(&first), ("btc_usdt", 99));
+        assert_eq!(Instant::now() - start, interval + Duration::from_millis(1));
+
+        // Final update before the idle period is still emitted at the end of it's window
+        let second = throttled.next().await.unwrap();
+        assert_eq!(sequence(&second), ("eth_usdt", 1));
+        assert_eq!(Instant::now() - start, Duration::from_millis(3100));
+
+        // No further updates are emitted while the Stream is idle
+        let idle = tokio::time::timeout(Duration::from_secs(10), throttled.next()).await;
+        assert!(idle.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_with_throttle_passes_through_markers_and_flushes_on_end() {
+        let events = vec![
+            ticker("btc_usdt", 1),
+            Event::Reconnecting(ExchangeId::BinanceSpot),
+            ticker("btc_usdt", 2),
+            ticker("eth_usdt", 1),
+        ];
+
+        let actual = futures::stream::iter(events)
+            .with_throttle(Duration::from_secs(60))
+            .collect::<Vec<_>>()
+            .await;
+
+        // Reconnection markers are not delayed, & pending updates are flushed once ended
+        assert_eq!(actual.len(), 3);
+        assert!(matches!(actual[0], Event::Reconnecting(_)));
+        assert_eq!(sequence(&actual[1]), ("btc_usdt", 2));
+        assert_eq!(sequence(&actual[2]), ("eth_usdt", 1));
+    }
+
+    #[test]
+    fn test_throttler_closes_windows_in_deadline_order() {
+        let start = Instant::now();
+        let interval = Duration::from_millis(10);
+        let mut throttler = Throttler::new(interval);
+
+        throttler.update("btc_usdt", 1, start);
+        throttler.update("eth_usdt", 1, start + Duration::from_millis(5));
+        throttler.update("btc_usdt", 2, start + Duration::from_millis(6));
+        assert_eq!(throttler.next_deadline(), Some(start + interval));
+
+        assert_eq!(throttler.pop_closed(start + Duration::from_millis(9)), None);
+        assert_eq!(
+            throttler.pop_closed(start + Duration::from_millis(20)),
+            Some(2)
+        );
+        assert_eq!(
+            throttler.pop_closed(start + Duration::from_millis(20)),
+            Some(1)
+        );
+        assert_eq!(throttler.pop_any(), None);
+    }
+}
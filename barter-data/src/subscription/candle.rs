@@ -1,27 +1,31 @@
 use super::SubscriptionKind;
 use chrono::{DateTime, Utc};
-use derive_more::Display;
 use serde::{Deserialize, Serialize};
 
 /// Barter [`Subscription`](super::Subscription) [`SubscriptionKind`] that yields [`Candle`]
-/// [`MarketEvent<T>`](crate::event::MarketEvent) events.
-#[derive(
-    Copy,
-    Clone,
-    Eq,
-    PartialEq,
-    Ord,
-    PartialOrd,
-    Hash,
-    Debug,
-    Default,
-    Deserialize,
-    Serialize,
-    Display,
-)]
-pub struct Candles;
-
-impl SubscriptionKind for Candles {
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events for the contained [`Interval`].
+///
+/// By default every update of the in-progress [`Candle`] is yielded. If `ONLY_CLOSED` is true,
+/// only closed [`Candle`]s are yielded (see [`ClosedCandles`]).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct Candles<const ONLY_CLOSED: bool = false>(pub Interval);
+
+/// [`Candles`] [`SubscriptionKind`] that only yields closed [`Candle`]s.
+pub type ClosedCandles = Candles<true>;
+
+impl<const ONLY_CLOSED: bool> Candles<ONLY_CLOSED> {
+    /// Constructs a new [`Candles`] [`SubscriptionKind`] for the provided [`Interval`].
+    pub fn new(interval: Interval) -> Self {
+        Self(interval)
+    }
+
+    /// Determines if a [`Candle`] update should be yielded, given if it has closed.
+    pub fn yields(closed: bool) -> bool {
+        closed || !ONLY_CLOSED
+    }
+}
+
+impl<const ONLY_CLOSED: bool> SubscriptionKind for Candles<ONLY_CLOSED> {
     type Event = Candle;
 
     fn as_str(&self) -> &'static str {
@@ -29,6 +33,12 @@ impl SubscriptionKind for Candles {
     }
 }
 
+impl<const ONLY_CLOSED: bool> std::fmt::Display for Candles<ONLY_CLOSED> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "candles_{}", self.0)
+    }
+}
+
 /// Normalised Barter OHLCV [`Candle`] model.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Candle {
@@ -40,3 +50,58 @@ pub struct Candle {
     pub volume: f64,
     pub trade_count: u64,
 }
+
+/// Interval of time covered by each [`Candle`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum Interval {
+    #[serde(rename = "1m")]
+    M1,
+    #[serde(rename = "3m")]
+    M3,
+    #[serde(rename = "5m")]
+    M5,
+    #[serde(rename = "15m")]
+    M15,
+    #[serde(rename = "30m")]
+    M30,
+    #[serde(rename = "1h")]
+    H1,
+    #[serde(rename = "2h")]
+    H2,
+    #[serde(rename = "4h")]
+    H4,
+    #[serde(rename = "6h")]
+    H6,
+    #[serde(rename = "12h")]
+    H12,
+    #[serde(rename = "1d")]
+    D1,
+    #[serde(rename = "1w")]
+    W1,
+}
+
+impl Interval {
+    /// Returns the `&str` representation of the [`Interval`] (eg/ "1m", "4h", "1d").
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Interval::M1 => "1m",
+            Interval::M3 => "3m",
+            Interval::M5 => "5m",
+            Interval::M15 => "15m",
+            Interval::M30 => "30m",
+            Interval::H1 => "1h",
+            Interval::H2 => "2h",
+            Interval::H4 => "4h",
+            Interval::H6 => "6h",
+            Interval::H12 => "12h",
+            Interval::D1 => "1d",
+            Interval::W1 => "1w",
+        }
+    }
+}
+
+impl std::fmt::Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
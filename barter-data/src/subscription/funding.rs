@@ -0,0 +1,39 @@
+use super::SubscriptionKind;
+use chrono::{DateTime, Utc};
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubscriptionKind`] that yields [`FundingRate`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    Display,
+)]
+pub struct FundingRates;
+
+impl SubscriptionKind for FundingRates {
+    type Event = FundingRate;
+
+    fn as_str(&self) -> &'static str {
+        "funding_rates"
+    }
+}
+
+/// Normalised Barter [`FundingRate`] model for a perpetual futures instrument.
+#[derive(Clone, Copy, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct FundingRate {
+    /// Funding rate applied at the next funding time.
+    pub rate: f64,
+    pub next_funding_time: DateTime<Utc>,
+    pub mark_price: f64,
+}
@@ -20,9 +20,15 @@ pub mod book;
 /// Candle [`SubscriptionKind`] and the associated Barter output data model.
 pub mod candle;
 
+/// Funding rate [`SubscriptionKind`] and the associated Barter output data model.
+pub mod funding;
+
 /// Liquidation [`SubscriptionKind`] and the associated Barter output data model.
 pub mod liquidation;
 
+/// Ticker [`SubscriptionKind`] and the associated Barter output data model.
+pub mod ticker;
+
 /// Public trade [`SubscriptionKind`] and the associated Barter output data model.
 pub mod trade;
 
@@ -55,7 +61,10 @@ pub enum SubKind {
     OrderBooksL2,
     OrderBooksL3,
     Liquidations,
-    Candles,
+    #[display("Candles({_0})")]
+    Candles(candle::Interval),
+    Tickers,
+    FundingRates,
 }
 
 impl<Exchange, Instrument, Kind> std::fmt::Display for Subscription<Exchange, Instrument, Kind>
@@ -220,12 +229,14 @@ pub fn exchange_supports_instrument_kind_sub_kind(
     use SubKind::*;
 
     match (exchange_id, instrument_kind, sub_kind) {
-        (BinanceSpot, Spot, PublicTrades | OrderBooksL1) => true,
-        (BinanceFuturesUsd, Perpetual, PublicTrades | OrderBooksL1 | Liquidations) => true,
+        (BinanceSpot, Spot, PublicTrades | OrderBooksL1 | Candles(_)) => true,
+        (BinanceFuturesUsd, Perpetual, PublicTrades | OrderBooksL1 | Liquidations | Candles(_)) => {
+            true
+        }
         (Bitfinex, Spot, PublicTrades) => true,
         (Bitmex, Perpetual, PublicTrades) => true,
-        (BybitSpot, Spot, PublicTrades) => true,
-        (BybitPerpetualsUsd, Perpetual, PublicTrades) => true,
+        (BybitSpot, Spot, PublicTrades | Tickers) => true,
+        (BybitPerpetualsUsd, Perpetual, PublicTrades | Tickers) => true,
         (Coinbase, Spot, PublicTrades) => true,
         (GateioSpot, Spot, PublicTrades) => true,
         (GateioFuturesUsd, Future(_), PublicTrades) => true,
@@ -234,7 +245,7 @@ pub fn exchange_supports_instrument_kind_sub_kind(
         (GateioPerpetualsBtc, Perpetual, PublicTrades) => true,
         (GateioOptions, Option(_), PublicTrades) => true,
         (Kraken, Spot, PublicTrades | OrderBooksL1) => true,
-        (Okx, Spot | Future(_) | Perpetual | Option(_), PublicTrades) => true,
+        (Okx, Spot | Future(_) | Perpetual | Option(_), PublicTrades | Tickers) => true,
 
         (_, _, _) => false,
     }
@@ -499,6 +510,87 @@ mod tests {
                 }
             }
         }
+        #[test]
+        fn test_validate_dynamic_subscriptions_match_dynamic_streams_support() {
+            struct TestCase {
+                input: Subscription<ExchangeId, Instrument, SubKind>,
+                is_valid: bool,
+            }
+
+            let tests = vec![
+                TestCase {
+                    // TC0: Valid BinanceSpot Spot Candles subscription
+                    input: Subscription::from((
+                        ExchangeId::BinanceSpot,
+                        "base",
+                        "quote",
+                        InstrumentKind::Spot,
+                        SubKind::Candles(candle::Interval::M1),
+                    )),
+                    is_valid: true,
+                },
+                TestCase {
+                    // TC1: Valid BybitPerpetualsUsd Perpetual Tickers subscription
+                    input: Subscription::from((
+                        ExchangeId::BybitPerpetualsUsd,
+                        "base",
+                        "quote",
+                        InstrumentKind::Perpetual,
+                        SubKind::Tickers,
+                    )),
+                    is_valid: true,
+                },
+                TestCase {
+                    // TC2: Valid Okx Perpetual Tickers subscription
+                    input: Subscription::from((
+                        ExchangeId::Okx,
+                        "base",
+                        "quote",
+                        InstrumentKind::Perpetual,
+                        SubKind::Tickers,
+                    )),
+                    is_valid: true,
+                },
+                TestCase {
+                    // TC3: Invalid Kraken Spot Tickers subscription
+                    input: Subscription::from((
+                        ExchangeId::Kraken,
+                        "base",
+                        "quote",
+                        InstrumentKind::Spot,
+                        SubKind::Tickers,
+                    )),
+                    is_valid: false,
+                },
+                TestCase {
+                    // TC4: Invalid Coinbase Spot Tickers subscription
+                    input: Subscription::from((
+                        ExchangeId::Coinbase,
+                        "base",
+                        "quote",
+                        InstrumentKind::Spot,
+                        SubKind::Tickers,
+                    )),
+                    is_valid: false,
+                },
+                TestCase {
+                    // TC5: Invalid Bitstamp Spot PublicTrades subscription
+                    input: Subscription::from((
+                        ExchangeId::Bitstamp,
+                        "base",
+                        "quote",
+                        InstrumentKind::Spot,
+                        SubKind::PublicTrades,
+                    )),
+                    is_valid: false,
+                },
+            ];
+
+            for (index, test) in tests.into_iter().enumerate() {
+                let actual = test.input.validate().is_ok();
+                assert_eq!(actual, test.is_valid, "TC{} failed", index);
+            }
+        }
     }
 
     mod instrument_map {
@@ -0,0 +1,46 @@
+use super::SubscriptionKind;
+use derive_more::Display;
+use serde::{Deserialize, Serialize};
+
+/// Barter [`Subscription`](super::Subscription) [`SubscriptionKind`] that yields [`Ticker`]
+/// [`MarketEvent<T>`](crate::event::MarketEvent) events.
+#[derive(
+    Copy,
+    Clone,
+    Eq,
+    PartialEq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Debug,
+    Default,
+    Deserialize,
+    Serialize,
+    Display,
+)]
+pub struct Tickers;
+
+impl SubscriptionKind for Tickers {
+    type Event = Ticker;
+
+    fn as_str(&self) -> &'static str {
+        "tickers"
+    }
+}
+
+/// Normalised Barter [`Ticker`] model containing rolling window statistics for an instrument.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Ticker {
+    /// Price of the last trade in the rolling window.
+    pub last_price: f64,
+    /// Total traded base asset volume in the rolling window.
+    pub volume: f64,
+    /// Number of trades in the rolling window, if provided by the exchange.
+    pub count: Option<u64>,
+    /// Exchange trade identifier of the first trade in the rolling window, if provided by the
+    /// exchange.
+    pub first_id: Option<u64>,
+    /// Exchange trade identifier of the last trade in the rolling window, if provided by the
+    /// exchange.
+    pub last_id: Option<u64>,
+}
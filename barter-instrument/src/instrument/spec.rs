@@ -13,6 +13,17 @@ pub struct InstrumentSpecPrice {
     pub tick_size: f64,
 }
 
+impl InstrumentSpecPrice {
+    /// Rounds the provided price to the nearest multiple of the `tick_size`. Prices are returned
+    /// unchanged if the `tick_size` is not positive.
+    pub fn round(&self, price: f64) -> f64 {
+        if self.tick_size <= 0.0 {
+            return price;
+        }
+        (price / self.tick_size).round() * self.tick_size
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, PartialOrd, Deserialize, Serialize)]
 pub struct InstrumentSpecQuantity<AssetKey> {
     pub unit: OrderQuantityUnits<AssetKey>,
@@ -20,6 +31,21 @@ pub struct InstrumentSpecQuantity<AssetKey> {
     pub increment: f64,
 }
 
+impl<AssetKey> InstrumentSpecQuantity<AssetKey> {
+    /// Rounds the provided quantity towards zero to a multiple of the `increment`, so an order is
+    /// never larger than requested. Quantities are returned unchanged if the `increment` is not
+    /// positive.
+    pub fn round_down(&self, quantity: f64) -> f64 {
+        if self.increment <= 0.0 {
+            return quantity;
+        }
+
+        // Tolerate floating point error, eg/ 0.3 / 0.1 = 2.9999999999999996
+        let increments = (quantity.abs() / self.increment + 1e-9).floor();
+        (increments * self.increment).copysign(quantity)
+    }
+}
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Deserialize, Serialize)]
 pub enum OrderQuantityUnits<AssetKey> {
     Asset(AssetKey),
@@ -31,3 +57,27 @@ pub enum OrderQuantityUnits<AssetKey> {
 pub struct InstrumentSpecNotional {
     pub min: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_instrument_spec_rounds_price_to_tick_and_quantity_down_to_increment() {
+        let price = InstrumentSpecPrice {
+            min: 0.0,
+            tick_size: 0.5,
+        };
+        assert_eq!(price.round(100.2), 100.0);
+        assert_eq!(price.round(100.3), 100.5);
+
+        let quantity = InstrumentSpecQuantity::<String> {
+            unit: OrderQuantityUnits::Contract,
+            min: 0.0,
+            increment: 0.1,
+        };
+        assert!((quantity.round_down(0.3) - 0.3).abs() < 1e-12);
+        assert!((quantity.round_down(0.39) - 0.3).abs() < 1e-12);
+        assert!((quantity.round_down(-0.39) + 0.3).abs() < 1e-12);
+    }
+}
@@ -20,6 +20,12 @@ pub enum SocketError {
         payload: Vec<u8>,
     },
 
+    #[error("Decompressing binary payload error: {error} for payload: {payload:?}")]
+    Decompress {
+        error: std::io::Error,
+        payload: Vec<u8>,
+    },
+
     #[error("Serialising JSON error: {0}")]
     Serialise(serde_json::Error),
 
@@ -1,6 +1,7 @@
 use crate::{error::SocketError, protocol::StreamParser};
+use flate2::read::{DeflateDecoder, GzDecoder};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::fmt::Debug;
+use std::{fmt::Debug, io::Read};
 use tokio::net::TcpStream;
 use tokio_tungstenite::{
     connect_async,
@@ -57,6 +58,71 @@ impl StreamParser for WebSocketParser {
     }
 }
 
+/// [`StreamParser`] implementation for a [`WebSocket`] that sends compressed binary frames (eg/
+/// gzip by Huobi, raw deflate by OKX), decompressing each binary payload before deserialising.
+///
+/// Exchange connectors opt in by using this parser for their [`WebSocket`] stream (eg/ the
+/// `barter-data` Okx connector). Text frames are deserialised as normal.
+///
+/// **Note:**
+/// Negotiating the `permessage-deflate` WebSocket extension is not supported by the tungstenite
+/// version in use, so only application level frame compression is handled.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct CompressedWebSocketParser;
+
+impl StreamParser for CompressedWebSocketParser {
+    type Stream = WebSocket;
+    type Message = WsMessage;
+    type Error = WsError;
+
+    fn parse<Output>(
+        input: Result<Self::Message, Self::Error>,
+    ) -> Option<Result<Output, SocketError>>
+    where
+        Output: DeserializeOwned,
+    {
+        match input {
+            Ok(WsMessage::Binary(binary)) => process_compressed_binary(binary),
+            input => WebSocketParser::parse(input),
+        }
+    }
+}
+
+/// Process a compressed payload of `Vec<u8>` bytes by decompressing it, and deserialising the
+/// result into an `ExchangeMessage`. A malformed payload returns a [`SocketError::Decompress`].
+pub fn process_compressed_binary<ExchangeMessage>(
+    payload: Vec<u8>,
+) -> Option<Result<ExchangeMessage, SocketError>>
+where
+    ExchangeMessage: DeserializeOwned,
+{
+    match decompress(&payload) {
+        Ok(decompressed) => process_binary(decompressed),
+        Err(error) => {
+            debug!(
+                ?error,
+                ?payload,
+                action = "returning Some(Err(err))",
+                "failed to decompress WebSocket binary Message"
+            );
+            Some(Err(SocketError::Decompress { error, payload }))
+        }
+    }
+}
+
+/// Decompress a gzip (identified by it's magic bytes) or raw deflate compressed payload.
+pub fn decompress(payload: &[u8]) -> std::io::Result<Vec<u8>> {
+    const GZIP_MAGIC_BYTES: [u8; 2] = [0x1f, 0x8b];
+
+    let mut decompressed = Vec::with_capacity(payload.len() * 4);
+    match payload.starts_with(&GZIP_MAGIC_BYTES) {
+        true => GzDecoder::new(payload).read_to_end(&mut decompressed)?,
+        false => DeflateDecoder::new(payload).read_to_end(&mut decompressed)?,
+    };
+
+    Ok(decompressed)
+}
+
 /// Process a payload of `String` by deserialising into an `ExchangeMessage`.
 pub fn process_text<ExchangeMessage>(
     payload: String,
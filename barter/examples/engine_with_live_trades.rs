@@ -3,7 +3,10 @@ use barter::{
     engine::{trader::Trader, Engine},
     event::{Event, EventTx},
     execution::{
-        simulated::{Config as ExecutionConfig, SimulatedExecution},
+        simulated::{
+            Config as ExecutionConfig, LimitFillModel, SimulatedExecution, SlippageModel,
+            SpreadModel,
+        },
         Fees,
     },
     portfolio::{
@@ -69,7 +72,10 @@ async fn main() {
             .statistic_config(StatisticConfig {
                 starting_equity: 10_000.0,
                 trading_days_per_year: 365,
+                data_period: chrono::Duration::days(1),
                 risk_free_return: 0.0,
+                omega_threshold: None,
+                with_benchmark: false,
             })
             .build_and_init()
             .expect("failed to build & initialise MetaPortfolio"),
@@ -91,13 +97,21 @@ async fn main() {
             .data(live::ReconnectingMarketFeed::new(
                 stream_market_event_trades().await,
             ))
-            .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .strategy(RSIStrategy::new(StrategyConfig {
+                rsi_period: 14,
+                gap_rewarm: None,
+            }))
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
                     slippage: 0.05,
                     network: 0.0,
                 },
+                exchange_fees_pct: None,
+                slippage_model: SlippageModel::Flat,
+                spread_model: SpreadModel::Zero,
+                latency: chrono::Duration::zero(),
+                limit_fill_model: LimitFillModel::Immediate,
             }))
             .build()
             .expect("failed to build trader"),
@@ -116,7 +130,10 @@ async fn main() {
         .statistics_summary(TradingSummary::init(StatisticConfig {
             starting_equity: 1000.0,
             trading_days_per_year: 365,
+            data_period: chrono::Duration::days(1),
             risk_free_return: 0.0,
+            omega_threshold: None,
+            with_benchmark: false,
         }))
         .build()
         .expect("failed to build engine");
@@ -180,6 +197,10 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // OrderNew Event occurred in Engine
                 println!("{new_order:?}");
             }
+            Event::OrderCancel(cancelled_order) => {
+                // OrderCancel Event occurred in Engine
+                println!("{cancelled_order:?}");
+            }
             Event::OrderUpdate => {
                 // OrderUpdate Event occurred in Engine
             }
@@ -203,6 +224,14 @@ async fn listen_to_engine_events(mut event_rx: mpsc::UnboundedReceiver<Event>) {
                 // Balance update Event occurred in Engine
                 println!("{balance_update:?}");
             }
+            Event::MarginCall(margin_call) => {
+                // MarginCall Event occurred in Engine
+                println!("{margin_call:?}");
+            }
+            Event::Bankruptcy(bankruptcy) => {
+                // Bankruptcy Event occurred in Engine
+                println!("{bankruptcy:?}");
+            }
         }
     }
 }
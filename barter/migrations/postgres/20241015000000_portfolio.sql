@@ -0,0 +1,56 @@
+-- Barter Portfolio persistence for the PostgresRepository.
+--
+-- Positions are stored in typed columns for analytical SQL after a trading session, alongside
+-- the full JSON serialised Position used to restore it.
+
+CREATE TABLE IF NOT EXISTS barter_open_position (
+    position_id             TEXT PRIMARY KEY,
+    exchange                TEXT NOT NULL,
+    instrument              TEXT NOT NULL,
+    side                    TEXT NOT NULL,
+    quantity                DOUBLE PRECISION NOT NULL,
+    enter_time              TIMESTAMPTZ NOT NULL,
+    update_time             TIMESTAMPTZ NOT NULL,
+    enter_avg_price_gross   DOUBLE PRECISION NOT NULL,
+    enter_value_gross       DOUBLE PRECISION NOT NULL,
+    enter_fees_total        DOUBLE PRECISION NOT NULL,
+    current_symbol_price    DOUBLE PRECISION NOT NULL,
+    unrealised_profit_loss  DOUBLE PRECISION NOT NULL,
+    realised_profit_loss    DOUBLE PRECISION NOT NULL,
+    position                JSONB NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS barter_exited_position (
+    id                      BIGSERIAL PRIMARY KEY,
+    engine_id               UUID NOT NULL,
+    position_id             TEXT NOT NULL,
+    exchange                TEXT NOT NULL,
+    instrument              TEXT NOT NULL,
+    side                    TEXT NOT NULL,
+    quantity                DOUBLE PRECISION NOT NULL,
+    enter_time              TIMESTAMPTZ NOT NULL,
+    exit_time               TIMESTAMPTZ NOT NULL,
+    enter_avg_price_gross   DOUBLE PRECISION NOT NULL,
+    enter_value_gross       DOUBLE PRECISION NOT NULL,
+    enter_fees_total        DOUBLE PRECISION NOT NULL,
+    exit_avg_price_gross    DOUBLE PRECISION NOT NULL,
+    exit_value_gross        DOUBLE PRECISION NOT NULL,
+    exit_fees_total         DOUBLE PRECISION NOT NULL,
+    realised_profit_loss    DOUBLE PRECISION NOT NULL,
+    position                JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS barter_exited_position_engine_id_idx
+    ON barter_exited_position (engine_id, id);
+
+CREATE TABLE IF NOT EXISTS barter_balance (
+    engine_id               UUID PRIMARY KEY,
+    time                    TIMESTAMPTZ NOT NULL,
+    total                   DOUBLE PRECISION NOT NULL,
+    available               DOUBLE PRECISION NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS barter_statistic (
+    market_id               TEXT PRIMARY KEY,
+    statistic               JSONB NOT NULL
+);
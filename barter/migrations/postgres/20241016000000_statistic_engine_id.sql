@@ -0,0 +1,7 @@
+-- Namespace market statistics by engine so Portfolios sharing a database never collide. Rows
+-- persisted before this migration are assigned the nil engine_id.
+ALTER TABLE barter_statistic
+    ADD COLUMN engine_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000';
+ALTER TABLE barter_statistic ALTER COLUMN engine_id DROP DEFAULT;
+ALTER TABLE barter_statistic DROP CONSTRAINT barter_statistic_pkey;
+ALTER TABLE barter_statistic ADD PRIMARY KEY (engine_id, market_id);
@@ -0,0 +1,10 @@
+-- Balance of each currency held by a Portfolio with positions quoted in multiple currencies. The
+-- barter_balance row remains the Portfolio Balance converted into it's base currency.
+CREATE TABLE IF NOT EXISTS barter_currency_balance (
+    engine_id               UUID NOT NULL,
+    currency                TEXT NOT NULL,
+    time                    TIMESTAMPTZ NOT NULL,
+    total                   DOUBLE PRECISION NOT NULL,
+    available               DOUBLE PRECISION NOT NULL,
+    PRIMARY KEY (engine_id, currency)
+);
@@ -0,0 +1,87 @@
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use std::{fmt::Debug, sync::Arc};
+
+/// Source of the current time used to timestamp the [`Event`](crate::event::Event)s generated
+/// by the trading system.
+///
+/// Live-trading uses the wall-clock [`SystemClock`], whereas backtests can use a
+/// [`SimulatedClock`] driven by the market event timestamps, so that the same historical data
+/// always produces identical [`Event`](crate::event::Event) timestamps.
+pub trait Clock: Debug + Send + Sync {
+    /// Return the current time.
+    fn time(&self) -> DateTime<Utc>;
+
+    /// Advance the [`Clock`] to the exchange timestamp of the next market event.
+    ///
+    /// Wall-clock [`Clock`]s ignore this.
+    fn advance(&self, _time: DateTime<Utc>) {}
+}
+
+/// Wall-clock [`Clock`] for live-trading & dry-trading, returning [`Utc::now`].
+#[derive(Copy, Clone, Eq, PartialEq, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn time(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// Deterministic [`Clock`] for backtesting, that only moves forward when advanced to the
+/// timestamp of the next market event.
+///
+/// Cloned [`SimulatedClock`]s share the same time, so a single [`SimulatedClock`] can be
+/// provided to every component of a trading system (eg/ historical feeds, Traders & Portfolio).
+#[derive(Clone, Debug)]
+pub struct SimulatedClock {
+    time: Arc<RwLock<DateTime<Utc>>>,
+}
+
+impl Clock for SimulatedClock {
+    fn time(&self) -> DateTime<Utc> {
+        *self.time.read()
+    }
+
+    fn advance(&self, time: DateTime<Utc>) {
+        let mut current = self.time.write();
+        if time > *current {
+            *current = time;
+        }
+    }
+}
+
+impl Default for SimulatedClock {
+    fn default() -> Self {
+        Self::new(DateTime::<Utc>::UNIX_EPOCH)
+    }
+}
+
+impl SimulatedClock {
+    /// Constructs a new [`SimulatedClock`] starting at the provided time.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            time: Arc::new(RwLock::new(start)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn simulated_clock_only_moves_forward_and_is_shared_between_clones() {
+        let start = DateTime::<Utc>::UNIX_EPOCH + Duration::days(1);
+        let clock = SimulatedClock::new(start);
+        let cloned = clock.clone();
+
+        cloned.advance(start + Duration::minutes(1));
+        assert_eq!(clock.time(), start + Duration::minutes(1));
+
+        // Out of order market event timestamps do not move the clock backwards
+        clock.advance(start);
+        assert_eq!(cloned.time(), start + Duration::minutes(1));
+    }
+}
@@ -0,0 +1,607 @@
+use crate::{
+    data::{
+        error::DataError,
+        historical::{CsvCandleFeed, CsvConfig},
+        Feed, MarketGenerator,
+    },
+    execution::simulated::{Config as ExecutionConfig, SimulatedExecution},
+    portfolio::{
+        allocator::{
+            DcaAllocator, DcaConfig, DefaultAllocator, KellyAllocator, KellyConfig, OrderAllocator,
+            VolatilityNormalisedAllocator, VolatilityNormalisedConfig, VolatilityTargetAllocator,
+            VolatilityTargetAllocatorConfig,
+        },
+        error::PortfolioError,
+        margin::MarginAccount,
+        portfolio::MetaPortfolioBuilder,
+        position::{CloseReason, Position, PositionMode},
+        repository::{BalanceHandler, PositionHandler, StatisticHandler},
+        risk::{
+            AtrStopConfig, AtrStopRisk, CircuitBreakerConfig, CircuitBreakerRisk, DefaultRisk,
+            OrderEvaluator, StopLossConfig, StopLossRisk, TrailingStopConfig, TrailingStopRisk,
+        },
+        EntryOrderType, OrderEvent, OrderType,
+    },
+    statistic::summary::{Initialiser, PositionSummariser},
+    strategy::{
+        example::{
+            BollingerConfig, BollingerStrategy, Config as RSIConfig, KeltnerConfig,
+            KeltnerStrategy, MACDConfig, MACDStrategy, RSIStrategy,
+        },
+        moving_average::{Config as MovingAverageCrossConfig, MovingAverageCrossStrategy},
+        random::{Config as RandomConfig, RandomStrategy},
+        Signal, SignalGenerator, SignalStrength,
+    },
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+#[cfg(feature = "parquet")]
+use crate::data::historical::parquet::{ParquetCandleFeed, ParquetConfig};
+
+/// All errors generated when constructing backtest components from a [`BacktestConfig`].
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Failed to deserialise BacktestConfig: {0}")]
+    Deserialise(#[from] serde_json::Error),
+
+    #[error("Failed to construct configured data source: {0}")]
+    Data(#[from] Box<DataError>),
+
+    #[error("Failed to construct configured portfolio component: {0}")]
+    Portfolio(#[from] Box<PortfolioError>),
+}
+
+/// Configuration of every component required to run a backtest, deserialisable from any
+/// [`serde`] supported format (eg/ JSON or TOML), so strategy parameters & fees can be changed
+/// without recompiling.
+///
+/// The strategy & allocator types are selected by their `strategy_type` & `allocator_type` tags,
+/// and the risk manager & data source types by their `risk_type` & `source_type` tags.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct BacktestConfig {
+    pub data: DataConfig,
+    pub strategy: StrategyConfig,
+    pub portfolio: PortfolioConfig,
+    #[serde(default)]
+    pub execution: ExecutionConfig,
+}
+
+impl BacktestConfig {
+    /// Deserialises a [`BacktestConfig`] from the provided JSON.
+    pub fn from_json(json: &str) -> Result<Self, ConfigError> {
+        serde_json::from_str(json).map_err(ConfigError::from)
+    }
+
+    /// Constructs the configured [`SimulatedExecution`].
+    pub fn execution(&self) -> SimulatedExecution {
+        SimulatedExecution::new(self.execution)
+    }
+}
+
+/// Historical data source a [`ConfiguredFeed`] is constructed from.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "source_type", rename_all = "snake_case")]
+pub enum DataConfig {
+    Csv(CsvConfig),
+    #[cfg(feature = "parquet")]
+    Parquet(ParquetConfig),
+}
+
+impl DataConfig {
+    /// Constructs the configured historical [`ConfiguredFeed`].
+    pub fn build(&self) -> Result<ConfiguredFeed, ConfigError> {
+        Ok(match self {
+            DataConfig::Csv(config) => {
+                ConfiguredFeed::Csv(CsvCandleFeed::new(config.clone()).map_err(Box::new)?)
+            }
+            #[cfg(feature = "parquet")]
+            DataConfig::Parquet(config) => {
+                ConfiguredFeed::Parquet(ParquetCandleFeed::new(config.clone()).map_err(Box::new)?)
+            }
+        })
+    }
+}
+
+/// [`MarketGenerator`] constructed from a [`DataConfig`].
+#[derive(Debug)]
+pub enum ConfiguredFeed {
+    Csv(CsvCandleFeed),
+    #[cfg(feature = "parquet")]
+    Parquet(ParquetCandleFeed),
+}
+
+impl MarketGenerator<MarketEvent<Instrument, DataKind>> for ConfiguredFeed {
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        match self {
+            ConfiguredFeed::Csv(feed) => feed.next(),
+            #[cfg(feature = "parquet")]
+            ConfiguredFeed::Parquet(feed) => feed.next(),
+        }
+    }
+}
+
+/// Strategy type & parameters a [`ConfiguredStrategy`] is constructed from.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "strategy_type", rename_all = "snake_case")]
+pub enum StrategyConfig {
+    Rsi(RSIConfig),
+    Macd(MACDConfig),
+    Bollinger(BollingerConfig),
+    Keltner(KeltnerConfig),
+    MovingAverageCross(MovingAverageCrossConfig),
+    Random(RandomConfig),
+}
+
+impl StrategyConfig {
+    /// Constructs the configured [`ConfiguredStrategy`].
+    pub fn build(&self) -> ConfiguredStrategy {
+        match *self {
+            StrategyConfig::Rsi(config) => ConfiguredStrategy::Rsi(RSIStrategy::new(config)),
+            StrategyConfig::Macd(config) => ConfiguredStrategy::Macd(MACDStrategy::new(config)),
+            StrategyConfig::Bollinger(config) => {
+                ConfiguredStrategy::Bollinger(BollingerStrategy::new(config))
+            }
+            StrategyConfig::Keltner(config) => {
+                ConfiguredStrategy::Keltner(KeltnerStrategy::new(config))
+            }
+            StrategyConfig::MovingAverageCross(config) => {
+                ConfiguredStrategy::MovingAverageCross(MovingAverageCrossStrategy::new(config))
+            }
+            StrategyConfig::Random(config) => {
+                ConfiguredStrategy::Random(Box::new(RandomStrategy::new(config)))
+            }
+        }
+    }
+}
+
+/// [`SignalGenerator`] constructed from a [`StrategyConfig`].
+#[derive(Clone, Debug)]
+pub enum ConfiguredStrategy {
+    Rsi(RSIStrategy),
+    Macd(MACDStrategy),
+    Bollinger(BollingerStrategy),
+    Keltner(KeltnerStrategy),
+    MovingAverageCross(MovingAverageCrossStrategy),
+    Random(Box<RandomStrategy>),
+}
+
+impl ConfiguredStrategy {
+    fn as_strategy(&self) -> &dyn SignalGenerator {
+        match self {
+            ConfiguredStrategy::Rsi(strategy) => strategy,
+            ConfiguredStrategy::Macd(strategy) => strategy,
+            ConfiguredStrategy::Bollinger(strategy) => strategy,
+            ConfiguredStrategy::Keltner(strategy) => strategy,
+            ConfiguredStrategy::MovingAverageCross(strategy) => strategy,
+            ConfiguredStrategy::Random(strategy) => strategy.as_ref(),
+        }
+    }
+
+    fn as_strategy_mut(&mut self) -> &mut dyn SignalGenerator {
+        match self {
+            ConfiguredStrategy::Rsi(strategy) => strategy,
+            ConfiguredStrategy::Macd(strategy) => strategy,
+            ConfiguredStrategy::Bollinger(strategy) => strategy,
+            ConfiguredStrategy::Keltner(strategy) => strategy,
+            ConfiguredStrategy::MovingAverageCross(strategy) => strategy,
+            ConfiguredStrategy::Random(strategy) => strategy.as_mut(),
+        }
+    }
+}
+
+impl SignalGenerator for ConfiguredStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        self.as_strategy_mut().generate_signal(market)
+    }
+
+    fn is_warm(&self) -> bool {
+        self.as_strategy().is_warm()
+    }
+}
+
+/// Portfolio settings used to prepare a [`MetaPortfolioBuilder`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct PortfolioConfig {
+    pub starting_cash: f64,
+    pub allocator: AllocatorConfig,
+    pub risk: RiskConfig,
+    #[serde(default)]
+    pub entry_order_type: EntryOrderType,
+    #[serde(default)]
+    pub position_mode: PositionMode,
+}
+
+impl PortfolioConfig {
+    /// Returns a [`MetaPortfolioBuilder`] with the configured starting cash, entry order type,
+    /// [`PositionMode`], [`ConfiguredAllocator`] & [`ConfiguredRisk`] already set. The remaining
+    /// engine specific fields (eg/ engine_id, markets & repository) must be provided before
+    /// building.
+    pub fn builder<Repository, Statistic>(
+        &self,
+    ) -> Result<
+        MetaPortfolioBuilder<Repository, ConfiguredAllocator, ConfiguredRisk, Statistic>,
+        ConfigError,
+    >
+    where
+        Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+        Statistic: Initialiser + PositionSummariser,
+    {
+        Ok(MetaPortfolioBuilder::new()
+            .starting_cash(self.starting_cash)
+            .entry_order_type(self.entry_order_type)
+            .position_mode(self.position_mode)
+            .allocation_manager(self.allocator.build()?)
+            .risk_manager(self.risk.build()))
+    }
+}
+
+/// Allocator type & parameters a [`ConfiguredAllocator`] is constructed from.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "allocator_type", rename_all = "snake_case")]
+pub enum AllocatorConfig {
+    Default(DefaultAllocator),
+    VolatilityNormalised(VolatilityNormalisedConfig),
+    VolatilityTarget(VolatilityTargetAllocatorConfig),
+    Kelly(KellyConfig),
+    Dca(DcaConfig),
+}
+
+impl AllocatorConfig {
+    /// Constructs the configured [`ConfiguredAllocator`], erroring if it's parameters are
+    /// invalid.
+    pub fn build(&self) -> Result<ConfiguredAllocator, ConfigError> {
+        Ok(match *self {
+            AllocatorConfig::Default(allocator) => ConfiguredAllocator::Default(allocator),
+            AllocatorConfig::VolatilityNormalised(config) => {
+                ConfiguredAllocator::VolatilityNormalised(
+                    VolatilityNormalisedAllocator::new(config).map_err(Box::new)?,
+                )
+            }
+            AllocatorConfig::VolatilityTarget(config) => ConfiguredAllocator::VolatilityTarget(
+                VolatilityTargetAllocator::new(config).map_err(Box::new)?,
+            ),
+            AllocatorConfig::Kelly(config) => {
+                ConfiguredAllocator::Kelly(KellyAllocator::new(config))
+            }
+            AllocatorConfig::Dca(config) => ConfiguredAllocator::Dca(DcaAllocator::new(config)),
+        })
+    }
+}
+
+/// [`OrderAllocator`] constructed from an [`AllocatorConfig`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum ConfiguredAllocator {
+    Default(DefaultAllocator),
+    VolatilityNormalised(VolatilityNormalisedAllocator),
+    VolatilityTarget(VolatilityTargetAllocator),
+    Kelly(KellyAllocator),
+    Dca(DcaAllocator),
+}
+
+impl ConfiguredAllocator {
+    fn as_allocator(&self) -> &dyn OrderAllocator {
+        match self {
+            ConfiguredAllocator::Default(allocator) => allocator,
+            ConfiguredAllocator::VolatilityNormalised(allocator) => allocator,
+            ConfiguredAllocator::VolatilityTarget(allocator) => allocator,
+            ConfiguredAllocator::Kelly(allocator) => allocator,
+            ConfiguredAllocator::Dca(allocator) => allocator,
+        }
+    }
+
+    fn as_allocator_mut(&mut self) -> &mut dyn OrderAllocator {
+        match self {
+            ConfiguredAllocator::Default(allocator) => allocator,
+            ConfiguredAllocator::VolatilityNormalised(allocator) => allocator,
+            ConfiguredAllocator::VolatilityTarget(allocator) => allocator,
+            ConfiguredAllocator::Kelly(allocator) => allocator,
+            ConfiguredAllocator::Dca(allocator) => allocator,
+        }
+    }
+}
+
+impl OrderAllocator for ConfiguredAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        position: Option<&Position>,
+        signal_strength: SignalStrength,
+    ) {
+        self.as_allocator()
+            .allocate_order(order, position, signal_strength)
+    }
+
+    fn allocate_scale_in(
+        &self,
+        order: &mut OrderEvent,
+        position: &Position,
+        signal_strength: SignalStrength,
+    ) -> bool {
+        self.as_allocator()
+            .allocate_scale_in(order, position, signal_strength)
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        self.as_allocator_mut().update_from_market(market)
+    }
+
+    fn update_from_exit(&mut self, position: &Position) {
+        self.as_allocator_mut().update_from_exit(position)
+    }
+
+    fn update_from_equity(&mut self, time: DateTime<Utc>, equity: f64) {
+        self.as_allocator_mut().update_from_equity(time, equity)
+    }
+}
+
+/// Risk manager type & parameters a [`ConfiguredRisk`] is constructed from.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+#[serde(tag = "risk_type", rename_all = "snake_case")]
+pub enum RiskConfig {
+    Default,
+    StopLoss(StopLossConfig),
+    TrailingStop(TrailingStopConfig),
+    AtrStop(AtrStopConfig),
+    /// Daily loss circuit breaker layered over the inner `risk` manager.
+    CircuitBreaker {
+        #[serde(flatten)]
+        config: CircuitBreakerConfig,
+        risk: Box<RiskConfig>,
+    },
+}
+
+impl RiskConfig {
+    /// Constructs the configured [`ConfiguredRisk`].
+    pub fn build(&self) -> ConfiguredRisk {
+        match self {
+            RiskConfig::Default => ConfiguredRisk::Default(DefaultRisk {}),
+            RiskConfig::StopLoss(config) => {
+                ConfiguredRisk::StopLoss(StopLossRisk::new(config.clone()))
+            }
+            RiskConfig::TrailingStop(config) => {
+                ConfiguredRisk::TrailingStop(TrailingStopRisk::new(*config))
+            }
+            RiskConfig::AtrStop(config) => ConfiguredRisk::AtrStop(AtrStopRisk::new(*config)),
+            RiskConfig::CircuitBreaker { config, risk } => ConfiguredRisk::CircuitBreaker(
+                Box::new(CircuitBreakerRisk::new(*config, risk.build())),
+            ),
+        }
+    }
+}
+
+/// [`OrderEvaluator`] constructed from a [`RiskConfig`].
+///
+/// [`OrderEvaluator`] has an associated const so can't be used as a trait object, so each method
+/// is dispatched to the configured variant explicitly.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub enum ConfiguredRisk {
+    Default(DefaultRisk),
+    StopLoss(StopLossRisk),
+    TrailingStop(TrailingStopRisk),
+    AtrStop(AtrStopRisk),
+    CircuitBreaker(Box<CircuitBreakerRisk<ConfiguredRisk>>),
+}
+
+impl OrderEvaluator for ConfiguredRisk {
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, order: OrderEvent) -> Option<OrderEvent> {
+        match self {
+            ConfiguredRisk::Default(risk) => risk.evaluate_order(order),
+            ConfiguredRisk::StopLoss(risk) => risk.evaluate_order(order),
+            ConfiguredRisk::TrailingStop(risk) => risk.evaluate_order(order),
+            ConfiguredRisk::AtrStop(risk) => risk.evaluate_order(order),
+            ConfiguredRisk::CircuitBreaker(risk) => risk.evaluate_order(order),
+        }
+    }
+
+    fn should_exit(&self, position: &Position) -> bool {
+        match self {
+            ConfiguredRisk::Default(risk) => risk.should_exit(position),
+            ConfiguredRisk::StopLoss(risk) => risk.should_exit(position),
+            ConfiguredRisk::TrailingStop(risk) => risk.should_exit(position),
+            ConfiguredRisk::AtrStop(risk) => risk.should_exit(position),
+            ConfiguredRisk::CircuitBreaker(risk) => risk.should_exit(position),
+        }
+    }
+
+    fn exit_reason(&self, position: &Position) -> CloseReason {
+        match self {
+            ConfiguredRisk::Default(risk) => risk.exit_reason(position),
+            ConfiguredRisk::StopLoss(risk) => risk.exit_reason(position),
+            ConfiguredRisk::TrailingStop(risk) => risk.exit_reason(position),
+            ConfiguredRisk::AtrStop(risk) => risk.exit_reason(position),
+            ConfiguredRisk::CircuitBreaker(risk) => risk.exit_reason(position),
+        }
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        match self {
+            ConfiguredRisk::Default(risk) => risk.update_from_market(market),
+            ConfiguredRisk::StopLoss(risk) => risk.update_from_market(market),
+            ConfiguredRisk::TrailingStop(risk) => risk.update_from_market(market),
+            ConfiguredRisk::AtrStop(risk) => risk.update_from_market(market),
+            ConfiguredRisk::CircuitBreaker(risk) => risk.update_from_market(market),
+        }
+    }
+
+    fn update_from_equity(&mut self, time: DateTime<Utc>, equity: f64) {
+        match self {
+            ConfiguredRisk::Default(risk) => risk.update_from_equity(time, equity),
+            ConfiguredRisk::StopLoss(risk) => risk.update_from_equity(time, equity),
+            ConfiguredRisk::TrailingStop(risk) => risk.update_from_equity(time, equity),
+            ConfiguredRisk::AtrStop(risk) => risk.update_from_equity(time, equity),
+            ConfiguredRisk::CircuitBreaker(risk) => risk.update_from_equity(time, equity),
+        }
+    }
+
+    fn update_from_exit(&mut self, position: &Position) {
+        match self {
+            ConfiguredRisk::Default(risk) => risk.update_from_exit(position),
+            ConfiguredRisk::StopLoss(risk) => risk.update_from_exit(position),
+            ConfiguredRisk::TrailingStop(risk) => risk.update_from_exit(position),
+            ConfiguredRisk::AtrStop(risk) => risk.update_from_exit(position),
+            ConfiguredRisk::CircuitBreaker(risk) => risk.update_from_exit(position),
+        }
+    }
+
+    fn evaluate_margin(&self, order: OrderEvent, margin: &MarginAccount) -> Option<OrderEvent> {
+        match self {
+            ConfiguredRisk::Default(risk) => risk.evaluate_margin(order, margin),
+            ConfiguredRisk::StopLoss(risk) => risk.evaluate_margin(order, margin),
+            ConfiguredRisk::TrailingStop(risk) => risk.evaluate_margin(order, margin),
+            ConfiguredRisk::AtrStop(risk) => risk.evaluate_margin(order, margin),
+            ConfiguredRisk::CircuitBreaker(risk) => risk.evaluate_margin(order, margin),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::{portfolio::MetaPortfolio, repository::in_memory::InMemoryRepository},
+        statistic::summary::pnl::PnLReturnSummary,
+        test_util::order_event,
+    };
+    use barter_instrument::{
+        asset::symbol::Symbol, exchange::ExchangeId, instrument::kind::InstrumentKind,
+        market::Market,
+    };
+    use uuid::Uuid;
+
+    const CONFIG: &str = r#"{
+        "data": {
+            "source_type": "csv",
+            "path": "data/candles.csv",
+            "exchange": "binance_spot",
+            "instrument": { "base": "btc", "quote": "usdt", "instrument_kind": "spot" }
+        },
+        "strategy": {
+            "strategy_type": "rsi",
+            "rsi_period": 21
+        },
+        "portfolio": {
+            "starting_cash": 10000.0,
+            "allocator": { "allocator_type": "default", "default_order_value": 250.0 },
+            "risk": { "risk_type": "trailing_stop", "activation": 0.02, "retracement": 0.05 }
+        },
+        "execution": {
+            "simulated_fees_pct": { "exchange": 0.001, "slippage": 0.0, "network": 0.0 }
+        }
+    }"#;
+
+    #[test]
+    fn backtest_config_builds_configured_components() {
+        let config = BacktestConfig::from_json(CONFIG).unwrap();
+
+        match config.strategy.build() {
+            ConfiguredStrategy::Rsi(strategy) => assert_eq!(strategy.rsi_period(), 21),
+            _ => panic!("expected a configured RSIStrategy"),
+        }
+
+        assert_eq!(config.execution.simulated_fees_pct.exchange, 0.001);
+        assert!(
+            matches!(config.data, DataConfig::Csv(ref csv) if csv.instrument.base == Symbol::from("btc"))
+        );
+
+        assert_eq!(
+            config.portfolio.allocator.build().unwrap(),
+            ConfiguredAllocator::Default(DefaultAllocator {
+                default_order_value: 250.0
+            })
+        );
+        assert!(matches!(
+            config.portfolio.risk.build(),
+            ConfiguredRisk::TrailingStop(_)
+        ));
+
+        let portfolio: Result<MetaPortfolio<_, _, _, PnLReturnSummary>, _> = config
+            .portfolio
+            .builder()
+            .unwrap()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("btc", "usdt", InstrumentKind::Spot),
+            )])
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .statistic_config(())
+            .build_and_init();
+        assert!(portfolio.is_ok());
+    }
+
+    #[test]
+    fn backtest_config_with_unknown_strategy_type_errors_descriptively() {
+        let config = CONFIG.replace(
+            r#""strategy_type": "rsi""#,
+            r#""strategy_type": "ichimoku""#,
+        );
+
+        let error = BacktestConfig::from_json(&config).unwrap_err().to_string();
+        assert!(
+            error.contains("unknown variant `ichimoku`") && error.contains("`rsi`"),
+            "{error}"
+        );
+    }
+
+    #[test]
+    fn backtest_config_with_zero_volatility_period_errors_instead_of_panicking() {
+        let allocators = [
+            r#"{ "allocator_type": "volatility_normalised", "risk_unit": 10.0, "volatility_period": 0, "default_order_value": 250.0 }"#,
+            r#"{ "allocator_type": "volatility_target", "starting_equity": 10000.0, "target_volatility": 0.15, "periods_per_year": 365.0, "volatility_period": 0, "max_leverage": 1.0, "default_order_value": 250.0 }"#,
+        ];
+
+        for allocator in allocators {
+            let config = CONFIG.replace(
+                r#"{ "allocator_type": "default", "default_order_value": 250.0 }"#,
+                allocator,
+            );
+            let config = BacktestConfig::from_json(&config).unwrap();
+
+            assert!(
+                matches!(
+                    config.portfolio.allocator.build(),
+                    Err(ConfigError::Portfolio(_))
+                ),
+                "{allocator}"
+            );
+        }
+    }
+
+    #[test]
+    fn backtest_config_builds_circuit_breaker_risk_over_inner_risk() {
+        let config = CONFIG.replace(
+            r#""risk": { "risk_type": "trailing_stop", "activation": 0.02, "retracement": 0.05 }"#,
+            r#""risk": {
+                "risk_type": "circuit_breaker",
+                "daily_loss_limit": 500.0,
+                "risk": { "risk_type": "trailing_stop", "activation": 0.02, "retracement": 0.05 }
+            }"#,
+        );
+        let config = BacktestConfig::from_json(&config).unwrap();
+
+        let mut risk = config.portfolio.risk.build();
+        match &risk {
+            ConfiguredRisk::CircuitBreaker(circuit_breaker) => {
+                assert_eq!(circuit_breaker.config.daily_loss_limit, 500.0);
+                assert!(matches!(
+                    circuit_breaker.risk,
+                    ConfiguredRisk::TrailingStop(_)
+                ));
+            }
+            _ => panic!("expected a configured CircuitBreakerRisk"),
+        }
+
+        // Entries are rejected once the daily loss limit is exceeded
+        let time = Utc::now();
+        risk.update_from_equity(time, 10000.0);
+        assert!(risk.evaluate_order(order_event()).is_some());
+        risk.update_from_equity(time, 9400.0);
+        assert_eq!(risk.evaluate_order(order_event()), None);
+    }
+}
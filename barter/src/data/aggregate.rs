@@ -0,0 +1,423 @@
+use crate::{
+    clock::{Clock, SystemClock},
+    data::{AsyncMarketGenerator, Feed, MarketGenerator},
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::{candle::Candle, trade::PublicTrade},
+};
+use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+/// [`Feed`] wrapper that aggregates the [`PublicTrade`]s of an inner [`MarketGenerator`] into
+/// [`Candle`]s on fixed time boundaries aligned to the Unix epoch (eg/ every 60s).
+///
+/// A window `[start, start + timeframe)` yields one closed [`Candle`] with a close time of
+/// `start + timeframe` once a [`PublicTrade`] past the window boundary is received. Windows
+/// without any trades are emitted as flat [`Candle`]s carrying forward the previous close. The
+/// in-progress window is flushed when the inner [`Feed`] is [`Feed::Finished`], and can be
+/// inspected at any time for live use via [`TradeAggregator::tick`]. Non-trade market events are
+/// passed through unchanged.
+#[derive(Debug)]
+pub struct TradeAggregator<Data> {
+    pub data: Data,
+    timeframe: Duration,
+    windows: HashMap<(ExchangeId, Instrument), Window>,
+    output: VecDeque<MarketEvent<Instrument, DataKind>>,
+    finished: bool,
+    clock: Arc<dyn Clock>,
+}
+
+/// In-progress [`Candle`] aggregated from the [`PublicTrade`]s within a time window.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Window {
+    start: DateTime<Utc>,
+    candle: Candle,
+}
+
+impl<Data> MarketGenerator<MarketEvent<Instrument, DataKind>> for TradeAggregator<Data>
+where
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            if let Some(feed) = self.next_output() {
+                break feed;
+            }
+
+            let feed = self.data.next();
+            if let Some(feed) = self.process(feed) {
+                break feed;
+            }
+        }
+    }
+}
+
+impl<Data> AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> for TradeAggregator<Data>
+where
+    Data: AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> + Send,
+{
+    async fn next_market(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            if let Some(feed) = self.next_output() {
+                break feed;
+            }
+
+            let feed = self.data.next_market().await;
+            if let Some(feed) = self.process(feed) {
+                break feed;
+            }
+        }
+    }
+}
+
+impl<Data> TradeAggregator<Data> {
+    /// Constructs a new [`TradeAggregator`] that aggregates the inner [`MarketGenerator`]
+    /// [`PublicTrade`]s into [`Candle`]s of the provided timeframe.
+    pub fn new(data: Data, timeframe: Duration) -> Self {
+        assert!(
+            timeframe > Duration::zero(),
+            "TradeAggregator timeframe must be positive"
+        );
+
+        Self {
+            data,
+            timeframe,
+            windows: HashMap::new(),
+            output: VecDeque::new(),
+            finished: false,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Use the provided [`Clock`] to timestamp the time_received of each aggregated [`Candle`],
+    /// rather than the wall-clock [`SystemClock`]. The [`Clock`] is advanced to the exchange time
+    /// of each inner market event.
+    pub fn with_clock<C>(self, clock: C) -> Self
+    where
+        C: Clock + 'static,
+    {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+
+    /// Returns a snapshot of every in-progress [`Candle`] without closing its window. Intended to
+    /// be called on a timer tick by live consumers that want intra-window updates.
+    ///
+    /// Windows that ended before `now` are not closed until the next [`PublicTrade`] arrives, so
+    /// their snapshot still reflects the last window that received a trade.
+    pub fn tick(&self, now: DateTime<Utc>) -> Vec<MarketEvent<Instrument, DataKind>> {
+        self.windows
+            .iter()
+            .map(|((exchange, instrument), window)| {
+                window_market_event(*exchange, instrument.clone(), window.candle, now)
+            })
+            .collect()
+    }
+
+    /// Returns the next queued output [`Feed`], if any.
+    fn next_output(&mut self) -> Option<Feed<MarketEvent<Instrument, DataKind>>> {
+        match self.output.pop_front() {
+            Some(event) => Some(Feed::Next(event)),
+            None => self.finished.then_some(Feed::Finished),
+        }
+    }
+
+    /// Processes the next inner [`Feed`], returning a [`Feed::Unhealthy`] to be passed through.
+    fn process(
+        &mut self,
+        feed: Feed<MarketEvent<Instrument, DataKind>>,
+    ) -> Option<Feed<MarketEvent<Instrument, DataKind>>> {
+        match feed {
+            Feed::Next(market) => {
+                self.clock.advance(market.time_exchange);
+                self.update(market);
+                None
+            }
+            Feed::Unhealthy => Some(Feed::Unhealthy),
+            Feed::Finished => {
+                // Flush in-progress windows in close time order
+                let mut windows = self.windows.drain().collect::<Vec<_>>();
+                windows.sort_by_key(|(_, window)| window.start);
+                self.output
+                    .extend(windows.into_iter().map(|((exchange, instrument), window)| {
+                        window_market_event(exchange, instrument, window.candle, self.clock.time())
+                    }));
+                self.finished = true;
+                None
+            }
+        }
+    }
+
+    /// Aggregates the next inner market event into the associated window, queueing any closed
+    /// [`Candle`]s or non-trade market event for output.
+    fn update(&mut self, market: MarketEvent<Instrument, DataKind>) {
+        let DataKind::Trade(trade) = &market.kind else {
+            self.output.push_back(market);
+            return;
+        };
+
+        let start = self.window_start(market.time_exchange);
+        let key = (market.exchange, market.instrument.clone());
+
+        let Some(window) = self.windows.get_mut(&key) else {
+            self.windows
+                .insert(key, Window::new(start, self.timeframe, trade));
+            return;
+        };
+
+        if start <= window.start {
+            // Late trades for an already closed window are aggregated into the current window
+            window.aggregate(trade);
+            return;
+        }
+
+        // Close the current window, filling any empty windows with flat candles
+        let mut closed = *window;
+        let time_received = self.clock.time();
+        loop {
+            self.output.push_back(window_market_event(
+                key.0,
+                key.1.clone(),
+                closed.candle,
+                time_received,
+            ));
+
+            let next_start = closed.start + self.timeframe;
+            if next_start >= start {
+                break;
+            }
+            closed = Window::flat(next_start, self.timeframe, closed.candle.close);
+        }
+
+        *window = Window::new(start, self.timeframe, trade);
+    }
+
+    /// Determines the start of the time window containing the provided trade time.
+    fn window_start(&self, time: DateTime<Utc>) -> DateTime<Utc> {
+        let timeframe_ms = self.timeframe.num_milliseconds().max(1);
+        let start = time.timestamp_millis().div_euclid(timeframe_ms) * timeframe_ms;
+        DateTime::from_timestamp_millis(start).unwrap_or(time)
+    }
+}
+
+impl Window {
+    fn new(start: DateTime<Utc>, timeframe: Duration, trade: &PublicTrade) -> Self {
+        Self {
+            start,
+            candle: Candle {
+                close_time: start + timeframe,
+                open: trade.price,
+                high: trade.price,
+                low: trade.price,
+                close: trade.price,
+                volume: trade.amount,
+                trade_count: 1,
+            },
+        }
+    }
+
+    fn flat(start: DateTime<Utc>, timeframe: Duration, close: f64) -> Self {
+        Self {
+            start,
+            candle: Candle {
+                close_time: start + timeframe,
+                open: close,
+                high: close,
+                low: close,
+                close,
+                volume: 0.0,
+                trade_count: 0,
+            },
+        }
+    }
+
+    fn aggregate(&mut self, trade: &PublicTrade) {
+        self.candle.high = self.candle.high.max(trade.price);
+        self.candle.low = self.candle.low.min(trade.price);
+        self.candle.close = trade.price;
+        self.candle.volume += trade.amount;
+        self.candle.trade_count += 1;
+    }
+}
+
+/// Constructs a [`Candle`] [`MarketEvent`] timestamped at the [`Candle`] close time.
+fn window_market_event(
+    exchange: ExchangeId,
+    instrument: Instrument,
+    candle: Candle,
+    time_received: DateTime<Utc>,
+) -> MarketEvent<Instrument, DataKind> {
+    MarketEvent {
+        time_exchange: candle.close_time,
+        time_received,
+        exchange,
+        instrument,
+        kind: DataKind::Candle(candle),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::historical::MarketFeed;
+    use barter_instrument::instrument::kind::InstrumentKind;
+    use barter_integration::Side;
+    use chrono::TimeZone;
+
+    fn second(second: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::seconds(second)
+    }
+
+    fn trade_event(
+        second_offset: i64,
+        price: f64,
+        amount: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        MarketEvent {
+            time_exchange: second(second_offset),
+            time_received: second(second_offset),
+            exchange: ExchangeId::BinanceSpot,
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            kind: DataKind::Trade(PublicTrade {
+                id: second_offset.to_string(),
+                price,
+                amount,
+                side: Side::Buy,
+            }),
+        }
+    }
+
+    fn drain<Data>(feed: &mut TradeAggregator<Data>) -> Vec<Candle>
+    where
+        Data: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    {
+        let mut candles = Vec::new();
+        while let Feed::Next(event) = feed.next() {
+            let DataKind::Candle(candle) = event.kind else {
+                panic!("expected DataKind::Candle");
+            };
+            candles.push(candle);
+        }
+        candles
+    }
+
+    #[test]
+    fn aggregate_trades_spanning_three_windows_into_candles() {
+        let trades = vec![
+            // [00:00, 00:01)
+            trade_event(5, 100.0, 1.0),
+            trade_event(20, 105.0, 2.0),
+            trade_event(59, 98.0, 1.0),
+            // [00:01, 00:02) - trade exactly on the boundary belongs to the next window
+            trade_event(60, 99.0, 0.5),
+            trade_event(90, 110.0, 0.5),
+            // [00:02, 00:03)
+            trade_event(125, 108.0, 3.0),
+            trade_event(150, 107.0, 1.0),
+        ];
+
+        let mut feed = TradeAggregator::new(MarketFeed::new(trades), Duration::seconds(60));
+
+        let expected = vec![
+            Candle {
+                close_time: second(60),
+                open: 100.0,
+                high: 105.0,
+                low: 98.0,
+                close: 98.0,
+                volume: 4.0,
+                trade_count: 3,
+            },
+            Candle {
+                close_time: second(120),
+                open: 99.0,
+                high: 110.0,
+                low: 99.0,
+                close: 110.0,
+                volume: 1.0,
+                trade_count: 2,
+            },
+            Candle {
+                close_time: second(180),
+                open: 108.0,
+                high: 108.0,
+                low: 107.0,
+                close: 107.0,
+                volume: 4.0,
+                trade_count: 2,
+            },
+        ];
+
+        assert_eq!(drain(&mut feed), expected);
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn empty_windows_carry_forward_previous_close_as_flat_candles() {
+        let trades = vec![trade_event(10, 100.0, 1.0), trade_event(190, 120.0, 2.0)];
+
+        let mut feed = TradeAggregator::new(MarketFeed::new(trades), Duration::seconds(60));
+
+        let flat = |close_time| Candle {
+            close_time,
+            open: 100.0,
+            high: 100.0,
+            low: 100.0,
+            close: 100.0,
+            volume: 0.0,
+            trade_count: 0,
+        };
+
+        let expected = vec![
+            Candle {
+                volume: 1.0,
+                trade_count: 1,
+                ..flat(second(60))
+            },
+            flat(second(120)),
+            flat(second(180)),
+            Candle {
+                close_time: second(240),
+                open: 120.0,
+                high: 120.0,
+                low: 120.0,
+                close: 120.0,
+                volume: 2.0,
+                trade_count: 1,
+            },
+        ];
+
+        assert_eq!(drain(&mut feed), expected);
+    }
+
+    #[test]
+    fn tick_snapshots_in_progress_candle_without_closing_window() {
+        let trades = vec![trade_event(5, 100.0, 1.0), trade_event(30, 101.0, 1.0)];
+        let mut feed = TradeAggregator::new(MarketFeed::new(trades), Duration::seconds(60));
+
+        // Consume both trades so the window is in-progress but not yet flushed
+        let trade = feed.data.market_iterator.next().unwrap();
+        feed.update(trade);
+        assert_eq!(feed.tick(second(10)).len(), 1);
+        let trade = feed.data.market_iterator.next().unwrap();
+        feed.update(trade);
+
+        let snapshot = feed.tick(second(45));
+        assert_eq!(snapshot.len(), 1);
+        assert_eq!(snapshot[0].time_received, second(45));
+        let DataKind::Candle(candle) = &snapshot[0].kind else {
+            panic!("expected DataKind::Candle");
+        };
+        assert_eq!((candle.close, candle.trade_count), (101.0, 2));
+
+        // Snapshot does not consume the in-progress window
+        assert_eq!(drain(&mut feed).len(), 1);
+    }
+}
@@ -15,4 +15,28 @@ pub enum DataError {
 
     #[error("Barter-Data: {0}")]
     Data(#[from] barter_data::error::DataError),
+
+    #[error("IO: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("CSV file is missing the required column: {0}")]
+    CsvColumnMissing(String),
+
+    #[error("CSV row at line {line} is malformed: {reason}")]
+    CsvRowMalformed { line: u64, reason: String },
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet file is missing the required column: {0}")]
+    ParquetColumnMissing(String),
+
+    #[cfg(feature = "parquet")]
+    #[error("Parquet row {row} is malformed: {reason}")]
+    ParquetRowMalformed { row: u64, reason: String },
 }
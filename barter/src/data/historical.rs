@@ -1,4 +1,20 @@
-use crate::data::{Feed, MarketGenerator};
+use crate::{
+    clock::{Clock, SystemClock},
+    data::{error::DataError, AsyncMarketGenerator, Feed, MarketGenerator},
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, future::Future, io::Read, path::PathBuf, str::FromStr, sync::Arc};
+use tracing::warn;
+
+/// Historical [`Feed`] of [`Candle`] market events streamed lazily from a Parquet file.
+#[cfg(feature = "parquet")]
+pub mod parquet;
 
 /// Historical [`Feed`] of market events.
 #[derive(Debug)]
@@ -20,6 +36,16 @@ where
     }
 }
 
+impl<Iter> AsyncMarketGenerator<Iter::Item> for MarketFeed<Iter>
+where
+    Iter: Iterator,
+    Iter::Item: Send,
+{
+    fn next_market(&mut self) -> impl Future<Output = Feed<Iter::Item>> + Send {
+        std::future::ready(self.next())
+    }
+}
+
 impl<Iter> MarketFeed<Iter>
 where
     Iter: Iterator,
@@ -35,3 +61,366 @@ where
         }
     }
 }
+
+/// CSV header names of the columns used to construct each [`Candle`].
+///
+/// The `close_time` column may contain either an RFC3339 timestamp or epoch milliseconds. If
+/// `trade_count` is `None`, every [`Candle`] is given a trade count of zero.
+#[derive(Clone, Eq, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CsvColumns {
+    pub close_time: String,
+    pub open: String,
+    pub high: String,
+    pub low: String,
+    pub close: String,
+    pub volume: String,
+    pub trade_count: Option<String>,
+}
+
+impl Default for CsvColumns {
+    fn default() -> Self {
+        Self {
+            close_time: "close_time".to_owned(),
+            open: "open".to_owned(),
+            high: "high".to_owned(),
+            low: "low".to_owned(),
+            close: "close".to_owned(),
+            volume: "volume".to_owned(),
+            trade_count: Some("trade_count".to_owned()),
+        }
+    }
+}
+
+/// Configuration for constructing a [`CsvCandleFeed`] via the new() constructor method.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CsvConfig {
+    pub path: PathBuf,
+    pub exchange: ExchangeId,
+    pub instrument: Instrument,
+    #[serde(default)]
+    pub columns: CsvColumns,
+}
+
+/// Index of each [`CsvColumns`] column within a CSV record.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+struct CsvColumnIndices {
+    close_time: usize,
+    open: usize,
+    high: usize,
+    low: usize,
+    close: usize,
+    volume: usize,
+    trade_count: Option<usize>,
+}
+
+/// Historical [`Feed`] of [`Candle`] market events streamed lazily from a CSV source, one row at
+/// a time.
+///
+/// Malformed rows yield a [`Feed::Unhealthy`], and the associated [`DataError`] can be inspected
+/// via [`CsvCandleFeed::take_error`]. The [`Feed`] is [`Feed::Finished`] at EOF.
+#[derive(Debug)]
+pub struct CsvCandleFeed<R = File> {
+    exchange: ExchangeId,
+    instrument: Instrument,
+    columns: CsvColumnIndices,
+    reader: csv::Reader<R>,
+    record: csv::StringRecord,
+    error: Option<DataError>,
+    clock: Arc<dyn Clock>,
+}
+
+impl<R> MarketGenerator<MarketEvent<Instrument, DataKind>> for CsvCandleFeed<R>
+where
+    R: Read,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        let candle = match self.reader.read_record(&mut self.record) {
+            Ok(false) => return Feed::Finished,
+            Ok(true) => self.parse_candle(),
+            Err(error) => Err(DataError::from(error)),
+        };
+
+        match candle {
+            Ok(candle) => {
+                self.clock.advance(candle.close_time);
+                Feed::Next(MarketEvent {
+                    time_exchange: candle.close_time,
+                    time_received: self.clock.time(),
+                    exchange: self.exchange,
+                    instrument: self.instrument.clone(),
+                    kind: DataKind::Candle(candle),
+                })
+            }
+            Err(error) => {
+                warn!(%error, "CsvCandleFeed skipping malformed row");
+                self.error = Some(error);
+                Feed::Unhealthy
+            }
+        }
+    }
+}
+
+impl<R> AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> for CsvCandleFeed<R>
+where
+    R: Read,
+{
+    fn next_market(
+        &mut self,
+    ) -> impl Future<Output = Feed<MarketEvent<Instrument, DataKind>>> + Send {
+        std::future::ready(self.next())
+    }
+}
+
+// DataError is large due to the SocketError variant, but is only returned on construction or for
+// malformed rows, so boxing it is not worthwhile
+#[allow(clippy::result_large_err)]
+impl CsvCandleFeed<File> {
+    /// Constructs a new [`CsvCandleFeed`] that lazily reads the CSV file at the configured path.
+    pub fn new(config: CsvConfig) -> Result<Self, DataError> {
+        let file = File::open(&config.path)?;
+        Self::from_reader(file, config.exchange, config.instrument, &config.columns)
+    }
+}
+
+#[allow(clippy::result_large_err)]
+impl<R> CsvCandleFeed<R>
+where
+    R: Read,
+{
+    /// Constructs a new [`CsvCandleFeed`] that lazily reads CSV rows from the provided reader.
+    ///
+    /// Returns a [`DataError::CsvColumnMissing`] if the CSV headers do not contain every
+    /// configured [`CsvColumns`] column.
+    pub fn from_reader(
+        reader: R,
+        exchange: ExchangeId,
+        instrument: Instrument,
+        columns: &CsvColumns,
+    ) -> Result<Self, DataError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let headers = reader.headers()?.clone();
+
+        let index = |column: &str| {
+            headers
+                .iter()
+                .position(|header| header == column)
+                .ok_or_else(|| DataError::CsvColumnMissing(column.to_owned()))
+        };
+
+        let columns = CsvColumnIndices {
+            close_time: index(&columns.close_time)?,
+            open: index(&columns.open)?,
+            high: index(&columns.high)?,
+            low: index(&columns.low)?,
+            close: index(&columns.close)?,
+            volume: index(&columns.volume)?,
+            trade_count: columns.trade_count.as_deref().map(index).transpose()?,
+        };
+
+        Ok(Self {
+            exchange,
+            instrument,
+            columns,
+            reader,
+            record: csv::StringRecord::new(),
+            error: None,
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Use the provided [`Clock`] to timestamp the time_received of each market event, rather
+    /// than the wall-clock [`SystemClock`]. The [`Clock`] is advanced to the exchange time of
+    /// each market event before it is timestamped.
+    pub fn with_clock<C>(self, clock: C) -> Self
+    where
+        C: Clock + 'static,
+    {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+
+    /// Takes the [`DataError`] associated with the most recent [`Feed::Unhealthy`], if any.
+    pub fn take_error(&mut self) -> Option<DataError> {
+        self.error.take()
+    }
+
+    /// Parses the current CSV record into a [`Candle`].
+    fn parse_candle(&self) -> Result<Candle, DataError> {
+        let line = self.record.position().map_or(0, |position| position.line());
+
+        let field = |index: usize| {
+            self.record
+                .get(index)
+                .ok_or_else(|| DataError::CsvRowMalformed {
+                    line,
+                    reason: format!("missing field at column index {index}"),
+                })
+        };
+
+        let parse = |index: usize| {
+            let value = field(index)?;
+            f64::from_str(value).map_err(|error| DataError::CsvRowMalformed {
+                line,
+                reason: format!("invalid number {value:?}: {error}"),
+            })
+        };
+
+        let close_time = field(self.columns.close_time).and_then(|value| {
+            parse_close_time(value).ok_or_else(|| DataError::CsvRowMalformed {
+                line,
+                reason: format!("invalid close_time {value:?}"),
+            })
+        })?;
+
+        let trade_count = match self.columns.trade_count {
+            Some(index) => {
+                let value = field(index)?;
+                u64::from_str(value).map_err(|error| DataError::CsvRowMalformed {
+                    line,
+                    reason: format!("invalid trade_count {value:?}: {error}"),
+                })?
+            }
+            None => 0,
+        };
+
+        Ok(Candle {
+            close_time,
+            open: parse(self.columns.open)?,
+            high: parse(self.columns.high)?,
+            low: parse(self.columns.low)?,
+            close: parse(self.columns.close)?,
+            volume: parse(self.columns.volume)?,
+            trade_count,
+        })
+    }
+}
+
+/// Parses a CSV close time as either an RFC3339 timestamp or epoch milliseconds.
+fn parse_close_time(value: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            i64::from_str(value)
+                .ok()
+                .and_then(DateTime::from_timestamp_millis)
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use barter_instrument::instrument::kind::InstrumentKind;
+
+    fn instrument() -> Instrument {
+        Instrument::from(("btc", "usdt", InstrumentKind::Spot))
+    }
+
+    fn candle_closes<R: Read>(feed: &mut CsvCandleFeed<R>) -> Vec<Option<f64>> {
+        let mut closes = Vec::new();
+        loop {
+            match feed.next() {
+                Feed::Next(MarketEvent {
+                    kind: DataKind::Candle(candle),
+                    ..
+                }) => closes.push(Some(candle.close)),
+                Feed::Next(_) => panic!("expected DataKind::Candle"),
+                Feed::Unhealthy => closes.push(None),
+                Feed::Finished => break closes,
+            }
+        }
+    }
+
+    #[test]
+    fn csv_candle_feed_streams_fixture_and_surfaces_malformed_row() {
+        let mut feed = CsvCandleFeed::new(CsvConfig {
+            path: PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/data/candles_malformed.csv"
+            )),
+            exchange: ExchangeId::BinanceSpot,
+            instrument: instrument(),
+            columns: CsvColumns::default(),
+        })
+        .unwrap();
+
+        // First row yields a Candle w/ RFC3339 close_time
+        let Feed::Next(first) = feed.next() else {
+            panic!("expected first Candle");
+        };
+        assert_eq!(first.exchange, ExchangeId::BinanceSpot);
+        assert_eq!(
+            first.kind,
+            DataKind::Candle(Candle {
+                close_time: DateTime::parse_from_rfc3339("2024-01-01T01:00:00Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+                open: 100.0,
+                high: 110.0,
+                low: 95.0,
+                close: 105.0,
+                volume: 12.5,
+                trade_count: 40,
+            })
+        );
+
+        // Malformed row is surfaced as an error, then remaining rows continue to EOF
+        assert_eq!(
+            candle_closes(&mut feed),
+            vec![Some(107.0), None, Some(103.0)]
+        );
+        assert!(matches!(
+            feed.take_error(),
+            Some(DataError::CsvRowMalformed { line: 4, .. })
+        ));
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    fn csv_candle_feed_maps_custom_columns() {
+        let csv = "timestamp,o,h,l,c,vol\n\
+                   1704070800000,100,110,95,105,12.5\n";
+
+        let mut feed = CsvCandleFeed::from_reader(
+            csv.as_bytes(),
+            ExchangeId::BinanceSpot,
+            instrument(),
+            &CsvColumns {
+                close_time: "timestamp".to_owned(),
+                open: "o".to_owned(),
+                high: "h".to_owned(),
+                low: "l".to_owned(),
+                close: "c".to_owned(),
+                volume: "vol".to_owned(),
+                trade_count: None,
+            },
+        )
+        .unwrap();
+
+        let Feed::Next(event) = feed.next() else {
+            panic!("expected Candle");
+        };
+        assert_eq!(
+            event.time_exchange,
+            DateTime::parse_from_rfc3339("2024-01-01T01:00:00Z").unwrap()
+        );
+        assert_eq!(candle_closes(&mut feed), Vec::<Option<f64>>::new());
+    }
+
+    #[test]
+    fn csv_candle_feed_errors_on_missing_column() {
+        let csv = "close_time,open,high,low,close\n";
+        let result = CsvCandleFeed::from_reader(
+            csv.as_bytes(),
+            ExchangeId::BinanceSpot,
+            instrument(),
+            &CsvColumns::default(),
+        );
+
+        assert!(matches!(result, Err(DataError::CsvColumnMissing(column)) if column == "volume"));
+    }
+}
@@ -0,0 +1,416 @@
+use crate::{
+    clock::{Clock, SystemClock},
+    data::{error::DataError, AsyncMarketGenerator, Feed, MarketGenerator},
+};
+use ::parquet::{
+    file::{
+        metadata::RowGroupMetaData,
+        reader::FileReader,
+        serialized_reader::{ReadOptionsBuilder, SerializedFileReader},
+        statistics::Statistics,
+    },
+    record::{reader::RowIter, Field, Row},
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{fs::File, future::Future, path::PathBuf, sync::Arc};
+use tracing::warn;
+
+/// Name of the Parquet column containing each [`Candle`] close time.
+pub const COLUMN_CLOSE_TIME: &str = "close_time";
+
+/// Configuration for constructing a [`ParquetCandleFeed`] via the new() constructor method.
+///
+/// The Parquet file is expected to contain the columns "close_time" (epoch milliseconds, or a
+/// millisecond/microsecond timestamp), "open", "high", "low", "close", "volume", and optionally
+/// "trade_count". Candles with a close time outside of the inclusive `start` & `end` bounds are
+/// skipped, as are entire row groups whose close time statistics lie outside the bounds.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct ParquetConfig {
+    pub path: PathBuf,
+    pub exchange: ExchangeId,
+    pub instrument: Instrument,
+    #[serde(default)]
+    pub start: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub end: Option<DateTime<Utc>>,
+}
+
+/// Historical [`Feed`] of [`Candle`] market events streamed lazily from a Parquet file, reading
+/// one row group at a time so memory usage is bounded regardless of file size.
+///
+/// Malformed rows yield a [`Feed::Unhealthy`], and the associated [`DataError`] can be inspected
+/// via [`ParquetCandleFeed::take_error`]. The [`Feed`] is [`Feed::Finished`] at EOF.
+pub struct ParquetCandleFeed {
+    exchange: ExchangeId,
+    instrument: Instrument,
+    start: Option<i64>,
+    end: Option<i64>,
+    rows: RowIter<'static>,
+    rows_read: u64,
+    error: Option<DataError>,
+    clock: Arc<dyn Clock>,
+}
+
+impl std::fmt::Debug for ParquetCandleFeed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ParquetCandleFeed")
+            .field("exchange", &self.exchange)
+            .field("instrument", &self.instrument)
+            .field("start", &self.start)
+            .field("end", &self.end)
+            .field("rows_read", &self.rows_read)
+            .field("clock", &self.clock)
+            .finish()
+    }
+}
+
+impl MarketGenerator<MarketEvent<Instrument, DataKind>> for ParquetCandleFeed {
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            let row = match self.rows.next() {
+                None => break Feed::Finished,
+                Some(row) => row.map_err(DataError::from),
+            };
+            self.rows_read += 1;
+
+            match row.and_then(|row| parse_candle(&row, self.rows_read)) {
+                Ok(candle) if self.contains(candle.close_time.timestamp_millis()) => {
+                    self.clock.advance(candle.close_time);
+                    break Feed::Next(MarketEvent {
+                        time_exchange: candle.close_time,
+                        time_received: self.clock.time(),
+                        exchange: self.exchange,
+                        instrument: self.instrument.clone(),
+                        kind: DataKind::Candle(candle),
+                    });
+                }
+                Ok(_) => continue,
+                Err(error) => {
+                    warn!(%error, "ParquetCandleFeed skipping malformed row");
+                    self.error = Some(error);
+                    break Feed::Unhealthy;
+                }
+            }
+        }
+    }
+}
+
+impl AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> for ParquetCandleFeed {
+    fn next_market(
+        &mut self,
+    ) -> impl Future<Output = Feed<MarketEvent<Instrument, DataKind>>> + Send {
+        std::future::ready(self.next())
+    }
+}
+
+// DataError is large due to the SocketError variant, but is only returned on construction or for
+// malformed rows, so boxing it is not worthwhile
+#[allow(clippy::result_large_err)]
+impl ParquetCandleFeed {
+    /// Constructs a new [`ParquetCandleFeed`] that lazily reads the Parquet file at the
+    /// configured path, skipping any row groups outside of the configured time range.
+    pub fn new(config: ParquetConfig) -> Result<Self, DataError> {
+        let start = config.start.map(|start| start.timestamp_millis());
+        let end = config.end.map(|end| end.timestamp_millis());
+
+        let options = ReadOptionsBuilder::new()
+            .with_predicate(Box::new(move |row_group: &RowGroupMetaData, _| {
+                row_group_overlaps(row_group, start, end)
+            }))
+            .build();
+
+        let reader = SerializedFileReader::new_with_options(File::open(&config.path)?, options)?;
+
+        let schema = reader.metadata().file_metadata().schema_descr();
+        for column in [COLUMN_CLOSE_TIME, "open", "high", "low", "close", "volume"] {
+            if !schema
+                .root_schema()
+                .get_fields()
+                .iter()
+                .any(|field| field.name() == column)
+            {
+                return Err(DataError::ParquetColumnMissing(column.to_owned()));
+            }
+        }
+
+        Ok(Self {
+            exchange: config.exchange,
+            instrument: config.instrument,
+            start,
+            end,
+            rows: RowIter::from_file_into(Box::new(reader)),
+            rows_read: 0,
+            error: None,
+            clock: Arc::new(SystemClock),
+        })
+    }
+
+    /// Use the provided [`Clock`] to timestamp the time_received of each market event, rather
+    /// than the wall-clock [`SystemClock`]. The [`Clock`] is advanced to the exchange time of
+    /// each market event before it is timestamped.
+    pub fn with_clock<C>(self, clock: C) -> Self
+    where
+        C: Clock + 'static,
+    {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+
+    /// Takes the [`DataError`] associated with the most recent [`Feed::Unhealthy`], if any.
+    pub fn take_error(&mut self) -> Option<DataError> {
+        self.error.take()
+    }
+
+    /// Determines if the provided epoch millisecond close time is within the configured range.
+    fn contains(&self, close_time: i64) -> bool {
+        self.start.is_none_or(|start| close_time >= start)
+            && self.end.is_none_or(|end| close_time <= end)
+    }
+}
+
+/// Determines if a row group may contain close times within the inclusive `start` & `end`
+/// bounds, using the close time column statistics. Row groups without statistics are read.
+fn row_group_overlaps(row_group: &RowGroupMetaData, start: Option<i64>, end: Option<i64>) -> bool {
+    let statistics = row_group
+        .columns()
+        .iter()
+        .find(|column| column.column_path().string() == COLUMN_CLOSE_TIME)
+        .and_then(|column| column.statistics());
+
+    let Some(Statistics::Int64(statistics)) = statistics else {
+        return true;
+    };
+
+    let before_start = start
+        .zip(statistics.max_opt())
+        .is_some_and(|(start, max)| *max < start);
+    let after_end = end
+        .zip(statistics.min_opt())
+        .is_some_and(|(end, min)| *min > end);
+
+    !(before_start || after_end)
+}
+
+/// Parses a Parquet [`Row`] into a [`Candle`].
+#[allow(clippy::result_large_err)]
+fn parse_candle(row: &Row, row_number: u64) -> Result<Candle, DataError> {
+    let malformed = |reason: String| DataError::ParquetRowMalformed {
+        row: row_number,
+        reason,
+    };
+
+    let field = |name: &str| {
+        row.get_column_iter()
+            .find(|(column, _)| column.as_str() == name)
+            .map(|(_, field)| field)
+    };
+
+    let number = |name: &str| match field(name) {
+        Some(Field::Double(value)) => Ok(*value),
+        Some(Field::Float(value)) => Ok(f64::from(*value)),
+        Some(Field::Long(value)) => Ok(*value as f64),
+        Some(Field::Int(value)) => Ok(f64::from(*value)),
+        other => Err(malformed(format!("invalid {name}: {other:?}"))),
+    };
+
+    let close_time = match field(COLUMN_CLOSE_TIME) {
+        Some(Field::Long(millis) | Field::TimestampMillis(millis)) => {
+            DateTime::from_timestamp_millis(*millis)
+        }
+        Some(Field::TimestampMicros(micros)) => DateTime::from_timestamp_micros(*micros),
+        _ => None,
+    }
+    .ok_or_else(|| malformed(format!("invalid {COLUMN_CLOSE_TIME}")))?;
+
+    let trade_count =
+        match field("trade_count") {
+            None | Some(Field::Null) => 0,
+            Some(Field::Long(count)) => u64::try_from(*count)
+                .map_err(|_| malformed(format!("invalid trade_count: {count}")))?,
+            Some(Field::Int(count)) => u64::try_from(*count)
+                .map_err(|_| malformed(format!("invalid trade_count: {count}")))?,
+            Some(other) => return Err(malformed(format!("invalid trade_count: {other:?}"))),
+        };
+
+    Ok(Candle {
+        close_time,
+        open: number("open")?,
+        high: number("high")?,
+        low: number("low")?,
+        close: number("close")?,
+        volume: number("volume")?,
+        trade_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::parquet::{
+        data_type::{DoubleType, Int64Type},
+        file::{properties::WriterProperties, writer::SerializedFileWriter},
+        schema::parser::parse_message_type,
+    };
+    use barter_instrument::instrument::kind::InstrumentKind;
+    use chrono::TimeZone;
+    use std::sync::Arc;
+
+    const SCHEMA: &str = "
+        message candle {
+            REQUIRED INT64 close_time;
+            REQUIRED DOUBLE open;
+            REQUIRED DOUBLE high;
+            REQUIRED DOUBLE low;
+            REQUIRED DOUBLE close;
+            REQUIRED DOUBLE volume;
+            REQUIRED INT64 trade_count;
+        }
+    ";
+
+    /// Writes a temporary Parquet file containing the provided row groups of (close_time_ms,
+    /// close) candles, returning its path.
+    fn write_parquet(row_groups: &[Vec<(i64, f64)>]) -> PathBuf {
+        let path = std::env::temp_dir().join(format!("barter_{}.parquet", uuid::Uuid::new_v4()));
+        let schema = Arc::new(parse_message_type(SCHEMA).unwrap());
+        let mut writer = SerializedFileWriter::new(
+            File::create(&path).unwrap(),
+            schema,
+            Arc::new(WriterProperties::builder().build()),
+        )
+        .unwrap();
+
+        for candles in row_groups {
+            let mut row_group = writer.next_row_group().unwrap();
+            let mut column_index = 0;
+            while let Some(mut column) = row_group.next_column().unwrap() {
+                match column_index {
+                    0 => {
+                        let times = candles.iter().map(|(time, _)| *time).collect::<Vec<_>>();
+                        column
+                            .typed::<Int64Type>()
+                            .write_batch(&times, None, None)
+                            .unwrap();
+                    }
+                    6 => {
+                        let counts = vec![10; candles.len()];
+                        column
+                            .typed::<Int64Type>()
+                            .write_batch(&counts, None, None)
+                            .unwrap();
+                    }
+                    _ => {
+                        let closes = candles.iter().map(|(_, close)| *close).collect::<Vec<_>>();
+                        column
+                            .typed::<DoubleType>()
+                            .write_batch(&closes, None, None)
+                            .unwrap();
+                    }
+                }
+                column.close().unwrap();
+                column_index += 1;
+            }
+            row_group.close().unwrap();
+        }
+        writer.close().unwrap();
+
+        path
+    }
+
+    fn config(path: PathBuf) -> ParquetConfig {
+        ParquetConfig {
+            path,
+            exchange: ExchangeId::BinanceSpot,
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            start: None,
+            end: None,
+        }
+    }
+
+    fn closes(feed: &mut ParquetCandleFeed) -> Vec<f64> {
+        let mut closes = Vec::new();
+        loop {
+            match feed.next() {
+                Feed::Next(MarketEvent {
+                    kind: DataKind::Candle(candle),
+                    ..
+                }) => closes.push(candle.close),
+                Feed::Finished => break closes,
+                other => panic!("unexpected Feed: {other:?}"),
+            }
+        }
+    }
+
+    fn millis(hour: u32) -> i64 {
+        Utc.with_ymd_and_hms(2024, 1, 1, hour, 0, 0)
+            .unwrap()
+            .timestamp_millis()
+    }
+
+    #[test]
+    fn parquet_candle_feed_empty_file() {
+        let path = write_parquet(&[]);
+        let mut feed = ParquetCandleFeed::new(config(path.clone())).unwrap();
+
+        assert_eq!(feed.next(), Feed::Finished);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn parquet_candle_feed_single_row_group() {
+        let path = write_parquet(&[vec![
+            (millis(1), 101.0),
+            (millis(2), 102.0),
+            (millis(3), 103.0),
+        ]]);
+        let mut feed = ParquetCandleFeed::new(config(path.clone())).unwrap();
+
+        let Feed::Next(first) = feed.next() else {
+            panic!("expected first Candle");
+        };
+        assert_eq!(first.time_exchange.timestamp_millis(), millis(1));
+        assert_eq!(
+            first.kind,
+            DataKind::Candle(Candle {
+                close_time: first.time_exchange,
+                open: 101.0,
+                high: 101.0,
+                low: 101.0,
+                close: 101.0,
+                volume: 101.0,
+                trade_count: 10,
+            })
+        );
+        assert_eq!(closes(&mut feed), vec![102.0, 103.0]);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn parquet_candle_feed_time_range_excludes_first_and_last_candles() {
+        let path = write_parquet(&[
+            vec![(millis(1), 101.0), (millis(2), 102.0)],
+            vec![(millis(3), 103.0), (millis(4), 104.0)],
+            vec![(millis(5), 105.0)],
+        ]);
+
+        let mut feed = ParquetCandleFeed::new(ParquetConfig {
+            start: DateTime::from_timestamp_millis(millis(2)),
+            end: DateTime::from_timestamp_millis(millis(4)),
+            ..config(path.clone())
+        })
+        .unwrap();
+
+        assert_eq!(closes(&mut feed), vec![102.0, 103.0, 104.0]);
+
+        // Final row group lies entirely after the range, so is never read
+        assert_eq!(feed.rows_read, 4);
+        std::fs::remove_file(path).unwrap();
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Feed, MarketGenerator};
+use super::{AsyncMarketGenerator, Feed, MarketGenerator};
 use barter_data::{
     event::{DataKind, MarketEvent},
     streams::{consumer::MarketStreamEvent, reconnect},
@@ -28,6 +28,18 @@ impl<Event> MarketGenerator<Event> for MarketFeed<Event> {
     }
 }
 
+impl<Event> AsyncMarketGenerator<Event> for MarketFeed<Event>
+where
+    Event: Send,
+{
+    async fn next_market(&mut self) -> Feed<Event> {
+        self.market_rx
+            .recv()
+            .await
+            .map_or(Feed::Finished, Feed::Next)
+    }
+}
+
 impl<Event> MarketFeed<Event> {
     /// Initialises a live [`MarketFeed`] that yields market `Event`s from the provided
     /// [`mpsc::UnboundedReceiver`].
@@ -55,13 +67,15 @@ where
     St: Stream<Item = MarketStreamEvent<Instrument, DataKind>> + Unpin,
 {
     fn next(&mut self) -> Feed<MarketEvent> {
-        self.market_stream
-            .next()
-            .map(|event| match event {
-                reconnect::Event::Reconnecting(_) => Feed::Unhealthy,
-                reconnect::Event::Item(item) => Feed::Next(item),
-            })
-            .unwrap_or(Feed::Finished)
+        loop {
+            return match self.market_stream.next() {
+                Some(reconnect::Event::Reconnecting(_)) => Feed::Unhealthy,
+                // Reconnection markers carry no market data, so continue to the next event
+                Some(reconnect::Event::Reconnected { .. }) => continue,
+                Some(reconnect::Event::Item(item)) => Feed::Next(item),
+                None => Feed::Finished,
+            };
+        }
     }
 }
 
@@ -1,5 +1,9 @@
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
 use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
+use std::future::Future;
 
 /// Barter data module specific errors.
 pub mod error;
@@ -10,12 +14,36 @@ pub mod live;
 /// Historical market event feed for backtesting.
 pub mod historical;
 
+/// Resampling of market event feed candles into a larger timeframe.
+pub mod resample;
+
+/// Aggregation of market event feed trades into candles.
+pub mod aggregate;
+
+/// Replay of a historical market event feed paced at a controllable speed.
+pub mod replay;
+
 /// Generates the next `Event`. Acts as the system heartbeat.
 pub trait MarketGenerator<Event> {
     /// Return the next market `Event`.
     fn next(&mut self) -> Feed<Event>;
 }
 
+/// Asynchronously generates the next `Event`, so live & historical feeds share one interface that
+/// an async consumer can be written once against.
+///
+/// The [`Trader`](crate::engine::trader::Trader) runs on a dedicated thread & polls the
+/// synchronous [`MarketGenerator`], so this interface is intended for consumers running on an
+/// async runtime.
+///
+/// Live feeds await the next market `Event` rather than blocking the thread. Historical feeds
+/// already hold their next `Event`, so return an immediately ready future without any await
+/// points, allocations, or yields to the runtime.
+pub trait AsyncMarketGenerator<Event> {
+    /// Return the next market `Event`, awaiting it if it is not yet available.
+    fn next_market(&mut self) -> impl Future<Output = Feed<Event>> + Send;
+}
+
 /// Communicates the state of the [`Feed`] as well as the next event.
 #[derive(Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub enum Feed<Event> {
@@ -43,3 +71,144 @@ impl Default for MarketMeta {
         }
     }
 }
+
+/// Determine the close price of the input [`MarketEvent`], if it's [`DataKind`] has one.
+pub fn determine_market_close(market: &MarketEvent<Instrument, DataKind>) -> Option<f64> {
+    match &market.kind {
+        DataKind::Trade(trade) => Some(trade.price),
+        DataKind::Candle(candle) => Some(candle.close),
+        DataKind::OrderBookL1(book_l1) => book_l1.volume_weighed_mid_price().to_f64(),
+        DataKind::Ticker(ticker) => Some(ticker.last_price),
+        DataKind::OrderBook(_) | DataKind::Liquidation(_) | DataKind::FundingRate(_) => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::{
+            aggregate::TradeAggregator,
+            replay::{ReplayFeed, ReplaySpeed},
+            resample::ResamplingFeed,
+        },
+        test_util::{market_event_candle, market_event_trade},
+    };
+    use barter_integration::Side;
+    use futures::FutureExt;
+    use tokio::sync::mpsc;
+
+    /// Generic async consumer of any [`AsyncMarketGenerator`], as an engine event loop would be,
+    /// collecting the close of every market event until the [`Feed`] is [`Feed::Finished`].
+    async fn consume_closes<Generator>(mut generator: Generator) -> Vec<f64>
+    where
+        Generator: AsyncMarketGenerator<MarketEvent<Instrument, DataKind>>,
+    {
+        let mut closes = Vec::new();
+        loop {
+            match generator.next_market().await {
+                Feed::Next(market) => closes.extend(determine_market_close(&market)),
+                Feed::Unhealthy => continue,
+                Feed::Finished => break closes,
+            }
+        }
+    }
+
+    fn candle(close: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_candle();
+        if let DataKind::Candle(candle) = &mut market.kind {
+            candle.close = close;
+        }
+        market
+    }
+
+    #[tokio::test]
+    async fn historic_and_live_feeds_drive_the_same_async_consumer() {
+        let closes = vec![100.0, 101.0, 102.0];
+
+        // Historic feed resolves each market event immediately, without awaiting
+        let mut historic = historical::MarketFeed::new(closes.clone().into_iter().map(candle));
+        assert!(matches!(
+            historic.next_market().now_or_never(),
+            Some(Feed::Next(_))
+        ));
+        assert_eq!(consume_closes(historic).await, closes[1..]);
+
+        // Mock live feed awaits market events sent from a separate task, as a WebSocket would
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        let mut live = live::MarketFeed::new(market_rx);
+        assert!(live.next_market().now_or_never().is_none());
+
+        let sender = tokio::spawn({
+            let closes = closes.clone();
+            async move {
+                for close in closes {
+                    tokio::task::yield_now().await;
+                    market_tx.send(candle(close)).unwrap();
+                }
+            }
+        });
+
+        assert_eq!(consume_closes(live).await, closes);
+        sender.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn wrapped_feeds_yield_the_same_market_events_sync_and_async() {
+        // Candles & trades every 30 seconds, spanning several 1 minute windows
+        let start = DateTime::<Utc>::from_timestamp(1_700_000_040, 0).unwrap();
+        let events = (0..6)
+            .flat_map(|index| {
+                let time = start + chrono::Duration::seconds(30 * index);
+                let mut candle = candle(100.0 + index as f64);
+                candle.time_exchange = time;
+                if let DataKind::Candle(candle) = &mut candle.kind {
+                    candle.close_time = time;
+                }
+                let mut trade = market_event_trade(Side::Buy);
+                trade.time_exchange = time;
+                if let DataKind::Trade(trade) = &mut trade.kind {
+                    trade.price = 200.0 + index as f64;
+                }
+                [candle, trade]
+            })
+            .collect::<Vec<_>>();
+        let historic = || historical::MarketFeed::new(events.clone().into_iter());
+
+        let sync_closes =
+            |mut generator: Box<dyn MarketGenerator<MarketEvent<Instrument, DataKind>>>| {
+                std::iter::from_fn(move || match generator.next() {
+                    Feed::Next(market) => Some(determine_market_close(&market)),
+                    Feed::Unhealthy => Some(None),
+                    Feed::Finished => None,
+                })
+                .flatten()
+                .collect::<Vec<_>>()
+            };
+
+        let timeframe = chrono::Duration::minutes(1);
+        assert_eq!(
+            consume_closes(ResamplingFeed::new(historic(), timeframe)).await,
+            sync_closes(Box::new(ResamplingFeed::new(historic(), timeframe)))
+        );
+        assert_eq!(
+            consume_closes(TradeAggregator::new(historic(), timeframe)).await,
+            sync_closes(Box::new(TradeAggregator::new(historic(), timeframe)))
+        );
+
+        let max_delay = std::time::Duration::from_millis(1);
+        assert_eq!(
+            consume_closes(ReplayFeed::new(
+                historic(),
+                ReplaySpeed::Realtime,
+                max_delay
+            ))
+            .await,
+            sync_closes(Box::new(ReplayFeed::new(
+                historic(),
+                ReplaySpeed::Realtime,
+                max_delay
+            )))
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use crate::data::{AsyncMarketGenerator, Feed, MarketGenerator};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+/// Speed a [`ReplayFeed`] replays historical market events at, relative to the time elapsed
+/// between their exchange timestamps.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub enum ReplaySpeed {
+    /// Yield every market event immediately, without any delay.
+    #[default]
+    AsFastAsPossible,
+    /// Delay each market event by the time elapsed since the previous market event.
+    Realtime,
+    /// Delay each market event by the time elapsed since the previous market event, divided by
+    /// the multiplier (eg/ 10.0 replays 10x faster than realtime).
+    Multiplier(f64),
+}
+
+impl ReplaySpeed {
+    /// Scales the time elapsed between two market events into a replay delay.
+    fn scale(&self, elapsed: Duration) -> Duration {
+        match *self {
+            ReplaySpeed::AsFastAsPossible => Duration::ZERO,
+            ReplaySpeed::Realtime => elapsed,
+            ReplaySpeed::Multiplier(multiplier) if multiplier > 0.0 => elapsed.div_f64(multiplier),
+            ReplaySpeed::Multiplier(_) => Duration::ZERO,
+        }
+    }
+}
+
+/// [`Feed`] wrapper that replays the market events of an inner historical [`MarketGenerator`]
+/// at a controllable [`ReplaySpeed`], so live code paths can be exercised with historical data.
+///
+/// Each market event is delayed by the time elapsed between it's exchange timestamp & that of
+/// the previous market event, scaled by the [`ReplaySpeed`]. Delays are capped at the
+/// `max_delay` so large gaps in the data (eg/ weekends) are compressed.
+///
+/// **Note:**
+/// The [`Trader`](crate::engine::trader::Trader) polls it's [`MarketGenerator`] synchronously on
+/// a dedicated thread, so the [`ReplayFeed`] paces market events by blocking that thread. Async
+/// consumers of the [`AsyncMarketGenerator`] are paced by a non-blocking timer instead.
+#[derive(Debug)]
+pub struct ReplayFeed<Data> {
+    pub data: Data,
+    speed: ReplaySpeed,
+    max_delay: Duration,
+    /// Exchange timestamp of the previous market event, & the instant it was yielded.
+    previous: Option<(DateTime<Utc>, Instant)>,
+}
+
+impl<Data> MarketGenerator<MarketEvent<Instrument, DataKind>> for ReplayFeed<Data>
+where
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        let market = match self.data.next() {
+            Feed::Next(market) => market,
+            feed => return feed,
+        };
+
+        if let Some(delay) = self.remaining_delay(market.time_exchange, Instant::now()) {
+            std::thread::sleep(delay);
+        }
+
+        self.previous = Some((market.time_exchange, Instant::now()));
+        Feed::Next(market)
+    }
+}
+
+impl<Data> AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> for ReplayFeed<Data>
+where
+    Data: AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> + Send,
+{
+    async fn next_market(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        let market = match self.data.next_market().await {
+            Feed::Next(market) => market,
+            feed => return feed,
+        };
+
+        if let Some(delay) = self.remaining_delay(market.time_exchange, Instant::now()) {
+            tokio::time::sleep(delay).await;
+        }
+
+        self.previous = Some((market.time_exchange, Instant::now()));
+        Feed::Next(market)
+    }
+}
+
+impl<Data> ReplayFeed<Data> {
+    /// Constructs a new [`ReplayFeed`] that replays the inner [`MarketGenerator`] market events
+    /// at the provided [`ReplaySpeed`], capping the delay between market events at the
+    /// `max_delay`.
+    pub fn new(data: Data, speed: ReplaySpeed, max_delay: Duration) -> Self {
+        Self {
+            data,
+            speed,
+            max_delay,
+            previous: None,
+        }
+    }
+
+    /// Determines how much longer to wait before yielding a market event with the provided
+    /// exchange timestamp, accounting for the time already spent since the previous market event
+    /// was yielded. Returns `None` if it can be yielded immediately.
+    fn remaining_delay(&self, time_exchange: DateTime<Utc>, now: Instant) -> Option<Duration> {
+        let (previous_time, previous_instant) = self.previous?;
+
+        let elapsed = (time_exchange - previous_time).to_std().unwrap_or_default();
+        let delay = self.speed.scale(elapsed).min(self.max_delay);
+
+        delay
+            .checked_sub(now.duration_since(previous_instant))
+            .filter(|remaining| !remaining.is_zero())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{data::historical::MarketFeed, test_util::market_event_candle};
+
+    fn candle_events(offsets_ms: &[i64]) -> Vec<MarketEvent<Instrument, DataKind>> {
+        let start = Utc::now();
+        offsets_ms
+            .iter()
+            .map(|offset| {
+                let mut market = market_event_candle();
+                market.time_exchange = start + chrono::Duration::milliseconds(*offset);
+                market
+            })
+            .collect()
+    }
+
+    /// Replays every market event, returning the time elapsed before each was yielded.
+    fn replay(mut feed: ReplayFeed<impl MarketGenerator<MarketEvent>>) -> Vec<Duration> {
+        let start = Instant::now();
+        let mut elapsed = Vec::new();
+        while let Feed::Next(_) = feed.next() {
+            elapsed.push(start.elapsed());
+        }
+        elapsed
+    }
+
+    #[test]
+    fn replay_feed_paces_market_events_by_replay_speed() {
+        // 60ms between market events, followed by a one hour gap
+        let events = candle_events(&[0, 60, 120, 3_600_000]);
+        let max_delay = Duration::from_millis(100);
+
+        // No delay yields every market event immediately
+        let fast = ReplayFeed::new(
+            MarketFeed::new(events.clone()),
+            ReplaySpeed::AsFastAsPossible,
+            max_delay,
+        );
+        let elapsed = replay(fast);
+        assert_eq!(elapsed.len(), 4);
+        assert!(elapsed[3] < Duration::from_millis(20), "{elapsed:?}");
+
+        // Realtime respects the timestamp deltas, compressing the gap to the max delay
+        let realtime = ReplayFeed::new(MarketFeed::new(events), ReplaySpeed::Realtime, max_delay);
+        let elapsed = replay(realtime);
+        let expected = [0, 60, 120, 220].map(Duration::from_millis);
+        for (actual, expected) in elapsed.iter().zip(expected) {
+            assert!(
+                *actual >= expected && *actual < expected + Duration::from_millis(40),
+                "actual: {elapsed:?}, expected: {expected:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn replay_speed_multiplier_scales_delays() {
+        let elapsed = Duration::from_secs(10);
+        assert_eq!(
+            ReplaySpeed::Multiplier(10.0).scale(elapsed),
+            Duration::from_secs(1)
+        );
+        assert_eq!(ReplaySpeed::Multiplier(0.0).scale(elapsed), Duration::ZERO);
+        assert_eq!(ReplaySpeed::Realtime.scale(elapsed), elapsed);
+    }
+}
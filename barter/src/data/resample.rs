@@ -0,0 +1,357 @@
+use crate::{
+    clock::{Clock, SystemClock},
+    data::{AsyncMarketGenerator, Feed, MarketGenerator},
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::candle::Candle,
+};
+use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+/// [`Feed`] wrapper that resamples the [`Candle`]s of an inner [`MarketGenerator`] into a larger
+/// timeframe (eg/ 1m -> 15m).
+///
+/// Candles are bucketed by their close time into timeframe boundaries aligned to the Unix epoch,
+/// rather than by count, so gaps in the underlying series do not misalign later buckets. Each
+/// bucket `(end - timeframe, end]` yields one [`Candle`] with a close time of `end`, once a
+/// [`Candle`] from a later bucket is received. Partial buckets are flushed when the inner
+/// [`Feed`] is [`Feed::Finished`]. Non-candle market events are passed through unchanged.
+#[derive(Debug)]
+pub struct ResamplingFeed<Data> {
+    pub data: Data,
+    resampler: CandleResampler,
+    output: VecDeque<MarketEvent<Instrument, DataKind>>,
+    finished: bool,
+    clock: Arc<dyn Clock>,
+}
+
+/// Resamples [`Candle`] market events into a larger timeframe, bucketing them by close time as
+/// described by [`ResamplingFeed`]. Used directly where the resampled [`Candle`]s are consumed
+/// alongside the originals (eg/ by a
+/// [`MultiTimeframeStrategy`](crate::strategy::timeframe::MultiTimeframeStrategy)).
+#[derive(Clone, Debug)]
+pub struct CandleResampler {
+    timeframe_ms: i64,
+    buckets: HashMap<(ExchangeId, Instrument), Bucket>,
+}
+
+/// [`Candle`] aggregated from the underlying candles within a timeframe bucket.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct Bucket {
+    end: DateTime<Utc>,
+    candle: Candle,
+}
+
+impl<Data> MarketGenerator<MarketEvent<Instrument, DataKind>> for ResamplingFeed<Data>
+where
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+{
+    fn next(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            if let Some(feed) = self.next_output() {
+                break feed;
+            }
+
+            let feed = self.data.next();
+            if let Some(feed) = self.process(feed) {
+                break feed;
+            }
+        }
+    }
+}
+
+impl<Data> AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> for ResamplingFeed<Data>
+where
+    Data: AsyncMarketGenerator<MarketEvent<Instrument, DataKind>> + Send,
+{
+    async fn next_market(&mut self) -> Feed<MarketEvent<Instrument, DataKind>> {
+        loop {
+            if let Some(feed) = self.next_output() {
+                break feed;
+            }
+
+            let feed = self.data.next_market().await;
+            if let Some(feed) = self.process(feed) {
+                break feed;
+            }
+        }
+    }
+}
+
+impl<Data> ResamplingFeed<Data> {
+    /// Constructs a new [`ResamplingFeed`] that resamples the inner [`MarketGenerator`]
+    /// [`Candle`]s into the provided timeframe.
+    pub fn new(data: Data, timeframe: Duration) -> Self {
+        Self {
+            data,
+            resampler: CandleResampler::new(timeframe),
+            output: VecDeque::new(),
+            finished: false,
+            clock: Arc::new(SystemClock),
+        }
+    }
+
+    /// Use the provided [`Clock`] to timestamp the time_received of each resampled [`Candle`],
+    /// rather than the wall-clock [`SystemClock`]. The [`Clock`] is advanced to the exchange time
+    /// of each inner market event.
+    pub fn with_clock<C>(self, clock: C) -> Self
+    where
+        C: Clock + 'static,
+    {
+        Self {
+            clock: Arc::new(clock),
+            ..self
+        }
+    }
+
+    /// Returns the next queued output [`Feed`], if any.
+    fn next_output(&mut self) -> Option<Feed<MarketEvent<Instrument, DataKind>>> {
+        match self.output.pop_front() {
+            Some(event) => Some(Feed::Next(event)),
+            None => self.finished.then_some(Feed::Finished),
+        }
+    }
+
+    /// Processes the next inner [`Feed`], returning a [`Feed::Unhealthy`] to be passed through.
+    fn process(
+        &mut self,
+        feed: Feed<MarketEvent<Instrument, DataKind>>,
+    ) -> Option<Feed<MarketEvent<Instrument, DataKind>>> {
+        match feed {
+            Feed::Next(market) => {
+                self.clock.advance(market.time_exchange);
+                self.update(market);
+                None
+            }
+            Feed::Unhealthy => Some(Feed::Unhealthy),
+            Feed::Finished => {
+                self.output.extend(self.resampler.flush(self.clock.time()));
+                self.finished = true;
+                None
+            }
+        }
+    }
+
+    /// Aggregates the next inner market event into the associated bucket, queueing any
+    /// completed bucket or non-candle market event for output.
+    fn update(&mut self, market: MarketEvent<Instrument, DataKind>) {
+        if !matches!(market.kind, DataKind::Candle(_)) {
+            self.output.push_back(market);
+            return;
+        }
+
+        if let Some(completed) = self.resampler.update(&market, self.clock.time()) {
+            self.output.push_back(completed);
+        }
+    }
+}
+
+impl CandleResampler {
+    /// Constructs a new [`CandleResampler`] that resamples [`Candle`]s into the provided
+    /// timeframe.
+    ///
+    /// Panics if the timeframe is shorter than one millisecond, the resolution buckets are
+    /// aligned to.
+    pub fn new(timeframe: Duration) -> Self {
+        assert!(
+            timeframe.num_milliseconds() > 0,
+            "CandleResampler timeframe must be at least one millisecond"
+        );
+
+        Self {
+            timeframe_ms: timeframe.num_milliseconds(),
+            buckets: HashMap::new(),
+        }
+    }
+
+    /// Timeframe the [`Candle`]s are resampled into.
+    pub fn timeframe(&self) -> Duration {
+        Duration::milliseconds(self.timeframe_ms)
+    }
+
+    /// Aggregates the [`Candle`] market event into the associated bucket, returning the
+    /// previous bucket of the market as a resampled [`Candle`] market event if it has completed.
+    ///
+    /// Non-candle market events are ignored.
+    pub fn update(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+        time_received: DateTime<Utc>,
+    ) -> Option<MarketEvent<Instrument, DataKind>> {
+        let DataKind::Candle(candle) = &market.kind else {
+            return None;
+        };
+
+        let end = self.bucket_end(candle.close_time);
+        let key = (market.exchange, market.instrument.clone());
+
+        match self.buckets.get_mut(&key) {
+            Some(bucket) if bucket.end == end => {
+                bucket.aggregate(candle);
+                None
+            }
+            Some(bucket) if bucket.end > end => {
+                // Out of order candle for an already emitted bucket, so it cannot be aggregated
+                None
+            }
+            _ => self
+                .buckets
+                .insert(key.clone(), Bucket::new(end, *candle))
+                .map(|completed| completed.into_market_event(key.0, key.1, time_received)),
+        }
+    }
+
+    /// Drains every partial bucket as a resampled [`Candle`] market event, in close time order.
+    pub fn flush(
+        &mut self,
+        time_received: DateTime<Utc>,
+    ) -> Vec<MarketEvent<Instrument, DataKind>> {
+        let mut buckets = self.buckets.drain().collect::<Vec<_>>();
+        buckets.sort_by_key(|(_, bucket)| bucket.end);
+        buckets
+            .into_iter()
+            .map(|((exchange, instrument), bucket)| {
+                bucket.into_market_event(exchange, instrument, time_received)
+            })
+            .collect()
+    }
+
+    /// Determines the end of the timeframe bucket containing the provided close time.
+    fn bucket_end(&self, close_time: DateTime<Utc>) -> DateTime<Utc> {
+        let millis = close_time.timestamp_millis();
+        let end =
+            (millis + self.timeframe_ms - 1).div_euclid(self.timeframe_ms) * self.timeframe_ms;
+        DateTime::from_timestamp_millis(end).unwrap_or(close_time)
+    }
+}
+
+impl Bucket {
+    fn new(end: DateTime<Utc>, candle: Candle) -> Self {
+        Self {
+            end,
+            candle: Candle {
+                close_time: end,
+                ..candle
+            },
+        }
+    }
+
+    fn aggregate(&mut self, candle: &Candle) {
+        self.candle.high = self.candle.high.max(candle.high);
+        self.candle.low = self.candle.low.min(candle.low);
+        self.candle.close = candle.close;
+        self.candle.volume += candle.volume;
+        self.candle.trade_count += candle.trade_count;
+    }
+
+    fn into_market_event(
+        self,
+        exchange: ExchangeId,
+        instrument: Instrument,
+        time_received: DateTime<Utc>,
+    ) -> MarketEvent<Instrument, DataKind> {
+        MarketEvent {
+            time_exchange: self.end,
+            time_received,
+            exchange,
+            instrument,
+            kind: DataKind::Candle(self.candle),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::historical::MarketFeed;
+    use barter_instrument::instrument::kind::InstrumentKind;
+    use chrono::TimeZone;
+
+    fn minute(minute: i64) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap() + Duration::minutes(minute)
+    }
+
+    fn candle_event(close_minute: i64, price: f64) -> MarketEvent<Instrument, DataKind> {
+        MarketEvent {
+            time_exchange: minute(close_minute),
+            time_received: minute(close_minute),
+            exchange: ExchangeId::BinanceSpot,
+            instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+            kind: DataKind::Candle(Candle {
+                close_time: minute(close_minute),
+                open: price,
+                high: price + 1.0,
+                low: price - 1.0,
+                close: price + 0.5,
+                volume: 10.0,
+                trade_count: 2,
+            }),
+        }
+    }
+
+    #[test]
+    fn resample_1m_candles_into_5m_candles() {
+        // 1m candles closing at minutes 1..=12, with the candle closing at minute 7 missing
+        let candles = (1..=12)
+            .filter(|close_minute| *close_minute != 7)
+            .map(|close_minute| candle_event(close_minute, 100.0 + close_minute as f64));
+
+        let mut feed = ResamplingFeed::new(MarketFeed::new(candles), Duration::minutes(5));
+
+        let mut actual = Vec::new();
+        while let Feed::Next(event) = feed.next() {
+            let DataKind::Candle(candle) = event.kind else {
+                panic!("expected DataKind::Candle");
+            };
+            assert_eq!(event.time_exchange, candle.close_time);
+            actual.push(candle);
+        }
+
+        let expected = vec![
+            // (00:00, 00:05]
+            Candle {
+                close_time: minute(5),
+                open: 101.0,
+                high: 106.0,
+                low: 100.0,
+                close: 105.5,
+                volume: 50.0,
+                trade_count: 10,
+            },
+            // (00:05, 00:10] w/ missing minute does not shift the bucket boundaries
+            Candle {
+                close_time: minute(10),
+                open: 106.0,
+                high: 111.0,
+                low: 105.0,
+                close: 110.5,
+                volume: 40.0,
+                trade_count: 8,
+            },
+            // Partial (00:10, 00:15] bucket flushed at the end of the Feed
+            Candle {
+                close_time: minute(15),
+                open: 111.0,
+                high: 113.0,
+                low: 110.0,
+                close: 112.5,
+                volume: 20.0,
+                trade_count: 4,
+            },
+        ];
+
+        assert_eq!(actual, expected);
+        assert_eq!(feed.next(), Feed::Finished);
+    }
+
+    #[test]
+    #[should_panic(expected = "timeframe must be at least one millisecond")]
+    fn sub_millisecond_timeframe_is_rejected() {
+        CandleResampler::new(Duration::microseconds(500));
+    }
+}
@@ -1,4 +1,7 @@
-use crate::portfolio::repository::error::RepositoryError;
+use crate::{
+    execution::error::ExecutionError,
+    portfolio::{error::PortfolioError, repository::error::RepositoryError},
+};
 use thiserror::Error;
 
 /// All errors generated in barter-engine.
@@ -9,4 +12,10 @@ pub enum EngineError {
 
     #[error("Failed to interact with repository")]
     RepositoryInteractionError(#[from] RepositoryError),
+
+    #[error("Failed to interact with Portfolio: {0}")]
+    PortfolioInteractionError(#[from] PortfolioError),
+
+    #[error("Failed to interact with Execution: {0}")]
+    ExecutionInteractionError(#[from] ExecutionError),
 }
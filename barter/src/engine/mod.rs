@@ -1,6 +1,6 @@
 use crate::{
     data::MarketGenerator,
-    engine::{error::EngineError, trader::Trader},
+    engine::{error::EngineError, shadow::ShadowPortfolio, trader::Trader},
     event::{Event, MessageTransmitter},
     execution::ExecutionClient,
     portfolio::{
@@ -28,6 +28,13 @@ use uuid::Uuid;
 /// Barter Engine module specific errors.
 pub mod error;
 
+/// Progress reporting for long running [`Trader`] trading loops (eg/ backtests).
+pub mod progress;
+
+/// Hypothetical ("what-if") trading of alternative strategies against shadow Portfolios, driven
+/// by the same market data as the real Portfolio without placing real orders.
+pub mod shadow;
+
 /// Contains the trading event loop for a Trader capable of trading a single market pair. A Trader
 /// has its own Data handler, Strategy & Execution handler, as well as shared access to a global
 /// Portfolio instance.
@@ -77,6 +84,9 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     pub statistics_summary: Statistic,
+    /// [`ShadowPortfolio`]s updated by the [`Trader`]s' [`Shadow`](shadow::Shadow)s, whose
+    /// trading session summaries are generated separately from the real Portfolio's.
+    pub shadow_portfolios: Vec<ShadowPortfolio<Portfolio, Statistic>>,
 }
 
 /// Multi-threaded Trading Engine capable of trading with an arbitrary number of [`Trader`]s, one
@@ -118,6 +128,9 @@ where
     /// Uses trading session's exited [`Position`]s to calculate an average statistical summary
     /// across all [`Market`]s traded.
     statistics_summary: Statistic,
+    /// [`ShadowPortfolio`]s updated by the [`Trader`]s' [`Shadow`](shadow::Shadow)s, whose
+    /// trading session summaries are generated separately from the real Portfolio's.
+    shadow_portfolios: Vec<ShadowPortfolio<Portfolio, Statistic>>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -149,6 +162,7 @@ where
             traders: lego.traders,
             trader_command_txs: lego.trader_command_txs,
             statistics_summary: lego.statistics_summary,
+            shadow_portfolios: lego.shadow_portfolios,
         }
     }
 
@@ -162,7 +176,20 @@ where
     /// (eg/ terminate_traders, fetch_open_positions). If all of the [`Trader`]s stop organically
     /// (eg/ due to a finished [`MarketGenerator`]), the [`Engine`] terminates & prints a summary
     /// for the trading session.
-    pub async fn run(mut self) {
+    ///
+    /// Returns the statistical summary of the trading session across all [`Market`]s traded (eg/
+    /// the final [`TradingSummary`](crate::statistic::summary::trading::TradingSummary) of a
+    /// backtest).
+    pub async fn run(self) -> Statistic {
+        self.run_with_shadows().await.0
+    }
+
+    /// Run the trading [`Engine`] as per [`Engine::run`], additionally printing a separate
+    /// trading session summary for each [`ShadowPortfolio`].
+    ///
+    /// Returns the statistical summary of the real Portfolio's trading session, along with that
+    /// of each [`ShadowPortfolio`] in the order they were provided.
+    pub async fn run_with_shadows(mut self) -> (Statistic, Vec<Statistic>) {
         // Run Traders on threads & send notification when they have stopped organically
         let mut notify_traders_stopped = self.run_traders().await;
 
@@ -199,7 +226,37 @@ where
         }
 
         // Print Trading Session Summary
-        self.generate_session_summary().printstd();
+        let markets = self.trader_command_txs.into_keys().collect::<Vec<_>>();
+        let (table, summary) = generate_session_summary(
+            &self.portfolio,
+            self.engine_id,
+            &markets,
+            self.statistics_summary,
+        );
+        table.printstd();
+
+        // Print Trading Session Summary of each ShadowPortfolio
+        let shadow_summaries = self
+            .shadow_portfolios
+            .into_iter()
+            .map(|shadow| {
+                let (table, summary) = generate_session_summary(
+                    &shadow.portfolio,
+                    shadow.engine_id,
+                    &markets,
+                    shadow.statistics_summary,
+                );
+                info!(
+                    engine_id = %self.engine_id,
+                    shadow_engine_id = %shadow.engine_id,
+                    "shadow Portfolio trading session summary"
+                );
+                table.printstd();
+                summary
+            })
+            .collect();
+
+        (summary, shadow_summaries)
     }
 
     /// Runs each [`Trader`] it's own thread. Sends a message on the returned `mpsc::Receiver<bool>`
@@ -317,47 +374,59 @@ where
             );
         }
     }
+}
 
-    /// Generate a trading session summary. Uses the Portfolio's statistics per [`Market`] in
-    /// combination with the average statistics across all [`Market`]s traded.
-    fn generate_session_summary(mut self) -> Table {
-        // Fetch statistics for each Market
-        let stats_per_market = self.trader_command_txs.into_keys().filter_map(|market| {
-            let market_id = MarketId::from(&market);
+/// Generate a trading session summary for the Portfolio persisting state under the provided
+/// engine_id. Uses the Portfolio's statistics per [`Market`] in combination with the average
+/// statistics across all [`Market`]s traded, which are also returned.
+fn generate_session_summary<Portfolio, Statistic>(
+    portfolio: &Mutex<Portfolio>,
+    engine_id: Uuid,
+    markets: &[Market],
+    mut statistics_summary: Statistic,
+) -> (Table, Statistic)
+where
+    Portfolio: PositionHandler + StatisticHandler<Statistic>,
+    Statistic: PositionSummariser + TableBuilder,
+{
+    // Fetch statistics for each Market
+    let stats_per_market = markets.iter().filter_map(|market| {
+        let market_id = MarketId::from(market);
 
-            match self.portfolio.lock().get_statistics(&market_id) {
-                Ok(statistics) => Some((market_id.0, statistics)),
-                Err(error) => {
-                    error!(
-                        ?error,
-                        ?market,
-                        "failed to get Market statistics when generating trading session summary"
-                    );
-                    None
-                }
+        match portfolio.lock().get_statistics(engine_id, &market_id) {
+            Ok(statistics) => Some((market_id.0, statistics)),
+            Err(error) => {
+                error!(
+                    ?error,
+                    ?market,
+                    "failed to get Market statistics when generating trading session summary"
+                );
+                None
             }
+        }
+    });
+
+    // Generate average statistics across all markets using session's exited Positions
+    portfolio
+        .lock()
+        .get_exited_positions(engine_id)
+        .map(|exited_positions| {
+            statistics_summary.generate_summary(&exited_positions);
+        })
+        .unwrap_or_else(|error| {
+            warn!(
+                ?error,
+                why = "failed to get exited Positions from Portfolio's repository",
+                "failed to generate Statistics summary for trading session"
+            );
         });
 
-        // Generate average statistics across all markets using session's exited Positions
-        self.portfolio
-            .lock()
-            .get_exited_positions(self.engine_id)
-            .map(|exited_positions| {
-                self.statistics_summary.generate_summary(&exited_positions);
-            })
-            .unwrap_or_else(|error| {
-                warn!(
-                    ?error,
-                    why = "failed to get exited Positions from Portfolio's repository",
-                    "failed to generate Statistics summary for trading session"
-                );
-            });
+    // Combine Total & Per-Market Statistics Into Table
+    let table = crate::statistic::summary::combine(
+        stats_per_market.chain([("Total".to_smolstr(), statistics_summary)]),
+    );
 
-        // Combine Total & Per-Market Statistics Into Table
-        crate::statistic::summary::combine(
-            stats_per_market.chain([("Total".to_smolstr(), self.statistics_summary)]),
-        )
-    }
+    (table, statistics_summary)
 }
 
 /// Builder to construct [`Engine`] instances.
@@ -377,6 +446,7 @@ where
     traders: Option<Vec<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>>>,
     trader_command_txs: Option<HashMap<Market, mpsc::Sender<Command>>>,
     statistics_summary: Option<Statistic>,
+    shadow_portfolios: Option<Vec<ShadowPortfolio<Portfolio, Statistic>>>,
 }
 
 impl<EventTx, Statistic, Portfolio, Data, Strategy, Execution>
@@ -402,6 +472,7 @@ where
             traders: None,
             trader_command_txs: None,
             statistics_summary: None,
+            shadow_portfolios: None,
         }
     }
 
@@ -450,6 +521,15 @@ where
         }
     }
 
+    /// Optional [`ShadowPortfolio`]s to generate separate trading session summaries for,
+    /// defaulting to none.
+    pub fn shadow_portfolios(self, value: Vec<ShadowPortfolio<Portfolio, Statistic>>) -> Self {
+        Self {
+            shadow_portfolios: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Engine<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
@@ -472,6 +552,7 @@ where
             statistics_summary: self
                 .statistics_summary
                 .ok_or(EngineError::BuilderIncomplete("statistics_summary"))?,
+            shadow_portfolios: self.shadow_portfolios.unwrap_or_default(),
         })
     }
 }
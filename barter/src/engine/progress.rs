@@ -0,0 +1,84 @@
+use crate::portfolio::{error::PortfolioError, PortfolioReporter};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+
+/// Progress of a [`Trader`](super::trader::Trader) trading loop, reported to a progress callback
+/// every configured number of [`MarketEvent`](barter_data::event::MarketEvent)s (eg/ to drive a
+/// progress bar during a long backtest).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Progress {
+    /// Number of [`MarketEvent`](barter_data::event::MarketEvent)s processed so far.
+    pub events: u64,
+    /// Exchange timestamp of the latest [`MarketEvent`](barter_data::event::MarketEvent).
+    pub time: DateTime<Utc>,
+    /// Current Portfolio equity.
+    pub equity: f64,
+    /// Number of open Portfolio [`Position`](crate::portfolio::position::Position)s.
+    pub open_positions: usize,
+}
+
+/// Invokes a progress callback with a [`Progress`] snapshot every configured number of
+/// [`MarketEvent`](barter_data::event::MarketEvent)s.
+///
+/// The callback only receives a copy of the [`Progress`], so it cannot mutate the state of the
+/// [`Trader`](super::trader::Trader) or it's Portfolio.
+pub struct ProgressReporter<Portfolio> {
+    every: u64,
+    events: u64,
+    callback: Box<dyn FnMut(Progress) + Send>,
+    snapshot: fn(&mut Portfolio) -> Result<(f64, usize), PortfolioError>,
+}
+
+impl<Portfolio> Debug for ProgressReporter<Portfolio> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressReporter")
+            .field("every", &self.every)
+            .field("events", &self.events)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Portfolio> ProgressReporter<Portfolio>
+where
+    Portfolio: PortfolioReporter,
+{
+    /// Constructs a new [`ProgressReporter`] invoking the callback every `every` events (a value
+    /// of zero is treated as one).
+    pub fn new<Callback>(every: u64, callback: Callback) -> Self
+    where
+        Callback: FnMut(Progress) + Send + 'static,
+    {
+        Self {
+            every: every.max(1),
+            events: 0,
+            callback: Box::new(callback),
+            snapshot: |portfolio| Ok((portfolio.equity()?, portfolio.open_positions())),
+        }
+    }
+}
+
+impl<Portfolio> ProgressReporter<Portfolio> {
+    /// Counts a processed [`MarketEvent`](barter_data::event::MarketEvent), invoking the callback
+    /// with the latest [`Progress`] if it is due.
+    pub fn update(
+        &mut self,
+        time: DateTime<Utc>,
+        portfolio: &mut Portfolio,
+    ) -> Result<(), PortfolioError> {
+        self.events += 1;
+        if !self.events.is_multiple_of(self.every) {
+            return Ok(());
+        }
+
+        let (equity, open_positions) = (self.snapshot)(portfolio)?;
+        (self.callback)(Progress {
+            events: self.events,
+            time,
+            equity,
+            open_positions,
+        });
+
+        Ok(())
+    }
+}
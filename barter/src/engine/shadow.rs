@@ -0,0 +1,230 @@
+use super::error::EngineError;
+use crate::{
+    event::Event,
+    execution::{simulated::SimulatedExecution, ExecutionClient},
+    portfolio::{FillUpdater, MarketUpdater, OrderGenerator},
+    strategy::{SignalForceExit, SignalGenerator},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use parking_lot::Mutex;
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+use tracing::warn;
+use uuid::Uuid;
+
+/// Hypothetical ("what-if") trading of an alternative [`SignalGenerator`] alongside a
+/// [`Trader`](super::trader::Trader), used to compare strategies in production without placing
+/// real orders.
+///
+/// A [`Shadow`] consumes the same [`MarketEvent`]s as the [`Trader`](super::trader::Trader) it is
+/// attached to, but generates it's own [`Signal`](crate::strategy::Signal)s, and it's
+/// [`OrderEvent`](crate::portfolio::OrderEvent)s are always filled by a [`SimulatedExecution`].
+/// Every resulting [`Event`] is applied to the separate shadow Portfolio only, so shadow fills
+/// never touch the cash of the real Portfolio, and none are sent to the external [`Event`] sink.
+pub struct Shadow<Portfolio> {
+    /// Shared-access to the shadow Portfolio, separate from the real Portfolio.
+    portfolio: Arc<Mutex<Portfolio>>,
+    /// Alternative strategy whose [`Signal`](crate::strategy::Signal)s are tracked.
+    strategy: Box<dyn SignalGenerator + Send>,
+    /// Execution handler filling every shadow [`OrderEvent`](crate::portfolio::OrderEvent).
+    execution: SimulatedExecution,
+    /// Queue for storing [`Event`]s generated while processing an input.
+    event_q: VecDeque<Event>,
+    /// Flag indicating the shadow Portfolio is bankrupt, so no further [`Event`]s are processed.
+    bankrupt: bool,
+}
+
+impl<Portfolio> Debug for Shadow<Portfolio> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Shadow")
+            .field("execution", &self.execution)
+            .field("event_q", &self.event_q)
+            .field("bankrupt", &self.bankrupt)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Portfolio> Shadow<Portfolio>
+where
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+{
+    /// Constructs a new [`Shadow`] trading the provided strategy against the shadow Portfolio.
+    pub fn new<Strategy>(
+        portfolio: Arc<Mutex<Portfolio>>,
+        strategy: Strategy,
+        execution: SimulatedExecution,
+    ) -> Self
+    where
+        Strategy: SignalGenerator + Send + 'static,
+    {
+        Self {
+            portfolio,
+            strategy: Box::new(strategy),
+            execution,
+            event_q: VecDeque::with_capacity(4),
+            bankrupt: false,
+        }
+    }
+
+    /// Returns the shared-access shadow Portfolio.
+    pub fn portfolio(&self) -> &Arc<Mutex<Portfolio>> {
+        &self.portfolio
+    }
+
+    /// Determines if the shadow Portfolio is bankrupt & has stopped trading.
+    pub fn is_bankrupt(&self) -> bool {
+        self.bankrupt
+    }
+
+    /// Updates the shadow strategy, Portfolio & [`SimulatedExecution`] using the latest
+    /// [`MarketEvent`], generating & filling any resulting shadow orders.
+    pub fn update_from_market(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<(), EngineError> {
+        self.process(Event::Market(market.clone()))
+    }
+
+    /// Force-exits any open shadow Position of the [`SignalForceExit`] market (eg/ so the shadow
+    /// statistics reflect fully realised P&L at the end of a backtest).
+    pub fn force_exit(&mut self, signal_force_exit: SignalForceExit) -> Result<(), EngineError> {
+        self.process(Event::SignalForceExit(signal_force_exit))
+    }
+
+    /// Processes the input [`Event`] & every [`Event`] it generates until the event_q is empty.
+    fn process(&mut self, event: Event) -> Result<(), EngineError> {
+        if self.bankrupt {
+            return Ok(());
+        }
+
+        self.event_q.clear();
+        self.event_q.push_back(event);
+
+        while let Some(event) = self.event_q.pop_front() {
+            match event {
+                Event::Market(market) => {
+                    // Resting shadow orders traded through by this MarketEvent are filled
+                    self.event_q.extend(
+                        self.execution
+                            .update_from_market(&market)?
+                            .into_iter()
+                            .map(Event::Fill),
+                    );
+
+                    // Signals generated while the strategy is warming up are disregarded
+                    if let Some(signal) = self
+                        .strategy
+                        .generate_signal(&market)
+                        .filter(|_| self.strategy.is_warm())
+                    {
+                        self.event_q.push_back(Event::Signal(signal));
+                    }
+
+                    let portfolio = Arc::clone(&self.portfolio);
+                    let mut portfolio = portfolio.lock();
+                    portfolio.update_from_market(&market)?;
+                    self.event_q.extend(
+                        portfolio
+                            .take_expired_orders()
+                            .into_iter()
+                            .map(Event::OrderCancel),
+                    );
+
+                    let liquidation_events = portfolio.liquidate(&market)?;
+                    if self.check_bankruptcy(&liquidation_events) {
+                        return Ok(());
+                    }
+
+                    let bankruptcy_events = portfolio
+                        .check_bankruptcy(&market)?
+                        .map(Event::Bankruptcy)
+                        .into_iter()
+                        .collect::<Vec<_>>();
+                    if self.check_bankruptcy(&bankruptcy_events) {
+                        return Ok(());
+                    }
+
+                    if let Some(order) = portfolio.generate_risk_exit_order(&market)? {
+                        self.event_q.push_back(Event::OrderNew(order));
+                    }
+
+                    self.event_q.extend(
+                        portfolio
+                            .generate_rebalance_orders(&market)?
+                            .into_iter()
+                            .map(Event::OrderNew),
+                    );
+                }
+
+                Event::Signal(signal) => {
+                    if let Some(order) = self.portfolio.lock().generate_order(&signal)? {
+                        self.event_q.push_back(Event::OrderNew(order));
+                    }
+                }
+
+                Event::SignalForceExit(signal_force_exit) => {
+                    if let Some(order) = self
+                        .portfolio
+                        .lock()
+                        .generate_exit_order(signal_force_exit)?
+                    {
+                        self.event_q.push_back(Event::OrderNew(order));
+                    }
+                }
+
+                Event::OrderNew(order) => {
+                    if let Some(fill) = self.execution.submit_order(&order)? {
+                        self.event_q.push_back(Event::Fill(fill));
+                    }
+                }
+
+                Event::OrderCancel(order) => {
+                    self.execution.cancel_order(order.cid);
+                }
+
+                Event::Fill(fill) => {
+                    let fill_side_effect_events = self.portfolio.lock().update_from_fill(&fill)?;
+                    if self.check_bankruptcy(&fill_side_effect_events) {
+                        return Ok(());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true & halts the [`Shadow`] if the shadow Portfolio generated [`Event`]s contain
+    /// a [`Bankruptcy`](crate::portfolio::Bankruptcy).
+    fn check_bankruptcy(&mut self, events: &[Event]) -> bool {
+        if events
+            .iter()
+            .any(|event| matches!(event, Event::Bankruptcy(_)))
+        {
+            warn!(action = "halting Shadow", "shadow Portfolio is bankrupt");
+            self.bankrupt = true;
+            self.event_q.clear();
+        }
+
+        self.bankrupt
+    }
+}
+
+/// Shadow Portfolio attached to an [`Engine`](super::Engine), whose trading session summary is
+/// generated separately from that of the real Portfolio.
+#[derive(Debug)]
+pub struct ShadowPortfolio<Portfolio, Statistic> {
+    /// Identifier the shadow Portfolio persists it's state under, distinct from the real
+    /// Portfolio's engine_id.
+    pub engine_id: Uuid,
+    /// Shared-access to the shadow Portfolio, also provided to each [`Shadow`].
+    pub portfolio: Arc<Mutex<Portfolio>>,
+    /// Uses the shadow Portfolio's exited Positions to calculate an average statistical summary
+    /// across all markets traded.
+    pub statistics_summary: Statistic,
+}
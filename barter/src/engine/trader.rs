@@ -1,9 +1,17 @@
-use super::{error::EngineError, Command};
+use super::{
+    error::EngineError,
+    progress::{Progress, ProgressReporter},
+    shadow::Shadow,
+    Command,
+};
 use crate::{
+    clock::{Clock, SystemClock},
     data::{Feed, MarketGenerator},
     event::{Event, MessageTransmitter},
     execution::ExecutionClient,
-    portfolio::{FillUpdater, MarketUpdater, OrderGenerator},
+    portfolio::{
+        position::CloseReason, FillUpdater, MarketUpdater, OrderGenerator, PortfolioReporter,
+    },
     strategy::{SignalForceExit, SignalGenerator},
 };
 use barter_data::event::{DataKind, MarketEvent};
@@ -83,6 +91,20 @@ where
     strategy: Strategy,
     /// Execution handler that implements [`ExecutionClient`].
     execution: Execution,
+    /// [`Clock`] advanced to the exchange timestamp of every [`MarketEvent`], & used to
+    /// timestamp [`SignalForceExit`]s.
+    clock: Arc<dyn Clock>,
+    /// Force-close any open [`Position`](crate::portfolio::position::Position) of the [`Market`]
+    /// once the [`MarketGenerator`] yields [`Feed::Finished`], so the statistics reflect fully
+    /// realised P&L.
+    close_positions_on_finish: bool,
+    /// Flag indicating a [`MarketEvent`] has been received to price a [`SignalForceExit`].
+    market_received: bool,
+    /// [`Shadow`]s trading alternative strategies against shadow Portfolios, driven by the same
+    /// [`MarketEvent`]s as this [`Trader`].
+    shadows: Vec<Shadow<Portfolio>>,
+    /// Optional [`ProgressReporter`] invoked as [`MarketEvent`]s are processed.
+    progress: Option<ProgressReporter<Portfolio>>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -114,6 +136,11 @@ where
             data: lego.data,
             strategy: lego.strategy,
             execution: lego.execution,
+            clock: Arc::new(SystemClock),
+            close_positions_on_finish: false,
+            market_received: false,
+            shadows: Vec::new(),
+            progress: None,
             _statistic_marker: PhantomData,
         }
     }
@@ -124,8 +151,12 @@ where
     }
 
     /// Run the trading event-loop for this [`Trader`] instance. Loop will run until [`Trader`]
-    /// receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, or the
-    /// [`MarketGenerator`] yields [`Feed::Finished`].
+    /// receives a [`Command::Terminate`] via the mpsc::Receiver command_rx, the
+    /// [`MarketGenerator`] yields [`Feed::Finished`], or the Portfolio goes bankrupt (including
+    /// due to another [`Trader`] sharing the same Portfolio).
+    ///
+    /// If configured to close positions on finish, any open Position is force-exited at the last
+    /// known price before the loop stops.
     pub fn run(mut self) {
         // Run trading loop for this Trader instance
         'trading: loop {
@@ -134,18 +165,39 @@ where
                 match command {
                     Command::Terminate(_) => break 'trading,
                     Command::ExitPosition(market) => {
+                        let signal_force_exit = SignalForceExit {
+                            time: self.clock.time(),
+                            ..SignalForceExit::from(market)
+                        };
                         self.event_q
-                            .push_back(Event::SignalForceExit(SignalForceExit::from(market)));
+                            .push_back(Event::SignalForceExit(signal_force_exit));
                     }
                     _ => continue,
                 }
             }
 
+            // Halt if the Portfolio went bankrupt, possibly due to another Trader sharing it
+            if let Some(bankruptcy) = self.portfolio.lock().bankruptcy() {
+                warn!(
+                    engine_id = %self.engine_id,
+                    market = ?self.market,
+                    time = %bankruptcy.time,
+                    equity = bankruptcy.equity,
+                    action = "halting Trader",
+                    "Portfolio is bankrupt"
+                );
+                break 'trading;
+            }
+
             // If the Feed<MarketEvent> yields, populate event_q with the next MarketEvent
-            match self.data.next() {
+            let finished = match self.data.next() {
                 Feed::Next(market) => {
+                    self.clock.advance(market.time_exchange);
+                    self.market_received = true;
+                    self.update_shadows(|shadow| shadow.update_from_market(&market));
                     self.event_tx.send(Event::Market(market.clone()));
                     self.event_q.push_back(Event::Market(market));
+                    false
                 }
                 Feed::Unhealthy => {
                     warn!(
@@ -156,15 +208,43 @@ where
                     );
                     continue 'trading;
                 }
+                Feed::Finished if self.close_positions_on_finish => {
+                    // Without any MarketEvent the exit is priced at the last mark price
+                    let signal_force_exit = SignalForceExit {
+                        time: self.clock.time(),
+                        synthetic: !self.market_received,
+                        reason: CloseReason::SessionEnd,
+                        ..SignalForceExit::from(self.market.clone())
+                    };
+                    self.update_shadows(|shadow| shadow.force_exit(signal_force_exit.clone()));
+                    self.event_q
+                        .push_back(Event::SignalForceExit(signal_force_exit));
+                    true
+                }
                 Feed::Finished => break 'trading,
-            }
+            };
 
             // Handle Events in the event_q
             // '--> While loop will break when event_q is empty and requires another MarketEvent
             while let Some(event) = self.event_q.pop_front() {
                 match event {
                     Event::Market(market) => {
-                        if let Some(signal) = self.strategy.generate_signal(&market) {
+                        // Resting orders traded through by this MarketEvent are filled
+                        for fill in self
+                            .execution
+                            .update_from_market(&market)
+                            .expect("failed to update Execution from market")
+                        {
+                            self.event_tx.send(Event::Fill(fill.clone()));
+                            self.event_q.push_back(Event::Fill(fill));
+                        }
+
+                        // Signals generated while the Strategy is warming up are disregarded
+                        if let Some(signal) = self
+                            .strategy
+                            .generate_signal(&market)
+                            .filter(|_| self.strategy.is_warm())
+                        {
                             self.event_tx.send(Event::Signal(signal.clone()));
                             self.event_q.push_back(Event::Signal(signal));
                         }
@@ -177,6 +257,67 @@ where
                         {
                             self.event_tx.send(Event::PositionUpdate(position_update));
                         }
+
+                        // OrderEvents expired by the Portfolio are cancelled so they cannot fill
+                        for order in self.portfolio.lock().take_expired_orders() {
+                            self.event_tx.send(Event::OrderCancel(order.clone()));
+                            self.event_q.push_back(Event::OrderCancel(order));
+                        }
+
+                        if let Some(margin_call) = self
+                            .portfolio
+                            .lock()
+                            .margin_call(&market)
+                            .expect("failed to determine Portfolio margin call")
+                        {
+                            self.event_tx.send(Event::MarginCall(margin_call));
+                        }
+
+                        // Positions breaching the maintenance margin are liquidated
+                        let liquidation_events = self
+                            .portfolio
+                            .lock()
+                            .liquidate(&market)
+                            .expect("failed to liquidate Portfolio Position");
+
+                        if self.send_and_check_bankruptcy(liquidation_events) {
+                            break 'trading;
+                        }
+
+                        // Equity revalued by the MarketEvent may be wiped out without any fill
+                        let bankruptcy = self
+                            .portfolio
+                            .lock()
+                            .check_bankruptcy(&market)
+                            .expect("failed to check Portfolio bankruptcy");
+
+                        if self.send_and_check_bankruptcy(
+                            bankruptcy.map(Event::Bankruptcy).into_iter().collect(),
+                        ) {
+                            break 'trading;
+                        }
+
+                        if let Some(order) = self
+                            .portfolio
+                            .lock()
+                            .generate_risk_exit_order(&market)
+                            .expect("failed to generate risk exit order")
+                        {
+                            self.event_tx.send(Event::OrderNew(order.clone()));
+                            self.event_q.push_back(Event::OrderNew(order));
+                        }
+
+                        // Open Positions are rebalanced toward any Portfolio target exposure
+                        let rebalance_orders = self
+                            .portfolio
+                            .lock()
+                            .generate_rebalance_orders(&market)
+                            .expect("failed to generate rebalance orders");
+
+                        for order in rebalance_orders {
+                            self.event_tx.send(Event::OrderNew(order.clone()));
+                            self.event_q.push_back(Event::OrderNew(order));
+                        }
                     }
 
                     Event::Signal(signal) => {
@@ -204,13 +345,19 @@ where
                     }
 
                     Event::OrderNew(order) => {
-                        let fill = self
+                        // Resting orders are filled by a subsequent MarketEvent
+                        if let Some(fill) = self
                             .execution
-                            .generate_fill(&order)
-                            .expect("failed to generate Fill");
+                            .submit_order(&order)
+                            .expect("failed to generate Fill")
+                        {
+                            self.event_tx.send(Event::Fill(fill.clone()));
+                            self.event_q.push_back(Event::Fill(fill));
+                        }
+                    }
 
-                        self.event_tx.send(Event::Fill(fill.clone()));
-                        self.event_q.push_back(Event::Fill(fill));
+                    Event::OrderCancel(order) => {
+                        self.execution.cancel_order(order.cid);
                     }
 
                     Event::Fill(fill) => {
@@ -220,12 +367,31 @@ where
                             .update_from_fill(&fill)
                             .expect("failed to update Portfolio from fill");
 
-                        self.event_tx.send_many(fill_side_effect_events);
+                        if self.send_and_check_bankruptcy(fill_side_effect_events) {
+                            break 'trading;
+                        }
                     }
                     _ => {}
                 }
             }
 
+            if finished {
+                break 'trading;
+            }
+
+            // Report progress once the MarketEvent & the Events it generated are processed
+            if let Some(progress) = &mut self.progress {
+                if let Err(error) = progress.update(self.clock.time(), &mut self.portfolio.lock()) {
+                    warn!(
+                        engine_id = %self.engine_id,
+                        market = ?self.market,
+                        %error,
+                        action = "continuing trading",
+                        "failed to report Trader progress"
+                    );
+                }
+            }
+
             debug!(
                 engine_id = &*self.engine_id.to_string(),
                 market = &*format!("{:?}", self.market),
@@ -234,6 +400,50 @@ where
         }
     }
 
+    /// Actions the provided operation on every [`Shadow`]. Failures are logged rather than
+    /// propagated, so a failing [`Shadow`] never disrupts real trading.
+    fn update_shadows<Operation>(&mut self, mut operation: Operation)
+    where
+        Operation: FnMut(&mut Shadow<Portfolio>) -> Result<(), EngineError>,
+    {
+        for shadow in self.shadows.iter_mut() {
+            if let Err(error) = operation(shadow) {
+                warn!(
+                    engine_id = %self.engine_id,
+                    market = ?self.market,
+                    %error,
+                    action = "continuing real trading",
+                    "failed to update Shadow"
+                );
+            }
+        }
+    }
+
+    /// Sends the Portfolio generated [`Event`]s to the external sink, returning true if they
+    /// contain a [`Bankruptcy`](crate::portfolio::Bankruptcy) that must halt the [`Trader`].
+    fn send_and_check_bankruptcy(&mut self, events: Vec<Event>) -> bool {
+        let bankruptcy = events.iter().find_map(|event| match event {
+            Event::Bankruptcy(bankruptcy) => Some(*bankruptcy),
+            _ => None,
+        });
+
+        self.event_tx.send_many(events);
+
+        if let Some(bankruptcy) = bankruptcy {
+            warn!(
+                engine_id = %self.engine_id,
+                market = ?self.market,
+                time = %bankruptcy.time,
+                equity = bankruptcy.equity,
+                action = "halting Trader",
+                "Portfolio is bankrupt"
+            );
+            return true;
+        }
+
+        false
+    }
+
     /// Returns a [`Command`] if one has been received.
     fn receive_remote_command(&mut self) -> Option<Command> {
         match self.command_rx.try_recv() {
@@ -281,6 +491,10 @@ where
     data: Option<Data>,
     strategy: Option<Strategy>,
     execution: Option<Execution>,
+    clock: Option<Arc<dyn Clock>>,
+    close_positions_on_finish: Option<bool>,
+    shadows: Option<Vec<Shadow<Portfolio>>>,
+    progress: Option<ProgressReporter<Portfolio>>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -304,6 +518,10 @@ where
             data: None,
             strategy: None,
             execution: None,
+            clock: None,
+            close_positions_on_finish: None,
+            shadows: None,
+            progress: None,
             _statistic_marker: None,
         }
     }
@@ -364,6 +582,48 @@ where
         }
     }
 
+    /// Optional [`Clock`] used by the [`Trader`], defaulting to the wall-clock [`SystemClock`].
+    pub fn clock<C>(self, value: C) -> Self
+    where
+        C: Clock + 'static,
+    {
+        Self {
+            clock: Some(Arc::new(value)),
+            ..self
+        }
+    }
+
+    /// Optional flag to force-close any open Position once the [`MarketGenerator`] yields
+    /// [`Feed::Finished`] (eg/ to be flat at the end of a backtest), defaulting to false.
+    pub fn close_positions_on_finish(self, value: bool) -> Self {
+        Self {
+            close_positions_on_finish: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional [`Shadow`]s trading alternative strategies against shadow Portfolios using the
+    /// same [`MarketEvent`]s as the [`Trader`], defaulting to none.
+    pub fn shadows(self, value: Vec<Shadow<Portfolio>>) -> Self {
+        Self {
+            shadows: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional progress callback invoked with the [`Progress`] of the
+    /// trading loop every `every` [`MarketEvent`]s, defaulting to none.
+    pub fn progress<Callback>(self, every: u64, callback: Callback) -> Self
+    where
+        Portfolio: PortfolioReporter,
+        Callback: FnMut(Progress) + Send + 'static,
+    {
+        Self {
+            progress: Some(ProgressReporter::new(every, callback)),
+            ..self
+        }
+    }
+
     pub fn build(
         self,
     ) -> Result<Trader<EventTx, Statistic, Portfolio, Data, Strategy, Execution>, EngineError> {
@@ -391,6 +651,11 @@ where
             execution: self
                 .execution
                 .ok_or(EngineError::BuilderIncomplete("execution"))?,
+            clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+            close_positions_on_finish: self.close_positions_on_finish.unwrap_or_default(),
+            market_received: false,
+            shadows: self.shadows.unwrap_or_default(),
+            progress: self.progress,
             _statistic_marker: PhantomData,
         })
     }
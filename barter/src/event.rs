@@ -1,8 +1,9 @@
 use crate::{
     execution::FillEvent,
     portfolio::{
+        margin::MarginCall,
         position::{Position, PositionExit, PositionUpdate},
-        Balance, OrderEvent,
+        Balance, Bankruptcy, OrderEvent,
     },
     strategy::{Signal, SignalForceExit},
 };
@@ -13,22 +14,33 @@ use std::fmt::Debug;
 use tokio::sync::mpsc;
 use tracing::warn;
 
+/// Concurrent [`EventBus`](bus::EventBus) that routes [`Event`]s between the Data, Strategy,
+/// Portfolio & Execution components running in separate tasks.
+pub mod bus;
+
+/// Durable JSONL [`EventJournal`](journal::EventJournal) for auditing & replaying sessions.
+pub mod journal;
+
 /// Events that occur when bartering. [`MarketEvent`], [`Signal`], [`OrderEvent`], and
 /// [`FillEvent`] are vital to the [`Trader`](crate::engine::trader::Trader) event loop, dictating
 /// the trading sequence. The [`PositionExit`] Event is a representation of work done by the
-/// system, and is useful for analysing performance & reconciliations.
+/// system, and is useful for analysing performance & reconciliations. A [`Bankruptcy`] Event halts
+/// the [`Trader`](crate::engine::trader::Trader) event loop.
 #[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub enum Event {
     Market(MarketEvent<Instrument, DataKind>),
     Signal(Signal),
     SignalForceExit(SignalForceExit),
     OrderNew(OrderEvent),
+    OrderCancel(OrderEvent),
     OrderUpdate,
     Fill(FillEvent),
     PositionNew(Position),
     PositionUpdate(PositionUpdate),
     PositionExit(PositionExit),
     Balance(Balance),
+    MarginCall(MarginCall),
+    Bankruptcy(Bankruptcy),
 }
 
 /// Message transmitter for sending Barter messages to downstream consumers.
@@ -0,0 +1,746 @@
+use super::{Event, MessageTransmitter};
+use crate::{
+    data::{Feed, MarketGenerator},
+    engine::{error::EngineError, Command},
+    execution::ExecutionClient,
+    portfolio::{FillUpdater, MarketUpdater, OrderGenerator},
+    strategy::{SignalForceExit, SignalGenerator},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Debug, sync::Arc};
+use tokio::{sync::mpsc, task::JoinHandle};
+use tracing::{error, info, warn};
+
+/// Default capacity of each bounded [`EventHandler`] inbox.
+pub const DEFAULT_HANDLER_CAPACITY: usize = 64;
+
+/// Handles the [`Event`]s routed to it by an [`EventBus`], returning any [`Event`]s generated as
+/// a result (eg/ an [`OrderEvent`](crate::portfolio::OrderEvent) from a
+/// [`Signal`](crate::strategy::Signal)).
+pub trait EventHandler {
+    fn handle(&mut self, event: Event) -> Vec<Event>;
+}
+
+/// [`EventHandler`] that generates [`Signal`](crate::strategy::Signal)s from
+/// [`MarketEvent`]s using a Strategy that implements [`SignalGenerator`].
+#[derive(Debug)]
+pub struct StrategyHandler<Strategy>(pub Strategy);
+
+impl<Strategy> EventHandler for StrategyHandler<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    fn handle(&mut self, event: Event) -> Vec<Event> {
+        let Event::Market(market) = event else {
+            return vec![];
+        };
+
+        // Signals generated while the Strategy is warming up are disregarded
+        self.0
+            .generate_signal(&market)
+            .filter(|_| self.0.is_warm())
+            .map(Event::Signal)
+            .into_iter()
+            .collect()
+    }
+}
+
+/// [`EventHandler`] that updates a shared-access global Portfolio from [`MarketEvent`]s &
+/// [`FillEvent`](crate::execution::FillEvent)s, and generates
+/// [`OrderEvent`](crate::portfolio::OrderEvent)s from
+/// [`Signal`](crate::strategy::Signal)s.
+#[derive(Debug)]
+pub struct PortfolioHandler<Portfolio>(pub Arc<Mutex<Portfolio>>);
+
+impl<Portfolio> EventHandler for PortfolioHandler<Portfolio>
+where
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+{
+    fn handle(&mut self, event: Event) -> Vec<Event> {
+        let mut portfolio = self.0.lock();
+
+        let generated = match event {
+            Event::Market(market) => portfolio
+                .update_from_market(&market)
+                .map(|update| update.map(Event::PositionUpdate).into_iter().collect())
+                .and_then(|mut events: Vec<Event>| {
+                    events.extend(
+                        portfolio
+                            .take_expired_orders()
+                            .into_iter()
+                            .map(Event::OrderCancel),
+                    );
+                    events.extend(portfolio.margin_call(&market)?.map(Event::MarginCall));
+                    events.extend(portfolio.liquidate(&market)?);
+                    events.extend(portfolio.check_bankruptcy(&market)?.map(Event::Bankruptcy));
+                    let order = portfolio.generate_risk_exit_order(&market)?;
+                    events.extend(order.map(Event::OrderNew));
+                    let rebalance_orders = portfolio.generate_rebalance_orders(&market)?;
+                    events.extend(rebalance_orders.into_iter().map(Event::OrderNew));
+                    Ok(events)
+                }),
+            Event::Signal(signal) => portfolio
+                .generate_order(&signal)
+                .map(|order| order.map(Event::OrderNew).into_iter().collect()),
+            Event::SignalForceExit(signal_force_exit) => portfolio
+                .generate_exit_order(signal_force_exit)
+                .map(|order| order.map(Event::OrderNew).into_iter().collect()),
+            Event::Fill(fill) => portfolio.update_from_fill(&fill),
+            _ => Ok(vec![]),
+        };
+
+        generated.unwrap_or_else(|error| {
+            error!(?error, "Portfolio failed to handle Event");
+            vec![]
+        })
+    }
+}
+
+/// [`EventHandler`] that executes [`OrderEvent`](crate::portfolio::OrderEvent)s, and fills
+/// resting orders traded through by [`MarketEvent`]s, using an Execution handler that implements
+/// [`ExecutionClient`].
+#[derive(Debug)]
+pub struct ExecutionHandler<Execution>(pub Execution);
+
+impl<Execution> EventHandler for ExecutionHandler<Execution>
+where
+    Execution: ExecutionClient,
+{
+    fn handle(&mut self, event: Event) -> Vec<Event> {
+        let fills = match event {
+            Event::Market(market) => self.0.update_from_market(&market),
+            Event::OrderNew(order) => self
+                .0
+                .submit_order(&order)
+                .map(|fill| fill.into_iter().collect()),
+            Event::OrderCancel(order) => {
+                self.0.cancel_order(order.cid);
+                Ok(vec![])
+            }
+            _ => Ok(vec![]),
+        };
+
+        fills
+            .map(|fills| fills.into_iter().map(Event::Fill).collect())
+            .unwrap_or_else(|error| {
+                error!(?error, "Execution failed to handle Event");
+                vec![]
+            })
+    }
+}
+
+/// Configuration for constructing an [`EventBus`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Capacity of each bounded [`EventHandler`] inbox. Once a handler falls this far behind,
+    /// routing waits for it to catch up, which in turn pauses the consumption of new
+    /// [`MarketEvent`]s from the Data handler.
+    pub handler_capacity: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            handler_capacity: DEFAULT_HANDLER_CAPACITY,
+        }
+    }
+}
+
+/// Lego components for constructing an [`EventBus`] via the new() constructor method.
+#[derive(Debug)]
+pub struct EventBusLego<EventTx, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    Strategy: SignalGenerator,
+    Execution: ExecutionClient,
+{
+    /// mpsc::Receiver for receiving [`Command`]s from a remote source.
+    pub command_rx: mpsc::Receiver<Command>,
+    /// [`Event`] transmitter for sending every routed [`Event`] to an external sink.
+    pub event_tx: EventTx,
+    /// Shared-access to a global Portfolio instance.
+    pub portfolio: Arc<Mutex<Portfolio>>,
+    /// Data handler that implements [`MarketGenerator`].
+    pub data: Data,
+    /// Strategy that implements [`SignalGenerator`].
+    pub strategy: Strategy,
+    /// Execution handler that implements [`ExecutionClient`].
+    pub execution: Execution,
+    pub config: Config,
+}
+
+/// Event router that runs the Data, Strategy, Portfolio & Execution components in separate
+/// tasks, communicating over `tokio::sync::mpsc` channels. This is the concurrent counterpart of
+/// the synchronous [`Trader`](crate::engine::trader::Trader) event loop.
+///
+/// Every [`Event`] is sent to the external sink and dispatched to the [`EventHandler`]s that
+/// consume it:
+/// - [`Event::Market`] => Strategy, Portfolio & Execution.
+/// - [`Event::Signal`], [`Event::SignalForceExit`] & [`Event::Fill`] => Portfolio.
+/// - [`Event::OrderNew`] => Execution.
+///
+/// Each handler inbox is bounded to provide backpressure, while handler outputs are routed
+/// with priority over new [`MarketEvent`]s so the [`EventHandler`]s can always make progress.
+///
+/// On shutdown (a [`Command::Terminate`], a finished Data handler, or a Portfolio
+/// [`Bankruptcy`](crate::portfolio::Bankruptcy)), no further [`MarketEvent`]s are consumed, but
+/// every in-flight [`Event`] is drained so no [`FillEvent`](crate::execution::FillEvent) is lost.
+#[derive(Debug)]
+pub struct EventBus<EventTx, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    Strategy: SignalGenerator,
+    Execution: ExecutionClient,
+{
+    command_rx: mpsc::Receiver<Command>,
+    event_tx: EventTx,
+    portfolio: Arc<Mutex<Portfolio>>,
+    data: Data,
+    strategy: Strategy,
+    execution: Execution,
+    config: Config,
+}
+
+impl<EventTx, Portfolio, Data, Strategy, Execution>
+    EventBus<EventTx, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event> + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater + Send + 'static,
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>> + Send + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+    Execution: ExecutionClient + Send + 'static,
+{
+    /// Constructs a new [`EventBus`] instance using the provided [`EventBusLego`].
+    pub fn new(lego: EventBusLego<EventTx, Portfolio, Data, Strategy, Execution>) -> Self {
+        Self {
+            command_rx: lego.command_rx,
+            event_tx: lego.event_tx,
+            portfolio: lego.portfolio,
+            data: lego.data,
+            strategy: lego.strategy,
+            execution: lego.execution,
+            config: lego.config,
+        }
+    }
+
+    /// Returns an [`EventBusBuilder`] instance.
+    pub fn builder() -> EventBusBuilder<EventTx, Portfolio, Data, Strategy, Execution> {
+        EventBusBuilder::new()
+    }
+
+    /// Run the [`EventBus`] until shutdown, and every in-flight [`Event`] has been drained.
+    pub async fn run(mut self) {
+        // Run the Data handler on a blocking thread, since MarketGenerator::next() may block
+        let (market_tx, mut market_rx) = mpsc::channel(self.config.handler_capacity);
+        let mut data = self.data;
+        tokio::task::spawn_blocking(move || loop {
+            match data.next() {
+                Feed::Next(market) => {
+                    if market_tx.blocking_send(market).is_err() {
+                        break;
+                    }
+                }
+                Feed::Unhealthy => continue,
+                Feed::Finished => break,
+            }
+        });
+
+        // Every EventHandler sends the Events it generates for each input to the router
+        let (output_tx, mut output_rx) = mpsc::unbounded_channel();
+        let mut router = Router {
+            event_tx: self.event_tx,
+            strategy: spawn_handler(
+                StrategyHandler(self.strategy),
+                self.config.handler_capacity,
+                output_tx.clone(),
+            ),
+            portfolio: spawn_handler(
+                PortfolioHandler(self.portfolio),
+                self.config.handler_capacity,
+                output_tx.clone(),
+            ),
+            execution: spawn_handler(
+                ExecutionHandler(self.execution),
+                self.config.handler_capacity,
+                output_tx,
+            ),
+            in_flight: 0,
+            bankrupt: false,
+        };
+
+        let mut accepting = true;
+        loop {
+            tokio::select! {
+                // Route generated Events first so in-flight work is never starved
+                biased;
+
+                Some(generated) = output_rx.recv() => {
+                    router.in_flight -= 1;
+                    for event in generated {
+                        router.dispatch(event).await;
+                    }
+                }
+
+                command = self.command_rx.recv(), if accepting => match command {
+                    Some(Command::ExitPosition(market)) => {
+                        router
+                            .dispatch(Event::SignalForceExit(SignalForceExit::from(market)))
+                            .await;
+                    }
+                    Some(Command::Terminate(message)) => {
+                        info!(%message, "EventBus received Command::Terminate");
+                        accepting = false;
+                    }
+                    Some(_) => {}
+                    None => {
+                        warn!(
+                            action = "shutting down EventBus",
+                            "remote Command transmitter has been dropped"
+                        );
+                        accepting = false;
+                    }
+                },
+
+                market = market_rx.recv(), if accepting => match market {
+                    Some(market) => router.dispatch(Event::Market(market)).await,
+                    None => accepting = false,
+                },
+
+                else => break,
+            }
+
+            if router.bankrupt {
+                accepting = false;
+            }
+
+            // Stop once no more MarketEvents are consumed & every in-flight Event is drained
+            if !accepting && router.in_flight == 0 {
+                break;
+            }
+        }
+
+        // Closing the handler inboxes stops the handler tasks
+        router.shutdown().await;
+    }
+}
+
+/// Routes [`Event`]s to the bounded inboxes of the [`EventHandler`] tasks, tracking how many
+/// have yet to be handled.
+struct Router<EventTx> {
+    event_tx: EventTx,
+    strategy: (mpsc::Sender<Event>, JoinHandle<()>),
+    portfolio: (mpsc::Sender<Event>, JoinHandle<()>),
+    execution: (mpsc::Sender<Event>, JoinHandle<()>),
+    in_flight: usize,
+    bankrupt: bool,
+}
+
+impl<EventTx> Router<EventTx>
+where
+    EventTx: MessageTransmitter<Event>,
+{
+    /// Send the [`Event`] to the external sink, and to every [`EventHandler`] that consumes it.
+    async fn dispatch(&mut self, event: Event) {
+        self.event_tx.send(event.clone());
+
+        match event {
+            Event::Market(_) => {
+                self.route(Handler::Strategy, event.clone()).await;
+                self.route(Handler::Portfolio, event.clone()).await;
+                self.route(Handler::Execution, event).await;
+            }
+            Event::Signal(_) | Event::SignalForceExit(_) | Event::Fill(_) => {
+                self.route(Handler::Portfolio, event).await;
+            }
+            Event::OrderNew(_) | Event::OrderCancel(_) => {
+                self.route(Handler::Execution, event).await;
+            }
+            Event::Bankruptcy(bankruptcy) => {
+                warn!(
+                    time = %bankruptcy.time,
+                    equity = bankruptcy.equity,
+                    action = "halting EventBus",
+                    "Portfolio is bankrupt"
+                );
+                self.bankrupt = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Send the [`Event`] to the provided [`Handler`] inbox, waiting for capacity if it has
+    /// fallen behind.
+    async fn route(&mut self, handler: Handler, event: Event) {
+        let (inbox, _) = match handler {
+            Handler::Strategy => &self.strategy,
+            Handler::Portfolio => &self.portfolio,
+            Handler::Execution => &self.execution,
+        };
+
+        if inbox.send(event).await.is_ok() {
+            self.in_flight += 1;
+        } else {
+            error!(?handler, "EventHandler task stopped, dropping Event");
+        }
+    }
+
+    /// Close every [`EventHandler`] inbox and wait for the tasks to stop.
+    async fn shutdown(self) {
+        for (handler, (inbox, task)) in [
+            (Handler::Strategy, self.strategy),
+            (Handler::Portfolio, self.portfolio),
+            (Handler::Execution, self.execution),
+        ] {
+            drop(inbox);
+            if let Err(error) = task.await {
+                error!(?handler, ?error, "EventHandler task panicked");
+            }
+        }
+    }
+}
+
+/// [`EventHandler`] tasks run by an [`EventBus`].
+#[derive(Copy, Clone, Debug)]
+enum Handler {
+    Strategy,
+    Portfolio,
+    Execution,
+}
+
+/// Spawn a task that handles every [`Event`] received via the returned bounded inbox, sending
+/// the generated [`Event`]s of each input to the `output_tx`.
+fn spawn_handler<Handler>(
+    mut handler: Handler,
+    capacity: usize,
+    output_tx: mpsc::UnboundedSender<Vec<Event>>,
+) -> (mpsc::Sender<Event>, JoinHandle<()>)
+where
+    Handler: EventHandler + Send + 'static,
+{
+    let (inbox_tx, mut inbox_rx) = mpsc::channel(capacity);
+
+    let task = tokio::spawn(async move {
+        while let Some(event) = inbox_rx.recv().await {
+            if output_tx.send(handler.handle(event)).is_err() {
+                break;
+            }
+        }
+    });
+
+    (inbox_tx, task)
+}
+
+/// Builder to construct [`EventBus`] instances.
+#[derive(Debug)]
+pub struct EventBusBuilder<EventTx, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event>,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater,
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>>,
+    Strategy: SignalGenerator,
+    Execution: ExecutionClient,
+{
+    command_rx: Option<mpsc::Receiver<Command>>,
+    event_tx: Option<EventTx>,
+    portfolio: Option<Arc<Mutex<Portfolio>>>,
+    data: Option<Data>,
+    strategy: Option<Strategy>,
+    execution: Option<Execution>,
+    config: Option<Config>,
+}
+
+impl<EventTx, Portfolio, Data, Strategy, Execution>
+    EventBusBuilder<EventTx, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event> + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater + Send + 'static,
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>> + Send + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+    Execution: ExecutionClient + Send + 'static,
+{
+    pub fn new() -> Self {
+        Self {
+            command_rx: None,
+            event_tx: None,
+            portfolio: None,
+            data: None,
+            strategy: None,
+            execution: None,
+            config: None,
+        }
+    }
+
+    pub fn command_rx(self, value: mpsc::Receiver<Command>) -> Self {
+        Self {
+            command_rx: Some(value),
+            ..self
+        }
+    }
+
+    pub fn event_tx(self, value: EventTx) -> Self {
+        Self {
+            event_tx: Some(value),
+            ..self
+        }
+    }
+
+    pub fn portfolio(self, value: Arc<Mutex<Portfolio>>) -> Self {
+        Self {
+            portfolio: Some(value),
+            ..self
+        }
+    }
+
+    pub fn data(self, value: Data) -> Self {
+        Self {
+            data: Some(value),
+            ..self
+        }
+    }
+
+    pub fn strategy(self, value: Strategy) -> Self {
+        Self {
+            strategy: Some(value),
+            ..self
+        }
+    }
+
+    pub fn execution(self, value: Execution) -> Self {
+        Self {
+            execution: Some(value),
+            ..self
+        }
+    }
+
+    pub fn config(self, value: Config) -> Self {
+        Self {
+            config: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build(
+        self,
+    ) -> Result<EventBus<EventTx, Portfolio, Data, Strategy, Execution>, EngineError> {
+        Ok(EventBus {
+            command_rx: self
+                .command_rx
+                .ok_or(EngineError::BuilderIncomplete("command_rx"))?,
+            event_tx: self
+                .event_tx
+                .ok_or(EngineError::BuilderIncomplete("event_tx"))?,
+            portfolio: self
+                .portfolio
+                .ok_or(EngineError::BuilderIncomplete("portfolio"))?,
+            data: self.data.ok_or(EngineError::BuilderIncomplete("data"))?,
+            strategy: self
+                .strategy
+                .ok_or(EngineError::BuilderIncomplete("strategy"))?,
+            execution: self
+                .execution
+                .ok_or(EngineError::BuilderIncomplete("execution"))?,
+            config: self.config.unwrap_or_default(),
+        })
+    }
+}
+
+impl<EventTx, Portfolio, Data, Strategy, Execution> Default
+    for EventBusBuilder<EventTx, Portfolio, Data, Strategy, Execution>
+where
+    EventTx: MessageTransmitter<Event> + Send,
+    Portfolio: MarketUpdater + OrderGenerator + FillUpdater + Send + 'static,
+    Data: MarketGenerator<MarketEvent<Instrument, DataKind>> + Send + 'static,
+    Strategy: SignalGenerator + Send + 'static,
+    Execution: ExecutionClient + Send + 'static,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::historical,
+        event::EventTx,
+        execution::{
+            simulated::{
+                Config as ExecutionConfig, LimitFillModel, SimulatedExecution, SlippageModel,
+                SpreadModel,
+            },
+            Fees, FillEvent,
+        },
+        portfolio::{error::PortfolioError, position::PositionUpdate, OrderEvent},
+        strategy::{Decision, Signal, SignalStrength},
+        test_util::{market_event_trade, order_event, signal},
+    };
+    use barter_integration::Side;
+    use std::collections::HashMap;
+
+    /// Strategy that advises going Long on every [`MarketEvent`].
+    struct AlwaysLong;
+
+    impl SignalGenerator for AlwaysLong {
+        fn generate_signal(&mut self, _: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+            Some(Signal {
+                signals: HashMap::from([(Decision::EnterLong, SignalStrength(1.0))]),
+                ..signal()
+            })
+        }
+    }
+
+    /// Portfolio that generates an [`OrderEvent`] for every [`Signal`], and records every
+    /// [`FillEvent`] it is updated from.
+    #[derive(Default)]
+    struct RecordingPortfolio {
+        fills: Vec<FillEvent>,
+    }
+
+    impl MarketUpdater for RecordingPortfolio {
+        fn update_from_market(
+            &mut self,
+            _: &MarketEvent<Instrument, DataKind>,
+        ) -> Result<Option<PositionUpdate>, PortfolioError> {
+            Ok(None)
+        }
+    }
+
+    impl OrderGenerator for RecordingPortfolio {
+        fn generate_order(&mut self, _: &Signal) -> Result<Option<OrderEvent>, PortfolioError> {
+            Ok(Some(order_event()))
+        }
+
+        fn generate_exit_order(
+            &mut self,
+            _: SignalForceExit,
+        ) -> Result<Option<OrderEvent>, PortfolioError> {
+            Ok(None)
+        }
+
+        fn generate_risk_exit_order(
+            &mut self,
+            _: &MarketEvent<Instrument, DataKind>,
+        ) -> Result<Option<OrderEvent>, PortfolioError> {
+            Ok(None)
+        }
+    }
+
+    impl FillUpdater for RecordingPortfolio {
+        fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
+            self.fills.push(fill.clone());
+            Ok(vec![])
+        }
+    }
+
+    #[tokio::test]
+    async fn burst_of_market_events_delivers_every_fill_to_portfolio() {
+        const BURST: usize = 500;
+
+        let (_command_tx, command_rx) = mpsc::channel(1);
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let portfolio = Arc::new(Mutex::new(RecordingPortfolio::default()));
+
+        let bus = EventBus::builder()
+            .command_rx(command_rx)
+            .event_tx(EventTx::new(event_tx))
+            .portfolio(Arc::clone(&portfolio))
+            .data(historical::MarketFeed::new(
+                (0..BURST).map(|_| market_event_trade(Side::Buy)),
+            ))
+            .strategy(AlwaysLong)
+            .execution(SimulatedExecution::new(ExecutionConfig {
+                simulated_fees_pct: Fees::default(),
+                exchange_fees_pct: None,
+                slippage_model: SlippageModel::Flat,
+                spread_model: SpreadModel::Zero,
+                latency: chrono::Duration::zero(),
+                limit_fill_model: LimitFillModel::Immediate,
+            }))
+            // Minimal capacity so the handlers continuously apply backpressure
+            .config(Config {
+                handler_capacity: 1,
+            })
+            .build()
+            .unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), bus.run())
+            .await
+            .expect("EventBus failed to drain after MarketEvents finished");
+
+        // Every fill generated before the Data handler finished reached the Portfolio
+        assert_eq!(portfolio.lock().fills.len(), BURST);
+
+        let mut markets = 0;
+        let mut fills = 0;
+        while let Ok(event) = event_rx.try_recv() {
+            match event {
+                Event::Market(_) => markets += 1,
+                Event::Fill(_) => fills += 1,
+                _ => {}
+            }
+        }
+        assert_eq!(markets, BURST);
+        assert_eq!(fills, BURST);
+    }
+
+    #[tokio::test]
+    async fn terminate_drains_in_flight_events() {
+        let (command_tx, command_rx) = mpsc::channel(1);
+        let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+        let portfolio = Arc::new(Mutex::new(RecordingPortfolio::default()));
+
+        // Live feed that never finishes, so only the Command::Terminate stops the EventBus
+        let (market_tx, market_rx) = mpsc::unbounded_channel();
+        for _ in 0..10 {
+            market_tx.send(market_event_trade(Side::Buy)).unwrap();
+        }
+
+        let bus = EventBus::builder()
+            .command_rx(command_rx)
+            .event_tx(EventTx::new(event_tx))
+            .portfolio(Arc::clone(&portfolio))
+            .data(crate::data::live::MarketFeed::new(market_rx))
+            .strategy(AlwaysLong)
+            .execution(SimulatedExecution::new(ExecutionConfig {
+                simulated_fees_pct: Fees::default(),
+                exchange_fees_pct: None,
+                slippage_model: SlippageModel::Flat,
+                spread_model: SpreadModel::Zero,
+                latency: chrono::Duration::zero(),
+                limit_fill_model: LimitFillModel::Immediate,
+            }))
+            .build()
+            .unwrap();
+
+        let run = tokio::spawn(bus.run());
+        command_tx
+            .send(Command::Terminate("test".to_owned()))
+            .await
+            .unwrap();
+
+        tokio::time::timeout(std::time::Duration::from_secs(5), run)
+            .await
+            .expect("EventBus failed to stop after Command::Terminate")
+            .unwrap();
+
+        // Every MarketEvent consumed before the Command::Terminate resulted in a Portfolio fill
+        let mut markets = 0;
+        while let Ok(event) = event_rx.try_recv() {
+            if let Event::Market(_) = event {
+                markets += 1;
+            }
+        }
+        assert_eq!(portfolio.lock().fills.len(), markets);
+
+        // Finish the live feed so the Data handler thread stops
+        drop(market_tx);
+    }
+}
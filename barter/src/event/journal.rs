@@ -0,0 +1,234 @@
+use super::Event;
+use crate::data::historical::MarketFeed;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    path::Path,
+};
+use tracing::{debug, warn};
+
+/// Timestamped [`Event`] recorded as a single JSON line in an [`EventJournal`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct JournalEntry {
+    /// Time the [`Event`] was appended to the [`EventJournal`].
+    pub time: DateTime<Utc>,
+    pub event: Event,
+}
+
+/// Append-only journal that durably records every [`Event`] as JSON lines (JSONL), useful for
+/// auditing live sessions & deterministically replaying them via a [`JournalReplay`].
+#[derive(Debug)]
+pub struct EventJournal<W = BufWriter<File>>
+where
+    W: Write,
+{
+    writer: W,
+}
+
+impl EventJournal<BufWriter<File>> {
+    /// Opens the journal file at the provided path for appending, creating it if it does not
+    /// exist.
+    pub fn open<P>(path: P) -> Result<Self, std::io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self::from_writer(BufWriter::new(file)))
+    }
+
+    /// Constructs a [`JournalReplay`] that yields the [`JournalEntry`]s recorded in the journal
+    /// file at the provided path.
+    pub fn replay<P>(path: P) -> Result<JournalReplay<BufReader<File>>, std::io::Error>
+    where
+        P: AsRef<Path>,
+    {
+        Ok(JournalReplay::new(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<W> EventJournal<W>
+where
+    W: Write,
+{
+    /// Constructs a new [`EventJournal`] that records [`Event`]s to the provided writer.
+    pub fn from_writer(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Append a timestamped [`Event`] to the journal, flushing it to the underlying writer
+    /// so it survives a crash of the trading session.
+    pub fn append(&mut self, event: &Event) -> Result<(), std::io::Error> {
+        #[derive(Serialize)]
+        struct Entry<'a> {
+            time: DateTime<Utc>,
+            event: &'a Event,
+        }
+
+        serde_json::to_writer(
+            &mut self.writer,
+            &Entry {
+                time: Utc::now(),
+                event,
+            },
+        )?;
+        self.writer.write_all(b"\n")?;
+        self.writer.flush()
+    }
+
+    /// Consumes the [`EventJournal`], returning the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Iterator over the [`JournalEntry`]s of an [`EventJournal`], in recorded order.
+///
+/// A partially written final line (eg/ from a session that crashed mid-append) is skipped.
+/// Malformed lines elsewhere in the journal are skipped with a warning.
+#[derive(Debug)]
+pub struct JournalReplay<R>
+where
+    R: BufRead,
+{
+    reader: R,
+    line: String,
+}
+
+impl<R> Iterator for JournalReplay<R>
+where
+    R: BufRead,
+{
+    type Item = JournalEntry;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line.clear();
+            match self.reader.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => {}
+                Err(error) => {
+                    warn!(%error, "JournalReplay failed to read journal, finishing replay");
+                    return None;
+                }
+            }
+
+            let line = self.line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+
+            match serde_json::from_str::<JournalEntry>(line) {
+                Ok(entry) => return Some(entry),
+                // Only the final line can be missing its newline terminator
+                Err(error) if !self.line.ends_with('\n') => {
+                    debug!(%error, "JournalReplay skipping truncated final journal line");
+                    return None;
+                }
+                Err(error) => {
+                    warn!(%error, "JournalReplay skipping malformed journal line");
+                }
+            }
+        }
+    }
+}
+
+impl<R> JournalReplay<R>
+where
+    R: BufRead,
+{
+    /// Constructs a new [`JournalReplay`] that reads journal lines from the provided reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            line: String::new(),
+        }
+    }
+
+    /// Consumes the [`JournalReplay`], returning a historical [`MarketFeed`] of the recorded
+    /// [`Event::Market`]s, in recorded order, that can be used to re-run the session.
+    pub fn into_market_feed(
+        self,
+    ) -> MarketFeed<impl Iterator<Item = MarketEvent<Instrument, DataKind>>> {
+        MarketFeed::new(self.filter_map(|entry| match entry.event {
+            Event::Market(market) => Some(market),
+            _ => None,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::{Feed, MarketGenerator},
+        test_util::{fill_event, market_event_candle, market_event_trade, order_event, signal},
+    };
+    use barter_integration::Side;
+    use std::io::Cursor;
+
+    fn mixed_events() -> Vec<Event> {
+        vec![
+            Event::Market(market_event_trade(Side::Buy)),
+            Event::Signal(signal()),
+            Event::OrderNew(order_event()),
+            Event::Fill(fill_event()),
+            Event::Market(market_event_candle()),
+        ]
+    }
+
+    #[test]
+    fn test_event_journal_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("barter_journal_{}.jsonl", uuid::Uuid::new_v4()));
+
+        let events = mixed_events();
+        let mut journal = EventJournal::open(&path).unwrap();
+        for event in &events {
+            journal.append(event).unwrap();
+        }
+        drop(journal);
+
+        let replayed = EventJournal::replay(&path)
+            .unwrap()
+            .map(|entry| entry.event)
+            .collect::<Vec<_>>();
+        assert_eq!(replayed, events);
+
+        // Market events are replayed as a historical Feed in recorded order
+        let mut feed = EventJournal::replay(&path).unwrap().into_market_feed();
+        for event in events {
+            if let Event::Market(market) = event {
+                assert_eq!(feed.next(), Feed::Next(market));
+            }
+        }
+        assert_eq!(feed.next(), Feed::Finished);
+
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_journal_replay_skips_truncated_final_line() {
+        let events = mixed_events();
+        let mut journal = EventJournal::from_writer(Vec::new());
+        for event in &events {
+            journal.append(event).unwrap();
+        }
+        let mut bytes = journal.into_inner();
+
+        // Session crashed midway through appending a final Event
+        let complete = bytes.len();
+        EventJournal::from_writer(&mut bytes)
+            .append(&Event::Signal(signal()))
+            .unwrap();
+        bytes.truncate(complete + (bytes.len() - complete) / 2);
+
+        let replayed = JournalReplay::new(Cursor::new(bytes))
+            .map(|entry| entry.event)
+            .collect::<Vec<_>>();
+        assert_eq!(replayed, events);
+    }
+}
@@ -5,4 +5,10 @@ use thiserror::Error;
 pub enum ExecutionError {
     #[error("Failed to build struct due to missing attributes: {0}")]
     BuilderIncomplete(&'static str),
+
+    #[error("No market price has been observed to fill the order at")]
+    NoMarketPrice,
+
+    #[error("No OrderBook depth is available to fill the market order against")]
+    NoBookDepth,
 }
@@ -1,8 +1,14 @@
-use crate::{data::MarketMeta, portfolio::OrderEvent, strategy::Decision};
+use crate::{
+    data::MarketMeta,
+    portfolio::OrderEvent,
+    strategy::{Decision, StrategyId},
+};
+use barter_data::event::{DataKind, MarketEvent};
 use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
 use chrono::{DateTime, Utc};
 use error::ExecutionError;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 /// Barter execution module specific errors.
 pub mod error;
@@ -10,16 +16,50 @@ pub mod error;
 /// Handlers for simulated and live [`OrderEvent`] execution.
 pub mod simulated;
 
+/// Paper trading [`OrderEvent`] execution at the latest live market prices, or against live
+/// [`OrderBook`](barter_data::books::OrderBook) depth.
+pub mod paper;
+
+/// Best-execution routing of [`OrderEvent`]s across venues listing the same instrument.
+pub mod router;
+
 /// Generates a result [`FillEvent`] by executing an [`OrderEvent`].
 pub trait ExecutionClient {
     /// Return a [`FillEvent`] from executing the input [`OrderEvent`].
     fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError>;
+
+    /// Submit the input [`OrderEvent`] for execution, returning a [`FillEvent`] if it is filled
+    /// immediately, or `None` if it rests until a subsequent [`MarketEvent`] fills it. Defaults to
+    /// immediately filling via [`Self::generate_fill`].
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Option<FillEvent>, ExecutionError> {
+        self.generate_fill(order).map(Some)
+    }
+
+    /// Resolve any resting [`OrderEvent`]s against the latest [`MarketEvent`], returning the
+    /// [`FillEvent`]s of those now filled. Defaults to no fills for stateless clients.
+    fn update_from_market(
+        &mut self,
+        _market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<FillEvent>, ExecutionError> {
+        Ok(Vec::new())
+    }
+
+    /// Cancel the resting [`OrderEvent`] with the provided client order identifier, so it is
+    /// never filled, returning it if it was resting. Defaults to nothing to cancel for stateless
+    /// clients.
+    fn cancel_order(&mut self, _cid: Uuid) -> Option<OrderEvent> {
+        None
+    }
 }
 
 /// Fills are journals of work done by an Execution handler. These are sent back to the portfolio,
 /// so it can apply updates.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct FillEvent {
+    /// Client order identifier of the originating [`OrderEvent`]. Fills without one are not
+    /// matched to an [`OrderEvent`], and are applied to the Portfolio immediately.
+    #[serde(default)]
+    pub cid: Option<Uuid>,
     pub time: DateTime<Utc>,
     pub exchange: ExchangeId,
     pub instrument: Instrument,
@@ -33,6 +73,9 @@ pub struct FillEvent {
     pub fill_value_gross: f64,
     /// All fee types incurred when executing an [`OrderEvent`], and their associated [`FeeAmount`].
     pub fees: Fees,
+    /// [`StrategyId`] propagated from the originating [`OrderEvent`].
+    #[serde(default)]
+    pub strategy_id: StrategyId,
 }
 
 impl FillEvent {
@@ -68,6 +111,7 @@ pub type FeeAmount = f64;
 /// Builder to construct [FillEvent] instances.
 #[derive(Debug, Default)]
 pub struct FillEventBuilder {
+    pub cid: Option<Uuid>,
     pub time: Option<DateTime<Utc>>,
     pub exchange: Option<ExchangeId>,
     pub instrument: Option<Instrument>,
@@ -76,6 +120,7 @@ pub struct FillEventBuilder {
     pub quantity: Option<f64>,
     pub fill_value_gross: Option<f64>,
     pub fees: Option<Fees>,
+    pub strategy_id: Option<StrategyId>,
 }
 
 impl FillEventBuilder {
@@ -83,6 +128,13 @@ impl FillEventBuilder {
         Self::default()
     }
 
+    pub fn cid(self, value: Uuid) -> Self {
+        Self {
+            cid: Some(value),
+            ..self
+        }
+    }
+
     pub fn time(self, value: DateTime<Utc>) -> Self {
         Self {
             time: Some(value),
@@ -139,8 +191,16 @@ impl FillEventBuilder {
         }
     }
 
+    pub fn strategy_id(self, value: StrategyId) -> Self {
+        Self {
+            strategy_id: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<FillEvent, ExecutionError> {
         Ok(FillEvent {
+            cid: self.cid,
             time: self.time.ok_or(ExecutionError::BuilderIncomplete("time"))?,
             exchange: self
                 .exchange
@@ -161,6 +221,7 @@ impl FillEventBuilder {
                 .fill_value_gross
                 .ok_or(ExecutionError::BuilderIncomplete("fill_value_gross"))?,
             fees: self.fees.ok_or(ExecutionError::BuilderIncomplete("fees"))?,
+            strategy_id: self.strategy_id.unwrap_or_default(),
         })
     }
 }
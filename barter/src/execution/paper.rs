@@ -0,0 +1,899 @@
+use crate::{
+    data::{determine_market_close, MarketMeta},
+    execution::{
+        error::ExecutionError,
+        simulated::{SlippageModel, TradedRange},
+        ExecutionClient, Fees, FillEvent,
+    },
+    portfolio::{Balance, OrderEvent, OrderType},
+};
+use barter_data::{
+    books::{Level, OrderBook, OrderBookSide},
+    event::{DataKind, MarketEvent},
+};
+use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
+use chrono::{DateTime, Utc};
+use rust_decimal::{
+    prelude::{FromPrimitive, ToPrimitive},
+    Decimal,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Configuration for constructing a [`PaperExecution`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    /// Starting virtual cash balance.
+    pub starting_cash: f64,
+    /// Simulated fee percentage to be used for each [`Fees`] field in decimal form (eg/ 0.01 for 1%)
+    pub simulated_fees_pct: Fees,
+    /// Model used to simulate the slippage of each [`OrderEvent`].
+    #[serde(default)]
+    pub slippage_model: SlippageModel,
+    /// Determines what happens to the remainder of a market [`OrderEvent`] larger than the
+    /// depth of the live [`OrderBook`] it is filled against.
+    #[serde(default)]
+    pub book_depth_remainder: BookDepthRemainder,
+}
+
+/// Determines what happens to the remainder of a market [`OrderEvent`] that is only partially
+/// filled because it is larger than the total depth of the live [`OrderBook`].
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub enum BookDepthRemainder {
+    /// Remainder is rejected, leaving the [`OrderEvent`] partially filled.
+    #[default]
+    Reject,
+    /// Remainder rests until subsequent [`OrderBook`] updates provide the depth to fill it.
+    Pending,
+}
+
+/// Paper trading [`ExecutionClient`] that fills [`OrderEvent`]s at the most recent live market
+/// price observed for an [`Instrument`], without issuing any exchange requests.
+///
+/// Unlike the [`SimulatedExecution`](super::simulated::SimulatedExecution) used for backtests,
+/// which fills at the price of the [`MarketEvent`] that generated the [`OrderEvent`], the
+/// [`PaperExecution`] is driven by real-time market data - the latest price at the time of
+/// submission is used, even if the market has moved since the [`OrderEvent`] was generated.
+///
+/// Once a live L2 [`OrderBook`] has been observed for an [`Instrument`], market [`OrderEvent`]s
+/// are instead filled by walking it's levels, at the volume-weighted average price of the depth
+/// consumed. The [`SlippageModel`] is not applied to these fills, since walking the book already
+/// accounts for price impact. The consumed depth is removed from the local [`OrderBook`] until
+/// the next update, & any quantity exceeding the total depth is handled according to the
+/// configured [`BookDepthRemainder`].
+///
+/// Stop & limit [`OrderEvent`]s follow the same trigger & trade-through rules as the
+/// [`SimulatedExecution`](super::simulated::SimulatedExecution), judged against the latest market
+/// price: stops rest until it reaches their trigger price, and limits rest unless it is
+/// marketable. Resting [`OrderEvent`]s are resolved against subsequent [`MarketEvent`]s, with
+/// resting exit orders for the same instrument being one-cancels-other.
+///
+/// Every [`FillEvent`] is recorded, and used to maintain a virtual [`Balance`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct PaperExecution {
+    fees_pct: Fees,
+    slippage_model: SlippageModel,
+    book_depth_remainder: BookDepthRemainder,
+    /// Latest market price observed for each exchange [`Instrument`].
+    latest: HashMap<(ExchangeId, Instrument), MarketMeta>,
+    /// Latest L2 [`OrderBook`] observed for each exchange [`Instrument`].
+    books: HashMap<(ExchangeId, Instrument), OrderBook>,
+    /// Market [`OrderEvent`] remainders waiting for [`OrderBook`] depth to be filled against.
+    pending: Vec<OrderEvent>,
+    /// Resting stop & limit [`OrderEvent`]s awaiting a [`MarketEvent`] that triggers or trades
+    /// through them.
+    resting: Vec<OrderEvent>,
+    /// Virtual net quantity held of each exchange [`Instrument`].
+    holdings: HashMap<(ExchangeId, Instrument), f64>,
+    cash: f64,
+    balance: Balance,
+    fills: Vec<FillEvent>,
+}
+
+impl ExecutionClient for PaperExecution {
+    fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError> {
+        if let Some(walk) = self.walk_book(order) {
+            return walk
+                .map(|walk| self.generate_book_fill(order, &walk))
+                .ok_or(ExecutionError::NoBookDepth);
+        }
+
+        let market_meta = *self
+            .latest
+            .get(&(order.exchange, order.instrument.clone()))
+            .ok_or(ExecutionError::NoMarketPrice)?;
+
+        // Latest market price fill, adjusted for slippage
+        let price = self.slippage_model.fill_price(&OrderEvent {
+            market_meta,
+            ..order.clone()
+        });
+
+        // Marketable limit orders are never filled at a price worse than their limit price
+        let price = match (
+            order.order_type.limit_price(),
+            order.quantity.is_sign_positive(),
+        ) {
+            (Some(limit), true) => price.min(limit),
+            (Some(limit), false) => price.max(limit),
+            (None, _) => price,
+        };
+        let fill_value_gross = order.quantity.abs() * price;
+
+        Ok(FillEvent {
+            cid: Some(order.cid),
+            time: Utc::now(),
+            exchange: order.exchange,
+            instrument: order.instrument.clone(),
+            market_meta,
+            decision: order.decision,
+            quantity: order.quantity,
+            fill_value_gross,
+            fees: self.calculate_fees(fill_value_gross),
+            strategy_id: order.strategy_id.clone(),
+        })
+    }
+
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Option<FillEvent>, ExecutionError> {
+        let Some(order) = self.rest_unless_executable(order) else {
+            return Ok(None);
+        };
+
+        let fill = match self.walk_book(&order) {
+            Some(walk) => self.fill_against_book(&order, walk),
+            None => Some(self.generate_fill(&order)?),
+        };
+
+        if let Some(fill) = &fill {
+            self.apply_fill(fill);
+        }
+
+        Ok(fill)
+    }
+
+    fn update_from_market(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<FillEvent>, ExecutionError> {
+        let close = match &market.kind {
+            DataKind::OrderBook(event) => {
+                let book = self
+                    .books
+                    .entry((market.exchange, market.instrument.clone()))
+                    .or_default();
+                book.update(event.clone());
+                book.mid_price().and_then(|mid_price| mid_price.to_f64())
+            }
+            _ => determine_market_close(market),
+        };
+
+        if let Some(close) = close {
+            self.latest.insert(
+                (market.exchange, market.instrument.clone()),
+                MarketMeta {
+                    close,
+                    time: market.time_exchange,
+                },
+            );
+            self.revalue(market.time_exchange);
+        }
+
+        let mut fills = match TradedRange::of(&market.kind).or_else(|| close.map(TradedRange::at)) {
+            Some(range) => self.resolve_resting(market, &range),
+            None => Vec::new(),
+        };
+
+        if matches!(market.kind, DataKind::OrderBook(_)) {
+            // Pending remainders of the updated OrderBook are filled against it's new depth
+            let (pending, others): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+                .into_iter()
+                .partition(|order| {
+                    order.exchange == market.exchange && order.instrument == market.instrument
+                });
+            self.pending = others;
+
+            fills.extend(pending.into_iter().filter_map(|order| {
+                let walk = self.walk_book(&order)?;
+                self.fill_against_book(&order, walk)
+            }));
+        }
+
+        fills.iter().for_each(|fill| self.apply_fill(fill));
+        Ok(fills)
+    }
+
+    fn cancel_order(&mut self, cid: Uuid) -> Option<OrderEvent> {
+        [&mut self.pending, &mut self.resting]
+            .into_iter()
+            .find_map(|orders| {
+                let index = orders.iter().position(|order| order.cid == cid)?;
+                Some(orders.remove(index))
+            })
+    }
+}
+
+/// Outcome of walking the levels of a live [`OrderBook`] to fill a market [`OrderEvent`].
+#[derive(Clone, PartialEq, Debug)]
+struct BookWalk {
+    /// Absolute quantity filled, which is less than the [`OrderEvent`] quantity if the
+    /// [`OrderBook`] is too thin.
+    quantity: f64,
+    /// Gross value of the quantity filled.
+    value: f64,
+    /// Consumed [`Level`]s with their remaining amounts, used to deplete the [`OrderBook`].
+    consumed: Vec<Level>,
+}
+
+impl BookWalk {
+    /// Volume-weighted average price of the quantity filled.
+    fn average_price(&self) -> f64 {
+        self.value / self.quantity
+    }
+}
+
+impl PaperExecution {
+    /// Constructs a new [`PaperExecution`] component.
+    pub fn new(cfg: Config) -> Self {
+        Self {
+            fees_pct: cfg.simulated_fees_pct,
+            slippage_model: cfg.slippage_model,
+            book_depth_remainder: cfg.book_depth_remainder,
+            latest: HashMap::new(),
+            books: HashMap::new(),
+            pending: Vec::new(),
+            resting: Vec::new(),
+            holdings: HashMap::new(),
+            cash: cfg.starting_cash,
+            balance: Balance {
+                time: Utc::now(),
+                total: cfg.starting_cash,
+                available: cfg.starting_cash,
+            },
+            fills: Vec::new(),
+        }
+    }
+
+    /// Returns every [`FillEvent`] generated, in the order they were filled.
+    pub fn fills(&self) -> &[FillEvent] {
+        &self.fills
+    }
+
+    /// Returns the virtual [`Balance`], where the `available` cash excludes, and the `total`
+    /// includes, the value of the holdings at the latest market prices.
+    pub fn balance(&self) -> Balance {
+        self.balance
+    }
+
+    /// Returns the market [`OrderEvent`] remainders waiting for [`OrderBook`] depth to be filled
+    /// against.
+    pub fn pending_orders(&self) -> &[OrderEvent] {
+        &self.pending
+    }
+
+    /// Returns the resting stop & limit [`OrderEvent`]s awaiting a [`MarketEvent`] that triggers
+    /// or trades through them.
+    pub fn resting_orders(&self) -> &[OrderEvent] {
+        &self.resting
+    }
+
+    /// Rests the stop or limit [`OrderEvent`] unless the latest market price already triggers
+    /// or trades through it, returning the [`OrderEvent`] to execute immediately otherwise.
+    fn rest_unless_executable(&mut self, order: &OrderEvent) -> Option<OrderEvent> {
+        let mut order = order.clone();
+        let latest = self
+            .latest
+            .get(&(order.exchange, order.instrument.clone()))
+            .map(|market_meta| TradedRange::at(market_meta.close));
+
+        // Stop orders rest until triggered, at which point they become market or limit orders
+        if let Some(trigger) = order.order_type.trigger_price() {
+            if !latest.is_some_and(|market| market.triggers(&order, trigger)) {
+                self.resting.push(order);
+                return None;
+            }
+            order.order_type = order.order_type.triggered();
+        }
+
+        // Limit orders rest unless they are marketable
+        if let Some(price) = order.order_type.limit_price() {
+            if !latest.is_some_and(|market| market.trades_through(&order, price)) {
+                self.resting.push(order);
+                return None;
+            }
+        }
+
+        Some(order)
+    }
+
+    /// Resolves the resting [`OrderEvent`]s of the [`MarketEvent`] instrument against the
+    /// [`TradedRange`] it traded, returning the [`FillEvent`]s generated.
+    fn resolve_resting(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+        range: &TradedRange,
+    ) -> Vec<FillEvent> {
+        // Resolve stops before limits, so gapping through both levels fills the stop
+        let (resting, mut others) = std::mem::take(&mut self.resting)
+            .into_iter()
+            .partition::<Vec<OrderEvent>, _>(|order| {
+                order.exchange == market.exchange && order.instrument == market.instrument
+            });
+        let (stops, limits) = resting
+            .into_iter()
+            .partition::<Vec<OrderEvent>, _>(|order| order.order_type.trigger_price().is_some());
+
+        let mut fills = Vec::new();
+        let mut exit_filled = false;
+        for mut order in stops.into_iter().chain(limits) {
+            // One-cancels-other: only a single resting exit order can close the position
+            if exit_filled && order.decision.is_exit() {
+                info!(
+                    cid = %order.cid,
+                    exchange = %order.exchange,
+                    "cancelled resting exit OrderEvent after another exit OrderEvent was filled"
+                );
+                continue;
+            }
+
+            let price = match order.order_type {
+                OrderType::Stop { trigger } | OrderType::StopLimit { trigger, .. }
+                    if !range.triggers(&order, trigger) =>
+                {
+                    None
+                }
+                OrderType::Stop { trigger } => Some(range.stop_fill_price(&order, trigger)),
+                _ => {
+                    // Triggered stop-limit becomes a resting limit order
+                    order.order_type = order.order_type.triggered();
+                    order
+                        .order_type
+                        .limit_price()
+                        .filter(|price| range.trades_through(&order, *price))
+                }
+            };
+
+            match price {
+                Some(price) => {
+                    exit_filled |= order.decision.is_exit();
+                    fills.push(self.generate_resting_fill(&order, price, market.time_exchange));
+                }
+                None => others.push(order),
+            }
+        }
+
+        // Cancel any remaining resting exit orders for the instrument if an exit filled
+        if exit_filled {
+            others.retain(|order| {
+                order.exchange != market.exchange
+                    || order.instrument != market.instrument
+                    || !order.decision.is_exit()
+            });
+        }
+        self.resting = others;
+
+        fills
+    }
+
+    /// Generates the [`FillEvent`] of a resting [`OrderEvent`] filled at the provided price.
+    fn generate_resting_fill(
+        &self,
+        order: &OrderEvent,
+        price: f64,
+        traded_time: DateTime<Utc>,
+    ) -> FillEvent {
+        let fill_value_gross = order.quantity.abs() * price;
+
+        FillEvent {
+            cid: Some(order.cid),
+            time: Utc::now(),
+            exchange: order.exchange,
+            instrument: order.instrument.clone(),
+            market_meta: MarketMeta {
+                close: price,
+                time: traded_time,
+            },
+            decision: order.decision,
+            quantity: order.quantity,
+            fill_value_gross,
+            fees: self.calculate_fees(fill_value_gross),
+            strategy_id: order.strategy_id.clone(),
+        }
+    }
+
+    /// Walks the levels of the live [`OrderBook`] on the opposite side of a market
+    /// [`OrderEvent`] (ie/ the asks for a buy), consuming depth until it's quantity is filled.
+    ///
+    /// Returns `None` if the [`OrderEvent`] is not a market order, or no [`OrderBook`] has been
+    /// observed for it's [`Instrument`], and `Some(None)` if the [`OrderBook`] side is empty.
+    fn walk_book(&self, order: &OrderEvent) -> Option<Option<BookWalk>> {
+        if order.order_type != OrderType::Market {
+            return None;
+        }
+
+        let book = self
+            .books
+            .get(&(order.exchange, order.instrument.clone()))?;
+        let levels = if order.quantity.is_sign_positive() {
+            book.asks().levels()
+        } else {
+            book.bids().levels()
+        };
+
+        let mut remaining = Decimal::from_f64(order.quantity.abs()).unwrap_or_default();
+        let mut quantity = Decimal::ZERO;
+        let mut value = Decimal::ZERO;
+        let mut consumed = Vec::new();
+
+        for level in levels {
+            if remaining.is_zero() {
+                break;
+            }
+
+            let amount = level.amount.min(remaining);
+            remaining -= amount;
+            quantity += amount;
+            value += amount * level.price;
+            consumed.push(Level::new(level.price, level.amount - amount));
+        }
+
+        Some((!quantity.is_zero()).then(|| BookWalk {
+            quantity: quantity.to_f64().unwrap_or_default(),
+            value: value.to_f64().unwrap_or_default(),
+            consumed,
+        }))
+    }
+
+    /// Fills the market [`OrderEvent`] against the live [`OrderBook`] as determined by the
+    /// [`BookWalk`], depleting the consumed depth & handling any unfilled remainder according to
+    /// the configured [`BookDepthRemainder`].
+    fn fill_against_book(
+        &mut self,
+        order: &OrderEvent,
+        walk: Option<BookWalk>,
+    ) -> Option<FillEvent> {
+        let filled = walk.as_ref().map_or(0.0, |walk| walk.quantity);
+        let remainder = order.quantity.abs() - filled;
+
+        if remainder > f64::EPSILON * order.quantity.abs() {
+            match self.book_depth_remainder {
+                BookDepthRemainder::Reject => warn!(
+                    cid = %order.cid,
+                    remainder,
+                    "rejecting market order remainder exceeding the OrderBook depth"
+                ),
+                BookDepthRemainder::Pending => self.pending.push(OrderEvent {
+                    quantity: remainder.copysign(order.quantity),
+                    ..order.clone()
+                }),
+            }
+        }
+
+        let walk = walk?;
+        if let Some(book) = self
+            .books
+            .get_mut(&(order.exchange, order.instrument.clone()))
+        {
+            if order.quantity.is_sign_positive() {
+                book.upsert_asks(OrderBookSide::asks(walk.consumed.iter().copied()));
+            } else {
+                book.upsert_bids(OrderBookSide::bids(walk.consumed.iter().copied()));
+            }
+        }
+
+        Some(self.generate_book_fill(order, &walk))
+    }
+
+    /// Generates the [`FillEvent`] of the quantity filled by a [`BookWalk`].
+    fn generate_book_fill(&self, order: &OrderEvent, walk: &BookWalk) -> FillEvent {
+        let time = Utc::now();
+
+        FillEvent {
+            cid: Some(order.cid),
+            time,
+            exchange: order.exchange,
+            instrument: order.instrument.clone(),
+            market_meta: MarketMeta {
+                close: walk.average_price(),
+                time,
+            },
+            decision: order.decision,
+            quantity: walk.quantity.copysign(order.quantity),
+            fill_value_gross: walk.value,
+            fees: Fees {
+                slippage: 0.0,
+                ..self.calculate_fees(walk.value)
+            },
+            strategy_id: order.strategy_id.clone(),
+        }
+    }
+
+    /// Applies the [`FillEvent`] to the virtual cash, holdings & [`Balance`], and records it.
+    fn apply_fill(&mut self, fill: &FillEvent) {
+        // Buying spends cash, selling receives it, & both pay fees
+        self.cash -=
+            fill.fill_value_gross.copysign(fill.quantity) + fill.fees.calculate_total_fees();
+        *self
+            .holdings
+            .entry((fill.exchange, fill.instrument.clone()))
+            .or_default() += fill.quantity;
+        self.revalue(fill.time);
+
+        self.fills.push(fill.clone());
+    }
+
+    /// Revalue the virtual [`Balance`] using the latest market prices.
+    fn revalue(&mut self, time: DateTime<Utc>) {
+        let holdings_value = self
+            .holdings
+            .iter()
+            .filter_map(|(key, quantity)| {
+                self.latest
+                    .get(key)
+                    .map(|market_meta| quantity * market_meta.close)
+            })
+            .sum::<f64>();
+
+        self.balance = Balance {
+            time,
+            total: self.cash + holdings_value,
+            available: self.cash,
+        };
+    }
+
+    /// Calculates the simulated [`Fees`] a [`FillEvent`] will incur, based on its fill value.
+    fn calculate_fees(&self, fill_value_gross: f64) -> Fees {
+        let slippage_fee_pct = match self.slippage_model {
+            SlippageModel::Flat => self.fees_pct.slippage,
+            SlippageModel::Linear { .. } | SlippageModel::SquareRoot { .. } => 0.0,
+        };
+
+        Fees {
+            exchange: self.fees_pct.exchange * fill_value_gross,
+            slippage: slippage_fee_pct * fill_value_gross,
+            network: self.fees_pct.network * fill_value_gross,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        strategy::Decision,
+        test_util::{market_event_trade, order_event},
+    };
+    use barter_data::subscription::book::OrderBookEvent;
+    use barter_instrument::instrument::kind::InstrumentKind;
+    use barter_integration::Side;
+    use rust_decimal_macros::dec;
+
+    fn eth_trade(price: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_trade(Side::Buy);
+        market.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+        if let DataKind::Trade(trade) = &mut market.kind {
+            trade.price = price;
+        }
+        market
+    }
+
+    fn eth_book(bids: Vec<Level>, asks: Vec<Level>) -> MarketEvent<Instrument, DataKind> {
+        let mut market = eth_trade(0.0);
+        market.kind = DataKind::OrderBook(OrderBookEvent::Snapshot(OrderBook::new(
+            0, None, bids, asks,
+        )));
+        market
+    }
+
+    fn order(decision: Decision, quantity: f64, close: f64) -> OrderEvent {
+        let mut order = order_event();
+        order.decision = decision;
+        order.quantity = quantity;
+        order.market_meta.close = close;
+        order
+    }
+
+    fn paper_execution(slippage_model: SlippageModel) -> PaperExecution {
+        PaperExecution::new(Config {
+            starting_cash: 1000.0,
+            simulated_fees_pct: Fees::default(),
+            slippage_model,
+            book_depth_remainder: BookDepthRemainder::default(),
+        })
+    }
+
+    #[test]
+    fn order_without_observed_market_price_fails() {
+        let mut execution = paper_execution(SlippageModel::Flat);
+
+        assert!(matches!(
+            execution.submit_order(&order(Decision::EnterLong, 1.0, 100.0)),
+            Err(ExecutionError::NoMarketPrice)
+        ));
+        assert!(execution.fills().is_empty());
+    }
+
+    #[test]
+    fn fills_use_latest_observed_market_price() {
+        let mut execution = paper_execution(SlippageModel::Flat);
+
+        // Market moves after the OrderEvent was generated at a close of 100.0
+        for price in [100.0, 101.0, 105.0] {
+            assert!(execution
+                .update_from_market(&eth_trade(price))
+                .unwrap()
+                .is_empty());
+        }
+        let entry = execution
+            .submit_order(&order(Decision::EnterLong, 2.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.market_meta.close, 105.0);
+        assert_eq!(entry.fill_value_gross, 210.0);
+
+        // Other instruments do not affect the fill price
+        execution
+            .update_from_market(&market_event_trade(Side::Buy))
+            .unwrap();
+        execution.update_from_market(&eth_trade(110.0)).unwrap();
+        let exit = execution
+            .submit_order(&order(Decision::CloseLong, -2.0, 105.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(exit.market_meta.close, 110.0);
+        assert_eq!(exit.fill_value_gross, 220.0);
+
+        assert_eq!(execution.fills(), &[entry, exit]);
+    }
+
+    #[test]
+    fn fills_apply_configured_slippage_to_latest_price() {
+        let mut execution = paper_execution(SlippageModel::Linear {
+            impact: 0.01,
+            reference_volume: 1.0,
+        });
+        execution.update_from_market(&eth_trade(200.0)).unwrap();
+
+        // Buying 1.0 at 1% impact fills at 202.0, selling 1.0 fills at 198.0
+        let buy = execution
+            .submit_order(&order(Decision::EnterLong, 1.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert!((buy.fill_value_gross - 202.0).abs() < 1e-9);
+
+        let sell = execution
+            .submit_order(&order(Decision::CloseLong, -1.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert!((sell.fill_value_gross - 198.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn virtual_balance_tracks_fills_and_latest_prices() {
+        let mut execution = PaperExecution::new(Config {
+            starting_cash: 1000.0,
+            simulated_fees_pct: Fees {
+                exchange: 0.01,
+                slippage: 0.0,
+                network: 0.0,
+            },
+            slippage_model: SlippageModel::Flat,
+            book_depth_remainder: BookDepthRemainder::default(),
+        });
+
+        // Buy 2.0 at 100.0, paying a fee of 2.0
+        execution.update_from_market(&eth_trade(100.0)).unwrap();
+        execution
+            .submit_order(&order(Decision::EnterLong, 2.0, 100.0))
+            .unwrap();
+        assert!((execution.balance().available - 798.0).abs() < 1e-9);
+        assert!((execution.balance().total - 998.0).abs() < 1e-9);
+
+        // Holdings are marked to the latest price
+        execution.update_from_market(&eth_trade(150.0)).unwrap();
+        assert!((execution.balance().total - 1098.0).abs() < 1e-9);
+
+        // Sell 2.0 at 150.0, paying a fee of 3.0
+        execution
+            .submit_order(&order(Decision::CloseLong, -2.0, 100.0))
+            .unwrap();
+        assert!((execution.balance().available - 1095.0).abs() < 1e-9);
+        assert!((execution.balance().total - 1095.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn market_buy_walks_ask_levels_at_volume_weighted_average_price() {
+        let mut execution = paper_execution(SlippageModel::Flat);
+        execution
+            .update_from_market(&eth_book(
+                vec![Level::new(dec!(99), dec!(5))],
+                vec![
+                    Level::new(dec!(100), dec!(1)),
+                    Level::new(dec!(101), dec!(2)),
+                    Level::new(dec!(102), dec!(3)),
+                ],
+            ))
+            .unwrap();
+
+        // Buying 4.0 consumes 1.0 @ 100, 2.0 @ 101 & 1.0 @ 102
+        let buy = execution
+            .submit_order(&order(Decision::EnterLong, 4.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(buy.quantity, 4.0);
+        assert_eq!(buy.fill_value_gross, 404.0);
+        assert_eq!(buy.market_meta.close, 101.0);
+
+        // Consumed depth is removed until the next OrderBook update, leaving 2.0 @ 102
+        let buy = execution
+            .submit_order(&order(Decision::EnterLong, 1.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(buy.market_meta.close, 102.0);
+
+        // Selling walks the bids
+        let sell = execution
+            .submit_order(&order(Decision::CloseLong, -2.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(sell.quantity, -2.0);
+        assert_eq!(sell.market_meta.close, 99.0);
+    }
+
+    #[test]
+    fn market_order_larger_than_book_depth_partially_fills() {
+        let asks = || {
+            vec![
+                Level::new(dec!(100), dec!(1)),
+                Level::new(dec!(101), dec!(2)),
+            ]
+        };
+
+        // Rejected remainder leaves the order partially filled
+        let mut execution = paper_execution(SlippageModel::Flat);
+        execution
+            .update_from_market(&eth_book(vec![], asks()))
+            .unwrap();
+        let buy = execution
+            .submit_order(&order(Decision::EnterLong, 5.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(buy.quantity, 3.0);
+        assert_eq!(buy.fill_value_gross, 302.0);
+        assert!(execution.pending_orders().is_empty());
+        assert!(matches!(
+            execution.generate_fill(&order(Decision::EnterLong, 1.0, 100.0)),
+            Err(ExecutionError::NoBookDepth)
+        ));
+
+        // Pending remainder is filled once the OrderBook is replenished
+        let mut execution = PaperExecution::new(Config {
+            starting_cash: 1000.0,
+            simulated_fees_pct: Fees::default(),
+            slippage_model: SlippageModel::Flat,
+            book_depth_remainder: BookDepthRemainder::Pending,
+        });
+        execution
+            .update_from_market(&eth_book(vec![], asks()))
+            .unwrap();
+        let buy = execution
+            .submit_order(&order(Decision::EnterLong, 5.0, 100.0))
+            .unwrap()
+            .unwrap();
+        assert_eq!(buy.quantity, 3.0);
+        assert_eq!(execution.pending_orders().len(), 1);
+        assert_eq!(execution.pending_orders()[0].quantity, 2.0);
+
+        let fills = execution
+            .update_from_market(&eth_book(vec![], vec![Level::new(dec!(103), dec!(5))]))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].cid, buy.cid);
+        assert_eq!(fills[0].quantity, 2.0);
+        assert_eq!(fills[0].fill_value_gross, 206.0);
+        assert!(execution.pending_orders().is_empty());
+        assert_eq!(execution.fills().len(), 2);
+    }
+
+    fn resting_order(order_type: OrderType, decision: Decision, quantity: f64) -> OrderEvent {
+        let mut order = order(decision, quantity, 100.0);
+        order.order_type = order_type;
+        order
+    }
+
+    #[test]
+    fn limit_order_rests_until_market_trades_through_limit_price() {
+        let mut execution = paper_execution(SlippageModel::Flat);
+        execution.update_from_market(&eth_trade(100.0)).unwrap();
+
+        // Buy limit below the latest price rests rather than filling immediately
+        let buy = resting_order(OrderType::Limit { price: 95.0 }, Decision::EnterLong, 1.0);
+        assert!(execution.submit_order(&buy).unwrap().is_none());
+        assert_eq!(execution.resting_orders().len(), 1);
+
+        assert!(execution
+            .update_from_market(&eth_trade(97.0))
+            .unwrap()
+            .is_empty());
+
+        // Market trading through the limit fills it at the limit price
+        let fills = execution.update_from_market(&eth_trade(94.0)).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].cid, Some(buy.cid));
+        assert_eq!(fills[0].fill_value_gross, 95.0);
+        assert!(execution.resting_orders().is_empty());
+        assert_eq!(execution.fills(), fills.as_slice());
+    }
+
+    #[test]
+    fn marketable_limit_order_fills_immediately_no_worse_than_limit_price() {
+        let mut execution = paper_execution(SlippageModel::Linear {
+            impact: 0.1,
+            reference_volume: 1.0,
+        });
+        execution.update_from_market(&eth_trade(100.0)).unwrap();
+
+        // Slippage would fill at 110.0, but the limit caps the fill price at 105.0
+        let buy = resting_order(OrderType::Limit { price: 105.0 }, Decision::EnterLong, 1.0);
+        let fill = execution.submit_order(&buy).unwrap().unwrap();
+        assert!((fill.fill_value_gross - 105.0).abs() < 1e-9);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn stop_order_rests_until_triggered_then_fills_at_worse_of_trigger_and_open() {
+        let mut execution = paper_execution(SlippageModel::Flat);
+
+        // Stop rests even before a market price has been observed
+        let stop = resting_order(OrderType::Stop { trigger: 95.0 }, Decision::CloseLong, -1.0);
+        assert!(execution.submit_order(&stop).unwrap().is_none());
+        assert!(execution
+            .update_from_market(&eth_trade(100.0))
+            .unwrap()
+            .is_empty());
+        assert_eq!(execution.resting_orders().len(), 1);
+
+        // Market gapping through the trigger fills the stop at the worse traded price
+        let fills = execution.update_from_market(&eth_trade(90.0)).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].quantity, -1.0);
+        assert_eq!(fills[0].fill_value_gross, 90.0);
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn resting_exit_orders_are_one_cancels_other() {
+        let mut execution = paper_execution(SlippageModel::Flat);
+        execution.update_from_market(&eth_trade(100.0)).unwrap();
+
+        let stop_loss = resting_order(OrderType::Stop { trigger: 95.0 }, Decision::CloseLong, -1.0);
+        let take_profit =
+            resting_order(OrderType::Limit { price: 110.0 }, Decision::CloseLong, -1.0);
+        assert!(execution.submit_order(&stop_loss).unwrap().is_none());
+        assert!(execution.submit_order(&take_profit).unwrap().is_none());
+
+        let fills = execution.update_from_market(&eth_trade(94.0)).unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].cid, Some(stop_loss.cid));
+        assert!(execution.resting_orders().is_empty());
+    }
+
+    #[test]
+    fn cancelled_resting_order_is_never_filled() {
+        let mut execution = paper_execution(SlippageModel::Flat);
+        execution.update_from_market(&eth_trade(100.0)).unwrap();
+
+        let buy = resting_order(OrderType::Limit { price: 95.0 }, Decision::EnterLong, 1.0);
+        assert!(execution.submit_order(&buy).unwrap().is_none());
+        assert_eq!(execution.cancel_order(buy.cid), Some(buy.clone()));
+        assert_eq!(execution.cancel_order(buy.cid), None);
+
+        // Market trading through the cancelled limit does not fill it
+        assert!(execution
+            .update_from_market(&eth_trade(94.0))
+            .unwrap()
+            .is_empty());
+        assert!(execution.fills().is_empty());
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{data::MarketMeta, execution::FillEvent, portfolio::OrderEvent};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::book::OrderBookL1,
+};
+use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
+use rust_decimal::prelude::ToPrimitive;
+use serde::{Deserialize, Serialize};
+use std::{cmp::Ordering, collections::HashMap};
+use uuid::Uuid;
+
+/// Configuration for constructing a [`SmartOrderRouter`] via the new() constructor method.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct Config {
+    /// Taker fee percentage charged by each venue, eg/ 0.001 for 0.1%. Venues without a
+    /// configured fee are assumed to charge none.
+    pub taker_fees_pct: HashMap<ExchangeId, f64>,
+}
+
+/// Best-execution router for [`Instrument`]s listed on several venues. Uses the latest best bid
+/// & offer from each venue to route a market [`OrderEvent`] to the venue with the best price net
+/// of fees, splitting it across venues if the best venue lacks the size.
+///
+/// Routed child [`OrderEvent`]s are tracked until filled, so their [`FillEvent`]s can be mapped
+/// back to the parent [`OrderEvent`] the Portfolio generated via
+/// [`SmartOrderRouter::parent_fill`].
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct SmartOrderRouter {
+    pub config: Config,
+    pub books: HashMap<Instrument, HashMap<ExchangeId, OrderBookL1>>,
+    children: HashMap<Uuid, RoutedChild>,
+}
+
+/// Parent of a routed child [`OrderEvent`] awaiting it's [`FillEvent`]s.
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct RoutedChild {
+    parent_cid: Uuid,
+    parent_exchange: ExchangeId,
+    remaining_quantity: f64,
+}
+
+/// Venue liquidity available to a routed [`OrderEvent`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct VenueQuote {
+    exchange: ExchangeId,
+    price: f64,
+    amount: f64,
+    effective_price: f64,
+}
+
+impl SmartOrderRouter {
+    /// Constructs a new [`SmartOrderRouter`] using the provided [`Config`].
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            books: HashMap::new(),
+            children: HashMap::new(),
+        }
+    }
+
+    /// Updates the best bid & offer of a venue using the input [`MarketEvent`], if it contains
+    /// an [`OrderBookL1`].
+    pub fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        if let DataKind::OrderBookL1(book) = &market.kind {
+            self.books
+                .entry(market.instrument.clone())
+                .or_default()
+                .insert(market.exchange, *book);
+        }
+    }
+
+    /// Routes the input market [`OrderEvent`] across the venues listing it's [`Instrument`],
+    /// returning the child [`OrderEvent`]s to execute. Venues are consumed in order of best
+    /// price net of fees, and any size exceeding the available top of book liquidity is routed
+    /// to the best venue. If no venue quotes are available the input [`OrderEvent`] is returned
+    /// unchanged.
+    ///
+    /// Child [`OrderEvent`]s are assigned new cids, so their [`FillEvent`]s must be passed through
+    /// [`SmartOrderRouter::parent_fill`] before being applied to the Portfolio.
+    pub fn route(&mut self, order: &OrderEvent) -> Vec<OrderEvent> {
+        let is_buy = order.quantity.is_sign_positive();
+        let quotes = self.quotes(&order.instrument, is_buy);
+
+        let Some(best) = quotes.first() else {
+            return vec![order.clone()];
+        };
+
+        let mut remaining = order.quantity.abs();
+        let mut allocations: Vec<(VenueQuote, f64)> = Vec::with_capacity(quotes.len());
+        for quote in &quotes {
+            if remaining <= 0.0 {
+                break;
+            }
+            let quantity = remaining.min(quote.amount);
+            if quantity > 0.0 {
+                allocations.push((*quote, quantity));
+                remaining -= quantity;
+            }
+        }
+
+        // Route any size exceeding the available liquidity to the best venue
+        if remaining > 0.0 {
+            match allocations
+                .iter_mut()
+                .find(|(quote, _)| quote.exchange == best.exchange)
+            {
+                Some((_, quantity)) => *quantity += remaining,
+                None => allocations.insert(0, (*best, remaining)),
+            }
+        }
+
+        allocations
+            .into_iter()
+            .map(|(quote, quantity)| {
+                let child = OrderEvent {
+                    cid: Uuid::new_v4(),
+                    exchange: quote.exchange,
+                    market_meta: MarketMeta {
+                        close: quote.price,
+                        time: order.market_meta.time,
+                    },
+                    quantity: quantity.copysign(order.quantity),
+                    ..order.clone()
+                };
+
+                self.children.insert(
+                    child.cid,
+                    RoutedChild {
+                        parent_cid: order.cid,
+                        parent_exchange: order.exchange,
+                        remaining_quantity: quantity,
+                    },
+                );
+
+                child
+            })
+            .collect()
+    }
+
+    /// Maps the [`FillEvent`] of a routed child [`OrderEvent`] back to it's parent's cid &
+    /// exchange, so the Portfolio reconciles it against the parent [`OrderEvent`] it generated
+    /// & tracks the resulting Position in the parent's market. Children are forgotten once fully
+    /// filled. [`FillEvent`]s of orders that were not routed are returned unchanged.
+    pub fn parent_fill(&mut self, mut fill: FillEvent) -> FillEvent {
+        let Some(child_cid) = fill.cid else {
+            return fill;
+        };
+        let Some(child) = self.children.get_mut(&child_cid) else {
+            return fill;
+        };
+
+        fill.cid = Some(child.parent_cid);
+        fill.exchange = child.parent_exchange;
+
+        child.remaining_quantity -= fill.quantity.abs();
+        if child.remaining_quantity <= f64::EPSILON {
+            self.children.remove(&child_cid);
+        }
+
+        fill
+    }
+
+    /// Returns the available venue quotes for an [`Instrument`], sorted by best price net of
+    /// fees. Buy orders consume the best asks, and sell orders consume the best bids.
+    fn quotes(&self, instrument: &Instrument, is_buy: bool) -> Vec<VenueQuote> {
+        let Some(books) = self.books.get(instrument) else {
+            return vec![];
+        };
+
+        let mut quotes = books
+            .iter()
+            .filter_map(|(exchange, book)| {
+                let level = if is_buy { book.best_ask } else { book.best_bid };
+                let price = level.price.to_f64()?;
+                let amount = level.amount.to_f64()?;
+                let fee = self
+                    .config
+                    .taker_fees_pct
+                    .get(exchange)
+                    .copied()
+                    .unwrap_or_default();
+
+                let effective_price = if is_buy {
+                    price * (1.0 + fee)
+                } else {
+                    price * (1.0 - fee)
+                };
+
+                (price > 0.0).then_some(VenueQuote {
+                    exchange: *exchange,
+                    price,
+                    amount,
+                    effective_price,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        quotes.sort_by(|a, b| {
+            let ordering = a
+                .effective_price
+                .partial_cmp(&b.effective_price)
+                .unwrap_or(Ordering::Equal);
+            if is_buy {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+
+        quotes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::order_event;
+    use barter_data::books::Level;
+    use chrono::Utc;
+    use rust_decimal_macros::dec;
+
+    fn book_event(
+        exchange: ExchangeId,
+        instrument: &Instrument,
+        bid: Level,
+        ask: Level,
+    ) -> MarketEvent<Instrument, DataKind> {
+        MarketEvent {
+            time_exchange: Utc::now(),
+            time_received: Utc::now(),
+            exchange,
+            instrument: instrument.clone(),
+            kind: DataKind::OrderBookL1(OrderBookL1 {
+                last_update_time: Utc::now(),
+                best_bid: bid,
+                best_ask: ask,
+            }),
+        }
+    }
+
+    #[test]
+    fn route_to_cheapest_effective_venue() {
+        let order = order_event();
+
+        // Coinbase quotes the best raw price, but it's fee makes Kraken the cheaper venue
+        let mut router = SmartOrderRouter::new(Config {
+            taker_fees_pct: HashMap::from([
+                (ExchangeId::Coinbase, 0.001),
+                (ExchangeId::Kraken, 0.0),
+            ]),
+        });
+        router.update_from_market(&book_event(
+            ExchangeId::Coinbase,
+            &order.instrument,
+            Level::new(dec!(99.9), dec!(2.0)),
+            Level::new(dec!(100.0), dec!(2.0)),
+        ));
+        router.update_from_market(&book_event(
+            ExchangeId::Kraken,
+            &order.instrument,
+            Level::new(dec!(99.95), dec!(5.0)),
+            Level::new(dec!(100.05), dec!(5.0)),
+        ));
+
+        struct TestCase {
+            quantity: f64,
+            expected: Vec<(ExchangeId, f64, f64)>,
+        }
+
+        let cases = vec![
+            // TC0: Buy fits within the cheaper effective venue's liquidity
+            TestCase {
+                quantity: 3.0,
+                expected: vec![(ExchangeId::Kraken, 3.0, 100.05)],
+            },
+            // TC1: Buy splits from the cheaper effective venue toward the next venue
+            TestCase {
+                quantity: 6.0,
+                expected: vec![
+                    (ExchangeId::Kraken, 5.0, 100.05),
+                    (ExchangeId::Coinbase, 1.0, 100.0),
+                ],
+            },
+            // TC2: Buy exceeding all liquidity routes the remainder to the cheaper venue
+            TestCase {
+                quantity: 8.0,
+                expected: vec![
+                    (ExchangeId::Kraken, 6.0, 100.05),
+                    (ExchangeId::Coinbase, 2.0, 100.0),
+                ],
+            },
+            // TC3: Sell routes to the venue with the best bid net of fees
+            TestCase {
+                quantity: -4.0,
+                expected: vec![(ExchangeId::Kraken, -4.0, 99.95)],
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let mut input = order.clone();
+            input.quantity = test.quantity;
+
+            let actual = router
+                .route(&input)
+                .into_iter()
+                .map(|child| (child.exchange, child.quantity, child.market_meta.close))
+                .collect::<Vec<_>>();
+
+            assert_eq!(actual, test.expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn route_without_quotes_returns_order_unchanged() {
+        let mut router = SmartOrderRouter::default();
+        let order = order_event();
+        assert_eq!(router.route(&order), vec![order]);
+    }
+}
@@ -1,9 +1,19 @@
-use chrono::Utc;
-use serde::{Deserialize, Serialize};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::{candle::Candle, trade::PublicTrade},
+};
+use barter_instrument::instrument::Instrument;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::VecDeque;
+use tracing::info;
+use uuid::Uuid;
 
 use crate::{
+    data::MarketMeta,
     execution::{error::ExecutionError, ExecutionClient, Fees, FillEvent},
-    portfolio::OrderEvent,
+    portfolio::{OrderEvent, OrderType},
+    statistic::{de_duration_from_secs, se_duration_as_secs},
 };
 
 /// Configuration for constructing a [`SimulatedExecution`] via the new() constructor method.
@@ -11,31 +21,500 @@ use crate::{
 pub struct Config {
     /// Simulated fee percentage to be used for each [`Fees`] field in decimal form (eg/ 0.01 for 1%)
     pub simulated_fees_pct: Fees,
+    /// Optional maker & taker exchange fee percentages in decimal form. If provided, these
+    /// replace the `simulated_fees_pct` exchange fee based on the [`Liquidity`] of each
+    /// [`OrderEvent`].
+    #[serde(default)]
+    pub exchange_fees_pct: Option<MakerTakerFees>,
+    /// Model used to simulate the slippage of each [`OrderEvent`].
+    #[serde(default)]
+    pub slippage_model: SlippageModel,
+    /// Model used to simulate the bid-ask spread crossed by each taker [`OrderEvent`].
+    #[serde(default)]
+    pub spread_model: SpreadModel,
+    /// Simulated latency between an [`OrderEvent`] being submitted and it being filled,
+    /// (de)serialised as milliseconds.
+    #[serde(
+        default,
+        deserialize_with = "de_duration_from_millis",
+        serialize_with = "se_duration_as_millis"
+    )]
+    pub latency: Duration,
+    /// Model used to simulate when limit [`OrderEvent`]s are filled.
+    #[serde(default)]
+    pub limit_fill_model: LimitFillModel,
+}
+
+/// Serialize a [`Duration`] into an `i64` representing the associated milliseconds.
+fn se_duration_as_millis<S>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_i64(duration.num_milliseconds())
+}
+
+/// Deserialize a number representing milliseconds into a [`Duration`].
+fn de_duration_from_millis<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let millis: i64 = Deserialize::deserialize(deserializer)?;
+    Ok(Duration::milliseconds(millis))
+}
+
+/// Model used by the [`SimulatedExecution`] to simulate when limit [`OrderEvent`]s are filled.
+///
+/// With [`LimitFillModel::Immediate`] every order is filled as soon as it is submitted. With
+/// [`LimitFillModel::TradeThrough`] a resting limit order (see [`Liquidity::of`]) is queued, and
+/// only filled at its limit price once a subsequent [`MarketEvent`] trades through it.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum LimitFillModel {
+    #[default]
+    Immediate,
+    TradeThrough,
+}
+
+/// Model used by the [`SimulatedExecution`] to simulate slippage.
+///
+/// The [`SlippageModel::Flat`] model charges the `simulated_fees_pct` slippage as a fee. The
+/// volume-dependent models instead move the fill price against the order side by a market impact
+/// percentage that scales with the order quantity relative to a reference volume, so larger
+/// orders fill worse. Volume-dependent slippage is realised via the fill price, so no slippage
+/// fee is charged.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub enum SlippageModel {
+    #[default]
+    Flat,
+    /// Price impact = impact * (quantity / reference_volume)
+    Linear { impact: f64, reference_volume: f64 },
+    /// Price impact = impact * sqrt(quantity / reference_volume)
+    SquareRoot { impact: f64, reference_volume: f64 },
+}
+
+impl SlippageModel {
+    /// Calculates the price impact of the [`OrderEvent`] in decimal form (eg/ 0.01 for 1%).
+    pub fn price_impact(&self, order: &OrderEvent) -> f64 {
+        match *self {
+            SlippageModel::Flat => 0.0,
+            SlippageModel::Linear {
+                impact,
+                reference_volume,
+            } => impact * Self::participation(order, reference_volume),
+            SlippageModel::SquareRoot {
+                impact,
+                reference_volume,
+            } => impact * Self::participation(order, reference_volume).sqrt(),
+        }
+    }
+
+    /// Calculates the effective fill price of the [`OrderEvent`], moving the market price against
+    /// the order side by the price impact.
+    pub fn fill_price(&self, order: &OrderEvent) -> f64 {
+        let impact = self.price_impact(order);
+
+        match order.quantity.is_sign_positive() {
+            true => order.market_meta.close * (1.0 + impact),
+            false => order.market_meta.close * (1.0 - impact),
+        }
+    }
+
+    /// Size of the [`OrderEvent`] relative to the reference volume.
+    fn participation(order: &OrderEvent, reference_volume: f64) -> f64 {
+        match reference_volume > 0.0 {
+            true => order.quantity.abs() / reference_volume,
+            false => 0.0,
+        }
+    }
+}
+
+/// Model used by the [`SimulatedExecution`] to simulate the bid-ask spread crossed by taker
+/// [`OrderEvent`]s, for market data that only provides a single price (eg/ candles).
+///
+/// Buys fill at `price + half_spread` & sells fill at `price - half_spread`, so the spread always
+/// moves the fill price against the order side. Resting limit orders fill at their limit price,
+/// so do not cross the spread.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub enum SpreadModel {
+    #[default]
+    Zero,
+    /// Fixed half-spread in quote currency (eg/ 0.5 fills buys $0.50 above the price).
+    Absolute { half_spread: f64 },
+    /// Half-spread in basis points of the price (eg/ 5.0 fills buys 0.05% above the price).
+    Bps { half_spread: f64 },
+}
+
+impl SpreadModel {
+    /// Calculates the half-spread at the provided price. Always non-negative, so a misconfigured
+    /// negative half-spread can never improve the fill price.
+    pub fn half_spread(&self, price: f64) -> f64 {
+        match *self {
+            SpreadModel::Zero => 0.0,
+            SpreadModel::Absolute { half_spread } => half_spread.abs(),
+            SpreadModel::Bps { half_spread } => price.abs() * half_spread.abs() / 10_000.0,
+        }
+    }
+
+    /// Calculates the price the [`OrderEvent`] fills at after crossing the spread, moving the
+    /// provided price against the order side by the half-spread.
+    pub fn fill_price(&self, order: &OrderEvent, price: f64) -> f64 {
+        let half_spread = self.half_spread(price);
+
+        match order.quantity.is_sign_positive() {
+            true => price + half_spread,
+            false => price - half_spread,
+        }
+    }
 }
 
+/// Exchange fee percentages charged for providing (maker) & taking (taker) liquidity, in decimal
+/// form (eg/ 0.001 for 0.1%).
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct MakerTakerFees {
+    pub maker: f64,
+    pub taker: f64,
+}
+
+impl MakerTakerFees {
+    /// Returns the fee percentage charged for the provided [`Liquidity`].
+    pub fn fee_pct(&self, liquidity: Liquidity) -> f64 {
+        match liquidity {
+            Liquidity::Maker => self.maker,
+            Liquidity::Taker => self.taker,
+        }
+    }
+}
+
+/// Volume tiered maker & taker exchange fee schedule, where the [`MakerTakerFees`] applied to a
+/// fill step down as the cumulative notional traded over a rolling window grows (eg/ a 30 day
+/// VIP tier schedule).
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct FeeSchedule {
+    /// Fee tiers, each applying once the rolling traded notional reaches it's `min_volume`.
+    pub tiers: Vec<FeeTier>,
+    /// Rolling window over which traded notional is accumulated, (de)serialised as seconds.
+    /// Defaults to 30 days.
+    #[serde(
+        default = "default_volume_window",
+        deserialize_with = "de_duration_from_secs",
+        serialize_with = "se_duration_as_secs"
+    )]
+    pub window: Duration,
+}
+
+/// [`MakerTakerFees`] applied once the rolling traded notional reaches the `min_volume`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct FeeTier {
+    pub min_volume: f64,
+    pub fees: MakerTakerFees,
+}
+
+/// Default rolling window of a [`FeeSchedule`].
+fn default_volume_window() -> Duration {
+    Duration::days(30)
+}
+
+impl FeeSchedule {
+    /// Constructs a new [`FeeSchedule`] from the provided tiers, using the default 30 day rolling
+    /// window.
+    pub fn new<Tiers>(tiers: Tiers) -> Self
+    where
+        Tiers: IntoIterator<Item = FeeTier>,
+    {
+        Self {
+            tiers: tiers.into_iter().collect(),
+            window: default_volume_window(),
+        }
+    }
+
+    /// Returns the [`MakerTakerFees`] of the highest tier the rolling traded notional has
+    /// reached, if any.
+    pub fn fees(&self, volume: f64) -> Option<MakerTakerFees> {
+        self.tiers
+            .iter()
+            .filter(|tier| volume >= tier.min_volume)
+            .max_by(|a, b| a.min_volume.total_cmp(&b.min_volume))
+            .map(|tier| tier.fees)
+    }
+}
+
+/// Rolling traded notional used to determine the tier of a [`FeeSchedule`].
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+struct TieredFees {
+    schedule: FeeSchedule,
+    /// Notional of each fill, in time order, within the [`FeeSchedule`] window.
+    volume: VecDeque<(DateTime<Utc>, f64)>,
+}
+
+impl TieredFees {
+    /// Sum of the notional traded within the window ending at the provided time.
+    fn rolling_volume(&self, time: DateTime<Utc>) -> f64 {
+        let start = time - self.schedule.window;
+        self.volume
+            .iter()
+            .filter(|(fill_time, _)| *fill_time > start)
+            .map(|(_, notional)| notional)
+            .sum()
+    }
+
+    /// Records the notional of a [`FillEvent`], ageing out any volume that has fallen outside of
+    /// the window.
+    fn update_from_fill(&mut self, fill: &FillEvent) {
+        self.volume.push_back((fill.time, fill.fill_value_gross));
+
+        let start = fill.time - self.schedule.window;
+        while let Some((fill_time, _)) = self.volume.front() {
+            if *fill_time > start {
+                break;
+            }
+            self.volume.pop_front();
+        }
+    }
+}
+
+/// Whether an [`OrderEvent`] provides liquidity by resting on the book (maker), or takes
+/// liquidity by executing immediately (taker).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum Liquidity {
+    Maker,
+    Taker,
+}
+
+impl Liquidity {
+    /// Determines the [`Liquidity`] of an [`OrderEvent`]. Only a limit order priced so it does not
+    /// cross the current market price rests on the book as a maker. Market orders, stop orders,
+    /// and marketable limit orders are all takers.
+    pub fn of(order: &OrderEvent) -> Self {
+        let OrderType::Limit { price } = order.order_type else {
+            return Liquidity::Taker;
+        };
+
+        let marketable = match order.quantity.is_sign_positive() {
+            true => price >= order.market_meta.close,
+            false => price <= order.market_meta.close,
+        };
+
+        match marketable {
+            true => Liquidity::Taker,
+            false => Liquidity::Maker,
+        }
+    }
+}
+
+/// Open, low & high prices traded by the market data of a [`MarketEvent`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+pub(super) struct TradedRange {
+    open: f64,
+    low: f64,
+    high: f64,
+}
+
+impl TradedRange {
+    /// Returns the [`TradedRange`] of a market that has only traded at the provided price.
+    pub(super) fn at(price: f64) -> Self {
+        Self {
+            open: price,
+            low: price,
+            high: price,
+        }
+    }
+
+    /// Returns the [`TradedRange`] of the provided market data, if it contains traded prices.
+    pub(super) fn of(kind: &DataKind) -> Option<Self> {
+        match kind {
+            DataKind::Trade(PublicTrade { price, .. }) => Some(Self::at(*price)),
+            DataKind::Candle(Candle {
+                open, low, high, ..
+            }) => Some(Self {
+                open: *open,
+                low: *low,
+                high: *high,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Determines if a stop [`OrderEvent`] trigger price is reached. Buy stops trigger once the
+    /// price rises to the trigger, sell stops once it falls to it.
+    pub(super) fn triggers(&self, order: &OrderEvent, trigger: f64) -> bool {
+        match order.quantity.is_sign_positive() {
+            true => self.high >= trigger,
+            false => self.low <= trigger,
+        }
+    }
+
+    /// Determines if a limit [`OrderEvent`] price is traded through. Buy limits require the price
+    /// to fall to the limit, sell limits require it to rise to it.
+    pub(super) fn trades_through(&self, order: &OrderEvent, price: f64) -> bool {
+        match order.quantity.is_sign_positive() {
+            true => self.low <= price,
+            false => self.high >= price,
+        }
+    }
+
+    /// Price a triggered stop [`OrderEvent`] is filled at. If the market gapped through the
+    /// trigger price, the stop is filled at the worse open price.
+    pub(super) fn stop_fill_price(&self, order: &OrderEvent, trigger: f64) -> f64 {
+        match order.quantity.is_sign_positive() {
+            true => self.open.max(trigger),
+            false => self.open.min(trigger),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 /// Simulated execution handler that executes [`OrderEvent`]s to generate [`FillEvent`]s via a
 /// simulated broker interaction.
+///
+/// Stop orders rest until a subsequent [`MarketEvent`] reaches their trigger price, as do resting
+/// limit orders when using the [`LimitFillModel::TradeThrough`] model. Resting exit orders for the
+/// same instrument are one-cancels-other: once one fills, the others are cancelled, so a candle
+/// gapping through both a stop-loss & a take-profit only closes the position once. Stops are
+/// resolved first, so such a candle conservatively fills the stop.
+///
+/// If configured with a [`FeeSchedule`], the exchange fee of each fill is determined by the
+/// notional previously traded within it's rolling window, so subsequent fills step down through
+/// the fee tiers as volume grows.
 pub struct SimulatedExecution {
     fees_pct: Fees,
+    exchange_fees_pct: Option<MakerTakerFees>,
+    tiered_fees: Option<TieredFees>,
+    slippage_model: SlippageModel,
+    spread_model: SpreadModel,
+    latency: Duration,
+    limit_fill_model: LimitFillModel,
+    /// Resting [`OrderEvent`]s awaiting a [`MarketEvent`] that triggers or trades through them.
+    pending: Vec<OrderEvent>,
 }
 
 impl ExecutionClient for SimulatedExecution {
     fn generate_fill(&self, order: &OrderEvent) -> Result<FillEvent, ExecutionError> {
-        // Assume (for now) that all orders are filled at the market price
-        let fill_value_gross = SimulatedExecution::calculate_fill_value_gross(order);
+        // Market price fill, adjusted for slippage & the spread
+        let fill_value_gross = self.calculate_fill_value_gross(order);
+        let time = order.time + self.latency;
 
         Ok(FillEvent {
-            time: Utc::now(),
+            cid: Some(order.cid),
+            time,
             exchange: order.exchange,
             instrument: order.instrument.clone(),
             market_meta: order.market_meta,
             decision: order.decision,
             quantity: order.quantity,
             fill_value_gross,
-            fees: self.calculate_fees(&fill_value_gross),
+            fees: self.calculate_fees(&fill_value_gross, Liquidity::of(order), time),
+            strategy_id: order.strategy_id.clone(),
         })
     }
+
+    fn submit_order(&mut self, order: &OrderEvent) -> Result<Option<FillEvent>, ExecutionError> {
+        let mut order = order.clone();
+
+        // Stop orders rest until triggered, unless the market price has already reached them
+        if let Some(trigger) = order.order_type.trigger_price() {
+            if !TradedRange::at(order.market_meta.close).triggers(&order, trigger) {
+                self.pending.push(order);
+                return Ok(None);
+            }
+            order.order_type = order.order_type.triggered();
+        }
+
+        match (self.limit_fill_model, Liquidity::of(&order)) {
+            (LimitFillModel::TradeThrough, Liquidity::Maker) => {
+                self.pending.push(order);
+                Ok(None)
+            }
+            _ => {
+                let fill = self.generate_fill(&order)?;
+                self.update_volume(&fill);
+                Ok(Some(fill))
+            }
+        }
+    }
+
+    fn update_from_market(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<FillEvent>, ExecutionError> {
+        let Some(range) = TradedRange::of(&market.kind) else {
+            return Ok(Vec::new());
+        };
+
+        // Resolve stops before limits, so gapping through both levels fills the stop
+        let (resting, mut pending) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition::<Vec<OrderEvent>, _>(|order| {
+                order.exchange == market.exchange && order.instrument == market.instrument
+            });
+        let (stops, limits) = resting
+            .into_iter()
+            .partition::<Vec<OrderEvent>, _>(|order| order.order_type.trigger_price().is_some());
+
+        let mut fills = Vec::new();
+        let mut exit_filled = false;
+        for mut order in stops.into_iter().chain(limits) {
+            // One-cancels-other: only a single resting exit order can close the position
+            if exit_filled && order.decision.is_exit() {
+                info!(
+                    cid = %order.cid,
+                    exchange = %order.exchange,
+                    "cancelled resting exit OrderEvent after another exit OrderEvent was filled"
+                );
+                continue;
+            }
+
+            let fill = match order.order_type {
+                OrderType::Stop { trigger } | OrderType::StopLimit { trigger, .. }
+                    if !range.triggers(&order, trigger) =>
+                {
+                    None
+                }
+                OrderType::Stop { trigger } => {
+                    // Triggered stop becomes a market order, so crosses the spread
+                    let price = range.stop_fill_price(&order, trigger);
+                    Some(self.generate_resting_fill(
+                        &order,
+                        self.spread_model.fill_price(&order, price),
+                        Liquidity::Taker,
+                        market.time_exchange,
+                    ))
+                }
+                OrderType::StopLimit { .. } => {
+                    // Triggered stop-limit becomes a resting limit order
+                    order.order_type = order.order_type.triggered();
+                    self.resolve_limit(&order, &range, market.time_exchange)
+                }
+                _ => self.resolve_limit(&order, &range, market.time_exchange),
+            };
+
+            match fill {
+                Some(fill) => {
+                    exit_filled |= order.decision.is_exit();
+                    fills.push(fill);
+                }
+                None => pending.push(order),
+            }
+        }
+
+        // Cancel any remaining resting exit orders for the instrument if an exit filled
+        if exit_filled {
+            pending.retain(|order| {
+                order.exchange != market.exchange
+                    || order.instrument != market.instrument
+                    || !order.decision.is_exit()
+            });
+        }
+        self.pending = pending;
+        fills.iter().for_each(|fill| self.update_volume(fill));
+
+        Ok(fills)
+    }
+
+    fn cancel_order(&mut self, cid: Uuid) -> Option<OrderEvent> {
+        let index = self.pending.iter().position(|order| order.cid == cid)?;
+        Some(self.pending.remove(index))
+    }
 }
 
 impl SimulatedExecution {
@@ -43,19 +522,123 @@ impl SimulatedExecution {
     pub fn new(cfg: Config) -> Self {
         Self {
             fees_pct: cfg.simulated_fees_pct,
+            exchange_fees_pct: cfg.exchange_fees_pct,
+            tiered_fees: None,
+            slippage_model: cfg.slippage_model,
+            spread_model: cfg.spread_model,
+            latency: cfg.latency,
+            limit_fill_model: cfg.limit_fill_model,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Determine the exchange fee of each fill using the provided volume tiered [`FeeSchedule`],
+    /// rather than the flat maker & taker fees.
+    pub fn with_fee_schedule(self, schedule: FeeSchedule) -> Self {
+        Self {
+            tiered_fees: Some(TieredFees {
+                schedule,
+                volume: VecDeque::new(),
+            }),
+            ..self
+        }
+    }
+
+    /// Returns the notional traded within the [`FeeSchedule`] window ending at the provided time,
+    /// or `None` if no [`FeeSchedule`] is configured.
+    pub fn rolling_volume(&self, time: DateTime<Utc>) -> Option<f64> {
+        self.tiered_fees
+            .as_ref()
+            .map(|tiered| tiered.rolling_volume(time))
+    }
+
+    /// Returns the resting [`OrderEvent`]s awaiting a [`MarketEvent`] that triggers or trades
+    /// through them.
+    pub fn pending_orders(&self) -> &[OrderEvent] {
+        &self.pending
+    }
+
+    /// Generates the maker [`FillEvent`] of a resting limit [`OrderEvent`] at it's limit price,
+    /// if the [`TradedRange`] trades through it.
+    fn resolve_limit(
+        &self,
+        order: &OrderEvent,
+        range: &TradedRange,
+        traded_time: DateTime<Utc>,
+    ) -> Option<FillEvent> {
+        let price = order.order_type.limit_price()?;
+
+        range
+            .trades_through(order, price)
+            .then(|| self.generate_resting_fill(order, price, Liquidity::Maker, traded_time))
+    }
+
+    /// Generates the [`FillEvent`] of a resting [`OrderEvent`] filled at the provided price.
+    fn generate_resting_fill(
+        &self,
+        order: &OrderEvent,
+        price: f64,
+        liquidity: Liquidity,
+        traded_time: DateTime<Utc>,
+    ) -> FillEvent {
+        let fill_value_gross = order.quantity.abs() * price;
+        let time = traded_time + self.latency;
+
+        FillEvent {
+            cid: Some(order.cid),
+            time,
+            exchange: order.exchange,
+            instrument: order.instrument.clone(),
+            market_meta: MarketMeta {
+                close: price,
+                time: traded_time,
+            },
+            decision: order.decision,
+            quantity: order.quantity,
+            fill_value_gross,
+            fees: self.calculate_fees(&fill_value_gross, liquidity, time),
+            strategy_id: order.strategy_id.clone(),
         }
     }
 
     /// Calculates the simulated gross fill value (excluding TotalFees) based on the input [`OrderEvent`].
-    fn calculate_fill_value_gross(order: &OrderEvent) -> f64 {
-        order.quantity.abs() * order.market_meta.close
+    fn calculate_fill_value_gross(&self, order: &OrderEvent) -> f64 {
+        let price = self.slippage_model.fill_price(order);
+        order.quantity.abs() * self.spread_model.fill_price(order, price)
     }
 
-    /// Calculates the simulated [`Fees`] a [`FillEvent`] will incur, based on the input [`OrderEvent`].
-    fn calculate_fees(&self, fill_value_gross: &f64) -> Fees {
+    /// Records the notional of the [`FillEvent`] towards the [`FeeSchedule`] rolling volume.
+    fn update_volume(&mut self, fill: &FillEvent) {
+        if let Some(tiered) = &mut self.tiered_fees {
+            tiered.update_from_fill(fill);
+        }
+    }
+
+    /// Calculates the simulated [`Fees`] a [`FillEvent`] will incur, based on the input [`OrderEvent`]
+    /// fill value & [`Liquidity`], & the [`FeeSchedule`] tier reached at the provided time.
+    fn calculate_fees(
+        &self,
+        fill_value_gross: &f64,
+        liquidity: Liquidity,
+        time: DateTime<Utc>,
+    ) -> Fees {
+        let tier_fees = self
+            .tiered_fees
+            .as_ref()
+            .and_then(|tiered| tiered.schedule.fees(tiered.rolling_volume(time)));
+
+        let exchange_fee_pct = tier_fees
+            .or(self.exchange_fees_pct)
+            .map_or(self.fees_pct.exchange, |fees| fees.fee_pct(liquidity));
+
+        let slippage_fee_pct = match self.slippage_model {
+            SlippageModel::Flat => self.fees_pct.slippage,
+            SlippageModel::Linear { .. } | SlippageModel::SquareRoot { .. } => 0.0,
+        };
+
         Fees {
-            exchange: self.fees_pct.exchange * fill_value_gross,
-            slippage: self.fees_pct.slippage * fill_value_gross,
+            exchange: exchange_fee_pct * fill_value_gross,
+            slippage: slippage_fee_pct * fill_value_gross,
             network: self.fees_pct.network * fill_value_gross,
         }
     }
@@ -64,7 +647,10 @@ impl SimulatedExecution {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::order_event;
+    use crate::{
+        strategy::Decision,
+        test_util::{market_event_candle, order_event},
+    };
 
     #[test]
     fn should_generate_ok_fill_event_with_valid_order_event_provided() {
@@ -74,6 +660,11 @@ mod tests {
                 slippage: 0.05,
                 network: 0.0,
             },
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
         });
 
         let mut input_order = order_event();
@@ -101,7 +692,7 @@ mod tests {
         input_order.quantity = 100.0;
         input_order.market_meta.close = 10.0;
 
-        let actual = SimulatedExecution::calculate_fill_value_gross(&input_order);
+        let actual = SimulatedExecution::default().calculate_fill_value_gross(&input_order);
 
         let expected = 100.0 * 10.0;
 
@@ -114,7 +705,7 @@ mod tests {
         input_order.quantity = -(100.0);
         input_order.market_meta.close = 10.0;
 
-        let actual = SimulatedExecution::calculate_fill_value_gross(&input_order);
+        let actual = SimulatedExecution::default().calculate_fill_value_gross(&input_order);
 
         let expected = (100.0 * 10.0) as f64;
 
@@ -129,11 +720,20 @@ mod tests {
                 slippage: 0.1,
                 network: 0.001,
             },
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
         });
 
         let input_fill_value_gross = 100.0;
 
-        let actual_result = simulated_execution.calculate_fees(&input_fill_value_gross);
+        let actual_result = simulated_execution.calculate_fees(
+            &input_fill_value_gross,
+            Liquidity::Taker,
+            Utc::now(),
+        );
 
         let expected = Fees {
             exchange: 50.0,
@@ -143,4 +743,519 @@ mod tests {
 
         assert_eq!(actual_result, expected)
     }
+
+    fn maker_taker_execution() -> SimulatedExecution {
+        SimulatedExecution::new(Config {
+            simulated_fees_pct: Fees {
+                exchange: 0.5,
+                slippage: 0.0,
+                network: 0.0,
+            },
+            exchange_fees_pct: Some(MakerTakerFees {
+                maker: 0.001,
+                taker: 0.004,
+            }),
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        })
+    }
+
+    #[test]
+    fn market_order_incurs_taker_fee() {
+        let mut order = order_event();
+        order.quantity = 1.0;
+        order.market_meta.close = 1000.0;
+
+        let fill = maker_taker_execution().generate_fill(&order).unwrap();
+
+        assert_eq!(Liquidity::of(&order), Liquidity::Taker);
+        assert_eq!(fill.fees.exchange, 4.0);
+    }
+
+    #[test]
+    fn resting_limit_order_incurs_maker_fee() {
+        // Buy limit below the market price rests on the book
+        let mut buy = order_event();
+        buy.order_type = OrderType::Limit { price: 990.0 };
+        buy.quantity = 1.0;
+        buy.market_meta.close = 1000.0;
+
+        // Sell limit above the market price rests on the book
+        let mut sell = buy.clone();
+        sell.order_type = OrderType::Limit { price: 1010.0 };
+        sell.quantity = -1.0;
+
+        for order in [buy, sell] {
+            let fill = maker_taker_execution().generate_fill(&order).unwrap();
+            assert_eq!(Liquidity::of(&order), Liquidity::Maker);
+            assert_eq!(fill.fees.exchange, 1.0);
+        }
+    }
+
+    #[test]
+    fn limit_order_crossing_the_market_incurs_taker_fee() {
+        let mut order = order_event();
+        order.order_type = OrderType::Limit { price: 1005.0 };
+        order.quantity = 1.0;
+        order.market_meta.close = 1000.0;
+
+        let fill = maker_taker_execution().generate_fill(&order).unwrap();
+
+        assert_eq!(Liquidity::of(&order), Liquidity::Taker);
+        assert_eq!(fill.fees.exchange, 4.0);
+    }
+
+    #[test]
+    fn fee_schedule_steps_down_once_rolling_volume_passes_tier_threshold() {
+        let mut execution = maker_taker_execution().with_fee_schedule(FeeSchedule::new([
+            FeeTier {
+                min_volume: 0.0,
+                fees: MakerTakerFees {
+                    maker: 0.001,
+                    taker: 0.004,
+                },
+            },
+            FeeTier {
+                min_volume: 15_000.0,
+                fees: MakerTakerFees {
+                    maker: 0.0005,
+                    taker: 0.002,
+                },
+            },
+        ]));
+
+        let start = Utc::now();
+        let mut order_at = |days: i64| {
+            let mut order = order_event();
+            order.time = start + Duration::days(days);
+            order.quantity = 10.0;
+            order.market_meta.close = 1000.0;
+            execution
+                .submit_order(&order)
+                .unwrap()
+                .expect("market order fills immediately")
+        };
+
+        // First two 10,000 notional fills are charged the base taker tier
+        assert_eq!(order_at(0).fees.exchange, 40.0);
+        assert_eq!(order_at(1).fees.exchange, 40.0);
+
+        // 20,000 traded over the last 30 days reaches the lower tier
+        assert_eq!(order_at(2).fees.exchange, 20.0);
+        assert_eq!(
+            execution.rolling_volume(start + Duration::days(2)),
+            Some(30_000.0)
+        );
+
+        // Volume older than 30 days ages out, reverting to the base tier
+        let fill = {
+            let mut order = order_event();
+            order.time = start + Duration::days(40);
+            order.quantity = 10.0;
+            order.market_meta.close = 1000.0;
+            execution.submit_order(&order).unwrap().unwrap()
+        };
+        assert_eq!(fill.fees.exchange, 40.0);
+        assert_eq!(execution.rolling_volume(fill.time), Some(10_000.0));
+    }
+
+    fn slippage_execution(slippage_model: SlippageModel) -> SimulatedExecution {
+        SimulatedExecution::new(Config {
+            simulated_fees_pct: Fees {
+                exchange: 0.0,
+                slippage: 0.5,
+                network: 0.0,
+            },
+            exchange_fees_pct: None,
+            slippage_model,
+            spread_model: SpreadModel::Zero,
+            latency: Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        })
+    }
+
+    #[test]
+    fn linear_slippage_scales_proportionally_with_order_size() {
+        let execution = slippage_execution(SlippageModel::Linear {
+            impact: 0.01,
+            reference_volume: 100.0,
+        });
+
+        let mut order = order_event();
+        order.market_meta.close = 1000.0;
+
+        let slippage = |quantity: f64| {
+            let mut order = order.clone();
+            order.quantity = quantity;
+            let fill = execution.generate_fill(&order).unwrap();
+            assert_eq!(fill.fees.slippage, 0.0);
+            fill.fill_value_gross / quantity.abs() - 1000.0
+        };
+
+        // Buy of 10 moves price up 0.1%, buy of 20 moves it up 0.2%
+        let small = slippage(10.0);
+        let large = slippage(20.0);
+        assert!((small - 1.0).abs() < 1e-9);
+        assert!((large - 2.0 * small).abs() < 1e-9);
+
+        // Sells move the price down
+        assert!((slippage(-10.0) + 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn square_root_slippage_scales_with_square_root_of_order_size() {
+        let model = SlippageModel::SquareRoot {
+            impact: 0.01,
+            reference_volume: 100.0,
+        };
+
+        let mut order = order_event();
+        order.market_meta.close = 1000.0;
+        order.quantity = 25.0;
+        let small = model.fill_price(&order) - 1000.0;
+
+        order.quantity = 100.0;
+        let large = model.fill_price(&order) - 1000.0;
+
+        // 4x larger order produces 2x the price impact
+        assert!((small - 5.0).abs() < 1e-9);
+        assert!((large - 2.0 * small).abs() < 1e-9);
+    }
+
+    #[test]
+    fn flat_slippage_is_charged_as_fee_at_market_price() {
+        let execution = slippage_execution(SlippageModel::Flat);
+
+        let mut order = order_event();
+        order.quantity = 2.0;
+        order.market_meta.close = 100.0;
+
+        let fill = execution.generate_fill(&order).unwrap();
+        assert_eq!(fill.fill_value_gross, 200.0);
+        assert_eq!(fill.fees.slippage, 100.0);
+    }
+
+    fn spread_execution(spread_model: SpreadModel) -> SimulatedExecution {
+        SimulatedExecution::new(Config {
+            spread_model,
+            limit_fill_model: LimitFillModel::TradeThrough,
+            ..Config::default()
+        })
+    }
+
+    #[test]
+    fn buy_and_sell_on_same_candle_price_fill_symmetrically_worse_across_the_spread() {
+        let market = market_event_candle();
+        let DataKind::Candle(candle) = &market.kind else {
+            unreachable!()
+        };
+
+        let fill_price = |execution: &SimulatedExecution, quantity: f64| {
+            let mut order = order_event();
+            order.quantity = quantity;
+            order.market_meta.close = candle.close;
+            let fill = execution.generate_fill(&order).unwrap();
+            fill.fill_value_gross / quantity.abs()
+        };
+
+        // Fixed half-spread of 2.5 & percentage half-spread of 10bps of the 1000.0 close
+        let cases = [
+            (SpreadModel::Absolute { half_spread: 2.5 }, 2.5),
+            (SpreadModel::Bps { half_spread: 10.0 }, 1.0),
+            // Negative half-spread still moves the price against the order
+            (SpreadModel::Absolute { half_spread: -2.5 }, 2.5),
+            (SpreadModel::Zero, 0.0),
+        ];
+
+        for (index, (spread_model, half_spread)) in cases.into_iter().enumerate() {
+            let execution = spread_execution(spread_model);
+
+            let buy = fill_price(&execution, 2.0);
+            let sell = fill_price(&execution, -2.0);
+
+            assert!(
+                (buy - (candle.close + half_spread)).abs() < 1e-9,
+                "TC{index} buy"
+            );
+            assert!(
+                (sell - (candle.close - half_spread)).abs() < 1e-9,
+                "TC{index} sell"
+            );
+            assert!(
+                (buy - candle.close - (candle.close - sell)).abs() < 1e-9,
+                "TC{index} symmetric"
+            );
+        }
+    }
+
+    #[test]
+    fn triggered_stop_crosses_the_spread_but_resting_limit_fills_at_limit_price() {
+        let mut execution = spread_execution(SpreadModel::Absolute { half_spread: 2.0 });
+
+        let stop = resting_order(OrderType::Stop { trigger: 95.0 }, Decision::CloseLong, -1.0);
+        let limit = resting_order(OrderType::Limit { price: 90.0 }, Decision::EnterLong, 1.0);
+        execution.submit_order(&stop).unwrap();
+        execution.submit_order(&limit).unwrap();
+
+        let fills = execution
+            .update_from_market(&candle(94.0, 89.0, 96.0))
+            .unwrap();
+
+        // Stop sells at the gapped open of 94.0 less the half-spread
+        assert_eq!(fills[0].cid, Some(stop.cid));
+        assert_eq!(fills[0].fill_value_gross, 92.0);
+
+        // Limit buy rests as a maker, so fills at the limit price
+        assert_eq!(fills[1].cid, Some(limit.cid));
+        assert_eq!(fills[1].fill_value_gross, 90.0);
+    }
+
+    fn trade_through_execution(latency: Duration) -> SimulatedExecution {
+        SimulatedExecution::new(Config {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: Some(MakerTakerFees {
+                maker: 0.001,
+                taker: 0.004,
+            }),
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency,
+            limit_fill_model: LimitFillModel::TradeThrough,
+        })
+    }
+
+    #[test]
+    fn fill_time_is_offset_from_order_time_by_latency() {
+        let mut execution = trade_through_execution(Duration::milliseconds(250));
+
+        let order = order_event();
+        let fill = execution.submit_order(&order).unwrap().unwrap();
+
+        assert_eq!(fill.time, order.time + Duration::milliseconds(250));
+    }
+
+    #[test]
+    fn limit_buy_below_market_only_fills_once_candle_low_reaches_limit() {
+        let mut execution = trade_through_execution(Duration::milliseconds(100));
+
+        let market = market_event_candle();
+        let mut order = order_event();
+        order.exchange = market.exchange;
+        order.instrument = market.instrument.clone();
+        order.order_type = OrderType::Limit { price: 900.0 };
+        order.quantity = 1.0;
+        order.market_meta.close = 1000.0;
+
+        // Resting buy limit is queued rather than filled at the market price
+        assert_eq!(execution.submit_order(&order).unwrap(), None);
+        assert_eq!(execution.pending_orders().len(), 1);
+
+        // Candle low of 950.0 does not reach the limit
+        assert!(execution.update_from_market(&market).unwrap().is_empty());
+        assert_eq!(execution.pending_orders().len(), 1);
+
+        // Candle low of 890.0 trades through the limit
+        let mut traded_through = market_event_candle();
+        if let DataKind::Candle(candle) = &mut traded_through.kind {
+            candle.low = 890.0;
+        }
+        let fills = execution.update_from_market(&traded_through).unwrap();
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].cid, Some(order.cid));
+        assert_eq!(fills[0].fill_value_gross, 900.0);
+        assert_eq!(fills[0].fees.exchange, 0.9);
+        assert_eq!(
+            fills[0].time,
+            traded_through.time_exchange + Duration::milliseconds(100)
+        );
+        assert!(execution.pending_orders().is_empty());
+    }
+
+    #[test]
+    fn marketable_limit_order_fills_immediately_with_trade_through_model() {
+        let mut execution = trade_through_execution(Duration::zero());
+
+        let mut order = order_event();
+        order.order_type = OrderType::Limit { price: 1005.0 };
+        order.quantity = 1.0;
+        order.market_meta.close = 1000.0;
+
+        let fill = execution.submit_order(&order).unwrap().unwrap();
+
+        assert_eq!(fill.fill_value_gross, 1000.0);
+        assert_eq!(fill.fees.exchange, 4.0);
+        assert!(execution.pending_orders().is_empty());
+    }
+
+    /// Candle [`MarketEvent`] for the [`order_event`] market with the provided open, low & high.
+    fn candle(open: f64, low: f64, high: f64) -> MarketEvent<Instrument, DataKind> {
+        let order = order_event();
+        let mut market = market_event_candle();
+        market.exchange = order.exchange;
+        market.instrument = order.instrument;
+        if let DataKind::Candle(candle) = &mut market.kind {
+            candle.open = open;
+            candle.low = low;
+            candle.high = high;
+        }
+        market
+    }
+
+    fn resting_order(order_type: OrderType, decision: Decision, quantity: f64) -> OrderEvent {
+        let mut order = order_event();
+        order.order_type = order_type;
+        order.decision = decision;
+        order.quantity = quantity;
+        order.market_meta.close = 100.0;
+        order
+    }
+
+    #[test]
+    fn market_order_fills_immediately() {
+        let mut execution = trade_through_execution(Duration::zero());
+
+        let order = resting_order(OrderType::Market, Decision::EnterLong, 1.0);
+        let fill = execution.submit_order(&order).unwrap().unwrap();
+
+        assert_eq!(fill.fill_value_gross, 100.0);
+        assert!(execution.pending_orders().is_empty());
+    }
+
+    #[test]
+    fn stop_order_converts_to_market_order_once_triggered() {
+        let mut execution = trade_through_execution(Duration::zero());
+
+        let order = resting_order(OrderType::Stop { trigger: 105.0 }, Decision::EnterLong, 1.0);
+        assert_eq!(execution.submit_order(&order).unwrap(), None);
+
+        // High of 104.0 does not reach the buy stop trigger
+        assert!(execution
+            .update_from_market(&candle(100.0, 99.0, 104.0))
+            .unwrap()
+            .is_empty());
+
+        // High of 106.0 triggers the stop, which fills at the trigger price as a taker
+        let fills = execution
+            .update_from_market(&candle(101.0, 100.0, 106.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 105.0);
+        assert!((fills[0].fees.exchange - 105.0 * 0.004).abs() < 1e-9);
+        assert!(execution.pending_orders().is_empty());
+
+        // Sell stop gapped through by the open fills at the worse open price
+        let order = resting_order(
+            OrderType::Stop { trigger: 95.0 },
+            Decision::EnterShort,
+            -1.0,
+        );
+        assert_eq!(execution.submit_order(&order).unwrap(), None);
+        let fills = execution
+            .update_from_market(&candle(90.0, 88.0, 92.0))
+            .unwrap();
+        assert_eq!(fills[0].fill_value_gross, 90.0);
+    }
+
+    #[test]
+    fn stop_limit_order_converts_to_limit_order_once_triggered() {
+        let mut execution = trade_through_execution(Duration::zero());
+
+        let order = resting_order(
+            OrderType::StopLimit {
+                trigger: 95.0,
+                price: 94.0,
+            },
+            Decision::EnterShort,
+            -1.0,
+        );
+        assert_eq!(execution.submit_order(&order).unwrap(), None);
+
+        // Low of 92.0 triggers the sell stop, but the high of 93.5 never trades back up to the limit
+        assert!(execution
+            .update_from_market(&candle(93.0, 92.0, 93.5))
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            execution.pending_orders()[0].order_type,
+            OrderType::Limit { price } if price == 94.0
+        ));
+
+        // Subsequent rally trades through the limit, which fills at the limit price as a maker
+        let fills = execution
+            .update_from_market(&candle(93.0, 92.5, 94.5))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 94.0);
+        assert!((fills[0].fees.exchange - 94.0 * 0.001).abs() < 1e-9);
+    }
+
+    #[test]
+    fn limit_order_fills_once_traded_through() {
+        let mut execution = trade_through_execution(Duration::zero());
+
+        let order = resting_order(
+            OrderType::Limit { price: 110.0 },
+            Decision::EnterShort,
+            -1.0,
+        );
+        assert_eq!(execution.submit_order(&order).unwrap(), None);
+
+        assert!(execution
+            .update_from_market(&candle(100.0, 99.0, 109.0))
+            .unwrap()
+            .is_empty());
+
+        let fills = execution
+            .update_from_market(&candle(105.0, 104.0, 111.0))
+            .unwrap();
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].fill_value_gross, 110.0);
+    }
+
+    #[test]
+    fn candle_gapping_through_stop_and_limit_exits_only_closes_position_once() {
+        let mut execution = trade_through_execution(Duration::zero());
+
+        // Long Position protected by a take-profit limit & a stop-loss stop
+        let take_profit =
+            resting_order(OrderType::Limit { price: 110.0 }, Decision::CloseLong, -1.0);
+        let stop_loss = resting_order(OrderType::Stop { trigger: 90.0 }, Decision::CloseLong, -1.0);
+        assert_eq!(execution.submit_order(&take_profit).unwrap(), None);
+        assert_eq!(execution.submit_order(&stop_loss).unwrap(), None);
+
+        // Wide candle trades through both levels
+        let fills = execution
+            .update_from_market(&candle(100.0, 85.0, 115.0))
+            .unwrap();
+
+        assert_eq!(fills.len(), 1);
+        assert_eq!(fills[0].cid, Some(stop_loss.cid));
+        assert_eq!(fills[0].fill_value_gross, 90.0);
+        assert!(execution.pending_orders().is_empty());
+
+        // Cancelled take-profit does not fill on a later candle
+        assert!(execution
+            .update_from_market(&candle(112.0, 111.0, 120.0))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn cancelled_resting_order_is_never_filled() {
+        let mut execution = trade_through_execution(Duration::zero());
+
+        let order = resting_order(OrderType::Stop { trigger: 105.0 }, Decision::EnterLong, 1.0);
+        assert_eq!(execution.submit_order(&order).unwrap(), None);
+        assert_eq!(execution.cancel_order(order.cid), Some(order.clone()));
+        assert_eq!(execution.cancel_order(order.cid), None);
+
+        // High of 106.0 would have triggered the cancelled stop
+        assert!(execution
+            .update_from_market(&candle(101.0, 100.0, 106.0))
+            .unwrap()
+            .is_empty());
+    }
 }
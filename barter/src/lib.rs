@@ -17,7 +17,7 @@
 //! the **Engine's Events can be listened to using the event_rx** (useful for event-sourcing). At a high level,
 //! it provides several de-coupled components that interact via a set of traits:
 
-//! * **Data**: Continuer & MarketGenerator traits govern the generation of a MarketEvents data feed that acts as the system
+//! * **Data**: Continuer, MarketGenerator & AsyncMarketGenerator traits govern the generation of a MarketEvents data feed that acts as the system
 //!   heartbeat. For example, a LiveCandleHandler implementation is provided utilising [`Barter-Data`]'s WebSocket functionality to
 //!   provide a live market Candle data feed to the system.
 //! * **Strategy**: The SignalGenerator trait governs potential generation of SignalEvents after analysing incoming
@@ -68,6 +68,7 @@
 //!
 //! let config = StrategyConfig {
 //!     rsi_period: 14,
+//!     gap_rewarm: None,
 //! };
 //!
 //! let mut strategy = RSIStrategy::new(config);
@@ -107,10 +108,15 @@
 //!     allocator: DefaultAllocator{ default_order_value: 100.0 },
 //!     risk: DefaultRisk{},
 //!     starting_cash: 10000.0,
+//!     max_leverage: 1.0,
+//!     maintenance_margin_rate: 0.005,
 //!     statistic_config: StatisticConfig {
 //!         starting_equity: 10000.0 ,
 //!         trading_days_per_year: 365,
-//!         risk_free_return: 0.0
+//!         data_period: chrono::Duration::days(1),
+//!         risk_free_return: 0.0,
+//!         omega_threshold: None,
+//!         with_benchmark: false,
 //!     },
 //!     _statistic_marker: PhantomData::<TradingSummary>::default()
 //! };
@@ -142,7 +148,10 @@
 //!     test_util,
 //!     portfolio::OrderEvent,
 //!     execution::{
-//!         simulated::{Config as ExecutionConfig, SimulatedExecution},
+//!         simulated::{
+//!             Config as ExecutionConfig, LimitFillModel, SimulatedExecution, SlippageModel,
+//!             SpreadModel,
+//!         },
 //!         Fees, ExecutionClient,
 //!     }
 //! };
@@ -152,7 +161,12 @@
 //!         exchange: 0.1,
 //!         slippage: 0.05, // Simulated slippage modelled as a Fee
 //!         network: 0.0,
-//!     }
+//!     },
+//!     exchange_fees_pct: None,
+//!     slippage_model: SlippageModel::Flat,
+//!     spread_model: SpreadModel::Zero,
+//!     latency: chrono::Duration::zero(),
+//!     limit_fill_model: LimitFillModel::Immediate,
 //! };
 //!
 //! let mut execution = SimulatedExecution::new(config);
@@ -179,7 +193,10 @@
 //! let config = StatisticConfig {
 //!     starting_equity: 10000.0,
 //!     trading_days_per_year: 253,
+//!     data_period: chrono::Duration::days(1),
 //!     risk_free_return: 0.5,
+//!     omega_threshold: None,
+//!     with_benchmark: false,
 //! };
 //!
 //! let mut trading_summary = TradingSummary::init(config);
@@ -204,8 +221,8 @@
 #![allow(clippy::type_complexity)]
 #![allow(clippy::module_inception)]
 
-/// Defines a MarketEvent, and provides the Continuer and MarketGenerator traits for
-/// handling the generation of them. Contains implementations such as the (tick-by_tick)
+/// Defines a MarketEvent, and provides the Continuer, MarketGenerator and AsyncMarketGenerator
+/// traits for handling the generation of them. Contains implementations such as the (tick-by_tick)
 /// LiveTradeHandler, and HistoricalCandleHandler that generates a market feed and acts as the
 /// system heartbeat.
 pub mod data;
@@ -244,6 +261,16 @@ pub mod statistic;
 /// Execution components, as well as shared access to a global Portfolio.
 pub mod engine;
 
+/// Defines the Clock trait used to timestamp the Events generated by the trading system. Contains
+/// a wall-clock SystemClock for live-trading, and a deterministic SimulatedClock driven by market
+/// event timestamps for backtesting.
+pub mod clock;
+
+/// Config-file driven construction of backtest components. Defines a deserialisable
+/// BacktestConfig specifying the data source, strategy type & parameters, Portfolio allocator &
+/// risk settings, and simulated execution fees, as well as the factories that build them.
+pub mod config;
+
 #[macro_use]
 extern crate prettytable;
 
@@ -252,7 +279,7 @@ pub mod test_util {
         data::MarketMeta,
         execution::{Fees, FillEvent},
         portfolio::{position::Position, OrderEvent, OrderType},
-        strategy::{Decision, Signal},
+        strategy::{Decision, Signal, StrategyId},
     };
     use barter_data::{
         event::{DataKind, MarketEvent},
@@ -263,9 +290,10 @@ pub mod test_util {
         instrument::{kind::InstrumentKind, Instrument},
     };
     use barter_integration::Side;
-    use chrono::Utc;
+    use chrono::{DateTime, Utc};
     use smol_str::ToSmolStr;
     use std::ops::Add;
+    use uuid::Uuid;
 
     /// Build a [`MarketEvent`] of [`DataKind::PublicTrade`](DataKind) with the provided [`Side`].
     pub fn market_event_trade(side: Side) -> MarketEvent<Instrument, DataKind> {
@@ -303,6 +331,22 @@ pub mod test_util {
         }
     }
 
+    /// Build a [`MarketEvent`] of [`DataKind::Candle`](DataKind) that closes at the provided time
+    /// & price.
+    pub fn market_event_candle_at(
+        time: DateTime<Utc>,
+        close: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_candle();
+        market.time_exchange = time;
+        market.time_received = time;
+        if let DataKind::Candle(candle) = &mut market.kind {
+            candle.close_time = time;
+            candle.close = close;
+        }
+        market
+    }
+
     /// Build a [`Signal`].
     pub fn signal() -> Signal {
         Signal {
@@ -311,12 +355,15 @@ pub mod test_util {
             instrument: Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
             signals: Default::default(),
             market_meta: Default::default(),
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
         }
     }
 
     /// Build an [`OrderEvent`] to buy 1.0 contract.
     pub fn order_event() -> OrderEvent {
         OrderEvent {
+            cid: Uuid::new_v4(),
             time: Utc::now(),
             exchange: ExchangeId::BinanceSpot,
             instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
@@ -324,12 +371,14 @@ pub mod test_util {
             decision: Decision::default(),
             quantity: 1.0,
             order_type: OrderType::default(),
+            strategy_id: StrategyId::default(),
         }
     }
 
     /// Build a [`FillEvent`] for a single bought contract.
     pub fn fill_event() -> FillEvent {
         FillEvent {
+            cid: None,
             time: Utc::now(),
             exchange: ExchangeId::BinanceSpot,
             instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
@@ -338,6 +387,7 @@ pub mod test_util {
             quantity: 1.0,
             fill_value_gross: 100.0,
             fees: Fees::default(),
+            strategy_id: StrategyId::default(),
         }
     }
 
@@ -347,6 +397,7 @@ pub mod test_util {
             position_id: "engine_id_trader_{}_{}_position".to_smolstr(),
             exchange: ExchangeId::BinanceSpot,
             instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            strategy_id: StrategyId::default(),
             meta: Default::default(),
             side: Side::Buy,
             quantity: 1.0,
@@ -362,6 +413,12 @@ pub mod test_util {
             current_value_gross: 100.0,
             unrealised_profit_loss: 0.0,
             realised_profit_loss: 0.0,
+            closed_enter_value_gross: 0.0,
+            funding_profit_loss: 0.0,
+            leverage: 1.0,
+            liquidated: false,
+            synthetic_exit: false,
+            close_reason: None,
         }
     }
 }
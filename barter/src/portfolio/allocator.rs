@@ -1,8 +1,18 @@
 use crate::{
-    portfolio::{position::Position, OrderEvent},
+    data::determine_market_close,
+    portfolio::{error::PortfolioError, position::Position, OrderEvent},
+    statistic::{
+        metric::volatility::Volatility,
+        summary::{pnl::PnLReturnSummary, PositionSummariser},
+    },
     strategy::{Decision, SignalStrength},
 };
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::{instrument::Instrument, market::MarketId};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::info;
 
 /// Allocates an appropriate [`OrderEvent`] quantity.
 pub trait OrderAllocator {
@@ -14,6 +24,25 @@ pub trait OrderAllocator {
         position: Option<&Position>,
         signal_strength: SignalStrength,
     );
+
+    /// Allocates the [`OrderEvent`] quantity used to scale into an open [`Position`] on a repeated
+    /// entry signal in it's direction, returning false if the [`Position`] should not be increased.
+    /// Default implementation never scales into an open [`Position`].
+    fn allocate_scale_in(&self, _: &mut OrderEvent, _: &Position, _: SignalStrength) -> bool {
+        false
+    }
+
+    /// Updates any internal allocation state (eg/ volatility estimates) using the latest input
+    /// [`MarketEvent`]. Default implementation is a no-op.
+    fn update_from_market(&mut self, _: &MarketEvent<Instrument, DataKind>) {}
+
+    /// Updates any internal allocation state (eg/ trade statistics) using the latest exited
+    /// [`Position`]. Default implementation is a no-op.
+    fn update_from_exit(&mut self, _: &Position) {}
+
+    /// Updates any equity dependent allocation state (eg/ drawdown from peak equity) using the
+    /// latest Portfolio equity. Default implementation is a no-op.
+    fn update_from_equity(&mut self, _: DateTime<Utc>, _: f64) {}
 }
 
 /// Default allocation manager that implements [`OrderAllocator`]. Order size is calculated by
@@ -30,19 +59,491 @@ impl OrderAllocator for DefaultAllocator {
         position: Option<&Position>,
         signal_strength: SignalStrength,
     ) {
-        // Calculate exact order_size, then round it to a more appropriate decimal place
-        let default_order_size = self.default_order_value / order.market_meta.close;
-        let default_order_size = (default_order_size * 10000.0).floor() / 10000.0;
+        allocate_order_value(order, position, self.default_order_value, signal_strength)
+    }
+}
+
+/// Configuration for constructing a [`VolatilityNormalisedAllocator`] via the new() constructor
+/// method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct VolatilityNormalisedConfig {
+    /// Order value at risk from a one standard deviation move in an instrument's returns. Each
+    /// entry order value is calculated as `risk_unit / volatility`.
+    pub risk_unit: f64,
+    /// Number of returns used in each instrument's rolling [`Volatility`] estimate. Must be
+    /// positive.
+    pub volatility_period: usize,
+    /// Order value used before an instrument's [`Volatility`] estimate is available.
+    pub default_order_value: f64,
+}
+
+/// Volatility normalised allocation manager that implements [`OrderAllocator`]. Order size is
+/// inversely proportional to each instrument's recent [`Volatility`], so every [`Position`]
+/// contributes a similar amount of risk regardless of the instrument traded.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct VolatilityNormalisedAllocator {
+    pub config: VolatilityNormalisedConfig,
+    pub volatilities: HashMap<MarketId, Volatility>,
+}
+
+impl OrderAllocator for VolatilityNormalisedAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        position: Option<&Position>,
+        signal_strength: SignalStrength,
+    ) {
+        let order_value = self
+            .volatility(&MarketId::new(order.exchange, &order.instrument))
+            .map(|volatility| self.config.risk_unit / volatility)
+            .unwrap_or(self.config.default_order_value);
+
+        allocate_order_value(order, position, order_value, signal_strength)
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        let Some(close) = determine_market_close(market) else {
+            return;
+        };
+
+        self.volatilities
+            .entry(MarketId::new(market.exchange, &market.instrument))
+            .or_insert_with(|| Volatility::new(self.config.volatility_period))
+            .update(close);
+    }
+}
+
+impl VolatilityNormalisedAllocator {
+    /// Constructs a new [`VolatilityNormalisedAllocator`] using the provided configuration.
+    ///
+    /// Errors if the `volatility_period` is zero, since the [`Volatility`] estimates would never
+    /// be available & every order would silently fall back to the `default_order_value`.
+    pub fn new(config: VolatilityNormalisedConfig) -> Result<Self, PortfolioError> {
+        if config.volatility_period == 0 {
+            return Err(PortfolioError::InvalidAllocatorConfig(
+                "VolatilityNormalisedAllocator volatility_period must be positive",
+            ));
+        }
+
+        Ok(Self {
+            config,
+            volatilities: HashMap::new(),
+        })
+    }
+
+    /// Returns the non-zero [`Volatility`] estimate of the provided [`MarketId`], if available.
+    pub fn volatility(&self, market_id: &MarketId) -> Option<f64> {
+        self.volatilities
+            .get(market_id)
+            .and_then(Volatility::value)
+            .filter(|volatility| *volatility > 0.0)
+    }
+}
+
+/// Configuration for constructing a [`VolatilityTargetAllocator`] via the new() constructor
+/// method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct VolatilityTargetAllocatorConfig {
+    /// Portfolio equity at the start of trading. Updated with the realised PnL of each exited
+    /// [`Position`] to determine the equity each order value is scaled from.
+    pub starting_equity: f64,
+    /// Annualised volatility each [`Position`] is sized to contribute, eg/ 0.15 for 15%.
+    pub target_volatility: f64,
+    /// Number of market event periods per year used to annualise each instrument's
+    /// [`Volatility`], eg/ 365.0 for daily candles.
+    pub periods_per_year: f64,
+    /// Number of returns used in each instrument's rolling [`Volatility`] estimate.
+    pub volatility_period: usize,
+    /// Maximum order value as a multiple of equity, capping the leverage taken on in calm
+    /// markets.
+    pub max_leverage: f64,
+    /// Order value used before an instrument's [`Volatility`] estimate is available.
+    pub default_order_value: f64,
+}
+
+/// Volatility targeting allocation manager that implements [`OrderAllocator`]. Each entry order
+/// value is `equity * target_volatility / annualised_volatility`, so every [`Position`] is
+/// expected to contribute the configured annualised volatility. Order value is capped at
+/// `equity * max_leverage`.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct VolatilityTargetAllocator {
+    pub config: VolatilityTargetAllocatorConfig,
+    pub equity: f64,
+    pub volatilities: HashMap<MarketId, Volatility>,
+}
+
+impl OrderAllocator for VolatilityTargetAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        position: Option<&Position>,
+        signal_strength: SignalStrength,
+    ) {
+        let order_value = self
+            .annualised_volatility(&MarketId::new(order.exchange, &order.instrument))
+            .map(|volatility| {
+                (self.equity * self.config.target_volatility / volatility)
+                    .min(self.equity * self.config.max_leverage)
+            })
+            .unwrap_or(self.config.default_order_value);
+
+        allocate_order_value(order, position, order_value, signal_strength)
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        let Some(close) = determine_market_close(market) else {
+            return;
+        };
+
+        self.volatilities
+            .entry(MarketId::new(market.exchange, &market.instrument))
+            .or_insert_with(|| Volatility::new(self.config.volatility_period))
+            .update(close);
+    }
+
+    fn update_from_exit(&mut self, position: &Position) {
+        self.equity += position.realised_profit_loss;
+    }
+}
+
+impl VolatilityTargetAllocator {
+    /// Constructs a new [`VolatilityTargetAllocator`] using the provided configuration.
+    ///
+    /// Errors if the `volatility_period` is zero, since the [`Volatility`] estimates would never
+    /// be available & every order would silently fall back to the `default_order_value`.
+    pub fn new(config: VolatilityTargetAllocatorConfig) -> Result<Self, PortfolioError> {
+        if config.volatility_period == 0 {
+            return Err(PortfolioError::InvalidAllocatorConfig(
+                "VolatilityTargetAllocator volatility_period must be positive",
+            ));
+        }
+
+        Ok(Self {
+            config,
+            equity: config.starting_equity,
+            volatilities: HashMap::new(),
+        })
+    }
+
+    /// Returns the non-zero annualised [`Volatility`] estimate of the provided [`MarketId`], if
+    /// available.
+    pub fn annualised_volatility(&self, market_id: &MarketId) -> Option<f64> {
+        self.volatilities
+            .get(market_id)
+            .and_then(Volatility::value)
+            .filter(|volatility| *volatility > 0.0)
+            .map(|volatility| volatility * self.config.periods_per_year.sqrt())
+    }
+}
+
+/// Configuration for constructing a [`KellyAllocator`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KellyConfig {
+    /// Portfolio equity at the start of trading. Updated with the realised PnL of each exited
+    /// [`Position`] to determine the equity the Kelly fraction is applied to.
+    pub starting_equity: f64,
+    /// Multiplier applied to the full Kelly fraction, eg/ 0.5 for half-Kelly.
+    pub fraction: f64,
+    /// Minimum number of exited [`Position`]s required before the Kelly fraction is used.
+    pub min_trades: u64,
+    /// Order value used until enough [`Position`]s have been exited.
+    pub default_order_value: f64,
+}
+
+/// Kelly criterion allocation manager that implements [`OrderAllocator`]. Order value is the
+/// current equity multiplied by the configured fraction of the Kelly bet `W - (1 - W) / R`, where
+/// `W` is the historical win rate and `R` is the ratio of the average win to the average loss
+/// return of exited [`Position`]s.
+///
+/// A non-positive Kelly bet (ie/ no historical edge) allocates a zero quantity entry.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct KellyAllocator {
+    pub config: KellyConfig,
+    pub equity: f64,
+    pub pnl_returns: PnLReturnSummary,
+}
+
+impl OrderAllocator for KellyAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        position: Option<&Position>,
+        signal_strength: SignalStrength,
+    ) {
+        let order_value = self
+            .kelly_fraction()
+            .map(|kelly| self.equity * kelly * self.config.fraction)
+            .unwrap_or(self.config.default_order_value);
+
+        allocate_order_value(order, position, order_value, signal_strength)
+    }
+
+    fn update_from_exit(&mut self, position: &Position) {
+        self.equity += position.realised_profit_loss;
+        self.pnl_returns.update(position);
+    }
+}
+
+impl KellyAllocator {
+    /// Constructs a new [`KellyAllocator`] using the provided configuration.
+    pub fn new(config: KellyConfig) -> Self {
+        Self {
+            config,
+            equity: config.starting_equity,
+            pnl_returns: PnLReturnSummary::new(),
+        }
+    }
+
+    /// Returns the full Kelly fraction `W - (1 - W) / R` clamped to a minimum of zero, or `None`
+    /// if fewer than the configured minimum number of [`Position`]s have been exited.
+    pub fn kelly_fraction(&self) -> Option<f64> {
+        let total = self.pnl_returns.total;
+        let losses = self.pnl_returns.losses;
+
+        if total.count == 0 || total.count < self.config.min_trades {
+            return None;
+        }
+
+        let wins = total.count - losses.count;
+        if wins == 0 {
+            return Some(0.0);
+        }
+
+        let win_rate = wins as f64 / total.count as f64;
+        let average_win = (total.sum - losses.sum) / wins as f64;
+        let average_loss = -losses.mean;
+
+        // Without any losses the win/loss ratio is unbounded, so the Kelly bet is the win rate
+        let kelly = if losses.count == 0 || average_loss <= 0.0 {
+            win_rate
+        } else {
+            win_rate - (1.0 - win_rate) / (average_win / average_loss)
+        };
+
+        Some(kelly.max(0.0))
+    }
+}
+
+/// Configuration for constructing a [`DcaAllocator`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct DcaConfig {
+    /// Order value of the initial entry & of every scale-in entry.
+    pub order_value: f64,
+    /// Maximum number of entries (including the initial entry) into a single [`Position`].
+    pub max_entries: u32,
+    /// Maximum total entry value of a single [`Position`].
+    pub max_exposure: f64,
+}
+
+/// Dollar-cost-averaging allocation manager that implements [`OrderAllocator`]. Every entry,
+/// including each repeated entry signal in the direction of an open [`Position`], is allocated
+/// the same fixed order value regardless of [`SignalStrength`]. Scale-in entries are refused once
+/// the [`Position`] has reached the configured maximum number of entries or total exposure.
+///
+/// The [`Position`] averages it's entry price across every scale-in fill (see
+/// [`Position::increase`]).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct DcaAllocator {
+    pub config: DcaConfig,
+}
+
+impl OrderAllocator for DcaAllocator {
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        position: Option<&Position>,
+        _: SignalStrength,
+    ) {
+        allocate_order_value(
+            order,
+            position,
+            self.config.order_value,
+            SignalStrength(1.0),
+        )
+    }
+
+    fn allocate_scale_in(
+        &self,
+        order: &mut OrderEvent,
+        position: &Position,
+        _: SignalStrength,
+    ) -> bool {
+        if position.meta.entries >= self.config.max_entries {
+            info!(
+                position_id = &*position.position_id,
+                entries = position.meta.entries,
+                max_entries = self.config.max_entries,
+                "refusing to scale into Position that has reached the maximum number of entries"
+            );
+            return false;
+        }
+
+        let exposure = position.enter_value_gross + self.config.order_value;
+        if exposure > self.config.max_exposure {
+            info!(
+                position_id = &*position.position_id,
+                exposure,
+                max_exposure = self.config.max_exposure,
+                "refusing to scale into Position beyond the maximum exposure"
+            );
+            return false;
+        }
+
+        allocate_order_value(
+            order,
+            Some(position),
+            self.config.order_value,
+            SignalStrength(1.0),
+        );
+        true
+    }
+}
+
+impl DcaAllocator {
+    /// Constructs a new [`DcaAllocator`] using the provided configuration.
+    pub fn new(config: DcaConfig) -> Self {
+        Self { config }
+    }
+}
+
+/// Configuration for constructing an [`EquityCurveAllocator`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct EquityCurveConfig {
+    /// Drawdown from peak equity, as a fraction (eg/ 0.2 for 20%), at which entry order values
+    /// are scaled down to the `min_factor`.
+    pub max_drawdown: f64,
+    /// Floor of the factor entry order values are scaled by, reached at the `max_drawdown`
+    /// (eg/ 0.25 to trade a quarter of the base order value).
+    pub min_factor: f64,
+}
+
+/// Equity curve overlay layered over an inner [`OrderAllocator`]. Entry order values allocated
+/// by the inner [`OrderAllocator`] are scaled down while the Portfolio is in a drawdown from it's
+/// peak equity, & scaled back up as it recovers. Exit [`OrderEvent`]s are never scaled, so an
+/// open [`Position`] can always be closed in full.
+///
+/// The factor decreases linearly from 1.0 at a new equity high to the configured `min_factor` at
+/// the configured `max_drawdown`, and does not decrease further for deeper drawdowns.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct EquityCurveAllocator<Allocator = DefaultAllocator> {
+    pub config: EquityCurveConfig,
+    pub allocator: Allocator,
+    /// Highest Portfolio equity observed.
+    peak_equity: Option<f64>,
+    /// Latest Portfolio equity observed.
+    equity: Option<f64>,
+}
+
+impl<Allocator> OrderAllocator for EquityCurveAllocator<Allocator>
+where
+    Allocator: OrderAllocator,
+{
+    fn allocate_order(
+        &self,
+        order: &mut OrderEvent,
+        position: Option<&Position>,
+        signal_strength: SignalStrength,
+    ) {
+        self.allocator
+            .allocate_order(order, position, signal_strength);
+        self.scale_entry(order);
+    }
+
+    fn allocate_scale_in(
+        &self,
+        order: &mut OrderEvent,
+        position: &Position,
+        signal_strength: SignalStrength,
+    ) -> bool {
+        let scale_in = self
+            .allocator
+            .allocate_scale_in(order, position, signal_strength);
+        self.scale_entry(order);
+        scale_in
+    }
 
-        match order.decision {
-            // Entry
-            Decision::Long => order.quantity = default_order_size * signal_strength.0,
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        self.allocator.update_from_market(market)
+    }
 
-            // Entry
-            Decision::Short => order.quantity = -default_order_size * signal_strength.0,
+    fn update_from_exit(&mut self, position: &Position) {
+        self.allocator.update_from_exit(position)
+    }
+
+    fn update_from_equity(&mut self, time: DateTime<Utc>, equity: f64) {
+        self.peak_equity = Some(self.peak_equity.map_or(equity, |peak| peak.max(equity)));
+        self.equity = Some(equity);
+        self.allocator.update_from_equity(time, equity)
+    }
+}
 
-            // Exit
-            _ => order.quantity = 0.0 - position.as_ref().unwrap().quantity,
+impl<Allocator> EquityCurveAllocator<Allocator> {
+    /// Constructs a new [`EquityCurveAllocator`] layered over the provided inner allocator.
+    pub fn new(config: EquityCurveConfig, allocator: Allocator) -> Self {
+        Self {
+            config,
+            allocator,
+            peak_equity: None,
+            equity: None,
+        }
+    }
+
+    /// Returns the current drawdown from peak equity as a fraction (eg/ 0.1 for 10%), or 0.0 if
+    /// no equity has been observed.
+    pub fn drawdown(&self) -> f64 {
+        match (self.peak_equity, self.equity) {
+            (Some(peak), Some(equity)) if peak > 0.0 && equity < peak => (peak - equity) / peak,
+            _ => 0.0,
+        }
+    }
+
+    /// Returns the factor entry order values are currently scaled by, being exactly 1.0 at a new
+    /// equity high.
+    pub fn factor(&self) -> f64 {
+        let drawdown = self.drawdown();
+        if drawdown <= 0.0 {
+            return 1.0;
+        }
+        if self.config.max_drawdown <= 0.0 {
+            return self.config.min_factor;
+        }
+
+        let progress = (drawdown / self.config.max_drawdown).min(1.0);
+        1.0 - (1.0 - self.config.min_factor) * progress
+    }
+
+    /// Scales the quantity of an entry [`OrderEvent`] by the current drawdown [`Self::factor`].
+    fn scale_entry(&self, order: &mut OrderEvent) {
+        if order.decision.is_entry() {
+            order.quantity *= self.factor();
+        }
+    }
+}
+
+/// Allocates the [`OrderEvent`] quantity using the provided order value if it is an entry, or
+/// the quantity required to close the existing [`Position`] if it is an exit.
+fn allocate_order_value(
+    order: &mut OrderEvent,
+    position: Option<&Position>,
+    order_value: f64,
+    signal_strength: SignalStrength,
+) {
+    // Calculate exact order_size, then round it to a more appropriate decimal place
+    let order_size = order_value / order.market_meta.close;
+    let order_size = (order_size * 10000.0).floor() / 10000.0;
+
+    match order.decision {
+        // Entry
+        Decision::EnterLong => order.quantity = order_size * signal_strength.0,
+
+        // Entry
+        Decision::EnterShort => order.quantity = -order_size * signal_strength.0,
+
+        // Exit, only valid against an open Position of the side it closes
+        _ => {
+            order.quantity = position
+                .filter(|position| order.decision.closes(position.side))
+                .map_or(0.0, |position| 0.0 - position.quantity)
         }
     }
 }
@@ -50,7 +551,202 @@ impl OrderAllocator for DefaultAllocator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_util::{order_event, position};
+    use crate::{
+        execution::Fees,
+        portfolio::position::PositionEnterer,
+        test_util::{fill_event, market_event_candle, order_event, position},
+    };
+    use barter_instrument::{exchange::ExchangeId, instrument::kind::InstrumentKind};
+    use barter_integration::Side;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    #[test]
+    fn equity_curve_allocator_scales_entries_by_drawdown_from_peak_equity() {
+        let mut allocator = EquityCurveAllocator::new(
+            EquityCurveConfig {
+                max_drawdown: 0.2,
+                min_factor: 0.5,
+            },
+            DefaultAllocator {
+                default_order_value: 1000.0,
+            },
+        );
+        let allocate = |allocator: &EquityCurveAllocator, decision, position: Option<&Position>| {
+            let mut order = order_event();
+            order.market_meta.close = 10.0;
+            order.decision = decision;
+            allocator.allocate_order(&mut order, position, SignalStrength(1.0));
+            order.quantity
+        };
+        let time = Utc::now();
+
+        // New equity high trades the full base order value
+        allocator.update_from_equity(time, 10_000.0);
+        allocator.update_from_equity(time, 12_000.0);
+        assert_eq!(allocator.factor(), 1.0);
+        assert_eq!(allocate(&allocator, Decision::EnterLong, None), 100.0);
+
+        // 10% drawdown is half way to the max drawdown: factor = 1.0 - (1.0 - 0.5) * 0.5 = 0.75
+        allocator.update_from_equity(time, 10_800.0);
+        assert!((allocator.drawdown() - 0.1).abs() < 1e-10);
+        assert!((allocate(&allocator, Decision::EnterLong, None) - 75.0).abs() < 1e-10);
+        assert!((allocate(&allocator, Decision::EnterShort, None) + 75.0).abs() < 1e-10);
+
+        // Exits are never scaled
+        let mut position = position();
+        position.side = Side::Buy;
+        position.quantity = 100.0;
+        assert_eq!(
+            allocate(&allocator, Decision::CloseLong, Some(&position)),
+            -100.0
+        );
+
+        // Drawdowns beyond the max drawdown are floored at the min factor
+        allocator.update_from_equity(time, 6_000.0);
+        assert!((allocate(&allocator, Decision::EnterLong, None) - 50.0).abs() < 1e-10);
+
+        // Recovering to a new equity high restores the full base order value
+        allocator.update_from_equity(time, 12_500.0);
+        assert_eq!(allocator.factor(), 1.0);
+        assert_eq!(allocate(&allocator, Decision::EnterLong, None), 100.0);
+    }
+
+    fn kelly_allocator(min_trades: u64) -> KellyAllocator {
+        KellyAllocator::new(KellyConfig {
+            starting_equity: 10_000.0,
+            fraction: 0.5,
+            min_trades,
+            default_order_value: 100.0,
+        })
+    }
+
+    #[test]
+    fn kelly_allocator_sizes_entry_with_fractional_kelly() {
+        let mut allocator = kelly_allocator(5);
+
+        // 6 wins of +10% & 4 losses of -5%: W = 0.6, R = 2.0, Kelly = 0.6 - 0.4 / 2.0 = 0.4
+        for pnl_return in [0.1, -0.05, 0.1, 0.1, -0.05, 0.1, -0.05, 0.1, -0.05, 0.1] {
+            allocator.pnl_returns.total.update(pnl_return);
+            if pnl_return < 0.0 {
+                allocator.pnl_returns.losses.update(pnl_return);
+            }
+        }
+
+        let kelly = allocator.kelly_fraction().unwrap();
+        assert!((kelly - 0.4).abs() < 1e-10, "kelly: {kelly}");
+
+        let mut order = order_event();
+        order.market_meta.close = 10.0;
+        order.decision = Decision::EnterLong;
+        allocator.allocate_order(&mut order, None, SignalStrength(1.0));
+
+        // Order value = 10,000 equity * 0.4 Kelly * 0.5 fraction = 2,000
+        assert!(
+            (order.quantity - 200.0).abs() < 1e-6,
+            "quantity: {}",
+            order.quantity
+        );
+    }
+
+    #[test]
+    fn kelly_allocator_falls_back_to_default_order_value_with_insufficient_trades() {
+        let mut allocator = kelly_allocator(5);
+
+        let mut exited = position();
+        exited.realised_profit_loss = 50.0;
+        allocator.update_from_exit(&exited);
+        assert_eq!(allocator.equity, 10_050.0);
+        assert_eq!(allocator.kelly_fraction(), None);
+
+        let mut order = order_event();
+        order.market_meta.close = 10.0;
+        order.decision = Decision::EnterShort;
+        allocator.allocate_order(&mut order, None, SignalStrength(1.0));
+
+        assert_eq!(order.quantity, -10.0);
+    }
+
+    #[test]
+    fn kelly_allocator_without_edge_allocates_zero_quantity() {
+        let mut allocator = kelly_allocator(0);
+
+        // W = 0.5, R = 0.5, Kelly = 0.5 - 0.5 / 0.5 = -0.5 -> clamped to zero
+        for pnl_return in [0.05, -0.1] {
+            allocator.pnl_returns.total.update(pnl_return);
+            if pnl_return < 0.0 {
+                allocator.pnl_returns.losses.update(pnl_return);
+            }
+        }
+
+        assert_eq!(allocator.kelly_fraction(), Some(0.0));
+    }
+
+    #[test]
+    fn dca_allocator_scales_in_until_max_entries() {
+        let allocator = DcaAllocator::new(DcaConfig {
+            order_value: 100.0,
+            max_entries: 4,
+            max_exposure: 1000.0,
+        });
+
+        let entry_order = |close: f64| {
+            let mut order = order_event();
+            order.decision = Decision::EnterLong;
+            order.market_meta.close = close;
+            order
+        };
+        let entry_fill = |order: &OrderEvent| {
+            let mut fill = fill_event();
+            fill.decision = order.decision;
+            fill.quantity = order.quantity;
+            fill.fill_value_gross = order.quantity * order.market_meta.close;
+            fill.fees = Fees::default();
+            fill
+        };
+
+        // Initial entry of 100.0 at 10.0
+        let mut order = entry_order(10.0);
+        allocator.allocate_order(&mut order, None, SignalStrength(0.5));
+        assert_eq!(order.quantity, 10.0);
+        let mut position = Position::enter(Uuid::new_v4(), &entry_fill(&order)).unwrap();
+
+        // Three repeated Long signals each scale in a fixed 100.0 order value
+        for close in [5.0, 4.0, 2.0] {
+            let mut order = entry_order(close);
+            assert!(allocator.allocate_scale_in(&mut order, &position, SignalStrength(0.5)));
+            assert_eq!(order.quantity, 100.0 / close);
+            position.increase(&entry_fill(&order));
+        }
+
+        // Entry price averaged over 400.0 value & 10 + 20 + 25 + 50 quantity
+        assert_eq!(position.meta.entries, 4);
+        assert_eq!(position.quantity, 105.0);
+        assert!((position.enter_avg_price_gross - 400.0 / 105.0).abs() < 1e-10);
+
+        // Fourth scale in is refused at the maximum number of entries
+        let mut order = entry_order(1.0);
+        assert!(!allocator.allocate_scale_in(&mut order, &position, SignalStrength(1.0)));
+    }
+
+    #[test]
+    fn dca_allocator_refuses_scale_in_beyond_max_exposure() {
+        let allocator = DcaAllocator::new(DcaConfig {
+            order_value: 100.0,
+            max_entries: 10,
+            max_exposure: 250.0,
+        });
+
+        let mut position = position();
+        position.enter_value_gross = 200.0;
+
+        let mut order = order_event();
+        order.decision = Decision::EnterLong;
+        assert!(!allocator.allocate_scale_in(&mut order, &position, SignalStrength(1.0)));
+
+        position.enter_value_gross = 150.0;
+        assert!(allocator.allocate_scale_in(&mut order, &position, SignalStrength(1.0)));
+    }
 
     #[test]
     fn should_allocate_order_to_exit_open_long_position() {
@@ -88,6 +784,7 @@ mod tests {
         input_order.decision = Decision::CloseShort;
 
         let mut input_position = position();
+        input_position.side = Side::Sell;
         input_position.quantity = -100.0;
 
         let input_signal_strength = SignalStrength(0.0);
@@ -114,7 +811,7 @@ mod tests {
         let order_close = 10.0;
         let mut input_order = order_event();
         input_order.market_meta.close = order_close;
-        input_order.decision = Decision::Long;
+        input_order.decision = Decision::EnterLong;
 
         let input_signal_strength = SignalStrength(1.0);
 
@@ -136,7 +833,7 @@ mod tests {
         let order_close = 226.753403;
         let mut input_order = order_event();
         input_order.market_meta.close = order_close;
-        input_order.decision = Decision::Long;
+        input_order.decision = Decision::EnterLong;
 
         let input_signal_strength = SignalStrength(1.0);
 
@@ -160,7 +857,7 @@ mod tests {
         let order_close = 10.0;
         let mut input_order = order_event();
         input_order.market_meta.close = order_close;
-        input_order.decision = Decision::Short;
+        input_order.decision = Decision::EnterShort;
 
         let input_signal_strength = SignalStrength(1.0);
 
@@ -182,7 +879,7 @@ mod tests {
         let order_close = 226.753403;
         let mut input_order = order_event();
         input_order.market_meta.close = order_close;
-        input_order.decision = Decision::Short;
+        input_order.decision = Decision::EnterShort;
 
         let input_signal_strength = SignalStrength(1.0);
 
@@ -195,4 +892,188 @@ mod tests {
         assert_ne!(actual_result, 0.0);
         assert_eq!(actual_result, expected_result)
     }
+
+    #[test]
+    fn should_allocate_smaller_order_to_more_volatile_instrument() {
+        let mut allocator = VolatilityNormalisedAllocator::new(VolatilityNormalisedConfig {
+            risk_unit: 10.0,
+            volatility_period: 4,
+            default_order_value: 100.0,
+        })
+        .unwrap();
+
+        let calm = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        let volatile = Instrument::from(("doge", "usdt", InstrumentKind::Spot));
+
+        // Calm instrument returns alternate +/-1%, volatile instrument returns alternate +/-4%
+        let calm_closes = [100.0, 101.0, 99.99, 100.9899, 99.979801];
+        let volatile_closes = [100.0, 104.0, 99.84, 103.8336, 99.680256];
+
+        for (instrument, closes) in [(&calm, calm_closes), (&volatile, volatile_closes)] {
+            for close in closes {
+                let mut market = market_event_candle();
+                market.instrument = instrument.clone();
+                if let DataKind::Candle(candle) = &mut market.kind {
+                    candle.close = close;
+                }
+                allocator.update_from_market(&market);
+            }
+        }
+
+        let allocate = |instrument: &Instrument| {
+            let mut order = order_event();
+            order.instrument = instrument.clone();
+            order.market_meta.close = 100.0;
+            order.decision = Decision::EnterLong;
+            allocator.allocate_order(&mut order, None, SignalStrength(1.0));
+            order.quantity
+        };
+
+        let calm_quantity = allocate(&calm);
+        let volatile_quantity = allocate(&volatile);
+
+        // Equal risk contribution: quantity * close * volatility == risk_unit
+        let calm_volatility = allocator
+            .volatility(&MarketId::new(ExchangeId::BinanceSpot, &calm))
+            .unwrap();
+        let volatile_volatility = allocator
+            .volatility(&MarketId::new(ExchangeId::BinanceSpot, &volatile))
+            .unwrap();
+
+        assert!(volatile_quantity < calm_quantity);
+        assert!((calm_quantity * 100.0 * calm_volatility - 10.0).abs() < 1e-2);
+        assert!((volatile_quantity * 100.0 * volatile_volatility - 10.0).abs() < 1e-2);
+    }
+
+    #[test]
+    fn should_allocate_default_order_value_before_volatility_is_estimated() {
+        let allocator = VolatilityNormalisedAllocator::new(VolatilityNormalisedConfig {
+            risk_unit: 10.0,
+            volatility_period: 4,
+            default_order_value: 100.0,
+        })
+        .unwrap();
+
+        let mut input_order = order_event();
+        input_order.market_meta.close = 10.0;
+        input_order.decision = Decision::EnterLong;
+
+        allocator.allocate_order(&mut input_order, None, SignalStrength(1.0));
+
+        assert_eq!(input_order.quantity, 10.0);
+    }
+
+    #[test]
+    fn volatility_normalised_allocator_rejects_zero_volatility_period() {
+        let allocator = VolatilityNormalisedAllocator::new(VolatilityNormalisedConfig {
+            risk_unit: 10.0,
+            volatility_period: 0,
+            default_order_value: 100.0,
+        });
+
+        assert!(matches!(
+            allocator,
+            Err(PortfolioError::InvalidAllocatorConfig(_))
+        ));
+    }
+
+    fn volatility_target_allocator(max_leverage: f64) -> VolatilityTargetAllocator {
+        VolatilityTargetAllocator::new(VolatilityTargetAllocatorConfig {
+            starting_equity: 10_000.0,
+            target_volatility: 0.15,
+            periods_per_year: 252.0,
+            volatility_period: 4,
+            max_leverage,
+            default_order_value: 100.0,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn volatility_target_allocator_rejects_zero_volatility_period() {
+        let allocator = VolatilityTargetAllocator::new(VolatilityTargetAllocatorConfig {
+            starting_equity: 10_000.0,
+            target_volatility: 0.15,
+            periods_per_year: 252.0,
+            volatility_period: 0,
+            max_leverage: 1.0,
+            default_order_value: 100.0,
+        });
+
+        assert!(matches!(
+            allocator,
+            Err(PortfolioError::InvalidAllocatorConfig(_))
+        ));
+    }
+
+    /// Updates the allocator with closes whose returns alternate between +/- the provided return.
+    fn update_with_alternating_returns(
+        allocator: &mut VolatilityTargetAllocator,
+        instrument: &Instrument,
+        alternating_return: f64,
+    ) {
+        let mut close = 100.0;
+        for period in 0..5 {
+            if period > 0 {
+                close *= match period % 2 {
+                    1 => 1.0 + alternating_return,
+                    _ => 1.0 - alternating_return,
+                };
+            }
+
+            let mut market = market_event_candle();
+            market.instrument = instrument.clone();
+            if let DataKind::Candle(candle) = &mut market.kind {
+                candle.close = close;
+            }
+            allocator.update_from_market(&market);
+        }
+    }
+
+    fn allocate_long(allocator: &VolatilityTargetAllocator, instrument: &Instrument) -> f64 {
+        let mut order = order_event();
+        order.instrument = instrument.clone();
+        order.market_meta.close = 100.0;
+        order.decision = Decision::EnterLong;
+        allocator.allocate_order(&mut order, None, SignalStrength(1.0));
+        order.quantity
+    }
+
+    #[test]
+    fn volatility_target_allocator_halves_order_when_volatility_doubles() {
+        let mut allocator = volatility_target_allocator(2.0);
+
+        let calm = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        let volatile = Instrument::from(("doge", "usdt", InstrumentKind::Spot));
+        update_with_alternating_returns(&mut allocator, &calm, 0.01);
+        update_with_alternating_returns(&mut allocator, &volatile, 0.02);
+
+        let calm_quantity = allocate_long(&allocator, &calm);
+        let volatile_quantity = allocate_long(&allocator, &volatile);
+
+        // Order value = 10,000 * 0.15 / (0.01 * sqrt(252)) = ~9,449
+        assert!(
+            (calm_quantity - 94.49).abs() < 1e-2,
+            "quantity: {calm_quantity}"
+        );
+        assert!(
+            (calm_quantity / volatile_quantity - 2.0).abs() < 1e-3,
+            "calm: {calm_quantity}, volatile: {volatile_quantity}"
+        );
+    }
+
+    #[test]
+    fn volatility_target_allocator_caps_order_value_at_max_leverage() {
+        let mut allocator = volatility_target_allocator(0.5);
+
+        let instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+
+        // Cold start falls back to the default order value
+        assert_eq!(allocate_long(&allocator, &instrument), 1.0);
+
+        update_with_alternating_returns(&mut allocator, &instrument, 0.01);
+
+        // Uncapped order value of ~9,449 is capped at 10,000 * 0.5
+        assert_eq!(allocate_long(&allocator, &instrument), 50.0);
+    }
 }
@@ -0,0 +1,242 @@
+use crate::data::determine_market_close;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::{instrument::Instrument, market::MarketId};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Configuration for constructing a [`CorrelationTracker`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CorrelationConfig {
+    /// Decay factor (lambda) of the exponentially weighted moving averages, between 0 & 1. The
+    /// higher the lambda, the slower older returns decay (eg/ RiskMetrics uses 0.94).
+    pub lambda: f64,
+    /// Minimum number of returns (or overlapping returns for a pair of markets) required before
+    /// a volatility (or correlation) is reported.
+    pub min_observations: usize,
+}
+
+impl Default for CorrelationConfig {
+    fn default() -> Self {
+        Self {
+            lambda: 0.94,
+            min_observations: 2,
+        }
+    }
+}
+
+/// Exponentially weighted moving average (EWMA) volatility of each market, and the pairwise EWMA
+/// correlation between markets, estimated from the log returns of consecutive market closes.
+///
+/// Returns of two markets are paired by their exchange timestamp, so a correlation is only
+/// estimated between markets sampled on the same timestamps (eg/ candles of the same interval).
+/// Pairs of markets without enough overlapping returns report a correlation of `None`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct CorrelationTracker {
+    pub config: CorrelationConfig,
+    markets: HashMap<MarketId, MarketReturns>,
+    /// EWMA co-moments of each pair of markets, keyed by the (lesser, greater) [`MarketId`]s.
+    pairs: HashMap<(MarketId, MarketId), PairMoments>,
+}
+
+/// Latest close, return & EWMA variance of a single market.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+struct MarketReturns {
+    prev_close: Option<f64>,
+    latest_return: Option<(DateTime<Utc>, f64)>,
+    variance: f64,
+    observations: usize,
+}
+
+/// EWMA covariance & variances of a pair of markets, calculated over their overlapping returns.
+#[derive(Copy, Clone, PartialEq, Debug, Default)]
+struct PairMoments {
+    covariance: f64,
+    variance_a: f64,
+    variance_b: f64,
+    observations: usize,
+}
+
+impl CorrelationTracker {
+    /// Constructs a new [`CorrelationTracker`] using the provided configuration.
+    pub fn new(config: CorrelationConfig) -> Self {
+        Self {
+            config,
+            markets: HashMap::new(),
+            pairs: HashMap::new(),
+        }
+    }
+
+    /// Updates the EWMA volatility of the [`MarketEvent`] market using the return since it's
+    /// previous close, as well as it's correlation with every market that has a return at the
+    /// same exchange timestamp.
+    pub fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        let Some(close) = determine_market_close(market).filter(|close| *close > 0.0) else {
+            return;
+        };
+
+        let market_id = MarketId::new(market.exchange, &market.instrument);
+        let lambda = self.config.lambda;
+
+        let state = self.markets.entry(market_id.clone()).or_default();
+        let Some(prev_close) = state.prev_close.replace(close) else {
+            return;
+        };
+
+        let log_return = (close / prev_close).ln();
+        state.variance = ewma(lambda, state.variance, log_return * log_return);
+        state.observations = state.observations.saturating_add(1);
+        state.latest_return = Some((market.time_exchange, log_return));
+
+        // Update the co-moments with every other market that has a return at the same timestamp
+        for (other_id, other) in &self.markets {
+            let Some((time, other_return)) = other.latest_return else {
+                continue;
+            };
+            if *other_id == market_id || time != market.time_exchange {
+                continue;
+            }
+
+            let (key, return_a, return_b) = match market_id < *other_id {
+                true => (
+                    (market_id.clone(), other_id.clone()),
+                    log_return,
+                    other_return,
+                ),
+                false => (
+                    (other_id.clone(), market_id.clone()),
+                    other_return,
+                    log_return,
+                ),
+            };
+
+            let pair = self.pairs.entry(key).or_default();
+            pair.covariance = ewma(lambda, pair.covariance, return_a * return_b);
+            pair.variance_a = ewma(lambda, pair.variance_a, return_a * return_a);
+            pair.variance_b = ewma(lambda, pair.variance_b, return_b * return_b);
+            pair.observations = pair.observations.saturating_add(1);
+        }
+    }
+
+    /// Returns the EWMA volatility (standard deviation of log returns per period) of the
+    /// provided market, or `None` if it has fewer than the minimum number of returns.
+    pub fn volatility(&self, market: &MarketId) -> Option<f64> {
+        self.markets
+            .get(market)
+            .filter(|state| state.observations >= self.config.min_observations.max(1))
+            .map(|state| state.variance.sqrt())
+    }
+
+    /// Returns the EWMA correlation between the returns of the provided markets, or `None` if
+    /// they have fewer than the minimum number of overlapping returns, or either has not moved.
+    pub fn correlation(&self, a: &MarketId, b: &MarketId) -> Option<f64> {
+        if a == b {
+            return self.volatility(a).map(|_| 1.0);
+        }
+
+        let key = match a < b {
+            true => (a.clone(), b.clone()),
+            false => (b.clone(), a.clone()),
+        };
+
+        self.pairs
+            .get(&key)
+            .filter(|pair| pair.observations >= self.config.min_observations.max(1))
+            .and_then(|pair| {
+                let denominator = (pair.variance_a * pair.variance_b).sqrt();
+                (denominator > 0.0).then(|| (pair.covariance / denominator).clamp(-1.0, 1.0))
+            })
+    }
+}
+
+/// Blends the next observation into an exponentially weighted moving average.
+fn ewma(lambda: f64, average: f64, observation: f64) -> f64 {
+    lambda * average + (1.0 - lambda) * observation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_trade;
+    use barter_data::subscription::trade::PublicTrade;
+    use barter_instrument::{exchange::ExchangeId, instrument::kind::InstrumentKind};
+    use barter_integration::Side;
+    use chrono::Duration;
+
+    fn trade(base: &str, time: DateTime<Utc>, price: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_trade(Side::Buy);
+        market.time_exchange = time;
+        market.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+        market.kind = DataKind::Trade(PublicTrade {
+            id: "trade_id".to_string(),
+            price,
+            amount: 1.0,
+            side: Side::Buy,
+        });
+        market
+    }
+
+    fn market_id(base: &str) -> MarketId {
+        MarketId::new(
+            ExchangeId::BinanceSpot,
+            &Instrument::from((base, "usdt", InstrumentKind::Spot)),
+        )
+    }
+
+    #[test]
+    fn correlation_of_perfectly_correlated_series_converges_to_one() {
+        let mut tracker = CorrelationTracker::new(CorrelationConfig {
+            lambda: 0.9,
+            min_observations: 3,
+        });
+        let (btc, eth) = (market_id("btc"), market_id("eth"));
+
+        // Eth price is always a tenth of the btc price, so their returns are identical
+        let start = Utc::now();
+        let prices = [100.0, 102.0, 99.0, 104.0, 101.0, 107.0, 103.0, 108.0];
+        for (index, price) in prices.into_iter().enumerate() {
+            let time = start + Duration::minutes(index as i64);
+            tracker.update_from_market(&trade("btc", time, price));
+            tracker.update_from_market(&trade("eth", time, price / 10.0));
+
+            // Insufficient overlapping history until the third pair of returns
+            let correlation = tracker.correlation(&btc, &eth);
+            assert_eq!(correlation.is_some(), index >= 3, "index: {index}");
+        }
+
+        let correlation = tracker.correlation(&eth, &btc).unwrap();
+        assert!(
+            (correlation - 1.0).abs() < 1e-9,
+            "correlation: {correlation}"
+        );
+
+        let (btc_volatility, eth_volatility) = (
+            tracker.volatility(&btc).unwrap(),
+            tracker.volatility(&eth).unwrap(),
+        );
+        assert!(btc_volatility > 0.0);
+        assert!((btc_volatility - eth_volatility).abs() < 1e-12);
+    }
+
+    #[test]
+    fn correlation_of_markets_without_overlapping_returns_is_none() {
+        let mut tracker = CorrelationTracker::new(CorrelationConfig::default());
+
+        let start = Utc::now();
+        for index in 0..10 {
+            let price = 100.0 + index as f64;
+            tracker.update_from_market(&trade("btc", start + Duration::minutes(index), price));
+            tracker.update_from_market(&trade(
+                "eth",
+                start + Duration::minutes(index) + Duration::seconds(30),
+                price,
+            ));
+        }
+
+        assert!(tracker.volatility(&market_id("btc")).is_some());
+        assert_eq!(
+            tracker.correlation(&market_id("btc"), &market_id("eth")),
+            None
+        );
+    }
+}
@@ -1,4 +1,5 @@
 use crate::portfolio::repository::error::RepositoryError;
+use barter_instrument::asset::symbol::Symbol;
 use thiserror::Error;
 
 /// All errors generated in the barter::portfolio module.
@@ -19,6 +20,12 @@ pub enum PortfolioError {
     #[error("Cannot generate PositionExit from Position that has not been exited")]
     PositionExit,
 
+    #[error("Invalid allocator configuration: {0}")]
+    InvalidAllocatorConfig(&'static str),
+
+    #[error("Missing FX rate to convert {currency} into the base currency {base}")]
+    MissingFxRate { currency: Symbol, base: Symbol },
+
     #[error("Failed to interact with repository")]
     RepositoryInteraction(#[from] RepositoryError),
 }
@@ -0,0 +1,116 @@
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::funding::FundingRate,
+};
+use barter_instrument::{
+    instrument::{kind::InstrumentKind, Instrument},
+    market::MarketId,
+};
+use std::collections::HashMap;
+
+/// Accrues the periodic funding payments of perpetual futures
+/// [`Position`](super::position::Position)s from a stream (or historical series) of
+/// [`FundingRate`] market events.
+///
+/// The latest [`FundingRate`] of each perpetual market is held until it's funding time passes,
+/// at which point it is settled against any [`Position`](super::position::Position) open at that
+/// funding time. A [`FundingRate`] event timestamped at it's own funding time (eg/ a historical
+/// funding series) is settled immediately.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct FundingAccrual {
+    pending: HashMap<MarketId, FundingRate>,
+}
+
+impl FundingAccrual {
+    /// Constructs a new [`FundingAccrual`] without any pending [`FundingRate`]s.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the [`FundingAccrual`] with the next [`MarketEvent`], returning the
+    /// [`FundingRate`]s of the [`MarketEvent`] market whose funding time has passed, in order.
+    pub fn update_from_market(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Vec<FundingRate> {
+        if market.instrument.kind != InstrumentKind::Perpetual {
+            return Vec::new();
+        }
+
+        let market_id = MarketId::new(market.exchange, &market.instrument);
+
+        // Settle the pending FundingRate before it is replaced by the next funding interval
+        let mut settled = Vec::new();
+        settled.extend(self.settle(&market_id, market));
+
+        if let DataKind::FundingRate(funding) = &market.kind {
+            self.pending.insert(market_id.clone(), *funding);
+            settled.extend(self.settle(&market_id, market));
+        }
+
+        settled
+    }
+
+    /// Removes & returns the pending [`FundingRate`] of the market if it's funding time has
+    /// passed.
+    fn settle(
+        &mut self,
+        market_id: &MarketId,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Option<FundingRate> {
+        self.pending
+            .get(market_id)
+            .is_some_and(|funding| funding.next_funding_time <= market.time_exchange)
+            .then(|| self.pending.remove(market_id))
+            .flatten()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_trade;
+    use barter_integration::Side;
+    use chrono::{DateTime, Duration, Utc};
+
+    fn perpetual_event(time: DateTime<Utc>, kind: DataKind) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_trade(Side::Buy);
+        market.time_exchange = time;
+        market.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Perpetual));
+        market.kind = kind;
+        market
+    }
+
+    #[test]
+    fn funding_accrual_settles_latest_rate_once_funding_time_passes() {
+        let mut accrual = FundingAccrual::new();
+        let funding_time = Utc::now();
+        let funding = |rate| FundingRate {
+            rate,
+            next_funding_time: funding_time,
+            mark_price: 100.0,
+        };
+
+        // Predicted FundingRates before the funding time are pending, with the latest settled
+        let before = funding_time - Duration::minutes(2);
+        let settled = accrual.update_from_market(&perpetual_event(
+            before,
+            DataKind::FundingRate(funding(0.0002)),
+        ));
+        assert!(settled.is_empty());
+        let settled = accrual.update_from_market(&perpetual_event(
+            before + Duration::minutes(1),
+            DataKind::FundingRate(funding(0.0001)),
+        ));
+        assert!(settled.is_empty());
+
+        let trade = market_event_trade(Side::Buy).kind;
+        let settled = accrual.update_from_market(&perpetual_event(funding_time, trade.clone()));
+        assert_eq!(settled, vec![funding(0.0001)]);
+
+        // Already settled FundingRate is not settled again
+        let settled = accrual
+            .update_from_market(&perpetual_event(funding_time + Duration::minutes(1), trade));
+        assert!(settled.is_empty());
+    }
+}
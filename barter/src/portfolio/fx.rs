@@ -0,0 +1,133 @@
+use crate::{data::determine_market_close, portfolio::error::PortfolioError};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::{asset::symbol::Symbol, instrument::Instrument};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Exchange rates used to convert values denominated in a quote currency (eg/ "eur") into the
+/// base currency of a Portfolio (eg/ "usd").
+///
+/// Rates are provided up front via [`FxRates::with_rate`], or updated from any [`MarketEvent`]
+/// for a currency pair quoted against (or based in) the base currency, such as "eur/usd".
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct FxRates {
+    /// Currency the Portfolio [`Balance`](super::Balance) is denominated in.
+    pub base: Symbol,
+    /// Number of base currency units per unit of each currency.
+    rates: HashMap<Symbol, f64>,
+}
+
+impl FxRates {
+    /// Constructs a new [`FxRates`] converting into the provided base currency, without any
+    /// known exchange rates.
+    pub fn new<S>(base: S) -> Self
+    where
+        S: Into<Symbol>,
+    {
+        Self {
+            base: base.into(),
+            rates: HashMap::new(),
+        }
+    }
+
+    /// Adds the exchange rate of the provided currency, being the number of base currency units
+    /// per unit of the currency.
+    pub fn with_rate<S>(mut self, currency: S, rate: f64) -> Self
+    where
+        S: Into<Symbol>,
+    {
+        self.set_rate(currency.into(), rate);
+        self
+    }
+
+    /// Upserts the exchange rate of the provided currency, being the number of base currency
+    /// units per unit of the currency.
+    pub fn set_rate(&mut self, currency: Symbol, rate: f64) {
+        self.rates.insert(currency, rate);
+    }
+
+    /// Returns the number of base currency units per unit of the provided currency. The base
+    /// currency always converts 1:1.
+    pub fn rate(&self, currency: &Symbol) -> Result<f64, PortfolioError> {
+        if *currency == self.base {
+            return Ok(1.0);
+        }
+
+        self.rates
+            .get(currency)
+            .copied()
+            .ok_or_else(|| PortfolioError::MissingFxRate {
+                currency: currency.clone(),
+                base: self.base.clone(),
+            })
+    }
+
+    /// Converts a value denominated in the provided currency into the base currency.
+    pub fn convert(&self, value: f64, currency: &Symbol) -> Result<f64, PortfolioError> {
+        self.rate(currency).map(|rate| value * rate)
+    }
+
+    /// Updates the exchange rate of a currency pair quoted against, or based in, the base
+    /// currency using the latest [`MarketEvent`] close. Returns true if an exchange rate changed.
+    pub fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) -> bool {
+        let (base, quote) = (&market.instrument.base, &market.instrument.quote);
+        if base != &self.base && quote != &self.base {
+            return false;
+        }
+
+        let Some(close) = determine_market_close(market).filter(|close| *close > 0.0) else {
+            return false;
+        };
+
+        // eg/ "eur/usd" with a "usd" base currency, or it's inverse "usd/eur"
+        if quote == &self.base {
+            self.set_rate(base.clone(), close);
+        } else {
+            self.set_rate(quote.clone(), 1.0 / close);
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle;
+    use barter_instrument::instrument::kind::InstrumentKind;
+
+    fn fx_candle(base: &str, quote: &str, close: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_candle();
+        market.instrument = Instrument::from((base, quote, InstrumentKind::Spot));
+        if let DataKind::Candle(candle) = &mut market.kind {
+            candle.close = close;
+        }
+        market
+    }
+
+    #[test]
+    fn fx_rates_convert_into_base_currency() {
+        let rates = FxRates::new("usd").with_rate("eur", 1.1);
+
+        assert_eq!(rates.convert(100.0, &Symbol::from("usd")).unwrap(), 100.0);
+        assert!((rates.convert(100.0, &Symbol::from("eur")).unwrap() - 110.0).abs() < 1e-10);
+        assert!(matches!(
+            rates.convert(100.0, &Symbol::from("gbp")),
+            Err(PortfolioError::MissingFxRate { currency, .. }) if currency == Symbol::from("gbp")
+        ));
+    }
+
+    #[test]
+    fn fx_rates_update_from_currency_pair_market_events() {
+        let mut rates = FxRates::new("usd");
+
+        assert!(rates.update_from_market(&fx_candle("eur", "usd", 1.25)));
+        assert_eq!(rates.rate(&Symbol::from("eur")).unwrap(), 1.25);
+
+        assert!(rates.update_from_market(&fx_candle("usd", "jpy", 200.0)));
+        assert_eq!(rates.rate(&Symbol::from("jpy")).unwrap(), 0.005);
+
+        // Pairs not involving the base currency are ignored
+        assert!(!rates.update_from_market(&fx_candle("btc", "eur", 50_000.0)));
+        assert!(rates.rate(&Symbol::from("btc")).is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use crate::portfolio::{position::Position, Balance, OrderEvent};
+use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Default maximum leverage of a [`MetaPortfolio`](super::portfolio::MetaPortfolio), where 1.0
+/// opens fully-funded spot-style [`Position`]s.
+pub const DEFAULT_MAX_LEVERAGE: f64 = 1.0;
+
+/// Default maintenance margin rate of a [`MetaPortfolio`](super::portfolio::MetaPortfolio), as a
+/// fraction of an open [`Position`]'s notional value (eg/ 0.005 for 0.5%).
+pub const DEFAULT_MAINTENANCE_MARGIN_RATE: f64 = 0.005;
+
+/// Fraction of an open [`Position`]'s used margin that it's margin balance can fall to before a
+/// [`MarginCall`] is issued (eg/ 0.5 once losses have consumed half of the margin).
+pub const MARGIN_CALL_RATIO: f64 = 0.5;
+
+/// Margin state of an open [`Position`], tracked by a
+/// [`MetaPortfolio`](super::portfolio::MetaPortfolio) to derive it's [`MarginAccount`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct PositionMargin {
+    /// Margin used by the [`Position`] (see [`Position::used_margin`]).
+    pub used: f64,
+    /// Margin balance of the [`Position`] (see [`Position::margin_balance`]).
+    pub balance: f64,
+}
+
+impl From<&Position> for PositionMargin {
+    fn from(position: &Position) -> Self {
+        Self {
+            used: position.used_margin(),
+            balance: position.margin_balance(),
+        }
+    }
+}
+
+impl PositionMargin {
+    /// Determines if the margin balance has fallen to, or below, the [`MARGIN_CALL_RATIO`] of the
+    /// used margin, approaching liquidation.
+    pub fn is_margin_call(&self) -> bool {
+        self.balance <= self.used * MARGIN_CALL_RATIO
+    }
+}
+
+/// Used & available margin across every open [`Position`] of a Portfolio.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MarginAccount {
+    /// Leverage new [`Position`]s are entered with.
+    pub max_leverage: f64,
+    /// Sum of the margin used by every open [`Position`].
+    pub used: f64,
+    /// Margin available to enter new [`Position`]s, being the Portfolio equity (available cash
+    /// plus the margin balance of every open [`Position`]) less the used margin.
+    pub available: f64,
+}
+
+impl MarginAccount {
+    /// Constructs a new [`MarginAccount`] from the Portfolio [`Balance`] & the [`PositionMargin`]
+    /// of every open [`Position`].
+    pub fn new<'a, Margins>(max_leverage: f64, balance: &Balance, margins: Margins) -> Self
+    where
+        Margins: IntoIterator<Item = &'a PositionMargin>,
+    {
+        let (used, positions_balance) = margins
+            .into_iter()
+            .fold((0.0, 0.0), |(used, balance), margin| {
+                (used + margin.used, balance + margin.balance)
+            });
+
+        Self {
+            max_leverage,
+            used,
+            available: balance.available + positions_balance - used,
+        }
+    }
+
+    /// Portfolio equity, being the available cash plus the margin balance (posted margin &
+    /// unrealised profit or loss) of every open [`Position`].
+    pub fn equity(&self) -> f64 {
+        self.available + self.used
+    }
+
+    /// Calculate the initial margin required to enter the provided [`OrderEvent`] at the
+    /// [`MarginAccount::max_leverage`].
+    pub fn required(&self, order: &OrderEvent) -> f64 {
+        order.quantity.abs() * order.market_meta.close / self.max_leverage
+    }
+}
+
+/// Margin call issued when the margin balance of an open leveraged [`Position`] approaches
+/// liquidation. See [`PositionMargin::is_margin_call`].
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MarginCall {
+    pub time: DateTime<Utc>,
+    pub exchange: ExchangeId,
+    pub instrument: Instrument,
+    /// Margin used by the [`Position`].
+    pub used_margin: f64,
+    /// Margin balance of the [`Position`].
+    pub margin_balance: f64,
+}
+
+impl MarginCall {
+    /// Constructs a new [`MarginCall`] for the provided open [`Position`].
+    pub fn new(position: &Position) -> Self {
+        Self {
+            time: position.meta.update_time,
+            exchange: position.exchange,
+            instrument: position.instrument.clone(),
+            used_margin: position.used_margin(),
+            margin_balance: position.margin_balance(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::{order_event, position};
+
+    #[test]
+    fn test_position_margin_is_margin_call() {
+        let mut position = position();
+        position.leverage = 5.0;
+        position.quantity = 1.0;
+        position.enter_value_gross = 100.0;
+
+        // 20.0 margin posted, with no unrealised loss
+        position.current_value_gross = 100.0;
+        position.unrealised_profit_loss = 0.0;
+        let margin = PositionMargin::from(&position);
+        assert_eq!(margin.used, 20.0);
+        assert_eq!(margin.balance, 20.0);
+        assert!(!margin.is_margin_call());
+
+        // Losses consume over half of the margin
+        position.current_value_gross = 88.0;
+        position.unrealised_profit_loss = -12.0;
+        assert!(PositionMargin::from(&position).is_margin_call());
+    }
+
+    #[test]
+    fn test_margin_account_required_and_available() {
+        let balance = Balance::new(Utc::now(), 1000.0, 800.0);
+        let margins = [
+            PositionMargin {
+                used: 100.0,
+                balance: 90.0,
+            },
+            PositionMargin {
+                used: 50.0,
+                balance: 60.0,
+            },
+        ];
+
+        let account = MarginAccount::new(5.0, &balance, &margins);
+        assert_eq!(account.used, 150.0);
+        assert_eq!(account.available, 800.0 + 150.0 - 150.0);
+
+        let mut order = order_event();
+        order.quantity = -10.0;
+        order.market_meta.close = 100.0;
+        assert_eq!(account.required(&order), 200.0);
+    }
+}
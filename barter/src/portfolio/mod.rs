@@ -2,8 +2,8 @@ use crate::{
     data::MarketMeta,
     event::Event,
     execution::FillEvent,
-    portfolio::{error::PortfolioError, position::PositionUpdate},
-    strategy::{Decision, Signal, SignalForceExit},
+    portfolio::{error::PortfolioError, margin::MarginCall, position::PositionUpdate},
+    strategy::{Decision, Signal, SignalForceExit, StrategyId},
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
@@ -14,9 +14,23 @@ use uuid::Uuid;
 /// Logic for [`OrderEvent`] quantity allocation.
 pub mod allocator;
 
+/// Exponentially weighted volatility & correlation estimates of the markets traded by a
+/// Portfolio.
+pub mod correlation;
+
 /// Barter portfolio module specific errors.
 pub mod error;
 
+/// Accrual of perpetual futures funding payments on open [`Position`](position::Position)s.
+pub mod funding;
+
+/// Exchange rates converting values denominated in each quote currency into a Portfolio's base
+/// currency.
+pub mod fx;
+
+/// Leverage & margin accounting of open [`Position`](position::Position)s.
+pub mod margin;
+
 /// Core Portfolio logic containing an implementation of [`MarketUpdater`],
 /// [`OrderGenerator`] and [`FillUpdater`]. Utilises the risk and allocator logic to optimise
 /// [`OrderEvent`] generation.
@@ -32,6 +46,10 @@ pub mod repository;
 /// Logic for evaluating the risk associated with a proposed [`OrderEvent`].
 pub mod risk;
 
+/// Portfolio-level volatility targeting that scales overall exposure toward a target annualised
+/// volatility.
+pub mod volatility_target;
+
 /// Updates the Portfolio from an input [`MarketEvent`].
 pub trait MarketUpdater {
     /// Determines if the Portfolio has an open Position relating to the input [`MarketEvent`]. If
@@ -41,6 +59,49 @@ pub trait MarketUpdater {
         &mut self,
         market: &MarketEvent<Instrument, DataKind>,
     ) -> Result<Option<PositionUpdate>, PortfolioError>;
+
+    /// Determines if the open Position relating to the input [`MarketEvent`] is approaching
+    /// liquidation, returning a [`MarginCall`] if so. Default implementation never issues a
+    /// [`MarginCall`].
+    fn margin_call(
+        &mut self,
+        _: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Option<MarginCall>, PortfolioError> {
+        Ok(None)
+    }
+
+    /// Determines if the open Position relating to the input [`MarketEvent`] has breached the
+    /// maintenance margin, and if so force-closes it at it's liquidation price. Returns the
+    /// [`Event`]s generated by the liquidation. Default implementation never liquidates.
+    fn liquidate(
+        &mut self,
+        _: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<Event>, PortfolioError> {
+        Ok(Vec::new())
+    }
+
+    /// Determines if the Portfolio equity, revalued by the input [`MarketEvent`], has fallen to,
+    /// or below, zero, returning the [`Bankruptcy`] the first time it does. Default
+    /// implementation never declares a [`Bankruptcy`].
+    fn check_bankruptcy(
+        &mut self,
+        _: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Option<Bankruptcy>, PortfolioError> {
+        Ok(None)
+    }
+
+    /// Returns the [`Bankruptcy`] declared by the Portfolio, if any, after which no further
+    /// [`OrderEvent`]s are generated from [`Signal`]s. Default implementation is never bankrupt.
+    fn bankruptcy(&self) -> Option<Bankruptcy> {
+        None
+    }
+
+    /// Drains the [`OrderEvent`]s the Portfolio has stopped awaiting since last called (eg/ those
+    /// not filled within their time-to-live), which must be cancelled with the execution handler
+    /// so they are not filled later. Default implementation never expires [`OrderEvent`]s.
+    fn take_expired_orders(&mut self) -> Vec<OrderEvent> {
+        Vec::new()
+    }
 }
 
 /// May generate an [`OrderEvent`] from an input advisory [`Signal`].
@@ -54,6 +115,24 @@ pub trait OrderGenerator {
         &mut self,
         signal: SignalForceExit,
     ) -> Result<Option<OrderEvent>, PortfolioError>;
+
+    /// Generates an exit [`OrderEvent`] if the open [`Position`](position::Position) associated
+    /// with the input [`MarketEvent`] has breached the risk manager's limits (eg/ stop-loss).
+    fn generate_risk_exit_order(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Option<OrderEvent>, PortfolioError>;
+
+    /// Generates the [`OrderEvent`]s rebalancing the open [`Position`](position::Position)s of
+    /// the input [`MarketEvent`]'s market toward any Portfolio-level target exposure (eg/
+    /// [`VolatilityTarget`](volatility_target::VolatilityTarget)). Default implementation never
+    /// rebalances.
+    fn generate_rebalance_orders(
+        &mut self,
+        _: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        Ok(Vec::new())
+    }
 }
 
 /// Updates the Portfolio from an input [`FillEvent`].
@@ -64,10 +143,23 @@ pub trait FillUpdater {
     fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError>;
 }
 
+/// Reports the current state of the Portfolio (eg/ to drive the
+/// [`Progress`](crate::engine::progress::Progress) of a long running backtest).
+pub trait PortfolioReporter {
+    /// Returns the current Portfolio equity, being the available cash plus the margin balance of
+    /// every open [`Position`](position::Position).
+    fn equity(&mut self) -> Result<f64, PortfolioError>;
+
+    /// Returns the number of open [`Position`](position::Position)s.
+    fn open_positions(&self) -> usize;
+}
+
 /// Orders are generated by the portfolio and details work to be done by an Execution handler to
 /// open a trade.
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct OrderEvent {
+    /// Client order identifier used to match [`FillEvent`]s to this originating [`OrderEvent`].
+    pub cid: Uuid,
     pub time: DateTime<Utc>,
     pub exchange: ExchangeId,
     pub instrument: Instrument,
@@ -79,6 +171,10 @@ pub struct OrderEvent {
     pub quantity: f64,
     /// MARKET, LIMIT etc
     pub order_type: OrderType,
+    /// [`StrategyId`] of the strategy whose [`Signal`](crate::strategy::Signal) generated this
+    /// [`OrderEvent`].
+    #[serde(default)]
+    pub strategy_id: StrategyId,
 }
 
 impl OrderEvent {
@@ -92,22 +188,115 @@ impl OrderEvent {
 }
 
 /// Type of order the portfolio wants the execution::handler to place.
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+///
+/// Stop orders rest until the market trades through their trigger price, at which point a
+/// [`OrderType::Stop`] becomes a [`OrderType::Market`] order, and a [`OrderType::StopLimit`]
+/// becomes a [`OrderType::Limit`] order.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
 pub enum OrderType {
+    #[default]
     Market,
-    Limit,
+    Limit {
+        price: f64,
+    },
+    Stop {
+        trigger: f64,
+    },
+    StopLimit {
+        trigger: f64,
+        price: f64,
+    },
     Bracket,
 }
 
-impl Default for OrderType {
-    fn default() -> Self {
-        Self::Market
+impl OrderType {
+    /// Returns the limit price of a [`OrderType::Limit`] or [`OrderType::StopLimit`] order.
+    pub fn limit_price(&self) -> Option<f64> {
+        match *self {
+            OrderType::Limit { price } | OrderType::StopLimit { price, .. } => Some(price),
+            _ => None,
+        }
+    }
+
+    /// Returns the trigger price of a [`OrderType::Stop`] or [`OrderType::StopLimit`] order.
+    pub fn trigger_price(&self) -> Option<f64> {
+        match *self {
+            OrderType::Stop { trigger } | OrderType::StopLimit { trigger, .. } => Some(trigger),
+            _ => None,
+        }
+    }
+
+    /// Returns the [`OrderType`] a stop order becomes once it's trigger price is reached. Orders
+    /// without a trigger price are returned unchanged.
+    pub fn triggered(&self) -> Self {
+        match *self {
+            OrderType::Stop { .. } => OrderType::Market,
+            OrderType::StopLimit { price, .. } => OrderType::Limit { price },
+            order_type => order_type,
+        }
+    }
+}
+
+/// Type of order an [`OrderGenerator`] places to enter a new [`Position`](position::Position),
+/// with prices expressed as fractional offsets from the signal close price (eg/ 0.01 for 1%).
+///
+/// Offsets are applied relative to the order side: a buy limit is priced below the close & a buy
+/// stop triggers above it, and vice versa for a sell. A stop-limit limit price is offset beyond
+/// it's trigger price in the same direction as the stop.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub enum EntryOrderType {
+    #[default]
+    Market,
+    Limit {
+        offset: f64,
+    },
+    Stop {
+        offset: f64,
+    },
+    StopLimit {
+        trigger_offset: f64,
+        limit_offset: f64,
+    },
+}
+
+impl EntryOrderType {
+    /// Determines the [`OrderType`] of the provided entry [`OrderEvent`], priced relative to it's
+    /// market close price.
+    pub fn order_type(&self, order: &OrderEvent) -> OrderType {
+        let close = order.market_meta.close;
+
+        // +1.0 for buys & -1.0 for sells, so offsets move against the order side
+        let direction = match order.quantity.is_sign_positive() {
+            true => 1.0,
+            false => -1.0,
+        };
+
+        match *self {
+            EntryOrderType::Market => OrderType::Market,
+            EntryOrderType::Limit { offset } => OrderType::Limit {
+                price: close * (1.0 - direction * offset),
+            },
+            EntryOrderType::Stop { offset } => OrderType::Stop {
+                trigger: close * (1.0 + direction * offset),
+            },
+            EntryOrderType::StopLimit {
+                trigger_offset,
+                limit_offset,
+            } => {
+                let trigger = close * (1.0 + direction * trigger_offset);
+                OrderType::StopLimit {
+                    trigger,
+                    price: trigger * (1.0 + direction * limit_offset),
+                }
+            }
+        }
     }
 }
 
 /// Builder to construct OrderEvent instances.
 #[derive(Debug, Default)]
 pub struct OrderEventBuilder {
+    pub cid: Option<Uuid>,
     pub time: Option<DateTime<Utc>>,
     pub exchange: Option<ExchangeId>,
     pub instrument: Option<Instrument>,
@@ -115,6 +304,7 @@ pub struct OrderEventBuilder {
     pub decision: Option<Decision>,
     pub quantity: Option<f64>,
     pub order_type: Option<OrderType>,
+    pub strategy_id: Option<StrategyId>,
 }
 
 impl OrderEventBuilder {
@@ -122,6 +312,13 @@ impl OrderEventBuilder {
         Self::default()
     }
 
+    pub fn cid(self, value: Uuid) -> Self {
+        Self {
+            cid: Some(value),
+            ..self
+        }
+    }
+
     pub fn time(self, value: DateTime<Utc>) -> Self {
         Self {
             time: Some(value),
@@ -171,8 +368,16 @@ impl OrderEventBuilder {
         }
     }
 
+    pub fn strategy_id(self, value: StrategyId) -> Self {
+        Self {
+            strategy_id: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<OrderEvent, PortfolioError> {
         Ok(OrderEvent {
+            cid: self.cid.ok_or(PortfolioError::BuilderIncomplete("cid"))?,
             time: self.time.ok_or(PortfolioError::BuilderIncomplete("time"))?,
             exchange: self
                 .exchange
@@ -192,6 +397,7 @@ impl OrderEventBuilder {
             order_type: self
                 .order_type
                 .ok_or(PortfolioError::BuilderIncomplete("order_type"))?,
+            strategy_id: self.strategy_id.unwrap_or_default(),
         })
     }
 }
@@ -227,8 +433,35 @@ impl Balance {
         }
     }
 
+    /// Determines if the [`Balance`] total equity has fallen to, or below, zero.
+    pub fn is_bankrupt(&self) -> bool {
+        self.total <= 0.0
+    }
+
     /// Returns the unique identifier for an Engine's [`Balance`].
     pub fn balance_id(engine_id: Uuid) -> BalanceId {
         format!("{}_balance", engine_id)
     }
+
+    /// Returns the unique identifier for an Engine's [`Balance`] of each currency held.
+    pub fn currency_balances_id(engine_id: Uuid) -> BalanceId {
+        format!("{}_currency_balances", engine_id)
+    }
+}
+
+/// Portfolio bankruptcy detected when the total equity of the Portfolio [`Balance`] falls to, or
+/// below, zero. Trading halts once a [`Bankruptcy`] occurs.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Bankruptcy {
+    pub time: DateTime<Utc>,
+    pub equity: f64,
+}
+
+impl From<Balance> for Bankruptcy {
+    fn from(balance: Balance) -> Self {
+        Self {
+            time: balance.time,
+            equity: balance.total,
+        }
+    }
 }
@@ -1,33 +1,53 @@
 use super::{
     allocator::OrderAllocator,
+    correlation::{CorrelationConfig, CorrelationTracker},
     error::PortfolioError,
+    funding::FundingAccrual,
+    fx::FxRates,
+    margin::{
+        MarginAccount, MarginCall, PositionMargin, DEFAULT_MAINTENANCE_MARGIN_RATE,
+        DEFAULT_MAX_LEVERAGE,
+    },
     position::{
-        determine_position_id, Position, PositionEnterer, PositionExiter, PositionId,
-        PositionUpdate, PositionUpdater,
+        CloseReason, FillEffect, Position, PositionEnterer, PositionExiter, PositionId,
+        PositionMode, PositionUpdate, PositionUpdater,
     },
     repository::{error::RepositoryError, BalanceHandler, PositionHandler, StatisticHandler},
     risk::OrderEvaluator,
-    Balance, FillUpdater, MarketUpdater, OrderEvent, OrderGenerator, OrderType,
+    volatility_target::VolatilityTarget,
+    Balance, Bankruptcy, EntryOrderType, FillUpdater, MarketUpdater, OrderEvent, OrderGenerator,
+    OrderType, PortfolioReporter,
 };
 use crate::{
+    clock::{Clock, SystemClock},
     data::MarketMeta,
     event::Event,
-    execution::FillEvent,
+    execution::{Fees, FillEvent},
     statistic::summary::{Initialiser, PositionSummariser},
     strategy::{Decision, Signal, SignalForceExit, SignalStrength},
 };
 use barter_data::event::{DataKind, MarketEvent};
 use barter_instrument::{
-    instrument::Instrument,
+    asset::symbol::Symbol,
+    exchange::ExchangeId,
+    instrument::{spec::InstrumentSpec, Instrument},
     market::{Market, MarketId},
 };
 use barter_integration::Side;
-use chrono::Utc;
+use chrono::{DateTime, Duration, Utc};
 use serde::Serialize;
-use std::{collections::HashMap, marker::PhantomData};
-use tracing::info;
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    marker::PhantomData,
+    sync::Arc,
+};
+use tracing::{debug, info, warn};
 use uuid::Uuid;
 
+/// Default maximum number of [`FillEvent`]s a [`MetaPortfolio`] buffers while they await
+/// reconciliation, beyond which the oldest are dropped.
+pub const DEFAULT_MAX_PENDING_FILLS: usize = 1024;
+
 /// Lego components for constructing & initialising a [`MetaPortfolio`] via the init() constructor
 /// method.
 #[derive(Debug)]
@@ -52,6 +72,12 @@ where
     pub risk: RiskManager,
     /// Cash balance a [`MetaPortfolio`] starts with.
     pub starting_cash: f64,
+    /// Leverage a [`MetaPortfolio`] enters new [`Position`]s with (eg/ 1.0 for fully-funded
+    /// spot-style [`Position`]s). Entry orders exceeding the available margin are rejected.
+    pub max_leverage: f64,
+    /// Maintenance margin rate, as a fraction of notional value (eg/ 0.005 for 0.5%), below which
+    /// a leveraged [`Position`]'s margin balance cannot fall before it is liquidated.
+    pub maintenance_margin_rate: f64,
     /// Configuration used to initialise the Statistics for every Market's performance tracked by a
     /// [`MetaPortfolio`].
     pub statistic_config: Statistic::Config,
@@ -77,6 +103,58 @@ where
     allocation_manager: Allocator,
     /// Risk manager implements [`OrderEvaluator`].
     risk_manager: RiskManager,
+    /// Type of order placed to enter new [`Position`]s, unless amended by the risk manager.
+    entry_order_type: EntryOrderType,
+    /// Determines if opposing [`Position`]s in the same market are netted, or held separately.
+    position_mode: PositionMode,
+    /// Leverage new [`Position`]s are entered with.
+    max_leverage: f64,
+    /// Maintenance margin rate used to determine the liquidation price of open [`Position`]s.
+    maintenance_margin_rate: f64,
+    /// Exchange rates converting the quote currency of each [`Position`] into the base currency
+    /// the Portfolio [`Balance`] is denominated in. If `None`, every [`Position`] is assumed to
+    /// be quoted in the same currency as the Portfolio [`Balance`].
+    fx_rates: Option<FxRates>,
+    /// Exchange [`InstrumentSpec`] of each [`Market`], used to round [`OrderEvent`] prices &
+    /// quantities to valid increments. [`Market`]s without an [`InstrumentSpec`] are unconstrained.
+    instrument_specs: HashMap<MarketId, InstrumentSpec<Symbol>>,
+    /// [`Market`]s the Portfolio initialised Statistics for, which are updated with every
+    /// [`MarketEvent`] of that market.
+    statistic_markets: HashSet<MarketId>,
+    /// Quote currency & [`PositionMargin`] of every open [`Position`] entered or updated since initialisation.
+    margins: HashMap<PositionId, (Symbol, PositionMargin)>,
+    /// Open [`Position`]s a [`MarginCall`] has been issued for, until their margin recovers.
+    margin_calls: HashSet<PositionId>,
+    /// Generated [`OrderEvent`]s awaiting a [`FillEvent`], keyed by client order identifier.
+    orders: HashMap<Uuid, OrderEvent>,
+    /// [`FillEvent`]s received before they could be applied, either because their originating
+    /// [`OrderEvent`] is not yet known, or it depends on a preceding [`FillEvent`].
+    pending_fills: VecDeque<FillEvent>,
+    /// Maximum number of buffered [`FillEvent`]s, beyond which the oldest are dropped.
+    max_pending_fills: usize,
+    /// Time after which registered [`OrderEvent`]s & buffered [`FillEvent`]s still awaiting
+    /// reconciliation are expired. If `None`, they never expire (eg/ resting limit orders).
+    order_ttl: Option<Duration>,
+    /// Registered [`OrderEvent`]s expired by the `order_ttl`, awaiting cancellation with the
+    /// execution handler so they cannot be filled later.
+    expired_orders: Vec<OrderEvent>,
+    /// EWMA volatility of each market & the pairwise correlations between them, updated with
+    /// every [`MarketEvent`].
+    correlations: CorrelationTracker,
+    /// Perpetual funding payments accrued on open [`Position`]s at each funding time. If `None`,
+    /// funding is not modelled.
+    funding: Option<FundingAccrual>,
+    /// Portfolio-level [`VolatilityTarget`] updated with the Portfolio equity after every
+    /// [`MarketEvent`], rebalancing open [`Position`]s toward it's scaled exposure. If `None`,
+    /// exposure is not volatility targeted.
+    volatility_target: Option<VolatilityTarget>,
+    /// [`Clock`] used to timestamp generated [`OrderEvent`]s & the initial [`Balance`].
+    clock: Arc<dyn Clock>,
+    /// Determines if the Portfolio declares a [`Bankruptcy`] & stops generating [`OrderEvent`]s
+    /// from [`Signal`]s once it's equity falls to, or below, zero.
+    halt_on_bankruptcy: bool,
+    /// [`Bankruptcy`] declared by the Portfolio, shared by every Trader trading it.
+    bankruptcy: Option<Bankruptcy>,
     _statistic_marker: PhantomData<Statistic>,
 }
 
@@ -92,22 +170,201 @@ where
         &mut self,
         market: &MarketEvent<Instrument, DataKind>,
     ) -> Result<Option<PositionUpdate>, PortfolioError> {
-        // Determine the position_id associated to the input MarketEvent
-        let position_id =
-            determine_position_id(self.engine_id, &market.exchange, &market.instrument);
+        // Expire OrderEvents & FillEvents that have awaited reconciliation beyond their TTL
+        self.expire_pending(self.clock.time());
+
+        // Update any market dependent allocation state (eg/ volatility estimates)
+        self.allocation_manager.update_from_market(market);
+
+        // Update any market dependent risk state (eg/ ATR estimates)
+        self.risk_manager.update_from_market(market);
+
+        // Update the EWMA volatility & correlation estimates
+        self.correlations.update_from_market(market);
+
+        // Update any exchange rate quoted by the MarketEvent, revaluing the Portfolio Balance
+        if self
+            .fx_rates
+            .as_mut()
+            .is_some_and(|fx_rates| fx_rates.update_from_market(market))
+        {
+            self.revalue_balance(market.time_exchange)?;
+        }
+
+        // Update any market dependent statistics (eg/ buy-and-hold benchmark)
+        let market_id = statistic_market_id(market.exchange, &market.instrument);
+        if self.statistic_markets.contains(&market_id) {
+            let mut statistic = self.repository.get_statistics(self.engine_id, &market_id)?;
+            statistic.update_from_market(market);
+            self.repository
+                .set_statistics(self.engine_id, market_id, statistic)?;
+        }
+
+        // Determine any perpetual FundingRates whose funding time has passed
+        let fundings = self
+            .funding
+            .as_mut()
+            .map(|funding| funding.update_from_market(market))
+            .unwrap_or_default();
+
+        // Update every open Position for that Symbol-Exchange combination (long & short if hedging)
+        let mut position_update = None;
+        for position_id in self.market_position_ids(&market.exchange, &market.instrument) {
+            let Some(mut position) = self.repository.get_open_position(&position_id)? else {
+                continue;
+            };
+
+            // Accrue the funding payments of the open Position, debiting or crediting cash
+            let funding_profit_loss = fundings
+                .iter()
+                .map(|funding| position.accrue_funding(funding))
+                .sum::<f64>();
+            if funding_profit_loss != 0.0 {
+                self.settle_funding(&position, funding_profit_loss, market.time_exchange)?;
+            }
 
-        // Update Position if Portfolio has an open Position for that Symbol-Exchange combination
-        if let Some(mut position) = self.repository.get_open_position(&position_id)? {
             // Derive PositionUpdate event that communicates the open Position's change in state
-            if let Some(position_update) = position.update(market) {
-                // Save updated open Position in the repository
+            let update = position.update(market);
+
+            // Save updated open Position in the repository
+            if update.is_some() || funding_profit_loss != 0.0 {
+                self.margins.insert(position_id, position_margin(&position));
                 self.repository.set_open_position(position)?;
-                return Ok(Some(position_update));
             }
+
+            position_update = update.or(position_update);
+        }
+
+        // Update any equity dependent allocation & risk state (eg/ daily loss limits)
+        let equity = self.update_from_equity(market.time_exchange)?;
+
+        // Update the realised Portfolio volatility once per period to rescale target exposure
+        if let Some(volatility_target) = &mut self.volatility_target {
+            volatility_target.update(equity);
+        }
+
+        Ok(position_update)
+    }
+
+    fn margin_call(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Option<MarginCall>, PortfolioError> {
+        for position_id in self.market_position_ids(&market.exchange, &market.instrument) {
+            let Some((_, margin)) = self.margins.get(&position_id) else {
+                continue;
+            };
+
+            // Issue a single MarginCall each time the Position margin falls below the threshold
+            if !margin.is_margin_call() {
+                self.margin_calls.remove(&position_id);
+                continue;
+            }
+            if !self.margin_calls.insert(position_id.clone()) {
+                continue;
+            }
+
+            let Some(position) = self.repository.get_open_position(&position_id)? else {
+                continue;
+            };
+
+            info!(
+                position_id = &*position_id,
+                used_margin = margin.used,
+                margin_balance = margin.balance,
+                "issuing MarginCall for Position approaching liquidation"
+            );
+
+            return Ok(Some(MarginCall::new(&position)));
         }
 
         Ok(None)
     }
+
+    fn liquidate(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<Event>, PortfolioError> {
+        let mut generated_events = Vec::new();
+
+        for position_id in self.market_position_ids(&market.exchange, &market.instrument) {
+            let Some(mut position) = self.repository.get_open_position(&position_id)? else {
+                continue;
+            };
+
+            if !position.is_liquidatable(self.maintenance_margin_rate) {
+                continue;
+            }
+
+            // Liquidation supersedes any exit OrderEvent awaiting it's FillEvent
+            let position_mode = self.position_mode;
+            self.orders
+                .retain(|_, order| !order_targets_position(position_mode, order, &position));
+
+            // Force-close the full Position at it's liquidation price, at the time of the
+            // MarketEvent that breached the maintenance margin
+            let liquidation_price = position.liquidation_price(self.maintenance_margin_rate);
+            let fill = FillEvent {
+                cid: None,
+                time: market.time_exchange,
+                exchange: position.exchange,
+                instrument: position.instrument.clone(),
+                market_meta: MarketMeta {
+                    close: liquidation_price,
+                    time: market.time_exchange,
+                },
+                decision: position.determine_exit_decision(),
+                quantity: 0.0 - position.quantity,
+                fill_value_gross: position.quantity.abs() * liquidation_price,
+                fees: Fees::default(),
+                strategy_id: position.strategy_id.clone(),
+            };
+
+            warn!(
+                position_id = &*position_id,
+                price = position.current_symbol_price,
+                liquidation_price,
+                "liquidating Position that breached the maintenance margin"
+            );
+
+            // Tag the Position as liquidated so it's exited record & statistics reflect it
+            position.liquidated = true;
+            position.close_reason = Some(CloseReason::Liquidation);
+            self.repository.set_open_position(position)?;
+
+            generated_events.extend(self.apply_fill(&fill)?);
+        }
+
+        Ok(generated_events)
+    }
+
+    fn check_bankruptcy(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Option<Bankruptcy>, PortfolioError> {
+        if !self.halt_on_bankruptcy || self.bankruptcy.is_some() {
+            return Ok(None);
+        }
+
+        // Equity includes the unrealised P&L of open Positions revalued by the MarketEvent
+        let equity = self.equity()?;
+        if equity > 0.0 {
+            return Ok(None);
+        }
+
+        Ok(self.declare_bankruptcy(Bankruptcy {
+            time: market.time_exchange,
+            equity,
+        }))
+    }
+
+    fn bankruptcy(&self) -> Option<Bankruptcy> {
+        self.bankruptcy
+    }
+
+    fn take_expired_orders(&mut self) -> Vec<OrderEvent> {
+        std::mem::take(&mut self.expired_orders)
+    }
 }
 
 impl<Repository, Allocator, RiskManager, Statistic> OrderGenerator
@@ -119,76 +376,263 @@ where
     Statistic: Initialiser + PositionSummariser,
 {
     fn generate_order(&mut self, signal: &Signal) -> Result<Option<OrderEvent>, PortfolioError> {
-        // Determine the position_id & associated Option<Position> related to input SignalEvent
-        let position_id =
-            determine_position_id(self.engine_id, &signal.exchange, &signal.instrument);
-        let position = self.repository.get_open_position(&position_id)?;
+        // A bankrupt Portfolio does not act on any further Signals
+        if let Some(bankruptcy) = self.bankruptcy {
+            debug!(
+                time = %bankruptcy.time,
+                outcome = "no OrderEvent generated",
+                "cannot generate OrderEvent for a bankrupt Portfolio"
+            );
+            return Ok(None);
+        }
+
+        // Determine the open Position(s) related to input SignalEvent (long & short if hedging)
+        let positions = self
+            .market_position_ids(&signal.exchange, &signal.instrument)
+            .iter()
+            .filter_map(|position_id| self.repository.get_open_position(position_id).transpose())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // Parse signals from Strategy to determine net signal decision & associated strength
+        let netted_position = positions.first();
+        let parsed = match self.position_mode {
+            PositionMode::Netting => parse_signal_decisions(&netted_position, &signal.signals)
+                .or_else(|| parse_scale_in_decision(&netted_position, &signal.signals)),
+            PositionMode::Hedging => parse_hedged_signal_decisions(&positions, &signal.signals),
+        };
+        let Some((signal_decision, signal_strength)) = parsed else {
+            return Ok(None);
+        };
+
+        // Determine the open Position the net signal decision enters or exits, if any
+        let position = positions
+            .iter()
+            .find(|position| position.side == signal_decision.position_side());
 
         // If signal is advising to open a new Position rather than close one, check we have cash
         if position.is_none() && self.no_cash_to_enter_new_position()? {
             return Ok(None);
         }
 
-        // Parse signals from Strategy to determine net signal decision & associated strength
-        let position = position.as_ref();
-        let (signal_decision, signal_strength) =
-            match parse_signal_decisions(&position, &signal.signals) {
-                None => return Ok(None),
-                Some(net_signal) => net_signal,
-            };
+        // If an exit OrderEvent for the Position is already awaiting it's FillEvent, do not exit twice
+        if position.is_some_and(|position| self.exit_order_pending(position)) {
+            return Ok(None);
+        }
 
         // Construct mutable OrderEvent that can be modified by Allocation & Risk management
         let mut order = OrderEvent {
-            time: Utc::now(),
+            cid: Uuid::new_v4(),
+            time: self.clock.time(),
             exchange: signal.exchange,
             instrument: signal.instrument.clone(),
             market_meta: signal.market_meta,
             decision: *signal_decision,
             quantity: 0.0,
             order_type: OrderType::default(),
+            strategy_id: signal.strategy_id.clone(),
         };
 
-        // Manage OrderEvent size allocation
-        self.allocation_manager
-            .allocate_order(&mut order, position, *signal_strength);
+        // Manage OrderEvent size allocation, only scaling into an open Position if permitted
+        match position {
+            Some(position) if order.decision.is_entry() => {
+                if !self.allocation_manager.allocate_scale_in(
+                    &mut order,
+                    position,
+                    *signal_strength,
+                ) {
+                    return Ok(None);
+                }
+            }
+            _ => self
+                .allocation_manager
+                .allocate_order(&mut order, position, *signal_strength),
+        }
+
+        // Exit Decisions that do not close the open Position (eg/ CloseLong when flat) are void
+        if order.decision.is_exit() && order.quantity == 0.0 {
+            return Ok(None);
+        }
+
+        // Scale exit OrderEvent down to the fraction of the open Position the Signal closes
+        if let (Some(position), Some(close_fraction)) = (position, signal.close_fraction) {
+            if order.decision.is_exit() {
+                if close_fraction <= 0.0 {
+                    return Ok(None);
+                }
+                order.quantity = -position.quantity * close_fraction.min(1.0);
+            }
+        }
 
         // Manage global risk when evaluating OrderEvent - keep the same, refine or cancel
-        Ok(self.risk_manager.evaluate_order(order))
+        let order = match self.risk_manager.evaluate_order(order) {
+            // Reject entry OrderEvents that would exceed the available margin
+            Some(order) if order.decision.is_entry() => {
+                let margin = self.margin_account()?;
+                self.risk_manager.evaluate_margin(order, &margin)
+            }
+            order => order,
+        };
+        let order = order.map(|mut order| {
+            // Price entry market orders as the configured EntryOrderType
+            if order.decision.is_entry() && order.order_type == OrderType::Market {
+                order.order_type = self.entry_order_type.order_type(&order);
+            }
+            order
+        });
+
+        // Round OrderEvent to the exchange InstrumentSpec, rejecting entries below it's minimums
+        let order = order.and_then(|order| self.apply_instrument_spec(order));
+
+        // Register OrderEvent so it's FillEvent can be matched regardless of arrival order
+        if let Some(order) = &order {
+            self.orders.insert(order.cid, order.clone());
+
+            // Tag the Position exited by the Signal so it's exited record reflects why it closed
+            if let (Some(position), true) = (position, order.decision.is_exit()) {
+                self.tag_close_reason(position.clone(), CloseReason::Signal)?;
+            }
+        }
+
+        Ok(order)
     }
 
     fn generate_exit_order(
         &mut self,
         signal: SignalForceExit,
     ) -> Result<Option<OrderEvent>, PortfolioError> {
-        // Determine PositionId associated with the SignalForceExit
-        let position_id =
-            determine_position_id(self.engine_id, &signal.exchange, &signal.instrument);
+        // Retrieve the open Position associated with the SignalForceExit, skipping any hedged
+        // Position already being exited so each SignalForceExit exits the next open Position
+        let mut open_position = None;
+        for position_id in self.market_position_ids(&signal.exchange, &signal.instrument) {
+            let Some(position) = self.repository.get_open_position(&position_id)? else {
+                continue;
+            };
+            if self.position_mode == PositionMode::Hedging && self.exit_order_pending(&position) {
+                continue;
+            }
+            open_position = Some(position);
+            break;
+        }
+
+        let Some(mut position) = open_position else {
+            info!(
+                exchange = %signal.exchange,
+                instrument = %signal.instrument,
+                outcome = "no forced exit OrderEvent generated",
+                "cannot generate forced exit OrderEvent for a Position that isn't open"
+            );
+            return Ok(None);
+        };
+        let position_id = position.position_id.clone();
+
+        // Tag a Position exited without market data so it's exited record & statistics reflect it
+        if signal.synthetic {
+            warn!(
+                position_id = &*position_id,
+                price = position.current_symbol_price,
+                "exiting Position at it's last mark price without market data to price the close"
+            );
+            position.synthetic_exit = true;
+        }
+        let position = self.tag_close_reason(position, signal.reason)?;
+
+        Ok(Some(self.register_exit_order(&position)))
+    }
+
+    fn generate_risk_exit_order(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Option<OrderEvent>, PortfolioError> {
+        for position_id in self.market_position_ids(&market.exchange, &market.instrument) {
+            let Some(position) = self.repository.get_open_position(&position_id)? else {
+                continue;
+            };
+
+            if !self.risk_manager.should_exit(&position) || self.exit_order_pending(&position) {
+                continue;
+            }
+
+            let close_reason = self.risk_manager.exit_reason(&position);
+            info!(
+                position_id = &*position_id,
+                price = position.current_symbol_price,
+                ?close_reason,
+                "generating exit OrderEvent for Position that breached risk limits"
+            );
+
+            let position = self.tag_close_reason(position, close_reason)?;
+            return Ok(Some(self.register_exit_order(&position)));
+        }
+
+        Ok(None)
+    }
+
+    fn generate_rebalance_orders(
+        &mut self,
+        market: &MarketEvent<Instrument, DataKind>,
+    ) -> Result<Vec<OrderEvent>, PortfolioError> {
+        if self.volatility_target.is_none() {
+            return Ok(Vec::new());
+        }
+
+        // Rebalance the open Positions of the market, except any already being exited
+        let mut positions = Vec::new();
+        for position_id in self.market_position_ids(&market.exchange, &market.instrument) {
+            let Some(position) = self.repository.get_open_position(&position_id)? else {
+                continue;
+            };
+            if !self.exit_order_pending(&position) {
+                positions.push(position);
+            }
+        }
+
+        let time = self.clock.time();
+        let Some(volatility_target) = &self.volatility_target else {
+            return Ok(Vec::new());
+        };
+        let orders = volatility_target.rebalance_orders(time, &positions);
+
+        let mut rebalance_orders = Vec::with_capacity(orders.len());
+        for (position_id, mut order) in orders {
+            // Round rebalancing quantity down to the InstrumentSpec quantity increment, including
+            // the partial exits that apply_instrument_spec leaves unrounded
+            if let Some(spec) = self
+                .instrument_specs
+                .get(&MarketId::new(order.exchange, &order.instrument))
+            {
+                order.quantity = spec.quantity.round_down(order.quantity);
+            }
+            if order.quantity == 0.0 {
+                continue;
+            }
+
+            // Reject rebalancing entry OrderEvents that would exceed the available margin
+            let order = match order.decision.is_entry() {
+                true => {
+                    let margin = self.margin_account()?;
+                    self.risk_manager.evaluate_margin(order, &margin)
+                }
+                false => Some(order),
+            };
 
-        // Retrieve Option<Position> associated with the PositionId
-        let position = match self.repository.get_open_position(&position_id)? {
-            None => {
+            // Round OrderEvent to the exchange InstrumentSpec, & register it for it's FillEvent
+            if let Some(order) = order.and_then(|order| self.apply_instrument_spec(order)) {
                 info!(
-                    position_id = &*position_id,
-                    outcome = "no forced exit OrderEvent generated",
-                    "cannot generate forced exit OrderEvent for a Position that isn't open"
+                    exchange = %order.exchange,
+                    instrument = %order.instrument,
+                    decision = ?order.decision,
+                    quantity = order.quantity,
+                    "generating OrderEvent rebalancing Position toward volatility target"
                 );
-                return Ok(None);
+                if let Some(volatility_target) = &mut self.volatility_target {
+                    volatility_target.apply_rebalance(&position_id);
+                }
+                self.orders.insert(order.cid, order.clone());
+                rebalance_orders.push(order);
             }
-            Some(position) => position,
-        };
+        }
 
-        Ok(Some(OrderEvent {
-            time: Utc::now(),
-            exchange: signal.exchange,
-            instrument: signal.instrument,
-            market_meta: MarketMeta {
-                close: position.current_symbol_price,
-                time: position.meta.update_time,
-            },
-            decision: position.determine_exit_decision(),
-            quantity: 0.0 - position.quantity,
-            order_type: OrderType::Market,
-        }))
+        Ok(rebalance_orders)
     }
 }
 
@@ -201,133 +645,534 @@ where
     Statistic: Initialiser + PositionSummariser + Serialize,
 {
     fn update_from_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
-        // Allocate Vector<Event> to contain any update_from_fill generated events
-        let mut generated_events: Vec<Event> = Vec::with_capacity(2);
-
-        // Get the Portfolio Balance from Repository & update timestamp
-        let mut balance = self.repository.get_balance(self.engine_id)?;
-        balance.time = fill.time;
-
-        // Determine the position_id that is related to the input FillEvent
-        let position_id = determine_position_id(self.engine_id, &fill.exchange, &fill.instrument);
-
-        // Determine FillEvent context based on existence or absence of an open Position
-        match self.repository.remove_position(&position_id)? {
-            // EXIT SCENARIO - FillEvent for Symbol-Exchange combination with open Position
-            Some(mut position) => {
-                // Exit Position (in place mutation), & add the PositionExit event to Vec<Event>
-                let position_exit = position.exit(balance, fill)?;
-                generated_events.push(Event::PositionExit(position_exit));
-
-                // Update Portfolio balance on Position exit
-                // '--> available balance adds enter_total_fees since included in result PnL calc
-                balance.available += position.enter_value_gross
-                    + position.realised_profit_loss
-                    + position.enter_fees_total;
-                balance.total += position.realised_profit_loss;
-
-                // Update statistics for exited Position market
-                let market_id = MarketId::new(fill.exchange, &fill.instrument);
-
-                let mut stats = self.repository.get_statistics(&market_id)?;
-                stats.update(&position);
-
-                // Persist exited Position & Updated Market statistics in Repository
-                self.repository.set_statistics(market_id, stats)?;
-                self.repository
-                    .set_exited_position(self.engine_id, position)?;
-            }
-
-            // ENTRY SCENARIO - FillEvent for Symbol-Exchange with no Position
-            None => {
-                // Enter new Position, & add the PositionNew event to Vec<Event>
-                let position = Position::enter(self.engine_id, fill)?;
-                generated_events.push(Event::PositionNew(position.clone()));
-
-                // Update Portfolio Balance.available on Position entry
-                balance.available += -position.enter_value_gross - position.enter_fees_total;
+        // FillEvents without a client order identifier cannot be matched, so apply immediately
+        if fill.cid.is_none() {
+            return self.apply_fill(fill);
+        }
 
-                // Add to current Positions in Repository
-                self.repository.set_open_position(position)?;
+        // Buffer FillEvent & apply every buffered FillEvent that can now be reconciled
+        self.pending_fills.push_back(fill.clone());
+        let generated_events = self.reconcile_pending_fills()?;
+
+        // Drop the oldest buffered FillEvents exceeding the buffer capacity
+        while self.pending_fills.len() > self.max_pending_fills {
+            if let Some(fill) = self.pending_fills.pop_front() {
+                warn!(
+                    cid = ?fill.cid,
+                    time = %fill.time,
+                    quantity = fill.quantity,
+                    max_pending_fills = self.max_pending_fills,
+                    "dropping oldest buffered FillEvent exceeding the buffer capacity"
+                );
             }
-        };
-
-        // Add new Balance event to the Vec<Event>
-        generated_events.push(Event::Balance(balance));
-
-        // Persist updated Portfolio Balance in Repository
-        self.repository.set_balance(self.engine_id, balance)?;
+        }
 
         Ok(generated_events)
     }
 }
 
-impl<Repository, Allocator, RiskManager, Statistic> PositionHandler
-    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+impl<Repository, Allocator, RiskManager, Statistic>
+    MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
     Allocator: OrderAllocator,
     RiskManager: OrderEvaluator,
     Statistic: Initialiser + PositionSummariser,
 {
-    fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
-        self.repository.set_open_position(position)
-    }
-
-    fn get_open_position(
+    /// Tags the open [`Position`] with the [`CloseReason`] of the exit being generated for it,
+    /// persisting & returning the tagged [`Position`].
+    fn tag_close_reason(
         &mut self,
-        position_id: &PositionId,
-    ) -> Result<Option<Position>, RepositoryError> {
-        self.repository.get_open_position(position_id)
+        mut position: Position,
+        close_reason: CloseReason,
+    ) -> Result<Position, PortfolioError> {
+        position.close_reason = Some(close_reason);
+        self.repository.set_open_position(position.clone())?;
+        Ok(position)
     }
 
-    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
-        &mut self,
-        _: Uuid,
-        markets: Markets,
-    ) -> Result<Vec<Position>, RepositoryError> {
-        self.repository.get_open_positions(self.engine_id, markets)
+    /// Constructs & registers a market [`OrderEvent`] that exits the full quantity of the
+    /// provided open [`Position`].
+    fn register_exit_order(&mut self, position: &Position) -> OrderEvent {
+        let order = OrderEvent {
+            cid: Uuid::new_v4(),
+            time: self.clock.time(),
+            exchange: position.exchange,
+            instrument: position.instrument.clone(),
+            market_meta: MarketMeta {
+                close: position.current_symbol_price,
+                time: position.meta.update_time,
+            },
+            decision: position.determine_exit_decision(),
+            quantity: 0.0 - position.quantity,
+            order_type: OrderType::Market,
+            strategy_id: position.strategy_id.clone(),
+        };
+
+        // Register OrderEvent so it's FillEvent can be matched regardless of arrival order
+        self.orders.insert(order.cid, order.clone());
+
+        order
     }
 
-    fn remove_position(
-        &mut self,
-        position_id: &PositionId,
-    ) -> Result<Option<Position>, RepositoryError> {
-        self.repository.remove_position(position_id)
+    /// Rounds the prices of the provided [`OrderEvent`] to the tick size, and the quantity of an
+    /// entry [`OrderEvent`] down to the quantity increment, of it's [`Market`]'s
+    /// [`InstrumentSpec`].
+    ///
+    /// Entry [`OrderEvent`]s that fall below the minimum quantity or notional value once rounded
+    /// are rejected rather than sent. Exit [`OrderEvent`]s keep the [`Position`] quantity so an
+    /// open [`Position`] can always be closed.
+    fn apply_instrument_spec(&self, mut order: OrderEvent) -> Option<OrderEvent> {
+        let Some(spec) = self
+            .instrument_specs
+            .get(&MarketId::new(order.exchange, &order.instrument))
+        else {
+            return Some(order);
+        };
+
+        order.order_type = match order.order_type {
+            OrderType::Limit { price } => OrderType::Limit {
+                price: spec.price.round(price),
+            },
+            OrderType::Stop { trigger } => OrderType::Stop {
+                trigger: spec.price.round(trigger),
+            },
+            OrderType::StopLimit { trigger, price } => OrderType::StopLimit {
+                trigger: spec.price.round(trigger),
+                price: spec.price.round(price),
+            },
+            order_type => order_type,
+        };
+
+        if order.decision.is_exit() {
+            return Some(order);
+        }
+
+        order.quantity = spec.quantity.round_down(order.quantity);
+        let price = order
+            .order_type
+            .limit_price()
+            .unwrap_or(order.market_meta.close);
+        let notional = order.quantity.abs() * price;
+
+        if order.quantity == 0.0
+            || order.quantity.abs() < spec.quantity.min
+            || notional < spec.notional.min
+        {
+            warn!(
+                exchange = %order.exchange,
+                instrument = %order.instrument,
+                quantity = order.quantity,
+                notional,
+                "rejected OrderEvent below InstrumentSpec minimum quantity or notional"
+            );
+            return None;
+        }
+
+        Some(order)
     }
 
-    fn set_exited_position(&mut self, _: Uuid, position: Position) -> Result<(), RepositoryError> {
-        self.repository
-            .set_exited_position(self.engine_id, position)
+    /// Determines if an exit [`OrderEvent`] for the provided open [`Position`] is awaiting it's
+    /// [`FillEvent`].
+    fn exit_order_pending(&self, position: &Position) -> bool {
+        self.orders.values().any(|order| {
+            order.decision.is_exit() && order_targets_position(self.position_mode, order, position)
+        })
     }
 
-    fn get_exited_positions(&mut self, _: Uuid) -> Result<Vec<Position>, RepositoryError> {
-        self.repository.get_exited_positions(self.engine_id)
+    /// Registers an [`OrderEvent`] that was not generated by this [`MetaPortfolio`] (eg/ one
+    /// restored after a restart) so it's [`FillEvent`] can be matched. Returns the [`Event`]s
+    /// generated by applying any buffered [`FillEvent`]s that can now be reconciled.
+    pub fn register_order(&mut self, order: OrderEvent) -> Result<Vec<Event>, PortfolioError> {
+        self.orders.insert(order.cid, order);
+        self.reconcile_pending_fills()
     }
-}
 
-impl<Repository, Allocator, RiskManager, Statistic> StatisticHandler<Statistic>
-    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
-where
-    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
-    Allocator: OrderAllocator,
-    RiskManager: OrderEvaluator,
-    Statistic: Initialiser + PositionSummariser,
-{
-    fn set_statistics(
-        &mut self,
-        market_id: MarketId,
-        statistic: Statistic,
-    ) -> Result<(), RepositoryError> {
-        self.repository.set_statistics(market_id, statistic)
+    /// Removes a registered [`OrderEvent`] that was cancelled or rejected by the exchange, so it
+    /// no longer awaits a [`FillEvent`]. Any of it's buffered [`FillEvent`]s can no longer be
+    /// reconciled, so are dropped.
+    pub fn cancel_order(&mut self, cid: Uuid) -> Option<OrderEvent> {
+        let order = self.orders.remove(&cid);
+        self.pending_fills.retain(|fill| {
+            let cancelled = fill.cid == Some(cid);
+            if cancelled {
+                warn!(
+                    %cid,
+                    time = %fill.time,
+                    quantity = fill.quantity,
+                    "dropping buffered FillEvent of cancelled OrderEvent"
+                );
+            }
+            !cancelled
+        });
+        order
     }
 
-    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
-        self.repository.get_statistics(market_id)
+    /// Expires registered [`OrderEvent`]s & buffered [`FillEvent`]s that have awaited
+    /// reconciliation for longer than the configured order time-to-live.
+    fn expire_pending(&mut self, now: DateTime<Utc>) {
+        let Some(ttl) = self.order_ttl else {
+            return;
+        };
+
+        let expired_orders = &mut self.expired_orders;
+        self.orders.retain(|cid, order| {
+            let expired = now - order.time > ttl;
+            if expired {
+                warn!(
+                    %cid,
+                    time = %order.time,
+                    quantity = order.quantity,
+                    "expiring OrderEvent that was not filled within it's time-to-live"
+                );
+                expired_orders.push(order.clone());
+            }
+            !expired
+        });
+
+        self.pending_fills.retain(|fill| {
+            let expired = now - fill.time > ttl;
+            if expired {
+                warn!(
+                    cid = ?fill.cid,
+                    time = %fill.time,
+                    quantity = fill.quantity,
+                    "dropping buffered FillEvent that was not reconciled within it's time-to-live"
+                );
+            }
+            !expired
+        });
     }
-}
 
-impl<Repository, Allocator, RiskManager, Statistic>
+    /// Applies every buffered [`FillEvent`] that can be reconciled with it's originating
+    /// [`OrderEvent`]. A [`FillEvent`] can be applied once it's [`OrderEvent`] is registered, and
+    /// if it is an exit, once the [`Position`] it exits is open. An [`OrderEvent`] remains
+    /// registered until it's full quantity has been filled, since it may be partially filled by
+    /// multiple [`FillEvent`]s.
+    fn reconcile_pending_fills(&mut self) -> Result<Vec<Event>, PortfolioError> {
+        let mut generated_events = Vec::new();
+
+        let mut index = 0;
+        while index < self.pending_fills.len() {
+            let fill = &self.pending_fills[index];
+
+            let Some(order) = fill.cid.and_then(|cid| self.orders.get(&cid)) else {
+                debug!(
+                    cid = ?fill.cid,
+                    "buffering FillEvent until it's originating OrderEvent is registered"
+                );
+                index += 1;
+                continue;
+            };
+
+            let position_id = self.position_id(
+                &order.exchange,
+                &order.instrument,
+                order.decision.position_side(),
+            );
+            let position_open = self.repository.get_open_position(&position_id)?.is_some();
+
+            if order.decision.is_exit() && !position_open {
+                debug!(
+                    cid = ?fill.cid,
+                    position_id = &*position_id,
+                    "buffering FillEvent until the preceding FillEvent for it's Position is applied"
+                );
+                index += 1;
+                continue;
+            }
+
+            // Apply reconciled FillEvent, then re-check buffered FillEvents that may depend on it
+            let fill = self
+                .pending_fills
+                .remove(index)
+                .expect("index is within pending_fills bounds");
+            if let Some(cid) = fill.cid {
+                self.deduct_filled_quantity(cid, fill.quantity);
+            }
+            generated_events.extend(self.apply_fill(&fill)?);
+            index = 0;
+        }
+
+        Ok(generated_events)
+    }
+
+    /// Deducts the filled quantity from the registered [`OrderEvent`], removing it once it has
+    /// been fully filled.
+    fn deduct_filled_quantity(&mut self, cid: Uuid, filled_quantity: f64) {
+        let Some(order) = self.orders.get_mut(&cid) else {
+            return;
+        };
+
+        let remaining = order.quantity.abs() - filled_quantity.abs();
+        if remaining <= f64::EPSILON * order.quantity.abs().max(1.0) {
+            self.orders.remove(&cid);
+        } else {
+            order.quantity = remaining.copysign(order.quantity);
+        }
+    }
+
+    /// Updates the Portfolio state using the input [`FillEvent`], entering, increasing, partially
+    /// exiting, exiting or flipping the associated [`Position`].
+    fn apply_fill(&mut self, fill: &FillEvent) -> Result<Vec<Event>, PortfolioError> {
+        // Allocate Vector<Event> to contain any update_from_fill generated events
+        let mut generated_events: Vec<Event> = Vec::with_capacity(2);
+
+        // Get the cash Balance the FillEvent settles in from Repository & update timestamp
+        let currency = &fill.instrument.quote;
+        let mut balance = self.settlement_balance(currency)?;
+        balance.time = fill.time;
+
+        // Determine the position_id that is related to the input FillEvent
+        let position_id = self.position_id(
+            &fill.exchange,
+            &fill.instrument,
+            fill.decision.position_side(),
+        );
+
+        // Determine FillEvent context based on existence or absence of an open Position
+        match self.repository.remove_position(&position_id)? {
+            // FillEvent for Symbol-Exchange combination with open Position
+            Some(mut position) => match position.determine_fill_effect(fill) {
+                // INCREASE SCENARIO - entry FillEvent in the same direction as the open Position
+                FillEffect::Increase => {
+                    let position_update = position.increase(fill);
+                    generated_events.push(Event::PositionUpdate(position_update));
+
+                    // Update Portfolio Balance.available with the margin posted on Position increase
+                    balance.available -= fill.fill_value_gross / position.leverage
+                        + fill.fees.calculate_total_fees();
+
+                    self.margins.insert(position_id, position_margin(&position));
+                    self.repository.set_open_position(position)?;
+                }
+
+                // PARTIAL EXIT SCENARIO - FillEvent closing part of the open Position
+                FillEffect::PartialExit => {
+                    let enter_value_gross = position.enter_value_gross;
+                    let enter_fees_total = position.enter_fees_total;
+                    let realised_profit_loss = position.reduce(fill);
+                    generated_events
+                        .push(Event::PositionUpdate(PositionUpdate::from(&mut position)));
+
+                    // Update Portfolio balance with the released enter margin & fees
+                    balance.available += (enter_value_gross - position.enter_value_gross)
+                        / position.leverage
+                        + (enter_fees_total - position.enter_fees_total)
+                        + realised_profit_loss;
+                    balance.total += realised_profit_loss;
+
+                    self.margins.insert(position_id, position_margin(&position));
+                    self.repository.set_open_position(position)?;
+                }
+
+                // EXIT SCENARIO - FillEvent closing the full open Position
+                FillEffect::Exit => {
+                    self.exit_position(position, &mut balance, fill, &mut generated_events)?
+                }
+
+                // FLIP SCENARIO - exit the full open Position & enter the remainder
+                FillEffect::Flip => {
+                    // Opposing entries are always advised by a Signal
+                    position.close_reason = Some(CloseReason::Signal);
+                    let (exit_fill, entry_fill) = position.split_flip_fill(fill);
+                    self.exit_position(position, &mut balance, &exit_fill, &mut generated_events)?;
+                    self.enter_position(&mut balance, &entry_fill, &mut generated_events)?;
+                }
+            },
+
+            // ENTRY SCENARIO - FillEvent for Symbol-Exchange with no Position
+            None => self.enter_position(&mut balance, fill, &mut generated_events)?,
+        };
+
+        // Persist updated settlement Balance, deriving the Portfolio Balance in Repository
+        let balance = self.settle_balance(currency, balance)?;
+
+        // Add new Balance event to the Vec<Event>
+        generated_events.push(Event::Balance(balance));
+
+        // Add Bankruptcy event to the Vec<Event> if total equity has been wiped out
+        if balance.is_bankrupt() {
+            if let Some(bankruptcy) = self.declare_bankruptcy(Bankruptcy::from(balance)) {
+                generated_events.push(Event::Bankruptcy(bankruptcy));
+            }
+        }
+
+        // Update any equity dependent allocation & risk state (eg/ daily loss limits)
+        self.update_from_equity(fill.time)?;
+
+        Ok(generated_events)
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic>
+    MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+{
+    /// Enters a new [`Position`] using the input entry [`FillEvent`], updating the Portfolio
+    /// [`Balance`].
+    fn enter_position(
+        &mut self,
+        balance: &mut Balance,
+        fill: &FillEvent,
+        generated_events: &mut Vec<Event>,
+    ) -> Result<(), PortfolioError> {
+        // Enter new Position with the configured leverage, & add the PositionNew event to Vec<Event>
+        let mut position = Position::enter(self.engine_id, fill)?;
+        position.position_id =
+            self.position_id(&position.exchange, &position.instrument, position.side);
+        position.leverage = self.max_leverage;
+        generated_events.push(Event::PositionNew(position.clone()));
+
+        // Update Portfolio Balance.available with the margin posted on Position entry
+        balance.available +=
+            -position.enter_value_gross / position.leverage - position.enter_fees_total;
+        self.margins
+            .insert(position.position_id.clone(), position_margin(&position));
+
+        // Add to current Positions in Repository
+        self.repository.set_open_position(position)?;
+        Ok(())
+    }
+
+    /// Exits the full open quantity of the [`Position`] using the input exit [`FillEvent`],
+    /// updating the Portfolio [`Balance`] & market statistics.
+    fn exit_position(
+        &mut self,
+        mut position: Position,
+        balance: &mut Balance,
+        fill: &FillEvent,
+        generated_events: &mut Vec<Event>,
+    ) -> Result<(), PortfolioError> {
+        // Exit Position (in place mutation), & add the PositionExit event to Vec<Event>
+        let prev_realised_profit_loss = position.realised_profit_loss;
+        let position_exit = position.exit(*balance, fill)?;
+        generated_events.push(Event::PositionExit(position_exit));
+
+        // Update Portfolio balance on Position exit, excluding P&L realised by partial exits
+        // '--> available balance adds enter_total_fees since included in result PnL calc
+        let exit_profit_loss = position.realised_profit_loss - prev_realised_profit_loss;
+        balance.available += position.enter_value_gross / position.leverage
+            + exit_profit_loss
+            + position.enter_fees_total;
+        balance.total += exit_profit_loss;
+        self.margins.remove(&position.position_id);
+        self.margin_calls.remove(&position.position_id);
+
+        // Update statistics for exited Position market
+        let market_id = statistic_market_id(fill.exchange, &fill.instrument);
+
+        let mut stats = self.repository.get_statistics(self.engine_id, &market_id)?;
+        stats.update(&position);
+        self.allocation_manager.update_from_exit(&position);
+        self.risk_manager.update_from_exit(&position);
+        if let Some(volatility_target) = &mut self.volatility_target {
+            volatility_target.update_from_exit(&position);
+        }
+
+        // Persist exited Position & Updated Market statistics in Repository
+        self.repository
+            .set_statistics(self.engine_id, market_id, stats)?;
+        self.repository
+            .set_exited_position(self.engine_id, position)?;
+        Ok(())
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic> PortfolioReporter
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+{
+    fn equity(&mut self) -> Result<f64, PortfolioError> {
+        Ok(self.margin_account()?.equity())
+    }
+
+    fn open_positions(&self) -> usize {
+        // Margin is tracked for every open Position from entry until exit
+        self.margins.len()
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic> PositionHandler
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+{
+    fn set_open_position(&mut self, position: Position) -> Result<(), RepositoryError> {
+        self.repository.set_open_position(position)
+    }
+
+    fn get_open_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.repository.get_open_position(position_id)
+    }
+
+    fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
+        &mut self,
+        _: Uuid,
+        markets: Markets,
+    ) -> Result<Vec<Position>, RepositoryError> {
+        self.repository.get_open_positions(self.engine_id, markets)
+    }
+
+    fn remove_position(
+        &mut self,
+        position_id: &PositionId,
+    ) -> Result<Option<Position>, RepositoryError> {
+        self.repository.remove_position(position_id)
+    }
+
+    fn set_exited_position(&mut self, _: Uuid, position: Position) -> Result<(), RepositoryError> {
+        self.repository
+            .set_exited_position(self.engine_id, position)
+    }
+
+    fn get_exited_positions(&mut self, _: Uuid) -> Result<Vec<Position>, RepositoryError> {
+        self.repository.get_exited_positions(self.engine_id)
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic> StatisticHandler<Statistic>
+    for MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
+where
+    Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+    Allocator: OrderAllocator,
+    RiskManager: OrderEvaluator,
+    Statistic: Initialiser + PositionSummariser,
+{
+    fn set_statistics(
+        &mut self,
+        engine_id: Uuid,
+        market_id: MarketId,
+        statistic: Statistic,
+    ) -> Result<(), RepositoryError> {
+        self.repository
+            .set_statistics(engine_id, market_id, statistic)
+    }
+
+    fn get_statistics(
+        &mut self,
+        engine_id: Uuid,
+        market_id: &MarketId,
+    ) -> Result<Statistic, RepositoryError> {
+        self.repository.get_statistics(engine_id, market_id)
+    }
+}
+
+impl<Repository, Allocator, RiskManager, Statistic>
     MetaPortfolio<Repository, Allocator, RiskManager, Statistic>
 where
     Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
@@ -346,12 +1191,35 @@ where
             repository: lego.repository,
             allocation_manager: lego.allocator,
             risk_manager: lego.risk,
+            entry_order_type: EntryOrderType::default(),
+            position_mode: PositionMode::default(),
+            max_leverage: lego.max_leverage,
+            maintenance_margin_rate: lego.maintenance_margin_rate,
+            fx_rates: None,
+            instrument_specs: HashMap::new(),
+            statistic_markets: HashSet::new(),
+            margins: HashMap::new(),
+            margin_calls: HashSet::new(),
+            orders: HashMap::new(),
+            pending_fills: VecDeque::new(),
+            expired_orders: Vec::new(),
+            max_pending_fills: DEFAULT_MAX_PENDING_FILLS,
+            order_ttl: None,
+            correlations: CorrelationTracker::new(CorrelationConfig::default()),
+            funding: None,
+            volatility_target: None,
+            clock: Arc::new(SystemClock),
+            halt_on_bankruptcy: true,
+            bankruptcy: None,
             _statistic_marker: PhantomData,
         };
 
         // Persist initial state in the repository
         portfolio.bootstrap_repository(lego.starting_cash, &lego.markets, lego.statistic_config)?;
 
+        // Restore margin state from Positions left open before a restart
+        portfolio.restore_margins(&lego.markets)?;
+
         Ok(portfolio)
     }
 
@@ -368,21 +1236,48 @@ where
         Id: Into<MarketId>,
     {
         // Persist initial Balance (total & available)
-        self.repository.set_balance(
-            self.engine_id,
-            Balance {
-                time: Utc::now(),
-                total: starting_cash,
-                available: starting_cash,
-            },
-        )?;
+        let balance = Balance {
+            time: self.clock.time(),
+            total: starting_cash,
+            available: starting_cash,
+        };
+        self.repository.set_balance(self.engine_id, balance)?;
+
+        // Starting cash is held in the base currency when trading multiple quote currencies
+        if let Some(fx_rates) = &self.fx_rates {
+            self.repository
+                .set_currency_balance(self.engine_id, fx_rates.base.clone(), balance)?;
+        }
 
         // Persist initial MetaPortfolio Statistics for every Market
         markets.into_iter().try_for_each(|market| {
+            let market_id = market.into();
+            self.statistic_markets.insert(market_id.clone());
             self.repository
-                .set_statistics(market.into(), Statistic::init(statistic_config))
+                .set_statistics(self.engine_id, market_id, Statistic::init(statistic_config))
                 .map_err(PortfolioError::RepositoryInteraction)
-        })
+        })?;
+
+        // Restore risk state (eg/ trade cooldowns) from Positions exited before a restart
+        self.repository
+            .get_exited_positions(self.engine_id)?
+            .iter()
+            .for_each(|position| self.risk_manager.update_from_exit(position));
+
+        Ok(())
+    }
+
+    /// Rebuild the [`PositionMargin`] of every [`Position`] held open in the Repository for the
+    /// provided markets, so margin accounting & liquidations survive a restart.
+    fn restore_margins(&mut self, markets: &[Market]) -> Result<(), PortfolioError> {
+        self.margins = self
+            .repository
+            .get_open_positions(self.engine_id, markets.iter())?
+            .iter()
+            .map(|position| (position.position_id.clone(), position_margin(position)))
+            .collect();
+
+        Ok(())
     }
 
     /// Returns a [`MetaPortfolioBuilder`] instance.
@@ -390,6 +1285,180 @@ where
         MetaPortfolioBuilder::new()
     }
 
+    /// Returns the [`MarginAccount`] detailing the used & available margin across every open
+    /// [`Position`].
+    pub fn margin_account(&mut self) -> Result<MarginAccount, PortfolioError> {
+        let balance = self.repository.get_balance(self.engine_id)?;
+        let margins = self
+            .margins
+            .values()
+            .map(|(currency, margin)| self.convert_margin(currency, *margin))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(MarginAccount::new(self.max_leverage, &balance, &margins))
+    }
+
+    /// Returns the EWMA volatility of each market the Portfolio has received [`MarketEvent`]s
+    /// for, & the pairwise correlations between them.
+    pub fn correlations(&self) -> &CorrelationTracker {
+        &self.correlations
+    }
+
+    /// Returns the EWMA volatility of the provided market, if enough returns have been observed.
+    pub fn volatility(&self, market: &MarketId) -> Option<f64> {
+        self.correlations.volatility(market)
+    }
+
+    /// Returns the EWMA correlation between the provided markets, if enough overlapping returns
+    /// have been observed.
+    pub fn correlation(&self, a: &MarketId, b: &MarketId) -> Option<f64> {
+        self.correlations.correlation(a, b)
+    }
+
+    /// Returns the [`PositionId`] of the [`Position`] with the provided [`Side`] in a market,
+    /// as determined by the configured [`PositionMode`].
+    fn position_id(
+        &self,
+        exchange: &ExchangeId,
+        instrument: &Instrument,
+        side: Side,
+    ) -> PositionId {
+        self.position_mode
+            .position_id(self.engine_id, exchange, instrument, side)
+    }
+
+    /// Returns the [`PositionId`] of every [`Position`] that may be open in a market, as
+    /// determined by the configured [`PositionMode`].
+    fn market_position_ids(
+        &self,
+        exchange: &ExchangeId,
+        instrument: &Instrument,
+    ) -> Vec<PositionId> {
+        self.position_mode
+            .market_position_ids(self.engine_id, exchange, instrument)
+    }
+
+    /// Updates the allocation & risk managers with the latest Portfolio equity (see
+    /// [`MarginAccount::equity`]), returning it.
+    fn update_from_equity(&mut self, time: DateTime<Utc>) -> Result<f64, PortfolioError> {
+        let equity = self.margin_account()?.equity();
+        self.allocation_manager.update_from_equity(time, equity);
+        self.risk_manager.update_from_equity(time, equity);
+        Ok(equity)
+    }
+
+    /// Converts a [`PositionMargin`] denominated in the provided quote currency into the base
+    /// currency if trading multiple quote currencies.
+    fn convert_margin(
+        &self,
+        currency: &Symbol,
+        margin: PositionMargin,
+    ) -> Result<PositionMargin, PortfolioError> {
+        let Some(fx_rates) = &self.fx_rates else {
+            return Ok(margin);
+        };
+
+        let rate = fx_rates.rate(currency)?;
+        Ok(PositionMargin {
+            used: margin.used * rate,
+            balance: margin.balance * rate,
+        })
+    }
+
+    /// Debits or credits the funding P&L accrued on the open [`Position`] to the cash
+    /// [`Balance`] of it's quote currency.
+    fn settle_funding(
+        &mut self,
+        position: &Position,
+        funding_profit_loss: f64,
+        time: DateTime<Utc>,
+    ) -> Result<Balance, PortfolioError> {
+        let currency = &position.instrument.quote;
+        let mut balance = self.settlement_balance(currency)?;
+        balance.time = time;
+        balance.total += funding_profit_loss;
+        balance.available += funding_profit_loss;
+
+        self.settle_balance(currency, balance)
+    }
+
+    /// Returns the cash [`Balance`] of the currency a [`FillEvent`] settles in. If trading
+    /// multiple quote currencies this is the [`Balance`] of that currency, otherwise it is the
+    /// Portfolio [`Balance`].
+    fn settlement_balance(&mut self, currency: &Symbol) -> Result<Balance, PortfolioError> {
+        let Some(fx_rates) = &self.fx_rates else {
+            return Ok(self.repository.get_balance(self.engine_id)?);
+        };
+
+        // Ensure the settlement currency can be converted before any state is modified
+        fx_rates.rate(currency)?;
+
+        Ok(self
+            .repository
+            .get_currency_balances(self.engine_id)?
+            .remove(currency)
+            .unwrap_or_default())
+    }
+
+    /// Persists the updated cash [`Balance`] of the settlement currency, returning the updated
+    /// Portfolio [`Balance`] in the base currency.
+    fn settle_balance(
+        &mut self,
+        currency: &Symbol,
+        balance: Balance,
+    ) -> Result<Balance, PortfolioError> {
+        if self.fx_rates.is_none() {
+            self.repository.set_balance(self.engine_id, balance)?;
+            return Ok(balance);
+        }
+
+        self.repository
+            .set_currency_balance(self.engine_id, currency.clone(), balance)?;
+        self.revalue_balance(balance.time)
+    }
+
+    /// Derives & persists the Portfolio [`Balance`] by converting the [`Balance`] of every
+    /// currency held into the base currency using the latest [`FxRates`].
+    fn revalue_balance(&mut self, time: DateTime<Utc>) -> Result<Balance, PortfolioError> {
+        let Some(fx_rates) = &self.fx_rates else {
+            return Ok(self.repository.get_balance(self.engine_id)?);
+        };
+
+        let balance = self
+            .repository
+            .get_currency_balances(self.engine_id)?
+            .iter()
+            .try_fold(
+                Balance::new(time, 0.0, 0.0),
+                |mut base, (currency, balance)| {
+                    let rate = fx_rates.rate(currency)?;
+                    base.total += balance.total * rate;
+                    base.available += balance.available * rate;
+                    Ok::<_, PortfolioError>(base)
+                },
+            )?;
+
+        self.repository.set_balance(self.engine_id, balance)?;
+        Ok(balance)
+    }
+
+    /// Records the provided [`Bankruptcy`] if configured to halt on bankruptcy, returning it if
+    /// the Portfolio was not already bankrupt.
+    fn declare_bankruptcy(&mut self, bankruptcy: Bankruptcy) -> Option<Bankruptcy> {
+        if !self.halt_on_bankruptcy || self.bankruptcy.is_some() {
+            return None;
+        }
+
+        warn!(
+            engine_id = %self.engine_id,
+            time = %bankruptcy.time,
+            equity = bankruptcy.equity,
+            "Portfolio is bankrupt"
+        );
+        self.bankruptcy = Some(bankruptcy);
+        Some(bankruptcy)
+    }
+
     /// Determines if the Portfolio has any cash to enter a new [`Position`].
     fn no_cash_to_enter_new_position(&mut self) -> Result<bool, PortfolioError> {
         self.repository
@@ -413,7 +1482,20 @@ where
     repository: Option<Repository>,
     allocation_manager: Option<Allocator>,
     risk_manager: Option<RiskManager>,
+    entry_order_type: Option<EntryOrderType>,
+    position_mode: Option<PositionMode>,
+    max_leverage: Option<f64>,
+    maintenance_margin_rate: Option<f64>,
+    fx_rates: Option<FxRates>,
+    instrument_specs: Option<HashMap<MarketId, InstrumentSpec<Symbol>>>,
     statistic_config: Option<Statistic::Config>,
+    correlation_config: Option<CorrelationConfig>,
+    funding: Option<FundingAccrual>,
+    volatility_target: Option<VolatilityTarget>,
+    clock: Option<Arc<dyn Clock>>,
+    halt_on_bankruptcy: Option<bool>,
+    max_pending_fills: Option<usize>,
+    order_ttl: Option<Duration>,
     _statistic_marker: Option<PhantomData<Statistic>>,
 }
 
@@ -433,7 +1515,20 @@ where
             repository: None,
             allocation_manager: None,
             risk_manager: None,
+            entry_order_type: None,
+            position_mode: None,
+            max_leverage: None,
+            maintenance_margin_rate: None,
+            fx_rates: None,
+            instrument_specs: None,
             statistic_config: None,
+            correlation_config: None,
+            funding: None,
+            volatility_target: None,
+            clock: None,
+            halt_on_bankruptcy: None,
+            max_pending_fills: None,
+            order_ttl: None,
             _statistic_marker: None,
         }
     }
@@ -480,59 +1575,224 @@ where
         }
     }
 
-    pub fn statistic_config(self, value: Statistic::Config) -> Self {
+    pub fn entry_order_type(self, value: EntryOrderType) -> Self {
         Self {
-            statistic_config: Some(value),
+            entry_order_type: Some(value),
             ..self
         }
     }
 
-    pub fn build_and_init(
-        self,
-    ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic>, PortfolioError> {
-        // Construct Portfolio
-        let mut portfolio = MetaPortfolio {
-            engine_id: self
-                .engine_id
-                .ok_or(PortfolioError::BuilderIncomplete("engine_id"))?,
-            repository: self
-                .repository
-                .ok_or(PortfolioError::BuilderIncomplete("repository"))?,
-            allocation_manager: self
-                .allocation_manager
-                .ok_or(PortfolioError::BuilderIncomplete("allocation_manager"))?,
-            risk_manager: self
-                .risk_manager
-                .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
-            _statistic_marker: PhantomData,
-        };
+    /// Optional [`PositionMode`] determining if opposing [`Position`]s in the same market are
+    /// netted or held separately, defaulting to [`PositionMode::Netting`].
+    pub fn position_mode(self, value: PositionMode) -> Self {
+        Self {
+            position_mode: Some(value),
+            ..self
+        }
+    }
 
-        // Persist initial state in the Repository
-        portfolio.bootstrap_repository(
-            self.starting_cash
-                .ok_or(PortfolioError::BuilderIncomplete("starting_cash"))?,
-            &self
-                .markets
-                .ok_or(PortfolioError::BuilderIncomplete("markets"))?,
-            self.statistic_config
-                .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?,
-        )?;
+    pub fn max_leverage(self, value: f64) -> Self {
+        Self {
+            max_leverage: Some(value),
+            ..self
+        }
+    }
 
-        Ok(portfolio)
+    pub fn maintenance_margin_rate(self, value: f64) -> Self {
+        Self {
+            maintenance_margin_rate: Some(value),
+            ..self
+        }
     }
-}
 
-/// Parses an incoming [`Signal`]'s signals map. Determines what the net signal [`Decision`]
-/// will be, and it's associated [`SignalStrength`].
-pub fn parse_signal_decisions<'a>(
-    position: &'a Option<&Position>,
-    signals: &'a HashMap<Decision, SignalStrength>,
-) -> Option<(&'a Decision, &'a SignalStrength)> {
-    // Determine the presence of signals in the provided signals HashMap
-    let signal_close_long = signals.get_key_value(&Decision::CloseLong);
-    let signal_long = signals.get_key_value(&Decision::Long);
-    let signal_close_short = signals.get_key_value(&Decision::CloseShort);
-    let signal_short = signals.get_key_value(&Decision::Short);
+    pub fn fx_rates(self, value: FxRates) -> Self {
+        Self {
+            fx_rates: Some(value),
+            ..self
+        }
+    }
+
+    pub fn instrument_specs(self, value: HashMap<MarketId, InstrumentSpec<Symbol>>) -> Self {
+        Self {
+            instrument_specs: Some(value),
+            ..self
+        }
+    }
+
+    pub fn statistic_config(self, value: Statistic::Config) -> Self {
+        Self {
+            statistic_config: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional [`CorrelationConfig`] of the EWMA volatility & correlation estimates, defaulting
+    /// to a lambda of 0.94.
+    pub fn correlation_config(self, value: CorrelationConfig) -> Self {
+        Self {
+            correlation_config: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional [`FundingAccrual`] that debits & credits the perpetual funding payments of open
+    /// [`Position`]s. Funding is not modelled if omitted.
+    pub fn funding(self, value: FundingAccrual) -> Self {
+        Self {
+            funding: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional [`VolatilityTarget`] scaling the exposure of open [`Position`]s toward a target
+    /// annualised Portfolio volatility. Exposure is not volatility targeted if omitted.
+    pub fn volatility_target(self, value: VolatilityTarget) -> Self {
+        Self {
+            volatility_target: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional [`Clock`] used by the [`MetaPortfolio`], defaulting to the wall-clock
+    /// [`SystemClock`].
+    pub fn clock<C>(self, value: C) -> Self
+    where
+        C: Clock + 'static,
+    {
+        Self {
+            clock: Some(Arc::new(value)),
+            ..self
+        }
+    }
+
+    /// Optional flag determining if the [`MetaPortfolio`] declares a [`Bankruptcy`], halting
+    /// every Trader trading it, once it's equity falls to, or below, zero. Defaults to true.
+    pub fn halt_on_bankruptcy(self, value: bool) -> Self {
+        Self {
+            halt_on_bankruptcy: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional maximum number of [`FillEvent`]s buffered while awaiting reconciliation, beyond
+    /// which the oldest are dropped. Defaults to [`DEFAULT_MAX_PENDING_FILLS`].
+    pub fn max_pending_fills(self, value: usize) -> Self {
+        Self {
+            max_pending_fills: Some(value),
+            ..self
+        }
+    }
+
+    /// Optional time after which registered [`OrderEvent`]s & buffered [`FillEvent`]s still
+    /// awaiting reconciliation are expired. They never expire if omitted. Expired
+    /// [`OrderEvent`]s are cancelled with the execution handler via an
+    /// [`Event::OrderCancel`].
+    pub fn order_ttl(self, value: Duration) -> Self {
+        Self {
+            order_ttl: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build_and_init(
+        self,
+    ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic>, PortfolioError> {
+        // Construct Portfolio
+        let mut portfolio = MetaPortfolio {
+            engine_id: self
+                .engine_id
+                .ok_or(PortfolioError::BuilderIncomplete("engine_id"))?,
+            repository: self
+                .repository
+                .ok_or(PortfolioError::BuilderIncomplete("repository"))?,
+            allocation_manager: self
+                .allocation_manager
+                .ok_or(PortfolioError::BuilderIncomplete("allocation_manager"))?,
+            risk_manager: self
+                .risk_manager
+                .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            entry_order_type: self.entry_order_type.unwrap_or_default(),
+            position_mode: self.position_mode.unwrap_or_default(),
+            max_leverage: self.max_leverage.unwrap_or(DEFAULT_MAX_LEVERAGE),
+            maintenance_margin_rate: self
+                .maintenance_margin_rate
+                .unwrap_or(DEFAULT_MAINTENANCE_MARGIN_RATE),
+            fx_rates: self.fx_rates,
+            instrument_specs: self.instrument_specs.unwrap_or_default(),
+            statistic_markets: HashSet::new(),
+            margins: HashMap::new(),
+            margin_calls: HashSet::new(),
+            orders: HashMap::new(),
+            pending_fills: VecDeque::new(),
+            expired_orders: Vec::new(),
+            max_pending_fills: self.max_pending_fills.unwrap_or(DEFAULT_MAX_PENDING_FILLS),
+            order_ttl: self.order_ttl,
+            correlations: CorrelationTracker::new(self.correlation_config.unwrap_or_default()),
+            funding: self.funding,
+            volatility_target: self.volatility_target,
+            clock: self.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+            halt_on_bankruptcy: self.halt_on_bankruptcy.unwrap_or(true),
+            bankruptcy: None,
+            _statistic_marker: PhantomData,
+        };
+
+        // Persist initial state in the Repository
+        let markets = self
+            .markets
+            .ok_or(PortfolioError::BuilderIncomplete("markets"))?;
+        portfolio.bootstrap_repository(
+            self.starting_cash
+                .ok_or(PortfolioError::BuilderIncomplete("starting_cash"))?,
+            &markets,
+            self.statistic_config
+                .ok_or(PortfolioError::BuilderIncomplete("statistic_config"))?,
+        )?;
+
+        // Restore margin state from Positions left open before a restart
+        portfolio.restore_margins(&markets)?;
+
+        Ok(portfolio)
+    }
+}
+
+/// Determines if the provided [`OrderEvent`] enters or exits the provided [`Position`], given the
+/// [`PositionMode`] it is held in.
+fn order_targets_position(
+    position_mode: PositionMode,
+    order: &OrderEvent,
+    position: &Position,
+) -> bool {
+    order.exchange == position.exchange
+        && order.instrument == position.instrument
+        && (position_mode == PositionMode::Netting
+            || order.decision.position_side() == position.side)
+}
+
+/// Returns the [`MarketId`] the Statistics of a market are keyed by, matching the [`MarketId`]
+/// they are initialised with from each [`Market`] (& that the Engine reads them with).
+fn statistic_market_id(exchange: ExchangeId, instrument: &Instrument) -> MarketId {
+    MarketId::from(&Market::<Instrument>::new(exchange, instrument.clone()))
+}
+
+/// Returns the quote currency & [`PositionMargin`] of the provided [`Position`].
+fn position_margin(position: &Position) -> (Symbol, PositionMargin) {
+    (
+        position.instrument.quote.clone(),
+        PositionMargin::from(position),
+    )
+}
+
+/// Parses an incoming [`Signal`]'s signals map. Determines what the net signal [`Decision`]
+/// will be, and it's associated [`SignalStrength`].
+pub fn parse_signal_decisions<'a>(
+    position: &'a Option<&Position>,
+    signals: &'a HashMap<Decision, SignalStrength>,
+) -> Option<(&'a Decision, &'a SignalStrength)> {
+    // Determine the presence of signals in the provided signals HashMap
+    let signal_close_long = signals.get_key_value(&Decision::CloseLong);
+    let signal_long = signals.get_key_value(&Decision::EnterLong);
+    let signal_close_short = signals.get_key_value(&Decision::CloseShort);
+    let signal_short = signals.get_key_value(&Decision::EnterShort);
 
     // If an existing Position exists, check for net close signals
     if let Some(position) = position {
@@ -551,23 +1811,100 @@ pub fn parse_signal_decisions<'a>(
     }
 }
 
+/// Parses an incoming [`Signal`]'s signals map to determine the net signal [`Decision`] when
+/// holding [`Position`]s in [`PositionMode::Hedging`], given the open long and/or short
+/// [`Position`]s of the market.
+///
+/// Exits of an open [`Position`] take precedence, long before short. Otherwise a single entry
+/// [`Decision`] either scales into the open [`Position`] of it's side, or opens a new one
+/// alongside any opposing [`Position`] rather than closing it.
+pub fn parse_hedged_signal_decisions<'a>(
+    positions: &[Position],
+    signals: &'a HashMap<Decision, SignalStrength>,
+) -> Option<(&'a Decision, &'a SignalStrength)> {
+    let is_open = |side: Side| positions.iter().any(|position| position.side == side);
+
+    // If an existing Position exists, check for it's close signal
+    if is_open(Side::Buy) {
+        if let Some(signal_close_long) = signals.get_key_value(&Decision::CloseLong) {
+            return Some(signal_close_long);
+        }
+    }
+    if is_open(Side::Sell) {
+        if let Some(signal_close_short) = signals.get_key_value(&Decision::CloseShort) {
+            return Some(signal_close_short);
+        }
+    }
+
+    // Else check for net open signals
+    match (
+        signals.get_key_value(&Decision::EnterLong),
+        signals.get_key_value(&Decision::EnterShort),
+    ) {
+        (Some(signal_long), None) => Some(signal_long),
+        (None, Some(signal_short)) => Some(signal_short),
+        _ => None,
+    }
+}
+
+/// Parses an incoming [`Signal`]'s signals map for a repeated entry [`Decision`] in the direction
+/// of the open [`Position`], which an [`OrderAllocator`] may use to scale into the [`Position`].
+pub fn parse_scale_in_decision<'a>(
+    position: &'a Option<&Position>,
+    signals: &'a HashMap<Decision, SignalStrength>,
+) -> Option<(&'a Decision, &'a SignalStrength)> {
+    match (*position)?.side {
+        Side::Buy => signals.get_key_value(&Decision::EnterLong),
+        Side::Sell => signals.get_key_value(&Decision::EnterShort),
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
     use super::*;
     use crate::{
-        execution::Fees,
+        clock::SimulatedClock,
+        data::determine_market_close,
+        execution::{
+            router::SmartOrderRouter,
+            simulated::{Config as ExecutionConfig, SimulatedExecution},
+            ExecutionClient, Fees,
+        },
         portfolio::{
-            allocator::DefaultAllocator, position::PositionBuilder,
-            repository::error::RepositoryError, risk::DefaultRisk,
+            allocator::{DcaAllocator, DcaConfig, DefaultAllocator},
+            position::{determine_position_id, PositionBuilder},
+            repository::{error::RepositoryError, in_memory::InMemoryRepository},
+            risk::{
+                CooldownConfig, CooldownRisk, DefaultRisk, StopLossConfig, StopLossLimits,
+                StopLossRisk,
+            },
+            volatility_target::VolatilityTargetConfig,
+        },
+        statistic::summary::{
+            close_reason::CloseReasonBreakdown,
+            pnl::PnLReturnSummary,
+            strategy::StrategyBreakdown,
+            trading::{Config as StatisticConfig, TradingSummary},
         },
-        statistic::summary::pnl::PnLReturnSummary,
-        strategy::SignalForceExit,
-        test_util::{fill_event, market_event_trade, position, signal},
+        strategy::{tagged::TaggedStrategy, SignalForceExit, SignalGenerator, StrategyId},
+        test_util::{fill_event, market_event_trade, order_event, position, signal},
+    };
+    use barter_data::{
+        books::Level,
+        subscription::{book::OrderBookL1, funding::FundingRate, trade::PublicTrade},
     };
     use barter_instrument::{
         exchange::ExchangeId,
-        instrument::{kind::InstrumentKind, Instrument},
+        instrument::{
+            kind::InstrumentKind,
+            spec::{
+                InstrumentSpecNotional, InstrumentSpecPrice, InstrumentSpecQuantity,
+                OrderQuantityUnits,
+            },
+            Instrument,
+        },
     };
+    use chrono::Duration;
     use smol_str::SmolStr;
 
     #[derive(Default)]
@@ -585,11 +1922,18 @@ pub mod tests {
         get_exited_positions: Option<fn(engine_id: Uuid) -> Result<Vec<Position>, RepositoryError>>,
         set_balance: Option<fn(engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError>>,
         get_balance: Option<fn(engine_id: Uuid) -> Result<Balance, RepositoryError>>,
-        set_statistics:
-            Option<fn(market_id: MarketId, statistic: Statistic) -> Result<(), RepositoryError>>,
-        get_statistics: Option<fn(market_id: &MarketId) -> Result<Statistic, RepositoryError>>,
+        set_statistics: Option<
+            fn(
+                engine_id: Uuid,
+                market_id: MarketId,
+                statistic: Statistic,
+            ) -> Result<(), RepositoryError>,
+        >,
+        get_statistics:
+            Option<fn(engine_id: Uuid, market_id: &MarketId) -> Result<Statistic, RepositoryError>>,
         position: Option<PositionBuilder>,
         balance: Option<Balance>,
+        currency_balances: HashMap<Symbol, Balance>,
     }
 
     impl<Statistic> PositionHandler for MockRepository<Statistic> {
@@ -662,19 +2006,41 @@ pub mod tests {
         fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError> {
             self.get_balance.unwrap()(engine_id)
         }
+
+        fn set_currency_balance(
+            &mut self,
+            _: Uuid,
+            currency: Symbol,
+            balance: Balance,
+        ) -> Result<(), RepositoryError> {
+            self.currency_balances.insert(currency, balance);
+            Ok(())
+        }
+
+        fn get_currency_balances(
+            &mut self,
+            _: Uuid,
+        ) -> Result<HashMap<Symbol, Balance>, RepositoryError> {
+            Ok(self.currency_balances.clone())
+        }
     }
 
     impl<Statistic> StatisticHandler<Statistic> for MockRepository<Statistic> {
         fn set_statistics(
             &mut self,
+            engine_id: Uuid,
             market_id: MarketId,
             statistic: Statistic,
         ) -> Result<(), RepositoryError> {
-            self.set_statistics.unwrap()(market_id, statistic)
+            self.set_statistics.unwrap()(engine_id, market_id, statistic)
         }
 
-        fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
-            self.get_statistics.unwrap()(market_id)
+        fn get_statistics(
+            &mut self,
+            engine_id: Uuid,
+            market_id: &MarketId,
+        ) -> Result<Statistic, RepositoryError> {
+            self.get_statistics.unwrap()(engine_id, market_id)
         }
     }
 
@@ -697,11 +2063,13 @@ pub mod tests {
         build_uninitialised_portfolio(builder)
     }
 
-    fn build_uninitialised_portfolio<Repository, Statistic>(
-        builder: MetaPortfolioBuilder<Repository, DefaultAllocator, DefaultRisk, Statistic>,
-    ) -> Result<MetaPortfolio<Repository, DefaultAllocator, DefaultRisk, Statistic>, PortfolioError>
+    fn build_uninitialised_portfolio<Repository, Allocator, RiskManager, Statistic>(
+        builder: MetaPortfolioBuilder<Repository, Allocator, RiskManager, Statistic>,
+    ) -> Result<MetaPortfolio<Repository, Allocator, RiskManager, Statistic>, PortfolioError>
     where
         Repository: PositionHandler + BalanceHandler + StatisticHandler<Statistic>,
+        Allocator: OrderAllocator,
+        RiskManager: OrderEvaluator,
         Statistic: PositionSummariser + Initialiser,
     {
         Ok(MetaPortfolio {
@@ -717,18 +2085,107 @@ pub mod tests {
             risk_manager: builder
                 .risk_manager
                 .ok_or(PortfolioError::BuilderIncomplete("risk_manager"))?,
+            entry_order_type: builder.entry_order_type.unwrap_or_default(),
+            position_mode: builder.position_mode.unwrap_or_default(),
+            max_leverage: builder.max_leverage.unwrap_or(DEFAULT_MAX_LEVERAGE),
+            maintenance_margin_rate: builder
+                .maintenance_margin_rate
+                .unwrap_or(DEFAULT_MAINTENANCE_MARGIN_RATE),
+            fx_rates: builder.fx_rates,
+            instrument_specs: builder.instrument_specs.unwrap_or_default(),
+            statistic_markets: HashSet::new(),
+            margins: HashMap::new(),
+            margin_calls: HashSet::new(),
+            orders: HashMap::new(),
+            pending_fills: VecDeque::new(),
+            expired_orders: Vec::new(),
+            max_pending_fills: builder
+                .max_pending_fills
+                .unwrap_or(DEFAULT_MAX_PENDING_FILLS),
+            order_ttl: builder.order_ttl,
+            correlations: CorrelationTracker::new(builder.correlation_config.unwrap_or_default()),
+            funding: builder.funding,
+            volatility_target: builder.volatility_target,
+            clock: builder.clock.unwrap_or_else(|| Arc::new(SystemClock)),
+            halt_on_bankruptcy: builder.halt_on_bankruptcy.unwrap_or(true),
+            bankruptcy: None,
             _statistic_marker: Default::default(),
         })
     }
 
+    fn eth_trade(price: f64) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_trade(Side::Buy);
+        market.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+        market.kind = DataKind::Trade(PublicTrade {
+            id: "trade_id".to_string(),
+            price,
+            amount: 1.0,
+            side: Side::Buy,
+        });
+        market
+    }
+
     fn new_signal_force_exit() -> SignalForceExit {
         SignalForceExit {
             time: Utc::now(),
             exchange: ExchangeId::BinanceSpot,
             instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            synthetic: false,
+            reason: CloseReason::ForceExit,
         }
     }
 
+    #[test]
+    fn generate_risk_exit_order_closes_long_position_below_stop_loss() {
+        // Build Portfolio with a 5% stop-loss
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut input_position = position();
+                    input_position.side = Side::Buy;
+                    input_position.quantity = 2.5;
+                    input_position.enter_avg_price_gross = 100.0;
+                    input_position.current_symbol_price = 94.0;
+                    input_position
+                }))
+            }),
+            set_open_position: Some(|_| Ok(())),
+            ..Default::default()
+        };
+        let builder = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .starting_cash(1000.0)
+            .repository(mock_repository)
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(StopLossRisk::new(StopLossConfig {
+                default: StopLossLimits {
+                    stop_loss: Some(0.05),
+                    take_profit: None,
+                },
+                markets: HashMap::new(),
+            }));
+        let mut portfolio = build_uninitialised_portfolio(builder).unwrap();
+
+        let order = portfolio
+            .generate_risk_exit_order(&market_event_trade(Side::Sell))
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(order.decision, Decision::CloseLong);
+        assert_eq!(order.quantity, -2.5);
+        assert_eq!(order.market_meta.close, 94.0);
+
+        // Exit OrderEvent is awaiting it's FillEvent, so the Position is not exited twice
+        assert_eq!(
+            portfolio
+                .generate_risk_exit_order(&market_event_trade(Side::Sell))
+                .unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn update_from_market_with_long_position_increasing_in_value() {
         // Build Portfolio
@@ -746,6 +2203,13 @@ pub mod tests {
             }))
         });
         mock_repository.set_open_position = Some(|_| Ok(()));
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 1000.0,
+                available: 1000.0,
+            })
+        });
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -795,6 +2259,13 @@ pub mod tests {
             }))
         });
         mock_repository.set_open_position = Some(|_| Ok(()));
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 1000.0,
+                available: 1000.0,
+            })
+        });
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -839,6 +2310,13 @@ pub mod tests {
             }))
         });
         mock_repository.set_open_position = Some(|_| Ok(()));
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 1000.0,
+                available: 1000.0,
+            })
+        });
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -884,6 +2362,13 @@ pub mod tests {
             }))
         });
         mock_repository.set_open_position = Some(|_| Ok(()));
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 1000.0,
+                available: 1000.0,
+            })
+        });
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input MarketEvent
@@ -977,50 +2462,277 @@ pub mod tests {
         let mut input_signal = signal();
         input_signal
             .signals
-            .insert(Decision::Long, SignalStrength(1.0));
+            .insert(Decision::EnterLong, SignalStrength(1.0));
 
         let actual = portfolio.generate_order(&input_signal).unwrap().unwrap();
 
-        assert_eq!(actual.decision, Decision::Long)
+        assert_eq!(actual.decision, Decision::EnterLong)
     }
 
     #[test]
-    fn generate_order_short_with_no_position_and_input_net_short_signal() {
+    fn generate_order_long_priced_as_configured_entry_order_type() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| Ok(None));
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 100.0,
-                available: 100.0,
-            })
-        });
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 100.0,
+                })
+            }),
+            ..Default::default()
+        };
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
         let mut input_signal = signal();
+        input_signal.market_meta.close = 100.0;
+        input_signal
+            .signals
+            .insert(Decision::EnterLong, SignalStrength(1.0));
+
+        let cases = [
+            (EntryOrderType::Market, OrderType::Market),
+            (
+                EntryOrderType::Limit { offset: 0.01 },
+                OrderType::Limit { price: 99.0 },
+            ),
+            (
+                EntryOrderType::Stop { offset: 0.02 },
+                OrderType::Stop { trigger: 102.0 },
+            ),
+            (
+                EntryOrderType::StopLimit {
+                    trigger_offset: 0.02,
+                    limit_offset: 0.01,
+                },
+                OrderType::StopLimit {
+                    trigger: 102.0,
+                    price: 103.02,
+                },
+            ),
+        ];
+
+        for (entry_order_type, expected) in cases {
+            portfolio.entry_order_type = entry_order_type;
+            portfolio.orders.clear();
+
+            let actual = portfolio.generate_order(&input_signal).unwrap().unwrap();
+
+            match (actual.order_type, expected) {
+                (OrderType::Market, OrderType::Market) => {}
+                (OrderType::Limit { price }, OrderType::Limit { price: expected }) => {
+                    assert!((price - expected).abs() < 1e-9)
+                }
+                (OrderType::Stop { trigger }, OrderType::Stop { trigger: expected }) => {
+                    assert!((trigger - expected).abs() < 1e-9)
+                }
+                (
+                    OrderType::StopLimit { trigger, price },
+                    OrderType::StopLimit {
+                        trigger: expected_trigger,
+                        price: expected_price,
+                    },
+                ) => {
+                    assert!((trigger - expected_trigger).abs() < 1e-9);
+                    assert!((price - expected_price).abs() < 1e-9);
+                }
+                (actual, expected) => panic!("expected {expected:?}, actual {actual:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn generate_order_rounds_to_instrument_spec_and_rejects_below_min_notional() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(None)),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+        portfolio.entry_order_type = EntryOrderType::Limit { offset: 0.01 };
 
+        // Input SignalEvent allocated 100.0 / 30.0 = 3.333.. btc, limit priced at 29.7
+        let mut input_signal = signal();
+        input_signal.market_meta.close = 30.0;
         input_signal
             .signals
-            .insert(Decision::Short, SignalStrength(1.0));
+            .insert(Decision::EnterLong, SignalStrength(1.0));
+
+        let spec = |min_notional: f64| InstrumentSpec {
+            price: InstrumentSpecPrice {
+                min: 0.0,
+                tick_size: 0.25,
+            },
+            quantity: InstrumentSpecQuantity {
+                unit: OrderQuantityUnits::Asset(Symbol::from("btc")),
+                min: 0.5,
+                increment: 0.5,
+            },
+            notional: InstrumentSpecNotional { min: min_notional },
+        };
+        let market_id = MarketId::new(input_signal.exchange, &input_signal.instrument);
 
+        // Price rounded to the nearest tick & quantity rounded down to the step
+        portfolio
+            .instrument_specs
+            .insert(market_id.clone(), spec(50.0));
         let actual = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        assert_eq!(actual.order_type, OrderType::Limit { price: 29.75 });
+        assert_eq!(actual.quantity, 3.0);
+
+        // Unrounded notional of 99.0 passes, but rounding quantity down drops it below minimum
+        portfolio.orders.clear();
+        portfolio.instrument_specs.insert(market_id, spec(95.0));
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
+        assert!(portfolio.orders.is_empty());
+    }
+
+    #[test]
+    fn generate_rebalance_orders_only_records_scale_of_accepted_orders() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| Ok(Some(position()))),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Exposure scale of 2.0 doubles the 1.0 eth Position, requiring a 1.0 eth entry
+        let mut volatility_target = VolatilityTarget::new(VolatilityTargetConfig {
+            target_volatility: 0.1,
+            periods_per_year: 365.0,
+            volatility_period: 10,
+            max_scale: 2.0,
+            rebalance_threshold: 0.05,
+        });
+        volatility_target.scale = 2.0;
+        portfolio.volatility_target = Some(volatility_target);
+
+        let market_id = MarketId::new(ExchangeId::BinanceSpot, &position().instrument);
+        let spec = |increment: f64, min_notional: f64| InstrumentSpec {
+            price: InstrumentSpecPrice {
+                min: 0.0,
+                tick_size: 0.01,
+            },
+            quantity: InstrumentSpecQuantity {
+                unit: OrderQuantityUnits::Asset(Symbol::from("eth")),
+                min: 0.0,
+                increment,
+            },
+            notional: InstrumentSpecNotional { min: min_notional },
+        };
+        let market = market_event_trade(Side::Buy);
+
+        // Rebalancing OrderEvent rejected below the minimum notional leaves the scale unapplied
+        portfolio
+            .instrument_specs
+            .insert(market_id.clone(), spec(0.1, 500.0));
+        assert!(portfolio
+            .generate_rebalance_orders(&market)
+            .unwrap()
+            .is_empty());
+        assert!(portfolio
+            .volatility_target
+            .as_ref()
+            .unwrap()
+            .applied_scales
+            .is_empty());
+
+        // Retried OrderEvent is rounded down to the quantity increment & accepted
+        portfolio.instrument_specs.insert(market_id, spec(0.3, 0.0));
+        let orders = portfolio.generate_rebalance_orders(&market).unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].decision, Decision::EnterLong);
+        assert!((orders[0].quantity - 0.9).abs() < 1e-9);
+        assert_eq!(
+            portfolio.volatility_target.as_ref().unwrap().applied_scales[&position().position_id],
+            2.0
+        );
 
-        assert_eq!(actual.decision, Decision::Short)
+        // Position is not rebalanced again while the scale is unchanged
+        assert!(portfolio
+            .generate_rebalance_orders(&market)
+            .unwrap()
+            .is_empty());
     }
 
     #[test]
-    fn generate_order_close_long_with_long_position_and_input_net_close_long_signal() {
+    fn generate_order_scales_into_long_position_with_dca_allocator_until_capped() {
         // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_open_position = Some(|_| {
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_open_position: Some(|_| {
+                Ok(Some({
+                    let mut position = position();
+                    position.side = Side::Buy;
+                    position.meta.entries = 3;
+                    position
+                }))
+            }),
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 1000.0,
+                    available: 1000.0,
+                })
+            }),
+            ..Default::default()
+        };
+        let builder = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .starting_cash(1000.0)
+            .repository(mock_repository)
+            .allocation_manager(DcaAllocator::new(DcaConfig {
+                order_value: 100.0,
+                max_entries: 4,
+                max_exposure: 1000.0,
+            }))
+            .risk_manager(DefaultRisk {});
+        let mut portfolio = build_uninitialised_portfolio(builder).unwrap();
+
+        // Input SignalEvent
+        let mut input_signal = signal();
+        input_signal.market_meta.close = 50.0;
+        input_signal
+            .signals
+            .insert(Decision::EnterLong, SignalStrength(1.0));
+
+        // Fourth entry scales into the open Position
+        let actual = portfolio.generate_order(&input_signal).unwrap().unwrap();
+        assert_eq!(actual.decision, Decision::EnterLong);
+        assert_eq!(actual.quantity, 2.0);
+
+        // Fifth entry exceeds the maximum number of entries
+        portfolio.repository.get_open_position = Some(|_| {
             Ok(Some({
                 let mut position = position();
                 position.side = Side::Buy;
+                position.meta.entries = 4;
                 position
             }))
         });
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_none());
+    }
+
+    #[test]
+    fn generate_order_short_with_no_position_and_input_net_short_signal() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| Ok(None));
         mock_repository.get_balance = Some(|_| {
             Ok(Balance {
                 time: Utc::now(),
@@ -1035,15 +2747,48 @@ pub mod tests {
 
         input_signal
             .signals
-            .insert(Decision::CloseLong, SignalStrength(1.0));
+            .insert(Decision::EnterShort, SignalStrength(1.0));
 
         let actual = portfolio.generate_order(&input_signal).unwrap().unwrap();
 
-        assert_eq!(actual.decision, Decision::CloseLong)
+        assert_eq!(actual.decision, Decision::EnterShort)
     }
 
     #[test]
-    fn generate_order_close_short_with_short_position_and_input_net_close_short_signal() {
+    fn generate_order_close_long_with_long_position_and_input_net_close_long_signal() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_open_position = Some(|_| {
+            Ok(Some({
+                let mut position = position();
+                position.side = Side::Buy;
+                position
+            }))
+        });
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 100.0,
+                available: 100.0,
+            })
+        });
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input SignalEvent
+        let mut input_signal = signal();
+
+        input_signal
+            .signals
+            .insert(Decision::CloseLong, SignalStrength(1.0));
+
+        let actual = portfolio.generate_order(&input_signal).unwrap().unwrap();
+
+        assert_eq!(actual.decision, Decision::CloseLong)
+    }
+
+    #[test]
+    fn generate_order_close_short_with_short_position_and_input_net_close_short_signal() {
         // Build Portfolio
         let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
         mock_repository.get_open_position = Some(|_| {
@@ -1060,6 +2805,7 @@ pub mod tests {
                 available: 100.0,
             })
         });
+        mock_repository.set_open_position = Some(|_| Ok(()));
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1074,6 +2820,76 @@ pub mod tests {
         assert_eq!(actual.decision, Decision::CloseShort)
     }
 
+    #[test]
+    fn generate_order_maps_each_decision_against_flat_long_and_short_positions() {
+        type GetOpenPosition = fn(&SmolStr) -> Result<Option<Position>, RepositoryError>;
+
+        let flat: GetOpenPosition = |_| Ok(None);
+        let long: GetOpenPosition = |_| Ok(Some(position()));
+        let short: GetOpenPosition = |_| {
+            Ok(Some({
+                let mut position = position();
+                position.side = Side::Sell;
+                position.quantity = -1.0;
+                position
+            }))
+        };
+
+        // (Position state, Decision, expected OrderEvent quantity)
+        let cases: [(GetOpenPosition, Decision, Option<f64>); 12] = [
+            (flat, Decision::EnterLong, Some(1.0)),
+            (flat, Decision::CloseLong, None),
+            (flat, Decision::EnterShort, Some(-1.0)),
+            (flat, Decision::CloseShort, None),
+            (long, Decision::EnterLong, None),
+            (long, Decision::CloseLong, Some(-1.0)),
+            (long, Decision::EnterShort, None),
+            (long, Decision::CloseShort, None),
+            (short, Decision::EnterLong, None),
+            (short, Decision::CloseLong, None),
+            (short, Decision::EnterShort, None),
+            (short, Decision::CloseShort, Some(1.0)),
+        ];
+
+        for (index, (get_open_position, decision, expected)) in cases.into_iter().enumerate() {
+            let mock_repository = MockRepository::<PnLReturnSummary> {
+                get_open_position: Some(get_open_position),
+                get_balance: Some(|_| {
+                    Ok(Balance {
+                        time: Utc::now(),
+                        total: 1000.0,
+                        available: 1000.0,
+                    })
+                }),
+                set_open_position: Some(|_| Ok(())),
+                ..Default::default()
+            };
+            let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+            let mut input_signal = signal();
+            input_signal.signals.insert(decision, SignalStrength(1.0));
+
+            let actual = portfolio.generate_order(&input_signal).unwrap();
+
+            match (actual, expected) {
+                (Some(order), Some(quantity)) => {
+                    assert_eq!(order.decision, decision, "case: {index}");
+                    assert_eq!(order.quantity, quantity, "case: {index}");
+                    assert_eq!(
+                        order.decision.side(),
+                        match quantity > 0.0 {
+                            true => Side::Buy,
+                            false => Side::Sell,
+                        },
+                        "case: {index}"
+                    );
+                }
+                (None, None) => {}
+                (actual, expected) => panic!("case: {index}, {actual:?} != {expected:?}"),
+            }
+        }
+    }
+
     #[test]
     fn generate_exit_order_with_long_position_open() {
         // Build Portfolio
@@ -1086,6 +2902,7 @@ pub mod tests {
                 position
             }))
         });
+        mock_repository.set_open_position = Some(|_| Ok(()));
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1114,6 +2931,7 @@ pub mod tests {
                 position
             }))
         });
+        mock_repository.set_open_position = Some(|_| Ok(()));
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
 
         // Input SignalEvent
@@ -1136,197 +2954,1319 @@ pub mod tests {
         let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
         mock_repository.get_open_position = Some(|_| Ok(None));
 
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input SignalEvent
+        let input_signal = new_signal_force_exit();
+
+        let actual = portfolio.generate_exit_order(input_signal).unwrap();
+        assert!(actual.is_none());
+    }
+
+    #[test]
+    fn update_from_fill_entering_long_position() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 200.0,
+                available: 200.0,
+            })
+        });
+        mock_repository.remove_position = Some(|_| Ok(None));
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        mock_repository.set_balance = Some(|_, _| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input FillEvent
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::EnterLong;
+        input_fill.quantity = 1.0;
+        input_fill.fill_value_gross = 100.0;
+        input_fill.fees = Fees {
+            exchange: 1.0,
+            slippage: 1.0,
+            network: 1.0,
+        };
+
+        let result = portfolio.update_from_fill(&input_fill);
+        let updated_repository = portfolio.repository;
+        let entered_position = updated_repository.position.unwrap();
+        let updated_cash = updated_repository.balance.unwrap().available;
+
+        assert!(result.is_ok());
+        assert_eq!(entered_position.side.unwrap(), Side::Buy);
+        assert_eq!(entered_position.enter_value_gross.unwrap(), 100.0);
+        assert_eq!(entered_position.enter_fees_total.unwrap(), 3.0);
+        assert_eq!(updated_cash, 200.0 - 100.0 - 3.0); // cash += enter_value_gross - enter_fees
+    }
+
+    #[test]
+    fn update_from_fill_entering_short_position() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 200.0,
+                available: 200.0,
+            })
+        });
+        mock_repository.remove_position = Some(|_| Ok(None));
+        mock_repository.set_open_position = Some(|_| Ok(()));
+        mock_repository.set_balance = Some(|_, _| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input FillEvent
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::EnterShort;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 100.0;
+        input_fill.fees = Fees {
+            exchange: 1.0,
+            slippage: 1.0,
+            network: 1.0,
+        };
+
+        let result = portfolio.update_from_fill(&input_fill);
+        let updated_repository = portfolio.repository;
+        let entered_position = updated_repository.position.unwrap();
+        let updated_cash = updated_repository.balance.unwrap().available;
+
+        assert!(result.is_ok());
+        assert_eq!(entered_position.side.unwrap(), Side::Sell);
+        assert_eq!(entered_position.enter_value_gross.unwrap(), 100.0);
+        assert_eq!(entered_position.enter_fees_total.unwrap(), 3.0);
+        assert_eq!(updated_cash, 200.0 - 100.0 - 3.0); // cash += enter_value_gross - enter_fees
+    }
+
+    #[test]
+    fn update_from_fill_exiting_long_position_in_profit() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 200.0,
+                available: 97.0,
+            })
+        });
+        mock_repository.remove_position = Some(|_| {
+            Ok({
+                Some({
+                    let mut input_position = position();
+                    input_position.side = Side::Buy;
+                    input_position.quantity = 1.0;
+                    input_position.enter_fees_total = 3.0;
+                    input_position.enter_value_gross = 100.0;
+                    input_position
+                })
+            })
+        });
+        mock_repository.get_statistics = Some(|_, _| Ok(PnLReturnSummary::default()));
+        mock_repository.set_statistics = Some(|_, _, _| Ok(()));
+        mock_repository.set_exited_position = Some(|_, _| Ok(()));
+        mock_repository.set_balance = Some(|_, _| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input FillEvent
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 200.0;
+        input_fill.fees = Fees {
+            exchange: 1.0,
+            slippage: 1.0,
+            network: 1.0,
+        };
+
+        let result = portfolio.update_from_fill(&input_fill);
+        let updated_repository = portfolio.repository;
+        let updated_cash = updated_repository.balance.unwrap().available;
+        let updated_value = updated_repository.balance.unwrap().total;
+
+        assert!(result.is_ok());
+        // LONG result_profit_loss = exit_value_gross - enter_value_gross - total_fees
+        // cash += enter_value_gross + result_profit_loss + enter_fees_total
+        assert_eq!(updated_cash, 97.0 + 100.0 + (200.0 - 100.0 - 6.0) + 3.0);
+        // value += result_profit_loss
+        assert_eq!(updated_value, 200.0 + (200.0 - 100.0 - 6.0));
+    }
+
+    #[test]
+    fn update_from_fill_exiting_long_position_in_loss() {
+        // Build Portfolio
+        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
+        mock_repository.get_balance = Some(|_| {
+            Ok(Balance {
+                time: Utc::now(),
+                total: 200.0,
+                available: 97.0,
+            })
+        });
+        mock_repository.remove_position = Some(|_| {
+            Ok({
+                Some({
+                    let mut input_position = position();
+                    input_position.side = Side::Buy;
+                    input_position.quantity = 1.0;
+                    input_position.enter_fees_total = 3.0;
+                    input_position.enter_value_gross = 100.0;
+                    input_position
+                })
+            })
+        });
+        mock_repository.get_statistics = Some(|_, _| Ok(PnLReturnSummary::default()));
+        mock_repository.set_statistics = Some(|_, _, _| Ok(()));
+        mock_repository.set_exited_position = Some(|_, _| Ok(()));
+        mock_repository.set_balance = Some(|_, _| Ok(()));
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input FillEvent
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 50.0;
+        input_fill.fees = Fees {
+            exchange: 1.0,
+            slippage: 1.0,
+            network: 1.0,
+        };
+
+        let result = portfolio.update_from_fill(&input_fill);
+        let updated_repository = portfolio.repository;
+        let updated_cash = updated_repository.balance.unwrap().available;
+        let updated_value = updated_repository.balance.unwrap().total;
+
+        assert!(result.is_ok());
+        // LONG result_profit_loss = exit_value_gross - enter_value_gross - total_fees
+        // cash += enter_value_gross + result_profit_loss + enter_fees_total
+        assert_eq!(updated_cash, 97.0 + 100.0 + (50.0 - 100.0 - 6.0) + 3.0);
+        // value += result_profit_loss
+        assert_eq!(updated_value, 200.0 + (50.0 - 100.0 - 6.0));
+    }
+
+    #[test]
+    fn update_from_fill_exiting_long_position_into_bankruptcy() {
+        // Build Portfolio
+        let mock_repository = MockRepository::<PnLReturnSummary> {
+            get_balance: Some(|_| {
+                Ok(Balance {
+                    time: Utc::now(),
+                    total: 100.0,
+                    available: 0.0,
+                })
+            }),
+            remove_position: Some(|_| {
+                Ok({
+                    Some({
+                        let mut input_position = position();
+                        input_position.side = Side::Buy;
+                        input_position.quantity = 1.0;
+                        input_position.enter_fees_total = 0.0;
+                        input_position.enter_value_gross = 100.0;
+                        input_position
+                    })
+                })
+            }),
+            get_statistics: Some(|_, _| Ok(PnLReturnSummary::default())),
+            set_statistics: Some(|_, _, _| Ok(())),
+            set_exited_position: Some(|_, _| Ok(())),
+            set_balance: Some(|_, _| Ok(())),
+            ..Default::default()
+        };
+        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+
+        // Input FillEvent exits the Position with the instrument now worthless
+        let mut input_fill = fill_event();
+        input_fill.decision = Decision::CloseLong;
+        input_fill.quantity = -1.0;
+        input_fill.fill_value_gross = 0.0;
+
+        let events = portfolio.update_from_fill(&input_fill).unwrap();
+
+        match events.last() {
+            Some(Event::Bankruptcy(bankruptcy)) => {
+                assert_eq!(bankruptcy.time, input_fill.time);
+                assert_eq!(bankruptcy.equity, 0.0);
+            }
+            other => panic!("expected Event::Bankruptcy, but got: {other:?}"),
+        }
+    }
+
+    /// Builds a [`MetaPortfolio`] with 1000.0 cash that has entered a 1000.0 eth Position,
+    /// paying 10.0 in fees, so any crash in the eth price wipes out it's equity.
+    fn all_in_eth_portfolio(
+        halt_on_bankruptcy: bool,
+    ) -> MetaPortfolio<
+        InMemoryRepository<PnLReturnSummary>,
+        DefaultAllocator,
+        DefaultRisk,
+        PnLReturnSummary,
+    > {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .halt_on_bankruptcy(halt_on_bankruptcy)
+            .build_and_init()
+            .unwrap();
+
+        let mut entry_fill = fill_event();
+        entry_fill.quantity = 10.0;
+        entry_fill.fill_value_gross = 1000.0;
+        entry_fill.fees.exchange = 10.0;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+
+        portfolio
+    }
+
+    #[test]
+    fn check_bankruptcy_declares_bankruptcy_once_market_wipes_out_equity() {
+        let mut portfolio = all_in_eth_portfolio(true);
+
+        // Price dip leaves positive equity
+        let market = eth_trade(90.0);
+        portfolio.update_from_market(&market).unwrap();
+        assert_eq!(portfolio.check_bankruptcy(&market).unwrap(), None);
+        assert_eq!(portfolio.bankruptcy(), None);
+
+        // Price crash wipes out equity without any FillEvent
+        let market = eth_trade(0.5);
+        portfolio.update_from_market(&market).unwrap();
+        let bankruptcy = portfolio
+            .check_bankruptcy(&market)
+            .unwrap()
+            .expect("Bankruptcy not declared");
+        assert_eq!(bankruptcy.time, market.time_exchange);
+        assert!(bankruptcy.equity <= 0.0);
+
+        // Bankruptcy is only declared once, but remains flagged on the Portfolio
+        assert_eq!(portfolio.check_bankruptcy(&market).unwrap(), None);
+        assert_eq!(portfolio.bankruptcy(), Some(bankruptcy));
+
+        // Bankrupt Portfolio does not act on further Signals
+        let mut input_signal = signal();
+        input_signal.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+        input_signal.signals = HashMap::from([(Decision::CloseLong, SignalStrength(1.0))]);
+        assert_eq!(portfolio.generate_order(&input_signal).unwrap(), None);
+    }
+
+    #[test]
+    fn check_bankruptcy_is_disabled_if_not_halting_on_bankruptcy() {
+        let mut portfolio = all_in_eth_portfolio(false);
+
+        let market = eth_trade(0.5);
+        portfolio.update_from_market(&market).unwrap();
+        assert_eq!(portfolio.check_bankruptcy(&market).unwrap(), None);
+        assert_eq!(portfolio.bankruptcy(), None);
+
+        let mut input_signal = signal();
+        input_signal.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+        input_signal.signals = HashMap::from([(Decision::CloseLong, SignalStrength(1.0))]);
+        assert!(portfolio.generate_order(&input_signal).unwrap().is_some());
+    }
+
+    #[test]
+    fn market_statistics_are_updated_under_the_market_id_they_are_initialised_with() {
+        let engine_id = Uuid::new_v4();
+        let market = Market::new(
+            ExchangeId::BinanceSpot,
+            ("eth", "usdt", InstrumentKind::Spot),
+        );
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<TradingSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 1000.0,
+                trading_days_per_year: 365,
+                data_period: Duration::days(1),
+                risk_free_return: 0.0,
+                omega_threshold: None,
+                with_benchmark: true,
+            })
+            .build_and_init()
+            .unwrap();
+
+        // MarketEvents update the buy-and-hold benchmark
+        portfolio.update_from_market(&eth_trade(100.0)).unwrap();
+        portfolio.update_from_market(&eth_trade(110.0)).unwrap();
+
+        // Exited Position updates the trade statistics
+        portfolio.update_from_fill(&fill_event()).unwrap();
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 110.0;
+        portfolio.update_from_fill(&exit_fill).unwrap();
+
+        let statistics = portfolio
+            .get_statistics(engine_id, &MarketId::from(&market))
+            .unwrap();
+        assert_eq!(statistics.benchmark.unwrap().data_points, 2);
+        assert_eq!(statistics.pnl_returns.total.count, 1);
+    }
+
+    #[test]
+    fn update_from_fill_reconciles_out_of_order_fills() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        let entry_order = order_event();
+        let mut exit_order = order_event();
+        exit_order.decision = Decision::CloseLong;
+        exit_order.quantity = -1.0;
+
+        let mut entry_fill = fill_event();
+        entry_fill.cid = Some(entry_order.cid);
+        let mut exit_fill = fill_event();
+        exit_fill.cid = Some(exit_order.cid);
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+        exit_fill.fill_value_gross = 110.0;
+
+        // Exit FillEvent arrives before either OrderEvent is registered, so it's buffered
+        let events = portfolio.update_from_fill(&exit_fill).unwrap();
+        assert!(events.is_empty());
+
+        // Exit OrderEvent registered, but the exit FillEvent waits for the Position to be entered
+        let events = portfolio.register_order(exit_order).unwrap();
+        assert!(events.is_empty());
+
+        // Entry FillEvent arrives before it's OrderEvent is registered, so it's buffered
+        let events = portfolio.update_from_fill(&entry_fill).unwrap();
+        assert!(events.is_empty());
+        assert_eq!(portfolio.pending_fills.len(), 2);
+
+        // Entry OrderEvent registered: entry FillEvent applied, followed by the exit FillEvent
+        let events = portfolio.register_order(entry_order).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [
+                Event::PositionNew(_),
+                Event::Balance(_),
+                Event::PositionExit(_),
+                Event::Balance(balance),
+            ] if balance.total == 1010.0 && balance.available == 1010.0
+        ));
+        assert!(portfolio.pending_fills.is_empty());
+        assert!(portfolio.orders.is_empty());
+    }
+
+    #[test]
+    fn cancel_order_evicts_order_and_drops_its_buffered_fills() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        // Exit FillEvent is buffered until the Position it exits is entered
+        let mut exit_order = order_event();
+        exit_order.decision = Decision::CloseLong;
+        exit_order.quantity = -1.0;
+        let mut exit_fill = fill_event();
+        exit_fill.cid = Some(exit_order.cid);
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -1.0;
+
+        portfolio.register_order(exit_order.clone()).unwrap();
+        assert!(portfolio.update_from_fill(&exit_fill).unwrap().is_empty());
+        assert_eq!(portfolio.pending_fills.len(), 1);
+
+        assert_eq!(portfolio.cancel_order(exit_order.cid), Some(exit_order));
+        assert!(portfolio.orders.is_empty());
+        assert!(portfolio.pending_fills.is_empty());
+    }
+
+    #[test]
+    fn update_from_market_expires_orders_and_fills_awaiting_reconciliation_beyond_ttl() {
+        let start = DateTime::<Utc>::UNIX_EPOCH + Duration::days(1);
+        let clock = SimulatedClock::new(start);
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .order_ttl(Duration::minutes(5))
+            .clock(clock.clone())
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        // OrderEvent without a FillEvent, & a FillEvent without an OrderEvent
+        let mut order = order_event();
+        order.time = start;
+        let mut fill = fill_event();
+        fill.cid = Some(Uuid::new_v4());
+        fill.time = start + Duration::minutes(1);
+
+        portfolio.register_order(order.clone()).unwrap();
+        assert!(portfolio.update_from_fill(&fill).unwrap().is_empty());
+
+        // OrderEvent expires first, followed by the FillEvent
+        clock.advance(start + Duration::minutes(5) + Duration::seconds(1));
+        portfolio.update_from_market(&eth_trade(100.0)).unwrap();
+        assert!(portfolio.orders.is_empty());
+        assert_eq!(portfolio.pending_fills.len(), 1);
+
+        // Expired OrderEvent is handed over once to be cancelled with the execution handler
+        assert_eq!(portfolio.take_expired_orders(), vec![order]);
+        assert!(portfolio.take_expired_orders().is_empty());
+
+        clock.advance(start + Duration::minutes(6) + Duration::seconds(1));
+        portfolio.update_from_market(&eth_trade(100.0)).unwrap();
+        assert!(portfolio.pending_fills.is_empty());
+    }
+
+    #[test]
+    fn update_from_fill_drops_oldest_buffered_fills_beyond_capacity() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .max_pending_fills(2)
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        let fills = (0..3)
+            .map(|_| {
+                let mut fill = fill_event();
+                fill.cid = Some(Uuid::new_v4());
+                fill
+            })
+            .collect::<Vec<_>>();
+
+        for fill in &fills {
+            assert!(portfolio.update_from_fill(fill).unwrap().is_empty());
+        }
+
+        assert_eq!(portfolio.pending_fills, VecDeque::from(fills[1..].to_vec()));
+    }
+
+    #[test]
+    fn update_from_fill_opens_position_from_fills_of_routed_child_orders() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+        let execution = SimulatedExecution::new(ExecutionConfig::default());
+
+        // Coinbase & Kraken each quote 1.0 eth at the top of book
+        let mut router = SmartOrderRouter::default();
+        for (exchange, price) in [(ExchangeId::Coinbase, 100), (ExchangeId::Kraken, 101)] {
+            let level = Level::new(price, 1);
+            router.update_from_market(&MarketEvent {
+                time_exchange: Utc::now(),
+                time_received: Utc::now(),
+                exchange,
+                instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+                kind: DataKind::OrderBookL1(OrderBookL1 {
+                    last_update_time: Utc::now(),
+                    best_bid: level,
+                    best_ask: level,
+                }),
+            });
+        }
+
+        // Parent OrderEvent for 2.0 eth is registered, then split across both venues
+        let mut parent = order_event();
+        parent.quantity = 2.0;
+        parent.market_meta.close = 100.0;
+        assert!(portfolio.register_order(parent.clone()).unwrap().is_empty());
+
+        let children = router.route(&parent);
+        assert_eq!(children.len(), 2);
+
+        // Child FillEvents mapped back to the parent open a single Position on it's market
+        let mut events = Vec::new();
+        for child in &children {
+            let fill = execution.generate_fill(child).unwrap();
+            events.extend(
+                portfolio
+                    .update_from_fill(&router.parent_fill(fill))
+                    .unwrap(),
+            );
+        }
+        assert!(matches!(
+            events.as_slice(),
+            [
+                Event::PositionNew(_),
+                Event::Balance(_),
+                Event::PositionUpdate(_),
+                Event::Balance(_),
+            ]
+        ));
+
+        let position_id = determine_position_id(
+            portfolio.engine_id,
+            &ExchangeId::BinanceSpot,
+            &Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+        );
+        let position = portfolio
+            .repository
+            .get_open_position(&position_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(position.quantity, 2.0);
+        assert_eq!(position.enter_value_gross, 201.0);
+        assert!(portfolio.pending_fills.is_empty());
+        assert!(portfolio.orders.is_empty());
+    }
+
+    #[test]
+    fn update_from_fill_converts_multiple_quote_currencies_into_base_currency_equity() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![
+                Market::new(
+                    ExchangeId::BinanceSpot,
+                    ("btc", "usd", InstrumentKind::Spot),
+                ),
+                Market::new(
+                    ExchangeId::BinanceSpot,
+                    ("btc", "eur", InstrumentKind::Spot),
+                ),
+            ])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .fx_rates(FxRates::new("usd").with_rate("eur", 1.1))
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        let entry_fill = |quote: &str, price: f64| {
+            let mut fill = fill_event();
+            fill.instrument = Instrument::from(("btc", quote, InstrumentKind::Spot));
+            fill.decision = Decision::EnterLong;
+            fill.quantity = 1.0;
+            fill.fill_value_gross = price;
+            fill
+        };
+        let trade = |base: &str, quote: &str, price: f64| {
+            let mut market = market_event_trade(Side::Buy);
+            market.instrument = Instrument::from((base, quote, InstrumentKind::Spot));
+            market.kind = DataKind::Trade(PublicTrade {
+                id: "trade_id".to_string(),
+                price,
+                amount: 1.0,
+                side: Side::Buy,
+            });
+            market
+        };
+        let equity = |portfolio: &mut MetaPortfolio<_, _, _, _>| {
+            portfolio.margin_account().unwrap().equity()
+        };
+
+        // Enter 1 btc at 200.0 usd & 1 btc at 100.0 eur (110.0 usd)
+        portfolio
+            .update_from_fill(&entry_fill("usd", 200.0))
+            .unwrap();
+        let events = portfolio
+            .update_from_fill(&entry_fill("eur", 100.0))
+            .unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [Event::PositionNew(_), Event::Balance(balance)]
+                if balance.total == 1000.0 && (balance.available - 690.0).abs() < 1e-9
+        ));
+
+        let balances = portfolio
+            .repository
+            .get_currency_balances(portfolio.engine_id)
+            .unwrap();
+        assert_eq!(balances[&Symbol::from("usd")].available, 800.0);
+        assert_eq!(balances[&Symbol::from("eur")].available, -100.0);
+        assert!((equity(&mut portfolio) - 1000.0).abs() < 1e-9);
+
+        // btc/eur rises 20.0 eur, worth 22.0 usd at 1.1 eur/usd
+        portfolio
+            .update_from_market(&trade("btc", "eur", 120.0))
+            .unwrap();
+        assert!((equity(&mut portfolio) - 1022.0).abs() < 1e-9);
+
+        // eur/usd rises to 1.2: -100.0 eur cash & 120.0 eur Position revalued to 24.0 usd
+        portfolio
+            .update_from_market(&trade("eur", "usd", 1.2))
+            .unwrap();
+        assert!((equity(&mut portfolio) - 1024.0).abs() < 1e-9);
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert!((balance.available - 680.0).abs() < 1e-9);
+
+        // FillEvent settling in a currency without an FX rate is rejected without state changes
+        assert!(matches!(
+            portfolio.update_from_fill(&entry_fill("gbp", 100.0)),
+            Err(PortfolioError::MissingFxRate { currency, .. }) if currency == Symbol::from("gbp")
+        ));
+        assert!((equity(&mut portfolio) - 1024.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn generate_order_with_close_fraction_scales_out_of_position() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 200.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+        let execution = SimulatedExecution::new(ExecutionConfig::default());
+        let position_id = determine_position_id(
+            portfolio.engine_id,
+            &ExchangeId::BinanceSpot,
+            &Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+        );
+
+        // Generate & fill the OrderEvent advised by the Signal, returning the filled quantity
+        let trade = |portfolio: &mut MetaPortfolio<
+            InMemoryRepository<PnLReturnSummary>,
+            DefaultAllocator,
+            DefaultRisk,
+            PnLReturnSummary,
+        >,
+                     decision: Decision,
+                     price: f64,
+                     close_fraction: Option<f64>| {
+            let mut signal = signal();
+            signal.instrument = Instrument::from(("eth", "usdt", InstrumentKind::Spot));
+            signal.signals = HashMap::from([(decision, SignalStrength(1.0))]);
+            signal.market_meta.close = price;
+            signal.close_fraction = close_fraction;
+
+            let order = portfolio.generate_order(&signal).unwrap().unwrap();
+            let fill = execution.generate_fill(&order).unwrap();
+            portfolio.update_from_fill(&fill).unwrap();
+            fill.quantity
+        };
+
+        // Enter 2 contracts at 100.0
+        assert_eq!(trade(&mut portfolio, Decision::EnterLong, 100.0, None), 2.0);
+
+        // Close 50% at 120.0, realising (120 - 100) * 1 = 20.0 & leaving 1 contract open
+        assert_eq!(
+            trade(&mut portfolio, Decision::CloseLong, 120.0, Some(0.5)),
+            -1.0
+        );
+        let open = portfolio
+            .repository
+            .get_open_position(&position_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(open.quantity, 1.0);
+        assert!((open.realised_profit_loss - 20.0).abs() < 1e-10);
+
+        // Close the remaining 50% at 130.0 with an oversized fraction, realising 30.0 without
+        // going short
+        assert_eq!(
+            trade(&mut portfolio, Decision::CloseLong, 130.0, Some(1.5)),
+            -1.0
+        );
+        assert!(portfolio
+            .repository
+            .get_open_position(&position_id)
+            .unwrap()
+            .is_none());
+
+        let exited = portfolio
+            .repository
+            .get_exited_positions(portfolio.engine_id)
+            .unwrap();
+        assert_eq!(exited.len(), 1);
+        assert!((exited[0].realised_profit_loss - 50.0).abs() < 1e-10);
+
+        let balance = portfolio
+            .repository
+            .get_balance(portfolio.engine_id)
+            .unwrap();
+        assert!((balance.total - 1050.0).abs() < 1e-10);
+        assert!((balance.available - 1050.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn exited_positions_are_attributed_to_the_strategy_that_entered_them() {
+        /// Strategy that advises the same decision on every [`MarketEvent`].
+        struct Fixed(Decision);
+
+        impl SignalGenerator for Fixed {
+            fn generate_signal(
+                &mut self,
+                market: &MarketEvent<Instrument, DataKind>,
+            ) -> Option<Signal> {
+                Some(Signal {
+                    time: market.time_exchange,
+                    exchange: market.exchange,
+                    instrument: market.instrument.clone(),
+                    signals: HashMap::from([(self.0, SignalStrength(1.0))]),
+                    market_meta: MarketMeta {
+                        close: determine_market_close(market)?,
+                        time: market.time_exchange,
+                    },
+                    strategy_id: StrategyId::default(),
+                    close_fraction: None,
+                })
+            }
+        }
+
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![
+                Market::new(
+                    ExchangeId::BinanceSpot,
+                    ("eth", "usdt", InstrumentKind::Spot),
+                ),
+                Market::new(
+                    ExchangeId::BinanceSpot,
+                    ("btc", "usdt", InstrumentKind::Spot),
+                ),
+            ])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+        let execution = SimulatedExecution::new(ExecutionConfig::default());
+
+        let trade = |base: &str, price: f64| {
+            let mut market = eth_trade(price);
+            market.instrument = Instrument::from((base, "usdt", InstrumentKind::Spot));
+            market
+        };
+
+        // Enter & exit a Position for the provided strategy, tagging it if an id is provided
+        let mut round_trip =
+            |strategy_id: Option<&str>, base: &str, enter_price: f64, exit_price: f64| {
+                for (decision, price) in [
+                    (Decision::EnterLong, enter_price),
+                    (Decision::CloseLong, exit_price),
+                ] {
+                    let signal = match strategy_id {
+                        Some(id) => TaggedStrategy::new(Fixed(decision), id)
+                            .generate_signal(&trade(base, price)),
+                        None => Fixed(decision).generate_signal(&trade(base, price)),
+                    }
+                    .unwrap();
+
+                    let order = portfolio.generate_order(&signal).unwrap().unwrap();
+                    let fill = execution.generate_fill(&order).unwrap();
+                    assert_eq!(fill.strategy_id, signal.strategy_id);
+                    portfolio.update_from_fill(&fill).unwrap();
+                }
+            };
+
+        // 1 contract of eth entered at 100 & exited at 120
+        round_trip(Some("trend"), "eth", 100.0, 120.0);
+        // 1 contract of btc entered at 100 & exited at 90
+        round_trip(Some("mean_reversion"), "btc", 100.0, 90.0);
+        // 1 contract of btc entered at 100 & exited at 130
+        round_trip(Some("mean_reversion"), "btc", 100.0, 130.0);
+        // Untagged 1 contract of eth entered at 100 & exited at 105
+        round_trip(None, "eth", 100.0, 105.0);
+
+        let exited = portfolio
+            .repository
+            .get_exited_positions(portfolio.engine_id)
+            .unwrap();
+        let tags = exited
+            .iter()
+            .map(|position| position.strategy_id.0.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            tags,
+            ["trend", "mean_reversion", "mean_reversion", "unknown"]
+        );
+
+        let breakdown = StrategyBreakdown::new(&exited);
+        let summary = |id: &str| *breakdown.get(&StrategyId::new(id)).unwrap();
+
+        assert_eq!(summary("trend").trades, 1);
+        assert!((summary("trend").profit_loss - 20.0).abs() < 1e-9);
+        assert_eq!(summary("trend").win_rate(), 1.0);
+
+        assert_eq!(summary("mean_reversion").trades, 2);
+        assert!((summary("mean_reversion").profit_loss - 20.0).abs() < 1e-9);
+        assert_eq!(summary("mean_reversion").win_rate(), 0.5);
+
+        assert!((summary(StrategyId::UNKNOWN).profit_loss - 5.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exited_positions_carry_the_close_reason_of_their_exit() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(StopLossRisk::new(StopLossConfig {
+                default: StopLossLimits {
+                    stop_loss: Some(0.05),
+                    take_profit: None,
+                },
+                markets: HashMap::new(),
+            }))
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+        let execution = SimulatedExecution::new(ExecutionConfig::default());
+
+        let eth_signal = |decision: Decision, price: f64| Signal {
+            exchange: ExchangeId::BinanceSpot,
+            instrument: Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+            signals: HashMap::from([(decision, SignalStrength(1.0))]),
+            market_meta: MarketMeta {
+                close: price,
+                time: Utc::now(),
+            },
+            ..signal()
+        };
+
+        let fill = |portfolio: &mut MetaPortfolio<_, _, _, _>, order: OrderEvent| {
+            let fill = execution.generate_fill(&order).unwrap();
+            portfolio.update_from_fill(&fill).unwrap();
+        };
+
+        // Long entered at 100 is closed by the 5% stop-loss at 94
+        let order = portfolio
+            .generate_order(&eth_signal(Decision::EnterLong, 100.0))
+            .unwrap()
+            .unwrap();
+        fill(&mut portfolio, order);
+        portfolio.update_from_market(&eth_trade(94.0)).unwrap();
+        let order = portfolio
+            .generate_risk_exit_order(&eth_trade(94.0))
+            .unwrap()
+            .unwrap();
+        fill(&mut portfolio, order);
+
+        // Long entered at 100 is closed by a CloseLong Signal at 110
+        for (decision, price) in [(Decision::EnterLong, 100.0), (Decision::CloseLong, 110.0)] {
+            let order = portfolio
+                .generate_order(&eth_signal(decision, price))
+                .unwrap()
+                .unwrap();
+            fill(&mut portfolio, order);
+        }
+
+        let exited = portfolio
+            .repository
+            .get_exited_positions(portfolio.engine_id)
+            .unwrap();
+        let close_reasons = exited
+            .iter()
+            .map(|position| position.close_reason)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            close_reasons,
+            [Some(CloseReason::StopLoss), Some(CloseReason::Signal)]
+        );
+
+        let breakdown = CloseReasonBreakdown::new(&exited);
+        assert_eq!(breakdown.reasons.len(), 2);
+
+        let stop_loss = breakdown.get(CloseReason::StopLoss).unwrap();
+        assert_eq!((stop_loss.trades, stop_loss.losses), (1, 1));
+        assert!(stop_loss.profit_loss < 0.0);
+
+        let signal = breakdown.get(CloseReason::Signal).unwrap();
+        assert_eq!((signal.trades, signal.wins), (1, 1));
+        assert!(signal.profit_loss > 0.0);
+    }
+
+    #[test]
+    fn update_from_fill_applies_partial_fills_and_flips_position() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        let fill = |decision, quantity: f64, price: f64| {
+            let mut fill = fill_event();
+            fill.decision = decision;
+            fill.quantity = quantity;
+            fill.fill_value_gross = quantity.abs() * price;
+            fill
+        };
+
+        let position_id = determine_position_id(
+            portfolio.engine_id,
+            &ExchangeId::BinanceSpot,
+            &Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+        );
+
+        // Long entry accumulated over two partial fills
+        portfolio
+            .update_from_fill(&fill(Decision::EnterLong, 1.0, 100.0))
+            .unwrap();
+        let events = portfolio
+            .update_from_fill(&fill(Decision::EnterLong, 1.0, 110.0))
+            .unwrap();
+        assert!(
+            matches!(events.as_slice(), [Event::PositionUpdate(_), Event::Balance(balance)] if balance.available == 790.0)
+        );
+
+        // Partial close of 1 contract at 125.0 realises 125.0 - 105.0
+        let events = portfolio
+            .update_from_fill(&fill(Decision::CloseLong, -1.0, 125.0))
+            .unwrap();
+        assert!(
+            matches!(events.as_slice(), [Event::PositionUpdate(_), Event::Balance(balance)]
+            if balance.total == 1020.0 && balance.available == 915.0)
+        );
 
-        // Input SignalEvent
-        let input_signal = new_signal_force_exit();
+        // Short of 3 contracts at 120.0 closes the remaining long & enters a short of 2
+        let events = portfolio
+            .update_from_fill(&fill(Decision::EnterShort, -3.0, 120.0))
+            .unwrap();
+        match events.as_slice() {
+            [Event::PositionExit(exit), Event::PositionNew(new), Event::Balance(balance)] => {
+                assert_eq!(exit.realised_profit_loss, 20.0 + 15.0);
+                assert_eq!(new.side, Side::Sell);
+                assert_eq!(new.quantity, -2.0);
+                assert_eq!(balance.total, 1035.0);
+                assert_eq!(balance.available, 1035.0 - 240.0);
+            }
+            other => panic!("expected PositionExit, PositionNew & Balance, but got: {other:?}"),
+        }
 
-        let actual = portfolio.generate_exit_order(input_signal).unwrap();
-        assert!(actual.is_none());
+        let open_position = portfolio.get_open_position(&position_id).unwrap().unwrap();
+        assert!((open_position.enter_avg_price_gross - 120.0).abs() < 1e-9);
     }
 
     #[test]
-    fn update_from_fill_entering_long_position() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 200.0,
+    fn leveraged_position_uses_notional_over_leverage_margin() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![
+                Market::new(
+                    ExchangeId::BinanceSpot,
+                    ("eth", "usdt", InstrumentKind::Spot),
+                ),
+                Market::new(
+                    ExchangeId::BinanceSpot,
+                    ("btc", "usdt", InstrumentKind::Spot),
+                ),
+            ])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 4500.0,
             })
-        });
-        mock_repository.remove_position = Some(|_| Ok(None));
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+            .risk_manager(DefaultRisk {})
+            .max_leverage(5.0)
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        // Input FillEvent
-        let mut input_fill = fill_event();
-        input_fill.decision = Decision::Long;
-        input_fill.quantity = 1.0;
-        input_fill.fill_value_gross = 100.0;
-        input_fill.fees = Fees {
-            exchange: 1.0,
-            slippage: 1.0,
-            network: 1.0,
+        let long_signal = || {
+            let mut signal = signal();
+            signal
+                .signals
+                .insert(Decision::EnterLong, SignalStrength(1.0));
+            signal
         };
+        // 4500.0 order value requires 900.0 margin at 5x, within the 1000.0 available
+        assert!(portfolio.generate_order(&long_signal()).unwrap().is_some());
+
+        // Enter 5x leveraged Position with a notional value of 1000.0
+        let mut fill = fill_event();
+        fill.decision = Decision::EnterLong;
+        fill.quantity = 10.0;
+        fill.fill_value_gross = 1000.0;
+        let events = portfolio.update_from_fill(&fill).unwrap();
+        match events.as_slice() {
+            [Event::PositionNew(position), Event::Balance(balance)] => {
+                assert_eq!(position.leverage, 5.0);
+                assert_eq!(balance.available, 1000.0 - 1000.0 / 5.0);
+            }
+            other => panic!("expected PositionNew & Balance, but got: {other:?}"),
+        }
 
-        let result = portfolio.update_from_fill(&input_fill);
-        let updated_repository = portfolio.repository;
-        let entered_position = updated_repository.position.unwrap();
-        let updated_cash = updated_repository.balance.unwrap().available;
+        let margin = portfolio.margin_account().unwrap();
+        assert_eq!(margin.used, 1000.0 / 5.0);
+        assert_eq!(margin.available, 800.0);
 
-        assert!(result.is_ok());
-        assert_eq!(entered_position.side.unwrap(), Side::Buy);
-        assert_eq!(entered_position.enter_value_gross.unwrap(), 100.0);
-        assert_eq!(entered_position.enter_fees_total.unwrap(), 3.0);
-        assert_eq!(updated_cash, 200.0 - 100.0 - 3.0); // cash += enter_value_gross - enter_fees
+        // Used margin tracks the notional value of the Position as the market moves
+        portfolio.update_from_market(&eth_trade(95.0)).unwrap();
+        assert_eq!(portfolio.margin_account().unwrap().used, 950.0 / 5.0);
+        assert_eq!(portfolio.margin_call(&eth_trade(95.0)).unwrap(), None);
+
+        // Over-leveraged order requiring 900.0 margin is rejected by the risk evaluator
+        assert_eq!(portfolio.generate_order(&long_signal()).unwrap(), None);
+
+        // Losses consuming over half of the 200.0 margin posted issue a single MarginCall
+        portfolio.update_from_market(&eth_trade(88.0)).unwrap();
+        let margin_call = portfolio.margin_call(&eth_trade(88.0)).unwrap().unwrap();
+        assert_eq!(margin_call.used_margin, 880.0 / 5.0);
+        assert_eq!(margin_call.margin_balance, 200.0 - 120.0);
+
+        portfolio.update_from_market(&eth_trade(87.0)).unwrap();
+        assert_eq!(portfolio.margin_call(&eth_trade(87.0)).unwrap(), None);
     }
 
     #[test]
-    fn update_from_fill_entering_short_position() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 200.0,
+    fn build_and_init_restores_margins_of_positions_left_open_in_repository() {
+        let engine_id = Uuid::new_v4();
+
+        // Repository holds a 5x leveraged Position with a notional value of 1000.0 left open
+        let mut fill = fill_event();
+        fill.decision = Decision::EnterLong;
+        fill.quantity = 10.0;
+        fill.fill_value_gross = 1000.0;
+        let mut position = Position::enter(engine_id, &fill).unwrap();
+        position.leverage = 5.0;
+        let mut repository = InMemoryRepository::<PnLReturnSummary>::new();
+        repository.set_open_position(position).unwrap();
+
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(800.0)
+            .repository(repository)
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
             })
-        });
-        mock_repository.remove_position = Some(|_| Ok(None));
-        mock_repository.set_open_position = Some(|_| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
-
-        // Input FillEvent
-        let mut input_fill = fill_event();
-        input_fill.decision = Decision::Short;
-        input_fill.quantity = -1.0;
-        input_fill.fill_value_gross = 100.0;
-        input_fill.fees = Fees {
-            exchange: 1.0,
-            slippage: 1.0,
-            network: 1.0,
-        };
-
-        let result = portfolio.update_from_fill(&input_fill);
-        let updated_repository = portfolio.repository;
-        let entered_position = updated_repository.position.unwrap();
-        let updated_cash = updated_repository.balance.unwrap().available;
+            .risk_manager(DefaultRisk {})
+            .max_leverage(5.0)
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        assert!(result.is_ok());
-        assert_eq!(entered_position.side.unwrap(), Side::Sell);
-        assert_eq!(entered_position.enter_value_gross.unwrap(), 100.0);
-        assert_eq!(entered_position.enter_fees_total.unwrap(), 3.0);
-        assert_eq!(updated_cash, 200.0 - 100.0 - 3.0); // cash += enter_value_gross - enter_fees
+        let margin = portfolio.margin_account().unwrap();
+        assert_eq!(margin.used, 1000.0 / 5.0);
+        assert_eq!(margin.available, 800.0);
     }
 
     #[test]
-    fn update_from_fill_exiting_long_position_in_profit() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 97.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| {
-            Ok({
-                Some({
-                    let mut input_position = position();
-                    input_position.side = Side::Buy;
-                    input_position.quantity = 1.0;
-                    input_position.enter_fees_total = 3.0;
-                    input_position.enter_value_gross = 100.0;
-                    input_position
-                })
+    fn leveraged_long_breaching_maintenance_margin_is_liquidated() {
+        let engine_id = Uuid::new_v4();
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
             })
-        });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
-        mock_repository.set_exited_position = Some(|_, _| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
-
-        // Input FillEvent
-        let mut input_fill = fill_event();
-        input_fill.decision = Decision::CloseLong;
-        input_fill.quantity = -1.0;
-        input_fill.fill_value_gross = 200.0;
-        input_fill.fees = Fees {
-            exchange: 1.0,
-            slippage: 1.0,
-            network: 1.0,
-        };
+            .risk_manager(DefaultRisk {})
+            .max_leverage(10.0)
+            .maintenance_margin_rate(0.005)
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        let result = portfolio.update_from_fill(&input_fill);
-        let updated_repository = portfolio.repository;
-        let updated_cash = updated_repository.balance.unwrap().available;
-        let updated_value = updated_repository.balance.unwrap().total;
+        // Enter 10x leveraged long of 10 contracts at 100.0
+        let mut fill = fill_event();
+        fill.decision = Decision::EnterLong;
+        fill.quantity = 10.0;
+        fill.fill_value_gross = 1000.0;
+        portfolio.update_from_fill(&fill).unwrap();
+
+        // Liquidation price = 100.0 * (1 - 1/10) / (1 - 0.005)
+        let liquidation_price = 100.0 * 0.9 / 0.995;
+
+        // Marked down 5% is above the liquidation price
+        portfolio.update_from_market(&eth_trade(95.0)).unwrap();
+        assert!(portfolio.liquidate(&eth_trade(95.0)).unwrap().is_empty());
+
+        // Marked down 12% is liquidated at the liquidation price, at the breaching market time
+        let mut breach = eth_trade(88.0);
+        breach.time_exchange += Duration::hours(1);
+        portfolio.update_from_market(&breach).unwrap();
+        let events = portfolio.liquidate(&breach).unwrap();
+        let expected_pnl = 10.0 * (liquidation_price - 100.0);
+        match events.as_slice() {
+            [Event::PositionExit(exit), Event::Balance(balance)] => {
+                assert!(exit.liquidated);
+                assert_eq!(exit.exit_time, breach.time_exchange);
+                assert_eq!(balance.time, breach.time_exchange);
+                assert!((exit.exit_avg_price_gross - liquidation_price).abs() < 1e-9);
+                assert!((exit.realised_profit_loss - expected_pnl).abs() < 1e-9);
+                assert!((balance.total - (1000.0 + expected_pnl)).abs() < 1e-9);
+                assert!((balance.available - (1000.0 + expected_pnl)).abs() < 1e-9);
+            }
+            other => panic!("expected PositionExit & Balance, but got: {other:?}"),
+        }
 
-        assert!(result.is_ok());
-        // LONG result_profit_loss = exit_value_gross - enter_value_gross - total_fees
-        // cash += enter_value_gross + result_profit_loss + enter_fees_total
-        assert_eq!(updated_cash, 97.0 + 100.0 + (200.0 - 100.0 - 6.0) + 3.0);
-        // value += result_profit_loss
-        assert_eq!(updated_value, 200.0 + (200.0 - 100.0 - 6.0));
+        // Liquidated Position is closed & tagged in it's exited record
+        let position_id = determine_position_id(
+            engine_id,
+            &ExchangeId::BinanceSpot,
+            &Instrument::from(("eth", "usdt", InstrumentKind::Spot)),
+        );
+        assert_eq!(portfolio.get_open_position(&position_id).unwrap(), None);
+        let exited = portfolio.get_exited_positions(engine_id).unwrap();
+        assert!(matches!(exited.as_slice(), [position] if position.liquidated));
     }
 
     #[test]
-    fn update_from_fill_exiting_long_position_in_loss() {
-        // Build Portfolio
-        let mut mock_repository = MockRepository::<PnLReturnSummary>::default();
-        mock_repository.get_balance = Some(|_| {
-            Ok(Balance {
-                time: Utc::now(),
-                total: 200.0,
-                available: 97.0,
-            })
-        });
-        mock_repository.remove_position = Some(|_| {
-            Ok({
-                Some({
-                    let mut input_position = position();
-                    input_position.side = Side::Buy;
-                    input_position.quantity = 1.0;
-                    input_position.enter_fees_total = 3.0;
-                    input_position.enter_value_gross = 100.0;
-                    input_position
-                })
+    fn short_position_round_trip_realises_inverted_profit_net_of_fees() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("eth", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
             })
-        });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
-        mock_repository.set_exited_position = Some(|_, _| Ok(()));
-        mock_repository.set_balance = Some(|_, _| Ok(()));
-        let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
+            .risk_manager(DefaultRisk {})
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
 
-        // Input FillEvent
-        let mut input_fill = fill_event();
-        input_fill.decision = Decision::CloseLong;
-        input_fill.quantity = -1.0;
-        input_fill.fill_value_gross = 50.0;
-        input_fill.fees = Fees {
+        let fees = Fees {
             exchange: 1.0,
-            slippage: 1.0,
-            network: 1.0,
+            slippage: 0.5,
+            network: 0.0,
         };
 
-        let result = portfolio.update_from_fill(&input_fill);
-        let updated_repository = portfolio.repository;
-        let updated_cash = updated_repository.balance.unwrap().available;
-        let updated_value = updated_repository.balance.unwrap().total;
+        // Enter short of 2 contracts at 100.0
+        let mut entry_fill = fill_event();
+        entry_fill.decision = Decision::EnterShort;
+        entry_fill.quantity = -2.0;
+        entry_fill.fill_value_gross = 200.0;
+        entry_fill.fees = fees;
+        portfolio.update_from_fill(&entry_fill).unwrap();
+
+        // Mark short at 90.0: profit when price falls, approximating exit fees with entry fees
+        let mut market = market_event_trade(Side::Buy);
+        market.instrument = entry_fill.instrument.clone();
+        if let DataKind::Trade(trade) = &mut market.kind {
+            trade.price = 90.0;
+        }
+        let position_update = portfolio.update_from_market(&market).unwrap().unwrap();
+        assert_eq!(position_update.current_value_gross, 180.0);
+        assert_eq!(
+            position_update.unrealised_profit_loss,
+            200.0 - 180.0 - 1.5 * 2.0
+        );
 
-        assert!(result.is_ok());
-        // LONG result_profit_loss = exit_value_gross - enter_value_gross - total_fees
-        // cash += enter_value_gross + result_profit_loss + enter_fees_total
-        assert_eq!(updated_cash, 97.0 + 100.0 + (50.0 - 100.0 - 6.0) + 3.0);
-        // value += result_profit_loss
-        assert_eq!(updated_value, 200.0 + (50.0 - 100.0 - 6.0));
+        // Close short at 90.0
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseShort;
+        exit_fill.quantity = 2.0;
+        exit_fill.fill_value_gross = 180.0;
+        exit_fill.fees = fees;
+        let events = portfolio.update_from_fill(&exit_fill).unwrap();
+
+        let expected_realised = 200.0 - 180.0 - 1.5 - 1.5;
+        match events.as_slice() {
+            [Event::PositionExit(exit), Event::Balance(balance)] => {
+                assert_eq!(exit.realised_profit_loss, expected_realised);
+                assert_eq!(balance.total, 1000.0 + expected_realised);
+                assert_eq!(balance.available, 1000.0 + expected_realised);
+            }
+            other => panic!("expected PositionExit & Balance events, but got: {other:?}"),
+        }
     }
 
     #[test]
@@ -1352,8 +4292,8 @@ pub mod tests {
                 })
             })
         });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
+        mock_repository.get_statistics = Some(|_, _| Ok(PnLReturnSummary::default()));
+        mock_repository.set_statistics = Some(|_, _, _| Ok(()));
         mock_repository.set_exited_position = Some(|_, _| Ok(()));
         mock_repository.set_balance = Some(|_, _| Ok(()));
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
@@ -1405,8 +4345,8 @@ pub mod tests {
                 })
             })
         });
-        mock_repository.get_statistics = Some(|_| Ok(PnLReturnSummary::default()));
-        mock_repository.set_statistics = Some(|_, _| Ok(()));
+        mock_repository.get_statistics = Some(|_, _| Ok(PnLReturnSummary::default()));
+        mock_repository.set_statistics = Some(|_, _, _| Ok(()));
         mock_repository.set_exited_position = Some(|_, _| Ok(()));
         mock_repository.set_balance = Some(|_, _| Ok(()));
         let mut portfolio = new_mocked_portfolio(mock_repository).unwrap();
@@ -1446,7 +4386,7 @@ pub mod tests {
         // Signals HashMap
         let mut signals = HashMap::with_capacity(4);
         signals.insert(Decision::CloseLong, SignalStrength(1.0));
-        signals.insert(Decision::Short, SignalStrength(1.0));
+        signals.insert(Decision::EnterShort, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
@@ -1463,7 +4403,7 @@ pub mod tests {
 
         // Signals HashMap
         let mut signals = HashMap::with_capacity(4);
-        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::EnterLong, SignalStrength(1.0));
         signals.insert(Decision::CloseShort, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
@@ -1483,8 +4423,8 @@ pub mod tests {
         let mut signals = HashMap::with_capacity(4);
         signals.insert(Decision::CloseLong, SignalStrength(1.0));
         signals.insert(Decision::CloseShort, SignalStrength(1.0));
-        signals.insert(Decision::Short, SignalStrength(1.0));
-        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::EnterShort, SignalStrength(1.0));
+        signals.insert(Decision::EnterLong, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
@@ -1502,7 +4442,7 @@ pub mod tests {
         // Signals HashMap
         let mut signals = HashMap::with_capacity(4);
         signals.insert(Decision::CloseShort, SignalStrength(1.0));
-        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::EnterLong, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
@@ -1520,7 +4460,7 @@ pub mod tests {
         // Signals HashMap
         let mut signals = HashMap::with_capacity(4);
         signals.insert(Decision::CloseLong, SignalStrength(1.0));
-        signals.insert(Decision::Short, SignalStrength(1.0));
+        signals.insert(Decision::EnterShort, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
@@ -1539,8 +4479,8 @@ pub mod tests {
         let mut signals = HashMap::with_capacity(4);
         signals.insert(Decision::CloseShort, SignalStrength(1.0));
         signals.insert(Decision::CloseLong, SignalStrength(1.0));
-        signals.insert(Decision::Short, SignalStrength(1.0));
-        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::EnterShort, SignalStrength(1.0));
+        signals.insert(Decision::EnterLong, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
@@ -1554,12 +4494,12 @@ pub mod tests {
 
         // Signals HashMap
         let mut signals = HashMap::with_capacity(4);
-        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::EnterLong, SignalStrength(1.0));
         signals.insert(Decision::CloseShort, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
-        assert_eq!(actual.unwrap().0, &Decision::Long);
+        assert_eq!(actual.unwrap().0, &Decision::EnterLong);
     }
 
     #[test]
@@ -1569,12 +4509,12 @@ pub mod tests {
 
         // Signals HashMap
         let mut signals = HashMap::with_capacity(4);
-        signals.insert(Decision::Short, SignalStrength(1.0));
+        signals.insert(Decision::EnterShort, SignalStrength(1.0));
         signals.insert(Decision::CloseLong, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
-        assert_eq!(actual.unwrap().0, &Decision::Short);
+        assert_eq!(actual.unwrap().0, &Decision::EnterShort);
     }
 
     #[test]
@@ -1584,13 +4524,259 @@ pub mod tests {
 
         // Signals HashMap
         let mut signals = HashMap::with_capacity(4);
-        signals.insert(Decision::Long, SignalStrength(1.0));
+        signals.insert(Decision::EnterLong, SignalStrength(1.0));
         signals.insert(Decision::CloseShort, SignalStrength(1.0));
-        signals.insert(Decision::Short, SignalStrength(1.0));
+        signals.insert(Decision::EnterShort, SignalStrength(1.0));
         signals.insert(Decision::CloseLong, SignalStrength(1.0));
 
         let actual = parse_signal_decisions(&position, &signals);
 
         assert_eq!(actual, None);
     }
+
+    #[test]
+    fn update_from_market_accrues_funding_on_perpetual_positions_held_through_funding_times() {
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceFuturesUsd,
+                ("btc", "usdt", InstrumentKind::Perpetual),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .funding(FundingAccrual::new())
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        let start = Utc::now();
+        let fill = |time, decision, quantity: f64, price: f64| {
+            let mut fill = fill_event();
+            fill.time = time;
+            fill.exchange = ExchangeId::BinanceFuturesUsd;
+            fill.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Perpetual));
+            fill.decision = decision;
+            fill.quantity = quantity;
+            fill.fill_value_gross = quantity.abs() * price;
+            fill
+        };
+        let funding = |time, mark_price| {
+            let mut market = market_event_trade(Side::Buy);
+            market.time_exchange = time;
+            market.exchange = ExchangeId::BinanceFuturesUsd;
+            market.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Perpetual));
+            market.kind = DataKind::FundingRate(FundingRate {
+                rate: 0.0001,
+                next_funding_time: time,
+                mark_price,
+            });
+            market
+        };
+        let balance = |portfolio: &mut MetaPortfolio<
+            InMemoryRepository<PnLReturnSummary>,
+            DefaultAllocator,
+            DefaultRisk,
+            PnLReturnSummary,
+        >| {
+            portfolio
+                .repository
+                .get_balance(portfolio.engine_id)
+                .unwrap()
+        };
+
+        // Long 2 btc held through two funding times, paying 2 * mark price * 0.0001 at each
+        portfolio
+            .update_from_fill(&fill(start, Decision::EnterLong, 2.0, 100.0))
+            .unwrap();
+        portfolio
+            .update_from_market(&funding(start + Duration::hours(8), 100.0))
+            .unwrap();
+        portfolio
+            .update_from_market(&funding(start + Duration::hours(16), 110.0))
+            .unwrap();
+
+        let funding_cost = 0.02 + 0.022;
+        let position_id = determine_position_id(
+            portfolio.engine_id,
+            &ExchangeId::BinanceFuturesUsd,
+            &Instrument::from(("btc", "usdt", InstrumentKind::Perpetual)),
+        );
+        let position = portfolio
+            .repository
+            .get_open_position(&position_id)
+            .unwrap()
+            .unwrap();
+        assert!((position.funding_profit_loss + funding_cost).abs() < 1e-12);
+        assert!((balance(&mut portfolio).total - (1000.0 - funding_cost)).abs() < 1e-12);
+
+        // Exiting at 110.0 realises the price P&L net of the accrued funding
+        let exit_time = start + Duration::hours(17);
+        portfolio
+            .update_from_fill(&fill(exit_time, Decision::CloseLong, -2.0, 110.0))
+            .unwrap();
+        let exited = portfolio
+            .repository
+            .get_exited_positions(portfolio.engine_id)
+            .unwrap();
+        assert!((exited[0].realised_profit_loss - (20.0 - funding_cost)).abs() < 1e-12);
+
+        let balance_after_exit = balance(&mut portfolio);
+        assert!((balance_after_exit.total - (1020.0 - funding_cost)).abs() < 1e-9);
+        assert!((balance_after_exit.available - balance_after_exit.total).abs() < 1e-9);
+
+        // Position opened & closed within a funding interval pays no funding
+        portfolio
+            .update_from_fill(&fill(exit_time, Decision::EnterLong, 1.0, 110.0))
+            .unwrap();
+        portfolio
+            .update_from_fill(&fill(
+                exit_time + Duration::hours(1),
+                Decision::CloseLong,
+                -1.0,
+                110.0,
+            ))
+            .unwrap();
+        portfolio
+            .update_from_market(&funding(start + Duration::hours(24), 110.0))
+            .unwrap();
+        assert!((balance(&mut portfolio).total - balance_after_exit.total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn generate_order_suppresses_entries_within_cooldown_of_the_exit_fill() {
+        let start = DateTime::<Utc>::UNIX_EPOCH + Duration::days(1);
+        let clock = SimulatedClock::new(start);
+        let mut portfolio = MetaPortfolio::builder()
+            .engine_id(Uuid::new_v4())
+            .markets(vec![Market::new(
+                ExchangeId::BinanceSpot,
+                ("btc", "usdt", InstrumentKind::Spot),
+            )])
+            .starting_cash(1000.0)
+            .repository(InMemoryRepository::<PnLReturnSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 100.0,
+            })
+            .risk_manager(CooldownRisk::new(
+                CooldownConfig {
+                    cooldown: Duration::minutes(5),
+                },
+                DefaultRisk {},
+            ))
+            .clock(clock.clone())
+            .statistic_config(())
+            .build_and_init()
+            .unwrap();
+
+        let fill = |time, decision, quantity: f64| {
+            let mut fill = fill_event();
+            fill.time = time;
+            fill.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+            fill.decision = decision;
+            fill.quantity = quantity;
+            fill.fill_value_gross = quantity.abs() * 100.0;
+            fill
+        };
+        let mut entry_signal = signal();
+        entry_signal
+            .signals
+            .insert(Decision::EnterLong, SignalStrength(1.0));
+
+        // Position is closed one minute after entry, according to the exit fill timestamp
+        let exit_time = start + Duration::minutes(1);
+        portfolio
+            .update_from_fill(&fill(start, Decision::EnterLong, 1.0))
+            .unwrap();
+        portfolio
+            .update_from_fill(&fill(exit_time, Decision::CloseLong, -1.0))
+            .unwrap();
+
+        // Entry signal immediately after the exit is suppressed while cooling down
+        clock.advance(exit_time + Duration::seconds(1));
+        assert_eq!(portfolio.generate_order(&entry_signal).unwrap(), None);
+
+        clock.advance(exit_time + Duration::minutes(4));
+        assert_eq!(portfolio.generate_order(&entry_signal).unwrap(), None);
+
+        // Entry signal is allowed once the cooldown has elapsed on the event clock
+        clock.advance(exit_time + Duration::minutes(5));
+        let order = portfolio.generate_order(&entry_signal).unwrap().unwrap();
+        assert_eq!(order.decision, Decision::EnterLong);
+        assert_eq!(order.time, exit_time + Duration::minutes(5));
+    }
+
+    #[test]
+    fn generate_order_nets_or_hedges_opposing_entries_according_to_position_mode() {
+        let market = Market::new(
+            ExchangeId::BinanceSpot,
+            ("btc", "usdt", InstrumentKind::Spot),
+        );
+        let new_portfolio = |position_mode| {
+            MetaPortfolio::builder()
+                .engine_id(Uuid::new_v4())
+                .markets(vec![market.clone()])
+                .starting_cash(1000.0)
+                .repository(InMemoryRepository::<PnLReturnSummary>::new())
+                .allocation_manager(DefaultAllocator {
+                    default_order_value: 100.0,
+                })
+                .risk_manager(DefaultRisk {})
+                .position_mode(position_mode)
+                .statistic_config(())
+                .build_and_init()
+                .unwrap()
+        };
+
+        // Generate an OrderEvent for a Signal with the provided Decision, & fill it in full
+        let act = |portfolio: &mut MetaPortfolio<_, _, _, PnLReturnSummary>, decision| {
+            let mut signal = signal();
+            signal.market_meta.close = 100.0;
+            signal.signals.insert(decision, SignalStrength(1.0));
+
+            let Some(order) = portfolio.generate_order(&signal).unwrap() else {
+                return;
+            };
+            let mut fill = fill_event();
+            fill.cid = Some(order.cid);
+            fill.instrument = order.instrument.clone();
+            fill.decision = order.decision;
+            fill.quantity = order.quantity;
+            fill.fill_value_gross = order.quantity.abs() * order.market_meta.close;
+            portfolio.update_from_fill(&fill).unwrap();
+        };
+        let open_sides = |portfolio: &mut MetaPortfolio<_, _, _, PnLReturnSummary>| {
+            let mut sides = portfolio
+                .get_open_positions(Uuid::nil(), std::iter::once(&market))
+                .unwrap()
+                .into_iter()
+                .map(|position| (position.side, position.quantity))
+                .collect::<Vec<_>>();
+            sides.sort_by(|a, b| a.1.total_cmp(&b.1).reverse());
+            sides
+        };
+
+        // Netting: a Short signal while long does not open a second Position
+        let mut netting = new_portfolio(PositionMode::Netting);
+        act(&mut netting, Decision::EnterLong);
+        act(&mut netting, Decision::EnterShort);
+        assert_eq!(open_sides(&mut netting), vec![(Side::Buy, 1.0)]);
+
+        // Hedging: the same Signals open a separate short Position alongside the long
+        let mut hedging = new_portfolio(PositionMode::Hedging);
+        act(&mut hedging, Decision::EnterLong);
+        act(&mut hedging, Decision::EnterShort);
+        assert_eq!(
+            open_sides(&mut hedging),
+            vec![(Side::Buy, 1.0), (Side::Sell, -1.0)]
+        );
+
+        // Exits only close the Position of their own side
+        act(&mut hedging, Decision::CloseLong);
+        assert_eq!(open_sides(&mut hedging), vec![(Side::Sell, -1.0)]);
+        assert_eq!(hedging.get_exited_positions(Uuid::nil()).unwrap().len(), 1);
+    }
 }
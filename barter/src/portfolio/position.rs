@@ -1,13 +1,16 @@
 use crate::{
+    data::determine_market_close,
     execution::{FeeAmount, Fees, FillEvent},
     portfolio::{error::PortfolioError, Balance},
-    strategy::Decision,
+    strategy::{Decision, StrategyId},
+};
+use barter_data::{
+    event::{DataKind, MarketEvent},
+    subscription::funding::FundingRate,
 };
-use barter_data::event::{DataKind, MarketEvent};
 use barter_instrument::{exchange::ExchangeId, instrument::Instrument};
 use barter_integration::Side;
 use chrono::{DateTime, Utc};
-use rust_decimal::prelude::ToPrimitive;
 use serde::{Deserialize, Serialize};
 use smol_str::{format_smolstr, SmolStr};
 use std::convert::TryFrom;
@@ -45,6 +48,86 @@ pub fn determine_position_id(
     format_smolstr!("{}_{}_{}_position", engine_id, exchange, instrument)
 }
 
+/// Returns a unique identifier for the [`Position`] of the provided [`Side`] given an engine_id,
+/// [`Exchange`] & [`Instrument`]. Used in [`PositionMode::Hedging`], where a long & short
+/// [`Position`] in the same market coexist.
+pub fn determine_hedged_position_id(
+    engine_id: Uuid,
+    exchange: &ExchangeId,
+    instrument: &Instrument,
+    side: Side,
+) -> PositionId {
+    format_smolstr!(
+        "{}_{}_{}_{}_position",
+        engine_id,
+        exchange,
+        instrument,
+        side
+    )
+}
+
+/// Returns every identifier an open [`Position`] in the provided market may be keyed by, being
+/// the netted [`PositionId`] followed by the long & short hedged [`PositionId`]s.
+pub fn determine_market_position_ids(
+    engine_id: Uuid,
+    exchange: &ExchangeId,
+    instrument: &Instrument,
+) -> [PositionId; 3] {
+    [
+        determine_position_id(engine_id, exchange, instrument),
+        determine_hedged_position_id(engine_id, exchange, instrument, Side::Buy),
+        determine_hedged_position_id(engine_id, exchange, instrument, Side::Sell),
+    ]
+}
+
+/// Determines how opposing [`Position`]s in the same market are held.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum PositionMode {
+    /// A market has at most one open [`Position`], so an opposing entry closes (or flips) it.
+    #[default]
+    Netting,
+    /// A market may have an open long & short [`Position`] at the same time, so an opposing
+    /// entry opens a separate [`Position`] rather than closing the open one.
+    Hedging,
+}
+
+impl PositionMode {
+    /// Returns the [`PositionId`] of the [`Position`] with the provided [`Side`] in a market.
+    pub fn position_id(
+        &self,
+        engine_id: Uuid,
+        exchange: &ExchangeId,
+        instrument: &Instrument,
+        side: Side,
+    ) -> PositionId {
+        match self {
+            PositionMode::Netting => determine_position_id(engine_id, exchange, instrument),
+            PositionMode::Hedging => {
+                determine_hedged_position_id(engine_id, exchange, instrument, side)
+            }
+        }
+    }
+
+    /// Returns the [`PositionId`] of every [`Position`] that may be open in a market, ordered
+    /// long before short if hedging.
+    pub fn market_position_ids(
+        &self,
+        engine_id: Uuid,
+        exchange: &ExchangeId,
+        instrument: &Instrument,
+    ) -> Vec<PositionId> {
+        match self {
+            PositionMode::Netting => vec![determine_position_id(engine_id, exchange, instrument)],
+            PositionMode::Hedging => [Side::Buy, Side::Sell]
+                .into_iter()
+                .map(|side| determine_hedged_position_id(engine_id, exchange, instrument, side))
+                .collect(),
+        }
+    }
+}
+
 /// Data encapsulating the state of an ongoing or closed [`Position`].
 #[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Position {
@@ -60,6 +143,11 @@ pub struct Position {
     /// [`Instrument`] associated with this [`Position`].
     pub instrument: Instrument,
 
+    /// [`StrategyId`] of the strategy that entered this [`Position`], propagated from the entry
+    /// [`FillEvent`].
+    #[serde(default)]
+    pub strategy_id: StrategyId,
+
     /// Buy or Sell.
     ///
     /// Notes:
@@ -79,7 +167,7 @@ pub struct Position {
     /// Enter average price excluding the entry_fees_total.
     pub enter_avg_price_gross: f64,
 
-    /// abs(Quantity) * enter_avg_price_gross.
+    /// abs(Quantity) * enter_avg_price_gross of the open quantity.
     pub enter_value_gross: f64,
 
     /// All fees types incurred from exiting a [`Position`], and their associated [`FeeAmount`].
@@ -103,25 +191,109 @@ pub struct Position {
     /// Unrealised P&L whilst the [`Position`] is open.
     pub unrealised_profit_loss: f64,
 
-    /// Realised P&L after the [`Position`] has closed.
+    /// Realised P&L after the [`Position`] has closed, including the realised P&L of any
+    /// partial exits.
     pub realised_profit_loss: f64,
+
+    /// Enter value gross of the quantity already closed by partial exits. Used alongside
+    /// enter_value_gross to measure returns against the full value deployed.
+    #[serde(default)]
+    pub closed_enter_value_gross: f64,
+
+    /// Cumulative perpetual funding received (+ve) or paid (-ve) whilst the [`Position`] was
+    /// open, already included in the realised_profit_loss.
+    #[serde(default)]
+    pub funding_profit_loss: f64,
+
+    /// Leverage the [`Position`] was entered with, where 1.0 is a fully-funded spot-style
+    /// [`Position`]. The margin posted to enter is enter_value_gross / leverage.
+    #[serde(default = "default_leverage")]
+    pub leverage: f64,
+
+    /// Flag indicating the [`Position`] was force-closed by a liquidation after breaching the
+    /// maintenance margin.
+    #[serde(default)]
+    pub liquidated: bool,
+
+    /// Flag indicating the [`Position`] was force-closed at the end of a trading session without
+    /// any market data to price the close, so it was exited at it's last mark price.
+    #[serde(default)]
+    pub synthetic_exit: bool,
+
+    /// Origin of the exit that closed the [`Position`], tagged when the exit is generated. `None`
+    /// if it was closed by a [`FillEvent`] the Portfolio did not generate the exit for.
+    #[serde(default)]
+    pub close_reason: Option<CloseReason>,
+}
+
+/// Default [`Position`] leverage of a fully-funded spot-style [`Position`].
+fn default_leverage() -> f64 {
+    1.0
+}
+
+fn default_entries() -> u32 {
+    1
+}
+
+/// Origin of the exit that closed a [`Position`], used to break down performance by how each
+/// [`Position`] was closed (see
+/// [`CloseReasonBreakdown`](crate::statistic::summary::close_reason::CloseReasonBreakdown)).
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum CloseReason {
+    /// Exit advised by a strategy [`Signal`](crate::strategy::Signal), including an opposing
+    /// entry that flips the [`Position`].
+    Signal,
+    /// Stop-loss limit of the risk manager breached.
+    StopLoss,
+    /// Take-profit limit of the risk manager reached.
+    TakeProfit,
+    /// Trailing stop of the risk manager retraced through.
+    TrailingStop,
+    /// Any other risk limit breached (see
+    /// [`OrderEvaluator::exit_reason`](super::risk::OrderEvaluator::exit_reason)).
+    Risk,
+    /// Force-closed by a liquidation after breaching the maintenance margin.
+    Liquidation,
+    /// Flattened at the end of a trading session.
+    SessionEnd,
+    /// Force-exited by an external
+    /// [`Command::ExitPosition`](crate::engine::Command::ExitPosition).
+    ForceExit,
+}
+
+/// Effect an input [`FillEvent`] has on an open [`Position`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum FillEffect {
+    /// Entry [`FillEvent`] in the same direction, increasing the open quantity.
+    Increase,
+    /// [`FillEvent`] closing part of the open quantity.
+    PartialExit,
+    /// [`FillEvent`] closing the full open quantity.
+    Exit,
+    /// Opposing entry [`FillEvent`] that closes the full open quantity & enters a [`Position`]
+    /// with the remainder on the opposite [`Side`].
+    Flip,
 }
 
 impl PositionEnterer for Position {
     fn enter(engine_id: Uuid, fill: &FillEvent) -> Result<Position, PortfolioError> {
+        // Enter fees
+        let enter_fees_total = fill.fees.calculate_total_fees();
+
+        // Enter price
+        let enter_avg_price_gross = Position::calculate_avg_price_gross(fill);
+
         // Initialise Position Metadata
         let metadata = PositionMeta {
             enter_time: fill.market_meta.time,
             update_time: fill.time,
             exit_balance: None,
+            high_water_price: Some(enter_avg_price_gross),
+            entries: 1,
+            max_favourable_excursion: 0.0,
+            max_adverse_excursion: 0.0,
         };
 
-        // Enter fees
-        let enter_fees_total = fill.fees.calculate_total_fees();
-
-        // Enter price
-        let enter_avg_price_gross = Position::calculate_avg_price_gross(fill);
-
         // Unreal profit & loss
         let unrealised_profit_loss = -enter_fees_total * 2.0;
 
@@ -129,6 +301,7 @@ impl PositionEnterer for Position {
             position_id: determine_position_id(engine_id, &fill.exchange, &fill.instrument),
             exchange: fill.exchange,
             instrument: fill.instrument.clone(),
+            strategy_id: fill.strategy_id.clone(),
             meta: metadata,
             side: Position::parse_entry_side(fill)?,
             quantity: fill.quantity,
@@ -144,6 +317,12 @@ impl PositionEnterer for Position {
             current_value_gross: fill.fill_value_gross,
             unrealised_profit_loss,
             realised_profit_loss: 0.0,
+            closed_enter_value_gross: 0.0,
+            funding_profit_loss: 0.0,
+            leverage: default_leverage(),
+            liquidated: false,
+            synthetic_exit: false,
+            close_reason: None,
         })
     }
 }
@@ -151,17 +330,22 @@ impl PositionEnterer for Position {
 impl PositionUpdater for Position {
     fn update(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<PositionUpdate> {
         // Determine close from MarketEvent
-        let close = match &market.kind {
-            DataKind::Trade(trade) => trade.price,
-            DataKind::Candle(candle) => candle.close,
-            DataKind::OrderBookL1(book_l1) => book_l1.volume_weighed_mid_price().to_f64()?,
-            DataKind::OrderBook(_) | DataKind::Liquidation(_) => return None,
-        };
+        let close = determine_market_close(market)?;
 
         self.meta.update_time = market.time_exchange;
 
         self.current_symbol_price = close;
 
+        // Most favourable price since entry
+        let high_water_price = self.high_water_price();
+        self.meta.high_water_price = Some(match self.side {
+            Side::Buy => high_water_price.max(close),
+            Side::Sell => high_water_price.min(close),
+        });
+
+        // Best & worst unrealised profit & loss since entry
+        self.update_excursions(close);
+
         // Market value gross
         self.current_value_gross = close * self.quantity.abs();
 
@@ -183,20 +367,22 @@ impl PositionExiter for Position {
             return Err(PortfolioError::CannotExitPositionWithEntryFill);
         }
 
-        // Exit fees
-        self.exit_fees = fill.fees;
-        self.exit_fees_total = fill.fees.calculate_total_fees();
+        // Profit & loss of closing the remaining open quantity
+        let exit_profit_loss =
+            self.calculate_exit_profit_loss(fill, self.enter_value_gross, self.enter_fees_total);
+
+        // Exit price is the final mark of the Position's excursions
+        self.update_excursions(Position::calculate_avg_price_gross(fill));
 
-        // Exit value & price
-        self.exit_value_gross = fill.fill_value_gross;
-        self.exit_avg_price_gross = Position::calculate_avg_price_gross(fill);
+        // Exit fees, value & price
+        self.record_exit_fill(fill);
 
-        // Result profit & loss
-        self.realised_profit_loss = self.calculate_realised_profit_loss();
+        // Result profit & loss, including any partial exits
+        self.realised_profit_loss += exit_profit_loss;
         self.unrealised_profit_loss = self.realised_profit_loss;
 
         // Metadata
-        balance.total += self.realised_profit_loss;
+        balance.total += exit_profit_loss;
         self.meta.update_time = fill.time;
         self.meta.exit_balance = Some(balance);
 
@@ -219,8 +405,8 @@ impl Position {
     /// Determine the [`Position`] entry [`Side`] by analysing the input [`FillEvent`].
     pub fn parse_entry_side(fill: &FillEvent) -> Result<Side, PortfolioError> {
         match fill.decision {
-            Decision::Long if fill.quantity.is_sign_positive() => Ok(Side::Buy),
-            Decision::Short if fill.quantity.is_sign_negative() => Ok(Side::Sell),
+            Decision::EnterLong if fill.quantity.is_sign_positive() => Ok(Side::Buy),
+            Decision::EnterShort if fill.quantity.is_sign_negative() => Ok(Side::Sell),
             Decision::CloseLong | Decision::CloseShort => {
                 Err(PortfolioError::CannotEnterPositionWithExitFill)
             }
@@ -228,6 +414,182 @@ impl Position {
         }
     }
 
+    /// Determines the [`FillEffect`] the input [`FillEvent`] has on this open [`Position`].
+    ///
+    /// Exit [`FillEvent`]s never flip a [`Position`], so an exit quantity greater than the open
+    /// quantity is treated as a full [`FillEffect::Exit`].
+    pub fn determine_fill_effect(&self, fill: &FillEvent) -> FillEffect {
+        let open_quantity = self.quantity.abs();
+        let fill_quantity = fill.quantity.abs();
+
+        let same_direction = match self.side {
+            Side::Buy => fill.quantity.is_sign_positive(),
+            Side::Sell => fill.quantity.is_sign_negative(),
+        };
+
+        if fill.decision.is_entry() && same_direction {
+            FillEffect::Increase
+        } else if fill_quantity < open_quantity {
+            FillEffect::PartialExit
+        } else if fill.decision.is_entry() && fill_quantity > open_quantity {
+            FillEffect::Flip
+        } else {
+            FillEffect::Exit
+        }
+    }
+
+    /// Increases the open quantity using an entry [`FillEvent`] in the same direction,
+    /// re-averaging the enter price. Returns a [`PositionUpdate`] that communicates the change in
+    /// state.
+    pub fn increase(&mut self, fill: &FillEvent) -> PositionUpdate {
+        self.quantity += fill.quantity;
+
+        // Enter fees, value & average price of the open quantity
+        self.enter_fees = add_fees(self.enter_fees, fill.fees);
+        self.enter_fees_total += fill.fees.calculate_total_fees();
+        self.enter_value_gross += fill.fill_value_gross;
+        self.enter_avg_price_gross = self.enter_value_gross / self.quantity.abs();
+
+        self.meta.entries += 1;
+        self.meta.update_time = fill.time;
+        self.current_value_gross = self.current_symbol_price * self.quantity.abs();
+        self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
+
+        PositionUpdate::from(self)
+    }
+
+    /// Closes part of the open quantity using the input [`FillEvent`], realising a proportional
+    /// share of the P&L. The enter value & fees of the closed quantity are released from the open
+    /// quantity at the average enter price. Returns the realised P&L of the partial exit.
+    pub fn reduce(&mut self, fill: &FillEvent) -> f64 {
+        let closed_fraction = (fill.quantity.abs() / self.quantity.abs()).min(1.0);
+        let closed_enter_value = self.enter_value_gross * closed_fraction;
+        let closed_enter_fees = self.enter_fees_total * closed_fraction;
+
+        let exit_profit_loss =
+            self.calculate_exit_profit_loss(fill, closed_enter_value, closed_enter_fees);
+        self.record_exit_fill(fill);
+
+        // Release the closed quantity from the open quantity
+        self.quantity -= self.quantity * closed_fraction;
+        self.enter_fees = scale_fees(self.enter_fees, 1.0 - closed_fraction);
+        self.enter_fees_total -= closed_enter_fees;
+        self.enter_value_gross -= closed_enter_value;
+        self.closed_enter_value_gross += closed_enter_value;
+        self.realised_profit_loss += exit_profit_loss;
+
+        self.meta.update_time = fill.time;
+        self.current_value_gross = self.current_symbol_price * self.quantity.abs();
+        self.unrealised_profit_loss = self.calculate_unrealised_profit_loss();
+
+        exit_profit_loss
+    }
+
+    /// Accrues the perpetual funding payment of the input [`FundingRate`] on the open quantity,
+    /// valued at it's mark price. Longs pay & shorts receive a positive rate. Returns the funding
+    /// P&L, which is zero if the [`Position`] was entered after the funding time.
+    pub fn accrue_funding(&mut self, funding: &FundingRate) -> f64 {
+        if self.meta.enter_time > funding.next_funding_time {
+            return 0.0;
+        }
+
+        let price = match funding.mark_price > 0.0 {
+            true => funding.mark_price,
+            false => self.current_symbol_price,
+        };
+        let payment = self.quantity.abs() * price * funding.rate;
+
+        let funding_profit_loss = match self.side {
+            Side::Buy => -payment,
+            Side::Sell => payment,
+        };
+
+        self.funding_profit_loss += funding_profit_loss;
+        self.realised_profit_loss += funding_profit_loss;
+        self.meta.update_time = self.meta.update_time.max(funding.next_funding_time);
+
+        funding_profit_loss
+    }
+
+    /// Splits a [`FillEffect::Flip`] [`FillEvent`] into the exit [`FillEvent`] that closes the
+    /// full open quantity, and the entry [`FillEvent`] of the remainder on the opposite [`Side`].
+    /// Fill value & fees are apportioned by quantity.
+    pub fn split_flip_fill(&self, fill: &FillEvent) -> (FillEvent, FillEvent) {
+        let exit_fraction = (self.quantity.abs() / fill.quantity.abs()).min(1.0);
+        let entry_quantity = fill.quantity + self.quantity;
+
+        let exit = FillEvent {
+            decision: self.determine_exit_decision(),
+            quantity: -self.quantity,
+            fill_value_gross: fill.fill_value_gross * exit_fraction,
+            fees: scale_fees(fill.fees, exit_fraction),
+            ..fill.clone()
+        };
+
+        let entry = FillEvent {
+            decision: match entry_quantity.is_sign_positive() {
+                true => Decision::EnterLong,
+                false => Decision::EnterShort,
+            },
+            quantity: entry_quantity,
+            fill_value_gross: fill.fill_value_gross * (1.0 - exit_fraction),
+            fees: scale_fees(fill.fees, 1.0 - exit_fraction),
+            ..fill.clone()
+        };
+
+        (exit, entry)
+    }
+
+    /// Calculates the P&L of closing the provided enter value & fees with the input exit
+    /// [`FillEvent`].
+    fn calculate_exit_profit_loss(
+        &self,
+        fill: &FillEvent,
+        enter_value_gross: f64,
+        enter_fees_total: FeeAmount,
+    ) -> f64 {
+        let total_fees = enter_fees_total + fill.fees.calculate_total_fees();
+
+        match self.side {
+            Side::Buy => fill.fill_value_gross - enter_value_gross - total_fees,
+            Side::Sell => enter_value_gross - fill.fill_value_gross - total_fees,
+        }
+    }
+
+    /// Accumulates the exit fees, value & average price of the input exit [`FillEvent`].
+    fn record_exit_fill(&mut self, fill: &FillEvent) {
+        let prev_exit_quantity = match self.exit_avg_price_gross > 0.0 {
+            true => self.exit_value_gross / self.exit_avg_price_gross,
+            false => 0.0,
+        };
+
+        self.exit_fees = add_fees(self.exit_fees, fill.fees);
+        self.exit_fees_total += fill.fees.calculate_total_fees();
+        self.exit_value_gross += fill.fill_value_gross;
+        self.exit_avg_price_gross =
+            self.exit_value_gross / (prev_exit_quantity + fill.quantity.abs());
+    }
+
+    /// Returns the most favourable price reached since entering the [`Position`] (highest for a
+    /// long, lowest for a short), falling back to the entry price if it is not yet tracked.
+    pub fn high_water_price(&self) -> f64 {
+        self.meta
+            .high_water_price
+            .unwrap_or(self.enter_avg_price_gross)
+    }
+
+    /// Updates the [`PositionMeta`] maximum favourable & adverse excursions with the unrealised
+    /// profit & loss (gross of fees) of the open quantity marked at the provided price.
+    fn update_excursions(&mut self, price: f64) {
+        let excursion = match self.side {
+            Side::Buy => (price - self.enter_avg_price_gross) * self.quantity.abs(),
+            Side::Sell => (self.enter_avg_price_gross - price) * self.quantity.abs(),
+        };
+
+        self.meta.max_favourable_excursion = self.meta.max_favourable_excursion.max(excursion);
+        self.meta.max_adverse_excursion = self.meta.max_adverse_excursion.max(-excursion);
+    }
+
     /// Determines the [`Decision`] required to exit this [`Side`] (Buy or Sell) [`Position`].
     pub fn determine_exit_decision(&self) -> Decision {
         match self.side {
@@ -236,6 +598,45 @@ impl Position {
         }
     }
 
+    /// Calculate the margin used by the [`Position`], being it's current notional value divided by
+    /// it's leverage.
+    pub fn used_margin(&self) -> f64 {
+        self.current_value_gross / self.leverage
+    }
+
+    /// Calculate the margin balance of the [`Position`], being the margin posted to enter it plus
+    /// it's [`Position::unrealised_profit_loss`]. The [`Position`] is liquidated if this falls
+    /// below the exchange maintenance margin.
+    pub fn margin_balance(&self) -> f64 {
+        self.enter_value_gross / self.leverage + self.unrealised_profit_loss
+    }
+
+    /// Calculate the mark price at which the margin balance of the [`Position`] falls to the
+    /// maintenance margin, given the maintenance margin rate (eg/ 0.005 for 0.5% of notional).
+    ///
+    /// Long: enter_avg_price_gross * (1 - 1/leverage) / (1 - maintenance_margin_rate)
+    /// Short: enter_avg_price_gross * (1 + 1/leverage) / (1 + maintenance_margin_rate)
+    pub fn liquidation_price(&self, maintenance_margin_rate: f64) -> f64 {
+        let margin_rate = 1.0 / self.leverage;
+        match self.side {
+            Side::Buy => {
+                self.enter_avg_price_gross * (1.0 - margin_rate) / (1.0 - maintenance_margin_rate)
+            }
+            Side::Sell => {
+                self.enter_avg_price_gross * (1.0 + margin_rate) / (1.0 + maintenance_margin_rate)
+            }
+        }
+    }
+
+    /// Determines if the current price of the [`Position`] has reached it's liquidation price.
+    pub fn is_liquidatable(&self, maintenance_margin_rate: f64) -> bool {
+        let liquidation_price = self.liquidation_price(maintenance_margin_rate);
+        match self.side {
+            Side::Buy => self.current_symbol_price <= liquidation_price,
+            Side::Sell => self.current_symbol_price >= liquidation_price,
+        }
+    }
+
     /// Calculate the approximate [`Position::unrealised_profit_loss`] of a [`Position`].
     pub fn calculate_unrealised_profit_loss(&self) -> f64 {
         let approx_total_fees = self.enter_fees_total * 2.0;
@@ -259,7 +660,25 @@ impl Position {
     /// Calculate the PnL return of a closed [`Position`] - assumed [`Position::realised_profit_loss`] is
     /// appropriately calculated.
     pub fn calculate_profit_loss_return(&self) -> f64 {
-        self.realised_profit_loss / self.enter_value_gross
+        self.realised_profit_loss / (self.enter_value_gross + self.closed_enter_value_gross)
+    }
+}
+
+/// Sums every [`FeeAmount`] of the provided [`Fees`].
+fn add_fees(fees: Fees, other: Fees) -> Fees {
+    Fees {
+        exchange: fees.exchange + other.exchange,
+        slippage: fees.slippage + other.slippage,
+        network: fees.network + other.network,
+    }
+}
+
+/// Scales every [`FeeAmount`] of the provided [`Fees`] by the factor.
+fn scale_fees(fees: Fees, factor: f64) -> Fees {
+    Fees {
+        exchange: fees.exchange * factor,
+        slippage: fees.slippage * factor,
+        network: fees.network * factor,
     }
 }
 
@@ -269,6 +688,7 @@ pub struct PositionBuilder {
     pub position_id: Option<PositionId>,
     pub exchange: Option<ExchangeId>,
     pub instrument: Option<Instrument>,
+    pub strategy_id: Option<StrategyId>,
     pub meta: Option<PositionMeta>,
     pub side: Option<Side>,
     pub quantity: Option<f64>,
@@ -284,6 +704,12 @@ pub struct PositionBuilder {
     pub current_value_gross: Option<f64>,
     pub unrealised_profit_loss: Option<f64>,
     pub realised_profit_loss: Option<f64>,
+    pub closed_enter_value_gross: Option<f64>,
+    pub funding_profit_loss: Option<f64>,
+    pub leverage: Option<f64>,
+    pub liquidated: Option<bool>,
+    pub synthetic_exit: Option<bool>,
+    pub close_reason: Option<CloseReason>,
 }
 
 impl PositionBuilder {
@@ -319,6 +745,13 @@ impl PositionBuilder {
         }
     }
 
+    pub fn strategy_id(self, value: StrategyId) -> Self {
+        Self {
+            strategy_id: Some(value),
+            ..self
+        }
+    }
+
     pub fn side(self, value: Side) -> Self {
         Self {
             side: Some(value),
@@ -417,6 +850,48 @@ impl PositionBuilder {
         }
     }
 
+    pub fn closed_enter_value_gross(self, value: f64) -> Self {
+        Self {
+            closed_enter_value_gross: Some(value),
+            ..self
+        }
+    }
+
+    pub fn funding_profit_loss(self, value: f64) -> Self {
+        Self {
+            funding_profit_loss: Some(value),
+            ..self
+        }
+    }
+
+    pub fn leverage(self, value: f64) -> Self {
+        Self {
+            leverage: Some(value),
+            ..self
+        }
+    }
+
+    pub fn liquidated(self, value: bool) -> Self {
+        Self {
+            liquidated: Some(value),
+            ..self
+        }
+    }
+
+    pub fn synthetic_exit(self, value: bool) -> Self {
+        Self {
+            synthetic_exit: Some(value),
+            ..self
+        }
+    }
+
+    pub fn close_reason(self, value: CloseReason) -> Self {
+        Self {
+            close_reason: Some(value),
+            ..self
+        }
+    }
+
     pub fn build(self) -> Result<Position, PortfolioError> {
         Ok(Position {
             position_id: self
@@ -428,6 +903,7 @@ impl PositionBuilder {
             instrument: self
                 .instrument
                 .ok_or(PortfolioError::BuilderIncomplete("instrument"))?,
+            strategy_id: self.strategy_id.unwrap_or_default(),
             meta: self.meta.ok_or(PortfolioError::BuilderIncomplete("meta"))?,
             side: self.side.ok_or(PortfolioError::BuilderIncomplete("side"))?,
             quantity: self
@@ -469,6 +945,12 @@ impl PositionBuilder {
             realised_profit_loss: self
                 .realised_profit_loss
                 .ok_or(PortfolioError::BuilderIncomplete("realised_profit_loss"))?,
+            closed_enter_value_gross: self.closed_enter_value_gross.unwrap_or_default(),
+            funding_profit_loss: self.funding_profit_loss.unwrap_or_default(),
+            leverage: self.leverage.unwrap_or_else(default_leverage),
+            liquidated: self.liquidated.unwrap_or_default(),
+            synthetic_exit: self.synthetic_exit.unwrap_or_default(),
+            close_reason: self.close_reason,
         })
     }
 }
@@ -485,6 +967,25 @@ pub struct PositionMeta {
 
     /// Portfolio [`Balance`] calculated at the point of exiting a [`Position`].
     pub exit_balance: Option<Balance>,
+
+    /// Most favourable price reached since entering the [`Position`], initialised from the entry
+    /// price. Persisted with the [`Position`] so trailing stops survive restarts.
+    #[serde(default)]
+    pub high_water_price: Option<f64>,
+
+    /// Number of entry [`FillEvent`]s that entered & increased the [`Position`].
+    #[serde(default = "default_entries")]
+    pub entries: u32,
+
+    /// Maximum Favourable Excursion (MFE), being the best unrealised profit (gross of fees)
+    /// reached while the [`Position`] was open. Zero if it was never in profit.
+    #[serde(default)]
+    pub max_favourable_excursion: f64,
+
+    /// Maximum Adverse Excursion (MAE), being the magnitude of the worst unrealised loss (gross
+    /// of fees) reached while the [`Position`] was open. Zero if it was never at a loss.
+    #[serde(default)]
+    pub max_adverse_excursion: f64,
 }
 
 impl Default for PositionMeta {
@@ -493,6 +994,10 @@ impl Default for PositionMeta {
             enter_time: Utc::now(),
             update_time: Utc::now(),
             exit_balance: None,
+            high_water_price: None,
+            entries: default_entries(),
+            max_favourable_excursion: 0.0,
+            max_adverse_excursion: 0.0,
         }
     }
 }
@@ -550,6 +1055,27 @@ pub struct PositionExit {
 
     /// Realised P&L after the [`Position`] has closed.
     pub realised_profit_loss: f64,
+
+    /// Flag indicating the [`Position`] was force-closed by a liquidation.
+    #[serde(default)]
+    pub liquidated: bool,
+
+    /// Flag indicating the [`Position`] was force-closed at it's last mark price without market
+    /// data to price the close.
+    #[serde(default)]
+    pub synthetic_exit: bool,
+
+    /// Origin of the exit that closed the [`Position`], if known.
+    #[serde(default)]
+    pub close_reason: Option<CloseReason>,
+
+    /// Best unrealised profit reached while the [`Position`] was open (see [`PositionMeta`]).
+    #[serde(default)]
+    pub max_favourable_excursion: f64,
+
+    /// Worst unrealised loss reached while the [`Position`] was open (see [`PositionMeta`]).
+    #[serde(default)]
+    pub max_adverse_excursion: f64,
 }
 
 impl TryFrom<&mut Position> for PositionExit {
@@ -568,6 +1094,11 @@ impl TryFrom<&mut Position> for PositionExit {
             exit_avg_price_gross: exited_position.exit_avg_price_gross,
             exit_value_gross: exited_position.exit_value_gross,
             realised_profit_loss: exited_position.realised_profit_loss,
+            liquidated: exited_position.liquidated,
+            synthetic_exit: exited_position.synthetic_exit,
+            close_reason: exited_position.close_reason,
+            max_favourable_excursion: exited_position.meta.max_favourable_excursion,
+            max_adverse_excursion: exited_position.meta.max_adverse_excursion,
         })
     }
 }
@@ -581,7 +1112,7 @@ mod tests {
     #[test]
     fn enter_new_position_with_long_decision_provided() {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Long;
+        input_fill.decision = Decision::EnterLong;
         input_fill.quantity = 1.0;
         input_fill.fill_value_gross = 100.0;
         input_fill.fees = Fees {
@@ -618,7 +1149,7 @@ mod tests {
     #[test]
     fn enter_new_position_with_short_decision_provided() {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Short;
+        input_fill.decision = Decision::EnterShort;
         input_fill.quantity = -1.0;
         input_fill.fill_value_gross = 100.0;
         input_fill.fees = Fees {
@@ -699,7 +1230,7 @@ mod tests {
     fn enter_new_position_and_return_err_with_negative_quantity_long_decision_provided(
     ) -> Result<(), String> {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Long;
+        input_fill.decision = Decision::EnterLong;
         input_fill.quantity = -1.0;
         input_fill.fill_value_gross = 100.0;
         input_fill.fees = Fees {
@@ -721,7 +1252,7 @@ mod tests {
     fn enter_new_position_and_return_err_with_positive_quantity_short_decision_provided(
     ) -> Result<(), String> {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Short;
+        input_fill.decision = Decision::EnterShort;
         input_fill.quantity = 1.0;
         input_fill.fill_value_gross = 100.0;
         input_fill.fees = Fees {
@@ -966,6 +1497,57 @@ mod tests {
         assert_eq!(position.unrealised_profit_loss, (100.0 - 200.0 - 6.0));
     }
 
+    #[test]
+    fn position_records_max_favourable_and_adverse_excursion_through_peak_and_trough() {
+        // Enter Long 2.0 @ 100.0
+        let mut enter_fill = fill_event();
+        enter_fill.decision = Decision::EnterLong;
+        enter_fill.quantity = 2.0;
+        enter_fill.fill_value_gross = 200.0;
+        let mut position = Position::enter(Uuid::new_v4(), &enter_fill).unwrap();
+
+        // Mark the Position through a peak of 120.0 & a trough of 90.0
+        for price in [110.0, 120.0, 105.0, 90.0, 100.0] {
+            let mut market = market_event_trade(Side::Buy);
+            if let DataKind::Trade(trade) = &mut market.kind {
+                trade.price = price;
+            }
+            position.update(&market);
+        }
+        assert_eq!(position.meta.max_favourable_excursion, 40.0);
+        assert_eq!(position.meta.max_adverse_excursion, 20.0);
+
+        // Exit @ 102.0, within the range of excursions already reached
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseLong;
+        exit_fill.quantity = -2.0;
+        exit_fill.fill_value_gross = 204.0;
+        let exit = position
+            .exit(Balance::new(Utc::now(), 10_000.0, 10_000.0), &exit_fill)
+            .unwrap();
+
+        assert_eq!(exit.max_favourable_excursion, 40.0);
+        assert_eq!(exit.max_adverse_excursion, 20.0);
+    }
+
+    #[test]
+    fn position_exited_on_opening_event_has_zero_excursions() {
+        let mut enter_fill = fill_event();
+        enter_fill.decision = Decision::EnterShort;
+        enter_fill.quantity = -1.0;
+        let mut position = Position::enter(Uuid::new_v4(), &enter_fill).unwrap();
+
+        let mut exit_fill = fill_event();
+        exit_fill.decision = Decision::CloseShort;
+        exit_fill.quantity = 1.0;
+        let exit = position
+            .exit(Balance::new(Utc::now(), 10_000.0, 10_000.0), &exit_fill)
+            .unwrap();
+
+        assert_eq!(exit.max_favourable_excursion, 0.0);
+        assert_eq!(exit.max_adverse_excursion, 0.0);
+    }
+
     #[test]
     fn exit_long_position_with_positive_real_pnl() {
         // Initial Position
@@ -1277,7 +1859,7 @@ mod tests {
 
         // Input FillEvent
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Long;
+        input_fill.decision = Decision::EnterLong;
         input_fill.quantity = position.quantity;
         input_fill.fill_value_gross = 200.0;
         input_fill.fees = Fees {
@@ -1323,7 +1905,7 @@ mod tests {
 
         // Input FillEvent
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Short;
+        input_fill.decision = Decision::EnterShort;
         input_fill.quantity = -position.quantity;
         input_fill.fill_value_gross = 200.0;
         input_fill.fees = Fees {
@@ -1367,7 +1949,7 @@ mod tests {
     #[test]
     fn parse_entry_side_as_long_with_positive_quantity_long_decision_provided() {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Long;
+        input_fill.decision = Decision::EnterLong;
         input_fill.quantity = 1.0;
 
         let actual = Position::parse_entry_side(&input_fill).unwrap();
@@ -1378,7 +1960,7 @@ mod tests {
     #[test]
     fn parse_entry_side_as_short_with_negative_quantity_short_decision_provided() {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Short;
+        input_fill.decision = Decision::EnterShort;
         input_fill.quantity = -1.0;
 
         let actual = Position::parse_entry_side(&input_fill).unwrap();
@@ -1420,7 +2002,7 @@ mod tests {
     fn parse_entry_side_and_return_err_with_negative_quantity_long_decision_provided(
     ) -> Result<(), String> {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Long;
+        input_fill.decision = Decision::EnterLong;
         input_fill.quantity = -1.0;
 
         if let Err(_) = Position::parse_entry_side(&input_fill) {
@@ -1436,7 +2018,7 @@ mod tests {
     fn parse_entry_side_and_return_err_with_positive_quantity_short_decision_provided(
     ) -> Result<(), String> {
         let mut input_fill = fill_event();
-        input_fill.decision = Decision::Short;
+        input_fill.decision = Decision::EnterShort;
         input_fill.quantity = 1.0;
 
         if let Err(_) = Position::parse_entry_side(&input_fill) {
@@ -1642,4 +2224,103 @@ mod tests {
 
         assert!(PositionExit::try_from(&mut exited_position).is_err());
     }
+
+    fn partial_fill(decision: Decision, quantity: f64, price: f64, fee: f64) -> FillEvent {
+        let mut fill = fill_event();
+        fill.decision = decision;
+        fill.quantity = quantity;
+        fill.fill_value_gross = quantity.abs() * price;
+        fill.fees = Fees {
+            exchange: fee,
+            slippage: 0.0,
+            network: 0.0,
+        };
+        fill
+    }
+
+    #[test]
+    fn increase_position_accumulates_quantity_and_averages_enter_price() {
+        let mut position = Position::enter(
+            Uuid::new_v4(),
+            &partial_fill(Decision::EnterLong, 1.0, 100.0, 1.0),
+        )
+        .unwrap();
+
+        let increase = partial_fill(Decision::EnterLong, 3.0, 120.0, 2.0);
+        assert_eq!(
+            position.determine_fill_effect(&increase),
+            FillEffect::Increase
+        );
+        let update = position.increase(&increase);
+
+        assert_eq!(position.quantity, 4.0);
+        assert_eq!(position.enter_value_gross, 460.0);
+        assert_eq!(position.enter_avg_price_gross, 115.0);
+        assert_eq!(position.enter_fees_total, 3.0);
+        assert_eq!(position.enter_fees.exchange, 3.0);
+        assert_eq!(
+            update.unrealised_profit_loss,
+            position.unrealised_profit_loss
+        );
+    }
+
+    #[test]
+    fn reduce_position_realises_proportional_profit_loss() {
+        let mut position = Position::enter(
+            Uuid::new_v4(),
+            &partial_fill(Decision::EnterLong, 4.0, 100.0, 4.0),
+        )
+        .unwrap();
+
+        // Close 1 of 4 contracts at 110.0: (110 - 100) - 1.0 enter fee share - 0.5 exit fee
+        let partial_exit = partial_fill(Decision::CloseLong, -1.0, 110.0, 0.5);
+        assert_eq!(
+            position.determine_fill_effect(&partial_exit),
+            FillEffect::PartialExit
+        );
+        let realised = position.reduce(&partial_exit);
+
+        assert_eq!(realised, 8.5);
+        assert_eq!(position.quantity, 3.0);
+        assert_eq!(position.enter_avg_price_gross, 100.0);
+        assert_eq!(position.enter_value_gross, 300.0);
+        assert_eq!(position.enter_fees_total, 3.0);
+        assert_eq!(position.closed_enter_value_gross, 100.0);
+        assert_eq!(position.realised_profit_loss, 8.5);
+
+        // Close remaining 3 contracts at 90.0: (270 - 300) - 3.0 enter fees - 1.5 exit fee
+        let exit = partial_fill(Decision::CloseLong, -3.0, 90.0, 1.5);
+        assert_eq!(position.determine_fill_effect(&exit), FillEffect::Exit);
+        let position_exit = position.exit(Balance::default(), &exit).unwrap();
+
+        assert_eq!(position_exit.realised_profit_loss, 8.5 - 34.5);
+        assert_eq!(position.exit_value_gross, 380.0);
+        assert_eq!(position.exit_avg_price_gross, 95.0);
+        assert_eq!(position.exit_fees_total, 2.0);
+        assert_eq!(position.calculate_profit_loss_return(), -26.0 / 400.0);
+    }
+
+    #[test]
+    fn opposing_entry_fill_larger_than_position_flips_position() {
+        let position = Position::enter(
+            Uuid::new_v4(),
+            &partial_fill(Decision::EnterLong, 2.0, 100.0, 2.0),
+        )
+        .unwrap();
+
+        let flip = partial_fill(Decision::EnterShort, -5.0, 90.0, 5.0);
+        assert_eq!(position.determine_fill_effect(&flip), FillEffect::Flip);
+
+        let (exit, entry) = position.split_flip_fill(&flip);
+
+        assert_eq!(exit.decision, Decision::CloseLong);
+        assert_eq!(exit.quantity, -2.0);
+        assert_eq!(exit.fill_value_gross, 180.0);
+        assert_eq!(exit.fees.exchange, 2.0);
+
+        assert_eq!(entry.decision, Decision::EnterShort);
+        assert_eq!(entry.quantity, -3.0);
+        assert_eq!(entry.fill_value_gross, 270.0);
+        assert_eq!(entry.fees.exchange, 3.0);
+    }
 }
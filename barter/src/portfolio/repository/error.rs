@@ -15,6 +15,33 @@ pub enum RepositoryError {
     #[error("Failed to delete data from the repository")]
     DeleteError,
 
+    #[error("Failed to connect to the repository")]
+    ConnectionError,
+
     #[error("Failed to retrieve expected data due to it not being present")]
     ExpectedDataNotPresentError,
+
+    #[cfg(feature = "postgres")]
+    #[error("Failed to build the Postgres repository runtime due to: {0}")]
+    PostgresRuntimeError(#[source] std::io::Error),
+
+    #[cfg(feature = "postgres")]
+    #[error("Failed to connect to the Postgres repository due to: {0}")]
+    PostgresConnectionError(#[source] sqlx::Error),
+
+    #[cfg(feature = "postgres")]
+    #[error("Failed to apply Postgres repository migrations due to: {0}")]
+    PostgresMigrationError(#[source] sqlx::migrate::MigrateError),
+
+    #[cfg(feature = "postgres")]
+    #[error("Failed to write data to the Postgres repository due to: {0}")]
+    PostgresWriteError(#[source] sqlx::Error),
+
+    #[cfg(feature = "postgres")]
+    #[error("Failed to read data from the Postgres repository due to: {0}")]
+    PostgresReadError(#[source] sqlx::Error),
+
+    #[cfg(feature = "postgres")]
+    #[error("Failed to delete data from the Postgres repository due to: {0}")]
+    PostgresDeleteError(#[source] sqlx::Error),
 }
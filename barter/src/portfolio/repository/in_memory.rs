@@ -1,15 +1,18 @@
 use crate::{
     portfolio::{
-        position::{determine_position_id, Position, PositionId},
+        position::{determine_market_position_ids, Position, PositionId},
         repository::{
-            determine_exited_positions_id, error::RepositoryError, BalanceHandler, PositionHandler,
-            StatisticHandler,
+            determine_exited_positions_id, determine_statistics_id, error::RepositoryError,
+            BalanceHandler, PositionHandler, StatisticHandler, StatisticsId,
         },
         Balance, BalanceId,
     },
     statistic::summary::PositionSummariser,
 };
-use barter_instrument::market::{Market, MarketId};
+use barter_instrument::{
+    asset::symbol::Symbol,
+    market::{Market, MarketId},
+};
 use smol_str::SmolStr;
 use std::collections::HashMap;
 use uuid::Uuid;
@@ -23,7 +26,8 @@ pub struct InMemoryRepository<Statistic: PositionSummariser> {
     open_positions: HashMap<PositionId, Position>,
     closed_positions: HashMap<String, Vec<Position>>,
     current_balances: HashMap<BalanceId, Balance>,
-    statistics: HashMap<MarketId, Statistic>,
+    currency_balances: HashMap<BalanceId, HashMap<Symbol, Balance>>,
+    statistics: HashMap<StatisticsId, Statistic>,
 }
 
 impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Statistic> {
@@ -46,15 +50,10 @@ impl<Statistic: PositionSummariser> PositionHandler for InMemoryRepository<Stati
         markets: Markets,
     ) -> Result<Vec<Position>, RepositoryError> {
         Ok(markets
-            .filter_map(|market| {
-                self.open_positions
-                    .get(&determine_position_id(
-                        engine_id,
-                        &market.exchange,
-                        &market.instrument,
-                    ))
-                    .cloned()
+            .flat_map(|market| {
+                determine_market_position_ids(engine_id, &market.exchange, &market.instrument)
             })
+            .filter_map(|position_id| self.open_positions.get(&position_id).cloned())
             .collect())
     }
 
@@ -104,21 +103,51 @@ impl<Statistic: PositionSummariser> BalanceHandler for InMemoryRepository<Statis
             .copied()
             .ok_or(RepositoryError::ExpectedDataNotPresentError)
     }
+
+    fn set_currency_balance(
+        &mut self,
+        engine_id: Uuid,
+        currency: Symbol,
+        balance: Balance,
+    ) -> Result<(), RepositoryError> {
+        self.currency_balances
+            .entry(Balance::balance_id(engine_id))
+            .or_default()
+            .insert(currency, balance);
+        Ok(())
+    }
+
+    fn get_currency_balances(
+        &mut self,
+        engine_id: Uuid,
+    ) -> Result<HashMap<Symbol, Balance>, RepositoryError> {
+        Ok(self
+            .currency_balances
+            .get(&Balance::balance_id(engine_id))
+            .cloned()
+            .unwrap_or_default())
+    }
 }
 
 impl<Statistic: PositionSummariser> StatisticHandler<Statistic> for InMemoryRepository<Statistic> {
     fn set_statistics(
         &mut self,
+        engine_id: Uuid,
         market_id: MarketId,
         statistic: Statistic,
     ) -> Result<(), RepositoryError> {
-        self.statistics.insert(market_id, statistic);
+        self.statistics
+            .insert(determine_statistics_id(engine_id, &market_id), statistic);
         Ok(())
     }
 
-    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+    fn get_statistics(
+        &mut self,
+        engine_id: Uuid,
+        market_id: &MarketId,
+    ) -> Result<Statistic, RepositoryError> {
         self.statistics
-            .get(market_id)
+            .get(&determine_statistics_id(engine_id, market_id))
             .copied()
             .ok_or(RepositoryError::ExpectedDataNotPresentError)
     }
@@ -131,7 +160,71 @@ impl<Statistic: PositionSummariser> InMemoryRepository<Statistic> {
             open_positions: HashMap::new(),
             closed_positions: HashMap::new(),
             current_balances: HashMap::new(),
+            currency_balances: HashMap::new(),
             statistics: HashMap::new(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::position::determine_position_id, statistic::summary::pnl::PnLReturnSummary,
+        test_util::position,
+    };
+
+    #[test]
+    fn portfolios_sharing_repository_read_back_independent_state() {
+        let mut repository = InMemoryRepository::<PnLReturnSummary>::new();
+        let (engine_a, engine_b) = (Uuid::new_v4(), Uuid::new_v4());
+
+        // Both Portfolios hold a Position in the same market
+        let mut position_a = position();
+        position_a.position_id =
+            determine_position_id(engine_a, &position_a.exchange, &position_a.instrument);
+        let mut position_b = position();
+        position_b.position_id =
+            determine_position_id(engine_b, &position_b.exchange, &position_b.instrument);
+        position_b.quantity = 2.0;
+
+        repository.set_open_position(position_a.clone()).unwrap();
+        repository.set_open_position(position_b.clone()).unwrap();
+
+        let market = Market::new(position_a.exchange, position_a.instrument.clone());
+        assert_eq!(
+            repository
+                .get_open_positions(engine_a, std::iter::once(&market))
+                .unwrap(),
+            vec![position_a.clone()]
+        );
+        assert_eq!(
+            repository
+                .get_open_positions(engine_b, std::iter::once(&market))
+                .unwrap(),
+            vec![position_b.clone()]
+        );
+
+        // Statistics for the same market are namespaced by engine_id
+        let market_id = MarketId::from(&market);
+        let statistic_a = PnLReturnSummary::default();
+        let mut statistic_b = PnLReturnSummary::default();
+        statistic_b.update(&position_b);
+
+        repository
+            .set_statistics(engine_a, market_id.clone(), statistic_a)
+            .unwrap();
+        repository
+            .set_statistics(engine_b, market_id.clone(), statistic_b)
+            .unwrap();
+
+        assert_eq!(
+            repository.get_statistics(engine_a, &market_id).unwrap(),
+            statistic_a
+        );
+        assert_eq!(
+            repository.get_statistics(engine_b, &market_id).unwrap(),
+            statistic_b
+        );
+    }
+}
@@ -3,7 +3,11 @@ use crate::portfolio::{
     repository::error::RepositoryError,
     Balance,
 };
-use barter_instrument::market::{Market, MarketId};
+use barter_instrument::{
+    asset::symbol::Symbol,
+    market::{Market, MarketId},
+};
+use std::collections::HashMap;
 use uuid::Uuid;
 
 /// Barter repository module specific errors.
@@ -15,6 +19,10 @@ pub mod in_memory;
 /// Redis repository for state keeping.
 pub mod redis;
 
+/// PostgreSQL repository for durable, queryable state keeping.
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
 /// Handles the reading & writing of a [`Position`] to/from the persistence layer.
 pub trait PositionHandler {
     /// Upsert the open [`Position`] using it's [`PositionId`].
@@ -26,7 +34,9 @@ pub trait PositionHandler {
         position_id: &PositionId,
     ) -> Result<Option<Position>, RepositoryError>;
 
-    /// Get all open [`Position`]s associated with a Portfolio.
+    /// Get all open [`Position`]s associated with a Portfolio, including both the long & short
+    /// [`Position`] of a market held in
+    /// [`PositionMode::Hedging`](crate::portfolio::position::PositionMode::Hedging).
     fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
         &mut self,
         engine_id: Uuid,
@@ -56,19 +66,37 @@ pub trait BalanceHandler {
     fn set_balance(&mut self, engine_id: Uuid, balance: Balance) -> Result<(), RepositoryError>;
     /// Get the Portfolio [`Balance`] using the engine_id provided.
     fn get_balance(&mut self, engine_id: Uuid) -> Result<Balance, RepositoryError>;
+    /// Upsert the Portfolio [`Balance`] denominated in the provided currency at the engine_id.
+    fn set_currency_balance(
+        &mut self,
+        engine_id: Uuid,
+        currency: Symbol,
+        balance: Balance,
+    ) -> Result<(), RepositoryError>;
+    /// Get the Portfolio [`Balance`] of every currency held, using the engine_id provided.
+    fn get_currency_balances(
+        &mut self,
+        engine_id: Uuid,
+    ) -> Result<HashMap<Symbol, Balance>, RepositoryError>;
 }
 
 /// Handles the reading & writing of a Portfolio's statistics for each of it's
-/// markets, where each market is represented by a [`MarketId`].
+/// markets, where each market is represented by a [`MarketId`]. Statistics are namespaced by the
+/// engine_id so Portfolios sharing a persistence layer never overwrite each other's statistics.
 pub trait StatisticHandler<Statistic> {
-    /// Upsert the market statistics at the [`MarketId`] provided.
+    /// Upsert the market statistics at the engine_id & [`MarketId`] provided.
     fn set_statistics(
         &mut self,
+        engine_id: Uuid,
         market_id: MarketId,
         statistic: Statistic,
     ) -> Result<(), RepositoryError>;
-    /// Get the market statistics using the [`MarketId`] provided.
-    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError>;
+    /// Get the market statistics using the engine_id & [`MarketId`] provided.
+    fn get_statistics(
+        &mut self,
+        engine_id: Uuid,
+        market_id: &MarketId,
+    ) -> Result<Statistic, RepositoryError>;
 }
 
 /// Communicates a String represents a unique identifier for all a Portfolio's exited [`Position`]s.
@@ -79,3 +107,12 @@ pub type ExitedPositionsId = String;
 pub fn determine_exited_positions_id(engine_id: Uuid) -> ExitedPositionsId {
     format!("positions_exited_{}", engine_id)
 }
+
+/// Communicates a String represents a unique identifier for a Portfolio's market statistics.
+pub type StatisticsId = String;
+
+/// Returns the unique identifier for a Portfolio's market statistics, given an engine_id &
+/// [`MarketId`].
+pub fn determine_statistics_id(engine_id: Uuid, market_id: &MarketId) -> StatisticsId {
+    format!("{}_{}_statistics", engine_id, market_id.0)
+}
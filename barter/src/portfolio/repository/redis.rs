@@ -1,20 +1,24 @@
 use crate::{
     portfolio::{
         error::PortfolioError,
-        position::{determine_position_id, Position, PositionId},
+        position::{determine_market_position_ids, Position, PositionId},
         repository::{
-            determine_exited_positions_id, error::RepositoryError, BalanceHandler, PositionHandler,
-            StatisticHandler,
+            determine_exited_positions_id, determine_statistics_id, error::RepositoryError,
+            BalanceHandler, PositionHandler, StatisticHandler,
         },
         Balance,
     },
     statistic::summary::PositionSummariser,
 };
-use barter_instrument::market::{Market, MarketId};
+use barter_instrument::{
+    asset::symbol::Symbol,
+    market::{Market, MarketId},
+};
 use redis::{Commands, Connection, ErrorKind};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use smol_str::SmolStr;
 use std::{
+    collections::HashMap,
     fmt::{Debug, Formatter},
     marker::PhantomData,
 };
@@ -53,12 +57,15 @@ where
         &mut self,
         position_id: &PositionId,
     ) -> Result<Option<Position>, RepositoryError> {
-        let position_value: String = self
+        let position_value: Option<String> = self
             .conn
             .get(position_id.as_str())
             .map_err(|_| RepositoryError::ReadError)?;
 
-        Ok(Some(serde_json::from_str::<Position>(&position_value)?))
+        position_value
+            .map(|position_value| serde_json::from_str::<Position>(&position_value))
+            .transpose()
+            .map_err(RepositoryError::from)
     }
 
     fn get_open_positions<'a, Markets: Iterator<Item = &'a Market>>(
@@ -67,14 +74,10 @@ where
         markets: Markets,
     ) -> Result<Vec<Position>, RepositoryError> {
         markets
-            .filter_map(|market| {
-                self.get_open_position(&determine_position_id(
-                    engine_id,
-                    &market.exchange,
-                    &market.instrument,
-                ))
-                .transpose()
+            .flat_map(|market| {
+                determine_market_position_ids(engine_id, &market.exchange, &market.instrument)
             })
+            .filter_map(|position_id| self.get_open_position(&position_id).transpose())
             .collect()
     }
 
@@ -85,7 +88,7 @@ where
         let position = self.get_open_position(position_id)?;
 
         self.conn
-            .del(position_id.as_str())
+            .del::<_, ()>(position_id.as_str())
             .map_err(|_| RepositoryError::DeleteError)?;
 
         Ok(position)
@@ -138,6 +141,42 @@ where
 
         Ok(serde_json::from_str::<Balance>(&balance_value)?)
     }
+
+    fn set_currency_balance(
+        &mut self,
+        engine_id: Uuid,
+        currency: Symbol,
+        balance: Balance,
+    ) -> Result<(), RepositoryError> {
+        let balance_string = serde_json::to_string(&balance)?;
+
+        self.conn
+            .hset(
+                Balance::currency_balances_id(engine_id),
+                currency.as_ref(),
+                balance_string,
+            )
+            .map_err(|_| RepositoryError::WriteError)
+    }
+
+    fn get_currency_balances(
+        &mut self,
+        engine_id: Uuid,
+    ) -> Result<HashMap<Symbol, Balance>, RepositoryError> {
+        let balance_values: HashMap<String, String> = self
+            .conn
+            .hgetall(Balance::currency_balances_id(engine_id))
+            .map_err(|_| RepositoryError::ReadError)?;
+
+        balance_values
+            .into_iter()
+            .map(|(currency, balance)| {
+                serde_json::from_str::<Balance>(&balance)
+                    .map(|balance| (Symbol::from(currency), balance))
+                    .map_err(RepositoryError::JsonSerDeError)
+            })
+            .collect()
+    }
 }
 
 impl<Statistic> StatisticHandler<Statistic> for RedisRepository<Statistic>
@@ -146,18 +185,26 @@ where
 {
     fn set_statistics(
         &mut self,
+        engine_id: Uuid,
         market_id: MarketId,
         statistic: Statistic,
     ) -> Result<(), RepositoryError> {
         self.conn
-            .set(market_id.0.as_str(), serde_json::to_string(&statistic)?)
+            .set(
+                determine_statistics_id(engine_id, &market_id),
+                serde_json::to_string(&statistic)?,
+            )
             .map_err(|_| RepositoryError::WriteError)
     }
 
-    fn get_statistics(&mut self, market_id: &MarketId) -> Result<Statistic, RepositoryError> {
+    fn get_statistics(
+        &mut self,
+        engine_id: Uuid,
+        market_id: &MarketId,
+    ) -> Result<Statistic, RepositoryError> {
         let statistics: String = self
             .conn
-            .get(market_id.0.as_str())
+            .get(determine_statistics_id(engine_id, market_id))
             .map_err(|_| RepositoryError::ReadError)?;
 
         serde_json::from_str(&statistics).map_err(RepositoryError::JsonSerDeError)
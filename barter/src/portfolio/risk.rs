@@ -1,6 +1,19 @@
+use crate::{
+    portfolio::{
+        margin::MarginAccount,
+        position::{CloseReason, Position},
+        OrderEvent, OrderType,
+    },
+    statistic::{de_duration_from_secs, se_duration_as_secs},
+    strategy::indicator::Atr,
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::{instrument::Instrument, market::MarketId};
+use barter_integration::Side;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
-
-use crate::portfolio::{OrderEvent, OrderType};
+use std::collections::HashMap;
+use tracing::warn;
 
 /// Evaluates the risk associated with an [`OrderEvent`] to determine if it should be actioned. It
 /// can also amend the order (eg/ [`OrderType`]) to better fit the risk strategy required for
@@ -11,6 +24,39 @@ pub trait OrderEvaluator {
     /// May return an amended [`OrderEvent`] if the associated risk is appropriate. Returns `None`
     /// if the risk is too high.
     fn evaluate_order(&self, order: OrderEvent) -> Option<OrderEvent>;
+
+    /// Determines if an open [`Position`] has breached the risk limits (eg/ stop-loss) after
+    /// being updated with the latest market data, and should be closed. Default implementation
+    /// never closes a [`Position`].
+    fn should_exit(&self, _: &Position) -> bool {
+        false
+    }
+
+    /// Determines the [`CloseReason`] of an open [`Position`] that [`Self::should_exit`], which
+    /// is tagged on the [`Position`] when it's exit [`OrderEvent`] is generated. Default
+    /// implementation returns the generic [`CloseReason::Risk`].
+    fn exit_reason(&self, _: &Position) -> CloseReason {
+        CloseReason::Risk
+    }
+
+    /// Updates any internal risk state (eg/ volatility estimates) using the latest input
+    /// [`MarketEvent`]. Default implementation is a no-op.
+    fn update_from_market(&mut self, _: &MarketEvent<Instrument, DataKind>) {}
+
+    /// Updates any internal risk state (eg/ daily loss limits) using the latest Portfolio equity
+    /// (see [`MarginAccount::equity`]). Default implementation is a no-op.
+    fn update_from_equity(&mut self, _: DateTime<Utc>, _: f64) {}
+
+    /// Updates any internal risk state (eg/ trade cooldowns) using the latest exited
+    /// [`Position`]. Default implementation is a no-op.
+    fn update_from_exit(&mut self, _: &Position) {}
+
+    /// May return the entry [`OrderEvent`] if the initial margin it requires fits within the
+    /// available margin of the [`MarginAccount`]. Default implementation rejects any entry
+    /// [`OrderEvent`] that would exceed the available margin.
+    fn evaluate_margin(&self, order: OrderEvent, margin: &MarginAccount) -> Option<OrderEvent> {
+        (margin.required(&order) <= margin.available).then_some(order)
+    }
 }
 
 /// Default risk manager that implements [`OrderEvaluator`].
@@ -34,3 +80,774 @@ impl DefaultRisk {
         false
     }
 }
+
+/// Stop-loss & take-profit limits, expressed as fractions of a [`Position`]'s entry price
+/// (eg/ 0.05 for 5%). A `None` limit is never breached.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct StopLossLimits {
+    pub stop_loss: Option<f64>,
+    pub take_profit: Option<f64>,
+}
+
+/// Configuration for constructing a [`StopLossRisk`] via the new() constructor method.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct StopLossConfig {
+    /// [`StopLossLimits`] applied to every market without an override.
+    pub default: StopLossLimits,
+    /// Per-market [`StopLossLimits`] overrides.
+    #[serde(default)]
+    pub markets: HashMap<MarketId, StopLossLimits>,
+}
+
+/// Stop-loss & take-profit risk manager that implements [`OrderEvaluator`]. Orders are passed
+/// through unchanged, and an open [`Position`] is closed once it's current price breaches the
+/// configured [`StopLossLimits`] relative to it's entry price.
+///
+/// A long [`Position`] stop-loss is below the entry price & take-profit above it, and vice versa
+/// for a short [`Position`].
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct StopLossRisk {
+    pub config: StopLossConfig,
+}
+
+impl OrderEvaluator for StopLossRisk {
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, mut order: OrderEvent) -> Option<OrderEvent> {
+        order.order_type = StopLossRisk::DEFAULT_ORDER_TYPE;
+        Some(order)
+    }
+
+    fn should_exit(&self, position: &Position) -> bool {
+        self.breached_limit(position).is_some()
+    }
+
+    fn exit_reason(&self, position: &Position) -> CloseReason {
+        self.breached_limit(position)
+            .unwrap_or(CloseReason::StopLoss)
+    }
+}
+
+impl StopLossRisk {
+    /// Constructs a new [`StopLossRisk`] using the provided configuration.
+    pub fn new(config: StopLossConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the [`StopLossLimits`] of the provided [`MarketId`], falling back to the default.
+    pub fn limits(&self, market_id: &MarketId) -> StopLossLimits {
+        self.config
+            .markets
+            .get(market_id)
+            .copied()
+            .unwrap_or(self.config.default)
+    }
+
+    /// Determines which of the [`StopLossLimits`] the open [`Position`] has breached, if any.
+    fn breached_limit(&self, position: &Position) -> Option<CloseReason> {
+        let entry = position.enter_avg_price_gross;
+        let current = position.current_symbol_price;
+        if entry <= 0.0 {
+            return None;
+        }
+
+        let limits = self.limits(&MarketId::new(position.exchange, &position.instrument));
+
+        // Return relative to entry in the direction of the Position, positive when in profit
+        let directional_return = match position.side {
+            Side::Buy => (current - entry) / entry,
+            Side::Sell => (entry - current) / entry,
+        };
+
+        let stop_loss_breached = limits
+            .stop_loss
+            .is_some_and(|stop_loss| directional_return <= -stop_loss);
+        let take_profit_breached = limits
+            .take_profit
+            .is_some_and(|take_profit| directional_return >= take_profit);
+
+        if stop_loss_breached {
+            Some(CloseReason::StopLoss)
+        } else if take_profit_breached {
+            Some(CloseReason::TakeProfit)
+        } else {
+            None
+        }
+    }
+}
+
+/// Configuration for constructing a [`TrailingStopRisk`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct TrailingStopConfig {
+    /// Favourable move from the entry price required before the trailing stop is active, as a
+    /// fraction of the entry price (eg/ 0.05 for 5%).
+    pub activation: f64,
+    /// Retracement from the high-water price that closes the [`Position`], as a fraction of the
+    /// high-water price (eg/ 0.10 for 10%).
+    pub retracement: f64,
+}
+
+/// Trailing stop risk manager that implements [`OrderEvaluator`]. Orders are passed through
+/// unchanged, and an open [`Position`] is closed once price retraces by the configured fraction
+/// from it's high-water price (see [`Position::high_water_price`]).
+///
+/// The high-water price starts at the entry price and only ratchets in the favourable direction
+/// (up for a long, down for a short), so the stop never loosens. The stop is only active once
+/// price has moved favourably past the configured activation threshold.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct TrailingStopRisk {
+    pub config: TrailingStopConfig,
+}
+
+impl OrderEvaluator for TrailingStopRisk {
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, mut order: OrderEvent) -> Option<OrderEvent> {
+        order.order_type = TrailingStopRisk::DEFAULT_ORDER_TYPE;
+        Some(order)
+    }
+
+    fn should_exit(&self, position: &Position) -> bool {
+        let entry = position.enter_avg_price_gross;
+        let high_water = position.high_water_price();
+        let current = position.current_symbol_price;
+        if entry <= 0.0 || high_water <= 0.0 {
+            return false;
+        }
+
+        let (excursion, retracement) = match position.side {
+            Side::Buy => (
+                (high_water - entry) / entry,
+                (high_water - current) / high_water,
+            ),
+            Side::Sell => (
+                (entry - high_water) / entry,
+                (current - high_water) / high_water,
+            ),
+        };
+
+        excursion >= self.config.activation && retracement >= self.config.retracement
+    }
+
+    fn exit_reason(&self, _: &Position) -> CloseReason {
+        CloseReason::TrailingStop
+    }
+}
+
+impl TrailingStopRisk {
+    /// Constructs a new [`TrailingStopRisk`] using the provided configuration.
+    pub fn new(config: TrailingStopConfig) -> Self {
+        Self { config }
+    }
+
+    /// Returns the price at which the trailing stop closes the provided [`Position`], or `None`
+    /// if the activation threshold has not yet been reached.
+    pub fn stop_price(&self, position: &Position) -> Option<f64> {
+        let entry = position.enter_avg_price_gross;
+        let high_water = position.high_water_price();
+
+        match position.side {
+            Side::Buy if high_water >= entry * (1.0 + self.config.activation) => {
+                Some(high_water * (1.0 - self.config.retracement))
+            }
+            Side::Sell if high_water <= entry * (1.0 - self.config.activation) => {
+                Some(high_water * (1.0 + self.config.retracement))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Configuration for constructing an [`AtrStopRisk`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct AtrStopConfig {
+    /// Number of candles used by the [`Atr`] estimate.
+    pub atr_period: usize,
+    /// Multiple of the [`Atr`] the stop is placed away from the entry price.
+    pub multiplier: f64,
+    /// Stop distance used until the [`Atr`] is warm, as a fraction of the entry
+    /// price (eg/ 0.05 for 5%).
+    pub fallback_stop: f64,
+}
+
+/// Volatility adaptive stop-loss risk manager that implements [`OrderEvaluator`]. Orders are
+/// passed through unchanged, and an open [`Position`] is closed in full once price breaches a
+/// stop placed the configured multiple of the market's latest [`Atr`] away from
+/// the entry price.
+///
+/// A long [`Position`] stop is below the entry price, and a short [`Position`] stop is above it.
+/// Until the market's [`Atr`] is warm, the configured fallback percentage stop is
+/// used instead.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct AtrStopRisk {
+    pub config: AtrStopConfig,
+    /// [`Atr`] of each market, updated from candle [`MarketEvent`]s.
+    pub atrs: HashMap<MarketId, Atr>,
+}
+
+impl OrderEvaluator for AtrStopRisk {
+    const DEFAULT_ORDER_TYPE: OrderType = OrderType::Market;
+
+    fn evaluate_order(&self, mut order: OrderEvent) -> Option<OrderEvent> {
+        order.order_type = AtrStopRisk::DEFAULT_ORDER_TYPE;
+        Some(order)
+    }
+
+    fn should_exit(&self, position: &Position) -> bool {
+        if position.enter_avg_price_gross <= 0.0 {
+            return false;
+        }
+
+        let stop = self.stop_price(position);
+        match position.side {
+            Side::Buy => position.current_symbol_price <= stop,
+            Side::Sell => position.current_symbol_price >= stop,
+        }
+    }
+
+    fn exit_reason(&self, _: &Position) -> CloseReason {
+        CloseReason::StopLoss
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        let DataKind::Candle(candle) = &market.kind else {
+            return;
+        };
+
+        self.atrs
+            .entry(MarketId::new(market.exchange, &market.instrument))
+            .or_insert_with(|| Atr::new(self.config.atr_period))
+            .update(candle.high, candle.low, candle.close);
+    }
+}
+
+impl AtrStopRisk {
+    /// Constructs a new [`AtrStopRisk`] using the provided configuration.
+    pub fn new(config: AtrStopConfig) -> Self {
+        Self {
+            config,
+            atrs: HashMap::new(),
+        }
+    }
+
+    /// Returns the latest warm [`Atr`] value of the provided [`MarketId`].
+    pub fn atr(&self, market_id: &MarketId) -> Option<f64> {
+        self.atrs.get(market_id).and_then(Atr::value)
+    }
+
+    /// Returns the price at which the provided [`Position`] is stopped out.
+    pub fn stop_price(&self, position: &Position) -> f64 {
+        let entry = position.enter_avg_price_gross;
+        let distance = self
+            .atr(&MarketId::new(position.exchange, &position.instrument))
+            .map(|atr| self.config.multiplier * atr)
+            .unwrap_or(entry * self.config.fallback_stop);
+
+        match position.side {
+            Side::Buy => entry - distance,
+            Side::Sell => entry + distance,
+        }
+    }
+}
+
+/// Configuration for constructing a [`CircuitBreakerRisk`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct CircuitBreakerConfig {
+    /// Maximum loss of Portfolio equity (realised & unrealised) permitted within a UTC day
+    /// before new entries are halted.
+    pub daily_loss_limit: f64,
+}
+
+/// Daily loss circuit breaker layered over an inner [`OrderEvaluator`]. Once the Portfolio
+/// equity falls by more than the configured daily loss limit from it's value at the start of
+/// the UTC day, every entry [`OrderEvent`] is rejected until the next UTC day. Exit
+/// [`OrderEvent`]s are always delegated to the inner [`OrderEvaluator`], so open [`Position`]s
+/// can still be closed.
+///
+/// The start of day equity is the first Portfolio equity observed each UTC day, so losses never
+/// carry over from the previous day.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CircuitBreakerRisk<Risk = DefaultRisk> {
+    pub config: CircuitBreakerConfig,
+    pub risk: Risk,
+    /// UTC day the `day_start_equity` was observed.
+    day: Option<NaiveDate>,
+    day_start_equity: f64,
+    tripped: bool,
+}
+
+impl<Risk> OrderEvaluator for CircuitBreakerRisk<Risk>
+where
+    Risk: OrderEvaluator,
+{
+    const DEFAULT_ORDER_TYPE: OrderType = Risk::DEFAULT_ORDER_TYPE;
+
+    fn evaluate_order(&self, order: OrderEvent) -> Option<OrderEvent> {
+        if self.tripped && order.decision.is_entry() {
+            warn!(
+                exchange = %order.exchange,
+                instrument = %order.instrument,
+                daily_loss_limit = self.config.daily_loss_limit,
+                "rejecting entry OrderEvent while the daily loss circuit breaker is tripped"
+            );
+            return None;
+        }
+
+        self.risk.evaluate_order(order)
+    }
+
+    fn should_exit(&self, position: &Position) -> bool {
+        self.risk.should_exit(position)
+    }
+
+    fn exit_reason(&self, position: &Position) -> CloseReason {
+        self.risk.exit_reason(position)
+    }
+
+    fn evaluate_margin(&self, order: OrderEvent, margin: &MarginAccount) -> Option<OrderEvent> {
+        self.risk.evaluate_margin(order, margin)
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        self.risk.update_from_market(market)
+    }
+
+    fn update_from_equity(&mut self, time: DateTime<Utc>, equity: f64) {
+        // Reset the circuit breaker at each UTC day boundary
+        let day = time.date_naive();
+        if self.day != Some(day) {
+            self.day = Some(day);
+            self.day_start_equity = equity;
+            self.tripped = false;
+        }
+
+        if !self.tripped && self.day_start_equity - equity > self.config.daily_loss_limit {
+            warn!(
+                day_start_equity = self.day_start_equity,
+                equity,
+                daily_loss_limit = self.config.daily_loss_limit,
+                "daily loss limit exceeded, halting new entries until the next UTC day"
+            );
+            self.tripped = true;
+        }
+
+        self.risk.update_from_equity(time, equity)
+    }
+
+    fn update_from_exit(&mut self, position: &Position) {
+        self.risk.update_from_exit(position)
+    }
+}
+
+impl<Risk> CircuitBreakerRisk<Risk> {
+    /// Constructs a new [`CircuitBreakerRisk`] layered over the provided inner risk manager.
+    pub fn new(config: CircuitBreakerConfig, risk: Risk) -> Self {
+        Self {
+            config,
+            risk,
+            day: None,
+            day_start_equity: 0.0,
+            tripped: false,
+        }
+    }
+
+    /// Determines if the daily loss limit has been exceeded, halting new entries for the rest of
+    /// the current UTC day.
+    pub fn is_tripped(&self) -> bool {
+        self.tripped
+    }
+}
+
+/// Configuration for constructing a [`CooldownRisk`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct CooldownConfig {
+    /// Duration after a [`Position`] in a market is exited during which new entries in that
+    /// market are rejected, (de)serialised as seconds.
+    #[serde(
+        deserialize_with = "de_duration_from_secs",
+        serialize_with = "se_duration_as_secs"
+    )]
+    pub cooldown: Duration,
+}
+
+/// Per-market trade cooldown layered over an inner [`OrderEvaluator`]. Once a [`Position`] is
+/// exited, every entry [`OrderEvent`] in the same market is rejected until the cooldown has
+/// elapsed, preventing strategies from whipsawing in & out of a market. Exit [`OrderEvent`]s are
+/// always delegated to the inner [`OrderEvaluator`].
+///
+/// The cooldown is measured from the exit [`FillEvent`](crate::execution::FillEvent) timestamp
+/// to the [`OrderEvent`] timestamp, so it follows the event clock (eg/ in a backtest) rather than
+/// wall time. Exited [`Position`]s are persisted in the Repository, so the cooldowns are restored
+/// when a [`MetaPortfolio`](super::portfolio::MetaPortfolio) is re-initialised from it.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct CooldownRisk<Risk = DefaultRisk> {
+    pub config: CooldownConfig,
+    pub risk: Risk,
+    /// Time of the latest [`Position`] exit in each market.
+    last_exits: HashMap<MarketId, DateTime<Utc>>,
+}
+
+impl<Risk> OrderEvaluator for CooldownRisk<Risk>
+where
+    Risk: OrderEvaluator,
+{
+    const DEFAULT_ORDER_TYPE: OrderType = Risk::DEFAULT_ORDER_TYPE;
+
+    fn evaluate_order(&self, order: OrderEvent) -> Option<OrderEvent> {
+        if order.decision.is_entry() {
+            let market_id = MarketId::new(order.exchange, &order.instrument);
+            if let Some(cooldown_end) = self.cooldown_end(&market_id) {
+                if order.time < cooldown_end {
+                    warn!(
+                        exchange = %order.exchange,
+                        instrument = %order.instrument,
+                        %cooldown_end,
+                        "rejecting entry OrderEvent while the market is cooling down after an exit"
+                    );
+                    return None;
+                }
+            }
+        }
+
+        self.risk.evaluate_order(order)
+    }
+
+    fn should_exit(&self, position: &Position) -> bool {
+        self.risk.should_exit(position)
+    }
+
+    fn exit_reason(&self, position: &Position) -> CloseReason {
+        self.risk.exit_reason(position)
+    }
+
+    fn evaluate_margin(&self, order: OrderEvent, margin: &MarginAccount) -> Option<OrderEvent> {
+        self.risk.evaluate_margin(order, margin)
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        self.risk.update_from_market(market)
+    }
+
+    fn update_from_equity(&mut self, time: DateTime<Utc>, equity: f64) {
+        self.risk.update_from_equity(time, equity)
+    }
+
+    fn update_from_exit(&mut self, position: &Position) {
+        let market_id = MarketId::new(position.exchange, &position.instrument);
+        let exit_time = position.meta.update_time;
+
+        // Exits may be replayed out of order when restored from the Repository
+        self.last_exits
+            .entry(market_id)
+            .and_modify(|last_exit| *last_exit = (*last_exit).max(exit_time))
+            .or_insert(exit_time);
+
+        self.risk.update_from_exit(position)
+    }
+}
+
+impl<Risk> CooldownRisk<Risk> {
+    /// Constructs a new [`CooldownRisk`] layered over the provided inner risk manager.
+    pub fn new(config: CooldownConfig, risk: Risk) -> Self {
+        Self {
+            config,
+            risk,
+            last_exits: HashMap::new(),
+        }
+    }
+
+    /// Returns the time at which the cooldown of the provided market ends, if a [`Position`] in
+    /// the market has been exited.
+    pub fn cooldown_end(&self, market: &MarketId) -> Option<DateTime<Utc>> {
+        self.last_exits
+            .get(market)
+            .map(|last_exit| *last_exit + self.config.cooldown)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::position::PositionUpdater,
+        strategy::Decision,
+        test_util::{market_event_candle, market_event_trade, order_event, position},
+    };
+    use barter_instrument::{exchange::ExchangeId, instrument::kind::InstrumentKind};
+
+    fn stop_loss_risk() -> StopLossRisk {
+        let mut position = position();
+        position.exchange = ExchangeId::BinanceSpot;
+
+        StopLossRisk::new(StopLossConfig {
+            default: StopLossLimits {
+                stop_loss: Some(0.05),
+                take_profit: Some(0.10),
+            },
+            markets: HashMap::from([(
+                MarketId::new(position.exchange, &position.instrument),
+                StopLossLimits {
+                    stop_loss: Some(0.02),
+                    take_profit: None,
+                },
+            )]),
+        })
+    }
+
+    fn position_at(side: Side, current_symbol_price: f64) -> Position {
+        let mut position = position();
+        position.side = side;
+        position.enter_avg_price_gross = 100.0;
+        position.current_symbol_price = current_symbol_price;
+        position
+    }
+
+    #[test]
+    fn should_exit_respects_position_side() {
+        let mut risk = stop_loss_risk();
+        risk.config.markets.clear();
+
+        let cases = [
+            // Long stop-loss below entry & take-profit above entry
+            (Side::Buy, 96.0, false),
+            (Side::Buy, 95.0, true),
+            (Side::Buy, 109.0, false),
+            (Side::Buy, 110.0, true),
+            // Short stop-loss above entry & take-profit below entry
+            (Side::Sell, 104.0, false),
+            (Side::Sell, 105.0, true),
+            (Side::Sell, 91.0, false),
+            (Side::Sell, 90.0, true),
+        ];
+
+        for (index, (side, price, expected)) in cases.into_iter().enumerate() {
+            let actual = risk.should_exit(&position_at(side, price));
+            assert_eq!(actual, expected, "TC{index} failed");
+        }
+    }
+
+    #[test]
+    fn should_exit_uses_market_limits_over_default() {
+        let risk = stop_loss_risk();
+
+        // Market override stop-loss of 2% is breached before the default 5%
+        assert!(risk.should_exit(&position_at(Side::Buy, 98.0)));
+
+        // Market override has no take-profit, so the default 10% is not applied
+        assert!(!risk.should_exit(&position_at(Side::Buy, 150.0)));
+    }
+
+    /// Updates the [`Position`] with a trade at each price, returning the first price at which
+    /// the [`TrailingStopRisk`] fires.
+    fn first_trailing_exit(risk: &TrailingStopRisk, side: Side, prices: &[f64]) -> Option<f64> {
+        let mut position = position();
+        position.side = side;
+        position.enter_avg_price_gross = 100.0;
+        position.meta.high_water_price = Some(100.0);
+
+        prices.iter().copied().find(|price| {
+            let mut market = market_event_trade(Side::Buy);
+            if let DataKind::Trade(trade) = &mut market.kind {
+                trade.price = *price;
+            }
+            position.update(&market);
+
+            // High-water price is persisted with the Position, so it must survive a round trip
+            let position_json = serde_json::to_string(&position).unwrap();
+            position = serde_json::from_str(&position_json).unwrap();
+
+            risk.should_exit(&position)
+        })
+    }
+
+    #[test]
+    fn trailing_stop_fires_at_retracement_from_high_water_price() {
+        let risk = TrailingStopRisk::new(TrailingStopConfig {
+            activation: 0.05,
+            retracement: 0.10,
+        });
+
+        // Retracement before activation does not fire, then the trail ratchets up to 120.0 &
+        // fires at a 10% retracement to 108.0
+        let prices = [104.0, 93.0, 110.0, 120.0, 115.0, 109.0, 108.0, 100.0];
+        assert_eq!(first_trailing_exit(&risk, Side::Buy, &prices), Some(108.0));
+
+        // Short trail ratchets down to 80.0 & fires at a 10% retracement to 88.0
+        let prices = [96.0, 107.0, 90.0, 80.0, 85.0, 87.9, 88.0, 95.0];
+        assert_eq!(first_trailing_exit(&risk, Side::Sell, &prices), Some(88.0));
+    }
+
+    #[test]
+    fn trailing_stop_price_never_loosens() {
+        let risk = TrailingStopRisk::new(TrailingStopConfig {
+            activation: 0.0,
+            retracement: 0.10,
+        });
+
+        let mut position = position();
+        position.side = Side::Buy;
+        position.enter_avg_price_gross = 100.0;
+
+        let mut prev_stop = 0.0;
+        for price in [100.0, 110.0, 105.0, 120.0, 109.0] {
+            let mut market = market_event_trade(Side::Buy);
+            if let DataKind::Trade(trade) = &mut market.kind {
+                trade.price = price;
+            }
+            position.update(&market);
+
+            let stop = risk.stop_price(&position).unwrap();
+            assert!(
+                stop >= prev_stop,
+                "stop loosened from {prev_stop} to {stop}"
+            );
+            prev_stop = stop;
+        }
+
+        assert!((prev_stop - 108.0).abs() < 1e-10);
+    }
+
+    fn candle_for(
+        position: &Position,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_candle();
+        market.exchange = position.exchange;
+        market.instrument = position.instrument.clone();
+        if let DataKind::Candle(candle) = &mut market.kind {
+            candle.high = high;
+            candle.low = low;
+            candle.close = close;
+        }
+        market
+    }
+
+    #[test]
+    fn atr_stop_uses_wilder_smoothed_atr_once_warm() {
+        let mut risk = AtrStopRisk::new(AtrStopConfig {
+            atr_period: 3,
+            multiplier: 2.0,
+            fallback_stop: 0.05,
+        });
+        let mut position = position_at(Side::Buy, 100.0);
+
+        // Fallback percentage stop is used before the ATR is warm
+        assert_eq!(risk.stop_price(&position), 95.0);
+
+        // True ranges of 10, 8 & 12 seed an ATR of 10, then a true range of 6 smooths it to 26/3
+        let candles = [
+            (110.0, 100.0, 105.0),
+            (112.0, 104.0, 110.0),
+            (115.0, 103.0, 108.0),
+        ];
+        for (high, low, close) in candles {
+            risk.update_from_market(&candle_for(&position, high, low, close));
+        }
+        assert_eq!(risk.stop_price(&position), 100.0 - 2.0 * 10.0);
+
+        risk.update_from_market(&candle_for(&position, 111.0, 105.0, 109.0));
+        let atr = 26.0 / 3.0;
+        assert!((risk.stop_price(&position) - (100.0 - 2.0 * atr)).abs() < 1e-10);
+
+        // Long is closed once price falls to the stop, short stop is above the entry price
+        position.current_symbol_price = 82.7;
+        assert!(!risk.should_exit(&position));
+        position.current_symbol_price = 82.6;
+        assert!(risk.should_exit(&position));
+
+        let short = position_at(Side::Sell, 117.4);
+        assert!((risk.stop_price(&short) - (100.0 + 2.0 * atr)).abs() < 1e-10);
+        assert!(risk.should_exit(&short));
+    }
+
+    #[test]
+    fn circuit_breaker_halts_entries_after_daily_loss_until_next_utc_day() {
+        let mut risk = CircuitBreakerRisk::new(
+            CircuitBreakerConfig {
+                daily_loss_limit: 100.0,
+            },
+            DefaultRisk {},
+        );
+        let day = |day: u32, hour: u32| {
+            chrono::NaiveDate::from_ymd_opt(2024, 1, day)
+                .unwrap()
+                .and_hms_opt(hour, 0, 0)
+                .unwrap()
+                .and_utc()
+        };
+        let order = |decision: Decision| OrderEvent {
+            decision,
+            ..order_event()
+        };
+
+        // Losses within the limit do not trip the breaker
+        risk.update_from_equity(day(1, 0), 1000.0);
+        risk.update_from_equity(day(1, 6), 950.0);
+        assert!(!risk.is_tripped());
+        assert!(risk.evaluate_order(order(Decision::EnterLong)).is_some());
+
+        // Loss beyond the limit blocks new entries, but still allows exits
+        risk.update_from_equity(day(1, 12), 880.0);
+        assert!(risk.is_tripped());
+        assert!(risk.evaluate_order(order(Decision::EnterLong)).is_none());
+        assert!(risk.evaluate_order(order(Decision::EnterShort)).is_none());
+        assert!(risk.evaluate_order(order(Decision::CloseLong)).is_some());
+
+        // Recovering intraday does not reset the breaker
+        risk.update_from_equity(day(1, 23), 990.0);
+        assert!(risk.is_tripped());
+
+        // Breaker resets at the next UTC day, measuring losses from that day's starting equity
+        risk.update_from_equity(day(2, 0), 880.0);
+        assert!(!risk.is_tripped());
+        assert!(risk.evaluate_order(order(Decision::EnterLong)).is_some());
+        risk.update_from_equity(day(2, 12), 800.0);
+        assert!(!risk.is_tripped());
+    }
+
+    #[test]
+    fn cooldown_risk_rejects_entries_in_exited_market_until_cooldown_elapses() {
+        let mut risk = CooldownRisk::new(
+            CooldownConfig {
+                cooldown: Duration::minutes(5),
+            },
+            DefaultRisk {},
+        );
+
+        let mut exited = position();
+        let exit_time = exited.meta.update_time;
+        risk.update_from_exit(&exited);
+
+        // Replayed earlier exit does not shorten the cooldown
+        exited.meta.update_time = exit_time - Duration::hours(1);
+        risk.update_from_exit(&exited);
+
+        let order = |time, decision| {
+            let mut order = order_event();
+            order.time = time;
+            order.decision = decision;
+            order
+        };
+
+        // Entries in the exited market are rejected during the cooldown, exits are not
+        let cooling = exit_time + Duration::minutes(1);
+        assert!(risk
+            .evaluate_order(order(cooling, Decision::EnterShort))
+            .is_none());
+        assert!(risk
+            .evaluate_order(order(cooling, Decision::CloseLong))
+            .is_some());
+
+        // Entries in other markets are unaffected
+        let mut other_market = order(cooling, Decision::EnterLong);
+        other_market.instrument = Instrument::from(("btc", "usdt", InstrumentKind::Spot));
+        assert!(risk.evaluate_order(other_market).is_some());
+
+        let elapsed = exit_time + Duration::minutes(5);
+        assert!(risk
+            .evaluate_order(order(elapsed, Decision::EnterLong))
+            .is_some());
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    data::MarketMeta,
+    portfolio::{
+        position::{Position, PositionId},
+        OrderEvent, OrderType,
+    },
+    statistic::metric::volatility::Volatility,
+    strategy::Decision,
+};
+use barter_integration::Side;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use uuid::Uuid;
+
+/// Configuration for constructing a [`VolatilityTarget`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct VolatilityTargetConfig {
+    /// Annualised Portfolio volatility being targeted, eg/ 0.10 for 10%.
+    pub target_volatility: f64,
+    /// Number of periods (ie/ equity updates) per year used to annualise realised volatility. A
+    /// [`MetaPortfolio`](super::portfolio::MetaPortfolio) updates it's equity once per
+    /// `MarketEvent`, eg/ 365 when trading daily candles.
+    pub periods_per_year: f64,
+    /// Number of Portfolio returns used in the rolling realised [`Volatility`] estimate.
+    pub volatility_period: usize,
+    /// Maximum exposure scale, limiting how far exposure is levered up in calm markets.
+    pub max_scale: f64,
+    /// Minimum relative change in exposure scale before rebalancing orders are generated.
+    pub rebalance_threshold: f64,
+}
+
+/// Portfolio-level volatility targeting overlay. Scales overall exposure up or down each period
+/// so the annualised realised volatility of Portfolio returns tracks the configured target, and
+/// generates the rebalancing [`OrderEvent`]s required to move open [`Position`]s toward the
+/// scaled exposure.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct VolatilityTarget {
+    pub config: VolatilityTargetConfig,
+    pub volatility: Volatility,
+    /// Exposure scale implied by the most recent realised volatility.
+    pub scale: f64,
+    /// Exposure scale each open [`Position`] was last rebalanced to. [`Position`]s that have not
+    /// been rebalanced are sized at an exposure scale of 1.0.
+    pub applied_scales: HashMap<PositionId, f64>,
+}
+
+impl VolatilityTarget {
+    /// Constructs a new [`VolatilityTarget`] using the provided configuration, with an initial
+    /// exposure scale of 1.0.
+    pub fn new(config: VolatilityTargetConfig) -> Self {
+        Self {
+            config,
+            volatility: Volatility::new(config.volatility_period),
+            scale: 1.0,
+            applied_scales: HashMap::new(),
+        }
+    }
+
+    /// Updates the realised Portfolio volatility using the next period's total equity, and
+    /// returns the resulting exposure scale.
+    pub fn update(&mut self, equity: f64) -> f64 {
+        self.volatility.update(equity);
+
+        if let Some(realised) = self.annualised_volatility() {
+            self.scale = (self.config.target_volatility / realised).min(self.config.max_scale);
+        }
+
+        self.scale
+    }
+
+    /// Returns the non-zero annualised realised Portfolio volatility, if available.
+    pub fn annualised_volatility(&self) -> Option<f64> {
+        self.volatility
+            .value()
+            .filter(|volatility| *volatility > 0.0)
+            .map(|volatility| volatility * self.config.periods_per_year.sqrt())
+    }
+
+    /// Generates the [`OrderEvent`]s required to rebalance the open [`Position`]s toward the
+    /// current exposure scale, paired with the [`PositionId`] each rebalances. No order is
+    /// generated for a [`Position`] if the change in exposure scale since it was last rebalanced
+    /// is within the configured threshold.
+    ///
+    /// The exposure scale of a [`Position`] is only updated once it's [`OrderEvent`] is accepted
+    /// (see [`Self::apply_rebalance`]), so a rejected [`OrderEvent`] is retried next period.
+    pub fn rebalance_orders(
+        &self,
+        time: DateTime<Utc>,
+        positions: &[Position],
+    ) -> Vec<(PositionId, OrderEvent)> {
+        positions
+            .iter()
+            .filter_map(|position| {
+                let applied_scale = self
+                    .applied_scales
+                    .get(&position.position_id)
+                    .copied()
+                    .unwrap_or(1.0);
+
+                let adjustment = self.scale / applied_scale - 1.0;
+                if adjustment.abs() <= self.config.rebalance_threshold {
+                    return None;
+                }
+
+                let quantity = position.quantity * adjustment;
+                if quantity == 0.0 {
+                    return None;
+                }
+
+                let decision = match (position.side, quantity.is_sign_positive()) {
+                    (Side::Buy, true) => Decision::EnterLong,
+                    (Side::Buy, false) => Decision::CloseLong,
+                    (Side::Sell, false) => Decision::EnterShort,
+                    (Side::Sell, true) => Decision::CloseShort,
+                };
+
+                let order = OrderEvent {
+                    cid: Uuid::new_v4(),
+                    time,
+                    exchange: position.exchange,
+                    instrument: position.instrument.clone(),
+                    market_meta: MarketMeta {
+                        close: position.current_symbol_price,
+                        time,
+                    },
+                    decision,
+                    quantity,
+                    order_type: OrderType::Market,
+                    strategy_id: position.strategy_id.clone(),
+                };
+
+                Some((position.position_id.clone(), order))
+            })
+            .collect()
+    }
+
+    /// Records that the [`Position`] has been rebalanced to the current exposure scale, once it's
+    /// rebalancing [`OrderEvent`] has been accepted.
+    pub fn apply_rebalance(&mut self, position_id: &PositionId) {
+        self.applied_scales.insert(position_id.clone(), self.scale);
+    }
+
+    /// Forgets the exposure scale of the exited [`Position`].
+    pub fn update_from_exit(&mut self, position: &Position) {
+        self.applied_scales.remove(&position.position_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::position;
+
+    #[test]
+    fn rising_volatility_scales_exposure_down_toward_target() {
+        let mut target = VolatilityTarget::new(VolatilityTargetConfig {
+            target_volatility: 0.2,
+            periods_per_year: 100.0,
+            volatility_period: 4,
+            max_scale: 2.0,
+            rebalance_threshold: 0.05,
+        });
+
+        let mut long = position();
+        long.side = Side::Buy;
+        long.quantity = 10.0;
+
+        // Calm period: +/- 1% returns => annualised volatility 10%, scale capped at 2.0
+        let mut equity = 100.0;
+        for index in 0..5 {
+            equity *= if index % 2 == 0 { 1.01 } else { 0.99 };
+            target.update(equity);
+        }
+        let calm_scale = target.scale;
+        assert!((calm_scale - 2.0).abs() < 1e-9, "calm scale: {calm_scale}");
+
+        let orders = target.rebalance_orders(Utc::now(), &[long.clone()]);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].1.decision, Decision::EnterLong);
+        assert!((orders[0].1.quantity - 10.0).abs() < 1e-9);
+
+        // Exposure scale is not recorded until the OrderEvent is accepted, so it's regenerated
+        assert_eq!(
+            target.rebalance_orders(Utc::now(), &[long.clone()]).len(),
+            1
+        );
+        target.apply_rebalance(&orders[0].0);
+        long.quantity = 20.0;
+
+        // Volatile period: +/- 5% returns => annualised volatility 50%, scale falls to 0.4
+        for index in 0..4 {
+            equity *= if index % 2 == 0 { 1.05 } else { 0.95 };
+            target.update(equity);
+        }
+        let volatile_scale = target.scale;
+        assert!(
+            (volatile_scale - 0.4).abs() < 1e-9,
+            "volatile scale: {volatile_scale}"
+        );
+        assert!(volatile_scale < calm_scale);
+
+        // Rebalance reduces the long exposure from 20.0 (scale 2.0) to 4.0 (scale 0.4)
+        let orders = target.rebalance_orders(Utc::now(), &[long.clone()]);
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].1.decision, Decision::CloseLong);
+        assert!((orders[0].1.quantity + 16.0).abs() < 1e-9);
+        target.apply_rebalance(&orders[0].0);
+
+        // No further rebalancing while the scale is unchanged
+        assert!(target.rebalance_orders(Utc::now(), &[long]).is_empty());
+    }
+}
@@ -20,6 +20,33 @@ pub mod welford_online {
         prev_m + ((new_value - prev_mean) * (new_value - new_mean))
     }
 
+    /// Calculates the next mean of a fixed size rolling window, where the removed_value leaves the
+    /// window as the next_value enters it.
+    pub fn calculate_rolling_mean(
+        prev_mean: f64,
+        next_value: f64,
+        removed_value: f64,
+        count: f64,
+    ) -> f64 {
+        prev_mean + (next_value - removed_value) / count
+    }
+
+    /// Calculates the next Welford Online recurrence relation M of a fixed size rolling window,
+    /// where the removed_value leaves the window as the new_value enters it.
+    pub fn calculate_rolling_recurrence_relation_m(
+        prev_m: f64,
+        prev_mean: f64,
+        new_value: f64,
+        new_mean: f64,
+        removed_value: f64,
+    ) -> f64 {
+        let next_m = prev_m
+            + ((new_value - removed_value) * (new_value - new_mean + removed_value - prev_mean));
+
+        // Guard against floating point error producing a negative M
+        next_m.max(0.0)
+    }
+
     /// Calculates the next unbiased 'Sample' Variance using Bessel's correction (count - 1), and the
     /// Welford Online recurrence relation M.
     pub fn calculate_sample_variance(recurrence_relation_m: f64, count: u64) -> f64 {
@@ -187,6 +214,28 @@ mod tests {
         }
     }
 
+    #[test]
+    fn calculate_rolling_mean_and_recurrence_relation_m() {
+        // window = [10, 100, -10] -> [100, -10, 40]
+        let prev_mean = 100.0 / 3.0;
+        let prev_m = 20600.0 / 3.0;
+
+        let new_mean = welford_online::calculate_rolling_mean(prev_mean, 40.0, 10.0, 3.0);
+        let new_m = welford_online::calculate_rolling_recurrence_relation_m(
+            prev_m, prev_mean, 40.0, new_mean, 10.0,
+        );
+
+        // Two-pass: mean = 130 / 3, M = sum((x - mean)^2)
+        let expected_mean = 130.0 / 3.0;
+        let expected_m = [100.0_f64, -10.0, 40.0]
+            .iter()
+            .map(|value| (value - expected_mean).powi(2))
+            .sum::<f64>();
+
+        assert!((new_mean - expected_mean).abs() < 1e-10);
+        assert!((new_m - expected_m).abs() < 1e-8);
+    }
+
     #[test]
     fn calculate_sample_variance() {
         // fn calculate_sample_variance(recurrence_relation_m: f64, count: u64) -> f64
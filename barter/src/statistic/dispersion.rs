@@ -80,6 +80,35 @@ impl Range {
     }
 }
 
+/// Downside deviation of a dataset relative to a minimum acceptable return (target), where values
+/// at or above the target contribute no shortfall. Iteratively updated in a single pass, with the
+/// deviation normalised by the size of the entire dataset.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct DownsideDeviation {
+    pub sum_squared_shortfall: f64,
+    pub downside_count: u64,
+    pub deviation: f64,
+}
+
+impl DownsideDeviation {
+    /// Iteratively updates the DownsideDeviation given the target, new value, and the dataset
+    /// count.
+    pub fn update(&mut self, target: f64, new_value: f64, value_count: u64) {
+        // Accumulate squared shortfall below the target
+        let shortfall = (new_value - target).min(0.0);
+        if shortfall < 0.0 {
+            self.sum_squared_shortfall += shortfall.powi(2);
+            self.downside_count += 1;
+        }
+
+        // Update Downside Deviation
+        self.deviation = match value_count {
+            0 => 0.0,
+            count => (self.sum_squared_shortfall / count as f64).sqrt(),
+        };
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,4 +8,34 @@ pub enum StatisticError {
 
     #[error("Failed to build struct due to insufficient metrics provided")]
     BuilderNoMetricsProvided,
+
+    #[error("Monte Carlo resampling requires at least 2 closed Positions, but received {0}")]
+    InsufficientTrades(usize),
+}
+
+/// All errors generated when exporting statistics & [`Position`](crate::portfolio::position::Position)s.
+#[derive(Error, Debug)]
+pub enum ExportError {
+    #[error("Failed to write export file: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to serialise CSV export: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Failed to serialise JSON export: {0}")]
+    Json(#[from] serde_json::Error),
+}
+
+/// All errors generated when importing a trade log of fills (see
+/// [`TradeLog`](crate::statistic::import::TradeLog)).
+#[derive(Error, Debug)]
+pub enum ImportError {
+    #[error("Failed to read trade log: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to deserialise trade log CSV: {0}")]
+    Csv(#[from] csv::Error),
+
+    #[error("Trade log row at line {line} is malformed: {reason}")]
+    RowMalformed { line: u64, reason: String },
 }
@@ -0,0 +1,289 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::{
+        error::ExportError,
+        metric::ratio::Ratio,
+        summary::{trading::TradingSummary, TableBuilder},
+    },
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs::File, io::BufWriter, path::Path};
+
+/// Machine readable export of a summary, complementing the [`TableBuilder`] stdout output.
+///
+/// The JSON export contains both the serialised summary state and the computed metrics. Metrics
+/// that are infinite or NaN (eg/ the profit factor of a Portfolio without a losing trade) are
+/// serialised as `null`.
+pub trait SummaryExporter: TableBuilder + Serialize {
+    /// Computed metrics of the summary, keyed by metric name.
+    fn metrics(&self) -> Vec<(&'static str, f64)>;
+
+    /// Writes the serialised summary & computed metrics as a JSON object to the provided path.
+    fn to_json<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        let export = SummaryExport {
+            metrics: self.metrics().into_iter().collect(),
+            summary: self,
+        };
+
+        let writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(writer, &export).map_err(ExportError::from)
+    }
+
+    /// Writes the computed metrics as a single CSV record, with a header of metric names, to the
+    /// provided path.
+    fn to_csv<P: AsRef<Path>>(&self, path: P) -> Result<(), ExportError> {
+        let metrics = self.metrics();
+
+        let mut writer = csv::Writer::from_path(path)?;
+        writer.write_record(metrics.iter().map(|(name, _)| *name))?;
+        writer.write_record(metrics.iter().map(|(_, value)| value.to_string()))?;
+        writer.flush().map_err(ExportError::from)
+    }
+}
+
+/// JSON export layout of a [`SummaryExporter`].
+#[derive(Serialize)]
+struct SummaryExport<'a, Summary: ?Sized> {
+    metrics: BTreeMap<&'static str, f64>,
+    summary: &'a Summary,
+}
+
+impl SummaryExporter for TradingSummary {
+    fn metrics(&self) -> Vec<(&'static str, f64)> {
+        let (sharpe_annual, sortino_annual, calmar_annual) = self.tear_sheet.annualised();
+        vec![
+            ("trades", self.pnl_returns.total.count as f64),
+            ("wins", self.outcomes.wins as f64),
+            ("losses", self.outcomes.losses as f64),
+            ("trades_per_day", self.pnl_returns.trades_per_day),
+            ("mean_return", self.pnl_returns.total.mean),
+            ("std_dev_return", self.pnl_returns.total.dispersion.std_dev),
+            ("win_rate", self.outcomes.win_rate()),
+            ("profit_factor", self.outcomes.profit_factor()),
+            ("expectancy", self.outcomes.expectancy()),
+            ("sharpe_ratio_daily", self.tear_sheet.sharpe_ratio.daily()),
+            ("sortino_ratio_daily", self.tear_sheet.sortino_ratio.daily()),
+            ("calmar_ratio_daily", self.tear_sheet.calmar_ratio.daily()),
+            ("sharpe_ratio_annual", sharpe_annual),
+            ("sortino_ratio_annual", sortino_annual),
+            ("calmar_ratio_annual", calmar_annual),
+            ("omega_ratio", self.tear_sheet.omega_ratio.ratio()),
+            ("max_drawdown", self.drawdown.max_drawdown_value()),
+            (
+                "max_drawdown_duration_secs",
+                self.drawdown.max_drawdown_duration().num_seconds() as f64,
+            ),
+            ("avg_drawdown", self.drawdown.avg_drawdown.mean_drawdown),
+            ("ulcer_index", self.drawdown.ulcer_index.index),
+        ]
+    }
+}
+
+/// Flat, machine readable record of a [`Position`], used to export closed [`Position`]s.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct PositionRecord {
+    pub position_id: String,
+    pub exchange: String,
+    pub instrument: String,
+    pub side: String,
+    pub quantity: f64,
+    pub enter_time: DateTime<Utc>,
+    pub exit_time: Option<DateTime<Utc>>,
+    pub duration_secs: i64,
+    pub enter_avg_price_gross: f64,
+    pub exit_avg_price_gross: f64,
+    pub enter_value_gross: f64,
+    pub exit_value_gross: f64,
+    pub enter_fees_total: f64,
+    pub exit_fees_total: f64,
+    pub realised_profit_loss: f64,
+    pub profit_loss_return: f64,
+}
+
+impl From<&Position> for PositionRecord {
+    fn from(position: &Position) -> Self {
+        let exit_time = position.meta.exit_balance.map(|balance| balance.time);
+
+        Self {
+            position_id: position.position_id.to_string(),
+            exchange: position.exchange.to_string(),
+            instrument: position.instrument.to_string(),
+            side: position.side.to_string(),
+            quantity: position.quantity,
+            enter_time: position.meta.enter_time,
+            exit_time,
+            duration_secs: exit_time
+                .unwrap_or(position.meta.update_time)
+                .signed_duration_since(position.meta.enter_time)
+                .num_seconds(),
+            enter_avg_price_gross: position.enter_avg_price_gross,
+            exit_avg_price_gross: position.exit_avg_price_gross,
+            enter_value_gross: position.enter_value_gross,
+            exit_value_gross: position.exit_value_gross,
+            enter_fees_total: position.enter_fees_total,
+            exit_fees_total: position.exit_fees_total,
+            realised_profit_loss: position.realised_profit_loss,
+            profit_loss_return: position.calculate_profit_loss_return(),
+        }
+    }
+}
+
+/// Writes a [`PositionRecord`] for each provided [`Position`] as a JSON array to the provided path.
+pub fn export_positions_json<P: AsRef<Path>>(
+    positions: &[Position],
+    path: P,
+) -> Result<(), ExportError> {
+    let records = positions
+        .iter()
+        .map(PositionRecord::from)
+        .collect::<Vec<_>>();
+
+    let writer = BufWriter::new(File::create(path)?);
+    serde_json::to_writer_pretty(writer, &records).map_err(ExportError::from)
+}
+
+/// Writes a [`PositionRecord`] for each provided [`Position`] as CSV to the provided path.
+pub fn export_positions_csv<P: AsRef<Path>>(
+    positions: &[Position],
+    path: P,
+) -> Result<(), ExportError> {
+    let mut writer = csv::Writer::from_path(path)?;
+    for position in positions {
+        writer.serialize(PositionRecord::from(position))?;
+    }
+    writer.flush().map_err(ExportError::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::Balance,
+        statistic::summary::{trading::Config as StatisticConfig, Initialiser, PositionSummariser},
+        test_util::position,
+    };
+    use chrono::{Duration, DurationRound};
+    use std::path::PathBuf;
+    use uuid::Uuid;
+
+    fn temp_path(extension: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("barter_export_{}.{extension}", Uuid::new_v4()))
+    }
+
+    fn exited_positions() -> Vec<Position> {
+        let base_time = Utc::now().duration_trunc(Duration::seconds(1)).unwrap();
+
+        [(1, 10.0, 1010.0), (2, 20.0, 1030.0)]
+            .into_iter()
+            .map(|(days, pnl, total)| {
+                let mut position = position();
+                position.meta.enter_time = base_time + Duration::days(days - 1);
+                position.meta.update_time = base_time + Duration::days(days);
+                position.meta.exit_balance =
+                    Some(Balance::new(base_time + Duration::days(days), total, total));
+                position.exit_avg_price_gross = 100.0 + pnl;
+                position.exit_value_gross = 100.0 + pnl;
+                position.realised_profit_loss = pnl;
+                position
+            })
+            .collect()
+    }
+
+    #[test]
+    fn trading_summary_json_export_round_trips() {
+        let mut summary = TradingSummary::init(StatisticConfig {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            data_period: Duration::days(1),
+            risk_free_return: 0.0,
+            omega_threshold: None,
+            with_benchmark: false,
+        });
+        summary.generate_summary(&exited_positions());
+
+        let path = temp_path("json");
+        summary.to_json(&path).unwrap();
+        let exported: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        // Serialised summary state matches the in-memory summary (within float parsing error)
+        let actual = serde_json::from_value::<TradingSummary>(exported["summary"].clone()).unwrap();
+        assert_eq!(actual.pnl_returns.time, summary.pnl_returns.time);
+        assert_eq!(actual.pnl_returns.duration, summary.pnl_returns.duration);
+        assert_eq!(
+            actual.pnl_returns.total.count,
+            summary.pnl_returns.total.count
+        );
+        assert!((actual.pnl_returns.total.mean - summary.pnl_returns.total.mean).abs() < 1e-12);
+        assert_eq!(actual.outcomes, summary.outcomes);
+        assert_eq!(actual.drawdown, summary.drawdown);
+        assert_eq!(
+            actual.tear_sheet.sortino_ratio.ratio(),
+            summary.tear_sheet.sortino_ratio.ratio()
+        );
+
+        // Finite metrics match, and the infinite profit factor is exported as null
+        let metrics = &exported["metrics"];
+        assert_eq!(metrics["trades"], 2.0);
+        assert_eq!(metrics["win_rate"], 1.0);
+        assert_eq!(metrics["expectancy"], 15.0);
+        assert_eq!(
+            metrics["sortino_ratio_daily"],
+            serde_json::Value::Null,
+            "no downside returns"
+        );
+        assert_eq!(metrics["profit_factor"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn trading_summary_csv_export_writes_metric_header() {
+        let summary = TradingSummary::init(StatisticConfig {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            data_period: Duration::days(1),
+            risk_free_return: 0.0,
+            omega_threshold: None,
+            with_benchmark: false,
+        });
+
+        let path = temp_path("csv");
+        summary.to_csv(&path).unwrap();
+        let mut reader = csv::Reader::from_path(&path).unwrap();
+        let headers = reader.headers().unwrap().clone();
+        let record = reader.records().next().unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(headers.len(), summary.metrics().len());
+        assert_eq!(&headers[0], "trades");
+        assert_eq!(&record[0], "0");
+    }
+
+    #[test]
+    fn positions_export_round_trips_through_json_and_csv() {
+        let positions = exited_positions();
+        let expected = positions
+            .iter()
+            .map(PositionRecord::from)
+            .collect::<Vec<_>>();
+        assert_eq!(expected[1].duration_secs, Duration::days(1).num_seconds());
+
+        let path = temp_path("json");
+        export_positions_json(&positions, &path).unwrap();
+        let actual: Vec<PositionRecord> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(actual, expected);
+
+        let path = temp_path("csv");
+        export_positions_csv(&positions, &path).unwrap();
+        let actual = csv::Reader::from_path(&path)
+            .unwrap()
+            .deserialize()
+            .collect::<Result<Vec<PositionRecord>, _>>()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(actual, expected);
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{
+    execution::Fees,
+    portfolio::{
+        position::{determine_position_id, Position, PositionMeta},
+        Balance,
+    },
+    statistic::error::ImportError,
+    strategy::StrategyId,
+};
+use barter_instrument::{
+    exchange::ExchangeId,
+    instrument::{kind::InstrumentKind, Instrument},
+};
+use barter_integration::Side;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::File,
+    io::Read,
+    path::PathBuf,
+};
+use uuid::Uuid;
+
+/// Quantities smaller than this are considered fully matched, absorbing floating point error.
+const QUANTITY_EPSILON: f64 = 1e-9;
+
+/// Single fill of an externally executed trade, deserialised from a CSV trade log row with the
+/// header `time,instrument,side,price,quantity,fee`.
+///
+/// The `instrument` is a spot pair with the base & quote separated by one of `_`, `/` or `-`
+/// (eg/ "btc_usdt"), and the `quantity` is always positive - the `side` determines direction.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct TradeLogFill {
+    pub time: DateTime<Utc>,
+    pub instrument: String,
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+    pub fee: f64,
+}
+
+/// Configuration for importing a [`TradeLog`] via the new() constructor method.
+#[derive(Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct TradeLogConfig {
+    pub path: PathBuf,
+    /// [`ExchangeId`] the trade log fills were executed on.
+    pub exchange: ExchangeId,
+    /// Cash balance before the first fill, used to derive the exit [`Balance`] of each closed
+    /// [`Position`].
+    pub starting_cash: f64,
+}
+
+/// Closed [`Position`]s reconstructed from a CSV trade log of externally executed fills, so that
+/// the statistic summaries (eg/ [`TradingSummary`](super::summary::trading::TradingSummary)) can
+/// be generated for trades placed outside of the engine.
+///
+/// Fills of each instrument are matched first-in-first-out (FIFO) in time order. Every fill that
+/// reduces the open quantity closes one [`Position`] over the quantity it matched, and any
+/// remainder opens on the other side. Fills that are never matched are reported separately as
+/// [`TradeLog::open`].
+#[derive(Clone, PartialEq, Debug)]
+pub struct TradeLog {
+    /// Closed [`Position`]s, in the order they were exited.
+    pub closed: Vec<Position>,
+    /// Remaining unmatched quantity (& pro-rata fee) of each fill still open at the end of the
+    /// trade log, in time order.
+    pub open: Vec<TradeLogFill>,
+}
+
+impl TradeLog {
+    /// Imports a [`TradeLog`] from the CSV file at the configured path.
+    pub fn new(config: TradeLogConfig) -> Result<Self, ImportError> {
+        let file = File::open(&config.path)?;
+        Self::from_reader(file, config.exchange, config.starting_cash)
+    }
+
+    /// Imports a [`TradeLog`] from CSV rows read from the provided reader.
+    ///
+    /// Returns an [`ImportError::RowMalformed`] if a row has an unrecognised instrument, or a
+    /// non-positive price or quantity.
+    pub fn from_reader<R>(
+        reader: R,
+        exchange: ExchangeId,
+        starting_cash: f64,
+    ) -> Result<Self, ImportError>
+    where
+        R: Read,
+    {
+        let mut fills = Self::read_fills(reader)?;
+        fills.sort_by_key(|(_, fill)| fill.time);
+
+        let mut lots = HashMap::<Instrument, VecDeque<TradeLogFill>>::new();
+        let mut closed = Vec::new();
+        let mut equity = starting_cash;
+
+        for (instrument, fill) in fills {
+            let open_lots = lots.entry(instrument.clone()).or_default();
+
+            let mut remaining = fill;
+            if open_lots
+                .front()
+                .is_some_and(|lot| lot.side != remaining.side)
+            {
+                let position =
+                    Self::close_lots(exchange, instrument, open_lots, &mut remaining, &mut equity);
+                closed.push(position);
+            }
+
+            if remaining.quantity > QUANTITY_EPSILON {
+                open_lots.push_back(remaining);
+            }
+        }
+
+        let mut open = lots.into_values().flatten().collect::<Vec<_>>();
+        open.sort_by_key(|fill| fill.time);
+
+        Ok(Self { closed, open })
+    }
+
+    /// Deserialises & validates every [`TradeLogFill`] row, alongside it's parsed [`Instrument`].
+    fn read_fills<R>(reader: R) -> Result<Vec<(Instrument, TradeLogFill)>, ImportError>
+    where
+        R: Read,
+    {
+        let mut reader = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader);
+        let headers = reader.headers()?.clone();
+
+        let mut record = csv::StringRecord::new();
+        let mut fills = Vec::new();
+        while reader.read_record(&mut record)? {
+            let line = record.position().map_or(0, |position| position.line());
+            let malformed = |reason: &str| ImportError::RowMalformed {
+                line,
+                reason: reason.to_owned(),
+            };
+
+            let fill = record.deserialize::<TradeLogFill>(Some(&headers))?;
+            if fill.price <= 0.0 || fill.quantity <= 0.0 {
+                return Err(malformed("price & quantity must be positive"));
+            }
+            if fill.fee < 0.0 {
+                return Err(malformed("fee must not be negative"));
+            }
+
+            let instrument = parse_instrument(&fill.instrument)
+                .ok_or_else(|| malformed("instrument must be a base & quote pair"))?;
+
+            fills.push((instrument, fill));
+        }
+
+        Ok(fills)
+    }
+
+    /// Matches the exit fill against the open lots FIFO, returning the closed [`Position`] and
+    /// leaving any unmatched remainder of the fill (& it's pro-rata fee) in place.
+    fn close_lots(
+        exchange: ExchangeId,
+        instrument: Instrument,
+        open_lots: &mut VecDeque<TradeLogFill>,
+        exit: &mut TradeLogFill,
+        equity: &mut f64,
+    ) -> Position {
+        let (exit_quantity, exit_fee) = (exit.quantity, exit.fee);
+
+        let mut enter_time = exit.time;
+        let mut enter_value = 0.0;
+        let mut enter_fees = 0.0;
+        let mut entries = 0;
+        let mut side = exit.side;
+
+        while exit.quantity > QUANTITY_EPSILON {
+            let Some(lot) = open_lots.front_mut() else {
+                break;
+            };
+
+            let quantity = exit.quantity.min(lot.quantity);
+            let fee = lot.fee * quantity / lot.quantity;
+
+            enter_time = enter_time.min(lot.time);
+            enter_value += quantity * lot.price;
+            enter_fees += fee;
+            entries += 1;
+            side = lot.side;
+
+            lot.quantity -= quantity;
+            lot.fee -= fee;
+            exit.quantity -= quantity;
+
+            if lot.quantity <= QUANTITY_EPSILON {
+                open_lots.pop_front();
+            }
+        }
+
+        let closed_quantity = exit_quantity - exit.quantity;
+        let exit_fees = exit_fee * closed_quantity / exit_quantity;
+        exit.fee -= exit_fees;
+
+        let exit_value = closed_quantity * exit.price;
+
+        let mut position = Position {
+            position_id: determine_position_id(Uuid::nil(), &exchange, &instrument),
+            meta: PositionMeta {
+                enter_time,
+                update_time: exit.time,
+                exit_balance: None,
+                high_water_price: None,
+                entries,
+                max_favourable_excursion: 0.0,
+                max_adverse_excursion: 0.0,
+            },
+            exchange,
+            instrument,
+            strategy_id: StrategyId::default(),
+            side,
+            quantity: match side {
+                Side::Buy => closed_quantity,
+                Side::Sell => -closed_quantity,
+            },
+            enter_fees: Fees {
+                exchange: enter_fees,
+                slippage: 0.0,
+                network: 0.0,
+            },
+            enter_fees_total: enter_fees,
+            enter_avg_price_gross: enter_value / closed_quantity,
+            enter_value_gross: enter_value,
+            exit_fees: Fees {
+                exchange: exit_fees,
+                slippage: 0.0,
+                network: 0.0,
+            },
+            exit_fees_total: exit_fees,
+            exit_avg_price_gross: exit.price,
+            exit_value_gross: exit_value,
+            current_symbol_price: exit.price,
+            current_value_gross: exit_value,
+            unrealised_profit_loss: 0.0,
+            realised_profit_loss: 0.0,
+            closed_enter_value_gross: 0.0,
+            funding_profit_loss: 0.0,
+            leverage: 1.0,
+            liquidated: false,
+            synthetic_exit: false,
+            close_reason: None,
+        };
+
+        position.realised_profit_loss = position.calculate_realised_profit_loss();
+        position.unrealised_profit_loss = position.realised_profit_loss;
+
+        *equity += position.realised_profit_loss;
+        position.meta.exit_balance = Some(Balance::new(exit.time, *equity, *equity));
+
+        position
+    }
+}
+
+/// Parses a spot [`Instrument`] from a base & quote pair separated by one of `_`, `/` or `-`.
+fn parse_instrument(instrument: &str) -> Option<Instrument> {
+    let (base, quote) = instrument.split_once(['_', '/', '-'])?;
+    if base.is_empty() || quote.is_empty() || quote.contains(['_', '/', '-']) {
+        return None;
+    }
+
+    Some(Instrument::from((
+        base.to_lowercase(),
+        quote.to_lowercase(),
+        InstrumentKind::Spot,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::statistic::summary::{pnl::PnLReturnSummary, PositionSummariser};
+
+    fn trade_log() -> TradeLog {
+        TradeLog::new(TradeLogConfig {
+            path: PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/tests/data/trade_log.csv"
+            )),
+            exchange: ExchangeId::BinanceSpot,
+            starting_cash: 10_000.0,
+        })
+        .unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn trade_log_matches_fills_fifo_into_closed_positions() {
+        let log = trade_log();
+        assert_eq!(log.closed.len(), 2);
+
+        // Selling btc 2.5 closes the 2 @ 100 lot & partially closes the 1 @ 110 lot
+        let btc = &log.closed[0];
+        assert_eq!(
+            btc.instrument,
+            Instrument::from(("btc", "usdt", InstrumentKind::Spot))
+        );
+        assert_eq!(btc.side, Side::Buy);
+        assert_eq!(btc.meta.entries, 2);
+        assert_eq!(
+            btc.meta.enter_time,
+            DateTime::parse_from_rfc3339("2024-01-01T00:00:00Z").unwrap()
+        );
+        assert_close(btc.quantity, 2.5);
+        assert_close(btc.enter_value_gross, 255.0);
+        assert_close(btc.enter_avg_price_gross, 102.0);
+        assert_close(btc.exit_value_gross, 300.0);
+        assert_close(btc.enter_fees_total + btc.exit_fees_total, 0.5);
+        assert_close(btc.realised_profit_loss, 44.5);
+        assert_close(btc.meta.exit_balance.unwrap().total, 10_000.0 + 44.5);
+
+        // Short eth 4 @ 50 is bought back 4 @ 45
+        let eth = &log.closed[1];
+        assert_eq!(eth.side, Side::Sell);
+        assert_close(eth.quantity, -4.0);
+        assert_close(eth.realised_profit_loss, 20.0 - 0.4);
+        assert_close(eth.meta.exit_balance.unwrap().total, 10_000.0 + 44.5 + 19.6);
+
+        let mut summary = PnLReturnSummary::new();
+        summary.generate_summary(&log.closed);
+        assert_eq!(summary.total.count, 2);
+    }
+
+    #[test]
+    fn trade_log_reports_unmatched_fills_as_open() {
+        let log = trade_log();
+        assert_eq!(log.open.len(), 2);
+
+        // Remainder of the partially closed btc lot keeps it's pro-rata fee
+        let btc = &log.open[0];
+        assert_eq!(btc.instrument, "btc_usdt");
+        assert_eq!(btc.side, Side::Buy);
+        assert_close(btc.quantity, 0.5);
+        assert_close(btc.price, 110.0);
+        assert_close(btc.fee, 0.05);
+
+        // Sol is never closed
+        let sol = &log.open[1];
+        assert_eq!(sol.instrument, "sol_usdt");
+        assert_close(sol.quantity, 10.0);
+        assert_close(sol.fee, 0.1);
+    }
+
+    #[test]
+    fn trade_log_rejects_malformed_rows() {
+        let csv = "time,instrument,side,price,quantity,fee\n\
+                   2024-01-01T00:00:00Z,btcusdt,buy,100,1,0.1\n";
+
+        let error = TradeLog::from_reader(csv.as_bytes(), ExchangeId::BinanceSpot, 0.0);
+        assert!(matches!(
+            error,
+            Err(ImportError::RowMalformed { line: 2, .. })
+        ));
+    }
+}
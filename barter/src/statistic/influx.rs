@@ -0,0 +1,433 @@
+use crate::event::Event;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fmt::Write;
+
+/// Value of an InfluxDB [`Point`] field.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub enum FieldValue {
+    Float(f64),
+    Integer(i64),
+    Boolean(bool),
+    String(String),
+}
+
+impl FieldValue {
+    /// Returns true if the [`FieldValue`] can be written to InfluxDB, ie/ is not a NaN or
+    /// infinite float.
+    pub fn is_writable(&self) -> bool {
+        match self {
+            FieldValue::Float(value) => value.is_finite(),
+            _ => true,
+        }
+    }
+}
+
+/// InfluxDB data point that can be serialised into InfluxDB line protocol.
+///
+/// See documentation: <https://docs.influxdata.com/influxdb/v2/reference/syntax/line-protocol/>
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Point {
+    pub measurement: String,
+    pub tags: Vec<(String, String)>,
+    pub fields: Vec<(String, FieldValue)>,
+    pub time: DateTime<Utc>,
+}
+
+impl Point {
+    /// Constructs a new [`Point`] with no tags or fields.
+    pub fn new<S: Into<String>>(measurement: S, time: DateTime<Utc>) -> Self {
+        Self {
+            measurement: measurement.into(),
+            tags: vec![],
+            fields: vec![],
+            time,
+        }
+    }
+
+    /// Adds a tag to the [`Point`].
+    pub fn tag<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+
+    /// Adds a field to the [`Point`].
+    pub fn field<K: Into<String>>(mut self, key: K, value: FieldValue) -> Self {
+        self.fields.push((key.into(), value));
+        self
+    }
+
+    /// Returns true if the [`Point`] has at least one field that can be written to InfluxDB.
+    pub fn is_writable(&self) -> bool {
+        self.fields.iter().any(|(_, value)| value.is_writable())
+    }
+
+    /// Serialises the [`Point`] into a single line of InfluxDB line protocol with a nanosecond
+    /// precision timestamp. Non-finite float fields are skipped, since the line protocol cannot
+    /// represent them.
+    pub fn to_line(&self) -> String {
+        let mut line = escape(&self.measurement, &[',', ' ']);
+
+        for (key, value) in &self.tags {
+            let _ = write!(
+                line,
+                ",{}={}",
+                escape(key, &[',', '=', ' ']),
+                escape(value, &[',', '=', ' '])
+            );
+        }
+
+        let fields = self.fields.iter().filter(|(_, value)| value.is_writable());
+        for (index, (key, value)) in fields.enumerate() {
+            let delimiter = if index == 0 { ' ' } else { ',' };
+            let value = match value {
+                FieldValue::Float(value) => format!("{value}"),
+                FieldValue::Integer(value) => format!("{value}i"),
+                FieldValue::Boolean(value) => format!("{value}"),
+                FieldValue::String(value) => format!("\"{}\"", escape(value, &['"'])),
+            };
+            let _ = write!(line, "{delimiter}{}={value}", escape(key, &[',', '=', ' ']));
+        }
+
+        let _ = write!(
+            line,
+            " {}",
+            self.time.timestamp_nanos_opt().unwrap_or_default()
+        );
+        line
+    }
+}
+
+/// Escapes backslashes and the provided special characters with a backslash.
+fn escape(value: &str, special: &[char]) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for char in value.chars() {
+        if char == '\\' || special.contains(&char) {
+            escaped.push('\\');
+        }
+        escaped.push(char);
+    }
+    escaped
+}
+
+/// Serialises a batch of [`Point`]s into a newline delimited InfluxDB line protocol payload,
+/// skipping any [`Point`] without a writable field.
+pub fn to_line_protocol(points: &[Point]) -> String {
+    points
+        .iter()
+        .filter(|point| point.is_writable())
+        .map(Point::to_line)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Maps Barter [`Event`]s into InfluxDB [`Point`]s, tracking the equity peak required to
+/// calculate the current drawdown, & the time of the latest [`Event`] to timestamp those without
+/// a time of their own.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct PointMapper {
+    pub session: String,
+    pub equity_peak: Option<f64>,
+    pub latest_time: Option<DateTime<Utc>>,
+}
+
+impl PointMapper {
+    /// Constructs a new [`PointMapper`] that tags every [`Point`] with the provided session.
+    pub fn new<S: Into<String>>(session: S) -> Self {
+        Self {
+            session: session.into(),
+            equity_peak: None,
+            latest_time: None,
+        }
+    }
+
+    /// Maps the input [`Event`] into the associated equity, PnL, drawdown & event count
+    /// [`Point`]s.
+    ///
+    /// [`Event::OrderUpdate`]s carry no time, so are timestamped with the time of the latest
+    /// [`Event`], & are skipped if no [`Event`] has been mapped yet. [`Event::OrderCancel`]s are
+    /// also timestamped with the time of the latest [`Event`], since their [`OrderEvent`](crate::portfolio::OrderEvent) time is
+    /// when the cancelled order was generated.
+    pub fn map(&mut self, event: &Event) -> Vec<Point> {
+        let (kind, time) = match event {
+            Event::OrderUpdate => match self.latest_time {
+                Some(time) => ("order_update", time),
+                None => return vec![],
+            },
+            Event::Market(market) => ("market", market.time_received),
+            Event::Signal(signal) => ("signal", signal.time),
+            Event::SignalForceExit(signal) => ("signal_force_exit", signal.time),
+            Event::OrderNew(order) => ("order_new", order.time),
+            Event::OrderCancel(order) => ("order_cancel", self.latest_time.unwrap_or(order.time)),
+            Event::Fill(fill) => ("fill", fill.time),
+            Event::PositionNew(position) => ("position_new", position.meta.update_time),
+            Event::PositionUpdate(update) => ("position_update", update.update_time),
+            Event::PositionExit(exit) => ("position_exit", exit.exit_time),
+            Event::Balance(balance) => ("balance", balance.time),
+            Event::MarginCall(margin_call) => ("margin_call", margin_call.time),
+            Event::Bankruptcy(bankruptcy) => ("bankruptcy", bankruptcy.time),
+        };
+
+        self.latest_time = Some(time);
+
+        let mut points = vec![Point::new("event", time)
+            .tag("session", self.session.as_str())
+            .tag("kind", kind)
+            .field("count", FieldValue::Integer(1))];
+
+        match event {
+            Event::Balance(balance) => {
+                let peak = self
+                    .equity_peak
+                    .map_or(balance.total, |peak| peak.max(balance.total));
+                self.equity_peak = Some(peak);
+
+                let drawdown = if peak == 0.0 {
+                    0.0
+                } else {
+                    (peak - balance.total) / peak
+                };
+
+                points.push(
+                    Point::new("equity", balance.time)
+                        .tag("session", self.session.as_str())
+                        .field("total", FieldValue::Float(balance.total))
+                        .field("available", FieldValue::Float(balance.available)),
+                );
+                points.push(
+                    Point::new("drawdown", balance.time)
+                        .tag("session", self.session.as_str())
+                        .field("drawdown", FieldValue::Float(drawdown)),
+                );
+            }
+            Event::PositionUpdate(update) => points.push(
+                Point::new("pnl", update.update_time)
+                    .tag("session", self.session.as_str())
+                    .tag("position_id", update.position_id.as_str())
+                    .field(
+                        "unrealised",
+                        FieldValue::Float(update.unrealised_profit_loss),
+                    ),
+            ),
+            Event::PositionExit(exit) => points.push(
+                Point::new("pnl", exit.exit_time)
+                    .tag("session", self.session.as_str())
+                    .tag("position_id", exit.position_id.as_str())
+                    .field("realised", FieldValue::Float(exit.realised_profit_loss)),
+            ),
+            _ => {}
+        }
+
+        points
+    }
+}
+
+/// Feature-flagged InfluxDB sink that batches [`Point`]s & writes them over HTTP.
+#[cfg(feature = "influxdb")]
+pub mod sink {
+    use super::{to_line_protocol, Point, PointMapper};
+    use crate::event::{Event, MessageTransmitter};
+    use serde::{Deserialize, Serialize};
+    use std::time::Duration;
+    use tokio::sync::mpsc;
+    use tracing::warn;
+
+    /// Minimum interval between flushes of buffered [`Point`]s to InfluxDB.
+    pub const MIN_FLUSH_INTERVAL: Duration = Duration::from_millis(1);
+
+    /// Configuration for constructing an [`InfluxSink`] via the init() constructor method.
+    #[derive(Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+    pub struct Config {
+        /// Base url of the InfluxDB server, eg/ "http://localhost:8086".
+        pub url: String,
+        pub org: String,
+        pub bucket: String,
+        pub token: String,
+        /// Session identifier used to tag every [`Point`].
+        pub session: String,
+        /// Maximum number of [`Point`]s written in a single HTTP request.
+        pub batch_size: usize,
+        /// Maximum duration a [`Point`] is buffered before being written. A zero interval is
+        /// treated as the minimum [`MIN_FLUSH_INTERVAL`].
+        pub flush_interval: Duration,
+        /// Capacity of the buffer between the [`InfluxSink`] & the writer task. [`Point`]s are
+        /// dropped if the writer task cannot keep up.
+        pub buffer_capacity: usize,
+    }
+
+    /// [`MessageTransmitter`] that maps Barter [`Event`]s into [`Point`]s & forwards them to a
+    /// background task that batches writes to InfluxDB. If the buffer is full the [`Point`] is
+    /// dropped rather than blocking the trading loop.
+    #[derive(Debug)]
+    pub struct InfluxSink {
+        mapper: PointMapper,
+        point_tx: mpsc::Sender<Point>,
+        dropped: u64,
+    }
+
+    impl MessageTransmitter<Event> for InfluxSink {
+        fn send(&mut self, message: Event) {
+            for point in self.mapper.map(&message) {
+                if self.point_tx.try_send(point).is_err() {
+                    self.dropped += 1;
+                    warn!(
+                        dropped = self.dropped,
+                        why = "InfluxDB writer buffer full or closed",
+                        "dropped InfluxDB Point"
+                    );
+                }
+            }
+        }
+
+        fn send_many(&mut self, messages: Vec<Event>) {
+            messages.into_iter().for_each(|message| self.send(message))
+        }
+    }
+
+    impl InfluxSink {
+        /// Constructs a new [`InfluxSink`] & spawns the background writer task onto the current
+        /// tokio runtime.
+        pub fn init(config: Config) -> Self {
+            let (point_tx, point_rx) = mpsc::channel(config.buffer_capacity.max(1));
+            let mapper = PointMapper::new(config.session.clone());
+            tokio::spawn(run_writer(config, point_rx));
+
+            Self {
+                mapper,
+                point_tx,
+                dropped: 0,
+            }
+        }
+    }
+
+    /// Receives [`Point`]s & writes them to InfluxDB in batches, flushing when the batch is full
+    /// or the flush interval elapses.
+    async fn run_writer(config: Config, mut point_rx: mpsc::Receiver<Point>) {
+        let client = reqwest::Client::new();
+        let url = format!("{}/api/v2/write", config.url.trim_end_matches('/'));
+        let batch_size = config.batch_size.max(1);
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut interval = tokio::time::interval(config.flush_interval.max(MIN_FLUSH_INTERVAL));
+
+        loop {
+            let closed = tokio::select! {
+                point = point_rx.recv() => match point {
+                    Some(point) => {
+                        batch.push(point);
+                        if batch.len() < batch_size {
+                            continue;
+                        }
+                        false
+                    }
+                    None => true,
+                },
+                _ = interval.tick() => false,
+            };
+
+            if !batch.is_empty() {
+                let result = client
+                    .post(&url)
+                    .query(&[
+                        ("org", config.org.as_str()),
+                        ("bucket", config.bucket.as_str()),
+                        ("precision", "ns"),
+                    ])
+                    .header("Authorization", format!("Token {}", config.token))
+                    .body(to_line_protocol(&batch))
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status);
+
+                if let Err(error) = result {
+                    warn!(
+                        ?error,
+                        points = batch.len(),
+                        "failed to write Points to InfluxDB"
+                    );
+                }
+                batch.clear();
+            }
+
+            if closed {
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::portfolio::Balance;
+    use chrono::TimeZone;
+
+    #[test]
+    fn point_to_line() {
+        let time = Utc.timestamp_nanos(1_700_000_000_000_000_000);
+
+        let point = Point::new("my measurement", time)
+            .tag("session", "a,b=c")
+            .field("float", FieldValue::Float(1.5))
+            .field("int", FieldValue::Integer(-3))
+            .field("bool", FieldValue::Boolean(true))
+            .field("str", FieldValue::String(String::from("say \"hi\"")));
+
+        assert_eq!(
+            point.to_line(),
+            r#"my\ measurement,session=a\,b\=c float=1.5,int=-3i,bool=true,str="say \"hi\"" 1700000000000000000"#
+        );
+    }
+
+    #[test]
+    fn non_finite_float_fields_are_skipped() {
+        let time = Utc.timestamp_nanos(1_000);
+
+        let partial = Point::new("equity", time)
+            .field("total", FieldValue::Float(f64::NAN))
+            .field("available", FieldValue::Float(80.0));
+        let empty =
+            Point::new("drawdown", time).field("drawdown", FieldValue::Float(f64::INFINITY));
+        assert!(partial.is_writable());
+        assert!(!empty.is_writable());
+
+        assert_eq!(
+            to_line_protocol(&[partial, empty]),
+            "equity available=80 1000"
+        );
+    }
+
+    #[test]
+    fn point_mapper_timestamps_order_updates_with_latest_event_time() {
+        let mut mapper = PointMapper::new("backtest");
+        assert!(mapper.map(&Event::OrderUpdate).is_empty());
+
+        let time = Utc.timestamp_nanos(1_000);
+        mapper.map(&Event::Balance(Balance::new(time, 100.0, 80.0)));
+        assert_eq!(
+            to_line_protocol(&mapper.map(&Event::OrderUpdate)),
+            "event,session=backtest,kind=order_update count=1i 1000"
+        );
+    }
+
+    #[test]
+    fn point_mapper_balance_events() {
+        let mut mapper = PointMapper::new("backtest");
+        let time = Utc.timestamp_nanos(1_000);
+
+        let first = to_line_protocol(&mapper.map(&Event::Balance(Balance::new(time, 100.0, 80.0))));
+        assert_eq!(
+            first,
+            "event,session=backtest,kind=balance count=1i 1000\n\
+             equity,session=backtest total=100,available=80 1000\n\
+             drawdown,session=backtest drawdown=0 1000"
+        );
+
+        let second = to_line_protocol(&mapper.map(&Event::Balance(Balance::new(time, 75.0, 75.0))));
+        assert_eq!(
+            second,
+            "event,session=backtest,kind=balance count=1i 1000\n\
+             equity,session=backtest total=75,available=75 1000\n\
+             drawdown,session=backtest drawdown=0.25 1000"
+        );
+    }
+}
@@ -107,9 +107,34 @@ impl Drawdown {
 
     /// Calculates the value of the [`Drawdown`] in the specific period. Uses the formula:
     /// [`Drawdown`] = (range_low - range_high) / range_high
+    ///
+    /// The [`Drawdown`] is capped at -1.0 (ie/ a 100% loss) so a bankrupt Portfolio with zero or
+    /// negative equity still reports a finite value.
     pub fn calculate(&self) -> f64 {
         // range_low - range_high / range_high
-        (-self.equity_range.calculate()) / self.equity_range.high
+        let drawdown = (-self.equity_range.calculate()) / self.equity_range.high;
+
+        match drawdown.is_finite() {
+            true => drawdown.max(-1.0),
+            false => -1.0,
+        }
+    }
+}
+
+/// [`DrawdownPeriod`] is the time the Portfolio spent underwater during a [`Drawdown`], measured
+/// from the preceding equity peak until the equity recovered above it. An unrecovered
+/// [`DrawdownPeriod`] ends at the most recently observed [`EquityPoint`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct DrawdownPeriod {
+    pub start_time: DateTime<Utc>,
+    pub end_time: DateTime<Utc>,
+    pub recovered: bool,
+}
+
+impl DrawdownPeriod {
+    /// Calculates the peak-to-recovery [`Duration`] of the [`DrawdownPeriod`].
+    pub fn duration(&self) -> Duration {
+        self.end_time.signed_duration_since(self.start_time)
     }
 }
 
@@ -140,6 +165,43 @@ impl MaxDrawdown {
     }
 }
 
+/// [`UlcerIndex`] is the root mean square of the drawdown depths of every observed
+/// [`EquityPoint`], measured as a fraction of the running equity peak. It penalises both the depth
+/// & the duration of drawdowns, and is zero for an equity curve that never draws down.
+///
+/// See documentation: <https://en.wikipedia.org/wiki/Ulcer_index>
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct UlcerIndex {
+    pub peak: f64,
+    pub count: u64,
+    pub sum_squared_drawdown: f64,
+    pub index: f64,
+}
+
+impl UlcerIndex {
+    /// Initialises a new [`UlcerIndex`] using the starting equity as the first peak.
+    pub fn init(starting_equity: f64) -> Self {
+        Self {
+            peak: starting_equity,
+            ..Self::default()
+        }
+    }
+
+    /// Updates the [`UlcerIndex`] using the latest input [`EquityPoint`] of the Portfolio.
+    pub fn update(&mut self, current: EquityPoint) {
+        self.peak = self.peak.max(current.total);
+        self.count += 1;
+
+        let drawdown = match self.peak > 0.0 {
+            true => ((current.total - self.peak) / self.peak).max(-1.0),
+            false => 0.0,
+        };
+        self.sum_squared_drawdown += drawdown.powi(2);
+
+        self.index = (self.sum_squared_drawdown / self.count as f64).sqrt();
+    }
+}
+
 /// [`AvgDrawdown`] contains the average drawdown value and duration from a collection of [`Drawdown`]s
 /// within a specific period.
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
@@ -496,6 +558,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ulcer_index_update() {
+        let base_time = Utc::now();
+        let mut ulcer_index = UlcerIndex::init(100.0);
+
+        // Equity    = [110.0, 99.0, 121.0, 110.0]
+        // Peaks     = [110.0, 110.0, 121.0, 121.0]
+        // Drawdowns = [0.0, -0.1, 0.0, -1/11]
+        for (days, total) in [(1, 110.0), (2, 99.0), (3, 121.0), (4, 110.0)] {
+            ulcer_index.update(EquityPoint {
+                time: base_time.add(Duration::days(days)),
+                total,
+            });
+        }
+
+        let expected = ((0.01 + 1.0 / 121.0) / 4.0_f64).sqrt();
+        assert!((ulcer_index.index - expected).abs() < 1e-10);
+    }
+
+    #[test]
+    fn ulcer_index_without_drawdown_is_zero() {
+        let mut ulcer_index = UlcerIndex::init(100.0);
+
+        for total in [100.0, 105.0, 120.0] {
+            ulcer_index.update(EquityPoint {
+                time: Utc::now(),
+                total,
+            });
+        }
+
+        assert_eq!(ulcer_index.index, 0.0);
+    }
+
     #[test]
     fn avg_drawdown_update() {
         struct TestCase {
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 
 pub mod drawdown;
 pub mod ratio;
+pub mod volatility;
 
 /// Total equity at a point in time - equates to [`Balance.total`](Balance).
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
@@ -1,6 +1,47 @@
-use crate::statistic::summary::pnl::PnLReturnSummary;
+use crate::statistic::{dispersion::DownsideDeviation, summary::pnl::PnLReturnSummary};
+use chrono::Duration;
 use serde::{Deserialize, Serialize};
 
+/// Number of data periods (eg/ candle intervals) in a trading year, used to annualise ratios by
+/// the square root of the number of periods their returns are measured over.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct PeriodsPerYear {
+    pub periods_per_day: f64,
+    pub trading_days: u32,
+}
+
+impl Default for PeriodsPerYear {
+    fn default() -> Self {
+        Self {
+            periods_per_day: 1.0,
+            trading_days: 365,
+        }
+    }
+}
+
+impl PeriodsPerYear {
+    const SECONDS_IN_DAY: f64 = 86400.0;
+
+    /// Constructs the [`PeriodsPerYear`] of data with the provided period (eg/ 1 hour candles),
+    /// where each trading day spans a full calendar day of periods. Non-positive periods are
+    /// treated as daily.
+    pub fn new(data_period: Duration, trading_days: u32) -> Self {
+        let period_secs = data_period.num_seconds() as f64;
+        Self {
+            periods_per_day: match period_secs > 0.0 {
+                true => PeriodsPerYear::SECONDS_IN_DAY / period_secs,
+                false => 1.0,
+            },
+            trading_days,
+        }
+    }
+
+    /// Number of data periods in a trading year.
+    pub fn value(&self) -> f64 {
+        self.periods_per_day * self.trading_days as f64
+    }
+}
+
 pub trait Ratio {
     fn init(risk_free_return: f64) -> Self;
     fn ratio(&self) -> f64;
@@ -11,6 +52,12 @@ pub trait Ratio {
     fn annual(&self, trading_days: u32) -> f64 {
         calculate_annual(self.ratio(), self.trades_per_day(), trading_days)
     }
+    fn annualised(&self, periods_per_year: PeriodsPerYear) -> f64 {
+        calculate_annualised(
+            calculate_per_period(self.ratio(), self.trades_per_day(), periods_per_year),
+            periods_per_year,
+        )
+    }
 }
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
@@ -54,11 +101,19 @@ impl SharpeRatio {
     }
 }
 
+/// Sortino Ratio of the PnL returns, measuring the excess return over the `risk_free_return` per
+/// unit of [`DownsideDeviation`], using the `risk_free_return` as the minimum acceptable return.
+///
+/// If no returns have fallen below the minimum acceptable return the ratio is undefined, and
+/// [`f64::INFINITY`] is returned as a sentinel (or `0.0` if there is no excess return either).
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SortinoRatio {
     pub risk_free_return: f64,
     pub trades_per_day: f64,
-    pub sortino_ratio_per_trade: f64,
+    #[serde(default)]
+    pub excess_return: f64,
+    #[serde(default)]
+    pub downside_deviation: DownsideDeviation,
 }
 
 impl Ratio for SortinoRatio {
@@ -66,40 +121,127 @@ impl Ratio for SortinoRatio {
         Self {
             risk_free_return,
             trades_per_day: 0.0,
-            sortino_ratio_per_trade: 0.0,
+            excess_return: 0.0,
+            downside_deviation: DownsideDeviation::default(),
         }
     }
 
     fn ratio(&self) -> f64 {
-        self.sortino_ratio_per_trade
+        // Computed on read since f64::INFINITY has no JSON representation
+        match self.downside_deviation.deviation == 0.0 {
+            true if self.excess_return > 0.0 => f64::INFINITY,
+            true => 0.0,
+            false => self.excess_return / self.downside_deviation.deviation,
+        }
     }
 
     fn trades_per_day(&self) -> f64 {
         self.trades_per_day
     }
+
+    fn daily(&self) -> f64 {
+        match self.ratio() {
+            ratio if ratio.is_infinite() => ratio,
+            ratio => calculate_daily(ratio, self.trades_per_day),
+        }
+    }
+
+    fn annual(&self, trading_days: u32) -> f64 {
+        match self.ratio() {
+            ratio if ratio.is_infinite() => ratio,
+            ratio => calculate_annual(ratio, self.trades_per_day, trading_days),
+        }
+    }
+
+    fn annualised(&self, periods_per_year: PeriodsPerYear) -> f64 {
+        match self.ratio() {
+            ratio if ratio.is_infinite() => ratio,
+            ratio => calculate_annualised(
+                calculate_per_period(ratio, self.trades_per_day, periods_per_year),
+                periods_per_year,
+            ),
+        }
+    }
 }
 
 impl SortinoRatio {
-    pub fn update(&mut self, pnl_returns: &PnLReturnSummary) {
+    /// Updates the [`SortinoRatio`] given the updated [`PnLReturnSummary`] and the latest PnL
+    /// return that was added to it.
+    pub fn update(&mut self, pnl_returns: &PnLReturnSummary, pnl_return: f64) {
         // Update Trades Per Day
         self.trades_per_day = pnl_returns.trades_per_day;
 
-        // Calculate Sortino Ratio Per Trade
-        self.sortino_ratio_per_trade = match pnl_returns.losses.dispersion.std_dev == 0.0 {
-            true => 0.0,
-            false => {
-                (pnl_returns.total.mean - self.risk_free_return)
-                    / pnl_returns.losses.dispersion.std_dev
-            }
-        };
+        // Update Excess Return & Downside Deviation below the minimum acceptable return
+        self.excess_return = pnl_returns.total.mean - self.risk_free_return;
+        self.downside_deviation
+            .update(self.risk_free_return, pnl_return, pnl_returns.total.count);
+    }
+}
+
+/// Omega Ratio of the PnL returns, measuring the probability weighted gains above a threshold
+/// return against the probability weighted shortfalls below it. Uses the formula:
+/// [`OmegaRatio`] = sum(max(return - threshold, 0)) / sum(max(threshold - return, 0))
+///
+/// If no return has fallen below the threshold the ratio is undefined, and [`f64::INFINITY`] is
+/// returned as a sentinel (or `0.0` if no return has exceeded the threshold either).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct OmegaRatio {
+    pub threshold: f64,
+    pub gains: f64,
+    pub losses: f64,
+}
+
+impl OmegaRatio {
+    /// Initialises a new [`OmegaRatio`] using the provided threshold return.
+    pub fn init(threshold: f64) -> Self {
+        Self {
+            threshold,
+            gains: 0.0,
+            losses: 0.0,
+        }
+    }
+
+    /// Updates the [`OmegaRatio`] using the latest PnL return.
+    pub fn update(&mut self, pnl_return: f64) {
+        let excess_return = pnl_return - self.threshold;
+        if excess_return > 0.0 {
+            self.gains += excess_return;
+        } else {
+            self.losses -= excess_return;
+        }
+    }
+
+    /// Returns the current [`OmegaRatio`].
+    pub fn ratio(&self) -> f64 {
+        match (self.gains > 0.0, self.losses > 0.0) {
+            (false, _) => 0.0,
+            (true, false) => f64::INFINITY,
+            (true, true) => self.gains / self.losses,
+        }
     }
 }
 
+/// Calmar Ratio of the PnL returns, measuring the annualised excess return over the
+/// `risk_free_return` per unit of absolute max drawdown. Uses the formula:
+/// [`CalmarRatio`] = annualised_return / abs(max_drawdown)
+///
+/// Unlike the [`SharpeRatio`] & [`SortinoRatio`], returns scale linearly with time, so the mean
+/// excess return per trade is annualised by multiplying it by the number of trades per year (see
+/// [`CalmarRatio::annualised_return`]), rather than by it's square root. A negative annualised
+/// return therefore yields a negative [`CalmarRatio`].
+///
+/// If there has been no drawdown the ratio is undefined, and [`f64::INFINITY`] is returned as a
+/// sentinel (or `0.0` if there is no excess return either).
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct CalmarRatio {
     pub risk_free_return: f64,
     pub trades_per_day: f64,
-    pub calmar_ratio_per_trade: f64,
+    /// Mean excess return per trade over the `risk_free_return`.
+    #[serde(default)]
+    pub excess_return: f64,
+    /// Max drawdown of the PnL returns, as a fraction of the peak equity.
+    #[serde(default)]
+    pub max_drawdown: f64,
 }
 
 impl Ratio for CalmarRatio {
@@ -107,17 +249,33 @@ impl Ratio for CalmarRatio {
         Self {
             risk_free_return,
             trades_per_day: 0.0,
-            calmar_ratio_per_trade: 0.0,
+            excess_return: 0.0,
+            max_drawdown: 0.0,
         }
     }
 
+    /// Returns the [`CalmarRatio`] per trade, ie/ the mean excess return per trade over the
+    /// absolute max drawdown.
     fn ratio(&self) -> f64 {
-        self.calmar_ratio_per_trade
+        // Computed on read since f64::INFINITY has no JSON representation
+        self.per_unit_drawdown(self.excess_return)
     }
 
     fn trades_per_day(&self) -> f64 {
         self.trades_per_day
     }
+
+    fn daily(&self) -> f64 {
+        self.per_unit_drawdown(self.excess_return * self.trades_per_day)
+    }
+
+    fn annual(&self, trading_days: u32) -> f64 {
+        self.per_unit_drawdown(self.excess_return * self.trades_per_day * trading_days as f64)
+    }
+
+    fn annualised(&self, periods_per_year: PeriodsPerYear) -> f64 {
+        self.per_unit_drawdown(self.annualised_return(periods_per_year))
+    }
 }
 
 impl CalmarRatio {
@@ -125,11 +283,28 @@ impl CalmarRatio {
         // Update Trades Per Day
         self.trades_per_day = pnl_returns.trades_per_day;
 
-        // Calculate Calmar Ratio Per Trade
-        self.calmar_ratio_per_trade = match max_drawdown == 0.0 {
+        // Update Excess Return & Max Drawdown
+        self.excess_return = pnl_returns.total.mean - self.risk_free_return;
+        self.max_drawdown = max_drawdown;
+    }
+
+    /// Annualised excess return over the `risk_free_return`, being the mean excess return per
+    /// trade multiplied by the number of trades in a year of the provided [`PeriodsPerYear`].
+    ///
+    /// Equivalent to the per data period return multiplied by the [`PeriodsPerYear`], consistent
+    /// with the period scaling of [`Ratio::annualised`].
+    pub fn annualised_return(&self, periods_per_year: PeriodsPerYear) -> f64 {
+        self.excess_return * self.trades_per_day * periods_per_year.trading_days as f64
+    }
+
+    /// Divides the provided return by the absolute max drawdown, returning [`f64::INFINITY`]
+    /// if there is no drawdown & the return is positive (or `0.0` if it is not).
+    fn per_unit_drawdown(&self, excess_return: f64) -> f64 {
+        match self.max_drawdown == 0.0 {
+            true if excess_return > 0.0 => f64::INFINITY,
             true => 0.0,
-            false => (pnl_returns.total.mean - self.risk_free_return) / max_drawdown.abs(),
-        };
+            false => excess_return / self.max_drawdown.abs(),
+        }
     }
 }
 
@@ -141,6 +316,21 @@ pub fn calculate_annual(ratio_per_trade: f64, trades_per_day: f64, trading_days:
     calculate_daily(ratio_per_trade, trades_per_day) * (trading_days as f64).sqrt()
 }
 
+/// Scales a ratio per trade into a ratio per data period of the provided [`PeriodsPerYear`].
+pub fn calculate_per_period(
+    ratio_per_trade: f64,
+    trades_per_day: f64,
+    periods_per_year: PeriodsPerYear,
+) -> f64 {
+    ratio_per_trade * (trades_per_day / periods_per_year.periods_per_day).sqrt()
+}
+
+/// Annualises a ratio calculated from per-period returns by the square root of the
+/// [`PeriodsPerYear`].
+pub fn calculate_annualised(ratio_per_period: f64, periods_per_year: PeriodsPerYear) -> f64 {
+    ratio_per_period * periods_per_year.value().sqrt()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,14 +344,6 @@ mod tests {
         pnl_returns
     }
 
-    fn sortino_update_input(count: u64, mean: f64, loss_std_dev: f64) -> PnLReturnSummary {
-        let mut pnl_returns = PnLReturnSummary::new();
-        pnl_returns.total.count = count;
-        pnl_returns.total.mean = mean;
-        pnl_returns.losses.dispersion.std_dev = loss_std_dev;
-        pnl_returns
-    }
-
     fn calmar_ratio_returns_input(count: u64, mean: f64) -> PnLReturnSummary {
         let mut pnl_returns = PnLReturnSummary::new();
         pnl_returns.total.count = count;
@@ -219,59 +401,63 @@ mod tests {
     #[test]
     fn sortino_ratio_update() {
         let mut sortino = SortinoRatio::init(0.0);
+        let mut pnl_returns = PnLReturnSummary::new();
 
-        struct TestCase {
-            input_return: PnLReturnSummary,
-            expected_sortino: f64,
+        // Returns         = [0.1, -0.05, 0.2, -0.15]
+        // Mean            = 0.1 / 4 = 0.025
+        // Downside Dev.   = ((0.05^2 + 0.15^2) / 4).sqrt() = 0.00625.sqrt()
+        // Sortino         = 0.025 / 0.00625.sqrt() = 0.1.sqrt()
+        for pnl_return in [0.1, -0.05, 0.2, -0.15] {
+            pnl_returns.total.update(pnl_return);
+            sortino.update(&pnl_returns, pnl_return);
         }
 
-        // Returns       = [0.1, 0.2, 0.3, 0.4, -0.4, -0.6, -0.7]
-        // Means         = [0.1, 0.15, 0.2, 0.25, 0.12, 0.0, -0.1]
-        // Loss Std. Dev = [0.0, 0.0, 0.0, 0.0, 0.0, 0.1, 0.12472191]
+        let actual = sortino.ratio();
+        assert!((actual - 0.1_f64.sqrt()).abs() < 1e-10, "actual: {actual}");
+        assert_eq!(sortino.downside_deviation.downside_count, 2);
+    }
 
-        let test_cases = vec![
-            TestCase {
-                // Test case 0: 1st trade, 10% profit
-                input_return: sortino_update_input(1, 0.1, 0.0),
-                expected_sortino: 0.0,
-            },
-            TestCase {
-                // Test case 1: 2nd trade, 20% profit
-                input_return: sortino_update_input(2, 0.15, 0.0),
-                expected_sortino: 0.0,
-            },
-            TestCase {
-                // Test case 2: 3rd trade, 30% profit
-                input_return: sortino_update_input(3, 0.2, 0.0),
-                expected_sortino: 0.0,
-            },
-            TestCase {
-                // Test case 3: 4th trade, 40% profit
-                input_return: sortino_update_input(4, 0.25, 0.0),
-                expected_sortino: 0.0,
-            },
-            TestCase {
-                // Test case 4: 5th trade, -40% profit
-                input_return: sortino_update_input(5, 0.12, 0.0),
-                expected_sortino: 0.0,
-            },
-            TestCase {
-                // Test case 5: 6th trade, -60% profit
-                input_return: sortino_update_input(6, 0.0, 0.1),
-                expected_sortino: 0.0,
-            },
-            TestCase {
-                // Test case 5: 6th trade, -70% profit
-                input_return: sortino_update_input(7, -0.1, 0.12472191),
-                expected_sortino: -0.8017837443,
-            },
-        ];
+    #[test]
+    fn sortino_ratio_without_downside_returns_is_infinite() {
+        let mut sortino = SortinoRatio::init(0.05);
+        let mut pnl_returns = PnLReturnSummary::new();
+        assert_eq!(sortino.ratio(), 0.0);
 
-        for (index, test) in test_cases.into_iter().enumerate() {
-            sortino.update(&test.input_return);
-            let sortino_diff = sortino.sortino_ratio_per_trade - test.expected_sortino;
-            assert!(sortino_diff < 1e-10, "Test case: {:?}", index);
+        for pnl_return in [0.1, 0.2, 0.05] {
+            pnl_returns.total.update(pnl_return);
+            sortino.update(&pnl_returns, pnl_return);
+        }
+
+        assert_eq!(sortino.ratio(), f64::INFINITY);
+        assert_eq!(sortino.daily(), f64::INFINITY);
+
+        // Sentinel survives a JSON round trip, eg/ via a persisted repository
+        let json = serde_json::to_string(&sortino).unwrap();
+        let sortino = serde_json::from_str::<SortinoRatio>(&json).unwrap();
+        assert_eq!(sortino.ratio(), f64::INFINITY);
+    }
+
+    #[test]
+    fn omega_ratio_update() {
+        // Returns   = [0.1, -0.05, 0.02, -0.01]
+        // Threshold = 0.0:  gains = 0.1 + 0.02 = 0.12, losses = 0.05 + 0.01 = 0.06
+        // Threshold = 0.02: gains = 0.08, losses = 0.07 + 0.03 = 0.1
+        let mut omega = OmegaRatio::init(0.0);
+        let mut omega_threshold = OmegaRatio::init(0.02);
+        assert_eq!(omega.ratio(), 0.0);
+
+        omega.update(0.1);
+        assert_eq!(omega.ratio(), f64::INFINITY);
+
+        for pnl_return in [-0.05, 0.02, -0.01] {
+            omega.update(pnl_return);
         }
+        for pnl_return in [0.1, -0.05, 0.02, -0.01] {
+            omega_threshold.update(pnl_return);
+        }
+
+        assert!((omega.ratio() - 2.0).abs() < 1e-10);
+        assert!((omega_threshold.ratio() - 0.8).abs() < 1e-10);
     }
 
     #[test]
@@ -291,30 +477,24 @@ mod tests {
         let test_cases = vec![
             TestCase {
                 // Test case 0
-                input_return: calmar_ratio_returns_input(1, 0.5),
-                input_max_dd: 0.0,
-                expected_calmar: 0.0,
-            },
-            TestCase {
-                // Test case 1
-                input_return: calmar_ratio_returns_input(2, -0.5),
+                input_return: calmar_ratio_returns_input(2, -0.1),
                 input_max_dd: -0.70,
                 expected_calmar: (-0.1 / 0.7),
             },
             TestCase {
-                // Test case 2
+                // Test case 1
                 input_return: calmar_ratio_returns_input(3, 0.2),
                 input_max_dd: -0.7,
                 expected_calmar: (0.2 / 0.7),
             },
             TestCase {
-                // Test case 3
+                // Test case 2
                 input_return: calmar_ratio_returns_input(4, 0.5),
                 input_max_dd: -0.7,
                 expected_calmar: (0.5 / 0.7),
             },
             TestCase {
-                // Test case 4
+                // Test case 3
                 input_return: calmar_ratio_returns_input(5, 0.24),
                 input_max_dd: -0.8,
                 expected_calmar: (0.24 / 0.8),
@@ -323,11 +503,56 @@ mod tests {
 
         for (index, test) in test_cases.into_iter().enumerate() {
             calmar.update(&test.input_return, test.input_max_dd);
-            let calmar_diff = calmar.calmar_ratio_per_trade - test.expected_calmar;
-            assert!(calmar_diff < 1e-10, "Test case: {:?}", index);
+            let calmar_diff = calmar.ratio() - test.expected_calmar;
+            assert!(calmar_diff.abs() < 1e-10, "Test case: {:?}", index);
         }
     }
 
+    #[test]
+    fn calmar_ratio_is_annualised_return_over_max_drawdown() {
+        // Mean return per trade of 0.5%, at 0.4 trades per day over 365 trading days:
+        // Annualised Return = 0.005 * 0.4 * 365 = 0.73
+        // Calmar            = 0.73 / 0.2 = 3.65
+        let mut pnl_returns = calmar_ratio_returns_input(20, 0.005);
+        pnl_returns.trades_per_day = 0.4;
+        let mut calmar = CalmarRatio::init(0.0);
+        calmar.update(&pnl_returns, -0.2);
+
+        let daily = PeriodsPerYear::new(Duration::days(1), 365);
+        let hourly = PeriodsPerYear::new(Duration::hours(1), 365);
+        assert!((calmar.annualised_return(daily) - 0.73).abs() < 1e-10);
+        assert!((calmar.annualised(daily) - 3.65).abs() < 1e-10);
+        assert!((calmar.annual(365) - 3.65).abs() < 1e-10);
+        assert!((calmar.daily() - 0.01).abs() < 1e-10);
+
+        // Annualised by the trades per year, regardless of data period
+        assert!((calmar.annualised(hourly) - 3.65).abs() < 1e-10);
+
+        // Negative annualised return yields a negative Calmar
+        pnl_returns.total.mean = -0.0025;
+        calmar.update(&pnl_returns, -0.2);
+        assert!((calmar.annualised(daily) + 1.825).abs() < 1e-10);
+    }
+
+    #[test]
+    fn calmar_ratio_without_drawdown_is_infinite() {
+        let mut calmar = CalmarRatio::init(0.0);
+        assert_eq!(calmar.ratio(), 0.0);
+        assert_eq!(calmar.annual(365), 0.0);
+
+        let mut pnl_returns = calmar_ratio_returns_input(1, 0.5);
+        pnl_returns.trades_per_day = 1.0;
+        calmar.update(&pnl_returns, 0.0);
+        assert_eq!(calmar.ratio(), f64::INFINITY);
+        assert_eq!(calmar.daily(), f64::INFINITY);
+        assert_eq!(calmar.annualised(PeriodsPerYear::default()), f64::INFINITY);
+
+        // Sentinel survives a JSON round trip, eg/ via a persisted repository
+        let json = serde_json::to_string(&calmar).unwrap();
+        let calmar = serde_json::from_str::<CalmarRatio>(&json).unwrap();
+        assert_eq!(calmar.ratio(), f64::INFINITY);
+    }
+
     #[test]
     fn calculate_daily_ratios() {
         struct TestCase {
@@ -451,4 +676,33 @@ mod tests {
             assert_eq!(actual, test.expected_annual)
         }
     }
+
+    #[test]
+    fn annualised_sharpe_scales_by_square_root_of_periods_per_year() {
+        // Same per-period return series: [0.01, -0.005, 0.02, 0.0, 0.015]
+        let returns = [0.01, -0.005, 0.02, 0.0, 0.015];
+        let mean = returns.iter().sum::<f64>() / returns.len() as f64;
+        let std_dev =
+            (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / returns.len() as f64).sqrt();
+        let sharpe_per_period = mean / std_dev;
+
+        let daily = PeriodsPerYear::new(Duration::days(1), 365);
+        let hourly = PeriodsPerYear::new(Duration::hours(1), 365);
+        assert_eq!(daily.value(), 365.0);
+        assert_eq!(hourly.value(), 8760.0);
+
+        let annual_daily = calculate_annualised(sharpe_per_period, daily);
+        let annual_hourly = calculate_annualised(sharpe_per_period, hourly);
+        assert!((annual_daily - sharpe_per_period * 365.0_f64.sqrt()).abs() < 1e-10);
+        assert!((annual_hourly / annual_daily - 24.0_f64.sqrt()).abs() < 1e-10);
+
+        // Ratios per trade are annualised by the trades per year, regardless of data period
+        let mut sharpe = SharpeRatio::init(0.0);
+        sharpe.update(&sharpe_ratio_input(5, mean, std_dev));
+        sharpe.trades_per_day = 2.0;
+        let expected = sharpe_per_period * (2.0_f64 * 365.0).sqrt();
+        assert!((sharpe.annualised(daily) - expected).abs() < 1e-10);
+        assert!((sharpe.annualised(hourly) - expected).abs() < 1e-10);
+        assert!((sharpe.annualised(daily) - sharpe.annual(365)).abs() < 1e-10);
+    }
 }
@@ -0,0 +1,88 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Rolling [`Volatility`] estimate of an instrument, calculated as the population standard
+/// deviation of the simple returns within the most recent `period` closes.
+#[derive(Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Volatility {
+    pub period: usize,
+    pub prev_close: Option<f64>,
+    pub returns: VecDeque<f64>,
+}
+
+impl Volatility {
+    /// Initialises a new [`Volatility`] estimate using the provided rolling window period.
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            prev_close: None,
+            returns: VecDeque::with_capacity(period),
+        }
+    }
+
+    /// Updates the [`Volatility`] estimate using the next close price.
+    pub fn update(&mut self, close: f64) {
+        if let Some(prev_close) = self.prev_close {
+            if prev_close != 0.0 {
+                if self.returns.len() == self.period {
+                    self.returns.pop_front();
+                }
+                self.returns.push_back((close - prev_close) / prev_close);
+            }
+        }
+
+        self.prev_close = Some(close);
+    }
+
+    /// Determines if enough returns have been observed to fill the rolling window.
+    pub fn is_ready(&self) -> bool {
+        self.period > 0 && self.returns.len() == self.period
+    }
+
+    /// Returns the current [`Volatility`] estimate, or `None` if the rolling window is not yet
+    /// full.
+    pub fn value(&self) -> Option<f64> {
+        if !self.is_ready() {
+            return None;
+        }
+
+        let count = self.returns.len() as f64;
+        let mean = self.returns.iter().sum::<f64>() / count;
+        let variance = self
+            .returns
+            .iter()
+            .map(|value| (value - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        Some(variance.sqrt())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn volatility_update() {
+        let mut volatility = Volatility::new(2);
+
+        // Closes  = [100.0, 110.0, 99.0, 108.9]
+        // Returns = [0.1, -0.1, 0.1]
+        volatility.update(100.0);
+        assert_eq!(volatility.value(), None);
+
+        volatility.update(110.0);
+        assert_eq!(volatility.value(), None);
+
+        volatility.update(99.0);
+        let actual = volatility.value().unwrap();
+        assert!((actual - 0.1).abs() < 1e-10, "actual: {actual}");
+
+        // Window rolls over to returns [-0.1, 0.1]
+        volatility.update(108.9);
+        let actual = volatility.value().unwrap();
+        assert!((actual - 0.1).abs() < 1e-10, "actual: {actual}");
+        assert_eq!(volatility.returns.len(), 2);
+    }
+}
@@ -4,7 +4,11 @@ use serde::{Deserialize, Deserializer, Serializer};
 pub mod algorithm;
 pub mod dispersion;
 pub mod error;
+pub mod export;
+pub mod import;
+pub mod influx;
 pub mod metric;
+pub mod monte_carlo;
 pub mod summary;
 
 /// Serialize a [`Duration`] into a `u64` representing the associated seconds.
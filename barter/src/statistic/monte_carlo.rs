@@ -0,0 +1,234 @@
+use crate::{portfolio::position::Position, statistic::error::StatisticError};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Configuration for constructing a [`MonteCarlo`] analyzer via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Equity each resampled trade sequence starts with.
+    pub starting_equity: f64,
+    /// Number of resampled trade sequences to generate.
+    pub iterations: usize,
+    /// Seed of the random number generator, so every run with the same [`Position`]s produces
+    /// the same [`MonteCarloSummary`].
+    pub seed: u64,
+    /// Risk free return per trade used to calculate the Sharpe Ratio of each sequence.
+    pub risk_free_return: f64,
+}
+
+/// 5th, 50th & 95th percentiles of a distribution of resampled outcomes.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct PercentileBands {
+    pub p5: f64,
+    pub p50: f64,
+    pub p95: f64,
+}
+
+impl PercentileBands {
+    /// Calculates the [`PercentileBands`] of the provided outcomes.
+    fn from_outcomes(mut outcomes: Vec<f64>) -> Self {
+        outcomes.sort_by(f64::total_cmp);
+        Self {
+            p5: percentile(&outcomes, 0.05),
+            p50: percentile(&outcomes, 0.50),
+            p95: percentile(&outcomes, 0.95),
+        }
+    }
+}
+
+/// Distributions of the outcomes of every resampled trade sequence generated by a [`MonteCarlo`]
+/// analyzer.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MonteCarloSummary {
+    pub iterations: usize,
+    /// Closed [`Position`]s each sequence was resampled from.
+    pub trades: usize,
+    pub final_equity: PercentileBands,
+    /// Maximum drawdown of each sequence's equity curve, where -0.2 is a 20% drawdown (see
+    /// [`Drawdown::calculate`](super::metric::drawdown::Drawdown::calculate)).
+    pub max_drawdown: PercentileBands,
+    /// Sharpe Ratio per trade of each sequence's profit & loss returns.
+    pub sharpe_ratio: PercentileBands,
+}
+
+/// Monte Carlo robustness analyzer that resamples the profit & loss of closed [`Position`]s with
+/// replacement, generating alternative trade sequences to estimate how sensitive a strategy's
+/// outcome is to the ordering & selection of it's trades.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MonteCarlo {
+    config: Config,
+}
+
+impl MonteCarlo {
+    /// Constructs a new [`MonteCarlo`] analyzer using the provided configuration struct.
+    pub fn new(config: Config) -> Self {
+        Self { config }
+    }
+
+    /// Resamples the provided closed [`Position`]s into [`Config::iterations`] trade sequences
+    /// of the same length, returning the [`MonteCarloSummary`] of their outcomes.
+    pub fn run(&self, positions: &[Position]) -> Result<MonteCarloSummary, StatisticError> {
+        let trades = positions
+            .iter()
+            .filter(|position| position.meta.exit_balance.is_some())
+            .map(|position| {
+                (
+                    position.realised_profit_loss,
+                    position.calculate_profit_loss_return(),
+                )
+            })
+            .collect::<Vec<(f64, f64)>>();
+
+        if trades.len() < 2 {
+            return Err(StatisticError::InsufficientTrades(trades.len()));
+        }
+
+        let iterations = self.config.iterations.max(1);
+        let mut rng = StdRng::seed_from_u64(self.config.seed);
+        let mut final_equities = Vec::with_capacity(iterations);
+        let mut max_drawdowns = Vec::with_capacity(iterations);
+        let mut sharpe_ratios = Vec::with_capacity(iterations);
+
+        for _ in 0..iterations {
+            let sequence = (0..trades.len())
+                .map(|_| trades[rng.gen_range(0..trades.len())])
+                .collect::<Vec<(f64, f64)>>();
+
+            let (final_equity, max_drawdown) = self.simulate_equity(&sequence);
+            final_equities.push(final_equity);
+            max_drawdowns.push(max_drawdown);
+            sharpe_ratios.push(self.sharpe_ratio(&sequence));
+        }
+
+        Ok(MonteCarloSummary {
+            iterations,
+            trades: trades.len(),
+            final_equity: PercentileBands::from_outcomes(final_equities),
+            max_drawdown: PercentileBands::from_outcomes(max_drawdowns),
+            sharpe_ratio: PercentileBands::from_outcomes(sharpe_ratios),
+        })
+    }
+
+    /// Applies the profit & loss of each trade in sequence to the starting equity, returning the
+    /// final equity & the maximum drawdown of the equity curve.
+    fn simulate_equity(&self, sequence: &[(f64, f64)]) -> (f64, f64) {
+        let mut equity = self.config.starting_equity;
+        let mut peak = equity;
+        let mut max_drawdown: f64 = 0.0;
+
+        for (profit_loss, _) in sequence {
+            equity += profit_loss;
+            peak = peak.max(equity);
+
+            let drawdown = match peak > 0.0 {
+                true => ((equity - peak) / peak).max(-1.0),
+                false => -1.0,
+            };
+            max_drawdown = max_drawdown.min(drawdown);
+        }
+
+        (equity, max_drawdown)
+    }
+
+    /// Calculates the Sharpe Ratio per trade of the sequence's profit & loss returns.
+    fn sharpe_ratio(&self, sequence: &[(f64, f64)]) -> f64 {
+        let count = sequence.len() as f64;
+        let mean = sequence.iter().map(|(_, ret)| ret).sum::<f64>() / count;
+        let variance = sequence
+            .iter()
+            .map(|(_, ret)| (ret - mean).powi(2))
+            .sum::<f64>()
+            / count;
+
+        match variance == 0.0 {
+            true => 0.0,
+            false => (mean - self.config.risk_free_return) / variance.sqrt(),
+        }
+    }
+}
+
+/// Linearly interpolated percentile (0.0 to 1.0) of the provided sorted, non-empty values.
+fn percentile(sorted: &[f64], percentile: f64) -> f64 {
+    let rank = percentile * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{portfolio::Balance, test_util::position};
+    use chrono::Utc;
+
+    fn closed_position(profit_loss: f64) -> Position {
+        let mut position = position();
+        position.enter_value_gross = 1000.0;
+        position.realised_profit_loss = profit_loss;
+        position.meta.exit_balance = Some(Balance::new(Utc::now(), 10_000.0, 10_000.0));
+        position
+    }
+
+    fn config() -> Config {
+        Config {
+            starting_equity: 10_000.0,
+            iterations: 2_000,
+            seed: 42,
+            risk_free_return: 0.0,
+        }
+    }
+
+    #[test]
+    fn monte_carlo_resamples_trades_into_ordered_percentile_bands() {
+        let profit_losses = [
+            120.0, -80.0, 45.0, 60.0, -30.0, 95.0, -110.0, 20.0, 75.0, -15.0, 50.0, -60.0, 130.0,
+            -40.0, 10.0, 85.0, -25.0, 40.0, -70.0, 65.0,
+        ];
+        let positions = profit_losses
+            .iter()
+            .map(|profit_loss| closed_position(*profit_loss))
+            .collect::<Vec<_>>();
+        let actual_final_equity = 10_000.0 + profit_losses.iter().sum::<f64>();
+
+        let monte_carlo = MonteCarlo::new(config());
+        let summary = monte_carlo.run(&positions).unwrap();
+
+        // Same seed produces the same summary
+        assert_eq!(summary, monte_carlo.run(&positions).unwrap());
+        assert_eq!(summary.iterations, 2_000);
+        assert_eq!(summary.trades, 20);
+
+        // Median final equity is close to the actual final equity
+        assert!(
+            (summary.final_equity.p50 - actual_final_equity).abs() < 30.0,
+            "median: {}, actual: {actual_final_equity}",
+            summary.final_equity.p50
+        );
+
+        for bands in [
+            summary.final_equity,
+            summary.max_drawdown,
+            summary.sharpe_ratio,
+        ] {
+            assert!(bands.p5 <= bands.p50 && bands.p50 <= bands.p95, "{bands:?}");
+        }
+        assert!(summary.final_equity.p5 < summary.final_equity.p95);
+        assert!(summary.max_drawdown.p95 <= 0.0 && summary.max_drawdown.p5 < 0.0);
+    }
+
+    #[test]
+    fn monte_carlo_with_fewer_than_two_closed_trades_errors() {
+        let monte_carlo = MonteCarlo::new(config());
+
+        let mut open = closed_position(100.0);
+        open.meta.exit_balance = None;
+
+        assert!(matches!(
+            monte_carlo.run(&[closed_position(100.0), open]),
+            Err(StatisticError::InsufficientTrades(1))
+        ));
+        assert!(matches!(
+            monte_carlo.run(&[]),
+            Err(StatisticError::InsufficientTrades(0))
+        ));
+    }
+}
@@ -0,0 +1,213 @@
+use crate::{
+    data::determine_market_close,
+    portfolio::position::Position,
+    statistic::summary::{PositionSummariser, TableBuilder},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use chrono::{DateTime, Utc};
+use prettytable::Row;
+use serde::{Deserialize, Serialize};
+
+/// Close price of the benchmark instrument at a point in time.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BenchmarkPoint {
+    pub time: DateTime<Utc>,
+    pub close: f64,
+}
+
+/// Buy-and-hold benchmark of the instrument traded, used to judge whether a strategy adds value
+/// over simply holding the instrument for the same period.
+///
+/// The benchmark period spans the first & latest [`MarketEvent`] close prices. If market data
+/// is sparser than the trading period, the period is extended using the entry & exit prices of
+/// the exited [`Position`]s. Beta is the sensitivity of each [`Position`]'s return to the
+/// instrument's return over it's holding period.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BenchmarkSummary {
+    pub starting_equity: f64,
+    /// Realised profit & loss of every exited [`Position`].
+    pub profit_loss: f64,
+    pub start: Option<BenchmarkPoint>,
+    pub end: Option<BenchmarkPoint>,
+    /// Number of [`MarketEvent`] close prices observed.
+    pub data_points: u64,
+    /// Number of exited [`Position`]s with a paired strategy & benchmark return.
+    pub trades: u64,
+    mean_strategy_return: f64,
+    mean_benchmark_return: f64,
+    /// Sum of the products of the paired strategy & benchmark return deviations from their means.
+    co_moment: f64,
+    /// Sum of the squared benchmark return deviations from it's mean.
+    benchmark_moment: f64,
+}
+
+impl PositionSummariser for BenchmarkSummary {
+    fn update(&mut self, position: &Position) {
+        // Only update BenchmarkSummary with closed Positions
+        let Some(exit_balance) = position.meta.exit_balance else {
+            return;
+        };
+
+        self.profit_loss += position.realised_profit_loss;
+
+        // Extend the benchmark period if market data does not cover the Position
+        self.observe(BenchmarkPoint {
+            time: position.meta.enter_time,
+            close: position.enter_avg_price_gross,
+        });
+        self.observe(BenchmarkPoint {
+            time: exit_balance.time,
+            close: position.exit_avg_price_gross,
+        });
+
+        // Pair the Position return with the instrument return over it's holding period
+        if position.enter_avg_price_gross <= 0.0 {
+            return;
+        }
+        let strategy_return = position.calculate_profit_loss_return();
+        let benchmark_return = position.exit_avg_price_gross / position.enter_avg_price_gross - 1.0;
+
+        self.trades += 1;
+        let count = self.trades as f64;
+        let strategy_delta = strategy_return - self.mean_strategy_return;
+        let benchmark_delta = benchmark_return - self.mean_benchmark_return;
+        self.mean_strategy_return += strategy_delta / count;
+        self.mean_benchmark_return += benchmark_delta / count;
+        self.co_moment += strategy_delta * (benchmark_return - self.mean_benchmark_return);
+        self.benchmark_moment += benchmark_delta * (benchmark_return - self.mean_benchmark_return);
+    }
+}
+
+impl BenchmarkSummary {
+    /// Constructs a new [`BenchmarkSummary`] for a strategy starting with the provided equity.
+    pub fn new(starting_equity: f64) -> Self {
+        Self {
+            starting_equity,
+            profit_loss: 0.0,
+            start: None,
+            end: None,
+            data_points: 0,
+            trades: 0,
+            mean_strategy_return: 0.0,
+            mean_benchmark_return: 0.0,
+            co_moment: 0.0,
+            benchmark_moment: 0.0,
+        }
+    }
+
+    /// Updates the benchmark period using the latest [`MarketEvent`] close price.
+    pub fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        let Some(close) = determine_market_close(market) else {
+            return;
+        };
+
+        self.data_points += 1;
+        self.observe(BenchmarkPoint {
+            time: market.time_exchange,
+            close,
+        });
+    }
+
+    /// Extends the benchmark period to include the provided [`BenchmarkPoint`].
+    fn observe(&mut self, point: BenchmarkPoint) {
+        if self.start.is_none_or(|start| point.time < start.time) {
+            self.start = Some(point);
+        }
+        if self.end.is_none_or(|end| point.time >= end.time) {
+            self.end = Some(point);
+        }
+    }
+
+    /// Return of the strategy's realised profit & loss on the starting equity.
+    pub fn strategy_return(&self) -> f64 {
+        self.profit_loss / self.starting_equity
+    }
+
+    /// Return of buying & holding the instrument over the benchmark period, or `None` if the
+    /// period is not yet covered by two distinct prices.
+    pub fn benchmark_return(&self) -> Option<f64> {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) if end.time > start.time && start.close > 0.0 => {
+                Some(end.close / start.close - 1.0)
+            }
+            _ => None,
+        }
+    }
+
+    /// Excess return of the strategy over the buy-and-hold benchmark.
+    pub fn alpha(&self) -> Option<f64> {
+        self.benchmark_return()
+            .map(|benchmark_return| self.strategy_return() - benchmark_return)
+    }
+
+    /// Sensitivity of the strategy's [`Position`] returns to the instrument's returns over each
+    /// holding period, or `None` if fewer than two [`Position`]s with differing instrument
+    /// returns have exited.
+    pub fn beta(&self) -> Option<f64> {
+        (self.trades >= 2 && self.benchmark_moment > 0.0)
+            .then(|| self.co_moment / self.benchmark_moment)
+    }
+}
+
+impl TableBuilder for BenchmarkSummary {
+    fn titles(&self) -> Row {
+        row!["Strategy Return", "Benchmark Return", "Alpha", "Beta"]
+    }
+
+    fn row(&self) -> Row {
+        let format = |value: Option<f64>| value.map_or("N/A".to_string(), |v| format!("{v:.3}"));
+
+        row![
+            format!("{:.3}", self.strategy_return()),
+            format(self.benchmark_return()),
+            format(self.alpha()),
+            format(self.beta()),
+        ]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{portfolio::Balance, test_util::position};
+    use chrono::Duration;
+
+    fn exited_position(
+        enter_time: DateTime<Utc>,
+        exit_time: DateTime<Utc>,
+        enter_price: f64,
+        exit_price: f64,
+    ) -> Position {
+        let mut position = position();
+        position.quantity = 1.0;
+        position.meta.enter_time = enter_time;
+        position.meta.exit_balance = Some(Balance::new(exit_time, 1000.0, 1000.0));
+        position.enter_avg_price_gross = enter_price;
+        position.enter_value_gross = enter_price;
+        position.exit_avg_price_gross = exit_price;
+        position.realised_profit_loss = exit_price - enter_price;
+        position
+    }
+
+    #[test]
+    fn benchmark_uses_position_prices_when_market_data_is_sparse() {
+        let start = Utc::now();
+        let mut benchmark = BenchmarkSummary::new(1000.0);
+        assert_eq!(benchmark.benchmark_return(), None);
+        assert_eq!(benchmark.beta(), None);
+
+        // Single Position spanning beyond the market data observed
+        benchmark.update(&exited_position(
+            start,
+            start + Duration::days(2),
+            100.0,
+            150.0,
+        ));
+
+        assert_eq!(benchmark.data_points, 0);
+        assert_eq!(benchmark.benchmark_return(), Some(0.5));
+        assert_eq!(benchmark.strategy_return(), 0.05);
+        assert_eq!(benchmark.beta(), None);
+    }
+}
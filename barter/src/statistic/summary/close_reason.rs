@@ -0,0 +1,58 @@
+use crate::{
+    portfolio::position::{CloseReason, Position},
+    statistic::summary::{combine, strategy::StrategySummary, PositionSummariser},
+};
+use prettytable::Table;
+use serde::{Deserialize, Serialize};
+use smol_str::format_smolstr;
+use std::collections::BTreeMap;
+
+/// Per-[`CloseReason`] breakdown of exited [`Position`]s (eg/ to compare the P&L of stop-loss
+/// exits against strategy [`Signal`](crate::strategy::Signal) exits), summarising the trades,
+/// win rate & profit and loss of each with a [`StrategySummary`].
+///
+/// Exited [`Position`]s without a [`CloseReason`] (eg/ closed by an external
+/// [`FillEvent`](crate::execution::FillEvent)) are not included.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct CloseReasonBreakdown {
+    pub reasons: BTreeMap<CloseReason, StrategySummary>,
+}
+
+impl CloseReasonBreakdown {
+    /// Constructs a [`CloseReasonBreakdown`] from the provided [`Position`]s.
+    pub fn new(positions: &[Position]) -> Self {
+        let mut breakdown = Self::default();
+        positions
+            .iter()
+            .for_each(|position| breakdown.update(position));
+        breakdown
+    }
+
+    /// Updates the [`StrategySummary`] of the [`CloseReason`] the [`Position`] was closed by.
+    pub fn update(&mut self, position: &Position) {
+        let (Some(close_reason), Some(_)) = (position.close_reason, position.meta.exit_balance)
+        else {
+            return;
+        };
+
+        self.reasons
+            .entry(close_reason)
+            .or_default()
+            .update(position);
+    }
+
+    /// Returns the [`StrategySummary`] of the provided [`CloseReason`], if any [`Position`]s
+    /// were closed by it.
+    pub fn get(&self, close_reason: CloseReason) -> Option<&StrategySummary> {
+        self.reasons.get(&close_reason)
+    }
+
+    /// Generates a [`Table`] with a [`StrategySummary`] row per [`CloseReason`].
+    pub fn table(&self) -> Table {
+        combine(
+            self.reasons
+                .iter()
+                .map(|(close_reason, summary)| (format_smolstr!("{close_reason:?}"), *summary)),
+        )
+    }
+}
@@ -2,12 +2,13 @@ use crate::{
     portfolio::position::Position,
     statistic::{
         metric::{
-            drawdown::{AvgDrawdown, Drawdown, MaxDrawdown},
+            drawdown::{AvgDrawdown, Drawdown, DrawdownPeriod, MaxDrawdown, UlcerIndex},
             EquityPoint,
         },
         summary::{PositionSummariser, TableBuilder},
     },
 };
+use chrono::{DateTime, Duration, Utc};
 use prettytable::Row;
 use serde::{Deserialize, Serialize};
 
@@ -16,6 +17,15 @@ pub struct DrawdownSummary {
     pub current_drawdown: Drawdown,
     pub avg_drawdown: AvgDrawdown,
     pub max_drawdown: MaxDrawdown,
+    /// Time of the most recent equity peak, or `None` if no peak above the starting equity has
+    /// been observed.
+    #[serde(default)]
+    pub peak_time: Option<DateTime<Utc>>,
+    /// Peak-to-recovery [`DrawdownPeriod`] of the recovered [`MaxDrawdown`].
+    #[serde(default)]
+    pub max_drawdown_period: Option<DrawdownPeriod>,
+    #[serde(default)]
+    pub ulcer_index: UlcerIndex,
 }
 
 impl PositionSummariser for DrawdownSummary {
@@ -27,9 +37,24 @@ impl PositionSummariser for DrawdownSummary {
         };
 
         // Updates
+        self.ulcer_index.update(equity_point);
         if let Some(ended_drawdown) = self.current_drawdown.update(equity_point) {
             self.avg_drawdown.update(&ended_drawdown);
             self.max_drawdown.update(&ended_drawdown);
+
+            // Record the peak-to-recovery period if the ended Drawdown is the new MaxDrawdown
+            if self.max_drawdown.drawdown == ended_drawdown {
+                self.max_drawdown_period = Some(DrawdownPeriod {
+                    start_time: self.peak_time.unwrap_or(ended_drawdown.start_time),
+                    end_time: equity_point.time,
+                    recovered: true,
+                });
+            }
+        }
+
+        // Equity at or above the previous peak sets the next peak
+        if self.current_drawdown.is_waiting_for_peak() {
+            self.peak_time = Some(equity_point.time);
         }
     }
 }
@@ -39,17 +64,23 @@ impl TableBuilder for DrawdownSummary {
         row![
             "Max Drawdown",
             "Max Drawdown Days",
+            "Max Drawdown Recovered",
             "Avg. Drawdown",
             "Avg. Drawdown Days",
+            "Ulcer Index",
         ]
     }
 
     fn row(&self) -> Row {
         row![
-            format!("{:.3}", self.max_drawdown.drawdown.drawdown),
-            self.max_drawdown.drawdown.duration.num_days().to_string(),
+            format!("{:.3}", self.max_drawdown_value()),
+            self.max_drawdown_duration().num_days().to_string(),
+            self.max_drawdown_period()
+                .is_none_or(|period| period.recovered)
+                .to_string(),
             format!("{:.3}", self.avg_drawdown.mean_drawdown),
             self.avg_drawdown.mean_duration.num_days().to_string(),
+            format!("{:.3}", self.ulcer_index.index),
         ]
     }
 }
@@ -60,6 +91,89 @@ impl DrawdownSummary {
             current_drawdown: Drawdown::init(starting_equity),
             avg_drawdown: AvgDrawdown::init(),
             max_drawdown: MaxDrawdown::init(),
+            peak_time: None,
+            max_drawdown_period: None,
+            ulcer_index: UlcerIndex::init(starting_equity),
+        }
+    }
+
+    /// Determines if the ongoing, unrecovered [`Drawdown`] is larger than the recovered
+    /// [`MaxDrawdown`].
+    fn is_ongoing_drawdown_max(&self) -> bool {
+        !self.current_drawdown.is_waiting_for_peak()
+            && self.current_drawdown.drawdown.abs() > self.max_drawdown.drawdown.drawdown.abs()
+    }
+
+    /// Returns the value of the largest [`Drawdown`] observed, including any ongoing
+    /// [`Drawdown`] that has not yet recovered.
+    pub fn max_drawdown_value(&self) -> f64 {
+        match self.is_ongoing_drawdown_max() {
+            true => self.current_drawdown.drawdown,
+            false => self.max_drawdown.drawdown.drawdown,
+        }
+    }
+
+    /// Returns the peak-to-recovery [`DrawdownPeriod`] of the largest [`Drawdown`] observed. If
+    /// it is still ongoing, the [`DrawdownPeriod`] is flagged as unrecovered and ends at the most
+    /// recent [`EquityPoint`].
+    pub fn max_drawdown_period(&self) -> Option<DrawdownPeriod> {
+        match self.is_ongoing_drawdown_max() {
+            true => Some(DrawdownPeriod {
+                start_time: self.peak_time.unwrap_or(self.current_drawdown.start_time),
+                end_time: self.current_drawdown.start_time + self.current_drawdown.duration,
+                recovered: false,
+            }),
+            false => self.max_drawdown_period,
         }
     }
+
+    /// Returns the peak-to-recovery [`Duration`] of the largest [`Drawdown`] observed, measured
+    /// to the most recent [`EquityPoint`] if it has not yet recovered.
+    pub fn max_drawdown_duration(&self) -> Duration {
+        self.max_drawdown_period()
+            .map_or(Duration::zero(), |period| period.duration())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{portfolio::Balance, test_util::position};
+
+    #[test]
+    fn max_drawdown_duration_tracks_peak_to_recovery() {
+        let base_time = Utc::now();
+        let mut summary = DrawdownSummary::new(100.0);
+
+        let mut update = |days: i64, total: f64| {
+            let mut position = position();
+            position.meta.exit_balance =
+                Some(Balance::new(base_time + Duration::days(days), total, total));
+            summary.update(&position);
+            summary
+        };
+
+        // Peak at day 1, dip at day 2, recover at day 4
+        update(1, 110.0);
+        update(2, 99.0);
+        let summary_recovered = update(4, 120.0);
+        assert_eq!(summary_recovered.max_drawdown_duration(), Duration::days(3));
+        assert!(summary_recovered.max_drawdown_period().unwrap().recovered);
+
+        // Deeper dip from the day 4 peak, never recovered by day 9
+        update(5, 90.0);
+        update(7, 84.0);
+        let summary_ongoing = update(9, 100.0);
+        let period = summary_ongoing.max_drawdown_period().unwrap();
+        assert!(!period.recovered);
+        assert_eq!(period.start_time, base_time + Duration::days(4));
+        assert_eq!(summary_ongoing.max_drawdown_duration(), Duration::days(5));
+        assert_eq!(summary_ongoing.max_drawdown_value(), -0.3);
+
+        // Recovery of the deeper dip at day 12
+        let summary_final = update(12, 121.0);
+        let period = summary_final.max_drawdown_period().unwrap();
+        assert!(period.recovered);
+        assert_eq!(summary_final.max_drawdown_duration(), Duration::days(8));
+    }
 }
@@ -1,9 +1,16 @@
+pub mod benchmark;
+pub mod close_reason;
 pub mod data;
 pub mod drawdown;
 pub mod pnl;
+pub mod returns;
+pub mod rolling;
+pub mod strategy;
 pub mod trading;
 
 use crate::portfolio::position::Position;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
 use prettytable::{Cell, Row, Table};
 use smol_str::SmolStr;
 
@@ -14,6 +21,11 @@ pub trait Initialiser {
 
 pub trait PositionSummariser: Copy {
     fn update(&mut self, position: &Position);
+
+    /// Updates any market dependent statistics (eg/ a buy-and-hold benchmark) using the latest
+    /// [`MarketEvent`] of the summarised market. Default implementation is a no-op.
+    fn update_from_market(&mut self, _: &MarketEvent<Instrument, DataKind>) {}
+
     fn generate_summary(&mut self, positions: &[Position]) {
         for position in positions.iter() {
             self.update(position)
@@ -124,8 +124,12 @@ impl PnLReturnSummary {
     }
 
     pub fn update_trades_per_day(&mut self) {
-        self.trades_per_day = self.total.count as f64
-            / (self.duration.num_seconds() as f64 / PnLReturnSummary::SECONDS_IN_DAY)
+        // Trades executed in a zero length session are treated as occurring in a single day
+        let days = self.duration.num_seconds() as f64 / PnLReturnSummary::SECONDS_IN_DAY;
+        self.trades_per_day = match days > 0.0 {
+            true => self.total.count as f64 / days,
+            false => self.total.count as f64,
+        }
     }
 }
 
@@ -199,12 +203,218 @@ impl ProfitLossSummary {
     }
 }
 
+/// Summary of the outcomes of every closed [`Position`], used to derive the win rate, profit
+/// factor & expectancy. Only the raw accumulators are persisted, and the metrics are derived on
+/// read so the infinite profit factor sentinel survives serialisation.
+///
+/// Positions with a positive realised PnL are wins, negative are losses, and breakeven Positions
+/// only count towards the total number of trades. Liquidated Positions are also counted.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct TradeOutcomeSummary {
+    pub trades: u64,
+    pub wins: u64,
+    pub losses: u64,
+    pub gross_profit: f64,
+    pub gross_loss: f64,
+    #[serde(default)]
+    pub liquidations: u64,
+}
+
+impl PositionSummariser for TradeOutcomeSummary {
+    fn update(&mut self, position: &Position) {
+        self.trades += 1;
+        if position.liquidated {
+            self.liquidations += 1;
+        }
+
+        let pnl = position.realised_profit_loss;
+        if pnl > 0.0 {
+            self.wins += 1;
+            self.gross_profit += pnl;
+        } else if pnl < 0.0 {
+            self.losses += 1;
+            self.gross_loss += pnl.abs();
+        }
+    }
+}
+
+impl TableBuilder for TradeOutcomeSummary {
+    fn titles(&self) -> Row {
+        row!["Win Rate", "Profit Factor", "Expectancy", "Liquidations"]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            format!("{:.3}", self.win_rate()),
+            format!("{:.3}", self.profit_factor()),
+            format!("{:.3}", self.expectancy()),
+            self.liquidations.to_string(),
+        ]
+    }
+}
+
+impl TradeOutcomeSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Proportion of trades that were wins, or `0.0` if no trades have been closed.
+    pub fn win_rate(&self) -> f64 {
+        match self.trades {
+            0 => 0.0,
+            trades => self.wins as f64 / trades as f64,
+        }
+    }
+
+    /// Gross profit divided by gross loss. Returns [`f64::INFINITY`] if every trade with a
+    /// non-zero PnL was a win, and `0.0` if no profit has been made.
+    pub fn profit_factor(&self) -> f64 {
+        match (self.gross_profit > 0.0, self.gross_loss > 0.0) {
+            (false, _) => 0.0,
+            (true, false) => f64::INFINITY,
+            (true, true) => self.gross_profit / self.gross_loss,
+        }
+    }
+
+    /// Average realised PnL per trade, or `0.0` if no trades have been closed.
+    pub fn expectancy(&self) -> f64 {
+        match self.trades {
+            0 => 0.0,
+            trades => (self.gross_profit - self.gross_loss) / trades as f64,
+        }
+    }
+}
+
+/// Average Maximum Favourable Excursion (MFE) & Maximum Adverse Excursion (MAE) of every closed
+/// [`Position`], useful for analysing how well trades are entered & exited (eg/ if stops or
+/// targets are placed too tight).
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct ExcursionSummary {
+    pub trades: u64,
+    pub total_favourable_excursion: f64,
+    pub total_adverse_excursion: f64,
+}
+
+impl PositionSummariser for ExcursionSummary {
+    fn update(&mut self, position: &Position) {
+        self.trades += 1;
+        self.total_favourable_excursion += position.meta.max_favourable_excursion;
+        self.total_adverse_excursion += position.meta.max_adverse_excursion;
+    }
+}
+
+impl TableBuilder for ExcursionSummary {
+    fn titles(&self) -> Row {
+        row!["Avg. MFE", "Avg. MAE"]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            format!("{:.3}", self.mean_favourable_excursion()),
+            format!("{:.3}", self.mean_adverse_excursion()),
+        ]
+    }
+}
+
+impl ExcursionSummary {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Average Maximum Favourable Excursion per trade, or `0.0` if no trades have been closed.
+    pub fn mean_favourable_excursion(&self) -> f64 {
+        match self.trades {
+            0 => 0.0,
+            trades => self.total_favourable_excursion / trades as f64,
+        }
+    }
+
+    /// Average Maximum Adverse Excursion per trade, or `0.0` if no trades have been closed.
+    pub fn mean_adverse_excursion(&self) -> f64 {
+        match self.trades {
+            0 => 0.0,
+            trades => self.total_adverse_excursion / trades as f64,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{portfolio::Balance, test_util::position};
     use chrono::{Duration, Utc};
 
+    #[test]
+    fn trade_outcome_summary_with_three_wins_and_two_losses() {
+        let mut summary = TradeOutcomeSummary::new();
+        assert_eq!(summary.win_rate(), 0.0);
+        assert_eq!(summary.profit_factor(), 0.0);
+        assert_eq!(summary.expectancy(), 0.0);
+
+        // PnLs = [50.0, -20.0, 30.0, -10.0, 40.0]
+        let positions = [50.0, -20.0, 30.0, -10.0, 40.0].map(|pnl| {
+            let mut position = position();
+            position.realised_profit_loss = pnl;
+            position
+        });
+        summary.generate_summary(&positions);
+
+        assert_eq!(summary.trades, 5);
+        assert_eq!(summary.wins, 3);
+        assert_eq!(summary.losses, 2);
+        assert_eq!(summary.win_rate(), 0.6);
+        assert_eq!(summary.profit_factor(), 120.0 / 30.0);
+        assert_eq!(summary.expectancy(), 90.0 / 5.0);
+    }
+
+    #[test]
+    fn trade_outcome_summary_counts_liquidated_positions() {
+        let positions = [false, true, false, true].map(|liquidated| {
+            let mut position = position();
+            position.realised_profit_loss = if liquidated { -100.0 } else { 10.0 };
+            position.liquidated = liquidated;
+            position
+        });
+
+        let mut summary = TradeOutcomeSummary::new();
+        summary.generate_summary(&positions);
+
+        assert_eq!(summary.trades, 4);
+        assert_eq!(summary.liquidations, 2);
+    }
+
+    #[test]
+    fn excursion_summary_averages_position_excursions() {
+        let mut summary = ExcursionSummary::new();
+        assert_eq!(summary.mean_favourable_excursion(), 0.0);
+        assert_eq!(summary.mean_adverse_excursion(), 0.0);
+
+        let positions = [(20.0, 10.0), (0.0, 30.0)].map(|(favourable, adverse)| {
+            let mut position = position();
+            position.meta.max_favourable_excursion = favourable;
+            position.meta.max_adverse_excursion = adverse;
+            position
+        });
+        summary.generate_summary(&positions);
+
+        assert_eq!(summary.trades, 2);
+        assert_eq!(summary.mean_favourable_excursion(), 10.0);
+        assert_eq!(summary.mean_adverse_excursion(), 20.0);
+    }
+
+    #[test]
+    fn trade_outcome_summary_without_losses_has_infinite_profit_factor() {
+        let mut position = position();
+        position.realised_profit_loss = 10.0;
+
+        let mut summary = TradeOutcomeSummary::new();
+        summary.update(&position);
+
+        assert_eq!(summary.win_rate(), 1.0);
+        assert_eq!(summary.profit_factor(), f64::INFINITY);
+        assert_eq!(summary.expectancy(), 10.0);
+    }
+
     #[test]
     fn update_pnl_return_summary() {
         // Todo:
@@ -0,0 +1,220 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::{
+        metric::EquityPoint,
+        summary::{PositionSummariser, TableBuilder},
+    },
+};
+use chrono::{DateTime, Duration, Utc};
+use prettytable::Row;
+use serde::{Deserialize, Serialize};
+
+/// Compound annual growth rate (CAGR) & time-weighted return (TWR) of a trading session,
+/// calculated from it's equity curve.
+///
+/// The CAGR is annualised over the calendar span between the first & latest [`EquityPoint`]s,
+/// rather than the number of trades. The TWR chains the return of each period between
+/// [`EquityPoint`]s, so it is not distorted by external cash flows (eg/ deposits & withdrawals)
+/// recorded via [`ReturnSummary::update_cash_flow`].
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct ReturnSummary {
+    pub starting_equity: f64,
+    pub start: Option<EquityPoint>,
+    pub end: Option<EquityPoint>,
+    /// Product of one plus the return of each period between [`EquityPoint`]s.
+    pub time_weighted_growth: f64,
+}
+
+impl PositionSummariser for ReturnSummary {
+    fn update(&mut self, position: &Position) {
+        // Only update ReturnSummary with closed Positions
+        let Some(exit_balance) = position.meta.exit_balance else {
+            return;
+        };
+
+        // Trading session starts with the starting equity when the first Position is entered
+        if self.start.is_none() {
+            self.update_equity(EquityPoint {
+                time: position.meta.enter_time,
+                total: self.starting_equity,
+            });
+        }
+
+        self.update_equity(EquityPoint::from(exit_balance));
+    }
+}
+
+impl Default for ReturnSummary {
+    fn default() -> Self {
+        Self::new(0.0)
+    }
+}
+
+impl TableBuilder for ReturnSummary {
+    fn titles(&self) -> Row {
+        row!["CAGR", "Time-Weighted Return"]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            format!("{:.3}", self.cagr().unwrap_or_default()),
+            format!("{:.3}", self.time_weighted_return()),
+        ]
+    }
+}
+
+impl ReturnSummary {
+    /// Approximate number of seconds in a calendar year, accounting for leap years.
+    const SECONDS_IN_YEAR: f64 = 365.25 * 86400.0;
+
+    /// Constructs a new [`ReturnSummary`] for a trading session with the provided starting
+    /// equity.
+    pub fn new(starting_equity: f64) -> Self {
+        Self {
+            starting_equity,
+            start: None,
+            end: None,
+            time_weighted_growth: 1.0,
+        }
+    }
+
+    /// Updates the equity curve with the next [`EquityPoint`], chaining the return since the
+    /// previous [`EquityPoint`] into the time-weighted return.
+    pub fn update_equity(&mut self, equity_point: EquityPoint) {
+        match self.end {
+            None => self.start = Some(equity_point),
+            Some(previous) if previous.total > 0.0 => {
+                self.time_weighted_growth *= equity_point.total / previous.total;
+            }
+            Some(_) => {}
+        }
+
+        self.end = Some(equity_point);
+    }
+
+    /// Records an external cash flow (eg/ a deposit if positive, or a withdrawal if negative)
+    /// at the provided time.
+    ///
+    /// The cash flow starts a new time-weighted return period without affecting the
+    /// time-weighted return itself.
+    pub fn update_cash_flow(&mut self, time: DateTime<Utc>, cash_flow: f64) {
+        let Some(previous) = self.end else {
+            self.starting_equity += cash_flow;
+            return;
+        };
+
+        self.end = Some(EquityPoint {
+            time,
+            total: previous.total + cash_flow,
+        });
+    }
+
+    /// Calendar span between the first & latest [`EquityPoint`]s of the trading session.
+    pub fn duration(&self) -> Duration {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => end.time.signed_duration_since(start.time),
+            _ => Duration::zero(),
+        }
+    }
+
+    /// Compound annual growth rate of the trading session, calculated as
+    /// `(end_equity / start_equity)^(1 / years) - 1`.
+    ///
+    /// The `years` are derived from the [`EquityPoint`] timestamps, with sessions shorter than a
+    /// day annualised as a full day so the CAGR stays finite. Returns `None` if the equity curve
+    /// is empty or started without any equity.
+    pub fn cagr(&self) -> Option<f64> {
+        let (start, end) = (self.start?, self.end?);
+        if start.total <= 0.0 {
+            return None;
+        }
+
+        let years =
+            self.duration().max(Duration::days(1)).num_seconds() as f64 / Self::SECONDS_IN_YEAR;
+
+        let growth = (end.total / start.total).max(0.0);
+
+        Some((growth.powf(years.recip()) - 1.0).min(f64::MAX))
+    }
+
+    /// Time-weighted return of the trading session, unaffected by the size & timing of external
+    /// cash flows.
+    pub fn time_weighted_return(&self) -> f64 {
+        self.time_weighted_growth - 1.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cagr_annualises_over_equity_curve_calendar_span() {
+        let start = Utc::now();
+        let mut summary = ReturnSummary::new(1000.0);
+
+        summary.update_equity(EquityPoint {
+            time: start,
+            total: 1000.0,
+        });
+        assert_eq!(summary.cagr(), Some(0.0));
+
+        // Equity doubles over six months, regardless of how many trades generated it
+        summary.update_equity(EquityPoint {
+            time: start + Duration::days(30),
+            total: 1200.0,
+        });
+        summary.update_equity(EquityPoint {
+            time: start + Duration::seconds((ReturnSummary::SECONDS_IN_YEAR / 2.0) as i64),
+            total: 2000.0,
+        });
+
+        let cagr = summary.cagr().unwrap();
+        assert!((cagr - 3.0).abs() < 1e-6, "cagr: {cagr}");
+        assert!((summary.time_weighted_return() - 1.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn cagr_of_session_shorter_than_a_day_is_finite() {
+        let start = Utc::now();
+        let mut summary = ReturnSummary::new(1000.0);
+
+        summary.update_equity(EquityPoint {
+            time: start,
+            total: 1000.0,
+        });
+        summary.update_equity(EquityPoint {
+            time: start + Duration::minutes(1),
+            total: 5000.0,
+        });
+
+        let cagr = summary.cagr().unwrap();
+        assert!(cagr.is_finite(), "cagr: {cagr}");
+        assert!(cagr > 0.0);
+    }
+
+    #[test]
+    fn time_weighted_return_is_not_distorted_by_cash_flows() {
+        let start = Utc::now();
+        let mut summary = ReturnSummary::new(1000.0);
+
+        summary.update_equity(EquityPoint {
+            time: start,
+            total: 1000.0,
+        });
+
+        // +10%, then deposit 1000.0, then +10% again
+        summary.update_equity(EquityPoint {
+            time: start + Duration::days(1),
+            total: 1100.0,
+        });
+        summary.update_cash_flow(start + Duration::days(1), 1000.0);
+        summary.update_equity(EquityPoint {
+            time: start + Duration::days(2),
+            total: 2310.0,
+        });
+
+        let twr = summary.time_weighted_return();
+        assert!((twr - 0.21).abs() < 1e-10, "twr: {twr}");
+    }
+}
@@ -0,0 +1,238 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::{
+        metric::ratio::{Ratio, SharpeRatio},
+        summary::{
+            drawdown::DrawdownSummary,
+            pnl::{PnLReturnSummary, TradeOutcomeSummary},
+            PositionSummariser,
+        },
+    },
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Sliding window of closed [`Position`]s used by a [`RollingSummary`].
+#[derive(Copy, Clone, PartialEq, Eq, Debug, Deserialize, Serialize)]
+pub enum RollingWindow {
+    /// Most recent number of closed [`Position`]s.
+    Trades(usize),
+    /// Closed [`Position`]s that exited within the most recent calendar [`Duration`].
+    Duration(
+        #[serde(
+            deserialize_with = "crate::statistic::de_duration_from_secs",
+            serialize_with = "crate::statistic::se_duration_as_secs"
+        )]
+        Duration,
+    ),
+}
+
+/// Configuration for constructing a [`RollingSummary`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub window: RollingWindow,
+    pub starting_equity: f64,
+    pub risk_free_return: f64,
+}
+
+/// Snapshot of the key performance metrics of the closed [`Position`]s within a window.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MetricsSnapshot {
+    pub trades: u64,
+    pub mean_return: f64,
+    pub std_dev_return: f64,
+    pub sharpe_ratio_per_trade: f64,
+    pub win_rate: f64,
+    pub max_drawdown: f64,
+}
+
+/// Time series of [`MetricsSnapshot`]s computed over a sliding [`RollingWindow`] of closed
+/// [`Position`]s, suitable for plotting how performance evolved during a trading session.
+///
+/// A [`MetricsSnapshot`] is recorded at the exit time of every closed [`Position`]. The existing
+/// one-pass accumulators are reset & replayed over the [`Position`]s in each window, with the
+/// window drawdown measured from the equity at the start of the window.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RollingSummary {
+    config: Config,
+    window: VecDeque<Position>,
+    window_starting_equity: f64,
+    pub series: Vec<(DateTime<Utc>, MetricsSnapshot)>,
+}
+
+impl RollingSummary {
+    /// Constructs a new [`RollingSummary`] using the provided [`Config`].
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            window: VecDeque::new(),
+            window_starting_equity: config.starting_equity,
+            series: Vec::new(),
+        }
+    }
+
+    /// Generates the [`MetricsSnapshot`] time series for the provided closed [`Position`]s, which
+    /// are expected to be in exit order.
+    pub fn generate(
+        config: Config,
+        positions: &[Position],
+    ) -> Vec<(DateTime<Utc>, MetricsSnapshot)> {
+        let mut summary = Self::new(config);
+        positions
+            .iter()
+            .for_each(|position| summary.update(position));
+        summary.series
+    }
+
+    /// Updates the [`RollingSummary`] with the next closed [`Position`], recording a
+    /// [`MetricsSnapshot`] of the updated window. Positions that have not exited are ignored.
+    pub fn update(&mut self, position: &Position) {
+        let Some(exit_balance) = position.meta.exit_balance else {
+            return;
+        };
+
+        // Slide the window forward, tracking the equity before the earliest windowed Position
+        self.window.push_back(position.clone());
+        while self.is_outside_window(exit_balance.time) {
+            if let Some(evicted) = self.window.pop_front() {
+                if let Some(evicted_balance) = evicted.meta.exit_balance {
+                    self.window_starting_equity = evicted_balance.total;
+                }
+            }
+        }
+
+        self.series.push((exit_balance.time, self.snapshot()));
+    }
+
+    /// Determines if the earliest windowed [`Position`] has fallen outside the [`RollingWindow`].
+    fn is_outside_window(&self, latest: DateTime<Utc>) -> bool {
+        match self.config.window {
+            RollingWindow::Trades(trades) => self.window.len() > trades.max(1),
+            RollingWindow::Duration(duration) => self
+                .window
+                .front()
+                .and_then(|position| position.meta.exit_balance)
+                .is_some_and(|balance| latest.signed_duration_since(balance.time) >= duration),
+        }
+    }
+
+    /// Replays the windowed [`Position`]s through freshly initialised accumulators.
+    fn snapshot(&self) -> MetricsSnapshot {
+        let mut pnl_returns = PnLReturnSummary::new();
+        let mut outcomes = TradeOutcomeSummary::new();
+        let mut drawdown = DrawdownSummary::new(self.window_starting_equity);
+        let mut sharpe = SharpeRatio::init(self.config.risk_free_return);
+
+        for position in &self.window {
+            pnl_returns.update(position);
+            outcomes.update(position);
+            drawdown.update(position);
+            sharpe.update(&pnl_returns);
+        }
+
+        MetricsSnapshot {
+            trades: pnl_returns.total.count,
+            mean_return: pnl_returns.total.mean,
+            std_dev_return: pnl_returns.total.dispersion.std_dev,
+            sharpe_ratio_per_trade: sharpe.ratio(),
+            win_rate: outcomes.win_rate(),
+            max_drawdown: drawdown.max_drawdown_value(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{portfolio::Balance, test_util::position};
+
+    fn closed_positions(returns: &[f64]) -> Vec<Position> {
+        let base_time = Utc::now();
+        let mut equity = 1000.0;
+
+        returns
+            .iter()
+            .enumerate()
+            .map(|(index, pnl_return)| {
+                let time = base_time + Duration::days(index as i64 + 1);
+                equity += pnl_return * 100.0;
+
+                let mut position = position();
+                position.meta.enter_time = time - Duration::hours(1);
+                position.meta.update_time = time;
+                position.meta.exit_balance = Some(Balance::new(time, equity, equity));
+                position.realised_profit_loss = pnl_return * 100.0;
+                position
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rolling_sharpe_over_trade_window_matches_full_recompute() {
+        let returns = [
+            0.05, -0.02, 0.03, 0.01, -0.04, 0.06, 0.02, -0.01, 0.04, -0.03, 0.02, 0.05, -0.06,
+            0.01, 0.03,
+        ];
+        let positions = closed_positions(&returns);
+
+        let series = RollingSummary::generate(
+            Config {
+                window: RollingWindow::Trades(10),
+                starting_equity: 1000.0,
+                risk_free_return: 0.0,
+            },
+            &positions,
+        );
+        assert_eq!(series.len(), returns.len());
+
+        for (index, (time, snapshot)) in series.iter().enumerate() {
+            let window = &returns[index.saturating_sub(9)..=index];
+
+            // Full recompute of the Sharpe Ratio on the window
+            let count = window.len() as f64;
+            let mean = window.iter().sum::<f64>() / count;
+            let std_dev = (window.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / count).sqrt();
+            let expected_sharpe = match std_dev == 0.0 {
+                true => 0.0,
+                false => mean / std_dev,
+            };
+
+            assert_eq!(*time, positions[index].meta.exit_balance.unwrap().time);
+            assert_eq!(snapshot.trades, window.len() as u64);
+            assert!(
+                (snapshot.mean_return - mean).abs() < 1e-10,
+                "index: {index}"
+            );
+            assert!(
+                (snapshot.sharpe_ratio_per_trade - expected_sharpe).abs() < 1e-10,
+                "index: {index}"
+            );
+        }
+    }
+
+    #[test]
+    fn rolling_window_by_duration_evicts_expired_positions() {
+        let positions = closed_positions(&[0.1, -0.2, 0.1, 0.1]);
+
+        let series = RollingSummary::generate(
+            Config {
+                window: RollingWindow::Duration(Duration::days(2)),
+                starting_equity: 1000.0,
+                risk_free_return: 0.0,
+            },
+            &positions,
+        );
+
+        let trades = series
+            .iter()
+            .map(|(_, snapshot)| snapshot.trades)
+            .collect::<Vec<_>>();
+        assert_eq!(trades, vec![1, 2, 2, 2]);
+
+        // Window [-0.2, 0.1] starts from 1010 equity, so the -0.2 loss is a drawdown of 20/1010
+        let (_, snapshot) = series[2];
+        assert_eq!(snapshot.win_rate, 0.5);
+        assert!((snapshot.max_drawdown - (-20.0 / 1010.0)).abs() < 1e-10);
+    }
+}
@@ -0,0 +1,105 @@
+use crate::{
+    portfolio::position::Position,
+    statistic::summary::{combine, PositionSummariser, TableBuilder},
+    strategy::StrategyId,
+};
+use prettytable::{Row, Table};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Profit & loss and win rate of the exited [`Position`]s attributed to a single strategy.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Default, Deserialize, Serialize)]
+pub struct StrategySummary {
+    pub trades: u64,
+    pub wins: u64,
+    pub losses: u64,
+    /// Realised profit & loss of every exited [`Position`].
+    pub profit_loss: f64,
+}
+
+impl PositionSummariser for StrategySummary {
+    fn update(&mut self, position: &Position) {
+        // Only update StrategySummary with closed Positions
+        if position.meta.exit_balance.is_none() {
+            return;
+        }
+
+        self.trades += 1;
+        self.profit_loss += position.realised_profit_loss;
+        match position.realised_profit_loss.is_sign_negative() {
+            true => self.losses += 1,
+            false => self.wins += 1,
+        }
+    }
+}
+
+impl StrategySummary {
+    /// Proportion of exited [`Position`]s that were profitable, or 0.0 if none have exited.
+    pub fn win_rate(&self) -> f64 {
+        match self.trades {
+            0 => 0.0,
+            trades => self.wins as f64 / trades as f64,
+        }
+    }
+}
+
+impl TableBuilder for StrategySummary {
+    fn titles(&self) -> Row {
+        row!["Trades", "Wins", "Losses", "Win Rate", "Profit & Loss"]
+    }
+
+    fn row(&self) -> Row {
+        row![
+            self.trades,
+            self.wins,
+            self.losses,
+            format!("{:.3}", self.win_rate()),
+            format!("{:.3}", self.profit_loss),
+        ]
+    }
+}
+
+/// Per-strategy breakdown of exited [`Position`]s, keyed by the [`StrategyId`] each
+/// [`Position`] is attributed to.
+#[derive(Clone, PartialEq, Debug, Default, Deserialize, Serialize)]
+pub struct StrategyBreakdown {
+    pub strategies: BTreeMap<StrategyId, StrategySummary>,
+}
+
+impl StrategyBreakdown {
+    /// Constructs a [`StrategyBreakdown`] from the provided [`Position`]s.
+    pub fn new(positions: &[Position]) -> Self {
+        let mut breakdown = Self::default();
+        positions
+            .iter()
+            .for_each(|position| breakdown.update(position));
+        breakdown
+    }
+
+    /// Updates the [`StrategySummary`] of the strategy the [`Position`] is attributed to.
+    pub fn update(&mut self, position: &Position) {
+        if position.meta.exit_balance.is_none() {
+            return;
+        }
+
+        self.strategies
+            .entry(position.strategy_id.clone())
+            .or_default()
+            .update(position);
+    }
+
+    /// Returns the [`StrategySummary`] of the provided strategy, if any of it's [`Position`]s
+    /// have exited.
+    pub fn get(&self, strategy_id: &StrategyId) -> Option<&StrategySummary> {
+        self.strategies.get(strategy_id)
+    }
+
+    /// Generates a [`Table`] with a [`StrategySummary`] row per strategy.
+    pub fn table(&self) -> Table {
+        combine(
+            self.strategies
+                .iter()
+                .map(|(strategy_id, summary)| (strategy_id.0.clone(), *summary)),
+        )
+    }
+}
@@ -1,13 +1,22 @@
 use crate::{
     portfolio::position::Position,
     statistic::{
-        metric::ratio::{CalmarRatio, Ratio, SharpeRatio, SortinoRatio},
+        de_duration_from_secs,
+        metric::ratio::{
+            CalmarRatio, OmegaRatio, PeriodsPerYear, Ratio, SharpeRatio, SortinoRatio,
+        },
+        se_duration_as_secs,
         summary::{
-            drawdown::DrawdownSummary, pnl::PnLReturnSummary, Initialiser, PositionSummariser,
-            TableBuilder,
+            benchmark::BenchmarkSummary,
+            drawdown::DrawdownSummary,
+            pnl::{ExcursionSummary, PnLReturnSummary, TradeOutcomeSummary},
+            returns::ReturnSummary,
+            Initialiser, PositionSummariser, TableBuilder,
         },
     },
 };
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
 use chrono::{DateTime, Duration, Utc};
 use prettytable::{Cell, Row};
 use serde::{Deserialize, Serialize};
@@ -17,14 +26,47 @@ use serde::{Deserialize, Serialize};
 pub struct Config {
     pub starting_equity: f64,
     pub trading_days_per_year: usize,
+    /// Duration of each data period (eg/ candle interval) the strategy trades on, used with the
+    /// `trading_days_per_year` to annualise the [`TearSheet`] ratios. Defaults to one day.
+    #[serde(
+        default = "default_data_period",
+        deserialize_with = "de_duration_from_secs",
+        serialize_with = "se_duration_as_secs"
+    )]
+    pub data_period: Duration,
     pub risk_free_return: f64,
+    /// Threshold return of the [`OmegaRatio`], defaulting to the `risk_free_return` if `None`.
+    #[serde(default)]
+    pub omega_threshold: Option<f64>,
+    /// Compare the strategy against buying & holding the instrument traded over the same period
+    /// (see [`BenchmarkSummary`]).
+    #[serde(default)]
+    pub with_benchmark: bool,
+}
+
+fn default_data_period() -> Duration {
+    Duration::days(1)
 }
 
 #[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct TradingSummary {
     pub pnl_returns: PnLReturnSummary,
+    #[serde(default)]
+    pub outcomes: TradeOutcomeSummary,
+    /// Average maximum favourable & adverse excursion of each closed Position.
+    #[serde(default)]
+    pub excursions: ExcursionSummary,
     pub drawdown: DrawdownSummary,
+    /// CAGR & time-weighted return annualised over the trading session's calendar span.
+    #[serde(default)]
+    pub returns: ReturnSummary,
     pub tear_sheet: TearSheet,
+    /// Timestamp the Portfolio went bankrupt, if it has. Metrics are frozen from this point.
+    #[serde(default)]
+    pub bankruptcy: Option<DateTime<Utc>>,
+    /// Buy-and-hold benchmark comparison, if enabled via [`Config::with_benchmark`].
+    #[serde(default)]
+    pub benchmark: Option<BenchmarkSummary>,
 }
 
 impl Initialiser for TradingSummary {
@@ -33,17 +75,61 @@ impl Initialiser for TradingSummary {
     fn init(config: Self::Config) -> Self {
         Self {
             pnl_returns: PnLReturnSummary::new(),
+            outcomes: TradeOutcomeSummary::new(),
+            excursions: ExcursionSummary::new(),
             drawdown: DrawdownSummary::new(config.starting_equity),
-            tear_sheet: TearSheet::new(config.risk_free_return),
+            returns: ReturnSummary::new(config.starting_equity),
+            tear_sheet: TearSheet::new(
+                config.risk_free_return,
+                config.omega_threshold.unwrap_or(config.risk_free_return),
+            )
+            .with_periods_per_year(PeriodsPerYear::new(
+                config.data_period,
+                config.trading_days_per_year as u32,
+            )),
+            bankruptcy: None,
+            benchmark: config
+                .with_benchmark
+                .then(|| BenchmarkSummary::new(config.starting_equity)),
         }
     }
 }
 
 impl PositionSummariser for TradingSummary {
     fn update(&mut self, position: &Position) {
+        if self.bankruptcy.is_some() {
+            return;
+        }
+
         self.pnl_returns.update(position);
+        self.outcomes.update(position);
+        self.excursions.update(position);
+        if let Some(benchmark) = &mut self.benchmark {
+            benchmark.update(position);
+        }
         self.drawdown.update(position);
-        self.tear_sheet.update(&self.pnl_returns, &self.drawdown);
+        self.returns.update(position);
+        self.tear_sheet.update(
+            &self.pnl_returns,
+            &self.drawdown,
+            position.calculate_profit_loss_return(),
+        );
+
+        if let Some(exit_balance) = position.meta.exit_balance {
+            if exit_balance.is_bankrupt() {
+                self.bankruptcy = Some(exit_balance.time);
+            }
+        }
+    }
+
+    fn update_from_market(&mut self, market: &MarketEvent<Instrument, DataKind>) {
+        if self.bankruptcy.is_some() {
+            return;
+        }
+
+        if let Some(benchmark) = &mut self.benchmark {
+            benchmark.update_from_market(market);
+        }
     }
 }
 
@@ -55,6 +141,18 @@ impl TableBuilder for TradingSummary {
             titles.push(title.clone())
         }
 
+        for title in &self.outcomes.titles() {
+            titles.push(title.clone())
+        }
+
+        for title in &self.excursions.titles() {
+            titles.push(title.clone())
+        }
+
+        for title in &self.returns.titles() {
+            titles.push(title.clone())
+        }
+
         for title in &self.tear_sheet.titles() {
             titles.push(title.clone())
         }
@@ -63,6 +161,12 @@ impl TableBuilder for TradingSummary {
             titles.push(title.clone())
         }
 
+        if let Some(benchmark) = &self.benchmark {
+            for title in &benchmark.titles() {
+                titles.push(title.clone())
+            }
+        }
+
         Row::new(titles)
     }
 
@@ -73,6 +177,18 @@ impl TableBuilder for TradingSummary {
             cells.push(cell.clone())
         }
 
+        for cell in &self.outcomes.row() {
+            cells.push(cell.clone())
+        }
+
+        for cell in &self.excursions.row() {
+            cells.push(cell.clone())
+        }
+
+        for cell in &self.returns.row() {
+            cells.push(cell.clone())
+        }
+
         for cell in &self.tear_sheet.row() {
             cells.push(cell.clone())
         }
@@ -81,6 +197,12 @@ impl TableBuilder for TradingSummary {
             cells.push(cell.clone())
         }
 
+        if let Some(benchmark) = &self.benchmark {
+            for cell in &benchmark.row() {
+                cells.push(cell.clone())
+            }
+        }
+
         Row::new(cells)
     }
 }
@@ -90,20 +212,53 @@ pub struct TearSheet {
     pub sharpe_ratio: SharpeRatio,
     pub sortino_ratio: SortinoRatio,
     pub calmar_ratio: CalmarRatio,
+    #[serde(default)]
+    pub omega_ratio: OmegaRatio,
+    /// Data periods per year the ratios are annualised over (see [`TearSheet::annualised`]).
+    #[serde(default)]
+    pub periods_per_year: PeriodsPerYear,
 }
 
 impl TearSheet {
-    pub fn new(risk_free_return: f64) -> Self {
+    pub fn new(risk_free_return: f64, omega_threshold: f64) -> Self {
         Self {
             sharpe_ratio: SharpeRatio::init(risk_free_return),
             sortino_ratio: SortinoRatio::init(risk_free_return),
             calmar_ratio: CalmarRatio::init(risk_free_return),
+            omega_ratio: OmegaRatio::init(omega_threshold),
+            periods_per_year: PeriodsPerYear::default(),
         }
     }
 
-    pub fn update(&mut self, pnl_returns: &PnLReturnSummary, drawdown: &DrawdownSummary) {
+    /// Annualise the ratios over the provided [`PeriodsPerYear`] rather than daily periods over
+    /// 365 trading days.
+    pub fn with_periods_per_year(self, periods_per_year: PeriodsPerYear) -> Self {
+        Self {
+            periods_per_year,
+            ..self
+        }
+    }
+
+    /// Returns the annualised Sharpe, Sortino & Calmar ratios over the configured
+    /// [`PeriodsPerYear`]. The Sharpe & Sortino ratios are scaled by it's square root, whereas the
+    /// Calmar ratio divides the linearly annualised return by the max drawdown.
+    pub fn annualised(&self) -> (f64, f64, f64) {
+        (
+            self.sharpe_ratio.annualised(self.periods_per_year),
+            self.sortino_ratio.annualised(self.periods_per_year),
+            self.calmar_ratio.annualised(self.periods_per_year),
+        )
+    }
+
+    pub fn update(
+        &mut self,
+        pnl_returns: &PnLReturnSummary,
+        drawdown: &DrawdownSummary,
+        pnl_return: f64,
+    ) {
         self.sharpe_ratio.update(pnl_returns);
-        self.sortino_ratio.update(pnl_returns);
+        self.sortino_ratio.update(pnl_returns, pnl_return);
+        self.omega_ratio.update(pnl_return);
         self.calmar_ratio
             .update(pnl_returns, drawdown.max_drawdown.drawdown.drawdown);
     }
@@ -111,7 +266,12 @@ impl TearSheet {
 
 impl TableBuilder for TearSheet {
     fn titles(&self) -> Row {
-        row!["Sharpe Ratio", "Sortino Ratio", "Calmar Ratio"]
+        row![
+            "Sharpe Ratio",
+            "Sortino Ratio",
+            "Calmar Ratio",
+            "Omega Ratio"
+        ]
     }
 
     fn row(&self) -> Row {
@@ -119,6 +279,7 @@ impl TableBuilder for TearSheet {
             format!("{:.3}", self.sharpe_ratio.daily()),
             format!("{:.3}", self.sortino_ratio.daily()),
             format!("{:.3}", self.calmar_ratio.daily()),
+            format!("{:.3}", self.omega_ratio.ratio()),
         ]
     }
 }
@@ -132,3 +293,75 @@ pub fn calculate_trading_duration(start_time: &DateTime<Utc>, position: &Positio
         Some(exit_balance) => exit_balance.time.signed_duration_since(*start_time),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        portfolio::Balance,
+        test_util::{market_event_candle, position},
+    };
+
+    #[test]
+    fn trading_summary_with_benchmark_reports_negative_alpha_when_underperforming() {
+        let mut summary = TradingSummary::init(Config {
+            starting_equity: 1000.0,
+            trading_days_per_year: 365,
+            data_period: Duration::days(1),
+            risk_free_return: 0.0,
+            omega_threshold: None,
+            with_benchmark: true,
+        });
+        let start = Utc::now();
+
+        // Instrument rises 100.0 -> 200.0 over 10 days of candles
+        for day in 0..=10 {
+            let mut market = market_event_candle();
+            market.time_exchange = start + Duration::days(day);
+            if let DataKind::Candle(candle) = &mut market.kind {
+                candle.close = 100.0 + 10.0 * day as f64;
+            }
+            summary.update_from_market(&market);
+        }
+
+        // Strategy captures 10.0 & 20.0 of the move with two 1.0 quantity Positions
+        for (enter_day, exit_day, enter_price, exit_price) in
+            [(1, 2, 110.0, 120.0), (5, 7, 150.0, 170.0)]
+        {
+            let mut position = position();
+            position.quantity = 1.0;
+            position.meta.enter_time = start + Duration::days(enter_day);
+            position.meta.exit_balance = Some(Balance::new(
+                start + Duration::days(exit_day),
+                1000.0,
+                1000.0,
+            ));
+            position.enter_avg_price_gross = enter_price;
+            position.enter_value_gross = enter_price;
+            position.exit_avg_price_gross = exit_price;
+            position.realised_profit_loss = exit_price - enter_price;
+            summary.update(&position);
+        }
+
+        let benchmark = summary.benchmark.unwrap();
+        assert_eq!(benchmark.data_points, 11);
+        assert_eq!(benchmark.benchmark_return(), Some(1.0));
+        assert!((benchmark.strategy_return() - 0.03).abs() < 1e-10);
+
+        let alpha = benchmark.alpha().unwrap();
+        assert!((alpha - -0.97).abs() < 1e-10, "alpha: {alpha}");
+
+        // Long Positions move one-for-one with the instrument
+        let beta = benchmark.beta().unwrap();
+        assert!((beta - 1.0).abs() < 1e-10, "beta: {beta}");
+
+        // Benchmark return is printed alongside the strategy return
+        let titles = summary
+            .titles()
+            .iter()
+            .map(|title| title.get_content())
+            .collect::<Vec<_>>();
+        assert!(titles.contains(&"Strategy Return".to_string()));
+        assert!(titles.contains(&"Benchmark Return".to_string()));
+    }
+}
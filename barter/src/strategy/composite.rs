@@ -0,0 +1,430 @@
+use super::{Decision, Signal, SignalGenerator, SignalStrength, StrategyId};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Formatter},
+};
+
+/// Default minimum absolute weighted score a netted [`Decision`] must reach to be emitted.
+pub const DEFAULT_THRESHOLD: f64 = 0.5;
+
+/// Default [`StrategyId`] the netted [`Signal`]s of a [`CompositeStrategy`] are attributed to.
+pub const DEFAULT_STRATEGY_ID: &str = "composite";
+
+/// Weighted [`SignalGenerator`] member of a [`CompositeStrategy`].
+struct Member {
+    strategy: Box<dyn SignalGenerator + Send>,
+    weight: f64,
+}
+
+/// [`SignalGenerator`] that combines the advisory [`Signal`]s of multiple weighted member
+/// strategies into a single [`Signal`].
+///
+/// Every member is updated with each [`MarketEvent`]. Opposing votes are netted out - Long
+/// against Short, and CloseShort against CloseLong - and normalised by the total member weight.
+/// A netted [`Decision`] is only emitted if its absolute score reaches the configured threshold.
+///
+/// Netted [`Signal`]s are attributed to the [`CompositeStrategy`]'s own [`StrategyId`], rather
+/// than to any of it's members. If an exit is emitted, it's `close_fraction` is the weighted
+/// average of the members voting for that exit (a full close counting as 1.0).
+pub struct CompositeStrategy {
+    members: Vec<Member>,
+    threshold: f64,
+    strategy_id: StrategyId,
+}
+
+impl SignalGenerator for CompositeStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Update every member so their indicators stay in sync, even if a vote is absent
+        let votes = self
+            .members
+            .iter_mut()
+            .filter_map(|member| {
+                member
+                    .strategy
+                    .generate_signal(market)
+                    .map(|signal| (member.weight, signal))
+            })
+            .collect::<Vec<(f64, Signal)>>();
+
+        // Short-circuit if no member generated a Signal
+        let (_, template) = votes.first()?;
+
+        let total_weight = self.members.iter().map(|member| member.weight).sum::<f64>();
+        if total_weight <= 0.0 {
+            return None;
+        }
+
+        // Net opposing votes into an entry score (Long > 0) & an exit score (CloseShort > 0)
+        let (entry_score, exit_score) =
+            votes
+                .iter()
+                .fold((0.0, 0.0), |(entry, exit), (weight, signal)| {
+                    let strength = |decision| {
+                        signal
+                            .signals
+                            .get(&decision)
+                            .map_or(0.0, |strength: &SignalStrength| strength.0)
+                    };
+                    (
+                        entry
+                            + weight
+                                * (strength(Decision::EnterLong) - strength(Decision::EnterShort)),
+                        exit + weight
+                            * (strength(Decision::CloseShort) - strength(Decision::CloseLong)),
+                    )
+                });
+
+        let mut signals = HashMap::with_capacity(2);
+        self.insert_netted(
+            &mut signals,
+            entry_score / total_weight,
+            Decision::EnterLong,
+            Decision::EnterShort,
+        );
+        self.insert_netted(
+            &mut signals,
+            exit_score / total_weight,
+            Decision::CloseShort,
+            Decision::CloseLong,
+        );
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        // Net the close fractions of the members voting for the emitted exit, if any
+        let close_fraction = [Decision::CloseShort, Decision::CloseLong]
+            .into_iter()
+            .find(|exit| signals.contains_key(exit))
+            .and_then(|exit| Self::net_close_fraction(&votes, exit));
+
+        Some(Signal {
+            signals,
+            strategy_id: self.strategy_id.clone(),
+            close_fraction,
+            ..template.clone()
+        })
+    }
+
+    fn is_warm(&self) -> bool {
+        self.members.iter().all(|member| member.strategy.is_warm())
+    }
+}
+
+impl CompositeStrategy {
+    /// Returns a [`CompositeStrategyBuilder`] instance.
+    pub fn builder() -> CompositeStrategyBuilder {
+        CompositeStrategyBuilder::new()
+    }
+
+    /// Inserts the positive or negative [`Decision`] if the netted score reaches the threshold.
+    fn insert_netted(
+        &self,
+        signals: &mut HashMap<Decision, SignalStrength>,
+        score: f64,
+        positive: Decision,
+        negative: Decision,
+    ) {
+        if score.abs() < self.threshold || score == 0.0 {
+            return;
+        }
+
+        let decision = if score > 0.0 { positive } else { negative };
+        signals.insert(decision, SignalStrength(score.abs()));
+    }
+
+    /// Weighted average `close_fraction` of the votes advising the provided exit [`Decision`].
+    /// Returns `None` (ie/ a full close) if the netted fraction is 1.0 or above.
+    fn net_close_fraction(votes: &[(f64, Signal)], exit: Decision) -> Option<f64> {
+        let (weighted_fraction, exit_weight) = votes
+            .iter()
+            .filter(|(_, signal)| signal.signals.contains_key(&exit))
+            .fold((0.0, 0.0), |(fraction, total), (weight, signal)| {
+                (
+                    fraction + weight * signal.close_fraction.unwrap_or(1.0).min(1.0),
+                    total + weight,
+                )
+            });
+
+        if exit_weight <= 0.0 {
+            return None;
+        }
+
+        Some(weighted_fraction / exit_weight).filter(|fraction| *fraction < 1.0)
+    }
+}
+
+impl Debug for CompositeStrategy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositeStrategy")
+            .field(
+                "weights",
+                &self
+                    .members
+                    .iter()
+                    .map(|member| member.weight)
+                    .collect::<Vec<f64>>(),
+            )
+            .field("threshold", &self.threshold)
+            .field("strategy_id", &self.strategy_id)
+            .finish()
+    }
+}
+
+/// Builder to construct [`CompositeStrategy`] instances.
+pub struct CompositeStrategyBuilder {
+    members: Vec<Member>,
+    threshold: f64,
+    strategy_id: StrategyId,
+}
+
+impl Default for CompositeStrategyBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CompositeStrategyBuilder {
+    pub fn new() -> Self {
+        Self {
+            members: Vec::new(),
+            threshold: DEFAULT_THRESHOLD,
+            strategy_id: StrategyId::new(DEFAULT_STRATEGY_ID),
+        }
+    }
+
+    pub fn add<Strategy>(mut self, strategy: Strategy, weight: f64) -> Self
+    where
+        Strategy: SignalGenerator + Send + 'static,
+    {
+        self.members.push(Member {
+            strategy: Box::new(strategy),
+            weight,
+        });
+        self
+    }
+
+    pub fn threshold(self, value: f64) -> Self {
+        Self {
+            threshold: value,
+            ..self
+        }
+    }
+
+    pub fn strategy_id<Id>(self, value: Id) -> Self
+    where
+        Id: Into<StrategyId>,
+    {
+        Self {
+            strategy_id: value.into(),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> CompositeStrategy {
+        CompositeStrategy {
+            members: self.members,
+            threshold: self.threshold,
+            strategy_id: self.strategy_id,
+        }
+    }
+}
+
+impl Debug for CompositeStrategyBuilder {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositeStrategyBuilder")
+            .field("members", &self.members.len())
+            .field("threshold", &self.threshold)
+            .field("strategy_id", &self.strategy_id)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::MarketMeta, strategy::tagged::TaggedStrategy, test_util::market_event_candle,
+    };
+    use chrono::Utc;
+
+    /// Member strategy that always advises the same decisions with full strength.
+    struct FixedStrategy(Vec<Decision>);
+
+    impl SignalGenerator for FixedStrategy {
+        fn generate_signal(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+        ) -> Option<Signal> {
+            if self.0.is_empty() {
+                return None;
+            }
+
+            Some(Signal {
+                time: Utc::now(),
+                exchange: market.exchange,
+                instrument: market.instrument.clone(),
+                signals: self
+                    .0
+                    .iter()
+                    .map(|decision| (*decision, SignalStrength(1.0)))
+                    .collect(),
+                market_meta: MarketMeta::default(),
+                strategy_id: StrategyId::default(),
+                close_fraction: None,
+            })
+        }
+    }
+
+    /// Member strategy that wraps a [`FixedStrategy`], advising partial exits.
+    struct PartialExitStrategy(FixedStrategy, f64);
+
+    impl SignalGenerator for PartialExitStrategy {
+        fn generate_signal(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+        ) -> Option<Signal> {
+            self.0.generate_signal(market).map(|signal| Signal {
+                close_fraction: Some(self.1),
+                ..signal
+            })
+        }
+    }
+
+    #[test]
+    fn opposing_equal_weight_signals_cancel_to_none() {
+        let mut strategy = CompositeStrategy::builder()
+            .add(
+                FixedStrategy(vec![Decision::EnterLong, Decision::CloseShort]),
+                0.5,
+            )
+            .add(
+                FixedStrategy(vec![Decision::EnterShort, Decision::CloseLong]),
+                0.5,
+            )
+            .threshold(0.1)
+            .build();
+
+        assert_eq!(strategy.generate_signal(&market_event_candle()), None);
+    }
+
+    #[test]
+    fn weighted_majority_signal_crosses_threshold() {
+        let mut strategy = CompositeStrategy::builder()
+            .add(
+                FixedStrategy(vec![Decision::EnterLong, Decision::CloseShort]),
+                0.6,
+            )
+            .add(
+                FixedStrategy(vec![Decision::EnterShort, Decision::CloseLong]),
+                0.4,
+            )
+            .threshold(0.1)
+            .build();
+
+        let signal = strategy.generate_signal(&market_event_candle()).unwrap();
+        assert_eq!(signal.signals.len(), 2);
+        assert!((signal.signals[&Decision::EnterLong].0 - 0.2).abs() < 1e-10);
+        assert!((signal.signals[&Decision::CloseShort].0 - 0.2).abs() < 1e-10);
+    }
+
+    #[test]
+    fn absent_votes_dilute_score_below_threshold() {
+        let mut strategy = CompositeStrategy::builder()
+            .add(FixedStrategy(vec![Decision::EnterLong]), 0.4)
+            .add(FixedStrategy(vec![]), 0.6)
+            .build();
+
+        // Long score of 0.4 is below the default 0.5 threshold
+        assert_eq!(strategy.generate_signal(&market_event_candle()), None);
+
+        let mut strategy = CompositeStrategy::builder()
+            .add(FixedStrategy(vec![]), 1.0)
+            .build();
+
+        assert_eq!(strategy.generate_signal(&market_event_candle()), None);
+    }
+
+    #[test]
+    fn netted_signal_is_attributed_to_composite_strategy_id() {
+        let build = || {
+            CompositeStrategy::builder()
+                .add(
+                    TaggedStrategy::new(FixedStrategy(vec![Decision::EnterLong]), "momentum"),
+                    0.6,
+                )
+                .add(
+                    TaggedStrategy::new(FixedStrategy(vec![Decision::EnterLong]), "breakout"),
+                    0.4,
+                )
+        };
+
+        let signal = build()
+            .build()
+            .generate_signal(&market_event_candle())
+            .unwrap();
+        assert_eq!(signal.strategy_id, StrategyId::new(DEFAULT_STRATEGY_ID));
+
+        let signal = build()
+            .strategy_id("ensemble")
+            .build()
+            .generate_signal(&market_event_candle())
+            .unwrap();
+        assert_eq!(signal.strategy_id, StrategyId::new("ensemble"));
+    }
+
+    #[test]
+    fn close_fraction_is_netted_across_exit_voters() {
+        // Partial exit of 0.5 weighted 0.6, full exit weighted 0.4: 0.6 * 0.5 + 0.4 * 1.0 = 0.7
+        let mut strategy = CompositeStrategy::builder()
+            .add(
+                PartialExitStrategy(FixedStrategy(vec![Decision::CloseLong]), 0.5),
+                0.6,
+            )
+            .add(FixedStrategy(vec![Decision::CloseLong]), 0.4)
+            .build();
+
+        let signal = strategy.generate_signal(&market_event_candle()).unwrap();
+        assert!((signal.close_fraction.unwrap() - 0.7).abs() < 1e-10);
+
+        // Only members voting for the emitted exit contribute
+        let mut strategy = CompositeStrategy::builder()
+            .add(FixedStrategy(vec![Decision::CloseLong]), 0.8)
+            .add(
+                PartialExitStrategy(FixedStrategy(vec![Decision::CloseShort]), 0.25),
+                0.2,
+            )
+            .threshold(0.1)
+            .build();
+
+        let signal = strategy.generate_signal(&market_event_candle()).unwrap();
+        assert!(signal.signals.contains_key(&Decision::CloseLong));
+        assert_eq!(signal.close_fraction, None);
+    }
+
+    #[test]
+    fn close_fraction_is_cleared_without_a_netted_exit() {
+        // First voter advises a partial exit that doesn't survive netting
+        let mut strategy = CompositeStrategy::builder()
+            .add(
+                PartialExitStrategy(
+                    FixedStrategy(vec![Decision::EnterLong, Decision::CloseShort]),
+                    0.5,
+                ),
+                0.5,
+            )
+            .add(
+                FixedStrategy(vec![Decision::EnterLong, Decision::CloseLong]),
+                0.5,
+            )
+            .build();
+
+        let signal = strategy.generate_signal(&market_event_candle()).unwrap();
+        assert_eq!(signal.signals.len(), 1);
+        assert!(signal.signals.contains_key(&Decision::EnterLong));
+        assert_eq!(signal.close_fraction, None);
+    }
+}
@@ -1,22 +1,28 @@
-use super::{Decision, Signal, SignalGenerator, SignalStrength};
+use super::{
+    gap::{GapRewarm, GapRewarmConfig},
+    indicator::{Atr, Ema, MacdLine, RollingStdDev, Rsi},
+    Decision, Signal, SignalGenerator, SignalStrength, StrategyId,
+};
 use crate::data::MarketMeta;
 use barter_data::event::{DataKind, MarketEvent};
 use barter_instrument::instrument::Instrument;
-use chrono::Utc;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use ta::{indicators::RelativeStrengthIndex, Next};
 
 /// Configuration for constructing a [`RSIStrategy`] via the new() constructor method.
 #[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct Config {
     pub rsi_period: usize,
+    /// Optional policy to reset & re-warm the RSI indicator after a gap in market data.
+    #[serde(default)]
+    pub gap_rewarm: Option<GapRewarmConfig>,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Copy, Clone, Debug)]
 /// Example RSI based strategy that implements [`SignalGenerator`].
 pub struct RSIStrategy {
-    rsi: RelativeStrengthIndex,
+    rsi: Rsi,
+    gap_rewarm: Option<GapRewarm>,
 }
 
 impl SignalGenerator for RSIStrategy {
@@ -27,8 +33,24 @@ impl SignalGenerator for RSIStrategy {
             _ => return None,
         };
 
+        // Reset the RSI indicator if a gap in market data has made it's state stale
+        if let Some(gap_rewarm) = &mut self.gap_rewarm {
+            if gap_rewarm.update(market.time_exchange) {
+                self.rsi.reset();
+            }
+        }
+
         // Calculate the next RSI value using the new MarketEvent Candle data
-        let rsi = self.rsi.next(candle_close);
+        let rsi = self.rsi.update(candle_close)?;
+
+        // Suppress signals while the RSI indicator re-warms after a gap
+        if self
+            .gap_rewarm
+            .as_ref()
+            .is_some_and(GapRewarm::is_warming_up)
+        {
+            return None;
+        }
 
         // Generate advisory signals map
         let signals = RSIStrategy::generate_signals_map(rsi);
@@ -39,7 +61,7 @@ impl SignalGenerator for RSIStrategy {
         }
 
         Some(Signal {
-            time: Utc::now(),
+            time: market.time_received,
             exchange: market.exchange,
             instrument: market.instrument.clone(),
             market_meta: MarketMeta {
@@ -47,17 +69,32 @@ impl SignalGenerator for RSIStrategy {
                 time: market.time_exchange,
             },
             signals,
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
         })
     }
+
+    fn is_warm(&self) -> bool {
+        self.rsi.value().is_some()
+            && !self
+                .gap_rewarm
+                .as_ref()
+                .is_some_and(GapRewarm::is_warming_up)
+    }
 }
 
 impl RSIStrategy {
     /// Constructs a new [`RSIStrategy`] component using the provided configuration struct.
     pub fn new(config: Config) -> Self {
-        let rsi_indicator = RelativeStrengthIndex::new(config.rsi_period)
-            .expect("Failed to construct RSI indicator");
+        Self {
+            rsi: Rsi::new(config.rsi_period),
+            gap_rewarm: config.gap_rewarm.map(GapRewarm::new),
+        }
+    }
 
-        Self { rsi: rsi_indicator }
+    /// Returns the number of candles the RSI indicator is calculated over.
+    pub fn rsi_period(&self) -> usize {
+        self.rsi.period
     }
 
     /// Given the latest RSI value for a symbol, generates a map containing the [`SignalStrength`] for
@@ -65,7 +102,10 @@ impl RSIStrategy {
     fn generate_signals_map(rsi: f64) -> HashMap<Decision, SignalStrength> {
         let mut signals = HashMap::with_capacity(4);
         if rsi < 40.0 {
-            signals.insert(Decision::Long, RSIStrategy::calculate_signal_strength());
+            signals.insert(
+                Decision::EnterLong,
+                RSIStrategy::calculate_signal_strength(),
+            );
         }
         if rsi > 60.0 {
             signals.insert(
@@ -74,7 +114,10 @@ impl RSIStrategy {
             );
         }
         if rsi > 60.0 {
-            signals.insert(Decision::Short, RSIStrategy::calculate_signal_strength());
+            signals.insert(
+                Decision::EnterShort,
+                RSIStrategy::calculate_signal_strength(),
+            );
         }
         if rsi < 40.0 {
             signals.insert(
@@ -90,3 +133,713 @@ impl RSIStrategy {
         SignalStrength(1.0)
     }
 }
+
+/// Configuration for constructing a [`MACDStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct MACDConfig {
+    pub fast_period: usize,
+    pub slow_period: usize,
+    pub signal_period: usize,
+}
+
+impl Default for MACDConfig {
+    fn default() -> Self {
+        Self {
+            fast_period: 12,
+            slow_period: 26,
+            signal_period: 9,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+/// Example MACD crossover strategy that implements [`SignalGenerator`]. A crossover of the MACD
+/// line above the signal line advises entering Long, and a crossunder advises exiting Long &
+/// entering Short.
+///
+/// The signal line is an [`Ema`] of the [`MacdLine`], so the histogram is available once both
+/// have warmed up.
+pub struct MACDStrategy {
+    macd: MacdLine,
+    signal: Ema,
+    prev_histogram: Option<f64>,
+}
+
+impl SignalGenerator for MACDStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check if it's a MarketEvent with a candle
+        let candle_close = match &market.kind {
+            DataKind::Candle(candle) => candle.close,
+            _ => return None,
+        };
+
+        // Calculate the next MACD & signal line values using the new MarketEvent Candle data
+        let macd = self.macd.update(candle_close);
+        let signal = macd.and_then(|macd| self.signal.update(macd));
+
+        // No signals until the slow & signal EMAs have warmed up
+        let histogram = macd? - signal?;
+        let prev_histogram = self.prev_histogram.replace(histogram)?;
+
+        // Generate advisory signals map from any MACD & signal line crossover
+        let signals = MACDStrategy::generate_signals_map(prev_histogram, histogram, candle_close);
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: market.time_received,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: candle_close,
+                time: market.time_exchange,
+            },
+            signals,
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+
+    fn is_warm(&self) -> bool {
+        self.prev_histogram.is_some()
+    }
+}
+
+impl MACDStrategy {
+    /// Constructs a new [`MACDStrategy`] component using the provided configuration struct.
+    pub fn new(config: MACDConfig) -> Self {
+        Self {
+            macd: MacdLine::new(config.fast_period, config.slow_period),
+            signal: Ema::new(config.signal_period),
+            prev_histogram: None,
+        }
+    }
+
+    /// Given the previous & latest MACD histogram values for a symbol, generates a map containing
+    /// the [`SignalStrength`] for each [`Decision`] under consideration.
+    fn generate_signals_map(
+        prev_histogram: f64,
+        histogram: f64,
+        close: f64,
+    ) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(4);
+        let strength = MACDStrategy::calculate_signal_strength(histogram, close);
+
+        // MACD line crosses over the signal line
+        if prev_histogram <= 0.0 && histogram > 0.0 {
+            signals.insert(Decision::EnterLong, strength);
+            signals.insert(Decision::CloseShort, strength);
+        }
+
+        // MACD line crosses under the signal line
+        if prev_histogram >= 0.0 && histogram < 0.0 {
+            signals.insert(Decision::CloseLong, strength);
+            signals.insert(Decision::EnterShort, strength);
+        }
+
+        signals
+    }
+
+    /// Calculates the [`SignalStrength`] of a crossover from the histogram magnitude relative to
+    /// the close price, where a histogram of 1% of the close (or more) is full strength.
+    fn calculate_signal_strength(histogram: f64, close: f64) -> SignalStrength {
+        SignalStrength((histogram.abs() / close * 100.0).min(1.0))
+    }
+}
+
+/// Configuration for constructing a [`BollingerStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct BollingerConfig {
+    pub period: usize,
+    pub num_std: f64,
+}
+
+impl Default for BollingerConfig {
+    fn default() -> Self {
+        Self {
+            period: 20,
+            num_std: 2.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Example Bollinger Band mean-reversion strategy that implements [`SignalGenerator`]. A close
+/// crossing below the lower band advises entering Long, and a close crossing above the upper band
+/// advises exiting Long & entering Short.
+///
+/// The rolling mean & standard deviation are updated incrementally by a [`RollingStdDev`],
+/// rather than rescanning the window each bar.
+pub struct BollingerStrategy {
+    config: BollingerConfig,
+    std_dev: RollingStdDev,
+    prev_position: Option<BandPosition>,
+}
+
+/// Position of a close price relative to the Bollinger Bands or Keltner Channel.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+enum BandPosition {
+    Below,
+    Within,
+    Above,
+}
+
+impl SignalGenerator for BollingerStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check if it's a MarketEvent with a candle
+        let candle_close = match &market.kind {
+            DataKind::Candle(candle) => candle.close,
+            _ => return None,
+        };
+
+        // Update the rolling mean & standard deviation using the new MarketEvent Candle data
+        self.std_dev.update(candle_close);
+
+        // Determine where the close lies relative to the bands, once the window is full
+        let (lower, _, upper) = self.bands()?;
+        let position = if candle_close < lower {
+            BandPosition::Below
+        } else if candle_close > upper {
+            BandPosition::Above
+        } else {
+            BandPosition::Within
+        };
+        let prev_position = self.prev_position.replace(position);
+
+        // Generate advisory signals map if the close crossed a band
+        let signals = BollingerStrategy::generate_signals_map(prev_position, position);
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: market.time_received,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: candle_close,
+                time: market.time_exchange,
+            },
+            signals,
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+
+    fn is_warm(&self) -> bool {
+        self.bands().is_some()
+    }
+}
+
+impl BollingerStrategy {
+    /// Constructs a new [`BollingerStrategy`] component using the provided configuration struct.
+    pub fn new(config: BollingerConfig) -> Self {
+        Self {
+            config,
+            std_dev: RollingStdDev::new(config.period),
+            prev_position: None,
+        }
+    }
+
+    /// Returns the current (lower, middle, upper) Bollinger Band values, or `None` if the rolling
+    /// window is not yet full.
+    pub fn bands(&self) -> Option<(f64, f64, f64)> {
+        let mean = self.std_dev.mean()?;
+        let width = self.config.num_std * self.std_dev.value()?;
+
+        Some((mean - width, mean, mean + width))
+    }
+
+    /// Given the previous & latest [`BandPosition`] of the close, generates a map containing the
+    /// [`SignalStrength`] for each [`Decision`] under consideration.
+    fn generate_signals_map(
+        prev_position: Option<BandPosition>,
+        position: BandPosition,
+    ) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(4);
+        if prev_position == Some(position) {
+            return signals;
+        }
+
+        match position {
+            BandPosition::Below => {
+                signals.insert(Decision::EnterLong, SignalStrength(1.0));
+                signals.insert(Decision::CloseShort, SignalStrength(1.0));
+            }
+            BandPosition::Above => {
+                signals.insert(Decision::EnterShort, SignalStrength(1.0));
+                signals.insert(Decision::CloseLong, SignalStrength(1.0));
+            }
+            BandPosition::Within => {}
+        }
+        signals
+    }
+}
+
+/// Configuration for constructing a [`KeltnerStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct KeltnerConfig {
+    pub ema_period: usize,
+    pub atr_period: usize,
+    pub multiplier: f64,
+}
+
+impl Default for KeltnerConfig {
+    fn default() -> Self {
+        Self {
+            ema_period: 20,
+            atr_period: 10,
+            multiplier: 2.0,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+/// Example Keltner Channel breakout strategy that implements [`SignalGenerator`]. A close
+/// breaking out above the upper channel advises entering Long, and a close breaking out below the
+/// lower channel advises exiting Long & entering Short.
+///
+/// The channel is centred on an incrementally updated [`Ema`] of the close, with a width of the
+/// configured multiple of the Wilder smoothed [`Atr`]. The [`SignalStrength`] of a
+/// breakout scales with how far the close has broken beyond the channel, relative to the
+/// channel half-width.
+pub struct KeltnerStrategy {
+    multiplier: f64,
+    ema: Ema,
+    atr: Atr,
+    prev_position: Option<BandPosition>,
+}
+
+impl SignalGenerator for KeltnerStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check if it's a MarketEvent with a candle
+        let candle = match &market.kind {
+            DataKind::Candle(candle) => candle,
+            _ => return None,
+        };
+
+        // Update the EMA & ATR using the new MarketEvent Candle data
+        self.ema.update(candle.close);
+        self.atr.update(candle.high, candle.low, candle.close);
+
+        // Determine where the close lies relative to the channel, once both indicators are warm
+        let (lower, middle, upper) = self.channel()?;
+        let position = if candle.close < lower {
+            BandPosition::Below
+        } else if candle.close > upper {
+            BandPosition::Above
+        } else {
+            BandPosition::Within
+        };
+        let prev_position = self.prev_position.replace(position);
+
+        // Generate advisory signals map if the close broke out of the channel
+        let breakout = match position {
+            BandPosition::Below => lower - candle.close,
+            BandPosition::Within => 0.0,
+            BandPosition::Above => candle.close - upper,
+        };
+        let strength = KeltnerStrategy::calculate_signal_strength(breakout, upper - middle);
+        let signals = KeltnerStrategy::generate_signals_map(prev_position, position, strength);
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: market.time_received,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: candle.close,
+                time: market.time_exchange,
+            },
+            signals,
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+
+    fn is_warm(&self) -> bool {
+        self.channel().is_some()
+    }
+}
+
+impl KeltnerStrategy {
+    /// Constructs a new [`KeltnerStrategy`] component using the provided configuration struct.
+    pub fn new(config: KeltnerConfig) -> Self {
+        Self {
+            multiplier: config.multiplier,
+            ema: Ema::new(config.ema_period),
+            atr: Atr::new(config.atr_period),
+            prev_position: None,
+        }
+    }
+
+    /// Returns the current (lower, middle, upper) Keltner Channel values, or `None` if either
+    /// the EMA or [`Atr`] is not yet warm.
+    pub fn channel(&self) -> Option<(f64, f64, f64)> {
+        let middle = self.ema.value()?;
+        let width = self.multiplier * self.atr.value()?;
+
+        Some((middle - width, middle, middle + width))
+    }
+
+    /// Given the previous & latest [`BandPosition`] of the close, generates a map containing the
+    /// [`SignalStrength`] for each [`Decision`] under consideration.
+    fn generate_signals_map(
+        prev_position: Option<BandPosition>,
+        position: BandPosition,
+        strength: SignalStrength,
+    ) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(4);
+        if prev_position == Some(position) {
+            return signals;
+        }
+
+        match position {
+            BandPosition::Above => {
+                signals.insert(Decision::EnterLong, strength);
+                signals.insert(Decision::CloseShort, strength);
+            }
+            BandPosition::Below => {
+                signals.insert(Decision::EnterShort, strength);
+                signals.insert(Decision::CloseLong, strength);
+            }
+            BandPosition::Within => {}
+        }
+        signals
+    }
+
+    /// Calculates the [`SignalStrength`] of a breakout from the distance the close has broken
+    /// beyond the channel, where a breakout of a full channel half-width (or more) is full
+    /// strength.
+    fn calculate_signal_strength(breakout: f64, half_width: f64) -> SignalStrength {
+        if half_width > 0.0 {
+            SignalStrength((breakout / half_width).clamp(0.0, 1.0))
+        } else {
+            SignalStrength(1.0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle_at;
+    use chrono::{DateTime, Duration, Utc};
+
+    #[test]
+    fn macd_strategy_signals_on_crossovers_after_warmup() {
+        let mut strategy = MACDStrategy::new(MACDConfig {
+            fast_period: 3,
+            slow_period: 6,
+            signal_period: 3,
+        });
+
+        // Prices fall 100 -> 91, rise to 111, then fall again
+        let closes = (0..10)
+            .map(|index| 100.0 - index as f64)
+            .chain((1..=10).map(|index| 91.0 + 2.0 * index as f64))
+            .chain((1..=6).map(|index| 111.0 - 3.0 * index as f64));
+
+        let start = Utc::now();
+        let signals = closes
+            .enumerate()
+            .filter_map(|(index, close)| {
+                let time = start + Duration::minutes(index as i64);
+                strategy
+                    .generate_signal(&market_event_candle_at(time, close))
+                    .map(|signal| (index, signal.signals))
+            })
+            .collect::<Vec<_>>();
+
+        // Bar 1 crossunder is suppressed during warmup, so the first signal is the bar 10
+        // crossover, followed by the bar 20 crossunder
+        assert_eq!(signals.len(), 2);
+
+        let (index, crossover) = &signals[0];
+        assert_eq!(*index, 10);
+        assert!(crossover.contains_key(&Decision::EnterLong));
+        assert!(crossover.contains_key(&Decision::CloseShort));
+        let strength = crossover[&Decision::EnterLong].0;
+        assert!(strength > 0.0 && strength <= 1.0, "strength: {strength}");
+
+        let (index, crossunder) = &signals[1];
+        assert_eq!(*index, 20);
+        assert!(crossunder.contains_key(&Decision::CloseLong));
+        assert!(crossunder.contains_key(&Decision::EnterShort));
+    }
+
+    #[test]
+    fn bollinger_bands_match_two_pass_computation() {
+        let config = BollingerConfig::default();
+        let mut strategy = BollingerStrategy::new(config);
+
+        // Deterministic 10k candle series with a large price level to stress numerical stability
+        let mut seed: u64 = 42;
+        let closes = (0..10_000)
+            .map(|index| {
+                seed = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let noise = (seed >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+                1_000_000.0 + 500.0 * (index as f64 / 50.0).sin() + 25.0 * noise
+            })
+            .collect::<Vec<f64>>();
+
+        let start = Utc::now();
+        for (index, close) in closes.iter().enumerate() {
+            strategy.generate_signal(&market_event_candle_at(
+                start + Duration::minutes(index as i64),
+                *close,
+            ));
+
+            if index + 1 < config.period {
+                assert_eq!(strategy.bands(), None);
+                continue;
+            }
+
+            // Naive two-pass computation over the same window
+            let window = &closes[index + 1 - config.period..=index];
+            let mean = window.iter().sum::<f64>() / window.len() as f64;
+            let variance = window
+                .iter()
+                .map(|value| (value - mean).powi(2))
+                .sum::<f64>()
+                / window.len() as f64;
+            let width = config.num_std * variance.sqrt();
+
+            let (lower, middle, upper) = strategy.bands().unwrap();
+            assert!((middle - mean).abs() < 1e-6, "index {index} middle");
+            assert!(
+                ((upper - middle) - width).abs() < 1e-4,
+                "index {index} upper"
+            );
+            assert!(
+                ((middle - lower) - width).abs() < 1e-4,
+                "index {index} lower"
+            );
+        }
+    }
+
+    #[test]
+    fn bollinger_strategy_signals_on_band_crosses() {
+        let mut strategy = BollingerStrategy::new(BollingerConfig {
+            period: 5,
+            num_std: 1.0,
+        });
+
+        let start = Utc::now();
+        let closes = [100.0, 101.0, 100.0, 101.0, 100.0, 90.0, 89.0, 100.0, 112.0];
+        let signals = closes
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, close)| {
+                let time = start + Duration::minutes(index as i64);
+                strategy
+                    .generate_signal(&market_event_candle_at(time, close))
+                    .map(|signal| (index, signal.signals))
+            })
+            .collect::<Vec<_>>();
+
+        // Close crosses below the lower band at bar 5 & above the upper band at bar 8
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals[0].0, 5);
+        assert!(signals[0].1.contains_key(&Decision::EnterLong));
+        assert_eq!(signals[1].0, 8);
+        assert!(signals[1].1.contains_key(&Decision::EnterShort));
+        assert!(signals[1].1.contains_key(&Decision::CloseLong));
+    }
+
+    fn keltner_candle_event(
+        time: DateTime<Utc>,
+        high: f64,
+        low: f64,
+        close: f64,
+    ) -> MarketEvent<Instrument, DataKind> {
+        let mut market = market_event_candle_at(time, close);
+        if let DataKind::Candle(candle) = &mut market.kind {
+            candle.high = high;
+            candle.low = low;
+        }
+        market
+    }
+
+    #[test]
+    fn keltner_strategy_signals_on_channel_breakouts() {
+        let mut strategy = KeltnerStrategy::new(KeltnerConfig {
+            ema_period: 5,
+            atr_period: 3,
+            multiplier: 0.5,
+        });
+
+        // Bars ranging 99 -> 101 around a close of 100, then a breakout above & a breakdown below
+        let bars = [
+            (101.0, 99.0, 100.0),
+            (101.0, 99.0, 100.0),
+            (101.0, 99.0, 100.0),
+            (101.0, 99.0, 100.0),
+            (101.0, 99.0, 100.0),
+            (104.0, 102.0, 103.5),
+            (105.0, 103.0, 104.5),
+            (104.0, 90.0, 91.0),
+        ];
+
+        let start = Utc::now();
+        let signals = bars
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, (high, low, close))| {
+                let time = start + Duration::minutes(index as i64);
+                let signal =
+                    strategy.generate_signal(&keltner_candle_event(time, high, low, close));
+
+                // No signals until both the EMA & ATR are warm
+                assert_eq!(strategy.is_warm(), index >= 4);
+                signal.map(|signal| (index, signal.signals))
+            })
+            .collect::<Vec<_>>();
+
+        // Close breaks out above the upper channel at bar 5 & below the lower channel at bar 7
+        assert_eq!(signals.len(), 2);
+        assert_eq!(signals[0].0, 5);
+        assert!(signals[0].1.contains_key(&Decision::EnterLong));
+        assert!(signals[0].1.contains_key(&Decision::CloseShort));
+        assert_eq!(signals[1].0, 7);
+        assert!(signals[1].1.contains_key(&Decision::EnterShort));
+        assert!(signals[1].1.contains_key(&Decision::CloseLong));
+
+        // Close of 103.5 is 0.75 half-widths beyond the upper channel of 102.5
+        let long = signals[0].1[&Decision::EnterLong].0;
+        assert!((long - 0.75).abs() < 1e-10, "strength: {long}");
+
+        // Breakdowns further than a half-width beyond the channel are capped at full strength
+        assert_eq!(signals[1].1[&Decision::EnterShort], SignalStrength(1.0));
+    }
+
+    #[test]
+    fn keltner_channel_widens_after_volatility_spike() {
+        let mut strategy = KeltnerStrategy::new(KeltnerConfig {
+            ema_period: 3,
+            atr_period: 3,
+            multiplier: 2.0,
+        });
+
+        let start = Utc::now();
+        let mut width = |index: i64, high: f64, low: f64| {
+            let time = start + Duration::minutes(index);
+            strategy.generate_signal(&keltner_candle_event(time, high, low, 100.0));
+            strategy.channel().map(|(lower, _, upper)| upper - lower)
+        };
+
+        assert_eq!(width(0, 101.0, 99.0), None);
+        assert_eq!(width(1, 101.0, 99.0), None);
+
+        // Seeded with the mean true range of 2.0, so the channel is 2 * 2 * 2.0 wide
+        let calm = width(2, 101.0, 99.0).unwrap();
+        assert!((calm - 8.0).abs() < 1e-10, "width: {calm}");
+
+        // A true range of 20.0 is blended in using Wilder smoothing: (2.0 * 2 + 20.0) / 3
+        let spike = width(3, 110.0, 90.0).unwrap();
+        assert!((spike - 32.0).abs() < 1e-10, "width: {spike}");
+    }
+
+    #[test]
+    fn rsi_strategy_is_warm_exactly_after_rsi_period() {
+        let mut strategy = RSIStrategy::new(Config {
+            rsi_period: 5,
+            gap_rewarm: None,
+        });
+        assert!(!strategy.is_warm());
+
+        // Steadily falling prices generate an oversold RSI & Long signals once warm
+        let start = Utc::now();
+        let respected_signals = (0..8)
+            .filter_map(|index| {
+                let time = start + Duration::minutes(index);
+                let signal = strategy
+                    .generate_signal(&market_event_candle_at(time, 1000.0 - 10.0 * index as f64));
+
+                assert_eq!(strategy.is_warm(), index + 1 >= 5, "candle {index}");
+
+                // Caller respects the warmup flag & disregards early signals
+                signal.filter(|_| strategy.is_warm()).map(|_| index)
+            })
+            .collect::<Vec<i64>>();
+
+        assert_eq!(respected_signals, vec![4, 5, 6, 7]);
+    }
+
+    #[test]
+    fn rsi_strategy_signals_match_reference_rsi_once_warm() {
+        use ta::{indicators::RelativeStrengthIndex, Next};
+
+        let mut strategy = RSIStrategy::new(Config {
+            rsi_period: 14,
+            gap_rewarm: None,
+        });
+        let mut reference = RelativeStrengthIndex::new(14).unwrap();
+
+        // Oscillating prices cross both the oversold & overbought thresholds
+        let start = Utc::now();
+        for index in 0..300 {
+            let close = 1000.0 + 50.0 * (index as f64 / 15.0).sin() + (index % 7) as f64;
+            let signal = strategy.generate_signal(&market_event_candle_at(
+                start + Duration::minutes(index),
+                close,
+            ));
+            let expected = RSIStrategy::generate_signals_map(reference.next(close));
+
+            if !strategy.is_warm() {
+                assert!(signal.is_none(), "candle {index}");
+                continue;
+            }
+
+            let actual = signal.map(|signal| signal.signals).unwrap_or_default();
+            assert_eq!(actual, expected, "candle {index}");
+        }
+    }
+
+    #[test]
+    fn rsi_strategy_suppresses_signals_while_rewarming_after_gap() {
+        let mut strategy = RSIStrategy::new(Config {
+            rsi_period: 2,
+            gap_rewarm: Some(GapRewarmConfig {
+                max_gap: Duration::minutes(5),
+                warmup_period: 3,
+            }),
+        });
+
+        // Steadily falling prices generate an oversold RSI & Long signals
+        let start = Utc::now();
+        let mut close = 1000.0;
+        let mut next_signal = |strategy: &mut RSIStrategy, time: DateTime<Utc>| {
+            close -= 10.0;
+            strategy.generate_signal(&market_event_candle_at(time, close))
+        };
+
+        for minute in 0..5 {
+            next_signal(&mut strategy, start + Duration::minutes(minute));
+        }
+        assert!(next_signal(&mut strategy, start + Duration::minutes(5)).is_some());
+
+        // Large gap in market data resets the RSI, so signals are suppressed during re-warm
+        let after_gap = start + Duration::hours(2);
+        for minute in 0..3 {
+            assert!(
+                next_signal(&mut strategy, after_gap + Duration::minutes(minute)).is_none(),
+                "signal generated {minute} minutes after gap while re-warming"
+            );
+        }
+
+        // Signals resume once the re-warm period has elapsed
+        assert!(next_signal(&mut strategy, after_gap + Duration::minutes(3)).is_some());
+    }
+}
@@ -0,0 +1,88 @@
+use crate::statistic::{de_duration_from_secs, se_duration_as_secs};
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Configuration for constructing a [`GapRewarm`] via the new() constructor method.
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct GapRewarmConfig {
+    /// Largest gap between consecutive market data timestamps before indicator state is
+    /// considered stale.
+    #[serde(
+        deserialize_with = "de_duration_from_secs",
+        serialize_with = "se_duration_as_secs"
+    )]
+    pub max_gap: Duration,
+    /// Number of market data updates (including the first one after the gap) during which
+    /// signals are suppressed while indicators re-warm.
+    pub warmup_period: usize,
+}
+
+/// Detects gaps in market data larger than the configured threshold, and tracks the re-warm
+/// period that follows so stale indicator state does not produce spurious signals post-gap.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct GapRewarm {
+    pub config: GapRewarmConfig,
+    pub last_time: Option<DateTime<Utc>>,
+    pub remaining: usize,
+}
+
+impl GapRewarm {
+    /// Constructs a new [`GapRewarm`] using the provided [`GapRewarmConfig`].
+    pub fn new(config: GapRewarmConfig) -> Self {
+        Self {
+            config,
+            last_time: None,
+            remaining: 0,
+        }
+    }
+
+    /// Updates the [`GapRewarm`] with the timestamp of the next market data update. Returns true
+    /// if a gap larger than the configured threshold was detected, in which case indicators should
+    /// be reset by the caller.
+    pub fn update(&mut self, time: DateTime<Utc>) -> bool {
+        let gap = self
+            .last_time
+            .replace(time)
+            .is_some_and(|last_time| time - last_time > self.config.max_gap);
+
+        self.remaining = match gap {
+            true => self.config.warmup_period,
+            false => self.remaining.saturating_sub(1),
+        };
+
+        gap
+    }
+
+    /// Determines if indicators are still re-warming after a gap, and signals should be
+    /// suppressed.
+    pub fn is_warming_up(&self) -> bool {
+        self.remaining > 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gap_rewarm_update() {
+        let start = Utc::now();
+        let mut rewarm = GapRewarm::new(GapRewarmConfig {
+            max_gap: Duration::minutes(5),
+            warmup_period: 2,
+        });
+
+        // No gap with regular updates
+        assert!(!rewarm.update(start));
+        assert!(!rewarm.update(start + Duration::minutes(1)));
+        assert!(!rewarm.is_warming_up());
+
+        // Gap detected, so the post-gap update & the next one are re-warming
+        assert!(rewarm.update(start + Duration::hours(1)));
+        assert!(rewarm.is_warming_up());
+        assert!(!rewarm.update(start + Duration::hours(1) + Duration::minutes(1)));
+        assert!(rewarm.is_warming_up());
+        assert!(!rewarm.update(start + Duration::hours(1) + Duration::minutes(2)));
+        assert!(!rewarm.is_warming_up());
+    }
+}
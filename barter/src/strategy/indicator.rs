@@ -0,0 +1,445 @@
+use crate::statistic::algorithm::welford_online;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+/// Simple moving average (SMA) of the latest `period` values.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Sma {
+    pub period: usize,
+    window: VecDeque<f64>,
+    sum: f64,
+}
+
+impl Sma {
+    /// Constructs a new [`Sma`] of the provided period.
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            sum: 0.0,
+        }
+    }
+
+    /// Updates the [`Sma`] with the next value, returning the latest average once `period`
+    /// values have been ingested.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+        if self.window.len() == self.period {
+            self.sum -= self.window.pop_front().unwrap_or_default();
+        }
+        self.window.push_back(value);
+        self.sum += value;
+
+        self.value()
+    }
+
+    /// Returns the latest average, or `None` if fewer than `period` values have been ingested.
+    pub fn value(&self) -> Option<f64> {
+        (self.period > 0 && self.window.len() == self.period).then(|| self.sum / self.period as f64)
+    }
+}
+
+/// Exponential moving average (EMA) with a smoothing factor of `2 / (period + 1)`, seeded with
+/// the SMA of the first `period` values.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Ema {
+    pub period: usize,
+    /// Sum of the first `period` values, used to seed the EMA with their SMA.
+    seed_sum: f64,
+    samples: usize,
+    value: Option<f64>,
+}
+
+impl Ema {
+    /// Constructs a new [`Ema`] of the provided period.
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            seed_sum: 0.0,
+            samples: 0,
+            value: None,
+        }
+    }
+
+    /// Updates the [`Ema`] with the next value, returning the latest average once `period`
+    /// values have been ingested.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+        self.samples = self.samples.saturating_add(1);
+
+        match &mut self.value {
+            Some(ema) => {
+                let alpha = 2.0 / (self.period as f64 + 1.0);
+                *ema += alpha * (value - *ema);
+            }
+            None => {
+                self.seed_sum += value;
+                if self.samples == self.period {
+                    self.value = Some(self.seed_sum / self.period as f64);
+                }
+            }
+        }
+
+        self.value
+    }
+
+    /// Returns the latest average, or `None` if fewer than `period` values have been ingested.
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+/// Relative Strength Index (RSI) oscillator between 0 & 100.
+///
+/// Average gains & losses are smoothed with exponential averages of smoothing factor
+/// `2 / (period + 1)` seeded by the first value, matching the `ta` crate RSI the example
+/// strategies were originally built on.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Rsi {
+    pub period: usize,
+    prev_value: Option<f64>,
+    avg_gain: f64,
+    avg_loss: f64,
+    samples: usize,
+}
+
+impl Rsi {
+    /// Constructs a new [`Rsi`] of the provided period.
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            prev_value: None,
+            avg_gain: 0.0,
+            avg_loss: 0.0,
+            samples: 0,
+        }
+    }
+
+    /// Updates the [`Rsi`] with the next value, returning the latest RSI once `period` values
+    /// have been ingested.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+        self.samples = self.samples.saturating_add(1);
+
+        match self.prev_value.replace(value) {
+            // Seed both averages equally, so the first RSI is a neutral 50
+            None => {
+                self.avg_gain = 0.1;
+                self.avg_loss = 0.1;
+            }
+            Some(prev_value) => {
+                let alpha = 2.0 / (self.period as f64 + 1.0);
+                let change = value - prev_value;
+                self.avg_gain += alpha * (change.max(0.0) - self.avg_gain);
+                self.avg_loss += alpha * ((-change).max(0.0) - self.avg_loss);
+            }
+        }
+
+        self.value()
+    }
+
+    /// Resets the [`Rsi`] to it's initial state, requiring `period` values to re-warm.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.period);
+    }
+
+    /// Returns the latest RSI, or `None` if fewer than `period` values have been ingested.
+    pub fn value(&self) -> Option<f64> {
+        (self.period > 0 && self.samples >= self.period)
+            .then(|| 100.0 * self.avg_gain / (self.avg_gain + self.avg_loss))
+    }
+}
+
+/// Average True Range (ATR) volatility estimate, updated per candle using Wilder's smoothing.
+///
+/// The initial ATR is the mean of the first `period` true ranges, after which each true range
+/// is blended in as `ATR = (ATR * (period - 1) + TR) / period`.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Atr {
+    pub period: usize,
+    prev_close: Option<f64>,
+    samples: usize,
+    /// Sum of the true ranges used to seed the initial ATR.
+    seed_sum: f64,
+    value: Option<f64>,
+}
+
+impl Atr {
+    /// Constructs a new [`Atr`] of the provided period.
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            prev_close: None,
+            samples: 0,
+            seed_sum: 0.0,
+            value: None,
+        }
+    }
+
+    /// Updates the ATR with the next candle high, low & close, returning the latest ATR once it
+    /// is warm.
+    pub fn update(&mut self, high: f64, low: f64, close: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+
+        // True range accounts for any gap from the previous close
+        let true_range = match self.prev_close.replace(close) {
+            Some(prev_close) => (high - low)
+                .max((high - prev_close).abs())
+                .max((low - prev_close).abs()),
+            None => high - low,
+        };
+        self.samples = self.samples.saturating_add(1);
+
+        let period = self.period as f64;
+        self.value = match self.value {
+            Some(atr) => Some((atr * (period - 1.0) + true_range) / period),
+            None => {
+                self.seed_sum += true_range;
+                (self.samples == self.period).then(|| self.seed_sum / period)
+            }
+        };
+
+        self.value
+    }
+
+    /// Returns the latest ATR, or `None` if fewer than `period` candles have been ingested.
+    pub fn value(&self) -> Option<f64> {
+        self.value
+    }
+}
+
+/// Population standard deviation of the latest `period` values, updated incrementally using
+/// the Welford Online algorithm rather than rescanning the window each update.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RollingStdDev {
+    pub period: usize,
+    window: VecDeque<f64>,
+    mean: f64,
+    recurrence_relation_m: f64,
+}
+
+impl RollingStdDev {
+    /// Constructs a new [`RollingStdDev`] of the provided period.
+    pub fn new(period: usize) -> Self {
+        Self {
+            period,
+            window: VecDeque::with_capacity(period),
+            mean: 0.0,
+            recurrence_relation_m: 0.0,
+        }
+    }
+
+    /// Updates the [`RollingStdDev`] with the next value, returning the latest standard
+    /// deviation once `period` values have been ingested.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        if self.period == 0 {
+            return None;
+        }
+
+        let prev_mean = self.mean;
+
+        if self.window.len() < self.period {
+            self.window.push_back(value);
+            self.mean = welford_online::calculate_mean(prev_mean, value, self.window.len() as f64);
+            self.recurrence_relation_m = welford_online::calculate_recurrence_relation_m(
+                self.recurrence_relation_m,
+                prev_mean,
+                value,
+                self.mean,
+            );
+        } else if let Some(removed) = self.window.pop_front() {
+            self.window.push_back(value);
+            self.mean = welford_online::calculate_rolling_mean(
+                prev_mean,
+                value,
+                removed,
+                self.period as f64,
+            );
+            self.recurrence_relation_m = welford_online::calculate_rolling_recurrence_relation_m(
+                self.recurrence_relation_m,
+                prev_mean,
+                value,
+                self.mean,
+                removed,
+            );
+        }
+
+        self.value()
+    }
+
+    /// Returns the latest standard deviation, or `None` if fewer than `period` values have been
+    /// ingested.
+    pub fn value(&self) -> Option<f64> {
+        self.is_warm().then(|| {
+            welford_online::calculate_population_variance(
+                self.recurrence_relation_m,
+                self.window.len() as u64,
+            )
+            .sqrt()
+        })
+    }
+
+    /// Returns the mean of the latest `period` values, or `None` if fewer than `period` values
+    /// have been ingested.
+    pub fn mean(&self) -> Option<f64> {
+        self.is_warm().then_some(self.mean)
+    }
+
+    fn is_warm(&self) -> bool {
+        self.period > 0 && self.window.len() == self.period
+    }
+}
+
+/// Moving Average Convergence Divergence (MACD) line, being the difference between a fast &
+/// slow [`Ema`].
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct MacdLine {
+    fast: Ema,
+    slow: Ema,
+}
+
+impl MacdLine {
+    /// Constructs a new [`MacdLine`] from a fast & slow [`Ema`] of the provided periods.
+    pub fn new(fast_period: usize, slow_period: usize) -> Self {
+        Self {
+            fast: Ema::new(fast_period),
+            slow: Ema::new(slow_period),
+        }
+    }
+
+    /// Updates the [`MacdLine`] with the next value, returning the latest MACD once both the
+    /// fast & slow [`Ema`]s are warm.
+    pub fn update(&mut self, value: f64) -> Option<f64> {
+        self.fast.update(value);
+        self.slow.update(value);
+        self.value()
+    }
+
+    /// Returns the latest MACD, or `None` if either [`Ema`] is not yet warm.
+    pub fn value(&self) -> Option<f64> {
+        Some(self.fast.value()? - self.slow.value()?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ta::{indicators::RelativeStrengthIndex, Next};
+
+    fn assert_values(actual: Vec<Option<f64>>, expected: Vec<Option<f64>>) {
+        assert_eq!(actual.len(), expected.len());
+        for (index, (actual, expected)) in actual.into_iter().zip(expected).enumerate() {
+            match (actual, expected) {
+                (Some(actual), Some(expected)) => {
+                    assert!((actual - expected).abs() < 1e-10, "index {index}: {actual}")
+                }
+                (actual, expected) => assert_eq!(actual, expected, "index {index}"),
+            }
+        }
+    }
+
+    #[test]
+    fn sma_averages_latest_period_values_once_warm() {
+        let mut sma = Sma::new(3);
+        let actual = [1.0, 2.0, 3.0, 4.0, 8.0].map(|value| sma.update(value));
+
+        assert_values(
+            actual.to_vec(),
+            vec![None, None, Some(2.0), Some(3.0), Some(5.0)],
+        );
+        assert_eq!(Sma::new(0).update(1.0), None);
+    }
+
+    #[test]
+    fn ema_is_seeded_with_sma_of_first_period_values() {
+        let mut ema = Ema::new(3);
+        let actual = [1.0, 2.0, 3.0, 4.0, 6.0].map(|value| ema.update(value));
+
+        // Seeded with SMA 2.0, then smoothed with alpha 0.5
+        assert_values(
+            actual.to_vec(),
+            vec![None, None, Some(2.0), Some(3.0), Some(4.5)],
+        );
+    }
+
+    #[test]
+    fn rsi_matches_reference_implementation_once_warm() {
+        let mut rsi = Rsi::new(14);
+        let mut reference = RelativeStrengthIndex::new(14).unwrap();
+
+        let mut seed: u64 = 7;
+        for index in 0..500 {
+            seed = seed
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let noise = (seed >> 11) as f64 / (1u64 << 53) as f64 - 0.5;
+            let close = 100.0 + 10.0 * (index as f64 / 20.0).sin() + noise;
+
+            let expected = reference.next(close);
+            match rsi.update(close) {
+                None => assert!(index < 13, "index {index} not warm"),
+                Some(actual) => {
+                    assert!(index >= 13, "index {index} warm early");
+                    assert!((actual - expected).abs() < 1e-10, "index {index}");
+                }
+            }
+        }
+
+        // Steadily rising prices are overbought
+        let mut rsi = Rsi::new(3);
+        let actual = (0..10).filter_map(|index| rsi.update(100.0 + index as f64));
+        assert!(actual.last().unwrap() > 99.0);
+    }
+
+    #[test]
+    fn atr_uses_wilder_smoothing_after_mean_seed() {
+        let mut atr = Atr::new(3);
+        let bars = [
+            (101.0, 99.0, 100.0),
+            (103.0, 100.0, 102.0),
+            (102.0, 98.0, 99.0),
+            (115.0, 105.0, 110.0),
+        ];
+        let actual = bars.map(|(high, low, close)| atr.update(high, low, close));
+
+        // True ranges of 2, 3 & 4 seed an ATR of 3, then a gapped true range of 16 (115 - 99)
+        assert_values(
+            actual.to_vec(),
+            vec![None, None, Some(3.0), Some((3.0 * 2.0 + 16.0) / 3.0)],
+        );
+    }
+
+    #[test]
+    fn rolling_std_dev_matches_population_std_dev_of_window() {
+        let mut std_dev = RollingStdDev::new(8);
+        let values = [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0, 4.0];
+        let actual = values.map(|value| std_dev.update(value));
+
+        assert!(actual[..7].iter().all(Option::is_none));
+        assert!((actual[7].unwrap() - 2.0).abs() < 1e-10);
+        assert!((std_dev.mean().unwrap() - 5.25).abs() < 1e-10);
+
+        // Window [4, 4, 4, 5, 5, 7, 9, 4] has mean 5.25 & population variance 2.9375
+        assert!((actual[8].unwrap() - 2.9375_f64.sqrt()).abs() < 1e-10);
+    }
+
+    #[test]
+    fn macd_line_is_fast_ema_less_slow_ema_once_both_warm() {
+        let mut macd = MacdLine::new(2, 3);
+        let actual = [1.0, 2.0, 3.0, 4.0, 5.0].map(|value| macd.update(value));
+
+        // Fast EMA: 1.5, 2.5, 3.5, 4.5 & slow EMA: 2.0, 3.0, 4.0
+        assert_values(
+            actual.to_vec(),
+            vec![None, None, Some(0.5), Some(0.5), Some(0.5)],
+        );
+    }
+}
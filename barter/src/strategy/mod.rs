@@ -1,17 +1,58 @@
-use crate::data::MarketMeta;
+use crate::{data::MarketMeta, portfolio::position::CloseReason};
 use barter_data::event::{DataKind, MarketEvent};
 use barter_instrument::{exchange::ExchangeId, instrument::Instrument, market::Market};
+use barter_integration::Side;
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use smol_str::SmolStr;
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+};
 
-/// Barter example RSI strategy [`SignalGenerator`] implementation.
+/// Weighted combination of multiple [`SignalGenerator`] strategies into a single netted
+/// [`Signal`].
+pub mod composite;
+
+/// Barter example RSI, MACD, Bollinger Band & Keltner Channel strategy [`SignalGenerator`]
+/// implementations.
 pub mod example;
 
+/// Detection of market data gaps & the re-warm policy for indicators that follows them.
+pub mod gap;
+
+/// Reusable incremental indicator primitives (eg/ [`Ema`](indicator::Ema),
+/// [`Rsi`](indicator::Rsi)) shared by strategies & risk managers.
+pub mod indicator;
+
+/// Simple & exponential moving average crossover strategy [`SignalGenerator`] implementation.
+pub mod moving_average;
+
+/// Random entry & exit strategy [`SignalGenerator`] implementation, used to benchmark a real
+/// strategy's edge against random timing.
+pub mod random;
+
+/// [`SignalGenerator`] wrapper attributing it's [`Signal`]s to a [`StrategyId`].
+pub mod tagged;
+
+/// Multi-timeframe strategies consuming base timeframe market events alongside their candles
+/// resampled into higher timeframes (eg/ a 1h trend filter over 5m entries).
+pub mod timeframe;
+
+/// Trade-rate indicator derived from consecutive [`Ticker`](barter_data::subscription::ticker::Ticker)
+/// updates, used by strategies to gauge market activity.
+pub mod trade_rate;
+
 /// May generate an advisory [`Signal`] as a result of analysing an input [`MarketEvent`].
 pub trait SignalGenerator {
     /// Optionally return a [`Signal`] given input [`MarketEvent`].
     fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal>;
+
+    /// Determines if the strategy has ingested enough [`MarketEvent`]s to generate meaningful
+    /// [`Signal`]s. Callers should disregard any [`Signal`] generated while this is false.
+    fn is_warm(&self) -> bool {
+        true
+    }
 }
 
 /// Advisory [`Signal`] for a [`Market`] detailing the [`SignalStrength`] associated with each
@@ -24,43 +65,121 @@ pub struct Signal {
     pub signals: HashMap<Decision, SignalStrength>,
     /// Metadata propagated from the [`MarketEvent`] that yielded this [`Signal`].
     pub market_meta: MarketMeta,
+    /// [`StrategyId`] of the strategy that generated this [`Signal`], propagated to the
+    /// resulting orders, fills & [`Position`](crate::portfolio::position::Position)s.
+    #[serde(default)]
+    pub strategy_id: StrategyId,
+    /// Fraction of the open [`Position`](crate::portfolio::position::Position) to close if this
+    /// [`Signal`] advises an exit (eg/ 0.5 to close half). `None` closes the full
+    /// [`Position`](crate::portfolio::position::Position), as does any fraction of 1.0 or above.
+    #[serde(default)]
+    pub close_fraction: Option<f64>,
+}
+
+/// Tag attributing a [`Signal`], and the orders, fills & positions it results in, to the strategy
+/// that generated it. Defaults to [`StrategyId::UNKNOWN`] for untagged [`Signal`]s.
+#[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub struct StrategyId(pub SmolStr);
+
+impl StrategyId {
+    pub const UNKNOWN: &'static str = "unknown";
+
+    /// Constructs a new [`StrategyId`] from the provided tag.
+    pub fn new<S: AsRef<str>>(id: S) -> Self {
+        Self(SmolStr::new(id))
+    }
+}
+
+impl Default for StrategyId {
+    fn default() -> Self {
+        Self::new(Self::UNKNOWN)
+    }
+}
+
+impl Display for StrategyId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<&str> for StrategyId {
+    fn from(id: &str) -> Self {
+        Self::new(id)
+    }
 }
 
 /// Describes the type of advisory signal the strategy is endorsing.
+///
+/// Entries open (or scale into) a [`Position`](crate::portfolio::position::Position) in their
+/// direction, whereas exits are only actioned against an open
+/// [`Position`](crate::portfolio::position::Position) of the side they close (eg/ a
+/// [`Decision::CloseLong`] when flat or short generates no order).
 #[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
 pub enum Decision {
-    Long,
+    /// Enter (or scale into) a long Position with a buy order.
+    #[serde(alias = "Long")]
+    EnterLong,
+    /// Close an open long Position with a sell order.
     CloseLong,
-    Short,
+    /// Enter (or scale into) a short Position with a sell order.
+    #[serde(alias = "Short")]
+    EnterShort,
+    /// Close an open short Position with a buy order.
     CloseShort,
 }
 
 impl Default for Decision {
     fn default() -> Self {
-        Self::Long
+        Self::EnterLong
     }
 }
 
 impl Decision {
-    /// Determines if a [`Decision`] is Long.
+    /// Determines if a [`Decision`] is EnterLong.
     pub fn is_long(&self) -> bool {
-        matches!(self, Decision::Long)
+        matches!(self, Decision::EnterLong)
     }
 
-    /// Determines if a [`Decision`] is Short.
+    /// Determines if a [`Decision`] is EnterShort.
     pub fn is_short(&self) -> bool {
-        matches!(self, Decision::Short)
+        matches!(self, Decision::EnterShort)
     }
 
-    /// Determines if a [`Decision`] is an entry (long or short).
+    /// Determines if a [`Decision`] is an entry (enter_long or enter_short).
     pub fn is_entry(&self) -> bool {
-        matches!(self, Decision::Short | Decision::Long)
+        matches!(self, Decision::EnterShort | Decision::EnterLong)
     }
 
     /// Determines if a [`Decision`] is an exit (close_long or close_short).
     pub fn is_exit(&self) -> bool {
         matches!(self, Decision::CloseLong | Decision::CloseShort)
     }
+
+    /// Returns the order [`Side`] used to action the [`Decision`].
+    pub fn side(&self) -> Side {
+        match self {
+            Decision::EnterLong | Decision::CloseShort => Side::Buy,
+            Decision::EnterShort | Decision::CloseLong => Side::Sell,
+        }
+    }
+
+    /// Returns the [`Side`] of the Position the [`Decision`] enters or exits (ie/ [`Side::Buy`]
+    /// for long Positions & [`Side::Sell`] for short Positions).
+    pub fn position_side(&self) -> Side {
+        match self {
+            Decision::EnterLong | Decision::CloseLong => Side::Buy,
+            Decision::EnterShort | Decision::CloseShort => Side::Sell,
+        }
+    }
+
+    /// Determines if a [`Decision`] is the exit of an open Position with the provided
+    /// [`Side`] (ie/ CloseLong of a long Position, or CloseShort of a short Position).
+    pub fn closes(&self, position_side: Side) -> bool {
+        matches!(
+            (self, position_side),
+            (Decision::CloseLong, Side::Buy) | (Decision::CloseShort, Side::Sell)
+        )
+    }
 }
 
 /// Strength of an advisory [`Signal`] decision produced by [`SignalGenerator`] strategy.
@@ -68,12 +187,26 @@ impl Decision {
 pub struct SignalStrength(pub f64);
 
 /// Force exit Signal produced after an [`Engine`](crate::engine::Engine) receives a
-/// [`Command::ExitPosition`](crate::engine::Command) from an external source.
+/// [`Command::ExitPosition`](crate::engine::Command) from an external source, or when a
+/// [`Trader`](crate::engine::trader::Trader) configured to close positions on finish runs out of
+/// market data.
 #[derive(Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
 pub struct SignalForceExit {
     pub time: DateTime<Utc>,
     pub exchange: ExchangeId,
     pub instrument: Instrument,
+    /// Flag indicating there is no market data to price the exit, so the
+    /// [`Position`](crate::portfolio::position::Position) is closed at it's last mark price.
+    #[serde(default)]
+    pub synthetic: bool,
+    /// [`CloseReason`] tagged on the [`Position`](crate::portfolio::position::Position) exited.
+    #[serde(default = "default_force_exit_reason")]
+    pub reason: CloseReason,
+}
+
+/// Default [`SignalForceExit`] [`CloseReason`] of an externally commanded exit.
+fn default_force_exit_reason() -> CloseReason {
+    CloseReason::ForceExit
 }
 
 impl<M> From<M> for SignalForceExit
@@ -99,6 +232,8 @@ impl SignalForceExit {
             time: Utc::now(),
             exchange: exchange.into(),
             instrument: instrument.into(),
+            synthetic: false,
+            reason: CloseReason::ForceExit,
         }
     }
 }
@@ -109,31 +244,31 @@ mod tests {
 
     #[test]
     fn should_return_decision_is_long() {
-        let decision = Decision::Long;
+        let decision = Decision::EnterLong;
         assert_eq!(decision.is_long(), true)
     }
 
     #[test]
     fn should_return_decision_is_not_long() {
-        let decision = Decision::Short;
+        let decision = Decision::EnterShort;
         assert_eq!(decision.is_long(), false)
     }
 
     #[test]
     fn should_return_decision_is_short() {
-        let decision = Decision::Short;
+        let decision = Decision::EnterShort;
         assert_eq!(decision.is_short(), true)
     }
 
     #[test]
     fn should_return_decision_is_not_short() {
-        let decision = Decision::Long;
+        let decision = Decision::EnterLong;
         assert_eq!(decision.is_short(), false)
     }
 
     #[test]
     fn should_return_decision_is_entry() {
-        let decision = Decision::Long;
+        let decision = Decision::EnterLong;
         assert_eq!(decision.is_entry(), true)
     }
 
@@ -151,7 +286,47 @@ mod tests {
 
     #[test]
     fn should_return_decision_is_not_exit() {
-        let decision = Decision::Long;
+        let decision = Decision::EnterLong;
         assert_eq!(decision.is_exit(), false)
     }
+
+    #[test]
+    fn should_return_decision_order_side() {
+        assert_eq!(Decision::EnterLong.side(), Side::Buy);
+        assert_eq!(Decision::CloseLong.side(), Side::Sell);
+        assert_eq!(Decision::EnterShort.side(), Side::Sell);
+        assert_eq!(Decision::CloseShort.side(), Side::Buy);
+    }
+
+    #[test]
+    fn should_return_decision_position_side() {
+        assert_eq!(Decision::EnterLong.position_side(), Side::Buy);
+        assert_eq!(Decision::CloseLong.position_side(), Side::Buy);
+        assert_eq!(Decision::EnterShort.position_side(), Side::Sell);
+        assert_eq!(Decision::CloseShort.position_side(), Side::Sell);
+    }
+
+    #[test]
+    fn should_return_decision_closes_only_position_of_opposite_side() {
+        assert!(Decision::CloseLong.closes(Side::Buy));
+        assert!(!Decision::CloseLong.closes(Side::Sell));
+        assert!(Decision::CloseShort.closes(Side::Sell));
+        assert!(!Decision::CloseShort.closes(Side::Buy));
+        assert!(!Decision::EnterLong.closes(Side::Sell));
+    }
+
+    #[test]
+    fn should_deserialise_legacy_decision_names() {
+        let decisions: Vec<Decision> =
+            serde_json::from_str(r#"["Long", "Short", "EnterLong", "CloseShort"]"#).unwrap();
+        assert_eq!(
+            decisions,
+            vec![
+                Decision::EnterLong,
+                Decision::EnterShort,
+                Decision::EnterLong,
+                Decision::CloseShort
+            ]
+        );
+    }
 }
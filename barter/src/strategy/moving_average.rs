@@ -0,0 +1,276 @@
+use super::{
+    indicator::{Ema, Sma},
+    Decision, Signal, SignalGenerator, SignalStrength, StrategyId,
+};
+use crate::data::MarketMeta;
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Kind of moving average used by a [`MovingAverageCrossStrategy`].
+#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Deserialize, Serialize)]
+pub enum MovingAverageType {
+    /// Simple moving average.
+    Sma,
+    /// Exponential moving average, seeded with the SMA of the first `period` values.
+    Ema,
+}
+
+/// Configuration for constructing a [`MovingAverageCrossStrategy`] via the new() constructor
+/// method.
+#[derive(Copy, Clone, Eq, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    pub fast_period: usize,
+    pub slow_period: usize,
+    pub ma_type: MovingAverageType,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            fast_period: 10,
+            slow_period: 30,
+            ma_type: MovingAverageType::Ema,
+        }
+    }
+}
+
+/// Incrementally updated moving average of a fixed period, delegating to the [`Sma`] or [`Ema`]
+/// indicator of it's [`MovingAverageType`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum MovingAverage {
+    Sma(Sma),
+    Ema(Ema),
+}
+
+impl MovingAverage {
+    /// Constructs a new [`MovingAverage`] of the provided [`MovingAverageType`] & period.
+    pub fn new(ma_type: MovingAverageType, period: usize) -> Self {
+        match ma_type {
+            MovingAverageType::Sma => Self::Sma(Sma::new(period)),
+            MovingAverageType::Ema => Self::Ema(Ema::new(period)),
+        }
+    }
+
+    /// Updates the [`MovingAverage`] with the next value, returning the latest average once
+    /// `period` values have been ingested.
+    pub fn next(&mut self, input: f64) -> Option<f64> {
+        match self {
+            Self::Sma(sma) => sma.update(input),
+            Self::Ema(ema) => ema.update(input),
+        }
+    }
+
+    /// Returns the latest average, or `None` if fewer than `period` values have been ingested.
+    pub fn value(&self) -> Option<f64> {
+        match self {
+            Self::Sma(sma) => sma.value(),
+            Self::Ema(ema) => ema.value(),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+/// Moving average crossover strategy that implements [`SignalGenerator`]. A golden cross of the
+/// fast average above the slow average advises entering Long, and a death cross below advises
+/// exiting Long & entering Short.
+pub struct MovingAverageCrossStrategy {
+    fast: MovingAverage,
+    slow: MovingAverage,
+    /// Previous normalised spread between the fast & slow averages.
+    prev_spread: Option<f64>,
+}
+
+impl SignalGenerator for MovingAverageCrossStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Check if it's a MarketEvent with a candle
+        let candle_close = match &market.kind {
+            DataKind::Candle(candle) => candle.close,
+            _ => return None,
+        };
+
+        // Update both moving averages using the new MarketEvent Candle data
+        let fast = self.fast.next(candle_close);
+        let slow = self.slow.next(candle_close);
+
+        // No signals until both moving averages have warmed up
+        let spread = (fast? - slow?) / slow?;
+        let prev_spread = self.prev_spread.replace(spread)?;
+
+        // Generate advisory signals map from any crossover
+        let signals = MovingAverageCrossStrategy::generate_signals_map(prev_spread, spread);
+
+        // If signals map is empty, return no SignalEvent
+        if signals.is_empty() {
+            return None;
+        }
+
+        Some(Signal {
+            time: market.time_received,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            market_meta: MarketMeta {
+                close: candle_close,
+                time: market.time_exchange,
+            },
+            signals,
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+
+    fn is_warm(&self) -> bool {
+        self.prev_spread.is_some()
+    }
+}
+
+impl MovingAverageCrossStrategy {
+    /// Constructs a new [`MovingAverageCrossStrategy`] component using the provided
+    /// configuration struct.
+    pub fn new(config: Config) -> Self {
+        Self {
+            fast: MovingAverage::new(config.ma_type, config.fast_period),
+            slow: MovingAverage::new(config.ma_type, config.slow_period),
+            prev_spread: None,
+        }
+    }
+
+    /// Given the previous & latest normalised spread between the fast & slow averages,
+    /// generates a map containing the [`SignalStrength`] for each [`Decision`] under
+    /// consideration.
+    fn generate_signals_map(prev_spread: f64, spread: f64) -> HashMap<Decision, SignalStrength> {
+        let mut signals = HashMap::with_capacity(4);
+        let strength = MovingAverageCrossStrategy::calculate_signal_strength(spread);
+
+        // Golden cross: fast average crosses above the slow average
+        if prev_spread <= 0.0 && spread > 0.0 {
+            signals.insert(Decision::EnterLong, strength);
+            signals.insert(Decision::CloseShort, strength);
+        }
+
+        // Death cross: fast average crosses below the slow average
+        if prev_spread >= 0.0 && spread < 0.0 {
+            signals.insert(Decision::CloseLong, strength);
+            signals.insert(Decision::EnterShort, strength);
+        }
+
+        signals
+    }
+
+    /// Calculates the [`SignalStrength`] of a crossover from the normalised spread between the
+    /// averages, where a spread of 1% of the slow average (or more) is full strength.
+    fn calculate_signal_strength(spread: f64) -> SignalStrength {
+        SignalStrength((spread.abs() * 100.0).min(1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_candle_at;
+    use chrono::{Duration, Utc};
+
+    /// Reference EMA over a full series, seeded with the SMA of the first `period` values.
+    fn reference_ema(values: &[f64], period: usize) -> Vec<Option<f64>> {
+        let alpha = 2.0 / (period as f64 + 1.0);
+        let mut emas = vec![None; values.len()];
+        if values.len() < period {
+            return emas;
+        }
+
+        let mut ema = values[..period].iter().sum::<f64>() / period as f64;
+        emas[period - 1] = Some(ema);
+        for index in period..values.len() {
+            ema = alpha * values[index] + (1.0 - alpha) * ema;
+            emas[index] = Some(ema);
+        }
+        emas
+    }
+
+    #[test]
+    fn ema_matches_reference_implementation() {
+        let values = (0..500)
+            .map(|index| 100.0 + 10.0 * (index as f64 / 7.0).sin() + index as f64 * 0.1)
+            .collect::<Vec<f64>>();
+
+        for period in [1, 5, 20] {
+            let mut ema = MovingAverage::new(MovingAverageType::Ema, period);
+            let actual = values
+                .iter()
+                .map(|value| ema.next(*value))
+                .collect::<Vec<_>>();
+
+            for (index, (actual, expected)) in actual
+                .into_iter()
+                .zip(reference_ema(&values, period))
+                .enumerate()
+            {
+                match (actual, expected) {
+                    (Some(actual), Some(expected)) => assert!(
+                        (actual - expected).abs() < 1e-9,
+                        "period {period} index {index}: {actual} != {expected}"
+                    ),
+                    (None, None) => {}
+                    _ => panic!("period {period} index {index}: warmup mismatch"),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sma_averages_rolling_window() {
+        let mut sma = MovingAverage::new(MovingAverageType::Sma, 3);
+        let actual = [1.0, 2.0, 3.0, 4.0, 8.0]
+            .into_iter()
+            .map(|value| sma.next(value))
+            .collect::<Vec<_>>();
+
+        assert_eq!(actual, vec![None, None, Some(2.0), Some(3.0), Some(5.0)]);
+    }
+
+    #[test]
+    fn moving_average_cross_strategy_signals_on_crossovers_after_warmup() {
+        for ma_type in [MovingAverageType::Sma, MovingAverageType::Ema] {
+            let mut strategy = MovingAverageCrossStrategy::new(Config {
+                fast_period: 2,
+                slow_period: 4,
+                ma_type,
+            });
+
+            // Prices fall 100 -> 91, rise to 111, then fall again
+            let closes = (0..10)
+                .map(|index| 100.0 - index as f64)
+                .chain((1..=10).map(|index| 91.0 + 2.0 * index as f64))
+                .chain((1..=6).map(|index| 111.0 - 3.0 * index as f64));
+
+            let start = Utc::now();
+            let signals = closes
+                .enumerate()
+                .filter_map(|(index, close)| {
+                    let time = start + Duration::minutes(index as i64);
+                    let signal = strategy.generate_signal(&market_event_candle_at(time, close));
+
+                    // No signals while warming up
+                    assert_eq!(strategy.is_warm(), index >= 3, "{ma_type:?} bar {index}");
+                    signal.map(|signal| (index, signal.signals))
+                })
+                .collect::<Vec<_>>();
+
+            // Fast average crosses the slow average on the second bar of the rally & of the fall
+            assert_eq!(signals.len(), 2, "{ma_type:?}");
+
+            let (index, golden) = &signals[0];
+            assert_eq!(*index, 11, "{ma_type:?}");
+            assert!(golden.contains_key(&Decision::EnterLong));
+            assert!(golden.contains_key(&Decision::CloseShort));
+            let strength = golden[&Decision::EnterLong].0;
+            assert!(strength > 0.0 && strength <= 1.0, "strength: {strength}");
+
+            let (index, death) = &signals[1];
+            assert_eq!(*index, 21, "{ma_type:?}");
+            assert!(death.contains_key(&Decision::CloseLong));
+            assert!(death.contains_key(&Decision::EnterShort));
+        }
+    }
+}
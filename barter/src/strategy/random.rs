@@ -0,0 +1,167 @@
+use super::{Decision, Signal, SignalGenerator, SignalStrength, StrategyId};
+use crate::data::{determine_market_close, MarketMeta};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::{instrument::Instrument, market::MarketId};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Configuration for constructing a [`RandomStrategy`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct Config {
+    /// Probability of entering a Position on each [`MarketEvent`] while flat, clamped to [0, 1].
+    pub entry_probability: f64,
+    /// Probability of exiting the Position on each [`MarketEvent`] while in one, clamped to
+    /// [0, 1].
+    pub exit_probability: f64,
+    /// Seed for the random number generator, making the random trades reproducible.
+    pub seed: u64,
+}
+
+/// Strategy that enters & exits at random times, with a random side, that implements
+/// [`SignalGenerator`]. Used to benchmark a real strategy against random noise over the same
+/// market data.
+#[derive(Clone, Debug)]
+pub struct RandomStrategy {
+    config: Config,
+    rng: StdRng,
+    positions: HashMap<MarketId, Decision>,
+}
+
+impl SignalGenerator for RandomStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        let close = determine_market_close(market)?;
+        let market_id = MarketId::new(market.exchange, &market.instrument);
+
+        let decision = match self.positions.get(&market_id) {
+            None if self.rng.gen_bool(self.config.entry_probability) => {
+                let decision = match self.rng.gen_bool(0.5) {
+                    true => Decision::EnterLong,
+                    false => Decision::EnterShort,
+                };
+                self.positions.insert(market_id, decision);
+                decision
+            }
+            Some(entry) if self.rng.gen_bool(self.config.exit_probability) => {
+                let decision = match entry {
+                    Decision::EnterLong => Decision::CloseLong,
+                    _ => Decision::CloseShort,
+                };
+                self.positions.remove(&market_id);
+                decision
+            }
+            _ => return None,
+        };
+
+        Some(Signal {
+            time: market.time_exchange,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            signals: HashMap::from([(decision, SignalStrength(1.0))]),
+            market_meta: MarketMeta {
+                close,
+                time: market.time_exchange,
+            },
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+}
+
+impl RandomStrategy {
+    /// Constructs a new [`RandomStrategy`] component using the provided configuration struct.
+    ///
+    /// Probabilities outside of [0, 1] are clamped to it, & NaN probabilities are treated as 0.
+    pub fn new(config: Config) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(config.seed),
+            config: Config {
+                entry_probability: probability(config.entry_probability),
+                exit_probability: probability(config.exit_probability),
+                ..config
+            },
+            positions: HashMap::new(),
+        }
+    }
+}
+
+/// Clamps the provided value to a valid probability within [0, 1], since [`Rng::gen_bool`]
+/// panics outside of it.
+fn probability(value: f64) -> f64 {
+    if value.is_nan() {
+        0.0
+    } else {
+        value.clamp(0.0, 1.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::market_event_trade;
+    use barter_integration::Side;
+
+    fn run(seed: u64) -> Vec<(usize, Decision)> {
+        let mut strategy = RandomStrategy::new(Config {
+            entry_probability: 0.2,
+            exit_probability: 0.3,
+            seed,
+        });
+
+        (0..200)
+            .filter_map(|index| {
+                strategy
+                    .generate_signal(&market_event_trade(Side::Buy))
+                    .map(|signal| (index, *signal.signals.keys().next().unwrap()))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn random_strategy_is_reproducible_for_seed() {
+        let trades = run(42);
+
+        // Same seed yields the same number & timing of random trades
+        assert_eq!(trades, run(42));
+        assert_ne!(trades, run(7));
+
+        // Entries & exits alternate, starting with an entry
+        assert!(!trades.is_empty());
+        trades
+            .iter()
+            .enumerate()
+            .for_each(|(index, (_, decision))| {
+                assert_eq!(
+                    decision.is_entry(),
+                    index % 2 == 0,
+                    "trade {index}: {decision:?}"
+                );
+            });
+    }
+
+    #[test]
+    fn random_strategy_clamps_probabilities_outside_of_unit_interval() {
+        let mut strategy = RandomStrategy::new(Config {
+            entry_probability: 1.5,
+            exit_probability: -0.5,
+            seed: 42,
+        });
+        assert_eq!(strategy.config.entry_probability, 1.0);
+        assert_eq!(strategy.config.exit_probability, 0.0);
+
+        // Always enters, & never exits, without panicking
+        let signals = (0..10)
+            .filter_map(|_| strategy.generate_signal(&market_event_trade(Side::Buy)))
+            .collect::<Vec<_>>();
+        assert_eq!(signals.len(), 1);
+        assert!(signals[0].signals.keys().all(Decision::is_entry));
+
+        let strategy = RandomStrategy::new(Config {
+            entry_probability: f64::NAN,
+            exit_probability: f64::INFINITY,
+            seed: 42,
+        });
+        assert_eq!(strategy.config.entry_probability, 0.0);
+        assert_eq!(strategy.config.exit_probability, 1.0);
+    }
+}
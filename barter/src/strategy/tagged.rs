@@ -0,0 +1,42 @@
+use super::{Signal, SignalGenerator, StrategyId};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+
+/// [`SignalGenerator`] wrapper that tags every [`Signal`] generated by the inner strategy with
+/// a [`StrategyId`], so the resulting [`Position`](crate::portfolio::position::Position)s can be
+/// attributed to it when multiple strategies trade into the same Portfolio.
+#[derive(Clone, Debug)]
+pub struct TaggedStrategy<Strategy> {
+    pub strategy: Strategy,
+    pub strategy_id: StrategyId,
+}
+
+impl<Strategy> SignalGenerator for TaggedStrategy<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        self.strategy.generate_signal(market).map(|signal| Signal {
+            strategy_id: self.strategy_id.clone(),
+            ..signal
+        })
+    }
+
+    fn is_warm(&self) -> bool {
+        self.strategy.is_warm()
+    }
+}
+
+impl<Strategy> TaggedStrategy<Strategy> {
+    /// Constructs a new [`TaggedStrategy`] that tags the inner strategy's [`Signal`]s with the
+    /// provided [`StrategyId`].
+    pub fn new<Id>(strategy: Strategy, strategy_id: Id) -> Self
+    where
+        Id: Into<StrategyId>,
+    {
+        Self {
+            strategy,
+            strategy_id: strategy_id.into(),
+        }
+    }
+}
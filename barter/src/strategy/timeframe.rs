@@ -0,0 +1,292 @@
+use super::{indicator::Ema, Decision, Signal, SignalGenerator};
+use crate::{
+    data::resample::CandleResampler,
+    statistic::{de_duration_from_secs, se_duration_as_secs},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::instrument::Instrument;
+use chrono::Duration;
+use serde::{Deserialize, Serialize};
+
+/// [`MarketEvent`] tagged with the timeframe of it's
+/// [`Candle`](barter_data::subscription::candle::Candle) (eg/ 5m or 1h). Non-candle market events are tagged with the base timeframe of the feed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TimeframeMarketEvent {
+    pub timeframe: Duration,
+    pub market: MarketEvent<Instrument, DataKind>,
+}
+
+/// May generate an advisory [`Signal`] as a result of analysing a [`TimeframeMarketEvent`] of
+/// any timeframe it consumes, allowing it to maintain separate indicators per timeframe.
+pub trait MultiTimeframeSignalGenerator {
+    /// Optionally return a [`Signal`] given input [`TimeframeMarketEvent`].
+    fn generate_signal(&mut self, event: &TimeframeMarketEvent) -> Option<Signal>;
+
+    /// Determines if the strategy has ingested enough [`TimeframeMarketEvent`]s of every
+    /// timeframe to generate meaningful [`Signal`]s.
+    fn is_warm(&self) -> bool {
+        true
+    }
+}
+
+/// [`SignalGenerator`] adapter that feeds a [`MultiTimeframeSignalGenerator`] the base timeframe
+/// [`MarketEvent`]s it receives, as well as the same candles resampled into each higher timeframe
+/// via a [`CandleResampler`].
+///
+/// A higher timeframe candle completes once a base candle from it's next bucket is received, so
+/// it is always fed to the strategy before that base candle, & never contains future data. If
+/// the strategy generates [`Signal`]s for several timeframes from a single [`MarketEvent`], the
+/// [`Signal`] of the lowest timeframe is returned.
+#[derive(Clone, Debug)]
+pub struct MultiTimeframeStrategy<Strategy> {
+    pub strategy: Strategy,
+    base_timeframe: Duration,
+    resamplers: Vec<CandleResampler>,
+}
+
+impl<Strategy> SignalGenerator for MultiTimeframeStrategy<Strategy>
+where
+    Strategy: MultiTimeframeSignalGenerator,
+{
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        // Feed completed higher timeframe candles first, from the highest timeframe down
+        let higher_signal = self
+            .resamplers
+            .iter_mut()
+            .rev()
+            .filter_map(|resampler| {
+                resampler
+                    .update(market, market.time_received)
+                    .map(|completed| TimeframeMarketEvent {
+                        timeframe: resampler.timeframe(),
+                        market: completed,
+                    })
+            })
+            .fold(None, |signal, event| {
+                self.strategy.generate_signal(&event).or(signal)
+            });
+
+        self.strategy
+            .generate_signal(&TimeframeMarketEvent {
+                timeframe: self.base_timeframe,
+                market: market.clone(),
+            })
+            .or(higher_signal)
+    }
+
+    fn is_warm(&self) -> bool {
+        self.strategy.is_warm()
+    }
+}
+
+impl<Strategy> MultiTimeframeStrategy<Strategy> {
+    /// Constructs a new [`MultiTimeframeStrategy`] feeding the inner strategy [`MarketEvent`]s
+    /// of the provided base timeframe, plus their candles resampled into each higher timeframe.
+    pub fn new<Timeframes>(
+        strategy: Strategy,
+        base_timeframe: Duration,
+        higher_timeframes: Timeframes,
+    ) -> Self
+    where
+        Timeframes: IntoIterator<Item = Duration>,
+    {
+        let mut higher_timeframes = higher_timeframes
+            .into_iter()
+            .filter(|timeframe| *timeframe > base_timeframe)
+            .collect::<Vec<_>>();
+        higher_timeframes.sort();
+        higher_timeframes.dedup();
+
+        Self {
+            strategy,
+            base_timeframe,
+            resamplers: higher_timeframes
+                .into_iter()
+                .map(CandleResampler::new)
+                .collect(),
+        }
+    }
+}
+
+/// Configuration for constructing a [`TrendFilter`] via the new() constructor method.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct TrendFilterConfig {
+    /// Timeframe whose candles determine the trend (eg/ 1h), (de)serialised as seconds.
+    #[serde(
+        deserialize_with = "de_duration_from_secs",
+        serialize_with = "se_duration_as_secs"
+    )]
+    pub trend_timeframe: Duration,
+    /// Period of the trend timeframe [`Ema`] the trend is measured against.
+    pub ema_period: usize,
+}
+
+/// [`MultiTimeframeSignalGenerator`] that only passes on the entries of an inner
+/// [`SignalGenerator`] in the direction of a higher timeframe [`Ema`] trend (eg/ only take 5m
+/// longs when the 1h close is above it's [`Ema`]).
+///
+/// Candles of the trend timeframe update the trend, while every other [`TimeframeMarketEvent`]
+/// is passed to the inner strategy. Exits are never filtered.
+#[derive(Clone, Debug)]
+pub struct TrendFilter<Strategy> {
+    pub strategy: Strategy,
+    trend_timeframe: Duration,
+    ema: Ema,
+    trend_close: Option<f64>,
+}
+
+impl<Strategy> MultiTimeframeSignalGenerator for TrendFilter<Strategy>
+where
+    Strategy: SignalGenerator,
+{
+    fn generate_signal(&mut self, event: &TimeframeMarketEvent) -> Option<Signal> {
+        if event.timeframe == self.trend_timeframe {
+            if let DataKind::Candle(candle) = &event.market.kind {
+                self.ema.update(candle.close);
+                self.trend_close = Some(candle.close);
+            }
+            return None;
+        }
+
+        let (trend_up, trend_down) = match (self.trend_close, self.ema.value()) {
+            (Some(close), Some(ema)) => (close > ema, close < ema),
+            _ => (false, false),
+        };
+
+        let mut signal = self.strategy.generate_signal(&event.market)?;
+        signal.signals.retain(|decision, _| match decision {
+            Decision::EnterLong => trend_up,
+            Decision::EnterShort => trend_down,
+            Decision::CloseLong | Decision::CloseShort => true,
+        });
+
+        (!signal.signals.is_empty()).then_some(signal)
+    }
+
+    fn is_warm(&self) -> bool {
+        self.ema.value().is_some() && self.strategy.is_warm()
+    }
+}
+
+impl<Strategy> TrendFilter<Strategy> {
+    /// Constructs a new [`TrendFilter`] of the inner strategy using the provided configuration.
+    pub fn new(strategy: Strategy, config: TrendFilterConfig) -> Self {
+        Self {
+            strategy,
+            trend_timeframe: config.trend_timeframe,
+            ema: Ema::new(config.ema_period),
+            trend_close: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        data::MarketMeta,
+        strategy::{SignalStrength, StrategyId},
+        test_util::market_event_candle_at,
+    };
+    use chrono::{TimeZone, Utc};
+    use std::collections::HashMap;
+
+    /// Entry strategy that advises a long entry on every candle.
+    struct AlwaysLong;
+
+    impl SignalGenerator for AlwaysLong {
+        fn generate_signal(
+            &mut self,
+            market: &MarketEvent<Instrument, DataKind>,
+        ) -> Option<Signal> {
+            Some(Signal {
+                time: market.time_exchange,
+                exchange: market.exchange,
+                instrument: market.instrument.clone(),
+                signals: HashMap::from([(Decision::EnterLong, SignalStrength(1.0))]),
+                market_meta: MarketMeta::default(),
+                strategy_id: StrategyId::default(),
+                close_fraction: None,
+            })
+        }
+    }
+
+    #[test]
+    fn trend_filter_only_allows_5m_longs_when_1h_trend_is_up() {
+        let mut strategy = MultiTimeframeStrategy::new(
+            TrendFilter::new(
+                AlwaysLong,
+                TrendFilterConfig {
+                    trend_timeframe: Duration::hours(1),
+                    ema_period: 3,
+                },
+            ),
+            Duration::minutes(5),
+            [Duration::hours(1)],
+        );
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let mut closes = Vec::new();
+
+        // 1h trend is unknown until the first hourly candle completes
+        closes.push(200.0);
+
+        // Falling for 6 hours puts the 1h close below it's EMA, so 5m longs are suppressed
+        closes.extend((0..=72).map(|candle| 200.0 - candle as f64 * 0.5));
+
+        // Rising for 6 hours puts the 1h close above it's EMA, so 5m longs are allowed
+        closes.extend((0..=72).map(|candle| 164.0 + candle as f64));
+
+        let signals = closes
+            .into_iter()
+            .enumerate()
+            .map(|(index, close)| {
+                let time = start + Duration::minutes(5 * (index as i64 + 1));
+                strategy.generate_signal(&market_event_candle_at(time, close))
+            })
+            .collect::<Vec<_>>();
+
+        assert!(signals[0].is_none());
+        assert!(signals[73].is_none(), "5m long suppressed in 1h downtrend");
+        let signal = signals[146]
+            .as_ref()
+            .expect("5m long allowed in 1h uptrend");
+        assert!(signal.signals.contains_key(&Decision::EnterLong));
+        assert!(strategy.is_warm());
+    }
+
+    #[test]
+    fn multi_timeframe_strategy_feeds_completed_higher_timeframe_candles_first() {
+        /// Records the timeframe & close of every event consumed.
+        #[derive(Default)]
+        struct Recorder(Vec<(Duration, f64)>);
+
+        impl MultiTimeframeSignalGenerator for Recorder {
+            fn generate_signal(&mut self, event: &TimeframeMarketEvent) -> Option<Signal> {
+                if let DataKind::Candle(candle) = &event.market.kind {
+                    self.0.push((event.timeframe, candle.close));
+                }
+                None
+            }
+        }
+
+        let mut strategy = MultiTimeframeStrategy::new(
+            Recorder::default(),
+            Duration::minutes(5),
+            [Duration::minutes(15), Duration::minutes(5)],
+        );
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        for (minutes, close) in [(5, 1.0), (10, 2.0), (15, 3.0), (20, 4.0)] {
+            strategy.generate_signal(&market_event_candle_at(
+                start + Duration::minutes(minutes),
+                close,
+            ));
+        }
+
+        let m5 = Duration::minutes(5);
+        let m15 = Duration::minutes(15);
+        assert_eq!(
+            strategy.strategy.0,
+            vec![(m5, 1.0), (m5, 2.0), (m5, 3.0), (m15, 3.0), (m5, 4.0)]
+        );
+    }
+}
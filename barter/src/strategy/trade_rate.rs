@@ -0,0 +1,215 @@
+use barter_data::subscription::ticker::Ticker;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Determines how a [`TradeRate`] handles a [`Ticker`] whose `last_id` is lower than the previous
+/// one observed (eg/ exchange trade ids were reset or rolled over between intervals).
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub enum IdReset {
+    /// Use the [`Ticker`] `count` as the number of trades executed since the previous update.
+    #[default]
+    UseCount,
+    /// Discard the interval spanning the reset and start measuring again from the new `last_id`.
+    Restart,
+}
+
+/// Configuration for constructing a [`TradeRate`] via the new() constructor method.
+#[derive(
+    Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default, Deserialize, Serialize,
+)]
+pub struct Config {
+    pub id_reset: IdReset,
+}
+
+/// Trade activity measured between two consecutive [`Ticker`] updates.
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug, Deserialize, Serialize)]
+pub struct TradeRateSample {
+    /// Number of trades executed during the interval.
+    pub trades: u64,
+    /// Number of trades executed per second during the interval.
+    pub trades_per_second: f64,
+    /// Average base asset size of a trade in the [`Ticker`] rolling window.
+    pub avg_trade_size: f64,
+}
+
+/// Trade-rate indicator that derives the number of trades per interval from the `last_id` of
+/// consecutive [`Ticker`] updates, and the average trade size from the `volume` & `count`.
+///
+/// [`Ticker`]s without a `count` or `last_id` (ie/ not provided by the exchange) are ignored.
+#[derive(Copy, Clone, PartialEq, Debug, Deserialize, Serialize)]
+pub struct TradeRate {
+    pub config: Config,
+    pub prev: Option<(DateTime<Utc>, u64)>,
+}
+
+impl TradeRate {
+    /// Constructs a new [`TradeRate`] using the provided [`Config`].
+    pub fn new(config: Config) -> Self {
+        Self { config, prev: None }
+    }
+
+    /// Updates the [`TradeRate`] with the next [`Ticker`] received at the provided time. Returns
+    /// a [`TradeRateSample`] for the interval since the previous [`Ticker`], if one can be
+    /// determined. Returns `None` if the [`Ticker`] has no `count` or `last_id`.
+    pub fn update(&mut self, time: DateTime<Utc>, ticker: &Ticker) -> Option<TradeRateSample> {
+        let (count, last_id) = ticker.count.zip(ticker.last_id)?;
+        let (prev_time, prev_last_id) = self.prev.replace((time, last_id))?;
+
+        let trades = match last_id.checked_sub(prev_last_id) {
+            Some(trades) => trades,
+            None => match self.config.id_reset {
+                IdReset::UseCount => count,
+                IdReset::Restart => return None,
+            },
+        };
+
+        let elapsed = (time - prev_time).num_milliseconds() as f64 / 1000.0;
+        if elapsed <= 0.0 {
+            return None;
+        }
+
+        let avg_trade_size = match count {
+            0 => 0.0,
+            count => ticker.volume / count as f64,
+        };
+
+        Some(TradeRateSample {
+            trades,
+            trades_per_second: trades as f64 / elapsed,
+            avg_trade_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn ticker(volume: f64, count: u64, last_id: u64) -> Ticker {
+        Ticker {
+            last_price: 100.0,
+            volume,
+            count: Some(count),
+            first_id: Some(last_id + 1 - count),
+            last_id: Some(last_id),
+        }
+    }
+
+    #[test]
+    fn trade_rate_update() {
+        let start = Utc::now();
+        let mut use_count = TradeRate::new(Config {
+            id_reset: IdReset::UseCount,
+        });
+        let mut restart = TradeRate::new(Config {
+            id_reset: IdReset::Restart,
+        });
+
+        struct TestCase {
+            time: DateTime<Utc>,
+            ticker: Ticker,
+            expected_use_count: Option<TradeRateSample>,
+            expected_restart: Option<TradeRateSample>,
+        }
+
+        let cases = vec![
+            // TC0: First Ticker has no previous interval
+            TestCase {
+                time: start,
+                ticker: ticker(50.0, 100, 1_000),
+                expected_use_count: None,
+                expected_restart: None,
+            },
+            // TC1: 50 trades in 10 seconds, 60.0 volume over 120 trades
+            TestCase {
+                time: start + Duration::seconds(10),
+                ticker: ticker(60.0, 120, 1_050),
+                expected_use_count: Some(TradeRateSample {
+                    trades: 50,
+                    trades_per_second: 5.0,
+                    avg_trade_size: 0.5,
+                }),
+                expected_restart: Some(TradeRateSample {
+                    trades: 50,
+                    trades_per_second: 5.0,
+                    avg_trade_size: 0.5,
+                }),
+            },
+            // TC2: Ids reset, so trades are taken from count or interval is discarded
+            TestCase {
+                time: start + Duration::seconds(15),
+                ticker: ticker(20.0, 10, 10),
+                expected_use_count: Some(TradeRateSample {
+                    trades: 10,
+                    trades_per_second: 2.0,
+                    avg_trade_size: 2.0,
+                }),
+                expected_restart: None,
+            },
+            // TC3: 30 trades in 2 seconds after reset
+            TestCase {
+                time: start + Duration::seconds(17),
+                ticker: ticker(40.0, 40, 40),
+                expected_use_count: Some(TradeRateSample {
+                    trades: 30,
+                    trades_per_second: 15.0,
+                    avg_trade_size: 1.0,
+                }),
+                expected_restart: Some(TradeRateSample {
+                    trades: 30,
+                    trades_per_second: 15.0,
+                    avg_trade_size: 1.0,
+                }),
+            },
+        ];
+
+        for (index, test) in cases.into_iter().enumerate() {
+            let actual = use_count.update(test.time, &test.ticker);
+            assert_eq!(actual, test.expected_use_count, "TC{index} UseCount failed");
+
+            let actual = restart.update(test.time, &test.ticker);
+            assert_eq!(actual, test.expected_restart, "TC{index} Restart failed");
+        }
+    }
+
+    #[test]
+    fn trade_rate_ignores_tickers_without_count_or_ids() {
+        let start = Utc::now();
+        let mut trade_rate = TradeRate::new(Config::default());
+
+        let missing = Ticker {
+            count: None,
+            first_id: None,
+            last_id: None,
+            ..ticker(50.0, 100, 1_000)
+        };
+        assert_eq!(trade_rate.update(start, &missing), None);
+        assert_eq!(
+            trade_rate.update(start + Duration::seconds(10), &missing),
+            None
+        );
+        assert_eq!(trade_rate.prev, None);
+
+        // Missing count with a present last_id is also ignored
+        assert_eq!(trade_rate.update(start, &ticker(50.0, 100, 1_000)), None);
+        let missing_count = Ticker {
+            count: None,
+            ..ticker(60.0, 120, 1_050)
+        };
+        assert_eq!(
+            trade_rate.update(start + Duration::seconds(10), &missing_count),
+            None
+        );
+        assert_eq!(
+            trade_rate.update(start + Duration::seconds(20), &ticker(60.0, 120, 1_100)),
+            Some(TradeRateSample {
+                trades: 100,
+                trades_per_second: 5.0,
+                avg_trade_size: 0.5,
+            })
+        );
+    }
+}
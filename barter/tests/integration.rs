@@ -1,23 +1,51 @@
 use barter::{
-    data::historical,
-    engine::{trader::Trader, Engine},
-    event::EventTx,
+    clock::SimulatedClock,
+    data::{
+        historical::{self, CsvColumns},
+        MarketMeta,
+    },
+    engine::{
+        progress::Progress,
+        shadow::{Shadow, ShadowPortfolio},
+        trader::Trader,
+        Engine,
+    },
+    event::{Event, EventTx},
     execution::{
-        simulated::{Config as ExecutionConfig, SimulatedExecution},
+        simulated::{
+            Config as ExecutionConfig, LimitFillModel, SimulatedExecution, SlippageModel,
+            SpreadModel,
+        },
         Fees,
     },
     portfolio::{
-        allocator::DefaultAllocator, portfolio::MetaPortfolio,
-        repository::in_memory::InMemoryRepository, risk::DefaultRisk,
+        allocator::DefaultAllocator,
+        portfolio::MetaPortfolio,
+        position::Position,
+        repository::{in_memory::InMemoryRepository, PositionHandler, StatisticHandler},
+        risk::DefaultRisk,
+        volatility_target::{VolatilityTarget, VolatilityTargetConfig},
+        FillUpdater, MarketUpdater,
+    },
+    statistic::{
+        metric::ratio::Ratio,
+        summary::{
+            trading::{Config as StatisticConfig, TradingSummary},
+            Initialiser,
+        },
     },
-    statistic::summary::{
-        trading::{Config as StatisticConfig, TradingSummary},
-        Initialiser,
+    strategy::{
+        example::{Config as StrategyConfig, RSIStrategy},
+        Decision, Signal, SignalGenerator, SignalStrength, StrategyId,
     },
-    strategy::example::{Config as StrategyConfig, RSIStrategy},
-    test_util::market_event_trade,
+    test_util::{fill_event, market_event_candle, market_event_trade},
+};
+use barter_data::event::{DataKind, MarketEvent};
+use barter_instrument::{
+    exchange::ExchangeId,
+    instrument::{kind::InstrumentKind, Instrument},
+    market::{Market, MarketId},
 };
-use barter_instrument::{exchange::ExchangeId, instrument::kind::InstrumentKind, market::Market};
 use barter_integration::Side;
 use parking_lot::Mutex;
 use std::{collections::HashMap, sync::Arc, time::Duration};
@@ -56,7 +84,10 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
             .statistic_config(StatisticConfig {
                 starting_equity: 10_000.0,
                 trading_days_per_year: 365,
+                data_period: chrono::Duration::days(1),
                 risk_free_return: 0.0,
+                omega_threshold: None,
+                with_benchmark: false,
             })
             .build_and_init()
             .expect("failed to build & initialise MetaPortfolio"),
@@ -78,13 +109,21 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
             .data(historical::MarketFeed::new(
                 [market_event_trade(Side::Buy)].into_iter(),
             ))
-            .strategy(RSIStrategy::new(StrategyConfig { rsi_period: 14 }))
+            .strategy(RSIStrategy::new(StrategyConfig {
+                rsi_period: 14,
+                gap_rewarm: None,
+            }))
             .execution(SimulatedExecution::new(ExecutionConfig {
                 simulated_fees_pct: Fees {
                     exchange: 0.1,
                     slippage: 0.05,
                     network: 0.0,
                 },
+                exchange_fees_pct: None,
+                slippage_model: SlippageModel::Flat,
+                spread_model: SpreadModel::Zero,
+                latency: chrono::Duration::zero(),
+                limit_fill_model: LimitFillModel::Immediate,
             }))
             .build()
             .expect("failed to build trader"),
@@ -103,7 +142,10 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
         .statistics_summary(TradingSummary::init(StatisticConfig {
             starting_equity: 1000.0,
             trading_days_per_year: 365,
+            data_period: chrono::Duration::days(1),
             risk_free_return: 0.0,
+            omega_threshold: None,
+            with_benchmark: false,
         }))
         .build()
         .expect("failed to build engine");
@@ -120,3 +162,1040 @@ async fn engine_with_historic_data_stops_after_candles_finished() {
         "failed because Engine's command_rx.await is blocking the Engine from stopping"
     )
 }
+
+/// Strategy that enters Long at a trade price of 100.0, and exits at any other price.
+struct AllInStrategy;
+
+impl SignalGenerator for AllInStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        let DataKind::Trade(trade) = &market.kind else {
+            return None;
+        };
+
+        let decision = if trade.price == 100.0 {
+            Decision::EnterLong
+        } else {
+            Decision::CloseLong
+        };
+
+        Some(Signal {
+            time: market.time_exchange,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            signals: HashMap::from([(decision, SignalStrength(1.0))]),
+            market_meta: MarketMeta {
+                close: trade.price,
+                time: market.time_exchange,
+            },
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn engine_halts_trading_on_bankruptcy() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let market = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        data_period: chrono::Duration::days(1),
+        risk_free_return: 0.0,
+        omega_threshold: None,
+        with_benchmark: false,
+    };
+
+    // Portfolio allocates all of it's starting cash to the first Position
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 10_000.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    // Instrument price crashes to zero, then recovers
+    let market_event = |price: f64| {
+        let mut market_event = market_event_trade(Side::Buy);
+        if let DataKind::Trade(trade) = &mut market_event.kind {
+            trade.price = price;
+        }
+        market_event
+    };
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(
+            [market_event(100.0), market_event(0.0), market_event(100.0)].into_iter(),
+        ))
+        .strategy(AllInStrategy)
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: chrono::Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        }))
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&portfolio))
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market.clone(), trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()
+        .expect("failed to build engine");
+
+    tokio::time::timeout(Duration::from_millis(100), engine.run())
+        .await
+        .expect("Engine failed to halt after Portfolio bankruptcy");
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        events.push(event);
+    }
+
+    // Trader halted after the Bankruptcy, so the final MarketEvent was never consumed
+    let bankruptcy_index = events
+        .iter()
+        .position(|event| matches!(event, Event::Bankruptcy(_)))
+        .expect("Bankruptcy event not sent");
+    assert_eq!(bankruptcy_index, events.len() - 1);
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, Event::Market(_)))
+            .count(),
+        2
+    );
+
+    // Statistics record the bankruptcy & contain only finite metrics
+    let statistics = portfolio
+        .lock()
+        .get_statistics(engine_id, &MarketId::from(&market))
+        .unwrap();
+    assert!(statistics.bankruptcy.is_some());
+    assert!(statistics.pnl_returns.trades_per_day.is_finite());
+    assert!(statistics
+        .drawdown
+        .max_drawdown
+        .drawdown
+        .drawdown
+        .is_finite());
+    assert!(statistics
+        .tear_sheet
+        .sharpe_ratio
+        .sharpe_ratio_per_trade
+        .is_finite());
+    assert!(statistics.tear_sheet.sortino_ratio.ratio().is_finite());
+    assert!(statistics.tear_sheet.calmar_ratio.ratio().is_finite());
+}
+
+/// Strategy that enters Long at a trade price of 100.0, and never exits.
+struct HodlStrategy;
+
+impl SignalGenerator for HodlStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        let DataKind::Trade(trade) = &market.kind else {
+            return None;
+        };
+
+        (trade.price == 100.0).then(|| Signal {
+            time: market.time_exchange,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            signals: HashMap::from([(Decision::EnterLong, SignalStrength(1.0))]),
+            market_meta: MarketMeta {
+                close: trade.price,
+                time: market.time_exchange,
+            },
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+}
+
+#[test]
+fn traders_sharing_a_portfolio_halt_once_market_wipes_out_equity() {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let btc = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+    let eth = Market::new(
+        ExchangeId::BinanceSpot,
+        ("eth", "usdt", InstrumentKind::Spot),
+    );
+
+    // Portfolio allocates all of it's starting cash to the first Position
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![btc.clone(), eth.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::<TradingSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 10_000.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                data_period: chrono::Duration::days(1),
+                risk_free_return: 0.0,
+                omega_threshold: None,
+                with_benchmark: false,
+            })
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let market_event = |market: &Market, price: f64| {
+        let mut market_event = market_event_trade(Side::Buy);
+        market_event.instrument = market.instrument.clone();
+        if let DataKind::Trade(trade) = &mut market_event.kind {
+            trade.price = price;
+        }
+        market_event
+    };
+
+    let trader = |market: &Market, prices: Vec<f64>, command_rx| {
+        Trader::<_, TradingSummary, _, _, _, _>::builder()
+            .engine_id(engine_id)
+            .market(market.clone())
+            .command_rx(command_rx)
+            .event_tx(event_tx.clone())
+            .portfolio(Arc::clone(&portfolio))
+            .data(historical::MarketFeed::new(
+                prices
+                    .into_iter()
+                    .map(|price| market_event(market, price))
+                    .collect::<Vec<_>>(),
+            ))
+            .strategy(HodlStrategy)
+            .execution(SimulatedExecution::new(ExecutionConfig {
+                simulated_fees_pct: Fees {
+                    exchange: 0.01,
+                    slippage: 0.0,
+                    network: 0.0,
+                },
+                exchange_fees_pct: None,
+                slippage_model: SlippageModel::Flat,
+                spread_model: SpreadModel::Zero,
+                latency: chrono::Duration::zero(),
+                limit_fill_model: LimitFillModel::Immediate,
+            }))
+            .build()
+            .expect("failed to build trader")
+    };
+
+    // Btc Trader enters with all the cash & fees, then the btc price crashes without any fill
+    let (_btc_command_tx, btc_command_rx) = mpsc::channel(10);
+    trader(&btc, vec![100.0, 0.5, 100.0], btc_command_rx).run();
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        events.push(event);
+    }
+    assert!(matches!(
+        events.last(),
+        Some(Event::Bankruptcy(bankruptcy)) if bankruptcy.equity <= 0.0
+    ));
+    assert_eq!(
+        events
+            .iter()
+            .filter(|event| matches!(event, Event::Market(_)))
+            .count(),
+        2
+    );
+    assert!(!events
+        .iter()
+        .any(|event| matches!(event, Event::Fill(fill) if fill.quantity < 0.0)));
+    assert!(portfolio.lock().bankruptcy().is_some());
+
+    // Eth Trader sharing the bankrupt Portfolio halts without consuming any MarketEvents
+    let (_eth_command_tx, eth_command_rx) = mpsc::channel(10);
+    trader(&eth, vec![100.0, 100.0], eth_command_rx).run();
+    assert!(event_rx.try_recv().is_err());
+    assert!(portfolio
+        .lock()
+        .get_open_positions(engine_id, std::iter::once(&eth))
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn trader_rebalances_open_position_toward_volatility_target() {
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let engine_id = Uuid::new_v4();
+    let market = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+
+    // Portfolio targets 10% annualised volatility, never levering exposure above 1.0
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::<TradingSummary>::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 1000.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(StatisticConfig {
+                starting_equity: 10_000.0,
+                trading_days_per_year: 365,
+                data_period: chrono::Duration::days(1),
+                risk_free_return: 0.0,
+                omega_threshold: None,
+                with_benchmark: false,
+            })
+            .volatility_target(VolatilityTarget::new(VolatilityTargetConfig {
+                target_volatility: 0.1,
+                periods_per_year: 365.0,
+                volatility_period: 4,
+                max_scale: 1.0,
+                rebalance_threshold: 0.05,
+            }))
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    // Enter 10.0 btc at 100.0, then swing the price by 10% each period
+    let market_events = [100.0, 110.0, 90.0, 110.0, 90.0, 110.0].map(|price| {
+        let mut market_event = market_event_trade(Side::Buy);
+        if let DataKind::Trade(trade) = &mut market_event.kind {
+            trade.price = price;
+        }
+        market_event
+    });
+
+    let (_command_tx, command_rx) = mpsc::channel(10);
+    Trader::<_, TradingSummary, _, _, _, _>::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(command_rx)
+        .event_tx(EventTx::new(event_tx))
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(market_events))
+        .strategy(HodlStrategy)
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: chrono::Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        }))
+        .build()
+        .expect("failed to build trader")
+        .run();
+
+    let mut events = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        events.push(event);
+    }
+
+    // Realised volatility far exceeds the target, so the Position is partially closed
+    let rebalance = events
+        .iter()
+        .find_map(|event| match event {
+            Event::OrderNew(order) if order.decision == Decision::CloseLong => Some(order),
+            _ => None,
+        })
+        .expect("rebalancing OrderEvent not generated");
+    assert!(rebalance.quantity < 0.0 && rebalance.quantity > -10.0);
+    assert!(events.iter().any(|event| matches!(
+        event,
+        Event::Fill(fill) if fill.cid == Some(rebalance.cid)
+    )));
+
+    let positions = portfolio
+        .lock()
+        .get_open_positions(engine_id, std::iter::once(&market))
+        .unwrap();
+    assert_eq!(positions.len(), 1);
+    assert!(positions[0].quantity > 0.0 && positions[0].quantity < 10.0);
+}
+
+/// Strategy that enters Long at a candle close of 100.0, and exits at any other close.
+struct CandleAllInStrategy;
+
+impl SignalGenerator for CandleAllInStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        let DataKind::Candle(candle) = &market.kind else {
+            return None;
+        };
+
+        let decision = if candle.close == 100.0 {
+            Decision::EnterLong
+        } else {
+            Decision::CloseLong
+        };
+
+        Some(Signal {
+            time: market.time_exchange,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            signals: HashMap::from([(decision, SignalStrength(1.0))]),
+            market_meta: MarketMeta {
+                close: candle.close,
+                time: market.time_exchange,
+            },
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn engine_backtest_on_candles_returns_trading_summary() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let market = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        data_period: chrono::Duration::days(1),
+        risk_free_return: 0.0,
+        omega_threshold: None,
+        with_benchmark: false,
+    };
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 1_000.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    // Enter 10 units at 100.0 & exit at 110.0, enter again at 100.0 & exit at 95.0, then enter
+    // a final Position that remains open when the candles are finished
+    let candle = |close: f64| {
+        let mut market_event = market_event_candle();
+        if let DataKind::Candle(candle) = &mut market_event.kind {
+            candle.close = close;
+        }
+        market_event
+    };
+    let candles = [100.0, 110.0, 100.0, 95.0, 100.0].map(candle);
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(candles.into_iter()))
+        .strategy(CandleAllInStrategy)
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: chrono::Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        }))
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(portfolio)
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()
+        .expect("failed to build engine");
+
+    let summary = tokio::time::timeout(Duration::from_secs(1), engine.run())
+        .await
+        .expect("Engine failed to stop after candles finished");
+
+    // Only the two exited Positions are summarised
+    assert_eq!(summary.outcomes.trades, 2);
+    assert_eq!(summary.outcomes.wins, 1);
+    assert_eq!(summary.outcomes.losses, 1);
+    assert!((summary.outcomes.gross_profit - 100.0).abs() < 1e-9);
+    assert!((summary.outcomes.gross_loss - 50.0).abs() < 1e-9);
+}
+
+/// Strategy that enters Long at the provided candle close, and exits at any other close.
+struct CandleEntryStrategy(f64);
+
+impl SignalGenerator for CandleEntryStrategy {
+    fn generate_signal(&mut self, market: &MarketEvent<Instrument, DataKind>) -> Option<Signal> {
+        let DataKind::Candle(candle) = &market.kind else {
+            return None;
+        };
+
+        let decision = if candle.close == self.0 {
+            Decision::EnterLong
+        } else {
+            Decision::CloseLong
+        };
+
+        Some(Signal {
+            time: market.time_exchange,
+            exchange: market.exchange,
+            instrument: market.instrument.clone(),
+            signals: HashMap::from([(decision, SignalStrength(1.0))]),
+            market_meta: MarketMeta {
+                close: candle.close,
+                time: market.time_exchange,
+            },
+            strategy_id: StrategyId::default(),
+            close_fraction: None,
+        })
+    }
+}
+
+#[tokio::test]
+async fn engine_shadow_portfolio_tracks_alternative_strategy_without_affecting_real_portfolio() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let shadow_engine_id = Uuid::new_v4();
+    let market = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        data_period: chrono::Duration::days(1),
+        risk_free_return: 0.0,
+        omega_threshold: None,
+        with_benchmark: false,
+    };
+    let execution = || {
+        SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: chrono::Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        })
+    };
+    let portfolio = |engine_id: Uuid| {
+        Arc::new(Mutex::new(
+            MetaPortfolio::builder()
+                .engine_id(engine_id)
+                .markets(vec![market.clone()])
+                .starting_cash(10_000.0)
+                .repository(InMemoryRepository::new())
+                .allocation_manager(DefaultAllocator {
+                    default_order_value: 1_000.0,
+                })
+                .risk_manager(DefaultRisk {})
+                .statistic_config(statistic_config)
+                .build_and_init()
+                .expect("failed to build & initialise MetaPortfolio"),
+        ))
+    };
+    let real_portfolio = portfolio(engine_id);
+    let shadow_portfolio = portfolio(shadow_engine_id);
+
+    let candle = |close: f64| {
+        let mut market_event = market_event_candle();
+        if let DataKind::Candle(candle) = &mut market_event.kind {
+            candle.close = close;
+        }
+        market_event
+    };
+    let candles = [100.0, 110.0, 100.0, 95.0, 100.0].map(candle);
+
+    // Real Strategy enters at 100.0, whereas the shadow Strategy enters at 95.0 & exits at 100.0
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&real_portfolio))
+        .data(historical::MarketFeed::new(candles.into_iter()))
+        .strategy(CandleEntryStrategy(100.0))
+        .execution(execution())
+        .shadows(vec![Shadow::new(
+            Arc::clone(&shadow_portfolio),
+            CandleEntryStrategy(95.0),
+            execution(),
+        )])
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&real_portfolio))
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .shadow_portfolios(vec![ShadowPortfolio {
+            engine_id: shadow_engine_id,
+            portfolio: Arc::clone(&shadow_portfolio),
+            statistics_summary: TradingSummary::init(statistic_config),
+        }])
+        .build()
+        .expect("failed to build engine");
+
+    let (summary, shadow_summaries) =
+        tokio::time::timeout(Duration::from_secs(1), engine.run_with_shadows())
+            .await
+            .expect("Engine failed to stop after candles finished");
+
+    // Real Portfolio is summarised exactly as if it had traded without a shadow
+    assert_eq!(summary.outcomes.trades, 2);
+    assert!((summary.outcomes.gross_profit - 100.0).abs() < 1e-9);
+    assert!((summary.outcomes.gross_loss - 50.0).abs() < 1e-9);
+
+    // Shadow Portfolio entered 10.5263 units (1000/95 rounded) at 95.0 & exited them at 100.0
+    let [shadow_summary] = shadow_summaries.as_slice() else {
+        panic!("expected one shadow summary, got: {shadow_summaries:?}");
+    };
+    assert_eq!(shadow_summary.outcomes.trades, 1);
+    assert!((shadow_summary.outcomes.gross_profit - 10.5263 * 5.0).abs() < 1e-9);
+    assert_eq!(shadow_summary.outcomes.gross_loss, 0.0);
+
+    // Shadow fills never touch the real Portfolio's cash, nor are they sent to the Event sink
+    let real_exited = real_portfolio
+        .lock()
+        .get_exited_positions(engine_id)
+        .expect("failed to get exited Positions");
+    assert_eq!(real_exited.len(), 2);
+    let shadow_exited = shadow_portfolio
+        .lock()
+        .get_exited_positions(shadow_engine_id)
+        .expect("failed to get exited Positions");
+    assert_eq!(shadow_exited.len(), 1);
+    assert_eq!(shadow_exited[0].enter_avg_price_gross, 95.0);
+
+    let real_margin = real_portfolio.lock().margin_account().unwrap();
+    let shadow_margin = shadow_portfolio.lock().margin_account().unwrap();
+    assert_eq!(real_margin.used, 1_000.0);
+    assert!((real_margin.available - (10_000.0 + 100.0 - 50.0 - 1_000.0)).abs() < 1e-9);
+    assert_eq!(shadow_margin.used, 0.0);
+    assert!((shadow_margin.available - (10_000.0 + 10.5263 * 5.0)).abs() < 1e-9);
+
+    let mut fills = 0;
+    while let Ok(event) = event_rx.try_recv() {
+        if let Event::Fill(fill) = event {
+            assert!(
+                !(fill.decision.is_entry() && fill.market_meta.close == 95.0),
+                "shadow fill sent to Event sink"
+            );
+            fills += 1;
+        }
+    }
+    assert_eq!(fills, 5);
+}
+
+#[tokio::test]
+async fn engine_backtest_reports_progress_every_n_market_events() {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let market = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        data_period: chrono::Duration::days(1),
+        risk_free_return: 0.0,
+        omega_threshold: None,
+        with_benchmark: false,
+    };
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 1_000.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let candle = |close: f64| {
+        let mut market_event = market_event_candle();
+        if let DataKind::Candle(candle) = &mut market_event.kind {
+            candle.close = close;
+        }
+        market_event
+    };
+    let candles = [100.0, 110.0, 100.0, 95.0, 100.0].map(candle);
+
+    // Counting callback only has access to it's own state & the Progress snapshots
+    let reported = Arc::new(Mutex::new(Vec::<Progress>::new()));
+    let reported_tx = Arc::clone(&reported);
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(candles.into_iter()))
+        .strategy(CandleAllInStrategy)
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: chrono::Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        }))
+        .progress(2, move |progress| reported_tx.lock().push(progress))
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(portfolio)
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()
+        .expect("failed to build engine");
+
+    tokio::time::timeout(Duration::from_secs(1), engine.run())
+        .await
+        .expect("Engine failed to stop after candles finished");
+
+    // 5 candles reported every 2 events: after the exits at 110.0 & 95.0
+    let reported = reported.lock();
+    assert_eq!(
+        reported
+            .iter()
+            .map(|progress| progress.events)
+            .collect::<Vec<_>>(),
+        vec![2, 4]
+    );
+    assert!((reported[0].equity - 10_100.0).abs() < 1e-9);
+    assert!((reported[1].equity - 10_050.0).abs() < 1e-9);
+    assert!(reported.iter().all(|progress| progress.open_positions == 0));
+}
+
+/// Runs a backtest over the provided CSV candles using a [`SimulatedClock`], returning every
+/// timestamp of every [`Event`] generated, serialised as JSON.
+async fn backtest_event_timestamps(candles_csv: &'static str) -> String {
+    fn timestamps(value: &serde_json::Value, output: &mut Vec<serde_json::Value>) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                for (key, value) in fields {
+                    if key.contains("time") && value.is_string() {
+                        output.push(value.clone());
+                    } else {
+                        timestamps(value, output);
+                    }
+                }
+            }
+            serde_json::Value::Array(values) => {
+                values.iter().for_each(|value| timestamps(value, output))
+            }
+            _ => {}
+        }
+    }
+
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, mut event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let market = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        data_period: chrono::Duration::days(1),
+        risk_free_return: 0.0,
+        omega_threshold: None,
+        with_benchmark: false,
+    };
+
+    // Every component shares the same SimulatedClock, driven by the candle close times
+    let clock = SimulatedClock::default();
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 1_000.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .clock(clock.clone())
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    let data = historical::CsvCandleFeed::from_reader(
+        candles_csv.as_bytes(),
+        ExchangeId::BinanceSpot,
+        Instrument::from(("btc", "usdt", InstrumentKind::Spot)),
+        &CsvColumns::default(),
+    )
+    .expect("failed to build CsvCandleFeed")
+    .with_clock(clock.clone());
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&portfolio))
+        .data(data)
+        .strategy(CandleAllInStrategy)
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: chrono::Duration::milliseconds(100),
+            limit_fill_model: LimitFillModel::Immediate,
+        }))
+        .clock(clock)
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(portfolio)
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()
+        .expect("failed to build engine");
+
+    tokio::time::timeout(Duration::from_secs(1), engine.run())
+        .await
+        .expect("Engine failed to stop after candles finished");
+
+    let mut output = Vec::new();
+    while let Ok(event) = event_rx.try_recv() {
+        let event = serde_json::to_value(&event).expect("failed to serialise Event");
+        timestamps(&event, &mut output);
+    }
+
+    serde_json::to_string(&output).expect("failed to serialise Event timestamps")
+}
+
+#[tokio::test]
+async fn engine_backtests_with_simulated_clock_produce_identical_event_timestamps() {
+    const CANDLES: &str = "\
+close_time,open,high,low,close,volume,trade_count
+2024-01-01T00:01:00Z,100.0,101.0,99.0,100.0,10.0,5
+2024-01-01T00:02:00Z,100.0,111.0,99.0,110.0,10.0,5
+2024-01-01T00:03:00Z,110.0,111.0,99.0,100.0,10.0,5
+2024-01-01T00:04:00Z,100.0,101.0,94.0,95.0,10.0,5
+2024-01-01T00:05:00Z,95.0,101.0,94.0,100.0,10.0,5
+";
+
+    let first = backtest_event_timestamps(CANDLES).await;
+
+    // Wall-clock time elapses between the runs, but must not leak into any Event timestamp
+    std::thread::sleep(Duration::from_millis(5));
+    let second = backtest_event_timestamps(CANDLES).await;
+
+    assert!(first.contains("2024-01-01T00:05:00Z"), "{first}");
+    assert!(!first.contains(&chrono::Utc::now().format("%Y-%m-%d").to_string()));
+    assert_eq!(first, second);
+}
+
+/// Runs a backtest over the provided market events using a [`Trader`] that closes positions on finish,
+/// optionally entering 10 units at 100.0 before the run, returning the [`TradingSummary`] & every
+/// exited [`Position`].
+async fn backtest_closing_positions_on_finish(
+    market_events: Vec<MarketEvent<Instrument, DataKind>>,
+    enter_before_run: bool,
+) -> (TradingSummary, Vec<Position>) {
+    let (_command_tx, command_rx) = mpsc::channel(20);
+    let (event_tx, _event_rx) = mpsc::unbounded_channel();
+    let event_tx = EventTx::new(event_tx);
+    let engine_id = Uuid::new_v4();
+    let market = Market::new(
+        ExchangeId::BinanceSpot,
+        ("btc", "usdt", InstrumentKind::Spot),
+    );
+    let statistic_config = StatisticConfig {
+        starting_equity: 10_000.0,
+        trading_days_per_year: 365,
+        data_period: chrono::Duration::days(1),
+        risk_free_return: 0.0,
+        omega_threshold: None,
+        with_benchmark: false,
+    };
+
+    let portfolio = Arc::new(Mutex::new(
+        MetaPortfolio::builder()
+            .engine_id(engine_id)
+            .markets(vec![market.clone()])
+            .starting_cash(10_000.0)
+            .repository(InMemoryRepository::new())
+            .allocation_manager(DefaultAllocator {
+                default_order_value: 1_000.0,
+            })
+            .risk_manager(DefaultRisk {})
+            .statistic_config(statistic_config)
+            .build_and_init()
+            .expect("failed to build & initialise MetaPortfolio"),
+    ));
+
+    if enter_before_run {
+        let mut fill = fill_event();
+        fill.instrument = market.instrument.clone();
+        fill.decision = Decision::EnterLong;
+        fill.quantity = 10.0;
+        fill.fill_value_gross = 1_000.0;
+        portfolio
+            .lock()
+            .update_from_fill(&fill)
+            .expect("failed to enter Position");
+    }
+
+    let (trader_command_tx, trader_command_rx) = mpsc::channel(10);
+    let trader = Trader::builder()
+        .engine_id(engine_id)
+        .market(market.clone())
+        .command_rx(trader_command_rx)
+        .event_tx(event_tx)
+        .portfolio(Arc::clone(&portfolio))
+        .data(historical::MarketFeed::new(market_events))
+        .strategy(CandleAllInStrategy)
+        .execution(SimulatedExecution::new(ExecutionConfig {
+            simulated_fees_pct: Fees::default(),
+            exchange_fees_pct: None,
+            slippage_model: SlippageModel::Flat,
+            spread_model: SpreadModel::Zero,
+            latency: chrono::Duration::zero(),
+            limit_fill_model: LimitFillModel::Immediate,
+        }))
+        .close_positions_on_finish(true)
+        .build()
+        .expect("failed to build trader");
+
+    let engine = Engine::builder()
+        .engine_id(engine_id)
+        .command_rx(command_rx)
+        .portfolio(Arc::clone(&portfolio))
+        .traders(vec![trader])
+        .trader_command_txs(HashMap::from_iter([(market, trader_command_tx)]))
+        .statistics_summary(TradingSummary::init(statistic_config))
+        .build()
+        .expect("failed to build engine");
+
+    let summary = tokio::time::timeout(Duration::from_secs(1), engine.run())
+        .await
+        .expect("Engine failed to stop after candles finished");
+
+    let exited = portfolio
+        .lock()
+        .get_exited_positions(engine_id)
+        .expect("failed to get exited Positions");
+
+    (summary, exited)
+}
+
+#[tokio::test]
+async fn engine_closes_open_position_at_last_price_when_market_data_finished() {
+    let candle = |close: f64| {
+        let mut market_event = market_event_candle();
+        if let DataKind::Candle(candle) = &mut market_event.kind {
+            candle.close = close;
+        }
+        market_event
+    };
+    let mut market_events = [100.0, 110.0, 100.0].map(candle).to_vec();
+
+    // Final trade at 104.0 is ignored by the Strategy, but marks the open Position
+    let mut trade = market_event_trade(Side::Buy);
+    if let DataKind::Trade(trade) = &mut trade.kind {
+        trade.price = 104.0;
+    }
+    market_events.push(trade);
+
+    let (summary, exited) = backtest_closing_positions_on_finish(market_events, false).await;
+
+    // Position entered at 100.0 & exited at 110.0, then re-entered at 100.0 & force-closed at
+    // the last known price of 104.0
+    assert_eq!(summary.outcomes.trades, 2);
+    assert_eq!(summary.outcomes.wins, 2);
+    assert_eq!(exited.len(), 2);
+
+    let closed_on_finish = &exited[1];
+    assert_eq!(closed_on_finish.exit_avg_price_gross, 104.0);
+    assert!((closed_on_finish.realised_profit_loss - 40.0).abs() < 1e-9);
+    assert!(!closed_on_finish.synthetic_exit);
+}
+
+#[tokio::test]
+async fn engine_closes_open_position_at_last_mark_without_market_data() {
+    let (summary, exited) = backtest_closing_positions_on_finish(Vec::new(), true).await;
+
+    // Without any market data the Position is exited at it's last mark price & flagged synthetic
+    assert_eq!(summary.outcomes.trades, 1);
+    assert!(matches!(
+        exited.as_slice(),
+        [position] if position.synthetic_exit && position.exit_avg_price_gross == 100.0
+    ));
+}
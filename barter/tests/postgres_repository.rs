@@ -0,0 +1,164 @@
+//! Round trip tests for the [`PostgresRepository`]. Requires a running PostgreSQL instance, whose
+//! connection URI is provided via the `BARTER_TEST_POSTGRES_URI` environment variable. The tests
+//! are ignored by default, so must be run explicitly, eg/
+//! `BARTER_TEST_POSTGRES_URI=postgres://postgres@localhost/barter cargo test --features postgres
+//! -- --ignored`.
+#![cfg(feature = "postgres")]
+
+use barter::{
+    portfolio::{
+        position::determine_position_id,
+        repository::{
+            error::RepositoryError,
+            postgres::{Config, PostgresRepository, DEFAULT_MAX_CONNECTIONS},
+            BalanceHandler, PositionHandler, StatisticHandler,
+        },
+        Balance,
+    },
+    statistic::summary::{
+        trading::{Config as StatisticConfig, TradingSummary},
+        Initialiser,
+    },
+    test_util::position,
+};
+use barter_instrument::{
+    instrument::Instrument,
+    market::{Market, MarketId},
+};
+use chrono::Utc;
+use uuid::Uuid;
+
+fn repository() -> PostgresRepository<TradingSummary> {
+    let uri = std::env::var("BARTER_TEST_POSTGRES_URI")
+        .expect("BARTER_TEST_POSTGRES_URI must be set to run the Postgres repository tests");
+
+    PostgresRepository::new(Config {
+        uri,
+        max_connections: DEFAULT_MAX_CONNECTIONS,
+    })
+    .expect("failed to construct PostgresRepository")
+}
+
+#[test]
+#[ignore = "requires Postgres"]
+fn position_round_trips_through_set_get_and_remove() {
+    let mut repository = repository();
+
+    let engine_id = Uuid::new_v4();
+    let mut position = position();
+    position.position_id =
+        determine_position_id(engine_id, &position.exchange, &position.instrument);
+
+    repository.set_open_position(position.clone()).unwrap();
+    assert_eq!(
+        repository.get_open_position(&position.position_id).unwrap(),
+        Some(position.clone())
+    );
+
+    // Upsert overwrites the existing open Position
+    position.current_symbol_price = 110.0;
+    position.unrealised_profit_loss = 10.0;
+    repository.set_open_position(position.clone()).unwrap();
+    assert_eq!(
+        repository.get_open_position(&position.position_id).unwrap(),
+        Some(position.clone())
+    );
+
+    assert_eq!(
+        repository.remove_position(&position.position_id).unwrap(),
+        Some(position.clone())
+    );
+    assert_eq!(
+        repository.get_open_position(&position.position_id).unwrap(),
+        None
+    );
+
+    repository
+        .set_exited_position(engine_id, position.clone())
+        .unwrap();
+    assert_eq!(
+        repository.get_exited_positions(engine_id).unwrap(),
+        vec![position]
+    );
+}
+
+#[test]
+#[ignore = "requires Postgres"]
+fn balance_round_trips_through_set_and_get() {
+    let mut repository = repository();
+
+    let engine_id = Uuid::new_v4();
+    assert!(matches!(
+        repository.get_balance(engine_id),
+        Err(RepositoryError::ExpectedDataNotPresentError)
+    ));
+
+    let balance = Balance::new(Utc::now(), 1000.0, 900.0);
+    repository.set_balance(engine_id, balance).unwrap();
+
+    let actual = repository.get_balance(engine_id).unwrap();
+    assert_eq!(actual.total, balance.total);
+    assert_eq!(actual.available, balance.available);
+}
+
+#[test]
+#[ignore = "requires Postgres"]
+fn statistics_are_namespaced_by_engine_id() {
+    let mut repository = repository();
+
+    let (engine_a, engine_b) = (Uuid::new_v4(), Uuid::new_v4());
+    let position = position();
+    let market_id = MarketId::from(&Market::<Instrument>::new(
+        position.exchange,
+        position.instrument.clone(),
+    ));
+
+    let statistic_a = TradingSummary::init(StatisticConfig {
+        starting_equity: 1000.0,
+        trading_days_per_year: 365,
+        data_period: chrono::Duration::days(1),
+        risk_free_return: 0.0,
+        omega_threshold: None,
+        with_benchmark: false,
+    });
+    let mut statistic_b = statistic_a;
+    statistic_b.bankruptcy = Some(Utc::now());
+
+    repository
+        .set_statistics(engine_a, market_id.clone(), statistic_a)
+        .unwrap();
+    repository
+        .set_statistics(engine_b, market_id.clone(), statistic_b)
+        .unwrap();
+
+    let actual_a = repository.get_statistics(engine_a, &market_id).unwrap();
+    let actual_b = repository.get_statistics(engine_b, &market_id).unwrap();
+    assert!(actual_a.bankruptcy.is_none());
+    assert!(actual_b.bankruptcy.is_some());
+}
+
+/// Uses & drops the repository from within a Tokio runtime, as an Engine run under
+/// `#[tokio::main]` would, without panicking due to blocking inside an async context.
+async fn repository_is_usable_and_droppable_within_runtime() {
+    let mut repository = repository();
+
+    let engine_id = Uuid::new_v4();
+    let balance = Balance::new(Utc::now(), 1000.0, 900.0);
+    repository.set_balance(engine_id, balance).unwrap();
+    assert_eq!(repository.get_balance(engine_id).unwrap().total, 1000.0);
+
+    drop(repository);
+    tokio::task::yield_now().await;
+}
+
+#[tokio::test]
+#[ignore = "requires Postgres"]
+async fn repository_is_usable_and_droppable_within_current_thread_runtime() {
+    repository_is_usable_and_droppable_within_runtime().await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "requires Postgres"]
+async fn repository_is_usable_and_droppable_within_multi_thread_runtime() {
+    repository_is_usable_and_droppable_within_runtime().await
+}